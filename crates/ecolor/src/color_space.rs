@@ -0,0 +1,141 @@
+use crate::{gamma_from_linear, linear_from_gamma, Rgba};
+
+/// The color space that a painter writes its pixels in, i.e. what the display (or target texture) expects.
+///
+/// egui colors ([`crate::Color32`], [`Rgba`]) are always in sRGB,
+/// and are converted to the output color space when rasterized.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum ColorSpace {
+    /// sRGB primaries with the sRGB transfer function.
+    ///
+    /// This is what most displays expect.
+    #[default]
+    Srgb,
+
+    /// Display P3 primaries, which cover a wider gamut than sRGB, with the sRGB transfer function.
+    ///
+    /// This is what e.g. recent Apple displays expect.
+    DisplayP3,
+
+    /// sRGB primaries without a transfer function, i.e. linear light.
+    ///
+    /// Use this for floating point targets, e.g. an extended linear sRGB (scRGB) surface of an HDR display.
+    Linear,
+}
+
+impl ColorSpace {
+    /// Encode 0-1 linear sRGB (not clamped) for this output color space.
+    ///
+    /// Colors outside of the gamut of the color space get values outside of 0-1.
+    pub fn encode_linear_srgb(self, rgb: [f32; 3]) -> [f32; 3] {
+        match self {
+            Self::Srgb => rgb.map(gamma_from_linear),
+            Self::DisplayP3 => linear_display_p3_from_linear_srgb(rgb).map(gamma_from_linear),
+            Self::Linear => rgb,
+        }
+    }
+
+    /// The inverse of [`Self::encode_linear_srgb`].
+    pub fn decode_to_linear_srgb(self, rgb: [f32; 3]) -> [f32; 3] {
+        match self {
+            Self::Srgb => rgb.map(linear_from_gamma),
+            Self::DisplayP3 => linear_srgb_from_linear_display_p3(rgb.map(linear_from_gamma)),
+            Self::Linear => rgb,
+        }
+    }
+}
+
+/// Linear sRGB to linear Display P3 (both with a D65 white point).
+pub const LINEAR_DISPLAY_P3_FROM_LINEAR_SRGB: [[f32; 3]; 3] = [
+    [0.822_462_1, 0.177_538, 0.0],
+    [0.033_194_2, 0.966_805_8, 0.0],
+    [0.017_082_7, 0.072_397_4, 0.910_519_9],
+];
+
+/// Linear Display P3 to linear sRGB (both with a D65 white point).
+pub const LINEAR_SRGB_FROM_LINEAR_DISPLAY_P3: [[f32; 3]; 3] = [
+    [1.224_940_2, -0.224_940_2, 0.0],
+    [-0.042_056_96, 1.042_057, 0.0],
+    [-0.019_637_555, -0.078_636_05, 1.098_273_6],
+];
+
+/// 0-1 linear sRGB -> 0-1 linear Display P3 (not clamped).
+pub fn linear_display_p3_from_linear_srgb(rgb: [f32; 3]) -> [f32; 3] {
+    mul_matrix(&LINEAR_DISPLAY_P3_FROM_LINEAR_SRGB, rgb)
+}
+
+/// 0-1 linear Display P3 -> 0-1 linear sRGB (not clamped).
+///
+/// Colors outside of the sRGB gamut get components outside of 0-1.
+pub fn linear_srgb_from_linear_display_p3(rgb: [f32; 3]) -> [f32; 3] {
+    mul_matrix(&LINEAR_SRGB_FROM_LINEAR_DISPLAY_P3, rgb)
+}
+
+fn mul_matrix(m: &[[f32; 3]; 3], [r, g, b]: [f32; 3]) -> [f32; 3] {
+    m.map(|row| row[0] * r + row[1] * g + row[2] * b)
+}
+
+impl Rgba {
+    /// A color given in Display P3, as gamma-encoded 0-1 values (like `color(display-p3 r g b / a)` in CSS).
+    ///
+    /// Colors outside of the sRGB gamut get components outside of 0-1, which [`Rgba`] keeps as they are.
+    /// They are clamped when converted to a [`crate::Color32`].
+    pub fn from_display_p3_unmultiplied(r: f32, g: f32, b: f32, a: f32) -> Self {
+        let [r, g, b] = ColorSpace::DisplayP3.decode_to_linear_srgb([r, g, b]);
+        Self::from_rgba_unmultiplied(r, g, b, a)
+    }
+
+    /// The color in Display P3, as gamma-encoded 0-1 values, with unmultiplied alpha.
+    pub fn to_display_p3_unmultiplied(&self) -> [f32; 4] {
+        let [r, g, b, a] = self.to_rgba_unmultiplied();
+        let [r, g, b] = ColorSpace::DisplayP3.encode_linear_srgb([r, g, b]);
+        [r, g, b, a]
+    }
+
+    /// Is this color within the sRGB gamut, i.e. can it be shown without clipping on an sRGB display?
+    pub fn is_within_srgb_gamut(&self) -> bool {
+        const EPSILON: f32 = 1e-5; // Ignore rounding errors of the conversions
+        let [r, g, b, _] = self.to_rgba_unmultiplied();
+        [r, g, b]
+            .iter()
+            .all(|c| (-EPSILON..=1.0 + EPSILON).contains(c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_p3_round_trip() {
+        let close = |a: [f32; 3], b: [f32; 3]| a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-4);
+
+        for rgb in [
+            [0.0, 0.0, 0.0],
+            [1.0, 1.0, 1.0],
+            [0.2, 0.5, 0.9],
+            [1.0, 0.0, 0.0],
+        ] {
+            for space in [ColorSpace::Srgb, ColorSpace::DisplayP3, ColorSpace::Linear] {
+                let encoded = space.encode_linear_srgb(rgb);
+                assert!(
+                    close(space.decode_to_linear_srgb(encoded), rgb),
+                    "{space:?} {rgb:?}"
+                );
+            }
+        }
+
+        // White is the same in both:
+        assert!(close(
+            ColorSpace::DisplayP3.encode_linear_srgb([1.0, 1.0, 1.0]),
+            [1.0, 1.0, 1.0]
+        ));
+
+        // The reddest Display P3 red is outside of sRGB:
+        let p3_red = Rgba::from_display_p3_unmultiplied(1.0, 0.0, 0.0, 1.0);
+        assert!(!p3_red.is_within_srgb_gamut());
+        assert!(1.0 < p3_red.r() && p3_red.g() < 0.0);
+        assert!(Rgba::from_display_p3_unmultiplied(0.5, 0.5, 0.5, 1.0).is_within_srgb_gamut());
+    }
+}
@@ -3,6 +3,7 @@
 //! If you want a compact color representation, use [`Color32`].
 //! If you want to manipulate RGBA colors use [`Rgba`].
 //! If you want to manipulate colors in a way closer to how humans think about colors, use [`HsvaGamma`].
+//! If you want to show colors on a wide-gamut or HDR display, see [`ColorSpace`].
 //!
 //! ## Feature flags
 #![cfg_attr(feature = "document-features", doc = document_features::document_features!())]
@@ -16,6 +17,9 @@ mod cint_impl;
 mod color32;
 pub use color32::*;
 
+mod color_space;
+pub use color_space::*;
+
 mod hsva_gamma;
 pub use hsva_gamma::*;
 
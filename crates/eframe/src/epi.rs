@@ -41,7 +41,7 @@ pub type EventLoopBuilderHook = Box<dyn FnOnce(&mut EventLoopBuilder<UserEvent>)
 #[cfg(any(feature = "glow", feature = "wgpu"))]
 pub type WindowBuilderHook = Box<dyn FnOnce(egui::ViewportBuilder) -> egui::ViewportBuilder>;
 
-type DynError = Box<dyn std::error::Error + Send + Sync>;
+pub(crate) type DynError = Box<dyn std::error::Error + Send + Sync>;
 
 /// This is how your app is created.
 ///
@@ -154,6 +154,9 @@ pub trait App {
     ///
     /// On native the path is picked using [`crate::storage_dir`].
     /// The path can be customized via [`NativeOptions::persistence_path`].
+    ///
+    /// With the "hot_reload" feature, this is also called (into memory) before the app is reloaded,
+    /// see `NativeOptions::hot_reload`.
     fn save(&mut self, _storage: &mut dyn Storage) {}
 
     /// Called once on shutdown, after [`Self::save`].
@@ -176,6 +179,8 @@ pub trait App {
     // Settings:
 
     /// Time between automatic calls to [`Self::save`]
+    ///
+    /// On native, see also [`NativeOptions::autosave`].
     fn auto_save_interval(&self) -> std::time::Duration {
         std::time::Duration::from_secs(30)
     }
@@ -268,6 +273,26 @@ pub struct NativeOptions {
     /// The default is `true`.
     pub vsync: bool,
 
+    /// How frames are presented to begin with.
+    ///
+    /// Can be changed at runtime with [`Frame::set_presentation_mode`].
+    ///
+    /// The default is [`PresentationMode::PowerSaving`].
+    pub presentation_mode: PresentationMode,
+
+    /// Desired maximum number of frames that may be queued for presentation,
+    /// with [`PresentationMode::PowerSaving`].
+    ///
+    /// Use `1` for low input latency (at the cost of throughput), and `2` for high throughput.
+    ///
+    /// With wgpu this is `egui_wgpu::WgpuConfiguration::desired_maximum_frame_latency`,
+    /// which it overrides if set.
+    /// OpenGL has no such setting, so with glow `1` makes eframe wait for the GPU to finish
+    /// after each buffer swap, and higher values have no effect.
+    ///
+    /// `None` (the default) leaves it to the graphics backend.
+    pub desired_maximum_frame_latency: Option<u32>,
+
     /// Set the level of the multisampling anti-aliasing (MSAA).
     ///
     /// Must be a power-of-two. Higher = more smooth 3D.
@@ -330,6 +355,17 @@ pub struct NativeOptions {
     #[cfg(any(feature = "glow", feature = "wgpu"))]
     pub window_builder: Option<WindowBuilderHook>,
 
+    /// Sources of data from outside of the app (sockets, pipes, channels, …)
+    /// that should make it repaint when something arrives.
+    ///
+    /// With these, an app that only changes when new data arrives can be fully reactive,
+    /// and use no CPU at all while idle, without polling or calling
+    /// [`egui::Context::request_repaint`] from a thread of its own.
+    ///
+    /// Note: A [`NativeOptions`] clone will not include any wake sources.
+    #[cfg(any(feature = "glow", feature = "wgpu"))]
+    pub wake_sources: Vec<crate::WakeSource>,
+
     #[cfg(feature = "glow")]
     /// Needed for cross compiling for VirtualBox VMSVGA driver with OpenGL ES 2.0 and OpenGL 2.1 which doesn't support SRGB texture.
     /// See <https://github.com/emilk/egui/pull/1993>.
@@ -337,6 +373,20 @@ pub struct NativeOptions {
     /// For OpenGL ES 2.0: set this to [`egui_glow::ShaderVersion::Es100`] to solve blank texture problem (by using the "fallback shader").
     pub shader_version: Option<egui_glow::ShaderVersion>,
 
+    /// With the glow renderer: only repaint the parts of the window that changed since the last frame,
+    /// and tell the compositor which parts those are (with `EGL_KHR_swap_buffers_with_damage`).
+    ///
+    /// This saves power when only small parts of the window change, e.g. a blinking text cursor.
+    /// The changes are found by comparing what egui paints each frame (see [`egui_glow::DamageTracker`]),
+    /// so the clear color is no longer painted before [`App::update`], and anything you paint directly
+    /// with OpenGL must be done in a [`egui::PaintCallback`].
+    ///
+    /// Whole frames are still repainted when the driver can't tell how old the back buffer is.
+    ///
+    /// Default: `false`.
+    #[cfg(feature = "glow")]
+    pub partial_redraw: bool,
+
     /// On desktop: make the window position to be centered at initialization.
     ///
     /// Platform specific:
@@ -356,6 +406,9 @@ pub struct NativeOptions {
     /// data storage path for each target system.
     pub persistence_path: Option<std::path::PathBuf>,
 
+    /// When and how the app state is saved (only if the "persistence" feature is enabled).
+    pub autosave: AutoSavePolicy,
+
     /// Controls whether to apply dithering to minimize banding artifacts.
     ///
     /// Dithering assumes an sRGB output and thus will apply noise to any input value that lies between
@@ -364,6 +417,44 @@ pub struct NativeOptions {
     ///
     /// Defaults to true.
     pub dithering: bool,
+
+    /// Show [`IntegrationInfo::input_latency`], [`IntegrationInfo::cpu_usage`]
+    /// and the total of [`IntegrationInfo::texture_memory`] in a corner of the root viewport.
+    ///
+    /// Defaults to false.
+    pub latency_overlay: bool,
+
+    /// If the [`AppCreator`] fails, show the error in the window,
+    /// with buttons to copy the details, retry (by restarting the app) or quit,
+    /// instead of returning [`crate::Error::AppCreation`] from [`crate::run_native`].
+    ///
+    /// This makes e.g. a missing GPU feature or a corrupt save file understandable to users.
+    ///
+    /// Defaults to false.
+    pub startup_error_screen: bool,
+
+    /// Tell the app when its textures use more GPU memory than this, see [`TextureBudget`].
+    ///
+    /// How much they use is always reported in [`IntegrationInfo::texture_memory`].
+    ///
+    /// Defaults to `None`, i.e. no budget.
+    #[cfg(any(feature = "glow", feature = "wgpu"))]
+    pub texture_budget: Option<TextureBudget>,
+
+    /// A zoom lens that follows the mouse pointer, toggled with a keyboard shortcut.
+    ///
+    /// See [`crate::MagnifierOptions`]. Defaults to `None`, i.e. no magnifier.
+    #[cfg(any(feature = "glow", feature = "wgpu"))]
+    pub magnifier: Option<crate::MagnifierOptions>,
+
+    /// Reload the code of the app while it is running, without losing its state.
+    ///
+    /// See [`crate::HotReload`]. Defaults to `None`.
+    ///
+    /// Note: A [`NativeOptions`] clone will not include this.
+    #[cfg(any(feature = "glow", feature = "wgpu"))]
+    #[cfg(feature = "hot_reload")]
+    pub hot_reload: Option<crate::HotReload>,
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -378,11 +469,23 @@ impl Clone for NativeOptions {
             #[cfg(any(feature = "glow", feature = "wgpu"))]
             window_builder: None, // Skip any builder callbacks if cloning
 
+            #[cfg(any(feature = "glow", feature = "wgpu"))]
+            wake_sources: Vec::new(), // Each wake source can only be waited on once
+
+            #[cfg(any(feature = "glow", feature = "wgpu"))]
+            #[cfg(feature = "hot_reload")]
+            hot_reload: None, // Holds the app creator, which can't be cloned
+
             #[cfg(feature = "wgpu")]
             wgpu_options: self.wgpu_options.clone(),
 
             persistence_path: self.persistence_path.clone(),
 
+            autosave: self.autosave.clone(),
+
+            #[cfg(any(feature = "glow", feature = "wgpu"))]
+            texture_budget: self.texture_budget.clone(),
+
             ..*self
         }
     }
@@ -411,9 +514,15 @@ impl Default for NativeOptions {
             #[cfg(any(feature = "glow", feature = "wgpu"))]
             window_builder: None,
 
+            #[cfg(any(feature = "glow", feature = "wgpu"))]
+            wake_sources: Vec::new(),
+
             #[cfg(feature = "glow")]
             shader_version: None,
 
+            #[cfg(feature = "glow")]
+            partial_redraw: false,
+
             centered: false,
 
             #[cfg(feature = "wgpu")]
@@ -423,7 +532,151 @@ impl Default for NativeOptions {
 
             persistence_path: None,
 
+            autosave: AutoSavePolicy::default(),
+
             dithering: true,
+
+            latency_overlay: false,
+
+            startup_error_screen: false,
+
+            #[cfg(any(feature = "glow", feature = "wgpu"))]
+            texture_budget: None,
+
+            #[cfg(any(feature = "glow", feature = "wgpu"))]
+            magnifier: None,
+
+            #[cfg(any(feature = "glow", feature = "wgpu"))]
+            #[cfg(feature = "hot_reload")]
+            hot_reload: None,
+
+            presentation_mode: PresentationMode::default(),
+
+            desired_maximum_frame_latency: None,
+        }
+    }
+}
+
+/// A limit on how much GPU memory the textures of egui may use, see [`NativeOptions::texture_budget`].
+///
+/// Image-heavy apps can use this to free images that haven't been shown for a while
+/// (e.g. with [`egui::Context::forget_image`]) before the graphics driver starts swapping
+/// textures in and out of video memory.
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(any(feature = "glow", feature = "wgpu"))]
+#[derive(Clone)]
+pub struct TextureBudget {
+    /// How many bytes the textures may use at most.
+    pub max_bytes: usize,
+
+    /// Called after each frame that was painted while the textures used more than [`Self::max_bytes`].
+    ///
+    /// It gets the textures that should be freed to get back under budget,
+    /// the least recently painted first (see [`egui::TextureMemory::least_recently_painted_over_budget`]).
+    /// Textures that are freed will be gone from the next frame on.
+    #[allow(clippy::type_complexity)]
+    pub on_over_budget: std::sync::Arc<dyn Fn(&egui::Context, &[egui::TextureId]) + Send + Sync>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(any(feature = "glow", feature = "wgpu"))]
+impl TextureBudget {
+    /// See [`Self::max_bytes`] and [`Self::on_over_budget`].
+    pub fn new(
+        max_bytes: usize,
+        on_over_budget: impl Fn(&egui::Context, &[egui::TextureId]) + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            max_bytes,
+            on_over_budget: std::sync::Arc::new(on_over_budget),
+        }
+    }
+}
+
+/// How frames are presented, trading input latency against power usage.
+///
+/// See [`NativeOptions::presentation_mode`] and [`Frame::set_presentation_mode`].
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PresentationMode {
+    /// Wait for vsync (if [`NativeOptions::vsync`] is set), and let frames queue up as
+    /// configured by [`NativeOptions::desired_maximum_frame_latency`].
+    #[default]
+    PowerSaving,
+
+    /// Present each frame as soon as it is ready, with at most one frame in flight.
+    ///
+    /// This gives the lowest latency from input to photon, e.g. for drawing with a pen,
+    /// but uses more power and may cause tearing.
+    /// With wgpu this uses `wgpu::PresentMode::AutoNoVsync`, which prefers mailbox presentation
+    /// (no tearing) where it is supported.
+    LowLatency,
+}
+
+// ----------------------------------------------------------------------------
+
+/// Called when saving the app state to disk fails.
+///
+/// Gets the path of the file that could not be written.
+#[cfg(not(target_arch = "wasm32"))]
+pub type SaveErrorCallback =
+    std::sync::Arc<dyn Fn(&std::path::Path, &std::io::Error) + Send + Sync>;
+
+/// Controls when and how `eframe` saves the app state on native.
+///
+/// Only used if the "persistence" feature is enabled.
+///
+/// The state is always saved on shutdown.
+/// In addition it is saved at regular intervals, which can be set separately for
+/// the native window, the egui memory and the app state (see [`App::save`]).
+///
+/// The state file is written atomically: first to a temporary file, which then replaces the old file.
+/// This means a crash or a kill mid-write will not corrupt the saved state.
+/// The previous versions of the file are kept as backups (`app.ron.1`, `app.ron.2`, …),
+/// which are used if the main file cannot be read.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone)]
+pub struct AutoSavePolicy {
+    /// How often to save the position and size of the native window.
+    ///
+    /// `None` means the same as [`App::auto_save_interval`].
+    pub window_interval: Option<std::time::Duration>,
+
+    /// How often to save the egui memory (window positions, collapsing headers, …).
+    ///
+    /// `None` means the same as [`App::auto_save_interval`].
+    pub egui_memory_interval: Option<std::time::Duration>,
+
+    /// Save this long after the native window was last moved or resized,
+    /// or after the last user interaction that may have changed the egui memory.
+    ///
+    /// This means changes are saved soon after the user is done with them,
+    /// without writing to disk on every frame.
+    /// `None` turns this off, only saving at the regular intervals.
+    ///
+    /// Default: 2 seconds.
+    pub debounce: Option<std::time::Duration>,
+
+    /// How many previous versions of the state file to keep as backups.
+    ///
+    /// Default: 1.
+    pub num_backups: usize,
+
+    /// Called (from a background thread) when saving fails.
+    ///
+    /// By default failures are only logged.
+    pub on_save_error: Option<SaveErrorCallback>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Default for AutoSavePolicy {
+    fn default() -> Self {
+        Self {
+            window_interval: None,
+            egui_memory_interval: None,
+            debounce: Some(std::time::Duration::from_secs(2)),
+            num_backups: 1,
+            on_save_error: None,
         }
     }
 }
@@ -439,6 +692,15 @@ pub struct WebOptions {
     /// Unused by webgl context as of writing.
     pub depth_buffer: u8,
 
+    /// What rendering backend to use.
+    ///
+    /// If you enable both the `glow` and `wgpu` features and pick [`Renderer::Wgpu`],
+    /// eframe falls back to [`Renderer::Glow`] (WebGL) in browsers without WebGPU,
+    /// so the same build works everywhere.
+    ///
+    /// Default: [`Renderer::default`], i.e. wgpu if the `wgpu` feature is enabled.
+    pub renderer: Renderer,
+
     /// Which version of WebGl context to select
     ///
     /// Default: [`WebGlContextOption::BestFirst`].
@@ -465,6 +727,34 @@ pub struct WebOptions {
     /// [`stopPropagation`](https://developer.mozilla.org/en-US/docs/Web/API/Event/stopPropagation)
     /// is called on every event.
     pub should_propagate_event: Box<dyn Fn(&egui::Event) -> bool>,
+
+    /// Called while files dropped on the canvas are being read, once per chunk.
+    ///
+    /// Use this to show progress for large files.
+    /// Each file shows up in [`egui::RawInput::dropped_files`] once it has been read completely.
+    ///
+    /// Default: `None`.
+    pub on_file_drop_progress: Option<Box<dyn Fn(&egui::Context, &FileDropProgress)>>,
+
+    /// Put in front of every key that the app and egui store in the local storage of the browser.
+    ///
+    /// When you run several apps on the same page (see [`crate::WebRunner`]),
+    /// give each one its own prefix so they don't overwrite each other's state.
+    ///
+    /// Default: `""`.
+    pub storage_key_prefix: String,
+}
+
+/// How much of a dropped file has been read, see [`WebOptions::on_file_drop_progress`].
+#[cfg(target_arch = "wasm32")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FileDropProgress {
+    /// Name of the file.
+    pub name: String,
+
+    pub bytes_read: u64,
+
+    pub total_bytes: u64,
 }
 
 #[cfg(target_arch = "wasm32")]
@@ -473,6 +763,8 @@ impl Default for WebOptions {
         Self {
             depth_buffer: 0,
 
+            renderer: Renderer::default(),
+
             #[cfg(feature = "glow")]
             webgl_context_option: WebGlContextOption::BestFirst,
 
@@ -482,6 +774,10 @@ impl Default for WebOptions {
             dithering: true,
 
             should_propagate_event: Box::new(|_| false),
+
+            on_file_drop_progress: None,
+
+            storage_key_prefix: String::new(),
         }
     }
 }
@@ -610,6 +906,16 @@ pub struct Frame {
     /// Raw platform display handle for window
     #[cfg(not(target_arch = "wasm32"))]
     pub(crate) raw_display_handle: Result<RawDisplayHandle, HandleError>,
+
+    /// See [`Self::set_presentation_mode`].
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) presentation_mode: PresentationMode,
+
+    /// Where clicks on notifications are sent, see [`Self::show_notification`].
+    #[cfg(not(target_arch = "wasm32"))]
+    #[cfg(any(feature = "glow", feature = "wgpu"))]
+    #[cfg(feature = "notifications")]
+    pub(crate) event_loop_proxy: Option<winit::event_loop::EventLoopProxy<UserEvent>>,
 }
 
 // Implementing `Clone` would violate the guarantees of `HasWindowHandle` and `HasDisplayHandle`.
@@ -635,6 +941,32 @@ impl HasDisplayHandle for Frame {
 }
 
 impl Frame {
+    /// A frame without a window, storage or graphics context, for running an app headless.
+    #[cfg(not(target_arch = "wasm32"))]
+    #[cfg(feature = "input_replay")]
+    pub(crate) fn headless() -> Self {
+        Self {
+            info: IntegrationInfo {
+                cpu_usage: None,
+                input_latency: None,
+                texture_memory: None,
+            },
+            storage: None,
+            #[cfg(feature = "glow")]
+            gl: None,
+            #[cfg(feature = "glow")]
+            glow_register_native_texture: None,
+            #[cfg(feature = "wgpu")]
+            wgpu_render_state: None,
+            raw_window_handle: Err(HandleError::Unavailable),
+            raw_display_handle: Err(HandleError::Unavailable),
+            presentation_mode: PresentationMode::default(),
+            #[cfg(any(feature = "glow", feature = "wgpu"))]
+            #[cfg(feature = "notifications")]
+            event_loop_proxy: None,
+        }
+    }
+
     /// True if you are in a web environment.
     ///
     /// Equivalent to `cfg!(target_arch = "wasm32")`
@@ -648,6 +980,36 @@ impl Frame {
         &self.info
     }
 
+    /// How frames are currently presented.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn presentation_mode(&self) -> PresentationMode {
+        self.presentation_mode
+    }
+
+    /// Switch between low latency and power saving presentation,
+    /// e.g. to use [`PresentationMode::LowLatency`] only while the user is drawing.
+    ///
+    /// Takes effect from the next frame, in all viewports.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn set_presentation_mode(&mut self, presentation_mode: PresentationMode) {
+        self.presentation_mode = presentation_mode;
+    }
+
+    /// Post a notification to the operating system,
+    /// e.g. to tell the user that a background task is done while the window is minimized.
+    ///
+    /// Clicking the notification brings the app to the front,
+    /// see [`crate::Notification`] for how to react to it.
+    ///
+    /// Failures (e.g. no notification daemon running) are logged, not returned,
+    /// since there is usually nothing the app can do about them.
+    #[cfg(not(target_arch = "wasm32"))]
+    #[cfg(any(feature = "glow", feature = "wgpu"))]
+    #[cfg(feature = "notifications")]
+    pub fn show_notification(&self, notification: crate::Notification) {
+        crate::native::notifications::show(notification, self.event_loop_proxy.clone());
+    }
+
     /// A place where you can store custom data in a way that persists when you restart the app.
     pub fn storage(&self) -> Option<&dyn Storage> {
         self.storage.as_deref()
@@ -777,6 +1139,27 @@ pub struct IntegrationInfo {
     ///
     /// `None` if this is the first frame.
     pub cpu_usage: Option<f32>,
+
+    /// Seconds from receiving the oldest input event (key press, mouse move, …)
+    /// that was handled in a frame, until that frame was presented.
+    ///
+    /// This is measured for the most recent frame that had any input,
+    /// from when winit delivered the event until the buffer swap (or present) returned,
+    /// so the time until the display actually shows the frame is not included.
+    ///
+    /// Use this to notice latency regressions, e.g. with `NativeOptions::latency_overlay`.
+    ///
+    /// `None` until a frame with input has been presented, and always on the web.
+    pub input_latency: Option<f32>,
+
+    /// How much GPU memory the textures used when the previous frame was painted,
+    /// and when each texture was last painted.
+    ///
+    /// Use this to find out how close an image-heavy app is to running out of video memory,
+    /// and see `NativeOptions::texture_budget`.
+    ///
+    /// `None` before the first frame has been painted, and always on the web.
+    pub texture_memory: Option<egui::TextureMemory>,
 }
 
 // ----------------------------------------------------------------------------
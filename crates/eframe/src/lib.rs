@@ -176,9 +176,43 @@ mod native;
 #[cfg(feature = "persistence")]
 pub use native::file_storage::storage_dir;
 
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(any(feature = "glow", feature = "wgpu"))]
+pub use native::{
+    magnifier::MagnifierOptions,
+    splash::{BackgroundAppCreator, SplashContext, SplashScreen},
+    wake_source::WakeSource,
+};
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(any(feature = "glow", feature = "wgpu"))]
+#[cfg(feature = "hot_reload")]
+pub use native::hot_reload::{HotReload, HotReloadTrigger};
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(any(feature = "glow", feature = "wgpu"))]
+#[cfg(feature = "notifications")]
+pub use native::notifications::Notification;
+
 #[cfg(not(target_arch = "wasm32"))]
 pub mod icon_data;
 
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(feature = "input_replay")]
+mod replay;
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(feature = "input_replay")]
+pub use replay::run_replay;
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(feature = "theme_loader")]
+mod theme_watcher;
+
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(feature = "theme_loader")]
+pub use theme_watcher::WatchThemeFile;
+
 /// This is how you start a native (desktop) app.
 ///
 /// The first argument is name of your app, which is a an identifier
@@ -267,6 +301,71 @@ pub fn run_native(
     }
 }
 
+/// Like [`run_native`], but shows a splash screen with a logo and a progress bar
+/// while the app is created on another thread.
+///
+/// Use this when creating your app takes a while, e.g. because it loads a lot of assets,
+/// so the window shows something nice instead of nothing (or garbage) in the meantime.
+/// Once `create_app` returns, the app takes over the window.
+///
+/// Unlike with [`run_native`], there is no [`CreationContext`] when creating the app,
+/// because the graphics context can't be used from another thread.
+/// Instead you get a [`SplashContext`], with which you can set up egui and report progress.
+/// You can restore the state of the app with [`Frame::storage`] in the first [`App::update`].
+///
+/// ``` no_run
+/// # struct MyApp {}
+/// # impl eframe::App for MyApp {
+/// #     fn update(&mut self, _ctx: &egui::Context, _frame: &mut eframe::Frame) {}
+/// # }
+/// # fn load_assets(_: usize) {}
+/// fn main() -> eframe::Result {
+///     eframe::run_native_with_splash(
+///         "My app",
+///         eframe::NativeOptions::default(),
+///         eframe::SplashScreen::default(),
+///         Box::new(|splash| {
+///             for i in 0..10 {
+///                 splash.set_progress(i as f32 / 10.0, format!("Loading asset {i}…"));
+///                 load_assets(i);
+///             }
+///             Ok(Box::new(MyApp {}))
+///         }),
+///     )
+/// }
+/// ```
+///
+/// # Errors
+/// This function can fail if we fail to set up a graphics context.
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(any(feature = "glow", feature = "wgpu"))]
+pub fn run_native_with_splash(
+    app_name: &str,
+    native_options: NativeOptions,
+    splash: SplashScreen,
+    create_app: BackgroundAppCreator,
+) -> Result {
+    let title = native_options
+        .viewport
+        .title
+        .clone()
+        .unwrap_or_else(|| app_name.to_owned());
+    let icon = native_options.viewport.icon.clone();
+    run_native(
+        app_name,
+        native_options,
+        Box::new(move |cc| {
+            Ok(Box::new(native::splash::SplashApp::new(
+                &cc.egui_ctx,
+                splash,
+                &title,
+                icon,
+                create_app,
+            )))
+        }),
+    )
+}
+
 // ----------------------------------------------------------------------------
 
 /// The simplest way to get started when writing a native app.
@@ -2,7 +2,7 @@
 
 use web_time::Instant;
 
-use std::path::PathBuf;
+use std::{path::PathBuf, time::Duration};
 use winit::event_loop::ActiveEventLoop;
 
 use raw_window_handle::{HasDisplayHandle as _, HasWindowHandle as _};
@@ -33,6 +33,8 @@ pub fn viewport_builder(
     let inner_size_points = if let Some(mut window_settings) = window_settings {
         // Restore pos/size from previous session
 
+        window_settings.normalize(egui_zoom_factor, event_loop);
+
         if clamp_size_to_monitor_size {
             window_settings.clamp_size_to_sane_values(largest_monitor_point_size(
                 egui_zoom_factor,
@@ -130,19 +132,25 @@ fn largest_monitor_point_size(egui_zoom_factor: f32, event_loop: &ActiveEventLoo
 // ----------------------------------------------------------------------------
 
 /// For loading/saving app state and/or egui memory to disk.
-pub fn create_storage(_app_name: &str) -> Option<Box<dyn epi::Storage>> {
+pub fn create_storage(
+    _app_name: &str,
+    _policy: &epi::AutoSavePolicy,
+) -> Option<Box<dyn epi::Storage>> {
     #[cfg(feature = "persistence")]
     if let Some(storage) = super::file_storage::FileStorage::from_app_id(_app_name) {
-        return Some(Box::new(storage));
+        return Some(Box::new(storage.with_policy(_policy)));
     }
     None
 }
 
 #[allow(clippy::unnecessary_wraps)]
-pub fn create_storage_with_file(_file: impl Into<PathBuf>) -> Option<Box<dyn epi::Storage>> {
+pub fn create_storage_with_file(
+    _file: impl Into<PathBuf>,
+    _policy: &epi::AutoSavePolicy,
+) -> Option<Box<dyn epi::Storage>> {
     #[cfg(feature = "persistence")]
     return Some(Box::new(
-        super::file_storage::FileStorage::from_ron_filepath(_file),
+        super::file_storage::FileStorage::from_ron_filepath(_file).with_policy(_policy),
     ));
     #[cfg(not(feature = "persistence"))]
     None
@@ -150,12 +158,103 @@ pub fn create_storage_with_file(_file: impl Into<PathBuf>) -> Option<Box<dyn epi
 
 // ----------------------------------------------------------------------------
 
+/// Which parts of the persisted state to save.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct SaveParts {
+    window: bool,
+    egui_memory: bool,
+    app: bool,
+}
+
+impl SaveParts {
+    const ALL: Self = Self {
+        window: true,
+        egui_memory: true,
+        app: true,
+    };
+
+    fn any(self) -> bool {
+        self.window || self.egui_memory || self.app
+    }
+}
+
+/// Keeps track of when the different parts of the persisted state were last saved and changed.
+struct AutoSaveState {
+    policy: epi::AutoSavePolicy,
+
+    last_window_save: Instant,
+    last_egui_memory_save: Instant,
+    last_app_save: Instant,
+
+    /// When the native window was last moved or resized, if that has not been saved yet.
+    window_changed: Option<Instant>,
+
+    /// When the user last interacted with egui, if that has not been saved yet.
+    egui_memory_changed: Option<Instant>,
+}
+
+impl AutoSaveState {
+    fn new(policy: epi::AutoSavePolicy) -> Self {
+        let now = Instant::now();
+        Self {
+            policy,
+            last_window_save: now,
+            last_egui_memory_save: now,
+            last_app_save: now,
+            window_changed: None,
+            egui_memory_changed: None,
+        }
+    }
+
+    /// Which parts should be saved now?
+    fn due(&self, now: Instant, app_interval: Duration) -> SaveParts {
+        let debounce = self.policy.debounce;
+        let is_due = |last_save: Instant, interval: Option<Duration>, changed: Option<Instant>| {
+            let settled = match (changed, debounce) {
+                (Some(changed), Some(debounce)) => debounce <= now - changed,
+                _ => false,
+            };
+            interval.unwrap_or(app_interval) < now - last_save || settled
+        };
+
+        SaveParts {
+            window: is_due(
+                self.last_window_save,
+                self.policy.window_interval,
+                self.window_changed,
+            ),
+            egui_memory: is_due(
+                self.last_egui_memory_save,
+                self.policy.egui_memory_interval,
+                self.egui_memory_changed,
+            ),
+            app: app_interval < now - self.last_app_save,
+        }
+    }
+
+    fn saved(&mut self, now: Instant, parts: SaveParts) {
+        if parts.window {
+            self.last_window_save = now;
+            self.window_changed = None;
+        }
+        if parts.egui_memory {
+            self.last_egui_memory_save = now;
+            self.egui_memory_changed = None;
+        }
+        if parts.app {
+            self.last_app_save = now;
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 /// Everything needed to make a winit-based integration for [`epi`].
 ///
 /// Only one instance per app (not one per viewport).
 pub struct EpiIntegration {
     pub frame: epi::Frame,
-    last_auto_save: Instant,
+    autosave: AutoSaveState,
     pub beginning: Instant,
     is_first_frame: bool,
     pub egui_ctx: egui::Context,
@@ -167,6 +266,23 @@ pub struct EpiIntegration {
     can_drag_window: bool,
     #[cfg(feature = "persistence")]
     persist_window: bool,
+    latency_overlay: bool,
+    texture_budget: Option<epi::TextureBudget>,
+
+    /// The zoom lens of the root viewport, if enabled in the [`crate::NativeOptions`].
+    pub magnifier: Option<super::magnifier::Magnifier>,
+
+    /// `None` if the gamepads couldn't be read.
+    #[cfg(feature = "gamepad")]
+    gamepads: Option<super::gamepad::Gamepads>,
+
+    /// Set with [`Self::set_hot_reload`].
+    #[cfg(feature = "hot_reload")]
+    pub hot_reload: Option<super::hot_reload::HotReload>,
+
+    /// The presentation mode that the integration was last told to use.
+    presentation_mode: epi::PresentationMode,
+
     app_icon_setter: super::app_icon::AppTitleIconSetter,
 }
 
@@ -183,9 +299,16 @@ impl EpiIntegration {
             Box<dyn FnMut(glow::Texture) -> egui::TextureId>,
         >,
         #[cfg(feature = "wgpu")] wgpu_render_state: Option<egui_wgpu::RenderState>,
+        #[cfg(feature = "notifications")] event_loop_proxy: winit::event_loop::EventLoopProxy<
+            super::winit_integration::UserEvent,
+        >,
     ) -> Self {
         let frame = epi::Frame {
-            info: epi::IntegrationInfo { cpu_usage: None },
+            info: epi::IntegrationInfo {
+                cpu_usage: None,
+                input_latency: None,
+                texture_memory: None,
+            },
             storage,
             #[cfg(feature = "glow")]
             gl,
@@ -195,6 +318,9 @@ impl EpiIntegration {
             wgpu_render_state,
             raw_display_handle: window.display_handle().map(|h| h.as_raw()),
             raw_window_handle: window.window_handle().map(|h| h.as_raw()),
+            presentation_mode: native_options.presentation_mode,
+            #[cfg(feature = "notifications")]
+            event_loop_proxy: Some(event_loop_proxy),
         };
 
         let icon = native_options
@@ -214,19 +340,38 @@ impl EpiIntegration {
 
         Self {
             frame,
-            last_auto_save: Instant::now(),
+            autosave: AutoSaveState::new(native_options.autosave.clone()),
             egui_ctx,
             pending_full_output: Default::default(),
             close: false,
             can_drag_window: false,
             #[cfg(feature = "persistence")]
             persist_window: native_options.persist_window,
+            latency_overlay: native_options.latency_overlay,
+            texture_budget: native_options.texture_budget.clone(),
+            magnifier: native_options
+                .magnifier
+                .map(super::magnifier::Magnifier::new),
+            #[cfg(feature = "gamepad")]
+            gamepads: super::gamepad::Gamepads::new(),
+            #[cfg(feature = "hot_reload")]
+            hot_reload: None,
+            presentation_mode: native_options.presentation_mode,
             app_icon_setter,
             beginning: Instant::now(),
             is_first_frame: true,
         }
     }
 
+    /// Take the [`crate::NativeOptions::hot_reload`], if any.
+    #[cfg(feature = "hot_reload")]
+    pub fn set_hot_reload(&mut self, native_options: &mut crate::NativeOptions) {
+        if let Some(hot_reload) = native_options.hot_reload.take() {
+            hot_reload.set_egui_ctx(&self.egui_ctx);
+            self.hot_reload = Some(hot_reload);
+        }
+    }
+
     /// If `true`, it is time to close the native window.
     pub fn should_close(&self) -> bool {
         self.close
@@ -251,6 +396,8 @@ impl EpiIntegration {
             self.can_drag_window = true;
         }
 
+        self.note_persisted_change(event);
+
         egui_winit.on_window_event(window, event)
     }
 
@@ -271,6 +418,17 @@ impl EpiIntegration {
 
         let close_requested = raw_input.viewport().close_requested();
 
+        if viewport_ui_cb.is_none() {
+            if let Some(magnifier) = &mut self.magnifier {
+                magnifier.on_raw_input(&mut raw_input);
+            }
+
+            #[cfg(feature = "gamepad")]
+            if let Some(gamepads) = &mut self.gamepads {
+                gamepads.on_raw_input(&self.egui_ctx, &mut raw_input);
+            }
+        }
+
         app.raw_input_hook(&self.egui_ctx, &mut raw_input);
 
         let full_output = self.egui_ctx.run(raw_input, |egui_ctx| {
@@ -281,6 +439,10 @@ impl EpiIntegration {
             } else {
                 crate::profile_scope!("App::update");
                 app.update(egui_ctx, &mut self.frame);
+
+                if self.latency_overlay {
+                    latency_overlay(egui_ctx, &self.frame.info);
+                }
             }
         });
 
@@ -301,10 +463,47 @@ impl EpiIntegration {
         std::mem::take(&mut self.pending_full_output)
     }
 
+    /// The presentation mode set with [`epi::Frame::set_presentation_mode`],
+    /// if it has changed since the last call.
+    pub fn presentation_mode_change(&mut self) -> Option<epi::PresentationMode> {
+        let presentation_mode = self.frame.presentation_mode;
+        if presentation_mode == self.presentation_mode {
+            None
+        } else {
+            self.presentation_mode = presentation_mode;
+            Some(presentation_mode)
+        }
+    }
+
     pub fn report_frame_time(&mut self, seconds: f32) {
         self.frame.info.cpu_usage = Some(seconds);
     }
 
+    /// Call when a frame has been presented, with [`egui_winit::State::input_time`]
+    /// of the input it handled.
+    pub fn report_input_latency(&mut self, input_time: Option<Instant>) {
+        if let Some(input_time) = input_time {
+            self.frame.info.input_latency = Some(input_time.elapsed().as_secs_f32());
+        }
+    }
+
+    /// Call after painting, with how much GPU memory the textures use.
+    pub fn report_texture_memory(&mut self, texture_memory: &egui::TextureMemory) {
+        self.frame
+            .info
+            .texture_memory
+            .get_or_insert_with(Default::default)
+            .clone_from(texture_memory);
+
+        if let Some(budget) = &self.texture_budget {
+            let over_budget = texture_memory.least_recently_painted_over_budget(budget.max_bytes);
+            if !over_budget.is_empty() {
+                crate::profile_scope!("on_over_budget");
+                (budget.on_over_budget)(&self.egui_ctx, &over_budget);
+            }
+        }
+    }
+
     pub fn post_rendering(&mut self, window: &winit::window::Window) {
         crate::profile_function!();
         if std::mem::take(&mut self.is_first_frame) {
@@ -316,40 +515,83 @@ impl EpiIntegration {
     // ------------------------------------------------------------------------
     // Persistence stuff:
 
+    /// Remember that something that is persisted may have changed,
+    /// so that we can save it once things have settled down (see [`epi::AutoSavePolicy::debounce`]).
+    fn note_persisted_change(&mut self, event: &winit::event::WindowEvent) {
+        use winit::event::{ElementState, WindowEvent};
+
+        let now = Instant::now();
+        match event {
+            WindowEvent::Moved(_) | WindowEvent::Resized(_) => {
+                self.autosave.window_changed = Some(now);
+            }
+            WindowEvent::MouseInput {
+                state: ElementState::Released,
+                ..
+            }
+            | WindowEvent::MouseWheel { .. }
+            | WindowEvent::KeyboardInput { .. } => {
+                self.autosave.egui_memory_changed = Some(now);
+            }
+            _ => return,
+        }
+
+        if let Some(debounce) = self.autosave.policy.debounce {
+            // Make sure we wake up to do the save, even if the app is idle:
+            self.egui_ctx
+                .request_repaint_after_for(debounce, ViewportId::ROOT);
+        }
+    }
+
     pub fn maybe_autosave(
         &mut self,
         app: &mut dyn epi::App,
         window: Option<&winit::window::Window>,
     ) {
         let now = Instant::now();
-        if now - self.last_auto_save > app.auto_save_interval() {
-            self.save(app, window);
-            self.last_auto_save = now;
+        let parts = self.autosave.due(now, app.auto_save_interval());
+        if parts.any() {
+            self.save_parts(app, window, parts);
+            self.autosave.saved(now, parts);
         }
     }
 
+    /// Save everything, e.g. on shutdown.
+    pub fn save(&mut self, app: &mut dyn epi::App, window: Option<&winit::window::Window>) {
+        self.save_parts(app, window, SaveParts::ALL);
+        self.autosave.saved(Instant::now(), SaveParts::ALL);
+    }
+
     #[allow(clippy::unused_self)]
-    pub fn save(&mut self, _app: &mut dyn epi::App, _window: Option<&winit::window::Window>) {
+    fn save_parts(
+        &mut self,
+        _app: &mut dyn epi::App,
+        _window: Option<&winit::window::Window>,
+        _parts: SaveParts,
+    ) {
         #[cfg(feature = "persistence")]
         if let Some(storage) = self.frame.storage_mut() {
             crate::profile_function!();
 
-            if let Some(window) = _window {
+            if let Some(window) = _window.filter(|_| _parts.window) {
                 if self.persist_window {
                     crate::profile_scope!("native_window");
                     epi::set_value(
                         storage,
                         STORAGE_WINDOW_KEY,
-                        &WindowSettings::from_window(self.egui_ctx.zoom_factor(), window),
+                        &WindowSettings::from_window(
+                            self.egui_ctx.zoom_factor_of(ViewportId::ROOT),
+                            window,
+                        ),
                     );
                 }
             }
-            if _app.persist_egui_memory() {
+            if _parts.egui_memory && _app.persist_egui_memory() {
                 crate::profile_scope!("egui_memory");
                 self.egui_ctx
                     .memory(|mem| epi::set_value(storage, STORAGE_EGUI_MEMORY_KEY, mem));
             }
-            {
+            if _parts.app {
                 crate::profile_scope!("App::save");
                 _app.save(storage);
             }
@@ -360,6 +602,38 @@ impl EpiIntegration {
     }
 }
 
+/// Show the input latency, cpu usage and texture memory of recent frames in the top right corner,
+/// see [`crate::NativeOptions::latency_overlay`].
+fn latency_overlay(egui_ctx: &egui::Context, info: &epi::IntegrationInfo) {
+    let ms = |seconds: Option<f32>| {
+        seconds.map_or_else(
+            || "-".to_owned(),
+            |seconds| format!("{:.1} ms", 1e3 * seconds),
+        )
+    };
+    let mut text = format!(
+        "input latency: {}\ncpu usage:     {}",
+        ms(info.input_latency),
+        ms(info.cpu_usage)
+    );
+    if let Some(texture_memory) = &info.texture_memory {
+        text += &format!(
+            "\ntextures:      {:.1} MB",
+            texture_memory.total_bytes() as f64 * 1e-6
+        );
+    }
+
+    egui::Area::new(egui::Id::new("eframe_latency_overlay"))
+        .order(egui::Order::Debug)
+        .anchor(egui::Align2::RIGHT_TOP, egui::vec2(-8.0, 8.0))
+        .interactable(false)
+        .show(egui_ctx, |ui| {
+            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                ui.label(egui::RichText::new(text).monospace());
+            });
+        });
+}
+
 fn load_default_egui_icon() -> egui::IconData {
     crate::profile_function!();
     crate::icon_data::from_png_bytes(&include_bytes!("../../data/icon.png")[..]).unwrap()
@@ -90,11 +90,16 @@ fn roaming_appdata() -> Option<PathBuf> {
 
 /// A key-value store backed by a [RON](https://github.com/ron-rs/ron) file on disk.
 /// Used to restore egui state, glium window position/size and app state.
+///
+/// The file is written atomically (write to a temporary file, then rename),
+/// and the previous versions are kept as backups.
 pub struct FileStorage {
     ron_filepath: PathBuf,
     kv: HashMap<String, String>,
     dirty: bool,
     last_save_join_handle: Option<std::thread::JoinHandle<()>>,
+    num_backups: usize,
+    on_save_error: Option<crate::SaveErrorCallback>,
 }
 
 impl Drop for FileStorage {
@@ -108,15 +113,20 @@ impl Drop for FileStorage {
 
 impl FileStorage {
     /// Store the state in this .ron file.
+    ///
+    /// If the file is missing or corrupt, the most recent readable backup is loaded instead.
     pub(crate) fn from_ron_filepath(ron_filepath: impl Into<PathBuf>) -> Self {
         crate::profile_function!();
         let ron_filepath: PathBuf = ron_filepath.into();
         log::debug!("Loading app state from {:?}…", ron_filepath);
+        let defaults = crate::AutoSavePolicy::default();
         Self {
-            kv: read_ron(&ron_filepath).unwrap_or_default(),
+            kv: read_ron_or_backup(&ron_filepath).unwrap_or_default(),
             ron_filepath,
             dirty: false,
             last_save_join_handle: None,
+            num_backups: defaults.num_backups,
+            on_save_error: defaults.on_save_error,
         }
     }
 
@@ -139,6 +149,13 @@ impl FileStorage {
             None
         }
     }
+
+    /// Use the backup and error reporting settings of the given policy.
+    pub(crate) fn with_policy(mut self, policy: &crate::AutoSavePolicy) -> Self {
+        self.num_backups = policy.num_backups;
+        self.on_save_error = policy.on_save_error.clone();
+        self
+    }
 }
 
 impl crate::Storage for FileStorage {
@@ -160,6 +177,8 @@ impl crate::Storage for FileStorage {
 
             let file_path = self.ron_filepath.clone();
             let kv = self.kv.clone();
+            let num_backups = self.num_backups;
+            let on_save_error = self.on_save_error.clone();
 
             if let Some(join_handle) = self.last_save_join_handle.take() {
                 // wait for previous save to complete.
@@ -169,7 +188,12 @@ impl crate::Storage for FileStorage {
             let result = std::thread::Builder::new()
                 .name("eframe_persist".to_owned())
                 .spawn(move || {
-                    save_to_disk(&file_path, &kv);
+                    if let Err(err) = save_to_disk(&file_path, &kv, num_backups) {
+                        log::warn!("Failed to save app state to {file_path:?}: {err}");
+                        if let Some(on_save_error) = on_save_error {
+                            on_save_error(&file_path, &err);
+                        }
+                    }
                 });
             match result {
                 Ok(join_handle) => {
@@ -177,45 +201,93 @@ impl crate::Storage for FileStorage {
                 }
                 Err(err) => {
                     log::warn!("Failed to spawn thread to save app state: {err}");
+                    if let Some(on_save_error) = &self.on_save_error {
+                        on_save_error(&self.ron_filepath, &err);
+                    }
                 }
             }
         }
     }
 }
 
-fn save_to_disk(file_path: &PathBuf, kv: &HashMap<String, String>) {
+/// `app.ron` -> `app.ron.{suffix}`
+fn sibling_path(file_path: &Path, suffix: impl std::fmt::Display) -> PathBuf {
+    let mut path = file_path.as_os_str().to_owned();
+    path.push(format!(".{suffix}"));
+    PathBuf::from(path)
+}
+
+/// The path of the `n`th backup, where `1` is the most recent one.
+fn backup_path(file_path: &Path, n: usize) -> PathBuf {
+    sibling_path(file_path, n)
+}
+
+/// Write the state to a temporary file, rotate the backups, then atomically replace the old file.
+fn save_to_disk(
+    file_path: &Path,
+    kv: &HashMap<String, String>,
+    num_backups: usize,
+) -> std::io::Result<()> {
     crate::profile_function!();
 
     if let Some(parent_dir) = file_path.parent() {
         if !parent_dir.exists() {
-            if let Err(err) = std::fs::create_dir_all(parent_dir) {
-                log::warn!("Failed to create directory {parent_dir:?}: {err}");
-            }
+            std::fs::create_dir_all(parent_dir)?;
         }
     }
 
-    match std::fs::File::create(file_path) {
-        Ok(file) => {
-            let mut writer = std::io::BufWriter::new(file);
-            let config = Default::default();
-
-            crate::profile_scope!("ron::serialize");
-            if let Err(err) = ron::ser::to_writer_pretty(&mut writer, &kv, config)
-                .and_then(|_| writer.flush().map_err(|err| err.into()))
-            {
-                log::warn!("Failed to serialize app state: {}", err);
-            } else {
-                log::trace!("Persisted to {:?}", file_path);
+    let tmp_path = sibling_path(file_path, "tmp");
+    {
+        let file = std::fs::File::create(&tmp_path)?;
+        let mut writer = std::io::BufWriter::new(file);
+        let config = Default::default();
+
+        crate::profile_scope!("ron::serialize");
+        ron::ser::to_writer_pretty(&mut writer, &kv, config).map_err(std::io::Error::other)?;
+        writer.flush()?;
+
+        // Make sure the data is on disk before we replace the old file with it:
+        writer.get_ref().sync_all()?;
+    }
+
+    if 0 < num_backups && file_path.exists() {
+        crate::profile_scope!("rotate_backups");
+        for n in (1..num_backups).rev() {
+            let older = backup_path(file_path, n);
+            if older.exists() {
+                std::fs::rename(&older, backup_path(file_path, n + 1))?;
             }
         }
-        Err(err) => {
-            log::warn!("Failed to create file {file_path:?}: {err}");
-        }
+        // Copy rather than rename, so there is always a valid `file_path`:
+        std::fs::copy(file_path, backup_path(file_path, 1))?;
     }
+
+    std::fs::rename(&tmp_path, file_path)?;
+    log::trace!("Persisted to {:?}", file_path);
+    Ok(())
 }
 
 // ----------------------------------------------------------------------------
 
+/// Read the state file, falling back to the backups (most recent first) if it is missing or corrupt.
+fn read_ron_or_backup<T>(ron_path: &Path) -> Option<T>
+where
+    T: serde::de::DeserializeOwned,
+{
+    if let Some(value) = read_ron(ron_path) {
+        return Some(value);
+    }
+
+    (1..)
+        .map(|n| backup_path(ron_path, n))
+        .take_while(|path| path.exists())
+        .find_map(|path| {
+            let value = read_ron(&path)?;
+            log::info!("Restored app state from backup {path:?}");
+            Some(value)
+        })
+}
+
 fn read_ron<T>(ron_path: impl AsRef<Path>) -> Option<T>
 where
     T: serde::de::DeserializeOwned,
@@ -257,4 +329,24 @@ mod tests {
             assert_eq!(directories_storage_dir(app_id), storage_dir(app_id));
         }
     }
+
+    #[test]
+    fn save_rotates_backups_and_restores_from_them() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.ron");
+        let kv = |value: &str| HashMap::from([("key".to_owned(), value.to_owned())]);
+
+        for value in ["1", "2", "3"] {
+            save_to_disk(&path, &kv(value), 2).unwrap();
+        }
+        assert_eq!(read_ron(&path), Some(kv("3")));
+        assert_eq!(read_ron(backup_path(&path, 1)), Some(kv("2")));
+        assert_eq!(read_ron(backup_path(&path, 2)), Some(kv("1")));
+        assert!(!backup_path(&path, 3).exists());
+        assert!(!sibling_path(&path, "tmp").exists());
+
+        // A corrupt state file falls back to the most recent backup:
+        std::fs::write(&path, "not ron").unwrap();
+        assert_eq!(read_ron_or_backup(&path), Some(kv("2")));
+    }
 }
@@ -0,0 +1,135 @@
+//! Gamepad (game controller) input, see the `gamepad` feature.
+
+use std::time::Duration;
+
+use egui::{Event, GamepadAxis, GamepadButton, GamepadState, Key, Modifiers, RawInput};
+
+/// `gilrs` can't wake up the event loop, so while a gamepad is connected we check it this often.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Reads the gamepads with `gilrs`, and turns their input into egui input.
+pub struct Gamepads {
+    gilrs: gilrs::Gilrs,
+}
+
+impl Gamepads {
+    /// `None` if the gamepads can't be read on this platform.
+    pub fn new() -> Option<Self> {
+        match gilrs::Gilrs::new() {
+            Ok(gilrs) => Some(Self { gilrs }),
+            Err(err) => {
+                log::warn!("Failed to read gamepads: {err}");
+                None
+            }
+        }
+    }
+
+    /// Add the state of the gamepads to the input,
+    /// and turn button presses into key presses for navigating the ui.
+    pub fn on_raw_input(&mut self, egui_ctx: &egui::Context, raw_input: &mut RawInput) {
+        crate::profile_function!();
+
+        let has_focused_widget = egui_ctx.memory(|mem| mem.focused().is_some());
+
+        while let Some(gilrs::Event { event, .. }) = self.gilrs.next_event() {
+            let gilrs::EventType::ButtonPressed(button, _) = event else {
+                continue;
+            };
+            if !raw_input.focused {
+                continue; // Don't control a window in the background
+            }
+            let Some((key, modifiers)) = navigation_key(button, has_focused_widget) else {
+                continue;
+            };
+
+            // A full key press, so the key isn't stuck down if focus moves before the release:
+            for pressed in [true, false] {
+                raw_input.events.push(Event::Key {
+                    key,
+                    physical_key: None,
+                    scancode: None,
+                    pressed,
+                    repeat: false,
+                    modifiers,
+                });
+            }
+        }
+
+        raw_input.gamepads = self
+            .gilrs
+            .gamepads()
+            .map(|(id, gamepad)| gamepad_state(id, &gamepad))
+            .collect();
+
+        if !raw_input.gamepads.is_empty() {
+            egui_ctx.request_repaint_after(POLL_INTERVAL);
+        }
+    }
+}
+
+/// The key to press for a gamepad button, if any.
+///
+/// With nothing focused, the D-pad focuses the first (or last) widget, like Tab does.
+fn navigation_key(button: gilrs::Button, has_focused_widget: bool) -> Option<(Key, Modifiers)> {
+    use gilrs::Button;
+
+    let key = match button {
+        Button::DPadUp | Button::DPadLeft if !has_focused_widget => {
+            return Some((Key::Tab, Modifiers::SHIFT));
+        }
+        Button::DPadDown | Button::DPadRight if !has_focused_widget => Key::Tab,
+        Button::DPadUp => Key::ArrowUp,
+        Button::DPadDown => Key::ArrowDown,
+        Button::DPadLeft => Key::ArrowLeft,
+        Button::DPadRight => Key::ArrowRight,
+        Button::South => Key::Enter,
+        Button::East => Key::Escape,
+        _ => return None,
+    };
+    Some((key, Modifiers::NONE))
+}
+
+fn gamepad_state(id: gilrs::GamepadId, gamepad: &gilrs::Gamepad<'_>) -> GamepadState {
+    let buttons_down = [
+        (gilrs::Button::South, GamepadButton::South),
+        (gilrs::Button::East, GamepadButton::East),
+        (gilrs::Button::North, GamepadButton::North),
+        (gilrs::Button::West, GamepadButton::West),
+        (gilrs::Button::LeftTrigger, GamepadButton::LeftTrigger),
+        (gilrs::Button::LeftTrigger2, GamepadButton::LeftTrigger2),
+        (gilrs::Button::RightTrigger, GamepadButton::RightTrigger),
+        (gilrs::Button::RightTrigger2, GamepadButton::RightTrigger2),
+        (gilrs::Button::Select, GamepadButton::Select),
+        (gilrs::Button::Start, GamepadButton::Start),
+        (gilrs::Button::Mode, GamepadButton::Mode),
+        (gilrs::Button::LeftThumb, GamepadButton::LeftThumb),
+        (gilrs::Button::RightThumb, GamepadButton::RightThumb),
+        (gilrs::Button::DPadUp, GamepadButton::DPadUp),
+        (gilrs::Button::DPadDown, GamepadButton::DPadDown),
+        (gilrs::Button::DPadLeft, GamepadButton::DPadLeft),
+        (gilrs::Button::DPadRight, GamepadButton::DPadRight),
+    ]
+    .into_iter()
+    .filter(|(button, _)| gamepad.is_pressed(*button))
+    .map(|(_, button)| button)
+    .collect();
+
+    let axes = [
+        (gilrs::Axis::LeftStickX, GamepadAxis::LeftStickX),
+        (gilrs::Axis::LeftStickY, GamepadAxis::LeftStickY),
+        (gilrs::Axis::RightStickX, GamepadAxis::RightStickX),
+        (gilrs::Axis::RightStickY, GamepadAxis::RightStickY),
+        (gilrs::Axis::LeftZ, GamepadAxis::LeftZ),
+        (gilrs::Axis::RightZ, GamepadAxis::RightZ),
+    ]
+    .into_iter()
+    .filter_map(|(axis, egui_axis)| Some((egui_axis, gamepad.axis_data(axis)?.value())))
+    .collect();
+
+    GamepadState {
+        id: id.into(),
+        name: gamepad.name().to_owned(),
+        buttons_down,
+        axes,
+    }
+}
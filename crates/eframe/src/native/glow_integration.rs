@@ -33,12 +33,12 @@ use egui_winit::accesskit_winit;
 
 use crate::{
     native::epi_integration::EpiIntegration, App, AppCreator, CreationContext, NativeOptions,
-    Result, Storage,
+    PresentationMode, Result, Storage,
 };
 
 use super::{
     epi_integration, event_loop_context,
-    winit_integration::{create_egui_context, EventResult, UserEvent, WinitApp},
+    winit_integration::{self, create_egui_context, EventResult, UserEvent, WinitApp},
 };
 
 // ----------------------------------------------------------------------------
@@ -87,6 +87,17 @@ struct GlutinWindowContext {
     egui_ctx: egui::Context,
 
     swap_interval: glutin::surface::SwapInterval,
+
+    /// Wait for the GPU after each buffer swap, so that at most one frame is in flight.
+    finish_after_swap: bool,
+
+    /// From [`NativeOptions`], to pick the swap interval when the presentation mode changes.
+    vsync: bool,
+    desired_maximum_frame_latency: Option<u32>,
+
+    /// From [`NativeOptions::partial_redraw`].
+    partial_redraw: bool,
+
     gl_config: glutin::config::Config,
 
     max_texture_side: Option<usize>,
@@ -99,6 +110,9 @@ struct GlutinWindowContext {
     window_from_viewport: ViewportIdMap<WindowId>,
 
     focused_viewport: Option<ViewportId>,
+
+    /// Moves drag-and-drop between windows.
+    dnd_forwarder: egui_winit::DndForwarder,
 }
 
 struct Viewport {
@@ -118,6 +132,13 @@ struct Viewport {
     gl_surface: Option<glutin::surface::Surface<glutin::surface::WindowSurface>>,
     window: Option<Arc<Window>>,
     egui_winit: Option<egui_winit::State>,
+
+    /// The config the window was created with, if it differs from [`GlutinWindowContext::gl_config`]
+    /// because of [`ViewportBuilder::with_surface`].
+    gl_config: Option<glutin::config::Config>,
+
+    /// What changed since earlier frames, with [`NativeOptions::partial_redraw`].
+    damage_tracker: egui_glow::DamageTracker,
 }
 
 // ----------------------------------------------------------------------------
@@ -151,7 +172,7 @@ impl<'app> GlowWinitApp<'app> {
         let window_settings = epi_integration::load_window_settings(storage);
 
         let winit_window_builder = epi_integration::viewport_builder(
-            egui_ctx.zoom_factor(),
+            egui_ctx.zoom_factor_of(ViewportId::ROOT),
             event_loop,
             native_options,
             window_settings,
@@ -198,7 +219,7 @@ impl<'app> GlowWinitApp<'app> {
         crate::profile_function!();
 
         let storage = if let Some(file) = &self.native_options.persistence_path {
-            epi_integration::create_storage_with_file(file)
+            epi_integration::create_storage_with_file(file, &self.native_options.autosave)
         } else {
             epi_integration::create_storage(
                 self.native_options
@@ -206,6 +227,7 @@ impl<'app> GlowWinitApp<'app> {
                     .app_id
                     .as_ref()
                     .unwrap_or(&self.app_name),
+                &self.native_options.autosave,
             )
         };
 
@@ -229,7 +251,8 @@ impl<'app> GlowWinitApp<'app> {
 
         let painter = Rc::new(RefCell::new(painter));
 
-        let integration = EpiIntegration::new(
+        #[cfg_attr(not(feature = "hot_reload"), allow(unused_mut))]
+        let mut integration = EpiIntegration::new(
             egui_ctx,
             &glutin.window(ViewportId::ROOT),
             &self.app_name,
@@ -242,6 +265,8 @@ impl<'app> GlowWinitApp<'app> {
             })),
             #[cfg(feature = "wgpu")]
             None,
+            #[cfg(feature = "notifications")]
+            self.repaint_proxy.lock().clone(),
         );
 
         {
@@ -263,6 +288,14 @@ impl<'app> GlowWinitApp<'app> {
                 });
         }
 
+        super::wake_source::spawn(
+            &integration.egui_ctx,
+            std::mem::take(&mut self.native_options.wake_sources),
+        );
+
+        #[cfg(feature = "hot_reload")]
+        integration.set_hot_reload(&mut self.native_options);
+
         #[cfg(feature = "accesskit")]
         {
             let event_loop_proxy = self.repaint_proxy.lock().clone();
@@ -309,7 +342,11 @@ impl<'app> GlowWinitApp<'app> {
                 raw_window_handle: window.window_handle().map(|h| h.as_raw()),
             };
             crate::profile_scope!("app_creator");
-            app_creator(&cc).map_err(crate::Error::AppCreation)?
+            super::startup_error::app_or_error_screen(
+                app_creator(&cc),
+                &self.app_name,
+                &self.native_options,
+            )?
         };
 
         let glutin = Rc::new(RefCell::new(glutin));
@@ -367,6 +404,12 @@ impl<'app> WinitApp for GlowWinitApp<'app> {
             .and_then(|r| r.glutin.borrow().window_from_viewport.get(&id).copied())
     }
 
+    fn viewport_id_from_window_id(&self, id: WindowId) -> Option<ViewportId> {
+        self.running
+            .as_ref()
+            .and_then(|r| r.glutin.borrow().viewport_from_window.get(&id).copied())
+    }
+
     fn save_and_destroy(&mut self) {
         if let Some(mut running) = self.running.take() {
             crate::profile_function!();
@@ -457,6 +500,26 @@ impl<'app> WinitApp for GlowWinitApp<'app> {
         }
     }
 
+    fn on_monitors_changed(
+        &mut self,
+        changes: &egui_winit::MonitorChanges,
+    ) -> crate::Result<EventResult> {
+        if let Some(running) = &self.running {
+            let mut glutin = running.glutin.borrow_mut();
+            for viewport in glutin.viewports.values_mut() {
+                if let (Some(window), Some(egui_winit)) =
+                    (&viewport.window, &mut viewport.egui_winit)
+                {
+                    let policy = viewport.builder.monitor_relocation.unwrap_or_default();
+                    egui_winit.on_monitors_changed(window, changes, policy);
+                    window.request_redraw();
+                }
+            }
+        }
+
+        Ok(EventResult::Wait)
+    }
+
     #[cfg(feature = "accesskit")]
     fn on_accesskit_event(&mut self, event: accesskit_winit::Event) -> crate::Result<EventResult> {
         use super::winit_integration;
@@ -481,6 +544,36 @@ impl<'app> WinitApp for GlowWinitApp<'app> {
 }
 
 impl<'app> GlowWinitRunning<'app> {
+    /// Swap in a new app, keeping the state of the old one, see [`crate::HotReload`].
+    #[cfg(feature = "hot_reload")]
+    fn reload_app_if_requested(&mut self) {
+        use raw_window_handle::HasDisplayHandle as _;
+
+        let Some(hot_reload) = &mut self.integration.hot_reload else {
+            return;
+        };
+        if !hot_reload.take_request() {
+            return;
+        }
+
+        let storage = super::hot_reload::HotReload::save(self.app.as_mut());
+        let glutin = self.glutin.borrow();
+        let get_proc_address = |addr: &_| glutin.get_proc_address(addr);
+        let window = glutin.window(ViewportId::ROOT);
+        let cc = CreationContext {
+            egui_ctx: self.integration.egui_ctx.clone(),
+            integration_info: self.integration.frame.info().clone(),
+            storage: Some(&storage),
+            gl: Some(self.painter.borrow().gl().clone()),
+            get_proc_address: Some(&get_proc_address),
+            #[cfg(feature = "wgpu")]
+            wgpu_render_state: None,
+            raw_display_handle: window.display_handle().map(|h| h.as_raw()),
+            raw_window_handle: window.window_handle().map(|h| h.as_raw()),
+        };
+        hot_reload.reload(&mut self.app, &cc);
+    }
+
     fn run_ui_and_paint(
         &mut self,
         event_loop: &ActiveEventLoop,
@@ -520,6 +613,11 @@ impl<'app> GlowWinitRunning<'app> {
             }
         }
 
+        #[cfg(feature = "hot_reload")]
+        if viewport_id == ViewportId::ROOT {
+            self.reload_app_if_requested();
+        }
+
         let (raw_input, viewport_ui_cb) = {
             let mut glutin = self.glutin.borrow_mut();
             let egui_ctx = glutin.egui_ctx.clone();
@@ -529,7 +627,13 @@ impl<'app> GlowWinitRunning<'app> {
             let Some(window) = viewport.window.as_ref() else {
                 return Ok(EventResult::Wait);
             };
-            egui_winit::update_viewport_info(&mut viewport.info, &egui_ctx, window, false);
+            egui_winit::update_viewport_info(
+                &mut viewport.info,
+                &egui_ctx,
+                viewport_id,
+                window,
+                false,
+            );
 
             let Some(egui_winit) = viewport.egui_winit.as_mut() else {
                 return Ok(EventResult::Wait);
@@ -554,7 +658,10 @@ impl<'app> GlowWinitRunning<'app> {
             .clear_color(&self.integration.egui_ctx.style().visuals);
 
         let has_many_viewports = self.glutin.borrow().viewports.len() > 1;
-        let clear_before_update = !has_many_viewports; // HACK: for some reason, an early clear doesn't "take" on Mac with multiple viewports.
+        let partial_redraw = self.glutin.borrow().partial_redraw;
+        // HACK: for some reason, an early clear doesn't "take" on Mac with multiple viewports.
+        // With partial redraws we only know what to clear once we know what egui will paint.
+        let clear_before_update = !has_many_viewports && !partial_redraw;
 
         if clear_before_update {
             // clear before we call update, so users can paint between clear-color and egui windows:
@@ -595,6 +702,12 @@ impl<'app> GlowWinitRunning<'app> {
             self.integration
                 .update(self.app.as_mut(), viewport_ui_cb.as_deref(), raw_input);
 
+        if let Some(presentation_mode) = self.integration.presentation_mode_change() {
+            self.glutin
+                .borrow_mut()
+                .set_presentation_mode(presentation_mode);
+        }
+
         // ------------------------------------------------------------
 
         let Self {
@@ -618,6 +731,7 @@ impl<'app> GlowWinitRunning<'app> {
 
         glutin.remove_viewports_not_in(&viewport_output);
 
+        let finish_after_swap = glutin.finish_after_swap;
         let GlutinWindowContext {
             viewports,
             current_gl_context,
@@ -630,13 +744,30 @@ impl<'app> GlowWinitRunning<'app> {
         };
 
         viewport.info.events.clear(); // they should have been processed
+        viewport.info.command_results.clear();
         let window = viewport.window.clone().unwrap();
         let gl_surface = viewport.gl_surface.as_ref().unwrap();
         let egui_winit = viewport.egui_winit.as_mut().unwrap();
+        let input_time = egui_winit.input_time();
 
         egui_winit.handle_platform_output(&window, platform_output);
 
-        let clipped_primitives = integration.egui_ctx.tessellate(shapes, pixels_per_point);
+        let screen_size_in_pixels: [u32; 2] = window.inner_size().into();
+
+        let lens = if viewport_id == ViewportId::ROOT {
+            integration.magnifier.as_mut().and_then(|magnifier| {
+                magnifier.prepare(
+                    &integration.egui_ctx,
+                    &shapes,
+                    screen_size_in_pixels,
+                    pixels_per_point,
+                )
+            })
+        } else {
+            None
+        };
+
+        let mut clipped_primitives = integration.egui_ctx.tessellate(shapes, pixels_per_point);
 
         {
             // We may need to switch contexts again, because of immediate viewports:
@@ -645,18 +776,49 @@ impl<'app> GlowWinitRunning<'app> {
             frame_timer.resume();
         }
 
-        let screen_size_in_pixels: [u32; 2] = window.inner_size().into();
+        let damage = if partial_redraw && lens.is_none() {
+            crate::profile_scope!("damage");
+            let damage = viewport.damage_tracker.update(
+                screen_size_in_pixels,
+                pixels_per_point,
+                &clipped_primitives,
+                &textures_delta,
+                gl_surface.buffer_age(),
+            );
+            let screenshot = viewport
+                .actions_requested
+                .contains(&ActionRequested::Screenshot);
+            damage.filter(|_| !screenshot)
+        } else {
+            // The magnifier paints things we don't track:
+            viewport.damage_tracker = Default::default();
+            None
+        };
+        painter.set_damage(damage);
 
         if !clear_before_update {
             painter.clear(screen_size_in_pixels, clear_color);
         }
 
+        if let (Some(lens), Some(magnifier)) = (&lens, &mut integration.magnifier) {
+            clipped_primitives.extend(magnifier.paint_glow(
+                &integration.egui_ctx,
+                &mut painter,
+                lens,
+                clear_color,
+                &textures_delta,
+                pixels_per_point,
+            ));
+        }
+
         painter.paint_and_update_textures(
             screen_size_in_pixels,
             pixels_per_point,
             &clipped_primitives,
             &textures_delta,
         );
+        painter.set_damage(None);
+        integration.report_texture_memory(painter.texture_memory());
 
         {
             for action in viewport.actions_requested.drain() {
@@ -678,15 +840,7 @@ impl<'app> GlowWinitRunning<'app> {
                         egui_winit.egui_input_mut().events.push(egui::Event::Copy);
                     }
                     ActionRequested::Paste => {
-                        if let Some(contents) = egui_winit.clipboard_text() {
-                            let contents = contents.replace("\r\n", "\n");
-                            if !contents.is_empty() {
-                                egui_winit
-                                    .egui_input_mut()
-                                    .events
-                                    .push(egui::Event::Paste(contents));
-                            }
-                        }
+                        egui_winit.push_paste_event();
                     }
                 }
             }
@@ -704,10 +858,15 @@ impl<'app> GlowWinitRunning<'app> {
                     "failed to get current context to swap buffers".to_owned(),
                 ))?;
 
-            gl_surface.swap_buffers(context)?;
+            swap_buffers(gl_surface, context, screen_size_in_pixels, damage)?;
+            if finish_after_swap {
+                finish_gl(painter.gl());
+            }
             frame_timer.resume();
         }
 
+        integration.report_input_latency(input_time);
+
         // give it time to settle:
         #[cfg(feature = "__screenshot")]
         if integration.egui_ctx.cumulative_pass_nr() == 2 {
@@ -821,11 +980,37 @@ impl<'app> GlowWinitRunning<'app> {
             return EventResult::Exit;
         }
 
+        if let Some(modal) = viewport_id.and_then(|id| glutin.modal_child_window(id)) {
+            if winit_integration::is_blocked_by_modal(event, &modal) {
+                return EventResult::Wait;
+            }
+        }
+
+        let forwarded = viewport_id.and_then(|viewport_id| {
+            let glutin = &mut *glutin;
+            let mut windows: Vec<_> = glutin
+                .viewports
+                .iter_mut()
+                .filter_map(|(id, viewport)| {
+                    Some((
+                        *id,
+                        viewport.window.as_deref()?,
+                        viewport.egui_winit.as_mut()?,
+                    ))
+                })
+                .collect();
+            glutin
+                .dnd_forwarder
+                .on_window_event(viewport_id, &mut windows, event)
+        });
+
         let mut event_response = egui_winit::EventResponse {
             consumed: false,
             repaint: false,
         };
-        if let Some(viewport_id) = viewport_id {
+        if let Some(forwarded) = forwarded {
+            event_response = forwarded;
+        } else if let Some(viewport_id) = viewport_id {
             if let Some(viewport) = glutin.viewports.get_mut(&viewport_id) {
                 if let (Some(window), Some(egui_winit)) =
                     (&viewport.window, &mut viewport.egui_winit)
@@ -851,6 +1036,69 @@ impl<'app> GlowWinitRunning<'app> {
     }
 }
 
+/// The swap interval for `presentation_mode`,
+/// and whether to wait for the GPU after each swap to keep at most one frame in flight.
+fn glutin_presentation(
+    vsync: bool,
+    desired_maximum_frame_latency: Option<u32>,
+    presentation_mode: PresentationMode,
+) -> (glutin::surface::SwapInterval, bool) {
+    match presentation_mode {
+        PresentationMode::PowerSaving => {
+            let swap_interval = if vsync {
+                glutin::surface::SwapInterval::Wait(NonZeroU32::MIN)
+            } else {
+                glutin::surface::SwapInterval::DontWait
+            };
+            (swap_interval, desired_maximum_frame_latency == Some(1))
+        }
+        PresentationMode::LowLatency => (glutin::surface::SwapInterval::DontWait, true),
+    }
+}
+
+/// Block until the GPU has finished all commands, e.g. so that no frames queue up.
+#[allow(unsafe_code)]
+fn finish_gl(gl: &glow::Context) {
+    use glow::HasContext as _;
+
+    crate::profile_function!();
+    // SAFETY: `glFinish` only waits, and the context is current.
+    unsafe { gl.finish() };
+}
+
+/// Present the frame, telling the compositor if only the `damage` (in pixels) changed.
+#[cfg_attr(target_vendor = "apple", allow(unused_variables))]
+fn swap_buffers(
+    gl_surface: &glutin::surface::Surface<glutin::surface::WindowSurface>,
+    context: &glutin::context::PossiblyCurrentContext,
+    [_, height_px]: [u32; 2],
+    damage: Option<egui::Rect>,
+) -> glutin::error::Result<()> {
+    #[cfg(all(any(windows, unix), not(target_vendor = "apple")))]
+    if let (
+        Some(damage),
+        glutin::surface::Surface::Egl(surface),
+        glutin::context::PossiblyCurrentContext::Egl(context),
+    ) = (damage, gl_surface, context)
+    {
+        // An empty list of rectangles would mean that everything changed:
+        if damage.is_positive() {
+            let rect = glutin::surface::Rect::new(
+                damage.min.x as i32,
+                height_px as i32 - damage.max.y as i32, // from the bottom
+                damage.width() as i32,
+                damage.height() as i32,
+            );
+            match surface.swap_buffers_with_damage(context, &[rect]) {
+                Ok(()) => return Ok(()),
+                Err(err) => log::debug!("swap_buffers_with_damage failed: {err}"),
+            }
+        }
+    }
+
+    gl_surface.swap_buffers(context)
+}
+
 fn change_gl_context(
     current_gl_context: &mut Option<glutin::context::PossiblyCurrentContext>,
     not_current_gl_context: &mut Option<glutin::context::NotCurrentContext>,
@@ -887,6 +1135,54 @@ fn change_gl_context(
     *current_gl_context = Some(not_current.make_current(gl_surface).unwrap());
 }
 
+/// The config that best matches the requested `surface`,
+/// among those like `root_config` (which the GL context was created with) in all other respects.
+///
+/// Falls back to `root_config` if there is none.
+#[allow(unsafe_code)]
+fn find_gl_config(
+    root_config: &glutin::config::Config,
+    surface: egui::SurfaceConfig,
+) -> glutin::config::Config {
+    crate::profile_function!();
+
+    let template = glutin::config::ConfigTemplateBuilder::new()
+        .with_api(root_config.api())
+        .prefer_hardware_accelerated(Some(root_config.hardware_accelerated()))
+        .with_alpha_size(root_config.alpha_size())
+        .with_depth_size(root_config.depth_size())
+        .with_stencil_size(root_config.stencil_size())
+        .with_transparency(root_config.supports_transparency() == Some(true))
+        .with_float_pixels(surface.float);
+    let template = if surface.multisampling > 0 {
+        template.with_multisampling(surface.multisampling.try_into().unwrap_or(u8::MAX))
+    } else {
+        template
+    };
+
+    let display = root_config.display();
+    // SAFETY: the template does not refer to any native window.
+    let configs = match unsafe { display.find_configs(template.build()) } {
+        Ok(configs) => configs,
+        Err(err) => {
+            log::warn!("Failed to find a GL config for {surface:?}: {err}");
+            return root_config.clone();
+        }
+    };
+    configs
+        .min_by_key(|config| {
+            (
+                config.float_pixels() != surface.float,
+                surface.srgb && !config.srgb_capable(),
+                u16::from(config.num_samples()).abs_diff(surface.multisampling),
+            )
+        })
+        .unwrap_or_else(|| {
+            log::warn!("Found no GL config for {surface:?}");
+            root_config.clone()
+        })
+}
+
 impl GlutinWindowContext {
     #[allow(unsafe_code)]
     unsafe fn new(
@@ -907,11 +1203,11 @@ impl GlutinWindowContext {
             crate::HardwareAcceleration::Preferred => None,
             crate::HardwareAcceleration::Off => Some(false),
         };
-        let swap_interval = if native_options.vsync {
-            glutin::surface::SwapInterval::Wait(NonZeroU32::MIN)
-        } else {
-            glutin::surface::SwapInterval::DontWait
-        };
+        let (swap_interval, finish_after_swap) = glutin_presentation(
+            native_options.vsync,
+            native_options.desired_maximum_frame_latency,
+            native_options.presentation_mode,
+        );
         /*  opengl setup flow goes like this:
             1. we create a configuration for opengl "Display" / "Config" creation
             2. choose between special extensions like glx or egl or wgl and use them to create config/display
@@ -944,6 +1240,7 @@ impl GlutinWindowContext {
             .with_preference(glutin_winit::ApiPreference::FallbackEgl) // https://github.com/emilk/egui/issues/2520#issuecomment-1367841150
             .with_window_attributes(Some(egui_winit::create_winit_window_attributes(
                 egui_ctx,
+                ViewportId::ROOT,
                 event_loop,
                 viewport_builder.clone(),
             )));
@@ -968,7 +1265,12 @@ impl GlutinWindowContext {
                 .map_err(|e| crate::Error::NoGlutinConfigs(config_template_builder.build(), e))?
         };
         if let Some(window) = &window {
-            egui_winit::apply_viewport_builder_to_window(egui_ctx, window, &viewport_builder);
+            egui_winit::apply_viewport_builder_to_window(
+                egui_ctx,
+                ViewportId::ROOT,
+                window,
+                &viewport_builder,
+            );
         }
 
         let gl_display = gl_config.display();
@@ -1020,7 +1322,7 @@ impl GlutinWindowContext {
         if let Some(window) = &window {
             viewport_from_window.insert(window.id(), ViewportId::ROOT);
             window_from_viewport.insert(ViewportId::ROOT, window.id());
-            egui_winit::update_viewport_info(&mut info, egui_ctx, window, true);
+            egui_winit::update_viewport_info(&mut info, egui_ctx, ViewportId::ROOT, window, true);
         }
 
         let mut viewports = ViewportIdMap::default();
@@ -1037,6 +1339,8 @@ impl GlutinWindowContext {
                 gl_surface: None,
                 window: window.map(Arc::new),
                 egui_winit: None,
+                gl_config: None,
+                damage_tracker: Default::default(),
             },
         );
 
@@ -1048,6 +1352,10 @@ impl GlutinWindowContext {
         let mut slf = Self {
             egui_ctx: egui_ctx.clone(),
             swap_interval,
+            finish_after_swap,
+            vsync: native_options.vsync,
+            desired_maximum_frame_latency: native_options.desired_maximum_frame_latency,
+            partial_redraw: native_options.partial_redraw,
             gl_config,
             current_gl_context: None,
             not_current_gl_context,
@@ -1056,6 +1364,7 @@ impl GlutinWindowContext {
             max_texture_side: None,
             window_from_viewport,
             focused_viewport: Some(ViewportId::ROOT),
+            dnd_forwarder: Default::default(),
         };
 
         slf.initialize_window(ViewportId::ROOT, event_loop)?;
@@ -1087,6 +1396,8 @@ impl GlutinWindowContext {
     ) -> Result {
         crate::profile_function!();
 
+        let modal_parent = self.modal_parent_window(viewport_id);
+
         let viewport = self
             .viewports
             .get_mut(&viewport_id)
@@ -1096,25 +1407,44 @@ impl GlutinWindowContext {
             window
         } else {
             log::debug!("Creating a window for viewport {viewport_id:?}");
-            let window_attributes = egui_winit::create_winit_window_attributes(
+            let mut window_attributes = egui_winit::create_winit_window_attributes(
                 &self.egui_ctx,
+                viewport_id,
                 event_loop,
                 viewport.builder.clone(),
             );
-            if window_attributes.transparent()
-                && self.gl_config.supports_transparency() == Some(false)
-            {
+            if let Some(parent) = &modal_parent {
+                window_attributes = winit_integration::with_modal_parent(window_attributes, parent);
+            }
+
+            // A different config can only be used if there is a current context to check it against,
+            // see below. The root viewport is configured with `NativeOptions`.
+            viewport.gl_config = viewport
+                .builder
+                .surface
+                .filter(|_| viewport_id != ViewportId::ROOT && self.current_gl_context.is_some())
+                .map(|surface| find_gl_config(&self.gl_config, surface))
+                .filter(|gl_config| gl_config != &self.gl_config);
+            let gl_config = viewport.gl_config.as_ref().unwrap_or(&self.gl_config);
+
+            if window_attributes.transparent() && gl_config.supports_transparency() == Some(false) {
                 log::error!("Cannot create transparent window: the GL config does not support it");
             }
-            let window =
-                glutin_winit::finalize_window(event_loop, window_attributes, &self.gl_config)?;
+            let window = glutin_winit::finalize_window(event_loop, window_attributes, gl_config)?;
             egui_winit::apply_viewport_builder_to_window(
                 &self.egui_ctx,
+                viewport_id,
                 &window,
                 &viewport.builder,
             );
 
-            egui_winit::update_viewport_info(&mut viewport.info, &self.egui_ctx, &window, true);
+            egui_winit::update_viewport_info(
+                &mut viewport.info,
+                &self.egui_ctx,
+                viewport_id,
+                &window,
+                true,
+            );
             viewport.window.insert(Arc::new(window))
         };
 
@@ -1137,8 +1467,11 @@ impl GlutinWindowContext {
             let (width_px, height_px): (u32, u32) = window.inner_size().into();
             let width_px = NonZeroU32::new(width_px).unwrap_or(NonZeroU32::MIN);
             let height_px = NonZeroU32::new(height_px).unwrap_or(NonZeroU32::MIN);
-            let surface_attributes = {
-                glutin::surface::SurfaceAttributesBuilder::<glutin::surface::WindowSurface>::new()
+            let srgb = viewport.builder.surface.map(|surface| surface.srgb);
+            let create_surface = |gl_config: &glutin::config::Config| {
+                use glutin::surface::{SurfaceAttributesBuilder, WindowSurface};
+                let surface_attributes = SurfaceAttributesBuilder::<WindowSurface>::new()
+                    .with_srgb(srgb.map(|srgb| srgb && gl_config.srgb_capable()))
                     .build(
                         window
                             .window_handle()
@@ -1146,18 +1479,32 @@ impl GlutinWindowContext {
                             .as_raw(),
                         width_px,
                         height_px,
-                    )
+                    );
+                log::trace!("creating surface with attributes: {surface_attributes:?}");
+                unsafe {
+                    gl_config
+                        .display()
+                        .create_window_surface(gl_config, &surface_attributes)
+                }
             };
 
-            log::trace!("creating surface with attributes: {surface_attributes:?}");
-            let gl_surface = unsafe {
-                self.gl_config
-                    .display()
-                    .create_window_surface(&self.gl_config, &surface_attributes)?
-            };
+            let mut gl_surface =
+                create_surface(viewport.gl_config.as_ref().unwrap_or(&self.gl_config))?;
 
             log::trace!("surface created successfully: {gl_surface:?}. making context current");
 
+            if let Some(current_gl_context) = &self.current_gl_context {
+                // Make sure that the context can render to a surface with a different config,
+                // since that is not a given, and switching to it later would panic.
+                if viewport.gl_config.is_some() {
+                    if let Err(err) = current_gl_context.make_current(&gl_surface) {
+                        log::warn!("The requested surface for viewport {viewport_id:?} can't be used with the GL context, so using the default one instead: {err}");
+                        viewport.gl_config = None;
+                        gl_surface = create_surface(&self.gl_config)?;
+                    }
+                }
+            }
+
             let not_current_gl_context =
                 if let Some(not_current_context) = self.not_current_gl_context.take() {
                     not_current_context
@@ -1170,6 +1517,13 @@ impl GlutinWindowContext {
                 };
             let current_gl_context = not_current_gl_context.make_current(&gl_surface)?;
 
+            let gl_config = viewport.gl_config.as_ref().unwrap_or(&self.gl_config);
+            viewport.info.surface = Some(egui::SurfaceConfig {
+                multisampling: gl_config.num_samples().into(),
+                srgb: srgb == Some(true) && gl_config.srgb_capable(),
+                float: gl_config.float_pixels(),
+            });
+
             // try setting swap interval. but its not absolutely necessary, so don't panic on failure.
             log::trace!("made context current. setting swap interval for surface");
             if let Err(err) = gl_surface.set_swap_interval(&current_gl_context, self.swap_interval)
@@ -1220,6 +1574,60 @@ impl GlutinWindowContext {
             .expect("winit window doesn't exist")
     }
 
+    /// Switch the swap interval of all windows for `presentation_mode`.
+    fn set_presentation_mode(&mut self, presentation_mode: PresentationMode) {
+        crate::profile_function!();
+
+        let (swap_interval, finish_after_swap) = glutin_presentation(
+            self.vsync,
+            self.desired_maximum_frame_latency,
+            presentation_mode,
+        );
+        self.swap_interval = swap_interval;
+        self.finish_after_swap = finish_after_swap;
+
+        for viewport in self.viewports.values() {
+            let Some(gl_surface) = &viewport.gl_surface else {
+                continue;
+            };
+            change_gl_context(
+                &mut self.current_gl_context,
+                &mut self.not_current_gl_context,
+                gl_surface,
+            );
+            if let Some(current_gl_context) = &self.current_gl_context {
+                if let Err(err) =
+                    gl_surface.set_swap_interval(current_gl_context, self.swap_interval)
+                {
+                    log::warn!("Failed to set swap interval due to error: {err}");
+                }
+            }
+        }
+    }
+
+    /// The window of the modal parent of `viewport_id`, if it is a modal viewport.
+    fn modal_parent_window(&self, viewport_id: ViewportId) -> Option<Arc<Window>> {
+        let viewport = self.viewports.get(&viewport_id)?;
+        if viewport.class != ViewportClass::Modal {
+            return None;
+        }
+        self.viewports
+            .get(&viewport.builder.modal_parent_or(viewport.ids.parent))?
+            .window
+            .clone()
+    }
+
+    /// The window of a modal viewport that blocks the input to `viewport_id`, if any.
+    fn modal_child_window(&self, viewport_id: ViewportId) -> Option<Arc<Window>> {
+        self.viewports
+            .values()
+            .find(|viewport| {
+                viewport.class == ViewportClass::Modal
+                    && viewport.builder.modal_parent_or(viewport.ids.parent) == viewport_id
+            })
+            .and_then(|viewport| viewport.window.clone())
+    }
+
     fn resize(&mut self, viewport_id: ViewportId, physical_size: winit::dpi::PhysicalSize<u32>) {
         let width_px = NonZeroU32::new(physical_size.width).unwrap_or(NonZeroU32::MIN);
         let height_px = NonZeroU32::new(physical_size.height).unwrap_or(NonZeroU32::MIN);
@@ -1279,6 +1687,11 @@ impl GlutinWindowContext {
             },
         ) in viewport_output.clone()
         {
+            if class == ViewportClass::Texture {
+                // Texture viewports are run and painted by the user, not by us.
+                continue;
+            }
+
             let ids = ViewportIdPair::from_self_and_parent(viewport_id, parent);
 
             let viewport = initialize_or_update_viewport(
@@ -1296,6 +1709,7 @@ impl GlutinWindowContext {
 
                 egui_winit::process_viewport_commands(
                     egui_ctx,
+                    viewport_id,
                     &mut viewport.info,
                     std::mem::take(&mut viewport.deferred_commands),
                     window,
@@ -1350,6 +1764,8 @@ fn initialize_or_update_viewport(
                 window: None,
                 egui_winit: None,
                 gl_surface: None,
+                gl_config: None,
+                damage_tracker: Default::default(),
             })
         }
 
@@ -1372,6 +1788,7 @@ fn initialize_or_update_viewport(
                 viewport.window = None;
                 viewport.egui_winit = None;
                 viewport.gl_surface = None;
+                viewport.gl_config = None;
             }
 
             viewport.deferred_commands.append(&mut delta_commands);
@@ -1432,7 +1849,7 @@ fn render_immediate_viewport(
         let (Some(egui_winit), Some(window)) = (&mut viewport.egui_winit, &viewport.window) else {
             return;
         };
-        egui_winit::update_viewport_info(&mut viewport.info, egui_ctx, window, false);
+        egui_winit::update_viewport_info(&mut viewport.info, egui_ctx, viewport_id, window, false);
 
         let mut raw_input = egui_winit.take_egui_input(window);
         raw_input.viewports = glutin
@@ -1476,6 +1893,7 @@ fn render_immediate_viewport(
     };
 
     viewport.info.events.clear(); // they should have been processed
+    viewport.info.command_results.clear();
 
     let (Some(egui_winit), Some(window), Some(gl_surface)) = (
         &mut viewport.egui_winit,
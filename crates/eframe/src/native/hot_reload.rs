@@ -0,0 +1,140 @@
+//! Swap the code of a running app without losing its state, see [`HotReload`].
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use egui::mutex::Mutex;
+
+use crate::{epi::DynError, App, CreationContext, Storage};
+
+/// Creates the app anew after its code has been reloaded, see [`HotReload::new`].
+type ReloadAppCreator = Box<dyn FnMut(&CreationContext<'_>) -> Result<Box<dyn App>, DynError>>;
+
+/// Lets you reload the code of your app (e.g. from a dynamic library, with `hot-lib-reloader`)
+/// while it is running, and keep its state.
+///
+/// When a reload is requested with [`HotReloadTrigger::request_reload`], `eframe`:
+/// * asks the running app to [`App::save`] its state into memory,
+/// * creates a new app with the function given to [`Self::new`], with that state in [`CreationContext::storage`],
+/// * drops the old app.
+///
+/// The window, the graphics context and the [`egui::Memory`] (e.g. which windows are open,
+/// and where) stay as they were, so you can pick up right where you left off.
+///
+/// Make sure the code of the old app is still loaded when it is dropped,
+/// i.e. don't unload the old library until after the reload.
+///
+/// ``` no_run
+/// # fn create_app(cc: &eframe::CreationContext<'_>) -> Result<Box<dyn eframe::App>, Box<dyn std::error::Error + Send + Sync>> { todo!() }
+/// # fn wait_for_new_code() {}
+/// let hot_reload = eframe::HotReload::new(create_app);
+/// let trigger = hot_reload.trigger();
+/// std::thread::spawn(move || loop {
+///     wait_for_new_code();
+///     trigger.request_reload();
+/// });
+///
+/// let options = eframe::NativeOptions {
+///     hot_reload: Some(hot_reload),
+///     ..Default::default()
+/// };
+/// eframe::run_native("My app", options, Box::new(create_app));
+/// ```
+pub struct HotReload {
+    create_app: ReloadAppCreator,
+    trigger: HotReloadTrigger,
+}
+
+impl HotReload {
+    /// `create_app` is called every time the app is reloaded, usually the same function
+    /// that you give to [`crate::run_native`].
+    pub fn new(
+        create_app: impl FnMut(&CreationContext<'_>) -> Result<Box<dyn App>, DynError> + 'static,
+    ) -> Self {
+        Self {
+            create_app: Box::new(create_app),
+            trigger: Default::default(),
+        }
+    }
+
+    /// Use this to request a reload, from any thread.
+    pub fn trigger(&self) -> HotReloadTrigger {
+        self.trigger.clone()
+    }
+
+    /// Called by the integration once the app is running.
+    pub(crate) fn set_egui_ctx(&self, egui_ctx: &egui::Context) {
+        *self.trigger.egui_ctx.lock() = Some(egui_ctx.clone());
+    }
+
+    /// Has a reload been requested since last time?
+    pub(crate) fn take_request(&self) -> bool {
+        self.trigger.requested.swap(false, Ordering::Relaxed)
+    }
+
+    /// Save the state of `app`, to give to the new app in [`CreationContext::storage`].
+    pub(crate) fn save(app: &mut dyn App) -> MemoryStorage {
+        crate::profile_function!();
+        let mut storage = MemoryStorage::default();
+        app.save(&mut storage);
+        storage
+    }
+
+    /// Replace `app` with a newly created one.
+    ///
+    /// If the new app can't be created, the old one keeps running.
+    pub(crate) fn reload<'app>(&mut self, app: &mut Box<dyn 'app + App>, cc: &CreationContext<'_>) {
+        crate::profile_function!();
+        match (self.create_app)(cc) {
+            Ok(new_app) => {
+                log::info!("Reloaded the app");
+                *app = new_app; // The old app is dropped here
+            }
+            Err(err) => {
+                log::error!("Failed to reload the app, keeping the old one: {err}");
+            }
+        }
+    }
+}
+
+/// Requests a reload of the app, see [`HotReload::trigger`].
+#[derive(Clone, Default)]
+pub struct HotReloadTrigger {
+    requested: Arc<AtomicBool>,
+    egui_ctx: Arc<Mutex<Option<egui::Context>>>,
+}
+
+impl HotReloadTrigger {
+    /// Reload the app before its next frame, e.g. after its code has changed.
+    ///
+    /// Can be called from any thread.
+    pub fn request_reload(&self) {
+        self.requested.store(true, Ordering::Relaxed);
+        if let Some(egui_ctx) = &*self.egui_ctx.lock() {
+            egui_ctx.request_repaint();
+        }
+    }
+}
+
+/// Where the app state is kept during a reload.
+#[derive(Default)]
+pub(crate) struct MemoryStorage {
+    values: HashMap<String, String>,
+}
+
+impl Storage for MemoryStorage {
+    fn get_string(&self, key: &str) -> Option<String> {
+        self.values.get(key).cloned()
+    }
+
+    fn set_string(&mut self, key: &str, value: String) {
+        self.values.insert(key.to_owned(), value);
+    }
+
+    fn flush(&mut self) {}
+}
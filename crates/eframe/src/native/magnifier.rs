@@ -0,0 +1,544 @@
+//! A zoom lens for the root viewport, see [`MagnifierOptions`].
+
+use egui::{
+    ecolor::ColorSpace,
+    epaint::{text::Fonts, ClippedShape, ImageDelta, Primitive, Tessellator},
+    pos2, vec2, ClippedPrimitive, Color32, Context, Event, Key, KeyboardShortcut, Modifiers, Pos2,
+    RawInput, Rect, Shape, Stroke, TextureId, TexturesDelta, Vec2,
+};
+
+#[cfg(feature = "wgpu")]
+use egui_wgpu::wgpu;
+
+/// The font atlas the lens is painted with.
+///
+/// The backends hand out user texture ids counting up from zero, so this will not collide with them.
+const LENS_FONT_TEXTURE_ID: TextureId = TextureId::User(u64::MAX);
+
+/// The texture the lens is painted into (glow only, wgpu registers a native texture).
+#[cfg(feature = "glow")]
+const LENS_TEXTURE_ID: TextureId = TextureId::User(u64::MAX - 1);
+
+/// Options for the built-in magnifier: a lens that follows the mouse pointer,
+/// showing the ui under it enlarged.
+///
+/// Unlike the magnifier of the OS, which scales up the pixels on screen and blurs them,
+/// the lens paints the ui again at a higher resolution, so that text and shapes stay crisp.
+///
+/// The lens is toggled with [`Self::shortcut`], and only magnifies the root viewport.
+///
+/// With the `wgpu` renderer the lens is not available when [`crate::NativeOptions::depth_buffer`]
+/// or [`crate::NativeOptions::stencil_buffer`] is set.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MagnifierOptions {
+    /// Shows and hides the lens.
+    ///
+    /// This key press is not passed on to the app.
+    ///
+    /// Default: `Ctrl+Shift+M` (`Cmd+Shift+M` on Mac).
+    pub shortcut: KeyboardShortcut,
+
+    /// How much the lens enlarges, clamped to `1.0..=16.0`.
+    ///
+    /// Default: `3.0`.
+    pub zoom: f32,
+
+    /// The size of the lens on screen, in points.
+    ///
+    /// Default: `320 x 200`.
+    pub lens_size: Vec2,
+
+    /// Show the lens from the start.
+    ///
+    /// Default: `false`.
+    pub enabled: bool,
+}
+
+impl Default for MagnifierOptions {
+    fn default() -> Self {
+        Self {
+            shortcut: KeyboardShortcut::new(Modifiers::COMMAND.plus(Modifiers::SHIFT), Key::M),
+            zoom: 3.0,
+            lens_size: vec2(320.0, 200.0),
+            enabled: false,
+        }
+    }
+}
+
+/// The state of the magnifier, kept by the integration.
+pub(crate) struct Magnifier {
+    options: MagnifierOptions,
+    enabled: bool,
+    pointer_pos: Option<Pos2>,
+
+    /// Fonts for laying out text again at the pixels-per-point of the lens.
+    ///
+    /// Recreated each time the lens is shown, to pick up any changes to the font definitions.
+    fonts: Option<Fonts>,
+
+    #[cfg(feature = "glow")]
+    glow_texture_size: [usize; 2],
+
+    #[cfg(feature = "wgpu")]
+    wgpu_target: Option<WgpuTarget>,
+}
+
+/// The ui under the pointer, tessellated at the pixels-per-point of the lens.
+pub(crate) struct Lens {
+    /// Where on screen the lens is shown, in points.
+    rect: Rect,
+
+    /// The size of the texture the lens is painted into.
+    size_px: [u32; 2],
+
+    /// The pixels-per-point of the lens, i.e. of the screen times the zoom.
+    pixels_per_point: f32,
+
+    primitives: Vec<ClippedPrimitive>,
+
+    /// Changes to the font atlas of the lens.
+    textures_delta: TexturesDelta,
+}
+
+impl Lens {
+    /// The texture changes to apply when painting the lens: those of the frame, and of the lens.
+    ///
+    /// Nothing is freed, since the frame's `textures_delta` is applied by the caller afterwards.
+    fn textures_to_update(&self, textures_delta: &TexturesDelta) -> TexturesDelta {
+        TexturesDelta {
+            set: textures_delta
+                .set
+                .iter()
+                .chain(&self.textures_delta.set)
+                .cloned()
+                .collect(),
+            options: textures_delta.options.clone(),
+            free: vec![],
+        }
+    }
+}
+
+impl Magnifier {
+    pub fn new(options: MagnifierOptions) -> Self {
+        Self {
+            options,
+            enabled: options.enabled,
+            pointer_pos: None,
+            fonts: None,
+            #[cfg(feature = "glow")]
+            glow_texture_size: [0, 0],
+            #[cfg(feature = "wgpu")]
+            wgpu_target: None,
+        }
+    }
+
+    /// Follow the pointer, and toggle the lens on [`MagnifierOptions::shortcut`].
+    ///
+    /// Call with the input of the root viewport, before it is passed on to the app.
+    pub fn on_raw_input(&mut self, raw_input: &mut RawInput) {
+        let shortcut = self.options.shortcut;
+        let mut toggled = false;
+        raw_input.events.retain(|event| match event {
+            Event::Key {
+                key,
+                pressed: true,
+                modifiers,
+                ..
+            } if *key == shortcut.logical_key
+                && modifiers.matches_logically(shortcut.modifiers) =>
+            {
+                toggled = !toggled;
+                false
+            }
+            Event::PointerMoved(pos) => {
+                self.pointer_pos = Some(*pos);
+                true
+            }
+            Event::PointerGone => {
+                self.pointer_pos = None;
+                true
+            }
+            _ => true,
+        });
+
+        if toggled {
+            self.enabled = !self.enabled;
+            log::debug!("Magnifier enabled: {}", self.enabled);
+        }
+    }
+
+    /// Tessellate the shapes around the pointer for the lens, if it is shown.
+    pub fn prepare(
+        &mut self,
+        egui_ctx: &Context,
+        shapes: &[ClippedShape],
+        screen_size_px: [u32; 2],
+        pixels_per_point: f32,
+    ) -> Option<Lens> {
+        crate::profile_function!();
+
+        if !self.enabled {
+            self.fonts = None;
+            return None;
+        }
+        let pointer_pos = self.pointer_pos?;
+
+        let screen_rect = Rect::from_min_size(
+            Pos2::ZERO,
+            vec2(screen_size_px[0] as f32, screen_size_px[1] as f32) / pixels_per_point,
+        );
+        let rect = constrain(
+            Rect::from_center_size(pointer_pos, self.options.lens_size.min(screen_rect.size())),
+            screen_rect,
+        );
+        let size_px = [
+            (rect.width() * pixels_per_point).round() as u32,
+            (rect.height() * pixels_per_point).round() as u32,
+        ];
+        if size_px[0] == 0 || size_px[1] == 0 {
+            return None;
+        }
+
+        // `Fonts` only supports up to 100 pixels per point:
+        let zoom = self
+            .options
+            .zoom
+            .clamp(1.0, 16.0)
+            .min(99.0 / pixels_per_point);
+        let lens_pixels_per_point = pixels_per_point * zoom;
+        let source_size = vec2(size_px[0] as f32, size_px[1] as f32) / lens_pixels_per_point;
+        let source_rect = constrain(
+            Rect::from_center_size(pointer_pos, source_size),
+            screen_rect,
+        );
+
+        let max_texture_side = egui_ctx.fonts(|fonts| fonts.max_texture_side());
+        let fonts = self.fonts.get_or_insert_with(|| {
+            let definitions = egui_ctx.fonts(|fonts| fonts.lock().fonts.definitions().clone());
+            Fonts::new(lens_pixels_per_point, max_texture_side, definitions)
+        });
+        fonts.begin_pass(lens_pixels_per_point, max_texture_side);
+
+        // Move the source rect to the origin of the lens texture:
+        let offset = -source_rect.min.to_vec2();
+        let mut lens_shapes = vec![];
+        for clipped_shape in shapes {
+            if !clipped_shape.clip_rect.intersects(source_rect)
+                || !clipped_shape
+                    .shape
+                    .visual_bounding_rect()
+                    .intersects(source_rect)
+            {
+                continue;
+            }
+            let mut shape = clipped_shape.shape.clone();
+            if relayout_text(&mut shape, fonts) {
+                shape.translate(offset);
+                lens_shapes.push(ClippedShape {
+                    clip_rect: clipped_shape.clip_rect.translate(offset),
+                    shape,
+                });
+            }
+        }
+
+        let (font_tex_size, prepared_discs) = {
+            let atlas = fonts.texture_atlas();
+            let atlas = atlas.lock();
+            (atlas.size(), atlas.prepared_discs())
+        };
+        let mut primitives = Tessellator::new(
+            lens_pixels_per_point,
+            egui_ctx.tessellation_options(|options| *options),
+            font_tex_size,
+            prepared_discs,
+        )
+        .tessellate_shapes(lens_shapes);
+
+        // Text and anti-aliased shapes use the font atlas of the lens instead of that of egui:
+        for primitive in &mut primitives {
+            if let Primitive::Mesh(mesh) = &mut primitive.primitive {
+                if mesh.texture_id == TextureId::default() {
+                    mesh.texture_id = LENS_FONT_TEXTURE_ID;
+                }
+            }
+        }
+
+        let textures_delta = TexturesDelta {
+            set: fonts
+                .font_image_delta()
+                .map(|delta| (LENS_FONT_TEXTURE_ID, delta))
+                .into_iter()
+                .collect(),
+            options: vec![],
+            free: vec![],
+        };
+
+        Some(Lens {
+            rect,
+            size_px,
+            pixels_per_point: lens_pixels_per_point,
+            primitives,
+            textures_delta,
+        })
+    }
+
+    /// Paint the lens into a texture, returning the primitives that show it on screen.
+    ///
+    /// `textures_delta` is that of the frame, which is applied by the caller afterwards.
+    #[cfg(feature = "glow")]
+    pub fn paint_glow(
+        &mut self,
+        egui_ctx: &Context,
+        painter: &mut egui_glow::Painter,
+        lens: &Lens,
+        clear_color: [f32; 4],
+        textures_delta: &TexturesDelta,
+        pixels_per_point: f32,
+    ) -> Vec<ClippedPrimitive> {
+        crate::profile_function!();
+
+        let size = [lens.size_px[0] as usize, lens.size_px[1] as usize];
+        if self.glow_texture_size != size {
+            // Let the painter create the texture, so it gets the same format as any other:
+            painter.set_texture(
+                LENS_TEXTURE_ID,
+                &ImageDelta::full(
+                    egui::ColorImage::new(size, Color32::TRANSPARENT),
+                    egui::TextureOptions::LINEAR,
+                ),
+            );
+            self.glow_texture_size = size;
+        }
+        let Some(target) = painter.texture(LENS_TEXTURE_ID) else {
+            return vec![];
+        };
+
+        let textures_delta = lens.textures_to_update(textures_delta);
+
+        // The lens is converted to the output color space when it is shown on screen:
+        let output_color_space = painter.output_color_space();
+        painter.set_output_color_space(ColorSpace::Srgb);
+        let result = painter.paint_to_texture(
+            target,
+            lens.size_px,
+            lens.pixels_per_point,
+            clear_color,
+            &lens.primitives,
+            &textures_delta,
+        );
+        painter.set_output_color_space(output_color_space);
+        if let Err(err) = result {
+            log::error!("Failed to paint the magnifier lens: {err}");
+            return vec![];
+        }
+
+        // In OpenGL the top of the texture is at `v = 1`:
+        let uv = Rect::from_min_max(pos2(0.0, 1.0), pos2(1.0, 0.0));
+        overlay(egui_ctx, lens.rect, LENS_TEXTURE_ID, uv, pixels_per_point)
+    }
+
+    /// Paint the lens into a texture, returning the primitives that show it on screen.
+    ///
+    /// `textures_delta` is that of the frame, which is applied by the caller afterwards.
+    /// The [`egui_wgpu::Renderer`] must have been created without a depth format.
+    #[cfg(feature = "wgpu")]
+    pub fn paint_wgpu(
+        &mut self,
+        egui_ctx: &Context,
+        render_state: &egui_wgpu::RenderState,
+        lens: &Lens,
+        clear_color: [f32; 4],
+        textures_delta: &TexturesDelta,
+        pixels_per_point: f32,
+    ) -> Vec<ClippedPrimitive> {
+        crate::profile_function!();
+
+        let egui_wgpu::RenderState {
+            device,
+            queue,
+            renderer,
+            target_format,
+            ..
+        } = render_state;
+        let mut renderer = renderer.write();
+
+        if self
+            .wgpu_target
+            .as_ref()
+            .map_or(true, |target| target.size_px != lens.size_px)
+        {
+            let texture_id = self.wgpu_target.take().map(|target| target.texture_id);
+            self.wgpu_target = Some(WgpuTarget::new(
+                device,
+                &mut renderer,
+                *target_format,
+                lens.size_px,
+                texture_id,
+            ));
+        }
+        let Some(target) = &self.wgpu_target else {
+            return vec![];
+        };
+
+        let textures_delta = lens.textures_to_update(textures_delta);
+
+        let (view, resolve_target) = match &target.msaa_view {
+            Some(msaa_view) => (msaa_view, Some(&target.render_view)),
+            None => (&target.render_view, None),
+        };
+
+        // The lens is converted to the output color space when it is shown on screen:
+        let output_color_space = renderer.output_color_space();
+        renderer.set_output_color_space(ColorSpace::Srgb);
+        renderer.render_to_texture(
+            device,
+            queue,
+            &egui_wgpu::TextureTarget {
+                view,
+                resolve_target,
+                clear_color: wgpu::Color {
+                    r: clear_color[0] as f64,
+                    g: clear_color[1] as f64,
+                    b: clear_color[2] as f64,
+                    a: clear_color[3] as f64,
+                },
+            },
+            &lens.primitives,
+            &egui_wgpu::ScreenDescriptor {
+                size_in_pixels: lens.size_px,
+                pixels_per_point: lens.pixels_per_point,
+            },
+            &textures_delta,
+        );
+        renderer.set_output_color_space(output_color_space);
+
+        let uv = Rect::from_min_max(Pos2::ZERO, pos2(1.0, 1.0));
+        overlay(egui_ctx, lens.rect, target.texture_id, uv, pixels_per_point)
+    }
+}
+
+/// The texture the lens is painted into with wgpu.
+#[cfg(feature = "wgpu")]
+struct WgpuTarget {
+    size_px: [u32; 2],
+    texture_id: TextureId,
+    render_view: wgpu::TextureView,
+    msaa_view: Option<wgpu::TextureView>,
+}
+
+#[cfg(feature = "wgpu")]
+impl WgpuTarget {
+    fn new(
+        device: &wgpu::Device,
+        renderer: &mut egui_wgpu::Renderer,
+        format: wgpu::TextureFormat,
+        size_px: [u32; 2],
+        texture_id: Option<TextureId>,
+    ) -> Self {
+        let size = wgpu::Extent3d {
+            width: size_px[0],
+            height: size_px[1],
+            depth_or_array_layers: 1,
+        };
+
+        // Like the surface, the lens is painted with gamma encoded colors,
+        // so it is sampled as sRGB to get them back in linear space:
+        let sampled_format = format.add_srgb_suffix();
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("egui_magnifier"),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[sampled_format],
+        });
+        let render_view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let sampled_view = texture.create_view(&wgpu::TextureViewDescriptor {
+            format: Some(sampled_format),
+            ..Default::default()
+        });
+
+        let msaa_samples = renderer.msaa_samples();
+        let msaa_view = (1 < msaa_samples).then(|| {
+            device
+                .create_texture(&wgpu::TextureDescriptor {
+                    label: Some("egui_magnifier_msaa"),
+                    size,
+                    mip_level_count: 1,
+                    sample_count: msaa_samples,
+                    dimension: wgpu::TextureDimension::D2,
+                    format,
+                    usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+                    view_formats: &[],
+                })
+                .create_view(&wgpu::TextureViewDescriptor::default())
+        });
+
+        let texture_id = if let Some(texture_id) = texture_id {
+            renderer.update_egui_texture_from_wgpu_texture(
+                device,
+                &sampled_view,
+                wgpu::FilterMode::Linear,
+                texture_id,
+            );
+            texture_id
+        } else {
+            renderer.register_native_texture(device, &sampled_view, wgpu::FilterMode::Linear)
+        };
+
+        Self {
+            size_px,
+            texture_id,
+            render_view,
+            msaa_view,
+        }
+    }
+}
+
+/// Lay out the text in `shape` again with `fonts`.
+///
+/// Returns `false` for shapes that can't be painted in the lens, i.e. paint callbacks.
+fn relayout_text(shape: &mut Shape, fonts: &Fonts) -> bool {
+    match shape {
+        Shape::Vec(shapes) | Shape::Blend { shapes, mode: _ } => {
+            shapes.retain_mut(|shape| relayout_text(shape, fonts));
+            true
+        }
+        Shape::Text(text_shape) => {
+            text_shape.galley = fonts.layout_job((*text_shape.galley.job).clone());
+            true
+        }
+        Shape::Callback(_) => false,
+        _ => true,
+    }
+}
+
+/// Move `rect` so that it is inside of `area`, which must be at least as large.
+fn constrain(rect: Rect, area: Rect) -> Rect {
+    let min = rect.min.clamp(area.min, area.max - rect.size());
+    Rect::from_min_size(min, rect.size())
+}
+
+/// Show the texture of the lens at `rect`, with a frame around it.
+fn overlay(
+    egui_ctx: &Context,
+    rect: Rect,
+    texture_id: TextureId,
+    uv: Rect,
+    pixels_per_point: f32,
+) -> Vec<ClippedPrimitive> {
+    let stroke = Stroke::new(2.0, egui_ctx.style().visuals.selection.stroke.color);
+    let shape = Shape::Vec(vec![
+        Shape::image(texture_id, rect, uv, Color32::WHITE),
+        Shape::rect_stroke(rect.expand(stroke.width / 2.0), 0.0, stroke),
+    ]);
+    egui_ctx.tessellate(
+        vec![ClippedShape {
+            clip_rect: Rect::EVERYTHING,
+            shape,
+        }],
+        pixels_per_point,
+    )
+}
@@ -1,7 +1,12 @@
 mod app_icon;
 mod epi_integration;
 mod event_loop_context;
+#[cfg(feature = "gamepad")]
+mod gamepad;
+pub(crate) mod magnifier;
 pub mod run;
+pub mod splash;
+mod startup_error;
 
 /// File storage which can be used by native backends.
 #[cfg(feature = "persistence")]
@@ -9,6 +14,14 @@ pub mod file_storage;
 
 pub(crate) mod winit_integration;
 
+pub mod wake_source;
+
+#[cfg(feature = "hot_reload")]
+pub mod hot_reload;
+
+#[cfg(feature = "notifications")]
+pub mod notifications;
+
 #[cfg(feature = "glow")]
 mod glow_integration;
 
@@ -0,0 +1,216 @@
+//! Notifications posted to the operating system, see [`crate::Frame::show_notification`].
+
+use std::sync::Arc;
+
+use winit::event_loop::EventLoopProxy;
+
+use super::winit_integration::UserEvent;
+
+/// A notification to post to the operating system with [`crate::Frame::show_notification`].
+///
+/// Clicking the notification brings the app to the front, and calls [`Self::on_click`].
+///
+/// ```no_run
+/// # fn update(frame: &eframe::Frame) {
+/// frame.show_notification(
+///     eframe::Notification::new("Export finished", "Saved 42 images")
+///         .on_click(|ctx| ctx.data_mut(|d| d.insert_temp(eframe::egui::Id::new("show_exports"), true))),
+/// );
+/// # }
+/// ```
+#[derive(Default)]
+pub struct Notification {
+    /// The title, shown in bold.
+    pub title: String,
+
+    /// The text below the title.
+    pub body: String,
+
+    /// Shown next to the text.
+    ///
+    /// Only used on Linux and the BSDs, elsewhere the operating system shows the icon of the app.
+    pub icon: Option<Arc<egui::IconData>>,
+
+    /// Called on the UI thread if the user clicks the notification,
+    /// e.g. to show what the notification was about.
+    #[allow(clippy::type_complexity)]
+    pub on_click: Option<Box<dyn FnOnce(&egui::Context) + Send>>,
+}
+
+impl Notification {
+    pub fn new(title: impl Into<String>, body: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            body: body.into(),
+            ..Default::default()
+        }
+    }
+
+    /// See [`Self::icon`].
+    #[inline]
+    pub fn with_icon(mut self, icon: impl Into<Arc<egui::IconData>>) -> Self {
+        self.icon = Some(icon.into());
+        self
+    }
+
+    /// See [`Self::on_click`].
+    #[inline]
+    pub fn on_click(mut self, on_click: impl FnOnce(&egui::Context) + Send + 'static) -> Self {
+        self.on_click = Some(Box::new(on_click));
+        self
+    }
+}
+
+/// The user clicked a notification, sent to the event loop as [`UserEvent::NotificationClicked`].
+pub struct NotificationClick(Option<Box<dyn FnOnce(&egui::Context) + Send>>);
+
+impl std::fmt::Debug for NotificationClick {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NotificationClick").finish_non_exhaustive()
+    }
+}
+
+impl NotificationClick {
+    /// Bring the app to the front, and call the `on_click` callback of the notification.
+    pub(crate) fn handle(self, egui_ctx: &egui::Context) {
+        egui_ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(false));
+        egui_ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+        if let Some(on_click) = self.0 {
+            on_click(egui_ctx);
+        }
+        egui_ctx.request_repaint();
+    }
+}
+
+/// Post the notification, reporting clicks through `event_loop_proxy`.
+pub(crate) fn show(
+    notification: Notification,
+    event_loop_proxy: Option<EventLoopProxy<UserEvent>>,
+) {
+    let Notification {
+        title,
+        body,
+        icon,
+        on_click,
+    } = notification;
+
+    let mut click = Some(NotificationClick(on_click));
+    let on_click = move || {
+        if let (Some(proxy), Some(click)) = (&event_loop_proxy, click.take()) {
+            // Fails only if the event loop is gone, in which case there is no one to tell.
+            proxy.send_event(UserEvent::NotificationClicked(click)).ok();
+        }
+    };
+
+    if let Err(err) = post(title, body, icon, on_click) {
+        log::warn!("Failed to show notification: {err}");
+    }
+}
+
+/// Over D-Bus, waiting for clicks on a background thread until the notification is closed.
+#[cfg(all(
+    unix,
+    not(any(target_os = "macos", target_os = "ios", target_os = "android"))
+))]
+fn post(
+    title: String,
+    body: String,
+    icon: Option<Arc<egui::IconData>>,
+    mut on_click: impl FnMut() + Send + 'static,
+) -> Result<(), String> {
+    std::thread::Builder::new()
+        .name("eframe_notification".to_owned())
+        .spawn(move || {
+            let mut notification = notify_rust::Notification::new();
+            notification
+                .summary(&title)
+                .body(&body)
+                .action("default", "Open");
+            if let Some(icon) = icon {
+                match notify_rust::Image::from_rgba(
+                    icon.width as i32,
+                    icon.height as i32,
+                    icon.rgba.clone(),
+                ) {
+                    Ok(image) => {
+                        notification.image_data(image);
+                    }
+                    Err(err) => log::warn!("Bad notification icon: {err}"),
+                }
+            }
+
+            match notification.show() {
+                Ok(handle) => handle.wait_for_action(|action| {
+                    if action == "default" {
+                        on_click();
+                    }
+                }),
+                Err(err) => log::warn!("Failed to show notification: {err}"),
+            }
+        })
+        .map(|_| ())
+        .map_err(|err| err.to_string())
+}
+
+/// With `NSUserNotification`, waiting for a click on a background thread.
+#[cfg(target_os = "macos")]
+fn post(
+    title: String,
+    body: String,
+    _icon: Option<Arc<egui::IconData>>,
+    mut on_click: impl FnMut() + Send + 'static,
+) -> Result<(), String> {
+    std::thread::Builder::new()
+        .name("eframe_notification".to_owned())
+        .spawn(move || {
+            let mut options = mac_notification_sys::Notification::new();
+            options.wait_for_click(true);
+            match mac_notification_sys::send_notification(&title, None, &body, Some(&options)) {
+                Ok(mac_notification_sys::NotificationResponse::Click) => on_click(),
+                Ok(_) => {}
+                Err(err) => log::warn!("Failed to show notification: {err}"),
+            }
+        })
+        .map(|_| ())
+        .map_err(|err| err.to_string())
+}
+
+/// As a WinRT toast, which calls us back when it is clicked.
+///
+/// Toasts are shown on behalf of an installed app, so without one of our own they come from PowerShell.
+#[cfg(target_os = "windows")]
+fn post(
+    title: String,
+    body: String,
+    _icon: Option<Arc<egui::IconData>>,
+    mut on_click: impl FnMut() + Send + 'static,
+) -> Result<(), String> {
+    use tauri_winrt_notification::Toast;
+
+    Toast::new(Toast::POWERSHELL_APP_ID)
+        .title(&title)
+        .text1(&body)
+        .on_activated(move |_| {
+            on_click();
+            Ok(())
+        })
+        .show()
+        .map_err(|err| err.to_string())
+}
+
+#[cfg(not(any(
+    all(
+        unix,
+        not(any(target_os = "macos", target_os = "ios", target_os = "android"))
+    ),
+    target_os = "macos",
+    target_os = "windows",
+)))]
+fn post(
+    _title: String,
+    _body: String,
+    _icon: Option<Arc<egui::IconData>>,
+    _on_click: impl FnMut() + Send + 'static,
+) -> Result<(), String> {
+    Err("notifications are not supported on this platform".to_owned())
+}
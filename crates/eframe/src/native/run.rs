@@ -57,7 +57,12 @@ fn with_event_loop<R>(
 /// some events, but otherwise forwards events to the [`WinitApp`].
 struct WinitAppWrapper<T: WinitApp> {
     windows_next_repaint_times: HashMap<WindowId, Instant>,
+
+    /// When we last asked each window to repaint, for [`egui::Context::set_max_repaint_rate_of`].
+    windows_last_repaint_times: HashMap<WindowId, Instant>,
+
     winit_app: T,
+    monitors: egui_winit::MonitorTracker,
     return_result: Result<(), crate::Error>,
     run_and_return: bool,
 }
@@ -66,7 +71,9 @@ impl<T: WinitApp> WinitAppWrapper<T> {
     fn new(winit_app: T, run_and_return: bool) -> Self {
         Self {
             windows_next_repaint_times: HashMap::default(),
+            windows_last_repaint_times: HashMap::default(),
             winit_app,
+            monitors: Default::default(),
             return_result: Ok(()),
             run_and_return,
         }
@@ -81,6 +88,8 @@ impl<T: WinitApp> WinitAppWrapper<T> {
 
         log::trace!("event_result: {event_result:?}");
 
+        let event_result = event_result.map(|event_result| self.throttle(event_result));
+
         let combined_result = event_result.and_then(|event_result| {
             match event_result {
                 EventResult::Wait => {
@@ -107,12 +116,18 @@ impl<T: WinitApp> WinitAppWrapper<T> {
                     Ok(event_result)
                 }
                 EventResult::RepaintAt(window_id, repaint_time) => {
-                    self.windows_next_repaint_times.insert(
-                        window_id,
-                        self.windows_next_repaint_times
-                            .get(&window_id)
-                            .map_or(repaint_time, |last| (*last).min(repaint_time)),
-                    );
+                    let mut repaint_time = self
+                        .windows_next_repaint_times
+                        .get(&window_id)
+                        .map_or(repaint_time, |last| (*last).min(repaint_time));
+                    if let Some(earliest) = self.earliest_repaint_time(window_id) {
+                        if repaint_time < earliest {
+                            log::trace!("Throttling repaint of {window_id:?}");
+                            repaint_time = earliest;
+                        }
+                    }
+                    self.windows_next_repaint_times
+                        .insert(window_id, repaint_time);
                     Ok(event_result)
                 }
                 EventResult::Exit => {
@@ -146,6 +161,36 @@ impl<T: WinitApp> WinitAppWrapper<T> {
         self.check_redraw_requests(event_loop);
     }
 
+    /// Delay immediate repaints that would exceed the rate set with [`egui::Context::set_max_repaint_rate_of`].
+    ///
+    /// [`EventResult::RepaintAt`] is throttled in [`Self::handle_event_result`] instead,
+    /// after merging it with the repaint already scheduled for the window.
+    fn throttle(&self, event_result: EventResult) -> EventResult {
+        let (EventResult::RepaintNow(window_id) | EventResult::RepaintNext(window_id)) =
+            event_result
+        else {
+            return event_result;
+        };
+
+        match self.earliest_repaint_time(window_id) {
+            Some(earliest) if Instant::now() < earliest => {
+                log::trace!("Throttling repaint of {window_id:?}");
+                EventResult::RepaintAt(window_id, earliest)
+            }
+            _ => event_result,
+        }
+    }
+
+    /// The earliest time the window may repaint again, given the rate set with [`egui::Context::set_max_repaint_rate_of`].
+    fn earliest_repaint_time(&self, window_id: WindowId) -> Option<Instant> {
+        let min_interval = self.winit_app.egui_ctx().and_then(|ctx| {
+            let viewport_id = self.winit_app.viewport_id_from_window_id(window_id)?;
+            ctx.min_repaint_interval_of(viewport_id)
+        })?;
+        let last_repaint_time = self.windows_last_repaint_times.get(&window_id)?;
+        Some(*last_repaint_time + min_interval)
+    }
+
     fn check_redraw_requests(&mut self, event_loop: &ActiveEventLoop) {
         let now = Instant::now();
 
@@ -160,8 +205,10 @@ impl<T: WinitApp> WinitAppWrapper<T> {
                 if let Some(window) = self.winit_app.window(*window_id) {
                     log::trace!("request_redraw for {window_id:?}");
                     window.request_redraw();
+                    self.windows_last_repaint_times.insert(*window_id, now);
                 } else {
                     log::trace!("No window found for {window_id:?}");
+                    self.windows_last_repaint_times.remove(window_id);
                 }
                 false
             });
@@ -222,6 +269,8 @@ impl<T: WinitApp> ApplicationHandler<UserEvent> for WinitAppWrapper<T> {
             UserEvent::RequestRepaint { .. } => "UserEvent::RequestRepaint",
             #[cfg(feature = "accesskit")]
             UserEvent::AccessKitActionRequest(_) => "UserEvent::AccessKitActionRequest",
+            #[cfg(feature = "notifications")]
+            UserEvent::NotificationClicked(_) => "UserEvent::NotificationClicked",
         });
 
         event_loop_context::with_event_loop_context(event_loop, move || {
@@ -255,6 +304,13 @@ impl<T: WinitApp> ApplicationHandler<UserEvent> for WinitAppWrapper<T> {
                 UserEvent::AccessKitActionRequest(request) => {
                     self.winit_app.on_accesskit_event(request)
                 }
+                #[cfg(feature = "notifications")]
+                UserEvent::NotificationClicked(click) => {
+                    if let Some(egui_ctx) = self.winit_app.egui_ctx() {
+                        click.handle(egui_ctx);
+                    }
+                    Ok(EventResult::Wait)
+                }
             };
             self.handle_event_result(event_loop, event_result);
         });
@@ -265,6 +321,19 @@ impl<T: WinitApp> ApplicationHandler<UserEvent> for WinitAppWrapper<T> {
             log::trace!("Woke up to check next_repaint_time");
         }
 
+        if let Some(changes) = self.monitors.poll(event_loop) {
+            log::debug!(
+                "Monitors changed: {} connected, {} disconnected",
+                changes.connected.len(),
+                changes.disconnected.len()
+            );
+
+            event_loop_context::with_event_loop_context(event_loop, || {
+                let event_result = self.winit_app.on_monitors_changed(&changes);
+                self.handle_event_result(event_loop, event_result);
+            });
+        }
+
         self.check_redraw_requests(event_loop);
     }
 
@@ -282,6 +351,10 @@ impl<T: WinitApp> ApplicationHandler<UserEvent> for WinitAppWrapper<T> {
                 winit::event::WindowEvent::RedrawRequested => {
                     self.winit_app.run_ui_and_paint(event_loop, window_id)
                 }
+                winit::event::WindowEvent::Destroyed => {
+                    self.windows_last_repaint_times.remove(&window_id);
+                    self.winit_app.window_event(event_loop, window_id, event)
+                }
                 _ => self.winit_app.window_event(event_loop, window_id, event),
             };
 
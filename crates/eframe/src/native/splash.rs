@@ -0,0 +1,219 @@
+//! Show a splash screen while the app is created on another thread, see [`crate::run_native_with_splash`].
+
+use std::sync::{
+    mpsc::{self, Receiver},
+    Arc,
+};
+
+use egui::mutex::Mutex;
+
+use crate::{epi::DynError, App, Frame, Storage};
+
+/// Creates the app on a background thread, see [`crate::run_native_with_splash`].
+pub type BackgroundAppCreator =
+    Box<dyn FnOnce(&SplashContext) -> Result<Box<dyn App + Send>, DynError> + Send>;
+
+/// What the splash screen looks like, see [`crate::run_native_with_splash`].
+#[derive(Clone, Default)]
+pub struct SplashScreen {
+    /// Shown above the progress bar.
+    ///
+    /// Defaults to the title of the window.
+    pub title: Option<String>,
+
+    /// Shown above the title.
+    ///
+    /// Defaults to the icon of the window, see [`egui::ViewportBuilder::icon`].
+    pub logo: Option<Arc<egui::IconData>>,
+}
+
+/// Given to the [`BackgroundAppCreator`], to set up egui and report progress.
+#[derive(Clone)]
+pub struct SplashContext {
+    /// You can use this to e.g. set the fonts and style, or load textures.
+    pub egui_ctx: egui::Context,
+
+    progress: Arc<Mutex<Progress>>,
+}
+
+#[derive(Clone, Default)]
+struct Progress {
+    fraction: Option<f32>,
+    message: String,
+}
+
+impl SplashContext {
+    /// Show how far along the app creation is, in `0.0..=1.0`, and what is being done.
+    pub fn set_progress(&self, fraction: f32, message: impl Into<String>) {
+        *self.progress.lock() = Progress {
+            fraction: Some(fraction.clamp(0.0, 1.0)),
+            message: message.into(),
+        };
+        self.egui_ctx.request_repaint();
+    }
+}
+
+enum State {
+    Loading(Receiver<Result<Box<dyn App + Send>, DynError>>),
+    Running(Box<dyn App + Send>),
+    Failed(String),
+}
+
+/// Shows the splash screen until the app has been created, then hands over to it.
+pub struct SplashApp {
+    splash: SplashScreen,
+    ctx: SplashContext,
+    logo: Option<egui::TextureHandle>,
+    state: State,
+}
+
+impl SplashApp {
+    pub fn new(
+        egui_ctx: &egui::Context,
+        mut splash: SplashScreen,
+        default_title: &str,
+        default_logo: Option<Arc<egui::IconData>>,
+        create_app: BackgroundAppCreator,
+    ) -> Self {
+        splash.title.get_or_insert_with(|| default_title.to_owned());
+        let logo = splash.logo.clone().or(default_logo).map(|icon| {
+            let image = egui::ColorImage::from_rgba_unmultiplied(
+                [icon.width as usize, icon.height as usize],
+                &icon.rgba,
+            );
+            egui_ctx.load_texture("eframe_splash_logo", image, Default::default())
+        });
+
+        let ctx = SplashContext {
+            egui_ctx: egui_ctx.clone(),
+            progress: Default::default(),
+        };
+
+        let (sender, receiver) = mpsc::channel();
+        std::thread::Builder::new()
+            .name("eframe_app_creator".to_owned())
+            .spawn({
+                let ctx = ctx.clone();
+                move || {
+                    let app = create_app(&ctx);
+                    sender.send(app).ok();
+                    ctx.egui_ctx.request_repaint();
+                }
+            })
+            .expect("Failed to spawn thread");
+
+        Self {
+            splash,
+            ctx,
+            logo,
+            state: State::Loading(receiver),
+        }
+    }
+
+    fn splash_ui(&self, ctx: &egui::Context) {
+        let progress = self.ctx.progress.lock().clone();
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.vertical_centered(|ui| {
+                ui.add_space((ui.available_height() / 3.0 - 64.0).max(0.0));
+                if let Some(logo) = &self.logo {
+                    ui.add(egui::Image::new(logo).max_size(egui::Vec2::splat(128.0)));
+                }
+                if let Some(title) = &self.splash.title {
+                    ui.heading(title);
+                }
+                ui.add_space(8.0);
+
+                match &self.state {
+                    State::Failed(err) => {
+                        ui.colored_label(ui.visuals().error_fg_color, err);
+                    }
+                    _ => {
+                        let progress_bar = if let Some(fraction) = progress.fraction {
+                            egui::ProgressBar::new(fraction)
+                        } else {
+                            egui::ProgressBar::new(0.0).animate(true)
+                        };
+                        ui.add(progress_bar.desired_width(240.0));
+                        ui.weak(progress.message);
+                    }
+                }
+            });
+        });
+    }
+}
+
+impl App for SplashApp {
+    fn update(&mut self, ctx: &egui::Context, frame: &mut Frame) {
+        if let State::Loading(receiver) = &self.state {
+            match receiver.try_recv() {
+                Ok(Ok(app)) => {
+                    log::debug!("The app was created, hiding the splash screen");
+                    self.state = State::Running(app);
+                    self.logo = None; // Frees the texture
+                }
+                Ok(Err(err)) => {
+                    log::error!("Failed to create the app: {err}");
+                    self.state = State::Failed(format!("Failed to start: {err}"));
+                }
+                Err(mpsc::TryRecvError::Empty) => {}
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.state =
+                        State::Failed("Failed to start: the app creator panicked".to_owned());
+                }
+            }
+        }
+
+        if let State::Running(app) = &mut self.state {
+            app.update(ctx, frame);
+        } else {
+            self.splash_ui(ctx);
+        }
+    }
+
+    fn save(&mut self, storage: &mut dyn Storage) {
+        if let State::Running(app) = &mut self.state {
+            app.save(storage);
+        }
+    }
+
+    #[cfg(feature = "glow")]
+    fn on_exit(&mut self, gl: Option<&glow::Context>) {
+        if let State::Running(app) = &mut self.state {
+            app.on_exit(gl);
+        }
+    }
+
+    #[cfg(not(feature = "glow"))]
+    fn on_exit(&mut self) {
+        if let State::Running(app) = &mut self.state {
+            app.on_exit();
+        }
+    }
+
+    fn auto_save_interval(&self) -> std::time::Duration {
+        match &self.state {
+            State::Running(app) => app.auto_save_interval(),
+            _ => std::time::Duration::from_secs(30),
+        }
+    }
+
+    fn clear_color(&self, visuals: &egui::Visuals) -> [f32; 4] {
+        match &self.state {
+            State::Running(app) => app.clear_color(visuals),
+            _ => visuals.panel_fill.to_normalized_gamma_f32(),
+        }
+    }
+
+    fn persist_egui_memory(&self) -> bool {
+        match &self.state {
+            State::Running(app) => app.persist_egui_memory(),
+            _ => false, // Nothing has happened yet worth saving
+        }
+    }
+
+    fn raw_input_hook(&mut self, ctx: &egui::Context, raw_input: &mut egui::RawInput) {
+        if let State::Running(app) = &mut self.state {
+            app.raw_input_hook(ctx, raw_input);
+        }
+    }
+}
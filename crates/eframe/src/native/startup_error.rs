@@ -0,0 +1,113 @@
+//! Shown instead of the app if it fails to start, see [`crate::NativeOptions::startup_error_screen`].
+
+use crate::{epi::DynError, App, Frame, NativeOptions};
+
+/// The app, or the error screen if the app couldn't be created and the error screen is enabled.
+pub fn app_or_error_screen<'app>(
+    app: Result<Box<dyn 'app + App>, DynError>,
+    app_name: &str,
+    native_options: &NativeOptions,
+) -> crate::Result<Box<dyn 'app + App>> {
+    match app {
+        Ok(app) => Ok(app),
+        Err(err) if native_options.startup_error_screen => {
+            log::error!("Failed to create the app: {err}");
+            Ok(Box::new(StartupErrorApp::new(app_name, &*err)))
+        }
+        Err(err) => Err(crate::Error::AppCreation(err)),
+    }
+}
+
+struct StartupErrorApp {
+    app_name: String,
+    summary: String,
+    details: String,
+}
+
+impl StartupErrorApp {
+    fn new(app_name: &str, err: &(dyn std::error::Error + 'static)) -> Self {
+        use std::fmt::Write as _;
+
+        let mut details = format!("{app_name} failed to start: {err}\n");
+        let mut source = err.source();
+        while let Some(err) = source {
+            writeln!(details, "Caused by: {err}").ok();
+            source = err.source();
+        }
+        writeln!(
+            details,
+            "\neframe {} on {} ({})",
+            env!("CARGO_PKG_VERSION"),
+            std::env::consts::OS,
+            std::env::consts::ARCH
+        )
+        .ok();
+
+        Self {
+            app_name: app_name.to_owned(),
+            summary: err.to_string(),
+            details,
+        }
+    }
+}
+
+impl App for StartupErrorApp {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut Frame) {
+        egui::TopBottomPanel::bottom("startup_error_buttons").show(ctx, |ui| {
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                if ui.button("Quit").clicked() {
+                    ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+                }
+                if ui
+                    .button("Retry")
+                    .on_hover_text("Restart the app")
+                    .clicked()
+                {
+                    restart();
+                    ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+                }
+                if ui.button("📋 Copy details").clicked() {
+                    ctx.copy_text(self.details.clone());
+                }
+            });
+            ui.add_space(4.0);
+        });
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.heading(
+                egui::RichText::new(format!("⚠ {} failed to start", self.app_name))
+                    .color(ui.visuals().error_fg_color),
+            );
+            ui.add_space(8.0);
+            ui.label(&self.summary);
+            ui.add_space(8.0);
+            egui::CollapsingHeader::new("Details").show(ui, |ui| {
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    let mut details = self.details.as_str(); // Selectable, but not editable
+                    ui.add(
+                        egui::TextEdit::multiline(&mut details)
+                            .code_editor()
+                            .desired_width(f32::INFINITY),
+                    );
+                });
+            });
+        });
+    }
+
+    fn persist_egui_memory(&self) -> bool {
+        false // Don't overwrite the memory of the real app
+    }
+}
+
+/// Start a new instance of the app, with the same arguments.
+fn restart() {
+    let result = std::env::current_exe().and_then(|exe| {
+        std::process::Command::new(exe)
+            .args(std::env::args_os().skip(1))
+            .spawn()
+    });
+    if let Err(err) = result {
+        log::error!("Failed to restart the app: {err}");
+    }
+}
@@ -0,0 +1,96 @@
+//! Repaint the app when data arrives from outside of it, see [`WakeSource`].
+
+use std::{io::Read, sync::mpsc::Receiver};
+
+use egui::ViewportId;
+
+/// Something outside of the app that should make it repaint when there is new data,
+/// e.g. a network socket, a pipe or a channel.
+///
+/// Each wake source is waited on by its own background thread, which requests a repaint
+/// of the root viewport every time the source has something new.
+/// In between, the app uses no CPU at all, so apps that only change when data arrives
+/// (e.g. a network dashboard) can be fully reactive without polling or a thread of their own
+/// calling [`egui::Context::request_repaint`].
+///
+/// See [`crate::NativeOptions::wake_sources`].
+pub struct WakeSource {
+    name: String,
+    wait: Box<dyn FnMut() -> bool + Send>,
+}
+
+impl WakeSource {
+    /// `wait` should block until there is something new for the app and then return `true`,
+    /// or return `false` once there will never be anything new again.
+    ///
+    /// It is called over and over on a background thread,
+    /// and each time it returns a repaint is requested.
+    pub fn new(name: impl Into<String>, wait: impl FnMut() -> bool + Send + 'static) -> Self {
+        Self {
+            name: name.into(),
+            wait: Box::new(wait),
+        }
+    }
+
+    /// Repaint whenever a message is sent on `receiver`.
+    ///
+    /// The messages are forwarded to the returned receiver,
+    /// which you can drain with [`Receiver::try_iter`] in [`crate::App::update`].
+    pub fn channel<T: Send + 'static>(
+        name: impl Into<String>,
+        receiver: Receiver<T>,
+    ) -> (Self, Receiver<T>) {
+        let (sender, forwarded) = std::sync::mpsc::channel();
+        let source = Self::new(name, move || match receiver.recv() {
+            Ok(message) => sender.send(message).is_ok(),
+            Err(std::sync::mpsc::RecvError) => false, // All senders are gone
+        });
+        (source, forwarded)
+    }
+
+    /// Repaint whenever data can be read from `reader`,
+    /// e.g. a [`std::net::TcpStream`], a pipe, or the stdout of a child process.
+    ///
+    /// `reader` is read on the background thread, and the data is forwarded to the returned receiver
+    /// in chunks as they arrive. Reading stops (with one last repaint) at the end of the stream or on an error.
+    pub fn reader(
+        name: impl Into<String>,
+        mut reader: impl Read + Send + 'static,
+    ) -> (Self, Receiver<Vec<u8>>) {
+        let name = name.into();
+        let (sender, chunks) = std::sync::mpsc::channel();
+        let mut buffer = vec![0; 64 * 1024];
+        let source = Self::new(name.clone(), move || loop {
+            match reader.read(&mut buffer) {
+                Ok(0) => return false,
+                Ok(n) => return sender.send(buffer[..n].to_vec()).is_ok(),
+                Err(err) if err.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(err) => {
+                    log::warn!("Failed to read from wake source {name:?}: {err}");
+                    return false;
+                }
+            }
+        });
+        (source, chunks)
+    }
+}
+
+/// Start waiting on each of the wake sources on a background thread.
+pub(crate) fn spawn(egui_ctx: &egui::Context, sources: Vec<WakeSource>) {
+    for WakeSource { name, mut wait } in sources {
+        let egui_ctx = egui_ctx.clone();
+        let result = std::thread::Builder::new()
+            .name(format!("eframe wake source {name}"))
+            .spawn(move || {
+                while wait() {
+                    egui_ctx.request_repaint_of(ViewportId::ROOT);
+                }
+                // Let the app see that the source is done, e.g. a closed connection:
+                egui_ctx.request_repaint_of(ViewportId::ROOT);
+                log::debug!("Wake source {name:?} is done");
+            });
+        if let Err(err) = result {
+            log::warn!("Failed to spawn a thread for a wake source: {err}");
+        }
+    }
+}
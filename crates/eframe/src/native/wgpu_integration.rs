@@ -26,7 +26,7 @@ use winit_integration::UserEvent;
 
 use crate::{
     native::{epi_integration::EpiIntegration, winit_integration::EventResult},
-    App, AppCreator, CreationContext, NativeOptions, Result, Storage,
+    App, AppCreator, CreationContext, NativeOptions, PresentationMode, Result, Storage,
 };
 
 use super::{epi_integration, event_loop_context, winit_integration, winit_integration::WinitApp};
@@ -57,6 +57,12 @@ struct WgpuWinitRunning<'app> {
 
     /// Wrapped in an `Rc<RefCell<…>>` so it can be re-entrantly shared via a weak-pointer.
     shared: Rc<RefCell<SharedState>>,
+
+    /// The present mode and desired maximum frame latency for [`PresentationMode::PowerSaving`].
+    power_saving_presentation: (egui_wgpu::wgpu::PresentMode, Option<u32>),
+
+    /// See [`egui_wgpu::WgpuConfiguration::threaded_deferred_viewports`].
+    threaded_deferred_viewports: bool,
 }
 
 /// Everything needed by the immediate viewport renderer.\
@@ -70,6 +76,9 @@ pub struct SharedState {
     painter: egui_wgpu::winit::Painter,
     viewport_from_window: HashMap<WindowId, ViewportId>,
     focused_viewport: Option<ViewportId>,
+
+    /// Moves drag-and-drop between windows.
+    dnd_forwarder: egui_winit::DndForwarder,
 }
 
 pub type Viewports = ViewportIdMap<Viewport>;
@@ -85,6 +94,11 @@ pub struct Viewport {
     /// `None` for sync viewports.
     viewport_ui_cb: Option<Arc<DeferredViewportUiCallback>>,
 
+    /// Paints this viewport, if it is a deferred viewport painted on a thread of its own.
+    ///
+    /// Then the surface of the window is owned by this thread rather than the shared painter.
+    render_thread: Option<egui_wgpu::winit::ViewportRenderThread>,
+
     /// Window surface state that's initialized when the app starts running via a Resumed event
     /// and on Android will also be destroyed if the application is paused.
     window: Option<Arc<Window>>,
@@ -132,13 +146,18 @@ impl<'app> WgpuWinitApp<'app> {
             ..
         } = &mut *shared;
 
-        for viewport in viewports.values_mut() {
-            viewport.initialize_window(
-                event_loop,
-                &running.integration.egui_ctx,
-                viewport_from_window,
-                painter,
-            );
+        let viewport_ids: Vec<ViewportId> = viewports.keys().copied().collect();
+        for viewport_id in viewport_ids {
+            let modal_parent = modal_parent_window(viewports, viewport_id);
+            if let Some(viewport) = viewports.get_mut(&viewport_id) {
+                viewport.initialize_window(
+                    event_loop,
+                    &running.integration.egui_ctx,
+                    viewport_from_window,
+                    painter,
+                    modal_parent.as_deref(),
+                );
+            }
         }
     }
 
@@ -160,7 +179,7 @@ impl<'app> WgpuWinitApp<'app> {
             None,
             painter,
         )
-        .initialize_window(event_loop, egui_ctx, viewport_from_window, painter);
+        .initialize_window(event_loop, egui_ctx, viewport_from_window, painter, None);
     }
 
     #[cfg(target_os = "android")]
@@ -183,14 +202,31 @@ impl<'app> WgpuWinitApp<'app> {
     ) -> crate::Result<&mut WgpuWinitRunning<'app>> {
         crate::profile_function!();
 
+        let native_options = &self.native_options;
+        let power_saving_presentation = (
+            native_options.wgpu_options.present_mode,
+            native_options
+                .desired_maximum_frame_latency
+                .or(native_options.wgpu_options.desired_maximum_frame_latency),
+        );
+        let (present_mode, desired_maximum_frame_latency) =
+            wgpu_presentation(native_options.presentation_mode, power_saving_presentation);
+        let wgpu_options = egui_wgpu::WgpuConfiguration {
+            present_mode,
+            desired_maximum_frame_latency,
+            ..native_options.wgpu_options.clone()
+        };
+
+        let depth_format = egui_wgpu::depth_format_from_bits(
+            self.native_options.depth_buffer,
+            self.native_options.stencil_buffer,
+        );
+
         #[allow(unsafe_code, unused_mut, unused_unsafe)]
         let mut painter = egui_wgpu::winit::Painter::new(
-            self.native_options.wgpu_options.clone(),
+            wgpu_options,
             self.native_options.multisampling.max(1) as _,
-            egui_wgpu::depth_format_from_bits(
-                self.native_options.depth_buffer,
-                self.native_options.stencil_buffer,
-            ),
+            depth_format,
             self.native_options.viewport.transparent.unwrap_or(false),
             self.native_options.dithering,
         );
@@ -204,7 +240,7 @@ impl<'app> WgpuWinitApp<'app> {
 
         let wgpu_render_state = painter.render_state();
 
-        let integration = EpiIntegration::new(
+        let mut integration = EpiIntegration::new(
             egui_ctx.clone(),
             &window,
             &self.app_name,
@@ -215,8 +251,16 @@ impl<'app> WgpuWinitApp<'app> {
             #[cfg(feature = "glow")]
             None,
             wgpu_render_state.clone(),
+            #[cfg(feature = "notifications")]
+            self.repaint_proxy.lock().clone(),
         );
 
+        if integration.magnifier.is_some() && depth_format.is_some() {
+            // The lens is painted with `Renderer::render_to_texture`, which has no depth buffer.
+            log::warn!("The magnifier is not available with a depth or stencil buffer");
+            integration.magnifier = None;
+        }
+
         {
             let event_loop_proxy = self.repaint_proxy.clone();
 
@@ -236,6 +280,14 @@ impl<'app> WgpuWinitApp<'app> {
             });
         }
 
+        super::wake_source::spawn(
+            &egui_ctx,
+            std::mem::take(&mut self.native_options.wake_sources),
+        );
+
+        #[cfg(feature = "hot_reload")]
+        integration.set_hot_reload(&mut self.native_options);
+
         #[allow(unused_mut)] // used for accesskit
         let mut egui_winit = egui_winit::State::new(
             egui_ctx.clone(),
@@ -268,14 +320,18 @@ impl<'app> WgpuWinitApp<'app> {
         };
         let app = {
             crate::profile_scope!("user_app_creator");
-            app_creator(&cc).map_err(crate::Error::AppCreation)?
+            super::startup_error::app_or_error_screen(
+                app_creator(&cc),
+                &self.app_name,
+                &self.native_options,
+            )?
         };
 
         let mut viewport_from_window = HashMap::default();
         viewport_from_window.insert(window.id(), ViewportId::ROOT);
 
         let mut info = ViewportInfo::default();
-        egui_winit::update_viewport_info(&mut info, &egui_ctx, &window, true);
+        egui_winit::update_viewport_info(&mut info, &egui_ctx, ViewportId::ROOT, &window, true);
 
         let mut viewports = Viewports::default();
         viewports.insert(
@@ -288,6 +344,7 @@ impl<'app> WgpuWinitApp<'app> {
                 info,
                 actions_requested: Default::default(),
                 viewport_ui_cb: None,
+                render_thread: None,
                 window: Some(window),
                 egui_winit: Some(egui_winit),
             },
@@ -299,6 +356,7 @@ impl<'app> WgpuWinitApp<'app> {
             viewports,
             painter,
             focused_viewport: Some(ViewportId::ROOT),
+            dnd_forwarder: Default::default(),
         }));
 
         {
@@ -319,6 +377,11 @@ impl<'app> WgpuWinitApp<'app> {
             integration,
             app,
             shared,
+            power_saving_presentation,
+            threaded_deferred_viewports: self
+                .native_options
+                .wgpu_options
+                .threaded_deferred_viewports,
         }))
     }
 }
@@ -355,6 +418,16 @@ impl<'app> WinitApp for WgpuWinitApp<'app> {
         )
     }
 
+    fn viewport_id_from_window_id(&self, id: WindowId) -> Option<ViewportId> {
+        self.running
+            .as_ref()?
+            .shared
+            .borrow()
+            .viewport_from_window
+            .get(&id)
+            .copied()
+    }
+
     fn save_and_destroy(&mut self) {
         if let Some(mut running) = self.running.take() {
             running.save_and_destroy();
@@ -384,7 +457,7 @@ impl<'app> WinitApp for WgpuWinitApp<'app> {
             running
         } else {
             let storage = if let Some(file) = &self.native_options.persistence_path {
-                epi_integration::create_storage_with_file(file)
+                epi_integration::create_storage_with_file(file, &self.native_options.autosave)
             } else {
                 epi_integration::create_storage(
                     self.native_options
@@ -392,6 +465,7 @@ impl<'app> WinitApp for WgpuWinitApp<'app> {
                         .app_id
                         .as_ref()
                         .unwrap_or(&self.app_name),
+                    &self.native_options.autosave,
                 )
             };
             let egui_ctx = winit_integration::create_egui_context(storage.as_deref());
@@ -460,6 +534,26 @@ impl<'app> WinitApp for WgpuWinitApp<'app> {
         }
     }
 
+    fn on_monitors_changed(
+        &mut self,
+        changes: &egui_winit::MonitorChanges,
+    ) -> crate::Result<EventResult> {
+        if let Some(running) = &mut self.running {
+            let mut shared = running.shared.borrow_mut();
+            for viewport in shared.viewports.values_mut() {
+                if let (Some(window), Some(egui_winit)) =
+                    (&viewport.window, &mut viewport.egui_winit)
+                {
+                    let policy = viewport.builder.monitor_relocation.unwrap_or_default();
+                    egui_winit.on_monitors_changed(window, changes, policy);
+                    window.request_redraw();
+                }
+            }
+        }
+
+        Ok(EventResult::Wait)
+    }
+
     #[cfg(feature = "accesskit")]
     fn on_accesskit_event(&mut self, event: accesskit_winit::Event) -> crate::Result<EventResult> {
         if let Some(running) = &mut self.running {
@@ -505,6 +599,41 @@ impl<'app> WgpuWinitRunning<'app> {
         shared.painter.destroy();
     }
 
+    /// Swap in a new app, keeping the state of the old one, see [`crate::HotReload`].
+    #[cfg(feature = "hot_reload")]
+    fn reload_app_if_requested(&mut self) {
+        let Some(hot_reload) = &mut self.integration.hot_reload else {
+            return;
+        };
+        if !hot_reload.take_request() {
+            return;
+        }
+
+        let shared = self.shared.borrow();
+        let Some(window) = shared
+            .viewports
+            .get(&ViewportId::ROOT)
+            .and_then(|viewport| viewport.window.clone())
+        else {
+            return;
+        };
+
+        let storage = super::hot_reload::HotReload::save(self.app.as_mut());
+        let cc = CreationContext {
+            egui_ctx: self.integration.egui_ctx.clone(),
+            integration_info: self.integration.frame.info().clone(),
+            storage: Some(&storage),
+            #[cfg(feature = "glow")]
+            gl: None,
+            #[cfg(feature = "glow")]
+            get_proc_address: None,
+            wgpu_render_state: shared.painter.render_state(),
+            raw_display_handle: window.display_handle().map(|h| h.as_raw()),
+            raw_window_handle: window.window_handle().map(|h| h.as_raw()),
+        };
+        hot_reload.reload(&mut self.app, &cc);
+    }
+
     /// This is called both for the root viewport, and all deferred viewports
     fn run_ui_and_paint(&mut self, window_id: WindowId) -> Result<EventResult> {
         crate::profile_function!();
@@ -519,6 +648,11 @@ impl<'app> WgpuWinitRunning<'app> {
             return Ok(EventResult::Wait);
         };
 
+        #[cfg(feature = "hot_reload")]
+        if viewport_id == ViewportId::ROOT {
+            self.reload_app_if_requested();
+        }
+
         #[cfg(feature = "puffin")]
         puffin::GlobalProfiler::lock().new_frame();
 
@@ -526,6 +660,8 @@ impl<'app> WgpuWinitRunning<'app> {
             app,
             integration,
             shared,
+            power_saving_presentation,
+            threaded_deferred_viewports,
         } = self;
 
         let mut frame_timer = crate::stopwatch::Stopwatch::new();
@@ -562,6 +698,7 @@ impl<'app> WgpuWinitRunning<'app> {
 
             let Viewport {
                 viewport_ui_cb,
+                render_thread,
                 window,
                 egui_winit,
                 info,
@@ -573,16 +710,40 @@ impl<'app> WgpuWinitRunning<'app> {
             let Some(window) = window else {
                 return Ok(EventResult::Wait);
             };
-            egui_winit::update_viewport_info(info, &integration.egui_ctx, window, false);
+            egui_winit::update_viewport_info(
+                info,
+                &integration.egui_ctx,
+                viewport_id,
+                window,
+                false,
+            );
 
-            {
+            if render_thread.is_none() {
                 crate::profile_scope!("set_window");
                 pollster::block_on(painter.set_window(viewport_id, Some(window.clone())))?;
+
+                if *threaded_deferred_viewports && viewport_ui_cb.is_some() {
+                    *render_thread =
+                        spawn_render_thread(&integration.egui_ctx, viewport_id, painter);
+                }
             }
 
             let Some(egui_winit) = egui_winit.as_mut() else {
                 return Ok(EventResult::Wait);
             };
+
+            if let Some(render_thread) = render_thread {
+                for image in render_thread.take_screenshots() {
+                    egui_winit
+                        .egui_input_mut()
+                        .events
+                        .push(egui::Event::Screenshot {
+                            viewport_id,
+                            image: image.into(),
+                        });
+                }
+            }
+
             let mut raw_input = egui_winit.take_egui_input(window);
 
             integration.pre_update();
@@ -614,6 +775,18 @@ impl<'app> WgpuWinitRunning<'app> {
             ..
         } = &mut *shared_mut;
 
+        if let Some(presentation_mode) = integration.presentation_mode_change() {
+            let (present_mode, desired_maximum_frame_latency) =
+                wgpu_presentation(presentation_mode, *power_saving_presentation);
+            painter.set_presentation(present_mode, desired_maximum_frame_latency);
+            for render_thread in viewports
+                .values()
+                .filter_map(|vp| vp.render_thread.as_ref())
+            {
+                render_thread.set_presentation(present_mode, desired_maximum_frame_latency);
+            }
+        }
+
         let FullOutput {
             platform_output,
             textures_delta,
@@ -629,6 +802,7 @@ impl<'app> WgpuWinitRunning<'app> {
         };
 
         viewport.info.events.clear(); // they should have been processed
+        viewport.info.command_results.clear();
 
         let Viewport {
             window: Some(window),
@@ -641,28 +815,79 @@ impl<'app> WgpuWinitRunning<'app> {
 
         egui_winit.handle_platform_output(window, platform_output);
 
-        let clipped_primitives = egui_ctx.tessellate(shapes, pixels_per_point);
+        let lens = if viewport_id == ViewportId::ROOT {
+            integration.magnifier.as_mut().and_then(|magnifier| {
+                magnifier.prepare(
+                    egui_ctx,
+                    &shapes,
+                    window.inner_size().into(),
+                    pixels_per_point,
+                )
+            })
+        } else {
+            None
+        };
+
+        let mut clipped_primitives = egui_ctx.tessellate(shapes, pixels_per_point);
+
+        let clear_color = app.clear_color(&egui_ctx.style().visuals);
+
+        if let (Some(lens), Some(magnifier), Some(render_state)) =
+            (&lens, &mut integration.magnifier, painter.render_state())
+        {
+            clipped_primitives.extend(magnifier.paint_wgpu(
+                egui_ctx,
+                &render_state,
+                lens,
+                clear_color,
+                &textures_delta,
+                pixels_per_point,
+            ));
+        }
 
         let screenshot_requested = viewport
             .actions_requested
             .take(&ActionRequested::Screenshot)
             .is_some();
-        let (vsync_secs, screenshot) = painter.paint_and_update_textures(
-            viewport_id,
-            pixels_per_point,
-            app.clear_color(&egui_ctx.style().visuals),
-            &clipped_primitives,
-            &textures_delta,
-            screenshot_requested,
-        );
-        if let Some(screenshot) = screenshot {
-            egui_winit
-                .egui_input_mut()
-                .events
-                .push(egui::Event::Screenshot {
-                    viewport_id,
-                    image: screenshot.into(),
-                });
+        let vsync_secs = if let Some(render_thread) = &viewport.render_thread {
+            // Upload the textures now, so the other viewports can use them in their next frame:
+            painter.update_textures(&textures_delta);
+            render_thread.paint(egui_wgpu::winit::ViewportFrame {
+                pixels_per_point,
+                clear_color,
+                clipped_primitives,
+                textures_delta: egui::TexturesDelta {
+                    free: textures_delta.free,
+                    ..Default::default()
+                },
+                capture: screenshot_requested,
+            });
+            0.0 // Any vsync-waiting happens on the render thread.
+        } else {
+            let (vsync_secs, screenshot) = painter.paint_and_update_textures(
+                viewport_id,
+                pixels_per_point,
+                clear_color,
+                &clipped_primitives,
+                &textures_delta,
+                screenshot_requested,
+            );
+
+            if let Some(screenshot) = screenshot {
+                egui_winit
+                    .egui_input_mut()
+                    .events
+                    .push(egui::Event::Screenshot {
+                        viewport_id,
+                        image: screenshot.into(),
+                    });
+            }
+
+            vsync_secs
+        };
+        integration.report_input_latency(egui_winit.input_time());
+        if let Some(render_state) = painter.render_state() {
+            integration.report_texture_memory(render_state.renderer.read().texture_memory());
         }
 
         for action in viewport.actions_requested.drain() {
@@ -677,15 +902,7 @@ impl<'app> WgpuWinitRunning<'app> {
                     egui_winit.egui_input_mut().events.push(egui::Event::Copy);
                 }
                 ActionRequested::Paste => {
-                    if let Some(contents) = egui_winit.clipboard_text() {
-                        let contents = contents.replace("\r\n", "\n");
-                        if !contents.is_empty() {
-                            egui_winit
-                                .egui_input_mut()
-                                .events
-                                .push(egui::Event::Paste(contents));
-                        }
-                    }
+                    egui_winit.push_paste_event();
                 }
             }
         }
@@ -776,7 +993,12 @@ impl<'app> WgpuWinitRunning<'app> {
                         NonZeroU32::new(physical_size.height),
                     ) {
                         repaint_asap = true;
-                        shared.painter.on_window_resized(viewport_id, width, height);
+                        let SharedState {
+                            viewports, painter, ..
+                        } = &mut *shared;
+                        if let Some(viewport) = viewports.get(&viewport_id) {
+                            viewport.on_window_resized(painter, width, height);
+                        }
                     }
                 }
             }
@@ -808,8 +1030,33 @@ impl<'app> WgpuWinitRunning<'app> {
             _ => {}
         };
 
-        let event_response = viewport_id
-            .and_then(|viewport_id| {
+        if let Some(modal) = viewport_id.and_then(|id| modal_child_window(&shared.viewports, id)) {
+            if winit_integration::is_blocked_by_modal(event, &modal) {
+                return EventResult::Wait;
+            }
+        }
+
+        let forwarded = viewport_id.and_then(|viewport_id| {
+            let shared = &mut *shared;
+            let mut windows: Vec<_> = shared
+                .viewports
+                .iter_mut()
+                .filter_map(|(id, viewport)| {
+                    Some((
+                        *id,
+                        viewport.window.as_deref()?,
+                        viewport.egui_winit.as_mut()?,
+                    ))
+                })
+                .collect();
+            shared
+                .dnd_forwarder
+                .on_window_event(viewport_id, &mut windows, event)
+        });
+
+        let event_response = forwarded
+            .or_else(|| {
+                let viewport_id = viewport_id?;
                 shared.viewports.get_mut(&viewport_id).and_then(|viewport| {
                     Some(integration.on_window_event(
                         viewport.window.as_deref()?,
@@ -835,13 +1082,30 @@ impl<'app> WgpuWinitRunning<'app> {
 }
 
 impl Viewport {
+    /// Resize the surface, which is owned by [`Self::render_thread`] if there is one.
+    fn on_window_resized(
+        &self,
+        painter: &mut egui_wgpu::winit::Painter,
+        width: NonZeroU32,
+        height: NonZeroU32,
+    ) {
+        if let Some(render_thread) = &self.render_thread {
+            render_thread.on_window_resized(width, height);
+        } else {
+            painter.on_window_resized(self.ids.this, width, height);
+        }
+    }
+
     /// Create winit window, if needed.
+    ///
+    /// `modal_parent` is the window of the parent, if this is a modal viewport.
     fn initialize_window(
         &mut self,
         event_loop: &ActiveEventLoop,
         egui_ctx: &egui::Context,
         windows_id: &mut HashMap<WindowId, ViewportId>,
         painter: &mut egui_wgpu::winit::Painter,
+        modal_parent: Option<&Window>,
     ) {
         if self.window.is_some() {
             return; // we already have one
@@ -851,7 +1115,30 @@ impl Viewport {
 
         let viewport_id = self.ids.this;
 
-        match egui_winit::create_window(egui_ctx, event_loop, &self.builder) {
+        let window = if let Some(parent) = modal_parent {
+            let window_attributes = winit_integration::with_modal_parent(
+                egui_winit::create_winit_window_attributes(
+                    egui_ctx,
+                    viewport_id,
+                    event_loop,
+                    self.builder.clone(),
+                ),
+                parent,
+            );
+            event_loop.create_window(window_attributes).map(|window| {
+                egui_winit::apply_viewport_builder_to_window(
+                    egui_ctx,
+                    viewport_id,
+                    &window,
+                    &self.builder,
+                );
+                window
+            })
+        } else {
+            egui_winit::create_window(egui_ctx, viewport_id, event_loop, &self.builder)
+        };
+
+        match window {
             Ok(window) => {
                 windows_id.insert(window.id(), viewport_id);
 
@@ -872,7 +1159,13 @@ impl Viewport {
                     painter.max_texture_side(),
                 ));
 
-                egui_winit::update_viewport_info(&mut self.info, egui_ctx, &window, true);
+                egui_winit::update_viewport_info(
+                    &mut self.info,
+                    egui_ctx,
+                    viewport_id,
+                    &window,
+                    true,
+                );
                 self.window = Some(window);
             }
             Err(err) => {
@@ -882,6 +1175,34 @@ impl Viewport {
     }
 }
 
+/// Move the surface of a deferred viewport off the shared painter, onto a thread of its own.
+fn spawn_render_thread(
+    egui_ctx: &egui::Context,
+    viewport_id: ViewportId,
+    painter: &mut egui_wgpu::winit::Painter,
+) -> Option<egui_wgpu::winit::ViewportRenderThread> {
+    crate::profile_function!();
+
+    let viewport_painter = painter.split_off_viewport(viewport_id)?;
+
+    // Screenshots are handed to egui when the viewport runs its next pass:
+    let egui_ctx = egui_ctx.clone();
+    let on_screenshot = move || egui_ctx.request_repaint_of(viewport_id);
+
+    match egui_wgpu::winit::ViewportRenderThread::spawn(
+        viewport_id,
+        viewport_painter,
+        on_screenshot,
+    ) {
+        Ok(render_thread) => Some(render_thread),
+        Err(err) => {
+            // The surface went with the painter, so it will be recreated by the next `set_window`.
+            log::error!("Failed to spawn render thread for viewport {viewport_id:?}: {err}");
+            None
+        }
+    }
+}
+
 fn create_window(
     egui_ctx: &egui::Context,
     event_loop: &ActiveEventLoop,
@@ -892,14 +1213,15 @@ fn create_window(
 
     let window_settings = epi_integration::load_window_settings(storage);
     let viewport_builder = epi_integration::viewport_builder(
-        egui_ctx.zoom_factor(),
+        egui_ctx.zoom_factor_of(ViewportId::ROOT),
         event_loop,
         native_options,
         window_settings,
     )
     .with_visible(false); // Start hidden until we render the first frame to fix white flash on startup (https://github.com/emilk/egui/pull/3631)
 
-    let window = egui_winit::create_window(egui_ctx, event_loop, &viewport_builder)?;
+    let window =
+        egui_winit::create_window(egui_ctx, ViewportId::ROOT, event_loop, &viewport_builder)?;
     epi_integration::apply_window_settings(&window, window_settings);
     Ok((window, viewport_builder))
 }
@@ -936,14 +1258,20 @@ fn render_immediate_viewport(
         );
         if viewport.window.is_none() {
             event_loop_context::with_current_event_loop(|event_loop| {
-                viewport.initialize_window(event_loop, egui_ctx, viewport_from_window, painter);
+                viewport.initialize_window(
+                    event_loop,
+                    egui_ctx,
+                    viewport_from_window,
+                    painter,
+                    None,
+                );
             });
         }
 
         let (Some(window), Some(egui_winit)) = (&viewport.window, &mut viewport.egui_winit) else {
             return;
         };
-        egui_winit::update_viewport_info(&mut viewport.info, egui_ctx, window, false);
+        egui_winit::update_viewport_info(&mut viewport.info, egui_ctx, ids.this, window, false);
 
         let mut input = egui_winit.take_egui_input(window);
         input.viewports = viewports
@@ -984,6 +1312,7 @@ fn render_immediate_viewport(
         return;
     };
     viewport.info.events.clear(); // they should have been processed
+    viewport.info.command_results.clear();
     let (Some(egui_winit), Some(window)) = (&mut viewport.egui_winit, &viewport.window) else {
         return;
     };
@@ -1053,6 +1382,11 @@ fn handle_viewport_output(
         },
     ) in viewport_output.clone()
     {
+        if class == ViewportClass::Texture {
+            // Texture viewports are run and painted by the user, not by us.
+            continue;
+        }
+
         let ids = ViewportIdPair::from_self_and_parent(viewport_id, parent);
 
         let viewport =
@@ -1065,6 +1399,7 @@ fn handle_viewport_output(
 
             egui_winit::process_viewport_commands(
                 egui_ctx,
+                viewport_id,
                 &mut viewport.info,
                 std::mem::take(&mut viewport.deferred_commands),
                 window,
@@ -1079,7 +1414,7 @@ fn handle_viewport_output(
                         NonZeroU32::new(new_inner_size.width),
                         NonZeroU32::new(new_inner_size.height),
                     ) {
-                        painter.on_window_resized(viewport_id, width, height);
+                        viewport.on_window_resized(painter, width, height);
                     }
                 }
             }
@@ -1089,6 +1424,41 @@ fn handle_viewport_output(
     remove_viewports_not_in(viewports, painter, viewport_from_window, viewport_output);
 }
 
+/// The present mode and desired maximum frame latency for `presentation_mode`,
+/// given those for [`PresentationMode::PowerSaving`].
+fn wgpu_presentation(
+    presentation_mode: PresentationMode,
+    power_saving: (egui_wgpu::wgpu::PresentMode, Option<u32>),
+) -> (egui_wgpu::wgpu::PresentMode, Option<u32>) {
+    match presentation_mode {
+        PresentationMode::PowerSaving => power_saving,
+        PresentationMode::LowLatency => (egui_wgpu::wgpu::PresentMode::AutoNoVsync, Some(1)),
+    }
+}
+
+/// The window of the modal parent of `viewport_id`, if it is a modal viewport.
+fn modal_parent_window(viewports: &Viewports, viewport_id: ViewportId) -> Option<Arc<Window>> {
+    let viewport = viewports.get(&viewport_id)?;
+    if viewport.class != ViewportClass::Modal {
+        return None;
+    }
+    viewports
+        .get(&viewport.builder.modal_parent_or(viewport.ids.parent))?
+        .window
+        .clone()
+}
+
+/// The window of a modal viewport that blocks the input to `viewport_id`, if any.
+fn modal_child_window(viewports: &Viewports, viewport_id: ViewportId) -> Option<Arc<Window>> {
+    viewports
+        .values()
+        .find(|viewport| {
+            viewport.class == ViewportClass::Modal
+                && viewport.builder.modal_parent_or(viewport.ids.parent) == viewport_id
+        })
+        .and_then(|viewport| viewport.window.clone())
+}
+
 fn initialize_or_update_viewport<'a>(
     viewports: &'a mut Viewports,
     ids: ViewportIdPair,
@@ -1118,6 +1488,7 @@ fn initialize_or_update_viewport<'a>(
                 info: Default::default(),
                 actions_requested: HashSet::new(),
                 viewport_ui_cb,
+                render_thread: None,
                 window: None,
                 egui_winit: None,
             })
@@ -1139,6 +1510,7 @@ fn initialize_or_update_viewport<'a>(
                     ids.this,
                     viewport.builder.title
                 );
+                viewport.render_thread = None;
                 viewport.window = None;
                 viewport.egui_winit = None;
                 if let Err(err) = pollster::block_on(painter.set_window(viewport.ids.this, None)) {
@@ -25,6 +25,9 @@ pub fn create_egui_context(storage: Option<&dyn crate::Storage>) -> egui::Contex
 
     egui_ctx.set_embed_viewports(!IS_DESKTOP);
 
+    // Both the glow and the wgpu renderer can paint distance fields.
+    egui_ctx.set_distance_field_text(true);
+
     egui_ctx.options_mut(|o| {
         // eframe supports multi-pass (Context::request_discard).
         o.max_passes = 2.try_into().unwrap();
@@ -54,6 +57,10 @@ pub enum UserEvent {
     /// A request related to [`accesskit`](https://accesskit.dev/).
     #[cfg(feature = "accesskit")]
     AccessKitActionRequest(accesskit_winit::Event),
+
+    /// The user clicked a notification posted with [`crate::Frame::show_notification`].
+    #[cfg(feature = "notifications")]
+    NotificationClicked(super::notifications::NotificationClick),
 }
 
 #[cfg(feature = "accesskit")]
@@ -70,6 +77,8 @@ pub trait WinitApp {
 
     fn window_id_from_viewport_id(&self, id: ViewportId) -> Option<WindowId>;
 
+    fn viewport_id_from_window_id(&self, id: WindowId) -> Option<ViewportId>;
+
     fn save_and_destroy(&mut self);
 
     fn run_ui_and_paint(
@@ -96,6 +105,12 @@ pub trait WinitApp {
         event: winit::event::WindowEvent,
     ) -> crate::Result<EventResult>;
 
+    /// Called when monitors have been connected, disconnected or rearranged.
+    fn on_monitors_changed(
+        &mut self,
+        changes: &egui_winit::MonitorChanges,
+    ) -> crate::Result<EventResult>;
+
     #[cfg(feature = "accesskit")]
     fn on_accesskit_event(&mut self, event: accesskit_winit::Event) -> crate::Result<EventResult>;
 }
@@ -154,3 +169,110 @@ pub(crate) fn on_accesskit_window_event(
         }
     }
 }
+
+/// Make a window that is created with `window_attributes` a modal dialog of `parent`,
+/// as far as the platform allows (see [`egui::ViewportBuilder::with_modal`]).
+///
+/// Unless it has a position, the window is centered over the parent.
+/// On Windows the window is owned by the parent, so it stays above it and is minimized with it.
+/// On macOS it is attached as a child window of the parent.
+/// Elsewhere the window is created as usual; the input to the parent is still blocked by
+/// [`is_blocked_by_modal`].
+#[allow(unsafe_code)]
+pub(crate) fn with_modal_parent(
+    window_attributes: winit::window::WindowAttributes,
+    parent: &Window,
+) -> winit::window::WindowAttributes {
+    let window_attributes = centered_over(window_attributes, parent);
+
+    #[cfg(any(target_os = "windows", target_os = "macos"))]
+    {
+        use raw_window_handle::{HasWindowHandle as _, RawWindowHandle};
+
+        match parent.window_handle().map(|handle| handle.as_raw()) {
+            #[cfg(target_os = "windows")]
+            Ok(RawWindowHandle::Win32(handle)) => {
+                use winit::platform::windows::WindowAttributesExtWindows as _;
+                return window_attributes.with_owner_window(handle.hwnd.get());
+            }
+
+            #[cfg(target_os = "macos")]
+            Ok(handle @ RawWindowHandle::AppKit(_)) => {
+                // SAFETY: the handle is of a window that we created, and egui closes
+                // child viewports before their parents. A modal parent that isn't the
+                // parent must outlive the viewport, see `ViewportBuilder::with_modal_parent`.
+                return unsafe { window_attributes.with_parent_window(Some(handle)) };
+            }
+
+            _ => log::debug!("Could not get the window handle of the parent of a modal viewport"),
+        }
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let _ = parent;
+
+    window_attributes
+}
+
+/// Center a window that is created with `window_attributes` over `parent`,
+/// unless it has a position.
+fn centered_over(
+    mut window_attributes: winit::window::WindowAttributes,
+    parent: &Window,
+) -> winit::window::WindowAttributes {
+    if window_attributes.position.is_some() {
+        return window_attributes;
+    }
+    let (Ok(parent_pos), Some(inner_size)) =
+        (parent.outer_position(), window_attributes.inner_size)
+    else {
+        return window_attributes; // E.g. on Wayland, where windows can't be positioned
+    };
+
+    let size = inner_size.to_physical::<f64>(parent.scale_factor());
+    let parent_size = parent.outer_size();
+    window_attributes.position = Some(
+        winit::dpi::PhysicalPosition::new(
+            parent_pos.x as f64 + (parent_size.width as f64 - size.width) / 2.0,
+            parent_pos.y as f64 + (parent_size.height as f64 - size.height) / 2.0,
+        )
+        .into(),
+    );
+    window_attributes
+}
+
+/// Should `event` be kept from a window that has the modal child window `modal`?
+///
+/// Pointer and keyboard input is blocked, while events about the window itself
+/// (resizing, closing, focus, …) still get through.
+/// Clicking the blocked window brings the modal window to the front.
+pub(crate) fn is_blocked_by_modal(event: &winit::event::WindowEvent, modal: &Window) -> bool {
+    use winit::event::{ElementState, WindowEvent};
+
+    match event {
+        WindowEvent::MouseInput {
+            state: ElementState::Pressed,
+            ..
+        } => {
+            modal.focus_window();
+            true
+        }
+
+        WindowEvent::MouseInput { .. }
+        | WindowEvent::MouseWheel { .. }
+        | WindowEvent::CursorMoved { .. }
+        | WindowEvent::KeyboardInput { .. }
+        | WindowEvent::Ime(_)
+        | WindowEvent::Touch(_)
+        | WindowEvent::TouchpadPressure { .. }
+        | WindowEvent::PinchGesture { .. }
+        | WindowEvent::PanGesture { .. }
+        | WindowEvent::RotationGesture { .. }
+        | WindowEvent::DoubleTapGesture { .. }
+        | WindowEvent::AxisMotion { .. }
+        | WindowEvent::HoveredFile(_)
+        | WindowEvent::DroppedFile(_) => true,
+
+        _ => false,
+    }
+}
@@ -0,0 +1,39 @@
+use egui::input_replay::InputRecording;
+
+use crate::{App, Frame};
+
+/// Replay recorded input against an app, without opening a window, and return what egui output for each frame.
+///
+/// This lets you write regression tests for how your app responds to user interaction,
+/// by asserting against e.g. the [`egui::PlatformOutput`] or the shapes of each frame.
+///
+/// Record the input of a real session in [`App::raw_input_hook`] (which is not called during the replay):
+///
+/// ```
+/// # struct MyApp { recording: egui::input_replay::InputRecording }
+/// # impl eframe::App for MyApp {
+/// #     fn update(&mut self, _ctx: &egui::Context, _frame: &mut eframe::Frame) {}
+/// fn raw_input_hook(&mut self, _ctx: &egui::Context, raw_input: &mut egui::RawInput) {
+///     self.recording.record(raw_input);
+/// }
+/// # }
+/// ```
+///
+/// and save it with [`InputRecording::save`], e.g. in [`App::on_exit`].
+///
+/// The replay uses a fresh [`egui::Context`], with viewports embedded in the root viewport,
+/// and the time mocked as described in [`egui::input_replay`], so it is deterministic.
+/// The app has no storage or graphics context, and any [`egui::ViewportCommand`]s it sends are ignored.
+pub fn run_replay(app: &mut dyn App, recording: &InputRecording) -> Vec<egui::FullOutput> {
+    crate::profile_function!();
+
+    let egui_ctx = egui::Context::default();
+    egui_ctx.set_embed_viewports(true);
+    egui_ctx.options_mut(|o| {
+        // Like when running natively, see `create_egui_context`:
+        o.max_passes = 2.try_into().unwrap();
+    });
+
+    let mut frame = Frame::headless();
+    recording.replay(&egui_ctx, |egui_ctx| app.update(egui_ctx, &mut frame))
+}
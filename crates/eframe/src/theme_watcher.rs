@@ -0,0 +1,112 @@
+//! Reload a theme file whenever it changes, see [`WatchThemeFile`].
+
+use std::{path::PathBuf, sync::Arc, time::SystemTime};
+
+use egui::{style::ThemeLoader, Context, Id};
+
+/// How often we check the theme file for changes.
+const POLL_INTERVAL_SECS: f32 = 0.5;
+
+/// Adds [`Self::watch_theme_file`] to [`egui::Context`].
+pub trait WatchThemeFile {
+    /// Load the [`egui::Style`] from a `.ron` or `.toml` theme file,
+    /// and reload it whenever the file changes.
+    ///
+    /// This lets you iterate on the look of your app without recompiling.
+    /// See [`egui::style::ThemeLoader`] for the file format.
+    ///
+    /// The file is checked for changes twice a second,
+    /// so while a file is being watched egui will repaint at least that often.
+    ///
+    /// If the file fails to load (e.g. because of a typo) the error is logged,
+    /// and the previous style is kept.
+    ///
+    /// Calling this again replaces the watched file.
+    ///
+    /// ``` no_run
+    /// use eframe::WatchThemeFile as _;
+    /// # let ctx = eframe::egui::Context::default();
+    /// ctx.watch_theme_file("theme.toml");
+    /// ```
+    fn watch_theme_file(&self, path: impl Into<PathBuf>);
+}
+
+impl WatchThemeFile for Context {
+    fn watch_theme_file(&self, path: impl Into<PathBuf>) {
+        let mut watcher = ThemeWatcher {
+            path: path.into(),
+            modified: None,
+            last_poll: self.input(|i| i.time),
+        };
+        watcher.reload(self);
+
+        let was_watching = self.data_mut(|d| {
+            let was_watching = d.get_temp::<ThemeWatcher>(watcher_id()).is_some();
+            d.insert_temp(watcher_id(), watcher);
+            was_watching
+        });
+
+        if !was_watching {
+            self.on_begin_pass("theme_watcher", Arc::new(poll));
+        }
+    }
+}
+
+fn watcher_id() -> Id {
+    Id::new("eframe::ThemeWatcher")
+}
+
+#[derive(Clone)]
+struct ThemeWatcher {
+    path: PathBuf,
+
+    /// Modification time of the file when we last loaded it.
+    modified: Option<SystemTime>,
+
+    /// [`egui::InputState::time`] of the last check.
+    last_poll: f64,
+}
+
+impl ThemeWatcher {
+    fn file_modified(&self) -> Option<SystemTime> {
+        std::fs::metadata(&self.path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+    }
+
+    fn reload(&mut self, ctx: &Context) {
+        self.modified = self.file_modified();
+        match ThemeLoader::load(&self.path) {
+            Ok(style) => {
+                log::debug!("Loaded theme from {:?}", self.path);
+                ctx.set_style(style);
+            }
+            Err(err) => {
+                log::warn!("{:?}: {err}", self.path);
+            }
+        }
+    }
+}
+
+/// Called at the start of each pass.
+fn poll(ctx: &Context) {
+    let Some(mut watcher) = ctx.data(|d| d.get_temp::<ThemeWatcher>(watcher_id())) else {
+        return;
+    };
+
+    let now = ctx.input(|i| i.time);
+    if now - watcher.last_poll >= f64::from(POLL_INTERVAL_SECS) {
+        watcher.last_poll = now;
+
+        // The file may be missing for a moment while an editor saves it.
+        let modified = watcher.file_modified();
+        if modified.is_some() && modified != watcher.modified {
+            watcher.reload(ctx);
+            ctx.request_repaint();
+        }
+
+        ctx.data_mut(|d| d.insert_temp(watcher_id(), watcher));
+    }
+
+    ctx.request_repaint_after_secs(POLL_INTERVAL_SECS);
+}
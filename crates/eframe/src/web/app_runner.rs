@@ -9,7 +9,7 @@ pub struct AppRunner {
     pub(crate) web_options: crate::WebOptions,
     pub(crate) frame: epi::Frame,
     egui_ctx: egui::Context,
-    painter: super::ActiveWebPainter,
+    painter: Box<dyn WebPainter>,
     pub(crate) input: super::WebInput,
     app: Box<dyn epi::App>,
     pub(crate) needs_repaint: std::sync::Arc<NeedRepaint>,
@@ -36,7 +36,7 @@ impl AppRunner {
         app_creator: epi::AppCreator<'static>,
         text_agent: TextAgent,
     ) -> Result<Self, String> {
-        let painter = super::ActiveWebPainter::new(canvas, &web_options).await?;
+        let painter = super::web_painter::create_painter(canvas, &web_options).await?;
 
         let info = epi::IntegrationInfo {
             web_info: epi::WebInfo {
@@ -44,14 +44,21 @@ impl AppRunner {
                 location: super::web_location(),
             },
             cpu_usage: None,
+            input_latency: None,
+            texture_memory: None,
+        };
+        let storage = LocalStorage {
+            key_prefix: web_options.storage_key_prefix.clone(),
         };
-        let storage = LocalStorage::default();
 
         let egui_ctx = egui::Context::default();
         egui_ctx.set_os(egui::os::OperatingSystem::from_user_agent(
             &super::user_agent().unwrap_or_default(),
         ));
-        super::storage::load_memory(&egui_ctx);
+        super::storage::load_memory(&egui_ctx, &web_options.storage_key_prefix);
+
+        // Both the glow and the wgpu renderer can paint distance fields.
+        egui_ctx.set_distance_field_text(true);
 
         egui_ctx.options_mut(|o| {
             // On web by default egui follows the zoom factor of the browser,
@@ -67,15 +74,13 @@ impl AppRunner {
             storage: Some(&storage),
 
             #[cfg(feature = "glow")]
-            gl: Some(painter.gl().clone()),
+            gl: painter.gl(),
 
             #[cfg(feature = "glow")]
             get_proc_address: None,
 
-            #[cfg(all(feature = "wgpu", not(feature = "glow")))]
+            #[cfg(feature = "wgpu")]
             wgpu_render_state: painter.render_state(),
-            #[cfg(all(feature = "wgpu", feature = "glow"))]
-            wgpu_render_state: None,
         };
         let app = app_creator(&cc).map_err(|err| err.to_string())?;
 
@@ -84,12 +89,10 @@ impl AppRunner {
             storage: Some(Box::new(storage)),
 
             #[cfg(feature = "glow")]
-            gl: Some(painter.gl().clone()),
+            gl: painter.gl(),
 
-            #[cfg(all(feature = "wgpu", not(feature = "glow")))]
+            #[cfg(feature = "wgpu")]
             wgpu_render_state: painter.render_state(),
-            #[cfg(all(feature = "wgpu", feature = "glow"))]
-            wgpu_render_state: None,
         };
 
         let needs_repaint: std::sync::Arc<NeedRepaint> = Default::default();
@@ -151,7 +154,7 @@ impl AppRunner {
 
     pub fn save(&mut self) {
         if self.app.persist_egui_memory() {
-            super::storage::save_memory(&self.egui_ctx);
+            super::storage::save_memory(&self.egui_ctx, &self.web_options.storage_key_prefix);
         }
         if let Some(storage) = self.frame.storage_mut() {
             self.app.save(storage);
@@ -270,7 +273,9 @@ impl AppRunner {
             cursor_icon,
             open_url,
             copied_text,
-            events: _,                    // already handled
+            copied_data,
+            os_drag: _, // browsers only start drags from `draggable` elements
+            events: _,  // already handled
             mutable_text_under_cursor: _, // TODO(#4569): https://github.com/emilk/egui/issues/4569
             ime,
             #[cfg(feature = "accesskit")]
@@ -284,7 +289,9 @@ impl AppRunner {
             super::open_url(&open.url, open.new_tab);
         }
 
-        if !copied_text.is_empty() {
+        if let Some(copied_data) = copied_data {
+            super::clipboard::set_clipboard_data(&copied_data);
+        } else if !copied_text.is_empty() {
             super::set_clipboard_text(&copied_text);
         }
 
@@ -315,15 +322,18 @@ impl AppRunner {
 // ----------------------------------------------------------------------------
 
 #[derive(Default)]
-struct LocalStorage {}
+struct LocalStorage {
+    /// See [`crate::WebOptions::storage_key_prefix`].
+    key_prefix: String,
+}
 
 impl epi::Storage for LocalStorage {
     fn get_string(&self, key: &str) -> Option<String> {
-        super::storage::local_storage_get(key)
+        super::storage::local_storage_get(&format!("{}{key}", self.key_prefix))
     }
 
     fn set_string(&mut self, key: &str, value: String) {
-        super::storage::local_storage_set(key, &value);
+        super::storage::local_storage_set(&format!("{}{key}", self.key_prefix), &value);
     }
 
     fn flush(&mut self) {}
@@ -0,0 +1,174 @@
+//! Copying images and HTML to, and pasting images from, the clipboard of the browser.
+//!
+//! Plain text goes through [`super::set_clipboard_text`].
+
+use std::sync::Arc;
+
+use wasm_bindgen::{prelude::*, Clamped};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
+
+use super::{string_from_js_value, WebRunner};
+
+/// Put `data` on the clipboard, using the async Clipboard API.
+///
+/// Images are copied as PNG, HTML as both `text/html` and `text/plain`.
+/// Anything the browser doesn't support is copied as text.
+pub(crate) fn set_clipboard_data(data: &egui::ClipboardData) {
+    let item = match data {
+        egui::ClipboardData::Image(image) => png_clipboard_item(image),
+        egui::ClipboardData::Html { html, alt_text } => html_clipboard_item(html, alt_text),
+        egui::ClipboardData::Files(_) => Err(JsValue::from_str("files can't be copied on web")),
+    };
+
+    match item {
+        Ok(item) => write_clipboard_item(item, data.text_fallback()),
+        Err(err) => {
+            log::debug!("Copying as text: {}", string_from_js_value(&err));
+            let text = data.text_fallback();
+            if !text.is_empty() {
+                super::set_clipboard_text(&text);
+            }
+        }
+    }
+}
+
+fn write_clipboard_item(item: web_sys::ClipboardItem, text_fallback: String) {
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    let items = js_sys::Array::of1(&item);
+    let future = JsFuture::from(window.navigator().clipboard().write(&items));
+    wasm_bindgen_futures::spawn_local(async move {
+        if let Err(err) = future.await {
+            // E.g. Firefox doesn't support writing images to the clipboard.
+            log::warn!(
+                "Failed to copy to the clipboard, copying as text instead: {}",
+                string_from_js_value(&err)
+            );
+            if !text_fallback.is_empty() {
+                super::set_clipboard_text(&text_fallback);
+            }
+        }
+    });
+}
+
+fn html_clipboard_item(html: &str, alt_text: &str) -> Result<web_sys::ClipboardItem, JsValue> {
+    let record = js_sys::Object::new();
+    js_sys::Reflect::set(
+        &record,
+        &"text/html".into(),
+        &blob_from_str(html, "text/html")?,
+    )?;
+    js_sys::Reflect::set(
+        &record,
+        &"text/plain".into(),
+        &blob_from_str(alt_text, "text/plain")?,
+    )?;
+    web_sys::ClipboardItem::new_with_record_from_str_to_blob_promise(&record)
+}
+
+fn blob_from_str(text: &str, mime: &str) -> Result<web_sys::Blob, JsValue> {
+    let options = web_sys::BlobPropertyBag::new();
+    options.set_type(mime);
+    web_sys::Blob::new_with_str_sequence_and_options(&js_sys::Array::of1(&text.into()), &options)
+}
+
+/// Encodes the image as PNG by drawing it to an offscreen canvas.
+fn png_clipboard_item(image: &egui::ColorImage) -> Result<web_sys::ClipboardItem, JsValue> {
+    let [width, height] = image.size;
+    let rgba: Vec<u8> = image
+        .pixels
+        .iter()
+        .flat_map(|color| color.to_srgba_unmultiplied())
+        .collect();
+    let image_data = web_sys::ImageData::new_with_u8_clamped_array_and_sh(
+        Clamped(&rgba),
+        width as u32,
+        height as u32,
+    )?;
+
+    let (canvas, context) = canvas_2d(width as u32, height as u32)?;
+    context.put_image_data(&image_data, 0.0, 0.0)?;
+
+    // The clipboard item takes a promise, so the encoding can finish after we return:
+    let mut result = Ok(());
+    let png = js_sys::Promise::new(&mut |resolve, reject| {
+        result = canvas.to_blob(&resolve);
+        if let Err(err) = &result {
+            reject.call1(&JsValue::NULL, err).ok();
+        }
+    });
+    result?;
+
+    let record = js_sys::Object::new();
+    js_sys::Reflect::set(&record, &"image/png".into(), &png)?;
+    web_sys::ClipboardItem::new_with_record_from_str_to_blob_promise(&record)
+}
+
+fn canvas_2d(
+    width: u32,
+    height: u32,
+) -> Result<(HtmlCanvasElement, CanvasRenderingContext2d), JsValue> {
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or_else(|| JsValue::from_str("no document"))?;
+    let canvas: HtmlCanvasElement = document.create_element("canvas")?.dyn_into()?;
+    canvas.set_width(width);
+    canvas.set_height(height);
+    let context = canvas
+        .get_context("2d")?
+        .ok_or_else(|| JsValue::from_str("no 2d canvas context"))?
+        .dyn_into()?;
+    Ok((canvas, context))
+}
+
+/// Find the first image in a `paste` event, if any.
+pub(crate) fn pasted_image(data: &web_sys::DataTransfer) -> Option<web_sys::File> {
+    let items = data.items();
+    (0..items.length())
+        .filter_map(|i| items.get(i))
+        .filter(|item| item.kind() == "file" && item.type_().starts_with("image/"))
+        .find_map(|item| item.get_as_file().ok().flatten())
+}
+
+/// Decode the image in the background, then send it to egui as an [`egui::Event::PasteImage`].
+pub(crate) fn paste_image(runner_ref: WebRunner, file: web_sys::File) {
+    wasm_bindgen_futures::spawn_local(async move {
+        match decode_image(&file).await {
+            Ok(image) => {
+                if let Some(mut runner_lock) = runner_ref.try_lock() {
+                    runner_lock
+                        .input
+                        .raw
+                        .events
+                        .push(egui::Event::PasteImage(Arc::new(image)));
+                    runner_lock.needs_repaint.repaint_asap();
+                }
+            }
+            Err(err) => {
+                log::error!("Failed to paste image: {}", string_from_js_value(&err));
+            }
+        }
+    });
+}
+
+async fn decode_image(blob: &web_sys::Blob) -> Result<egui::ColorImage, JsValue> {
+    let window = web_sys::window().ok_or_else(|| JsValue::from_str("no window"))?;
+    let bitmap: web_sys::ImageBitmap = JsFuture::from(window.create_image_bitmap_with_blob(blob)?)
+        .await?
+        .dyn_into()?;
+    let (width, height) = (bitmap.width(), bitmap.height());
+
+    let (_canvas, context) = canvas_2d(width, height)?;
+    context.draw_image_with_image_bitmap(&bitmap, 0.0, 0.0)?;
+    bitmap.close();
+    let rgba = context
+        .get_image_data(0.0, 0.0, width as f64, height as f64)?
+        .data();
+
+    Ok(egui::ColorImage::from_rgba_unmultiplied(
+        [width as usize, height as usize],
+        &rgba,
+    ))
+}
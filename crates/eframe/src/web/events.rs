@@ -176,6 +176,7 @@ pub(crate) fn on_keydown(event: web_sys::KeyboardEvent, runner: &mut AppRunner)
         let egui_event = egui::Event::Key {
             key: egui_key,
             physical_key: None, // TODO(fornwall)
+            scancode: None,
             pressed: true,
             repeat: false, // egui will fill this in for us!
             modifiers,
@@ -256,6 +257,7 @@ pub(crate) fn on_keyup(event: web_sys::KeyboardEvent, runner: &mut AppRunner) {
         let egui_event = egui::Event::Key {
             key,
             physical_key: None, // TODO(fornwall)
+            scancode: None,
             pressed: false,
             repeat: false,
             modifiers,
@@ -275,6 +277,7 @@ pub(crate) fn on_keyup(event: web_sys::KeyboardEvent, runner: &mut AppRunner) {
             let egui_event = egui::Event::Key {
                 key,
                 physical_key: None,
+                scancode: None,
                 pressed: false,
                 repeat: false,
                 modifiers,
@@ -294,9 +297,12 @@ pub(crate) fn on_keyup(event: web_sys::KeyboardEvent, runner: &mut AppRunner) {
 }
 
 fn install_copy_cut_paste(runner_ref: &WebRunner, target: &EventTarget) -> Result<(), JsValue> {
-    runner_ref.add_event_listener(target, "paste", |event: web_sys::ClipboardEvent, runner| {
-        if let Some(data) = event.clipboard_data() {
-            if let Ok(text) = data.get_data("text") {
+    runner_ref.add_event_listener(target, "paste", {
+        let runner_ref = runner_ref.clone();
+
+        move |event: web_sys::ClipboardEvent, runner| {
+            if let Some(data) = event.clipboard_data() {
+                let text = data.get_data("text").unwrap_or_default();
                 let text = text.replace("\r\n", "\n");
 
                 let mut should_propagate = false;
@@ -305,6 +311,11 @@ fn install_copy_cut_paste(runner_ref: &WebRunner, target: &EventTarget) -> Resul
                     should_propagate = (runner.web_options.should_propagate_event)(&egui_event);
                     runner.input.raw.events.push(egui_event);
                     runner.needs_repaint.repaint_asap();
+                } else if let Some(file) = super::clipboard::pasted_image(&data) {
+                    // The image is decoded in the background, and then sent as `Event::PasteImage`.
+                    if runner.input.raw.focused {
+                        super::clipboard::paste_image(runner_ref.clone(), file);
+                    }
                 }
 
                 // Use web options to tell if the web event should be propagated to parent elements based on the egui event.
@@ -700,6 +711,7 @@ fn install_wheel(runner_ref: &WebRunner, target: &EventTarget) -> Result<(), JsV
                 unit,
                 delta,
                 modifiers,
+                phase: egui::ScrollPhase::Unknown, // Browsers don't tell us
             }
         };
         let should_propagate = (runner.web_options.should_propagate_event)(&egui_event);
@@ -773,13 +785,10 @@ fn install_drag_and_drop(runner_ref: &WebRunner, target: &EventTarget) -> Result
 
                             log::debug!("Loading {:?} ({} bytes)…", name, file.size());
 
-                            let future = wasm_bindgen_futures::JsFuture::from(file.array_buffer());
-
                             let runner_ref = runner_ref.clone();
                             let future = async move {
-                                match future.await {
-                                    Ok(array_buffer) => {
-                                        let bytes = js_sys::Uint8Array::new(&array_buffer).to_vec();
+                                match read_dropped_file(&runner_ref, &file).await {
+                                    Ok(bytes) => {
                                         log::debug!("Loaded {:?} ({} bytes).", name, bytes.len());
 
                                         if let Some(mut runner_lock) = runner_ref.try_lock() {
@@ -813,6 +822,43 @@ fn install_drag_and_drop(runner_ref: &WebRunner, target: &EventTarget) -> Result
     Ok(())
 }
 
+/// Read a dropped file chunk by chunk, reporting the progress to
+/// [`crate::WebOptions::on_file_drop_progress`].
+async fn read_dropped_file(
+    runner_ref: &WebRunner,
+    file: &web_sys::File,
+) -> Result<Vec<u8>, JsValue> {
+    use wasm_bindgen_futures::JsFuture;
+
+    let total_bytes = file.size() as u64;
+    let mut bytes = Vec::with_capacity(total_bytes as usize);
+    let reader: web_sys::ReadableStreamDefaultReader = file.stream().get_reader().dyn_into()?;
+
+    loop {
+        let chunk = JsFuture::from(reader.read()).await?;
+        if js_sys::Reflect::get(&chunk, &"done".into())?.is_truthy() {
+            break;
+        }
+        let value = js_sys::Reflect::get(&chunk, &"value".into())?;
+        bytes.extend(js_sys::Uint8Array::new(&value).to_vec());
+
+        if let Some(runner) = runner_ref.try_lock() {
+            if let Some(on_progress) = &runner.web_options.on_file_drop_progress {
+                on_progress(
+                    runner.egui_ctx(),
+                    &crate::FileDropProgress {
+                        name: file.name(),
+                        bytes_read: bytes.len() as u64,
+                        total_bytes,
+                    },
+                );
+            }
+        }
+    }
+
+    Ok(bytes)
+}
+
 /// Install a `ResizeObserver` to observe changes to the size of the canvas.
 ///
 /// This is the only way to ensure a canvas size change without an associated window `resize` event
@@ -13,8 +13,8 @@ pub fn local_storage_set(key: &str, value: &str) {
 }
 
 #[cfg(feature = "persistence")]
-pub(crate) fn load_memory(ctx: &egui::Context) {
-    if let Some(memory_string) = local_storage_get("egui_memory_ron") {
+pub(crate) fn load_memory(ctx: &egui::Context, key_prefix: &str) {
+    if let Some(memory_string) = local_storage_get(&format!("{key_prefix}egui_memory_ron")) {
         match ron::from_str(&memory_string) {
             Ok(memory) => {
                 ctx.memory_mut(|m| *m = memory);
@@ -27,13 +27,13 @@ pub(crate) fn load_memory(ctx: &egui::Context) {
 }
 
 #[cfg(not(feature = "persistence"))]
-pub(crate) fn load_memory(_: &egui::Context) {}
+pub(crate) fn load_memory(_: &egui::Context, _: &str) {}
 
 #[cfg(feature = "persistence")]
-pub(crate) fn save_memory(ctx: &egui::Context) {
+pub(crate) fn save_memory(ctx: &egui::Context, key_prefix: &str) {
     match ctx.memory(ron::to_string) {
         Ok(ron) => {
-            local_storage_set("egui_memory_ron", &ron);
+            local_storage_set(&format!("{key_prefix}egui_memory_ron"), &ron);
         }
         Err(err) => {
             log::warn!("Failed to serialize memory as RON: {err}");
@@ -42,4 +42,4 @@ pub(crate) fn save_memory(ctx: &egui::Context) {
 }
 
 #[cfg(not(feature = "persistence"))]
-pub(crate) fn save_memory(_: &egui::Context) {}
+pub(crate) fn save_memory(_: &egui::Context, _: &str) {}
@@ -1,16 +1,14 @@
 use wasm_bindgen::JsValue;
+use web_sys::HtmlCanvasElement;
+
+use crate::{Renderer, WebOptions};
 
 /// Renderer for a browser canvas.
-/// As of writing we're not allowing to decide on the painter at runtime,
-/// therefore this trait is merely there for specifying and documenting the interface.
+///
+/// Which one is used is decided at runtime, see [`create_painter`].
 pub(crate) trait WebPainter {
-    // Create a new web painter targeting a given canvas.
-    // fn new(canvas: HtmlCanvasElement, options: &WebOptions) -> Result<Self, String>
-    // where
-    //     Self: Sized;
-
     /// Reference to the canvas in use.
-    fn canvas(&self) -> &web_sys::HtmlCanvasElement;
+    fn canvas(&self) -> &HtmlCanvasElement;
 
     /// Maximum size of a texture in one direction.
     fn max_texture_side(&self) -> usize;
@@ -26,4 +24,92 @@ pub(crate) trait WebPainter {
 
     /// Destroy all resources.
     fn destroy(&mut self);
+
+    /// The OpenGL context, if this painter uses glow.
+    #[cfg(feature = "glow")]
+    fn gl(&self) -> Option<std::sync::Arc<glow::Context>> {
+        None
+    }
+
+    /// The wgpu render state, if this painter uses wgpu.
+    #[cfg(feature = "wgpu")]
+    fn render_state(&self) -> Option<egui_wgpu::RenderState> {
+        None
+    }
+}
+
+/// Create the painter for [`WebOptions::renderer`].
+///
+/// If both the `glow` and `wgpu` features are enabled, and wgpu is asked for but
+/// the browser doesn't support WebGPU (or starting wgpu fails), glow (WebGL) is used instead.
+pub(crate) async fn create_painter(
+    canvas: HtmlCanvasElement,
+    options: &WebOptions,
+) -> Result<Box<dyn WebPainter>, String> {
+    match options.renderer {
+        #[cfg(feature = "glow")]
+        Renderer::Glow => {
+            log::debug!("Using the glow renderer");
+            Ok(Box::new(
+                super::web_painter_glow::WebPainterGlow::new(canvas, options).await?,
+            ))
+        }
+
+        #[cfg(feature = "wgpu")]
+        Renderer::Wgpu => {
+            #[cfg(feature = "glow")]
+            if !is_webgpu_available() {
+                log::info!("WebGPU is not available in this browser, falling back to WebGL");
+                return Ok(Box::new(
+                    super::web_painter_glow::WebPainterGlow::new(canvas, options).await?,
+                ));
+            }
+
+            log::debug!("Using the wgpu renderer");
+            match super::web_painter_wgpu::WebPainterWgpu::new(canvas.clone(), options).await {
+                Ok(painter) => Ok(Box::new(painter)),
+
+                #[cfg(feature = "glow")]
+                Err(err) => {
+                    log::warn!("Failed to start wgpu, falling back to WebGL: {err}");
+                    // The canvas may already have a WebGPU context, and can only have one kind:
+                    let canvas = replace_canvas(&canvas)?;
+                    Ok(Box::new(
+                        super::web_painter_glow::WebPainterGlow::new(canvas, options).await?,
+                    ))
+                }
+
+                #[cfg(not(feature = "glow"))]
+                Err(err) => Err(err),
+            }
+        }
+    }
+}
+
+/// Does the browser support WebGPU, i.e. is there a `navigator.gpu`?
+///
+/// WebGPU is only available in secure contexts, i.e. on HTTPS and on localhost.
+#[cfg(all(feature = "glow", feature = "wgpu"))]
+fn is_webgpu_available() -> bool {
+    web_sys::window().is_some_and(|window| {
+        window.is_secure_context()
+            && js_sys::Reflect::get(&window.navigator(), &JsValue::from_str("gpu"))
+                .is_ok_and(|gpu| !gpu.is_undefined() && !gpu.is_null())
+    })
+}
+
+/// Put a copy of `canvas` in its place in the page, without any rendering context.
+#[cfg(all(feature = "glow", feature = "wgpu"))]
+fn replace_canvas(canvas: &HtmlCanvasElement) -> Result<HtmlCanvasElement, String> {
+    use wasm_bindgen::JsCast as _;
+
+    let new_canvas = canvas
+        .clone_node()
+        .map_err(|err| super::string_from_js_value(&err))?
+        .dyn_into::<HtmlCanvasElement>()
+        .map_err(|_| "Failed to copy the canvas".to_owned())?;
+    canvas
+        .replace_with_with_node_1(&new_canvas)
+        .map_err(|err| super::string_from_js_value(&err))?;
+    Ok(new_canvas)
 }
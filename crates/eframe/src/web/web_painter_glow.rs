@@ -14,10 +14,6 @@ pub(crate) struct WebPainterGlow {
 }
 
 impl WebPainterGlow {
-    pub fn gl(&self) -> &std::sync::Arc<glow::Context> {
-        self.painter.gl()
-    }
-
     pub async fn new(canvas: HtmlCanvasElement, options: &WebOptions) -> Result<Self, String> {
         let (gl, shader_prefix) =
             init_glow_context_from_canvas(&canvas, options.webgl_context_option)?;
@@ -32,6 +28,10 @@ impl WebPainterGlow {
 }
 
 impl WebPainter for WebPainterGlow {
+    fn gl(&self) -> Option<std::sync::Arc<glow::Context>> {
+        Some(self.painter.gl().clone())
+    }
+
     fn max_texture_side(&self) -> usize {
         self.painter.max_texture_side()
     }
@@ -52,6 +52,9 @@ impl WebPainter for WebPainterGlow {
         for (id, image_delta) in &textures_delta.set {
             self.painter.set_texture(*id, image_delta);
         }
+        for &(id, options) in &textures_delta.options {
+            self.painter.set_texture_options(id, options);
+        }
 
         egui_glow::painter::clear(self.painter.gl(), canvas_dimension, clear_color);
         self.painter
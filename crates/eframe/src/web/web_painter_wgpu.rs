@@ -48,11 +48,6 @@ pub(crate) struct WebPainterWgpu {
 }
 
 impl WebPainterWgpu {
-    #[allow(unused)] // only used if `wgpu` is the only active feature.
-    pub fn render_state(&self) -> Option<RenderState> {
-        self.render_state.clone()
-    }
-
     pub fn generate_depth_texture_view(
         &self,
         render_state: &RenderState,
@@ -80,7 +75,6 @@ impl WebPainterWgpu {
         })
     }
 
-    #[allow(unused)] // only used if `wgpu` is the only active feature.
     pub async fn new(
         canvas: web_sys::HtmlCanvasElement,
         options: &WebOptions,
@@ -171,6 +165,10 @@ impl WebPainterWgpu {
 }
 
 impl WebPainter for WebPainterWgpu {
+    fn render_state(&self) -> Option<RenderState> {
+        self.render_state.clone()
+    }
+
     fn canvas(&self) -> &HtmlCanvasElement {
         &self.canvas
     }
@@ -219,6 +217,9 @@ impl WebPainter for WebPainterWgpu {
                     image_delta,
                 );
             }
+            for &(id, options) in &textures_delta.options {
+                renderer.set_texture_options(&render_state.device, id, options);
+            }
 
             renderer.update_buffers(
                 &render_state.device,
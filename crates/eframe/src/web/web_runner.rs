@@ -11,6 +11,13 @@ use super::{events, text_agent::TextAgent, AppRunner, PanicHandler};
 /// This is cheap to clone.
 ///
 /// See [the crate level docs](crate) for an example.
+///
+/// You can run several apps on the same page, e.g. to embed egui widgets in an existing
+/// HTML dashboard: create one [`WebRunner`] per canvas, and [`Self::destroy`] it when you no longer need it.
+/// Use [`Self::start_in_new_canvas`] to have `eframe` create (and later remove) the canvas for you.
+/// Each app has its own [`egui::Context`] and graphics context (and so its own font atlas),
+/// but they all share the same wasm instance.
+/// Set a different [`crate::WebOptions::storage_key_prefix`] for each app that persists its state.
 #[derive(Clone)]
 pub struct WebRunner {
     /// Have we ever panicked?
@@ -29,6 +36,9 @@ pub struct WebRunner {
     frame: Rc<RefCell<Option<AnimationFrameRequest>>>,
 
     resize_observer: Rc<RefCell<Option<ResizeObserverContext>>>,
+
+    /// The canvas created by [`Self::start_in_new_canvas`], removed by [`Self::destroy`].
+    owned_canvas: Rc<RefCell<Option<web_sys::HtmlCanvasElement>>>,
 }
 
 impl WebRunner {
@@ -43,6 +53,7 @@ impl WebRunner {
             events_to_unsubscribe: Rc::new(RefCell::new(Default::default())),
             frame: Default::default(),
             resize_observer: Default::default(),
+            owned_canvas: Default::default(),
         }
     }
 
@@ -83,6 +94,38 @@ impl WebRunner {
         Ok(())
     }
 
+    /// Create a new canvas that fills `parent`, and start running the app in it.
+    ///
+    /// The canvas is removed again by [`Self::destroy`].
+    ///
+    /// # Errors
+    /// Failing to initialize graphics, or failure to create app.
+    pub async fn start_in_new_canvas(
+        &self,
+        parent: &web_sys::Element,
+        web_options: crate::WebOptions,
+        app_creator: epi::AppCreator<'static>,
+    ) -> Result<web_sys::HtmlCanvasElement, JsValue> {
+        self.destroy();
+
+        let document = web_sys::window()
+            .and_then(|window| window.document())
+            .ok_or_else(|| JsValue::from_str("no document"))?;
+        let canvas: web_sys::HtmlCanvasElement = document.create_element("canvas")?.dyn_into()?;
+        canvas.style().set_property("width", "100%")?;
+        canvas.style().set_property("height", "100%")?;
+        canvas.style().set_property("display", "block")?;
+        parent.append_child(&canvas)?;
+
+        if let Err(err) = self.start(canvas.clone(), web_options, app_creator).await {
+            canvas.remove();
+            return Err(err);
+        }
+        self.owned_canvas.replace(Some(canvas.clone()));
+
+        Ok(canvas)
+    }
+
     /// Has there been a panic?
     pub fn has_panicked(&self) -> bool {
         self.panic_handler.has_panicked()
@@ -127,6 +170,10 @@ impl WebRunner {
         if let Some(runner) = self.runner.replace(None) {
             runner.destroy();
         }
+
+        if let Some(canvas) = self.owned_canvas.take() {
+            canvas.remove();
+        }
     }
 
     /// Returns `None` if there has been a panic, or if we have been destroyed.
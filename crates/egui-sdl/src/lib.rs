@@ -0,0 +1,918 @@
+//! [`egui`] bindings for [SDL2](https://www.libsdl.org/), using the [`sdl2`] crate.
+//!
+//! The library translates SDL events to egui, handles copy/paste,
+//! updates the cursor, open links clicked in egui, etc.
+//!
+//! It mirrors [`egui-winit`](https://docs.rs/egui-winit):
+//! create one [`State`] per window, feed it every event with [`State::on_event`],
+//! call [`State::take_egui_input`] before each frame and [`State::handle_platform_output`] after it.
+//!
+//! SDL2 itself is linked dynamically by default.
+//! To build it from source and link it statically instead,
+//! enable the `bundled` feature of [`sdl2`] in your own `Cargo.toml`.
+//!
+//! ## Feature flags
+#![cfg_attr(feature = "document-features", doc = document_features::document_features!())]
+//!
+
+#![allow(clippy::manual_range_contains)]
+
+pub use egui;
+pub use sdl2;
+
+use egui::{Pos2, Rect, Vec2, ViewportId, ViewportInfo};
+use sdl2::{
+    event::{Event, WindowEvent},
+    keyboard::{Keycode, Mod, Scancode},
+    mouse::{Cursor, MouseButton, MouseWheelDirection, SystemCursor},
+    video::{FullscreenType, Window},
+};
+
+/// The `which` of mouse events that SDL synthesizes from touches, which we translate ourselves.
+const TOUCH_MOUSE_ID: u32 = u32::MAX;
+
+#[allow(unused_imports)]
+pub(crate) use profiling_scopes::{profile_function, profile_scope};
+
+/// The size of the drawable area of the window, in physical pixels.
+pub fn screen_size_in_pixels(window: &Window) -> egui::Vec2 {
+    let (width, height) = window.drawable_size();
+    egui::vec2(width as f32, height as f32)
+}
+
+/// The number of physical pixels per window coordinate.
+///
+/// SDL reports window sizes and mouse positions in window coordinates,
+/// which are larger than physical pixels on high-DPI displays
+/// if the window was created with [`sdl2::video::WindowBuilder::allow_highdpi`].
+pub fn native_pixels_per_point(window: &Window) -> f32 {
+    let (width, _) = window.size();
+    let (drawable_width, _) = window.drawable_size();
+    if width == 0 {
+        1.0
+    } else {
+        drawable_width as f32 / width as f32
+    }
+}
+
+/// Calculate the `pixels_per_point` for the window of the given viewport,
+/// given the egui zoom factor of that viewport.
+pub fn pixels_per_point(egui_ctx: &egui::Context, viewport_id: ViewportId, window: &Window) -> f32 {
+    egui_ctx.zoom_factor_of(viewport_id) * native_pixels_per_point(window)
+}
+
+// ----------------------------------------------------------------------------
+
+#[must_use]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct EventResponse {
+    /// If true, egui consumed this event, i.e. wants exclusive use of this event
+    /// (e.g. a mouse click on an egui window, or entering text into a text field).
+    ///
+    /// For instance, if you use egui for a game, you should only
+    /// pass on the events to your game when [`Self::consumed`] is `false`.
+    ///
+    /// Note that egui uses `tab` to move focus between elements, so this will always be `true` for tabs.
+    pub consumed: bool,
+
+    /// Do we need an egui refresh because of this event?
+    pub repaint: bool,
+}
+
+// ----------------------------------------------------------------------------
+
+/// Handles the integration between egui and an SDL2 window.
+///
+/// Instantiate one of these per viewport/window.
+pub struct State {
+    /// Shared clone.
+    egui_ctx: egui::Context,
+
+    viewport_id: ViewportId,
+    window_id: u32,
+    video: sdl2::VideoSubsystem,
+    start_time: web_time::Instant,
+    egui_input: egui::RawInput,
+    pointer_pos_in_points: Option<egui::Pos2>,
+    current_cursor_icon: Option<egui::CursorIcon>,
+
+    /// SDL only shows a cursor for as long as it is alive.
+    cursor: Option<Cursor>,
+
+    /// Is Some(…) when a touch is being translated to a pointer.
+    ///
+    /// Only one touch will be interpreted as pointer at any time.
+    pointer_touch_id: Option<u64>,
+
+    /// track ime state
+    has_sent_ime_enabled: bool,
+
+    allow_ime: bool,
+    ime_rect: Option<sdl2::rect::Rect>,
+}
+
+impl State {
+    /// Construct a new instance for the given window.
+    pub fn new(
+        egui_ctx: egui::Context,
+        viewport_id: ViewportId,
+        window: &Window,
+        max_texture_side: Option<usize>,
+    ) -> Self {
+        crate::profile_function!();
+
+        let egui_input = egui::RawInput {
+            focused: false, // SDL will tell us when we have focus
+            max_texture_side,
+            ..Default::default()
+        };
+
+        let mut slf = Self {
+            egui_ctx,
+            viewport_id,
+            window_id: window.id(),
+            video: window.subsystem().clone(),
+            start_time: web_time::Instant::now(),
+            egui_input,
+            pointer_pos_in_points: None,
+            current_cursor_icon: None,
+            cursor: None,
+
+            pointer_touch_id: None,
+
+            has_sent_ime_enabled: false,
+
+            allow_ime: false,
+            ime_rect: None,
+        };
+
+        // SDL starts out accepting text input, which would show the on-screen keyboard on mobile.
+        // We start it again once egui wants text.
+        slf.video.text_input().stop();
+
+        slf.egui_input
+            .viewports
+            .entry(viewport_id)
+            .or_default()
+            .native_pixels_per_point = Some(native_pixels_per_point(window));
+        slf
+    }
+
+    /// Call this once a graphics context has been created to update the maximum texture dimensions
+    /// that egui will use.
+    pub fn set_max_texture_side(&mut self, max_texture_side: usize) {
+        self.egui_input.max_texture_side = Some(max_texture_side);
+    }
+
+    /// Fetches text from the clipboard and returns it.
+    pub fn clipboard_text(&mut self) -> Option<String> {
+        match self.video.clipboard().clipboard_text() {
+            Ok(text) => Some(text),
+            Err(err) => {
+                log::error!("SDL paste error: {err}");
+                None
+            }
+        }
+    }
+
+    /// Places the text onto the clipboard.
+    pub fn set_clipboard_text(&mut self, text: &str) {
+        if let Err(err) = self.video.clipboard().set_clipboard_text(text) {
+            log::error!("SDL copy error: {err}");
+        }
+    }
+
+    /// Reads the clipboard and queues an [`egui::Event::Paste`] with its text.
+    pub fn push_paste_event(&mut self) {
+        let text = self
+            .clipboard_text()
+            .map(|text| text.replace("\r\n", "\n"))
+            .unwrap_or_default();
+        if !text.is_empty() {
+            self.egui_input.events.push(egui::Event::Paste(text));
+        }
+    }
+
+    /// Returns [`false`] or the last value that text input was started or stopped with, used for debouncing.
+    pub fn allow_ime(&self) -> bool {
+        self.allow_ime
+    }
+
+    #[inline]
+    pub fn egui_ctx(&self) -> &egui::Context {
+        &self.egui_ctx
+    }
+
+    /// The current input state.
+    /// This is changed by [`Self::on_event`] and cleared by [`Self::take_egui_input`].
+    #[inline]
+    pub fn egui_input(&self) -> &egui::RawInput {
+        &self.egui_input
+    }
+
+    /// The current input state.
+    /// This is changed by [`Self::on_event`] and cleared by [`Self::take_egui_input`].
+    #[inline]
+    pub fn egui_input_mut(&mut self) -> &mut egui::RawInput {
+        &mut self.egui_input
+    }
+
+    /// Prepare for a new frame by extracting the accumulated input,
+    ///
+    /// as well as setting [the time](egui::RawInput::time) and [screen rectangle](egui::RawInput::screen_rect).
+    ///
+    /// Use [`update_viewport_info`] to update the rest of the info of the viewport.
+    pub fn take_egui_input(&mut self, window: &Window) -> egui::RawInput {
+        crate::profile_function!();
+
+        self.egui_input.time = Some(self.start_time.elapsed().as_secs_f64());
+
+        // A minimized window can have 0 width and height.
+        let screen_size_in_points = screen_size_in_pixels(window)
+            / pixels_per_point(&self.egui_ctx, self.viewport_id, window);
+
+        self.egui_input.screen_rect = (screen_size_in_points.x > 0.0
+            && screen_size_in_points.y > 0.0)
+            .then(|| Rect::from_min_size(Pos2::ZERO, screen_size_in_points));
+
+        // Tell egui which viewport is now active:
+        self.egui_input.viewport_id = self.viewport_id;
+
+        self.egui_input
+            .viewports
+            .entry(self.viewport_id)
+            .or_default()
+            .native_pixels_per_point = Some(native_pixels_per_point(window));
+
+        self.egui_input.take()
+    }
+
+    /// Call this for every SDL event.
+    ///
+    /// Events of other windows are ignored, so with several windows you can pass every event to every [`State`].
+    ///
+    /// The result can be found in [`Self::egui_input`] and be extracted with [`Self::take_egui_input`].
+    pub fn on_event(&mut self, window: &Window, event: &Event) -> EventResponse {
+        crate::profile_function!();
+
+        if event
+            .get_window_id()
+            .is_some_and(|window_id| window_id != self.window_id)
+        {
+            return EventResponse::default();
+        }
+
+        match event {
+            Event::Window { win_event, .. } => self.on_window_event(*win_event),
+            Event::MouseMotion { which, .. }
+            | Event::MouseButtonDown { which, .. }
+            | Event::MouseButtonUp { which, .. }
+            | Event::MouseWheel { which, .. }
+                if *which == TOUCH_MOUSE_ID =>
+            {
+                EventResponse {
+                    repaint: false,
+                    consumed: self.egui_ctx.wants_pointer_input(),
+                }
+            }
+            Event::MouseMotion { x, y, .. } => {
+                self.on_cursor_moved(*x as f32, *y as f32);
+                EventResponse {
+                    repaint: true,
+                    consumed: self.egui_ctx.is_using_pointer(),
+                }
+            }
+            Event::MouseButtonDown {
+                mouse_btn, x, y, ..
+            }
+            | Event::MouseButtonUp {
+                mouse_btn, x, y, ..
+            } => {
+                self.on_cursor_moved(*x as f32, *y as f32);
+                let pressed = matches!(event, Event::MouseButtonDown { .. });
+                self.on_mouse_button_input(pressed, *mouse_btn);
+                EventResponse {
+                    repaint: true,
+                    consumed: self.egui_ctx.wants_pointer_input(),
+                }
+            }
+            Event::MouseWheel {
+                precise_x,
+                precise_y,
+                direction,
+                ..
+            } => {
+                let flip = if *direction == MouseWheelDirection::Flipped {
+                    -1.0
+                } else {
+                    1.0
+                };
+                // SDL scrolls right for positive x, while egui moves the content right.
+                let delta = flip * egui::vec2(-precise_x, *precise_y);
+                let modifiers = self.egui_input.modifiers;
+                self.egui_input.events.push(egui::Event::MouseWheel {
+                    unit: egui::MouseWheelUnit::Line,
+                    delta,
+                    modifiers,
+                    phase: egui::ScrollPhase::Unknown,
+                });
+                EventResponse {
+                    repaint: true,
+                    consumed: self.egui_ctx.wants_pointer_input(),
+                }
+            }
+            Event::FingerDown {
+                touch_id,
+                finger_id,
+                x,
+                y,
+                pressure,
+                ..
+            } => self.on_touch(
+                window,
+                egui::TouchPhase::Start,
+                *touch_id,
+                *finger_id,
+                egui::pos2(*x, *y),
+                *pressure,
+            ),
+            Event::FingerMotion {
+                touch_id,
+                finger_id,
+                x,
+                y,
+                pressure,
+                ..
+            } => self.on_touch(
+                window,
+                egui::TouchPhase::Move,
+                *touch_id,
+                *finger_id,
+                egui::pos2(*x, *y),
+                *pressure,
+            ),
+            Event::FingerUp {
+                touch_id,
+                finger_id,
+                x,
+                y,
+                pressure,
+                ..
+            } => self.on_touch(
+                window,
+                egui::TouchPhase::End,
+                *touch_id,
+                *finger_id,
+                egui::pos2(*x, *y),
+                *pressure,
+            ),
+            Event::KeyDown {
+                keycode,
+                scancode,
+                keymod,
+                ..
+            }
+            | Event::KeyUp {
+                keycode,
+                scancode,
+                keymod,
+                ..
+            } => {
+                let pressed = matches!(event, Event::KeyDown { .. });
+                self.on_keyboard_input(pressed, *keycode, *scancode, *keymod);
+
+                // When pressing the Tab key, egui focuses the first focusable element, hence Tab always consumes.
+                let consumed =
+                    self.egui_ctx.wants_keyboard_input() || *keycode == Some(Keycode::Tab);
+                EventResponse {
+                    repaint: true,
+                    consumed,
+                }
+            }
+            Event::TextInput { text, .. } => {
+                if self.has_sent_ime_enabled {
+                    self.egui_input
+                        .events
+                        .push(egui::Event::Ime(egui::ImeEvent::Commit(text.clone())));
+                    self.ime_event_disable();
+                } else if !text.is_empty() && text.chars().all(is_printable_char) {
+                    self.egui_input.events.push(egui::Event::Text(text.clone()));
+                }
+                EventResponse {
+                    repaint: true,
+                    consumed: self.egui_ctx.wants_keyboard_input(),
+                }
+            }
+            Event::TextEditing { text, .. } => {
+                // The text being composed with the IME, sent again whenever it changes.
+                if text.is_empty() {
+                    if self.has_sent_ime_enabled {
+                        self.ime_event_disable();
+                    }
+                } else {
+                    self.ime_event_enable();
+                    self.egui_input
+                        .events
+                        .push(egui::Event::Ime(egui::ImeEvent::Preedit(text.clone())));
+                }
+                EventResponse {
+                    repaint: true,
+                    consumed: self.egui_ctx.wants_keyboard_input(),
+                }
+            }
+            Event::DropFile { filename, .. } => {
+                self.egui_input.hovered_files.clear();
+                self.egui_input.dropped_files.push(egui::DroppedFile {
+                    path: Some(filename.into()),
+                    ..Default::default()
+                });
+                EventResponse {
+                    repaint: true,
+                    consumed: false,
+                }
+            }
+            Event::Quit { .. } => EventResponse {
+                repaint: true,
+                consumed: false,
+            },
+            _ => EventResponse {
+                repaint: false,
+                consumed: false,
+            },
+        }
+    }
+
+    fn on_window_event(&mut self, event: WindowEvent) -> EventResponse {
+        match event {
+            WindowEvent::FocusGained | WindowEvent::FocusLost => {
+                let focused = event == WindowEvent::FocusGained;
+                self.egui_input.focused = focused;
+                self.egui_input
+                    .events
+                    .push(egui::Event::WindowFocused(focused));
+                EventResponse {
+                    repaint: true,
+                    consumed: false,
+                }
+            }
+            WindowEvent::Leave => {
+                self.pointer_pos_in_points = None;
+                self.egui_input.events.push(egui::Event::PointerGone);
+                EventResponse {
+                    repaint: true,
+                    consumed: false,
+                }
+            }
+
+            // Things that may require repaint:
+            WindowEvent::Shown
+            | WindowEvent::Exposed
+            | WindowEvent::Moved(..)
+            | WindowEvent::Resized(..)
+            | WindowEvent::SizeChanged(..)
+            | WindowEvent::Minimized
+            | WindowEvent::Maximized
+            | WindowEvent::Restored
+            | WindowEvent::Enter
+            | WindowEvent::DisplayChanged(..)
+            | WindowEvent::Close => EventResponse {
+                repaint: true,
+                consumed: false,
+            },
+
+            _ => EventResponse {
+                repaint: false,
+                consumed: false,
+            },
+        }
+    }
+
+    pub fn ime_event_enable(&mut self) {
+        if !self.has_sent_ime_enabled {
+            self.egui_input
+                .events
+                .push(egui::Event::Ime(egui::ImeEvent::Enabled));
+            self.has_sent_ime_enabled = true;
+        }
+    }
+
+    pub fn ime_event_disable(&mut self) {
+        self.egui_input
+            .events
+            .push(egui::Event::Ime(egui::ImeEvent::Disabled));
+        self.has_sent_ime_enabled = false;
+    }
+
+    /// Call this with the relative motion of the mouse, e.g. from [`Event::MouseMotion`]
+    /// while the mouse is in relative mode.
+    pub fn on_mouse_motion(&mut self, delta: (i32, i32)) {
+        self.egui_input.events.push(egui::Event::MouseMoved(Vec2 {
+            x: delta.0 as f32,
+            y: delta.1 as f32,
+        }));
+    }
+
+    fn on_mouse_button_input(&mut self, pressed: bool, button: MouseButton) {
+        if let Some(pos) = self.pointer_pos_in_points {
+            if let Some(button) = translate_mouse_button(button) {
+                self.egui_input.events.push(egui::Event::PointerButton {
+                    pos,
+                    button,
+                    pressed,
+                    modifiers: self.egui_input.modifiers,
+                });
+            }
+        }
+    }
+
+    /// `x` and `y` are in window coordinates.
+    fn on_cursor_moved(&mut self, x: f32, y: f32) {
+        // Window coordinates are points at the native scale, so only the zoom is left to apply.
+        let zoom_factor = self.egui_ctx.zoom_factor_of(self.viewport_id);
+        let pos_in_points = egui::pos2(x / zoom_factor, y / zoom_factor);
+        self.pointer_pos_in_points = Some(pos_in_points);
+        self.egui_input
+            .events
+            .push(egui::Event::PointerMoved(pos_in_points));
+    }
+
+    /// `normalized_pos` is in the range `0..=1` across the window.
+    fn on_touch(
+        &mut self,
+        window: &Window,
+        phase: egui::TouchPhase,
+        touch_id: i64,
+        finger_id: i64,
+        normalized_pos: Pos2,
+        pressure: f32,
+    ) -> EventResponse {
+        let (width, height) = window.size();
+        let x = normalized_pos.x * width as f32;
+        let y = normalized_pos.y * height as f32;
+        let zoom_factor = self.egui_ctx.zoom_factor_of(self.viewport_id);
+        let pos = egui::pos2(x / zoom_factor, y / zoom_factor);
+
+        let finger_id = finger_id as u64;
+        self.egui_input.events.push(egui::Event::Touch {
+            device_id: egui::TouchDeviceId(touch_id as u64),
+            id: egui::TouchId::from(finger_id),
+            phase,
+            pos,
+            force: Some(pressure),
+        });
+
+        // If we're not yet translating a touch or we're translating this very
+        // touch …
+        if self.pointer_touch_id.is_none() || self.pointer_touch_id == Some(finger_id) {
+            // … emit PointerButton resp. PointerMoved events to emulate mouse
+            match phase {
+                egui::TouchPhase::Start => {
+                    self.pointer_touch_id = Some(finger_id);
+                    // First move the pointer to the right location
+                    self.on_cursor_moved(x, y);
+                    self.on_mouse_button_input(true, MouseButton::Left);
+                }
+                egui::TouchPhase::Move => {
+                    self.on_cursor_moved(x, y);
+                }
+                egui::TouchPhase::End | egui::TouchPhase::Cancel => {
+                    self.pointer_touch_id = None;
+                    self.on_mouse_button_input(false, MouseButton::Left);
+                    // The pointer should vanish completely to not get any
+                    // hover effects
+                    self.pointer_pos_in_points = None;
+                    self.egui_input.events.push(egui::Event::PointerGone);
+                }
+            }
+        }
+
+        let consumed = match phase {
+            egui::TouchPhase::Start | egui::TouchPhase::End | egui::TouchPhase::Cancel => {
+                self.egui_ctx.wants_pointer_input()
+            }
+            egui::TouchPhase::Move => self.egui_ctx.is_using_pointer(),
+        };
+        EventResponse {
+            repaint: true,
+            consumed,
+        }
+    }
+
+    fn on_keyboard_input(
+        &mut self,
+        pressed: bool,
+        keycode: Option<Keycode>,
+        scancode: Option<Scancode>,
+        keymod: Mod,
+    ) {
+        // SDL has no events for modifier changes, but tells us the modifiers with every key event.
+        self.egui_input.modifiers = translate_modifiers(keymod);
+
+        // The keycode is what the key means in the current keyboard layout,
+        // while the scancode is the position of the key, e.g. for WASD controls in games.
+        let logical_key = keycode.and_then(key_from_keycode);
+        let physical_key = scancode.and_then(key_from_scancode);
+
+        // Helpful logging to enable when adding new key support
+        log::trace!(
+            "logical {:?} -> {:?},  physical {:?} -> {:?}",
+            keycode,
+            logical_key,
+            scancode,
+            physical_key
+        );
+
+        // "Logical OR physical key" is a fallback mechanism for keyboard layouts without Latin characters: it lets them
+        // emit events as if the corresponding keys from the Latin layout were pressed. In this case, clipboard shortcuts
+        // are mapped to the physical keys that normally contain C, X, V, etc.
+        // See also: https://github.com/emilk/egui/issues/3653
+        if let Some(active_key) = logical_key.or(physical_key) {
+            if pressed {
+                if is_cut_command(self.egui_input.modifiers, active_key) {
+                    self.egui_input.events.push(egui::Event::Cut);
+                    return;
+                } else if is_copy_command(self.egui_input.modifiers, active_key) {
+                    self.egui_input.events.push(egui::Event::Copy);
+                    return;
+                } else if is_paste_command(self.egui_input.modifiers, active_key) {
+                    self.push_paste_event();
+                    return;
+                }
+            }
+
+            self.egui_input.events.push(egui::Event::Key {
+                key: active_key,
+                physical_key,
+                scancode: scancode.map(|scancode| scancode as i32 as u32),
+                pressed,
+                repeat: false, // egui will fill this in for us!
+                modifiers: self.egui_input.modifiers,
+            });
+        }
+    }
+
+    /// Call with the output given by `egui`.
+    ///
+    /// This will, if needed:
+    /// * update the cursor
+    /// * copy text to the clipboard
+    /// * open any clicked urls
+    /// * start and stop text input, and place the IME
+    pub fn handle_platform_output(&mut self, platform_output: egui::PlatformOutput) {
+        crate::profile_function!();
+
+        // SDL can't start drags of the operating system, and has no AccessKit adapter.
+        // The other fields are handled elsewhere, as in egui-winit.
+        let egui::PlatformOutput {
+            cursor_icon,
+            open_url,
+            copied_text,
+            copied_data,
+            ime,
+            ..
+        } = platform_output;
+
+        self.set_cursor_icon(cursor_icon);
+
+        if let Some(open_url) = open_url {
+            if let Err(err) = sdl2::url::open_url(&open_url.url) {
+                log::warn!("Failed to open url: {err}");
+            }
+        }
+
+        if let Some(copied_data) = copied_data {
+            // SDL2 only has a text clipboard.
+            let text = copied_data.text_fallback();
+            if !text.is_empty() {
+                self.set_clipboard_text(&text);
+            }
+        } else if !copied_text.is_empty() {
+            self.set_clipboard_text(&copied_text);
+        }
+
+        let allow_ime = ime.is_some();
+        if self.allow_ime != allow_ime {
+            self.allow_ime = allow_ime;
+            crate::profile_scope!("text_input");
+            if allow_ime {
+                self.video.text_input().start();
+            } else {
+                self.video.text_input().stop();
+            }
+        }
+
+        if let Some(ime) = ime {
+            // SDL wants the rectangle in window coordinates.
+            let zoom_factor = self.egui_ctx.zoom_factor_of(self.viewport_id);
+            let rect = zoom_factor * ime.rect;
+            let ime_rect = sdl2::rect::Rect::new(
+                rect.min.x.round() as i32,
+                rect.min.y.round() as i32,
+                rect.width().round().max(1.0) as u32,
+                rect.height().round().max(1.0) as u32,
+            );
+            if self.ime_rect != Some(ime_rect) {
+                self.ime_rect = Some(ime_rect);
+                crate::profile_scope!("set_ime_rect");
+                self.video.text_input().set_rect(ime_rect);
+            }
+        } else {
+            self.ime_rect = None;
+        }
+    }
+
+    fn set_cursor_icon(&mut self, cursor_icon: egui::CursorIcon) {
+        if self.current_cursor_icon == Some(cursor_icon) {
+            // Early-out to save CPU.
+            return;
+        }
+        self.current_cursor_icon = Some(cursor_icon);
+
+        let mouse = self.video.sdl().mouse();
+        if let Some(system_cursor) = translate_cursor(cursor_icon) {
+            match Cursor::from_system(system_cursor) {
+                Ok(cursor) => {
+                    cursor.set();
+                    // The cursor must outlive its use:
+                    self.cursor = Some(cursor);
+                }
+                Err(err) => log::warn!("Failed to create cursor: {err}"),
+            }
+            mouse.show_cursor(true);
+        } else {
+            mouse.show_cursor(false);
+        }
+    }
+}
+
+/// Updates the [`ViewportInfo`] of the window, except for the monitor info,
+/// which SDL gives for displays rather than windows.
+pub fn update_viewport_info(
+    viewport_info: &mut ViewportInfo,
+    egui_ctx: &egui::Context,
+    window: &Window,
+) {
+    crate::profile_function!();
+
+    let native_pixels_per_point = native_pixels_per_point(window);
+    let zoom_factor = egui_ctx.zoom_factor();
+    let flags = window.window_flags();
+    let has_flag = |flag: sdl2::sys::SDL_WindowFlags| flags & flag as u32 != 0;
+
+    let (x, y) = window.position();
+    let (width, height) = window.size();
+    let inner_rect = Rect::from_min_size(
+        egui::pos2(x as f32, y as f32),
+        egui::vec2(width as f32, height as f32),
+    ) / zoom_factor;
+
+    viewport_info.title = Some(window.title().to_owned());
+    viewport_info.native_pixels_per_point = Some(native_pixels_per_point);
+    viewport_info.inner_rect = Some(inner_rect);
+    viewport_info.focused = Some(has_flag(sdl2::sys::SDL_WindowFlags::SDL_WINDOW_INPUT_FOCUS));
+    viewport_info.minimized = Some(has_flag(sdl2::sys::SDL_WindowFlags::SDL_WINDOW_MINIMIZED));
+    viewport_info.maximized = Some(has_flag(sdl2::sys::SDL_WindowFlags::SDL_WINDOW_MAXIMIZED));
+    viewport_info.fullscreen = Some(window.fullscreen_state() != FullscreenType::Off);
+}
+
+/// SDL sends special keys (backspace, delete, F1, …) as key events only,
+/// but ignore any control characters that still end up as text.
+fn is_printable_char(chr: char) -> bool {
+    let is_in_private_use_area = '\u{e000}' <= chr && chr <= '\u{f8ff}'
+        || '\u{f0000}' <= chr && chr <= '\u{ffffd}'
+        || '\u{100000}' <= chr && chr <= '\u{10fffd}';
+
+    !is_in_private_use_area && !chr.is_ascii_control()
+}
+
+fn is_cut_command(modifiers: egui::Modifiers, keycode: egui::Key) -> bool {
+    keycode == egui::Key::Cut
+        || (modifiers.command && keycode == egui::Key::X)
+        || (cfg!(target_os = "windows") && modifiers.shift && keycode == egui::Key::Delete)
+}
+
+fn is_copy_command(modifiers: egui::Modifiers, keycode: egui::Key) -> bool {
+    keycode == egui::Key::Copy
+        || (modifiers.command && keycode == egui::Key::C)
+        || (cfg!(target_os = "windows") && modifiers.ctrl && keycode == egui::Key::Insert)
+}
+
+fn is_paste_command(modifiers: egui::Modifiers, keycode: egui::Key) -> bool {
+    keycode == egui::Key::Paste
+        || (modifiers.command && keycode == egui::Key::V)
+        || (cfg!(target_os = "windows") && modifiers.shift && keycode == egui::Key::Insert)
+}
+
+fn translate_modifiers(keymod: Mod) -> egui::Modifiers {
+    let alt = keymod.intersects(Mod::LALTMOD | Mod::RALTMOD);
+    let ctrl = keymod.intersects(Mod::LCTRLMOD | Mod::RCTRLMOD);
+    let shift = keymod.intersects(Mod::LSHIFTMOD | Mod::RSHIFTMOD);
+    let super_ = keymod.intersects(Mod::LGUIMOD | Mod::RGUIMOD);
+
+    egui::Modifiers {
+        alt,
+        ctrl,
+        shift,
+        mac_cmd: cfg!(target_os = "macos") && super_,
+        command: if cfg!(target_os = "macos") {
+            super_
+        } else {
+            ctrl
+        },
+    }
+}
+
+fn translate_mouse_button(button: MouseButton) -> Option<egui::PointerButton> {
+    match button {
+        MouseButton::Left => Some(egui::PointerButton::Primary),
+        MouseButton::Right => Some(egui::PointerButton::Secondary),
+        MouseButton::Middle => Some(egui::PointerButton::Middle),
+        MouseButton::X1 => Some(egui::PointerButton::Extra1),
+        MouseButton::X2 => Some(egui::PointerButton::Extra2),
+        MouseButton::Unknown => None,
+    }
+}
+
+/// The key as labeled in the current keyboard layout.
+fn key_from_keycode(keycode: Keycode) -> Option<egui::Key> {
+    key_from_sdl_name(&keycode.name())
+}
+
+/// The key at this position on a US keyboard.
+fn key_from_scancode(scancode: Scancode) -> Option<egui::Key> {
+    key_from_sdl_name(scancode.name())
+}
+
+/// SDL names keys mostly like egui does, e.g. `"A"`, `"Return"`, `"PageUp"`, `"Keypad 7"` and `"F1"`.
+fn key_from_sdl_name(name: &str) -> Option<egui::Key> {
+    let name = name.strip_prefix("Keypad ").unwrap_or(name);
+    egui::Key::from_name(name)
+}
+
+fn translate_cursor(cursor_icon: egui::CursorIcon) -> Option<SystemCursor> {
+    match cursor_icon {
+        egui::CursorIcon::None => None,
+
+        egui::CursorIcon::Default
+        | egui::CursorIcon::Alias
+        | egui::CursorIcon::Cell
+        | egui::CursorIcon::ContextMenu
+        | egui::CursorIcon::Copy
+        | egui::CursorIcon::Help
+        | egui::CursorIcon::ZoomIn
+        | egui::CursorIcon::ZoomOut
+        | egui::CursorIcon::VerticalText => Some(SystemCursor::Arrow),
+
+        egui::CursorIcon::Crosshair => Some(SystemCursor::Crosshair),
+        egui::CursorIcon::PointingHand | egui::CursorIcon::Grab | egui::CursorIcon::Grabbing => {
+            Some(SystemCursor::Hand)
+        }
+        egui::CursorIcon::AllScroll | egui::CursorIcon::Move => Some(SystemCursor::SizeAll),
+        egui::CursorIcon::NoDrop | egui::CursorIcon::NotAllowed => Some(SystemCursor::No),
+        egui::CursorIcon::Progress => Some(SystemCursor::WaitArrow),
+        egui::CursorIcon::Wait => Some(SystemCursor::Wait),
+        egui::CursorIcon::Text => Some(SystemCursor::IBeam),
+
+        egui::CursorIcon::ResizeHorizontal
+        | egui::CursorIcon::ResizeEast
+        | egui::CursorIcon::ResizeWest
+        | egui::CursorIcon::ResizeColumn => Some(SystemCursor::SizeWE),
+        egui::CursorIcon::ResizeVertical
+        | egui::CursorIcon::ResizeNorth
+        | egui::CursorIcon::ResizeSouth
+        | egui::CursorIcon::ResizeRow => Some(SystemCursor::SizeNS),
+        egui::CursorIcon::ResizeNeSw
+        | egui::CursorIcon::ResizeNorthEast
+        | egui::CursorIcon::ResizeSouthWest => Some(SystemCursor::SizeNESW),
+        egui::CursorIcon::ResizeNwSe
+        | egui::CursorIcon::ResizeNorthWest
+        | egui::CursorIcon::ResizeSouthEast => Some(SystemCursor::SizeNWSE),
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+mod profiling_scopes {
+    #![allow(unused_macros)]
+    #![allow(unused_imports)]
+
+    /// Profiling macro for feature "puffin"
+    macro_rules! profile_function {
+        ($($arg: tt)*) => {
+            #[cfg(feature = "puffin")]
+            #[cfg(not(target_arch = "wasm32"))] // Disabled on web because of the coarse 1ms clock resolution there.
+            puffin::profile_function!($($arg)*);
+        };
+    }
+    pub(crate) use profile_function;
+
+    /// Profiling macro for feature "puffin"
+    macro_rules! profile_scope {
+        ($($arg: tt)*) => {
+            #[cfg(feature = "puffin")]
+            #[cfg(not(target_arch = "wasm32"))] // Disabled on web because of the coarse 1ms clock resolution there.
+            puffin::profile_scope!($($arg)*);
+        };
+    }
+    pub(crate) use profile_scope;
+}
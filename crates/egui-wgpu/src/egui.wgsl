@@ -9,9 +9,10 @@ struct VertexOutput {
 struct Locals {
     screen_size: vec2<f32>,
     dithering: u32, // 1 if dithering is enabled, 0 otherwise
-    // Uniform buffers need to be at least 16 bytes in WebGL.
+    // The color space of the render target: 0 = sRGB, 1 = Display P3, 2 = linear sRGB.
+    // Also makes the buffer 16 bytes, which uniform buffers need to be at least in WebGL.
     // See https://github.com/gfx-rs/wgpu/issues/2072
-    _padding: u32,
+    color_space: u32,
 };
 @group(0) @binding(0) var<uniform> r_locals: Locals;
 
@@ -58,6 +59,34 @@ fn gamma_from_linear_rgba(linear_rgba: vec4<f32>) -> vec4<f32> {
     return vec4<f32>(gamma_from_linear_rgb(linear_rgba.rgb), linear_rgba.a);
 }
 
+// Linear Display P3  from  linear sRGB (column major), see `ecolor::LINEAR_DISPLAY_P3_FROM_LINEAR_SRGB`.
+const P3_FROM_SRGB = mat3x3<f32>(
+    vec3<f32>(0.8224621, 0.0331942, 0.0170827),
+    vec3<f32>(0.1775380, 0.9668058, 0.0723974),
+    vec3<f32>(0.0, 0.0, 0.9105199),
+);
+
+// 0-1 linear in the output color space  from  0-1 linear sRGB
+fn output_linear_from_linear_rgb(rgb: vec3<f32>) -> vec3<f32> {
+    if r_locals.color_space == 1u {
+        return P3_FROM_SRGB * rgb;
+    }
+    return rgb;
+}
+
+// The output color space  from  0-1 sRGB gamma
+fn output_from_gamma_rgb(srgb: vec3<f32>) -> vec3<f32> {
+    if r_locals.color_space == 0u {
+        return srgb;
+    }
+    let rgb_linear = linear_from_gamma_rgb(srgb);
+    if r_locals.color_space == 2u {
+        return rgb_linear;
+    }
+    // Display P3 uses the same transfer function as sRGB:
+    return gamma_from_linear_rgb(P3_FROM_SRGB * rgb_linear);
+}
+
 // [u8; 4] SRGB as u32 -> [r, g, b, a] in 0.-1
 fn unpack_color(color: u32) -> vec4<f32> {
     return vec4<f32>(
@@ -95,11 +124,24 @@ fn vs_main(
 @group(1) @binding(0) var r_tex_color: texture_2d<f32>;
 @group(1) @binding(1) var r_tex_sampler: sampler;
 
-@fragment
-fn fs_main_linear_framebuffer(in: VertexOutput) -> @location(0) vec4<f32> {
+// The texture color (in gamma) of a mesh without `Mesh::distance_field`.
+fn texture_gamma(tex_coord: vec2<f32>) -> vec4<f32> {
     // We always have an sRGB aware texture at the moment.
-    let tex_linear = textureSample(r_tex_color, r_tex_sampler, in.tex_coord);
-    let tex_gamma = gamma_from_linear_rgba(tex_linear);
+    let tex_linear = textureSample(r_tex_color, r_tex_sampler, tex_coord);
+    return gamma_from_linear_rgba(tex_linear);
+}
+
+// The coverage of a glyph (in gamma) for a mesh with `Mesh::distance_field`,
+// where the alpha of the texture is the distance field, with 0.5 on the outline.
+fn distance_field_texture_gamma(tex_coord: vec2<f32>) -> vec4<f32> {
+    // The alpha is not affected by the sRGB conversion:
+    let distance = textureSample(r_tex_color, r_tex_sampler, tex_coord).a;
+    // Smooth the edge over one pixel on screen, however much the glyph is scaled:
+    let half_width = max(0.5 * length(vec2<f32>(dpdx(distance), dpdy(distance))), 0.0001);
+    return vec4<f32>(smoothstep(0.5 - half_width, 0.5 + half_width, distance));
+}
+
+fn linear_framebuffer_color(in: VertexOutput, tex_gamma: vec4<f32>) -> vec4<f32> {
     var out_color_gamma = in.color * tex_gamma;
     // Dither the float color down to eight bits to reduce banding.
     // This step is optional for egui backends.
@@ -109,15 +151,11 @@ fn fs_main_linear_framebuffer(in: VertexOutput) -> @location(0) vec4<f32> {
         let out_color_gamma_rgb = dither_interleaved(out_color_gamma.rgb, 256.0, in.position);
         out_color_gamma = vec4<f32>(out_color_gamma_rgb, out_color_gamma.a);
     }
-    let out_color_linear = linear_from_gamma_rgb(out_color_gamma.rgb);
+    let out_color_linear = output_linear_from_linear_rgb(linear_from_gamma_rgb(out_color_gamma.rgb));
     return vec4<f32>(out_color_linear, out_color_gamma.a);
 }
 
-@fragment
-fn fs_main_gamma_framebuffer(in: VertexOutput) -> @location(0) vec4<f32> {
-    // We always have an sRGB aware texture at the moment.
-    let tex_linear = textureSample(r_tex_color, r_tex_sampler, in.tex_coord);
-    let tex_gamma = gamma_from_linear_rgba(tex_linear);
+fn gamma_framebuffer_color(in: VertexOutput, tex_gamma: vec4<f32>) -> vec4<f32> {
     var out_color_gamma = in.color * tex_gamma;
     // Dither the float color down to eight bits to reduce banding.
     // This step is optional for egui backends.
@@ -125,5 +163,25 @@ fn fs_main_gamma_framebuffer(in: VertexOutput) -> @location(0) vec4<f32> {
         let out_color_gamma_rgb = dither_interleaved(out_color_gamma.rgb, 256.0, in.position);
         out_color_gamma = vec4<f32>(out_color_gamma_rgb, out_color_gamma.a);
     }
-    return out_color_gamma;
+    return vec4<f32>(output_from_gamma_rgb(out_color_gamma.rgb), out_color_gamma.a);
+}
+
+@fragment
+fn fs_main_linear_framebuffer(in: VertexOutput) -> @location(0) vec4<f32> {
+    return linear_framebuffer_color(in, texture_gamma(in.tex_coord));
+}
+
+@fragment
+fn fs_main_gamma_framebuffer(in: VertexOutput) -> @location(0) vec4<f32> {
+    return gamma_framebuffer_color(in, texture_gamma(in.tex_coord));
+}
+
+@fragment
+fn fs_main_linear_framebuffer_distance_field(in: VertexOutput) -> @location(0) vec4<f32> {
+    return linear_framebuffer_color(in, distance_field_texture_gamma(in.tex_coord));
+}
+
+@fragment
+fn fs_main_gamma_framebuffer_distance_field(in: VertexOutput) -> @location(0) vec4<f32> {
+    return gamma_framebuffer_color(in, distance_field_texture_gamma(in.tex_coord));
 }
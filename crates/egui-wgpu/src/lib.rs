@@ -24,6 +24,14 @@ pub use wgpu;
 mod renderer;
 
 pub use renderer::*;
+
+mod quad_layer;
+
+pub use quad_layer::{QuadLayer, QuadLayerTarget};
+
+mod render_hooks;
+
+pub use render_hooks::{RenderHookContext, RenderHooks};
 use wgpu::{Adapter, Device, Instance, Queue};
 
 /// Module for painting [`egui`](https://github.com/emilk/egui) with [`wgpu`] on [`winit`].
@@ -78,6 +86,9 @@ pub struct RenderState {
 
     /// Egui renderer responsible for drawing the UI.
     pub renderer: Arc<RwLock<Renderer>>,
+
+    /// Render passes of the app to run before and after the egui pass.
+    pub render_hooks: Arc<RwLock<RenderHooks>>,
 }
 
 impl RenderState {
@@ -204,6 +215,7 @@ impl RenderState {
             queue,
             target_format,
             renderer: Arc::new(RwLock::new(renderer)),
+            render_hooks: Default::default(),
         })
     }
 }
@@ -311,6 +323,17 @@ pub struct WgpuConfiguration {
 
     /// Callback for surface errors.
     pub on_surface_error: Arc<dyn Fn(wgpu::SurfaceError) -> SurfaceErrorAction + Send + Sync>,
+
+    /// Paint each deferred viewport on a thread of its own, with its own surface and queue submissions.
+    ///
+    /// The ui of all viewports still runs on the main thread,
+    /// but an expensive viewport (e.g. a 3D preview with heavy paint callbacks)
+    /// won't hold back the frame rate of the others, and neither will waiting for its vsync.
+    ///
+    /// Only used by the `winit` integration on native, see `winit::ViewportRenderThread`.
+    ///
+    /// Default: `false`.
+    pub threaded_deferred_viewports: bool,
 }
 
 #[test]
@@ -326,6 +349,7 @@ impl std::fmt::Debug for WgpuConfiguration {
             desired_maximum_frame_latency,
             wgpu_setup,
             on_surface_error: _,
+            threaded_deferred_viewports,
         } = self;
         f.debug_struct("WgpuConfiguration")
             .field("present_mode", &present_mode)
@@ -334,6 +358,7 @@ impl std::fmt::Debug for WgpuConfiguration {
                 &desired_maximum_frame_latency,
             )
             .field("wgpu_setup", &wgpu_setup)
+            .field("threaded_deferred_viewports", &threaded_deferred_viewports)
             .finish_non_exhaustive()
     }
 }
@@ -388,6 +413,8 @@ impl Default for WgpuConfiguration {
                 }
                 SurfaceErrorAction::SkipFrame
             }),
+
+            threaded_deferred_viewports: false,
         }
     }
 }
@@ -0,0 +1,468 @@
+//! Showing egui on a quad in a 3D scene, e.g. a panel in an XR app.
+
+use std::{borrow::Cow, num::NonZeroU64};
+
+use wgpu::util::DeviceExt as _;
+
+use crate::{Renderer, ScreenDescriptor, TextureTarget};
+
+/// Uniform buffer of `quad_layer.wgsl`.
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+#[repr(C)]
+struct QuadUniforms {
+    clip_from_points: [[f32; 4]; 4],
+    size_in_points: [f32; 2],
+    _padding: [f32; 2],
+}
+
+/// What a [`QuadLayer`] is painted into with [`QuadLayer::paint`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct QuadLayerTarget {
+    /// The color format of the views.
+    pub color_format: wgpu::TextureFormat,
+
+    /// The depth format of the views, if any.
+    ///
+    /// If set, the quad is depth tested against (and written to) the depth buffer,
+    /// so it is hidden behind nearer parts of your scene.
+    pub depth_format: Option<wgpu::TextureFormat>,
+
+    /// The number of MSAA samples per pixel of the views.
+    pub msaa_samples: u32,
+}
+
+/// Renders egui once into a texture, and then paints that texture as a quad into any number of views,
+/// each with its own transform.
+///
+/// This is how you show an egui panel in the world of a 3D or XR app,
+/// e.g. once for each eye of an `OpenXR` headset:
+///
+/// 1. Run egui and tessellate its output once per frame.
+/// 2. Call [`Self::update`] to render the layer, applying the textures delta to the [`Renderer`].
+/// 3. Call [`Self::set_views`] with the transform of each view (e.g. each eye).
+/// 4. Call [`Self::paint`] in the render pass of each view.
+///
+/// Alternatively, give [`Self::texture`] to a compositor, e.g. as an `OpenXR` quad composition layer.
+///
+/// The [`Renderer`] used with this must have been created without a depth format.
+pub struct QuadLayer {
+    texture: wgpu::Texture,
+    texture_view: wgpu::TextureView,
+
+    /// Used if the [`Renderer`] renders with MSAA.
+    msaa_texture_view: Option<(u32, wgpu::TextureView)>,
+
+    size_in_pixels: [u32; 2],
+    pixels_per_point: f32,
+
+    target: QuadLayerTarget,
+    pipeline: wgpu::RenderPipeline,
+    uniform_bind_group_layout: wgpu::BindGroupLayout,
+    texture_bind_group_layout: wgpu::BindGroupLayout,
+    texture_bind_group: wgpu::BindGroup,
+    sampler: wgpu::Sampler,
+
+    /// One uniform buffer and bind group per view.
+    views: Vec<(wgpu::Buffer, wgpu::BindGroup)>,
+}
+
+impl QuadLayer {
+    /// Create a layer of the given size, to be painted into views described by `target`.
+    ///
+    /// The layer texture gets the output color format of the `renderer`.
+    pub fn new(
+        device: &wgpu::Device,
+        renderer: &Renderer,
+        size_in_pixels: [u32; 2],
+        pixels_per_point: f32,
+        target: QuadLayerTarget,
+    ) -> Self {
+        crate::profile_function!();
+
+        let (texture, texture_view) =
+            create_layer_texture(device, renderer.output_color_format(), size_in_pixels);
+
+        let uniform_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("egui_quad_layer_uniform_bind_group_layout"),
+                entries: &[wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX,
+                    ty: wgpu::BindingType::Buffer {
+                        has_dynamic_offset: false,
+                        min_binding_size: NonZeroU64::new(std::mem::size_of::<QuadUniforms>() as _),
+                        ty: wgpu::BufferBindingType::Uniform,
+                    },
+                    count: None,
+                }],
+            });
+
+        let texture_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("egui_quad_layer_texture_bind_group_layout"),
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            multisampled: false,
+                            sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                        count: None,
+                    },
+                ],
+            });
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("egui_quad_layer_sampler"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        let texture_bind_group = create_texture_bind_group(
+            device,
+            &texture_bind_group_layout,
+            &texture,
+            &sampler,
+            target.color_format,
+        );
+
+        let pipeline = create_quad_pipeline(
+            device,
+            &uniform_bind_group_layout,
+            &texture_bind_group_layout,
+            target,
+        );
+
+        Self {
+            texture,
+            texture_view,
+            msaa_texture_view: None,
+            size_in_pixels,
+            pixels_per_point,
+            target,
+            pipeline,
+            uniform_bind_group_layout,
+            texture_bind_group_layout,
+            texture_bind_group,
+            sampler,
+            views: Vec::new(),
+        }
+    }
+
+    /// The texture egui is rendered into.
+    ///
+    /// It contains gamma-encoded, premultiplied colors.
+    #[inline]
+    pub fn texture(&self) -> &wgpu::Texture {
+        &self.texture
+    }
+
+    /// The size of the layer texture.
+    #[inline]
+    pub fn size_in_pixels(&self) -> [u32; 2] {
+        self.size_in_pixels
+    }
+
+    /// The size of the layer in egui points.
+    ///
+    /// Give this to egui as the screen rect of the layer.
+    #[inline]
+    pub fn size_in_points(&self) -> [f32; 2] {
+        [
+            self.size_in_pixels[0] as f32 / self.pixels_per_point,
+            self.size_in_pixels[1] as f32 / self.pixels_per_point,
+        ]
+    }
+
+    /// Change the resolution of the layer.
+    pub fn resize(
+        &mut self,
+        device: &wgpu::Device,
+        renderer: &Renderer,
+        size_in_pixels: [u32; 2],
+        pixels_per_point: f32,
+    ) {
+        self.pixels_per_point = pixels_per_point;
+        if self.size_in_pixels == size_in_pixels {
+            return;
+        }
+        crate::profile_function!();
+
+        let (texture, texture_view) =
+            create_layer_texture(device, renderer.output_color_format(), size_in_pixels);
+        self.texture_bind_group = create_texture_bind_group(
+            device,
+            &self.texture_bind_group_layout,
+            &texture,
+            &self.sampler,
+            self.target.color_format,
+        );
+        self.texture = texture;
+        self.texture_view = texture_view;
+        self.msaa_texture_view = None;
+        self.size_in_pixels = size_in_pixels;
+    }
+
+    /// Render egui into the layer texture, clearing it to `clear_color` first.
+    ///
+    /// The `textures_delta` is applied to the `renderer`, as in [`Renderer::render_to_texture`].
+    pub fn update(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        renderer: &mut Renderer,
+        paint_jobs: &[epaint::ClippedPrimitive],
+        clear_color: wgpu::Color,
+        textures_delta: &epaint::textures::TexturesDelta,
+    ) {
+        crate::profile_function!();
+
+        let screen_descriptor = ScreenDescriptor {
+            size_in_pixels: self.size_in_pixels,
+            pixels_per_point: self.pixels_per_point,
+        };
+
+        let msaa_samples = renderer.msaa_samples();
+        if msaa_samples <= 1 {
+            renderer.render_to_texture(
+                device,
+                queue,
+                &TextureTarget {
+                    view: &self.texture_view,
+                    resolve_target: None,
+                    clear_color,
+                },
+                paint_jobs,
+                &screen_descriptor,
+                textures_delta,
+            );
+            return;
+        }
+
+        if self
+            .msaa_texture_view
+            .as_ref()
+            .map_or(true, |(samples, _)| *samples != msaa_samples)
+        {
+            let format = renderer.output_color_format();
+            let view = device
+                .create_texture(&wgpu::TextureDescriptor {
+                    label: Some("egui_quad_layer_msaa_texture"),
+                    size: extent(self.size_in_pixels),
+                    mip_level_count: 1,
+                    sample_count: msaa_samples,
+                    dimension: wgpu::TextureDimension::D2,
+                    format,
+                    usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+                    view_formats: &[format],
+                })
+                .create_view(&wgpu::TextureViewDescriptor::default());
+            self.msaa_texture_view = Some((msaa_samples, view));
+        }
+        if let Some((_, msaa_texture_view)) = &self.msaa_texture_view {
+            renderer.render_to_texture(
+                device,
+                queue,
+                &TextureTarget {
+                    view: msaa_texture_view,
+                    resolve_target: Some(&self.texture_view),
+                    clear_color,
+                },
+                paint_jobs,
+                &screen_descriptor,
+                textures_delta,
+            );
+        }
+    }
+
+    /// Set the transform of each view the layer will be painted into.
+    ///
+    /// Each transform is a column-major matrix that maps egui points of the layer
+    /// (x right, y down, origin in the top left corner) to clip space of the view.
+    /// Usually this is `projection * view * model`, where `model` scales the layer from points
+    /// to the units of your scene, flips the y axis, and places it in the world.
+    pub fn set_views(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        clip_from_points: &[[[f32; 4]; 4]],
+    ) {
+        crate::profile_function!();
+
+        let size_in_points = self.size_in_points();
+        let uniforms = |clip_from_points| QuadUniforms {
+            clip_from_points,
+            size_in_points,
+            _padding: [0.0; 2],
+        };
+
+        for (i, transform) in clip_from_points.iter().enumerate() {
+            let contents = uniforms(*transform);
+            if let Some((buffer, _)) = self.views.get(i) {
+                queue.write_buffer(buffer, 0, bytemuck::cast_slice(&[contents]));
+            } else {
+                let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: Some("egui_quad_layer_uniform_buffer"),
+                    contents: bytemuck::cast_slice(&[contents]),
+                    usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                });
+                let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                    label: Some("egui_quad_layer_uniform_bind_group"),
+                    layout: &self.uniform_bind_group_layout,
+                    entries: &[wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: buffer.as_entire_binding(),
+                    }],
+                });
+                self.views.push((buffer, bind_group));
+            }
+        }
+        self.views.truncate(clip_from_points.len());
+    }
+
+    /// Paint the layer into the view with the given index (see [`Self::set_views`]).
+    ///
+    /// The render pass must match the [`QuadLayerTarget`] the layer was created with.
+    pub fn paint(&self, render_pass: &mut wgpu::RenderPass<'_>, view_index: usize) {
+        let Some((_, uniform_bind_group)) = self.views.get(view_index) else {
+            log::warn!("QuadLayer::paint: no view with index {view_index} - call set_views first");
+            return;
+        };
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, uniform_bind_group, &[]);
+        render_pass.set_bind_group(1, &self.texture_bind_group, &[]);
+        render_pass.draw(0..4, 0..1);
+    }
+}
+
+fn extent(size_in_pixels: [u32; 2]) -> wgpu::Extent3d {
+    wgpu::Extent3d {
+        width: size_in_pixels[0].max(1),
+        height: size_in_pixels[1].max(1),
+        depth_or_array_layers: 1,
+    }
+}
+
+fn create_layer_texture(
+    device: &wgpu::Device,
+    format: wgpu::TextureFormat,
+    size_in_pixels: [u32; 2],
+) -> (wgpu::Texture, wgpu::TextureView) {
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("egui_quad_layer_texture"),
+        size: extent(size_in_pixels),
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+            | wgpu::TextureUsages::TEXTURE_BINDING
+            | wgpu::TextureUsages::COPY_SRC,
+        view_formats: &[format.add_srgb_suffix(), format.remove_srgb_suffix()],
+    });
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+    (texture, view)
+}
+
+fn create_texture_bind_group(
+    device: &wgpu::Device,
+    layout: &wgpu::BindGroupLayout,
+    texture: &wgpu::Texture,
+    sampler: &wgpu::Sampler,
+    target_color_format: wgpu::TextureFormat,
+) -> wgpu::BindGroup {
+    // The texture holds gamma-encoded colors (however it was rendered).
+    // Sampling it through an sRGB view decodes them to linear, which is what an sRGB target expects:
+    let view_format = if target_color_format.is_srgb() {
+        texture.format().add_srgb_suffix()
+    } else {
+        texture.format().remove_srgb_suffix()
+    };
+    let view = texture.create_view(&wgpu::TextureViewDescriptor {
+        format: Some(view_format),
+        ..Default::default()
+    });
+    device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("egui_quad_layer_texture_bind_group"),
+        layout,
+        entries: &[
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::TextureView(&view),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: wgpu::BindingResource::Sampler(sampler),
+            },
+        ],
+    })
+}
+
+fn create_quad_pipeline(
+    device: &wgpu::Device,
+    uniform_bind_group_layout: &wgpu::BindGroupLayout,
+    texture_bind_group_layout: &wgpu::BindGroupLayout,
+    target: QuadLayerTarget,
+) -> wgpu::RenderPipeline {
+    crate::profile_function!();
+
+    let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("egui_quad_layer"),
+        source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(include_str!("quad_layer.wgsl"))),
+    });
+
+    let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some("egui_quad_layer_pipeline_layout"),
+        bind_group_layouts: &[uniform_bind_group_layout, texture_bind_group_layout],
+        push_constant_ranges: &[],
+    });
+
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("egui_quad_layer_pipeline"),
+        layout: Some(&layout),
+        vertex: wgpu::VertexState {
+            module: &module,
+            entry_point: Some("vs_main"),
+            buffers: &[],
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        },
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleStrip,
+            cull_mode: None,
+            ..Default::default()
+        },
+        depth_stencil: target.depth_format.map(|format| wgpu::DepthStencilState {
+            format,
+            depth_write_enabled: true,
+            depth_compare: wgpu::CompareFunction::Less,
+            stencil: wgpu::StencilState::default(),
+            bias: wgpu::DepthBiasState::default(),
+        }),
+        multisample: wgpu::MultisampleState {
+            count: target.msaa_samples,
+            ..Default::default()
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: &module,
+            entry_point: Some("fs_main"),
+            targets: &[Some(wgpu::ColorTargetState {
+                format: target.color_format,
+                // egui colors are premultiplied:
+                blend: Some(wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        }),
+        multiview: None,
+        cache: None,
+    })
+}
@@ -0,0 +1,33 @@
+// Draws the texture of a `QuadLayer` as a quad with an arbitrary transform.
+
+struct VertexOutput {
+    @location(0) tex_coord: vec2<f32>,
+    @builtin(position) position: vec4<f32>,
+};
+
+struct Locals {
+    // Maps egui points of the layer to clip space of the view.
+    clip_from_points: mat4x4<f32>,
+    size_in_points: vec2<f32>,
+    _padding: vec2<f32>,
+};
+@group(0) @binding(0) var<uniform> r_locals: Locals;
+
+@group(1) @binding(0) var r_tex_color: texture_2d<f32>;
+@group(1) @binding(1) var r_tex_sampler: sampler;
+
+// Drawn as a triangle strip with four vertices.
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    let tex_coord = vec2<f32>(f32(vertex_index & 1u), f32(vertex_index >> 1u));
+    var out: VertexOutput;
+    out.tex_coord = tex_coord;
+    out.position = r_locals.clip_from_points * vec4<f32>(tex_coord * r_locals.size_in_points, 0.0, 1.0);
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    // The texture view is picked so that this matches the color space of the target.
+    return textureSample(r_tex_color, r_tex_sampler, in.tex_coord);
+}
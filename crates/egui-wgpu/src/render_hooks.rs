@@ -0,0 +1,122 @@
+//! Render passes of the app that run before and after the egui pass, e.g. to draw a 3D scene underneath the UI.
+
+use crate::ScreenDescriptor;
+
+/// What a render hook gets to render with. See [`RenderHooks`].
+pub struct RenderHookContext<'a> {
+    /// The viewport being painted.
+    pub viewport_id: egui::ViewportId,
+
+    pub device: &'a wgpu::Device,
+    pub queue: &'a wgpu::Queue,
+
+    /// The encoder that egui also records its render pass into,
+    /// so everything is submitted together.
+    pub encoder: &'a mut wgpu::CommandEncoder,
+
+    /// The view to render into.
+    ///
+    /// Before the egui pass this is the multisampled texture if MSAA is on,
+    /// which egui resolves into the surface texture when it is done.
+    /// After the egui pass this is always the surface texture (or the texture of a screenshot).
+    pub color_view: &'a wgpu::TextureView,
+
+    /// The format of [`Self::color_view`].
+    pub color_format: wgpu::TextureFormat,
+
+    /// The number of MSAA samples per pixel of [`Self::color_view`].
+    pub msaa_samples: u32,
+
+    /// The size of the surface, and the scale of egui on it.
+    pub screen_descriptor: &'a ScreenDescriptor,
+}
+
+type RenderHook = Box<dyn FnMut(&mut RenderHookContext<'_>) + Send + Sync>;
+
+/// Render passes of the app that run before and after the egui pass,
+/// using the same encoder and surface texture.
+///
+/// This lets you draw e.g. a 3D scene underneath the UI, or an overlay on top of it,
+/// without forking the integration.
+/// Find it in [`crate::RenderState::render_hooks`], e.g. from the `wgpu_render_state` of `eframe`:
+///
+/// ```
+/// # fn register(render_state: &egui_wgpu::RenderState) {
+/// render_state.render_hooks.write().add_pre_ui(|ctx| {
+///     let mut render_pass = ctx.encoder.begin_render_pass(&egui_wgpu::wgpu::RenderPassDescriptor {
+///         label: Some("my_scene"),
+///         color_attachments: &[Some(egui_wgpu::wgpu::RenderPassColorAttachment {
+///             view: ctx.color_view,
+///             resolve_target: None,
+///             ops: egui_wgpu::wgpu::Operations {
+///                 load: egui_wgpu::wgpu::LoadOp::Load,
+///                 store: egui_wgpu::wgpu::StoreOp::Store,
+///             },
+///         })],
+///         ..Default::default()
+///     });
+///     // Draw the scene…
+/// });
+/// # }
+/// ```
+///
+/// With any pre-UI hooks, the surface is cleared to the clear color before they run,
+/// and egui then paints on top of what they rendered.
+/// Render passes of the hooks must load and store the color attachment.
+///
+/// Run by [`crate::winit::Painter`], for every viewport.
+#[derive(Default)]
+pub struct RenderHooks {
+    pre_ui: Vec<RenderHook>,
+    post_ui: Vec<RenderHook>,
+}
+
+impl RenderHooks {
+    /// Add a hook that runs before the egui pass, e.g. to draw a scene underneath the UI.
+    pub fn add_pre_ui(
+        &mut self,
+        hook: impl FnMut(&mut RenderHookContext<'_>) + Send + Sync + 'static,
+    ) {
+        self.pre_ui.push(Box::new(hook));
+    }
+
+    /// Add a hook that runs after the egui pass, e.g. to draw an overlay or post-process the frame.
+    pub fn add_post_ui(
+        &mut self,
+        hook: impl FnMut(&mut RenderHookContext<'_>) + Send + Sync + 'static,
+    ) {
+        self.post_ui.push(Box::new(hook));
+    }
+
+    /// Remove all hooks.
+    pub fn clear(&mut self) {
+        self.pre_ui.clear();
+        self.post_ui.clear();
+    }
+
+    /// Are there any hooks to run before the egui pass?
+    pub fn has_pre_ui(&self) -> bool {
+        !self.pre_ui.is_empty()
+    }
+
+    /// Are there any hooks to run after the egui pass?
+    pub fn has_post_ui(&self) -> bool {
+        !self.post_ui.is_empty()
+    }
+
+    /// Run the hooks that go before the egui pass.
+    pub fn run_pre_ui(&mut self, ctx: &mut RenderHookContext<'_>) {
+        crate::profile_function!();
+        for hook in &mut self.pre_ui {
+            hook(ctx);
+        }
+    }
+
+    /// Run the hooks that go after the egui pass.
+    pub fn run_post_ui(&mut self, ctx: &mut RenderHookContext<'_>) {
+        crate::profile_function!();
+        for hook in &mut self.post_ui {
+            hook(ctx);
+        }
+    }
+}
@@ -3,7 +3,7 @@
 use std::{borrow::Cow, num::NonZeroU64, ops::Range};
 
 use ahash::HashMap;
-use epaint::{emath::NumExt, PaintCallbackInfo, Primitive, Vertex};
+use epaint::{ecolor::ColorSpace, emath::NumExt, BlendMode, PaintCallbackInfo, Primitive, Vertex};
 
 use wgpu::util::DeviceExt as _;
 
@@ -139,21 +139,50 @@ impl ScreenDescriptor {
     }
 }
 
+/// The texture [`Renderer::render_to_texture`] renders into.
+pub struct TextureTarget<'a> {
+    /// The view to render into.
+    ///
+    /// It must have the `output_color_format` the [`Renderer`] was created with,
+    /// and [`Renderer::msaa_samples`] samples per pixel.
+    pub view: &'a wgpu::TextureView,
+
+    /// If the [`Renderer`] uses more than one sample per pixel,
+    /// the result is resolved into this single-sampled view, which you then use instead of [`Self::view`].
+    pub resolve_target: Option<&'a wgpu::TextureView>,
+
+    /// The target is cleared to this color first.
+    pub clear_color: wgpu::Color,
+}
+
 /// Uniform buffer used when rendering.
 #[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
 #[repr(C)]
 struct UniformBuffer {
     screen_size_in_points: [f32; 2],
     dithering: u32,
-    // Uniform buffers need to be at least 16 bytes in WebGL.
-    // See https://github.com/gfx-rs/wgpu/issues/2072
-    _padding: u32,
+
+    /// See [`color_space_uniform`].
+    ///
+    /// Also makes the buffer 16 bytes, which uniform buffers need to be at least in WebGL.
+    /// See https://github.com/gfx-rs/wgpu/issues/2072
+    color_space: u32,
 }
 
 impl PartialEq for UniformBuffer {
     fn eq(&self, other: &Self) -> bool {
         self.screen_size_in_points == other.screen_size_in_points
             && self.dithering == other.dithering
+            && self.color_space == other.color_space
+    }
+}
+
+/// Must match `egui.wgsl`.
+fn color_space_uniform(color_space: ColorSpace) -> u32 {
+    match color_space {
+        ColorSpace::Srgb => 0,
+        ColorSpace::DisplayP3 => 1,
+        ColorSpace::Linear => 2,
     }
 }
 
@@ -179,6 +208,26 @@ pub struct Texture {
 pub struct Renderer {
     pipeline: wgpu::RenderPipeline,
 
+    /// Pipelines for the other [`BlendMode`]s than [`BlendMode::Normal`] (which uses [`Self::pipeline`]),
+    /// and for meshes with [`epaint::Mesh::distance_field`], by blend mode and whether it is a distance field.
+    ///
+    /// Created in [`Self::update_buffers`] when first needed.
+    other_pipelines: HashMap<(BlendMode, bool), wgpu::RenderPipeline>,
+
+    /// The sample count [`Self::pipeline`] was created with.
+    pipeline_msaa_samples: u32,
+
+    /// The sample count set with [`Self::set_msaa_samples`].
+    ///
+    /// [`Self::pipeline`] is recreated to match in [`Self::update_buffers`].
+    msaa_samples: u32,
+
+    // Kept around for recreating the pipeline:
+    pipeline_layout: wgpu::PipelineLayout,
+    shader_module: wgpu::ShaderModule,
+    output_color_format: wgpu::TextureFormat,
+    output_depth_format: Option<wgpu::TextureFormat>,
+
     index_buffer: SlicedBuffer,
     vertex_buffer: SlicedBuffer,
 
@@ -194,7 +243,18 @@ pub struct Renderer {
     next_user_texture_id: u64,
     samplers: HashMap<epaint::textures::TextureOptions, wgpu::Sampler>,
 
+    /// Bind groups for meshes which sample a texture differently than it was created,
+    /// see [`epaint::Mesh::texture_options`].
+    ///
+    /// Created in [`Self::update_buffers`] when first needed.
+    override_bind_groups:
+        HashMap<(epaint::TextureId, epaint::textures::TextureOptions), wgpu::BindGroup>,
+
+    /// How much GPU memory the textures use, see [`Self::texture_memory`].
+    texture_memory: epaint::textures::TextureMemory,
+
     dithering: bool,
+    output_color_space: ColorSpace,
 
     /// Storage for resources shared with all invocations of [`CallbackTrait`]'s methods.
     ///
@@ -230,7 +290,7 @@ impl Renderer {
             contents: bytemuck::cast_slice(&[UniformBuffer {
                 screen_size_in_points: [0.0, 0.0],
                 dithering: u32::from(dithering),
-                _padding: Default::default(),
+                color_space: color_space_uniform(ColorSpace::default()),
             }]),
             usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
         });
@@ -299,79 +359,15 @@ impl Renderer {
             push_constant_ranges: &[],
         });
 
-        let depth_stencil = output_depth_format.map(|format| wgpu::DepthStencilState {
-            format,
-            depth_write_enabled: false,
-            depth_compare: wgpu::CompareFunction::Always,
-            stencil: wgpu::StencilState::default(),
-            bias: wgpu::DepthBiasState::default(),
-        });
-
-        let pipeline = {
-            crate::profile_scope!("create_render_pipeline");
-            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-                label: Some("egui_pipeline"),
-                layout: Some(&pipeline_layout),
-                vertex: wgpu::VertexState {
-                    entry_point: Some("vs_main"),
-                    module: &module,
-                    buffers: &[wgpu::VertexBufferLayout {
-                        array_stride: 5 * 4,
-                        step_mode: wgpu::VertexStepMode::Vertex,
-                        // 0: vec2 position
-                        // 1: vec2 texture coordinates
-                        // 2: uint color
-                        attributes: &wgpu::vertex_attr_array![0 => Float32x2, 1 => Float32x2, 2 => Uint32],
-                    }],
-                    compilation_options: wgpu::PipelineCompilationOptions::default()
-                },
-                primitive: wgpu::PrimitiveState {
-                    topology: wgpu::PrimitiveTopology::TriangleList,
-                    unclipped_depth: false,
-                    conservative: false,
-                    cull_mode: None,
-                    front_face: wgpu::FrontFace::default(),
-                    polygon_mode: wgpu::PolygonMode::default(),
-                    strip_index_format: None,
-                },
-                depth_stencil,
-                multisample: wgpu::MultisampleState {
-                    alpha_to_coverage_enabled: false,
-                    count: msaa_samples,
-                    mask: !0,
-                },
-
-                fragment: Some(wgpu::FragmentState {
-                    module: &module,
-                    entry_point: Some(if output_color_format.is_srgb() {
-                        log::warn!("Detected a linear (sRGBA aware) framebuffer {:?}. egui prefers Rgba8Unorm or Bgra8Unorm", output_color_format);
-                        "fs_main_linear_framebuffer"
-                    } else {
-                        "fs_main_gamma_framebuffer" // this is what we prefer
-                    }),
-                    targets: &[Some(wgpu::ColorTargetState {
-                        format: output_color_format,
-                        blend: Some(wgpu::BlendState {
-                            color: wgpu::BlendComponent {
-                                src_factor: wgpu::BlendFactor::One,
-                                dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                                operation: wgpu::BlendOperation::Add,
-                            },
-                            alpha: wgpu::BlendComponent {
-                                src_factor: wgpu::BlendFactor::OneMinusDstAlpha,
-                                dst_factor: wgpu::BlendFactor::One,
-                                operation: wgpu::BlendOperation::Add,
-                            },
-                        }),
-                        write_mask: wgpu::ColorWrites::ALL,
-                    })],
-                    compilation_options: wgpu::PipelineCompilationOptions::default()
-                }),
-                multiview: None,
-                cache: None,
-            }
-        )
-        };
+        let pipeline = create_pipeline(
+            device,
+            &pipeline_layout,
+            &module,
+            output_color_format,
+            output_depth_format,
+            msaa_samples,
+            (BlendMode::Normal, false),
+        );
 
         const VERTEX_BUFFER_START_CAPACITY: wgpu::BufferAddress =
             (std::mem::size_of::<Vertex>() * 1024) as _;
@@ -380,6 +376,13 @@ impl Renderer {
 
         Self {
             pipeline,
+            other_pipelines: HashMap::default(),
+            pipeline_msaa_samples: msaa_samples,
+            msaa_samples,
+            pipeline_layout,
+            shader_module: module,
+            output_color_format,
+            output_depth_format,
             vertex_buffer: SlicedBuffer {
                 buffer: create_vertex_buffer(device, VERTEX_BUFFER_START_CAPACITY),
                 slices: Vec::with_capacity(64),
@@ -395,18 +398,115 @@ impl Renderer {
             previous_uniform_buffer_content: UniformBuffer {
                 screen_size_in_points: [0.0, 0.0],
                 dithering: 0,
-                _padding: 0,
+                color_space: 0,
             },
             uniform_bind_group,
             texture_bind_group_layout,
             textures: HashMap::default(),
             next_user_texture_id: 0,
             samplers: HashMap::default(),
+            override_bind_groups: HashMap::default(),
+            texture_memory: Default::default(),
             dithering,
+            output_color_space: ColorSpace::default(),
             callback_resources: CallbackResources::default(),
         }
     }
 
+    /// The color format of the render target, as given to [`Self::new`].
+    #[inline]
+    pub fn output_color_format(&self) -> wgpu::TextureFormat {
+        self.output_color_format
+    }
+
+    /// The color space the renderer writes its pixels in.
+    #[inline]
+    pub fn output_color_space(&self) -> ColorSpace {
+        self.output_color_space
+    }
+
+    /// Set the color space of the render target, e.g. [`ColorSpace::DisplayP3`] for a wide-gamut display.
+    ///
+    /// egui colors are in sRGB, and are converted to this color space when rasterized,
+    /// so that they look the same as on an sRGB display.
+    /// [`ColorSpace::Linear`] is meant for targets that aren't sRGB-encoded, e.g. [`wgpu::TextureFormat::Rgba16Float`].
+    ///
+    /// Takes effect in the next call to [`Self::update_buffers`]. Default: [`ColorSpace::Srgb`].
+    pub fn set_output_color_space(&mut self, color_space: ColorSpace) {
+        self.output_color_space = color_space;
+    }
+
+    /// The number of MSAA samples per pixel the render pass given to [`Self::render`] must have.
+    #[inline]
+    pub fn msaa_samples(&self) -> u32 {
+        self.msaa_samples
+    }
+
+    /// Change the number of MSAA samples per pixel, e.g. when the user toggles anti-aliasing in the settings.
+    ///
+    /// The render pipeline is recreated lazily in the next call to [`Self::update_buffers`],
+    /// so all other state (textures, buffers, callback resources) is kept.
+    /// From then on, the render pass given to [`Self::render`] must have this sample count,
+    /// and a single-sampled resolve target if it is larger than one.
+    ///
+    /// The sample count must be supported by the device for the output formats.
+    pub fn set_msaa_samples(&mut self, msaa_samples: u32) {
+        self.msaa_samples = msaa_samples;
+    }
+
+    /// Recreate the pipeline if the sample count was changed.
+    fn update_pipeline(&mut self, device: &wgpu::Device) {
+        if self.pipeline_msaa_samples == self.msaa_samples {
+            return;
+        }
+        crate::profile_function!();
+        log::debug!(
+            "Recreating the egui pipeline with {} MSAA samples",
+            self.msaa_samples
+        );
+        self.pipeline = create_pipeline(
+            device,
+            &self.pipeline_layout,
+            &self.shader_module,
+            self.output_color_format,
+            self.output_depth_format,
+            self.msaa_samples,
+            (BlendMode::Normal, false),
+        );
+        self.other_pipelines.clear(); // recreated when needed
+        self.pipeline_msaa_samples = self.msaa_samples;
+    }
+
+    /// Create the pipeline for `blend_mode` and `distance_field` unless we already have it.
+    fn create_pipeline_if_needed(
+        &mut self,
+        device: &wgpu::Device,
+        blend_mode: BlendMode,
+        distance_field: bool,
+    ) {
+        let key = (blend_mode, distance_field);
+        if key == (BlendMode::Normal, false) || self.other_pipelines.contains_key(&key) {
+            return;
+        }
+        let pipeline = create_pipeline(
+            device,
+            &self.pipeline_layout,
+            &self.shader_module,
+            self.output_color_format,
+            self.output_depth_format,
+            self.pipeline_msaa_samples,
+            key,
+        );
+        self.other_pipelines.insert(key, pipeline);
+    }
+
+    /// The pipeline for painting meshes with this [`BlendMode`], and with or without [`epaint::Mesh::distance_field`].
+    fn pipeline(&self, blend_mode: BlendMode, distance_field: bool) -> &wgpu::RenderPipeline {
+        self.other_pipelines
+            .get(&(blend_mode, distance_field))
+            .unwrap_or(&self.pipeline)
+    }
+
     /// Executes the egui renderer onto an existing wgpu renderpass.
     ///
     /// Note that the lifetime of `render_pass` is `'static` which requires a call to [`wgpu::RenderPass::forget_lifetime`].
@@ -429,6 +529,10 @@ impl Renderer {
         // run.
         let mut needs_reset = true;
 
+        // The blend mode of the pipeline currently set, and if it is for distance fields:
+        let mut blend_mode = BlendMode::Normal;
+        let mut distance_field = false;
+
         let mut index_buffer_slices = self.index_buffer.slices.iter();
         let mut vertex_buffer_slices = self.vertex_buffer.slices.iter();
 
@@ -448,6 +552,8 @@ impl Renderer {
                 );
                 render_pass.set_pipeline(&self.pipeline);
                 render_pass.set_bind_group(0, &self.uniform_bind_group, &[]);
+                blend_mode = BlendMode::Normal;
+                distance_field = false;
                 needs_reset = false;
             }
 
@@ -473,6 +579,18 @@ impl Renderer {
                     let vertex_buffer_slice = vertex_buffer_slices.next().unwrap();
 
                     if let Some(Texture { bind_group, .. }) = self.textures.get(&mesh.texture_id) {
+                        let bind_group = mesh
+                            .texture_options
+                            .and_then(|options| {
+                                self.override_bind_groups.get(&(mesh.texture_id, options))
+                            })
+                            .unwrap_or(bind_group);
+                        if mesh.blend_mode != blend_mode || mesh.distance_field != distance_field {
+                            render_pass
+                                .set_pipeline(self.pipeline(mesh.blend_mode, mesh.distance_field));
+                            blend_mode = mesh.blend_mode;
+                            distance_field = mesh.distance_field;
+                        }
                         render_pass.set_bind_group(1, bind_group, &[]);
                         render_pass.set_index_buffer(
                             self.index_buffer.buffer.slice(
@@ -536,6 +654,70 @@ impl Renderer {
         render_pass.set_scissor_rect(0, 0, size_in_pixels[0], size_in_pixels[1]);
     }
 
+    /// Render into a texture, e.g. for an egui texture viewport shown on a surface in a 3D scene
+    /// (see `egui::Context::show_viewport_texture`).
+    ///
+    /// This uploads the buffers, renders, and submits the work to the queue right away,
+    /// so that you can use the same [`Renderer`] for the parent viewport afterwards.
+    ///
+    /// The [`Renderer`] must have been created without a depth format.
+    ///
+    /// The `textures_delta` is applied too: new textures are uploaded before rendering,
+    /// and textures are freed after the work has been submitted.
+    pub fn render_to_texture(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        target: &TextureTarget<'_>,
+        paint_jobs: &[epaint::ClippedPrimitive],
+        screen_descriptor: &ScreenDescriptor,
+        textures_delta: &epaint::textures::TexturesDelta,
+    ) {
+        crate::profile_function!();
+
+        for (id, image_delta) in &textures_delta.set {
+            self.update_texture(device, queue, *id, image_delta);
+        }
+        for &(id, options) in &textures_delta.options {
+            self.set_texture_options(device, id, options);
+        }
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("egui_render_to_texture"),
+        });
+
+        let user_cmd_bufs =
+            self.update_buffers(device, queue, &mut encoder, paint_jobs, screen_descriptor);
+
+        {
+            let mut render_pass = encoder
+                .begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: Some("egui_render_to_texture"),
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view: target.view,
+                        resolve_target: target.resolve_target,
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Clear(target.clear_color),
+                            store: wgpu::StoreOp::Store,
+                        },
+                    })],
+                    depth_stencil_attachment: None,
+                    timestamp_writes: None,
+                    occlusion_query_set: None,
+                })
+                .forget_lifetime();
+
+            self.render(&mut render_pass, paint_jobs, screen_descriptor);
+        }
+
+        // Submit right away, since the next call to `update_buffers` overwrites the buffers:
+        queue.submit(user_cmd_bufs.into_iter().chain([encoder.finish()]));
+
+        for id in &textures_delta.free {
+            self.free_texture(id);
+        }
+    }
+
     /// Should be called before [`Self::render`].
     pub fn update_texture(
         &mut self,
@@ -647,30 +829,30 @@ impl Renderer {
             (texture, origin, None)
         };
 
+        if image_delta.pos.is_none() {
+            // The old bind groups refer to the old texture:
+            self.override_bind_groups.retain(|(x, _), _| x != &id);
+        }
+
         let bind_group = bind_group.unwrap_or_else(|| {
             let sampler = self
                 .samplers
                 .entry(image_delta.options)
                 .or_insert_with(|| create_sampler(image_delta.options, device));
-            device.create_bind_group(&wgpu::BindGroupDescriptor {
+            create_texture_bind_group(
+                device,
+                &self.texture_bind_group_layout,
+                &texture,
+                sampler,
                 label,
-                layout: &self.texture_bind_group_layout,
-                entries: &[
-                    wgpu::BindGroupEntry {
-                        binding: 0,
-                        resource: wgpu::BindingResource::TextureView(
-                            &texture.create_view(&wgpu::TextureViewDescriptor::default()),
-                        ),
-                    },
-                    wgpu::BindGroupEntry {
-                        binding: 1,
-                        resource: wgpu::BindingResource::Sampler(sampler),
-                    },
-                ],
-            })
+            )
         });
 
         queue_write_data_to_texture(&texture, origin);
+        if image_delta.pos.is_none() {
+            self.texture_memory
+                .on_alloc(id, [width as usize, height as usize], 4, false);
+        }
         self.textures.insert(
             id,
             Texture {
@@ -681,7 +863,80 @@ impl Renderer {
         );
     }
 
+    /// Change how a texture is sampled, without uploading it again.
+    ///
+    /// Should be called before [`Self::render`].
+    pub fn set_texture_options(
+        &mut self,
+        device: &wgpu::Device,
+        id: epaint::TextureId,
+        options: epaint::textures::TextureOptions,
+    ) {
+        let Some(Texture {
+            texture: Some(texture),
+            bind_group,
+            options: Some(old_options),
+        }) = self.textures.get_mut(&id)
+        else {
+            return; // A user texture, or one that has been freed
+        };
+        if *old_options == options {
+            return;
+        }
+
+        let sampler = self
+            .samplers
+            .entry(options)
+            .or_insert_with(|| create_sampler(options, device));
+        *bind_group = create_texture_bind_group(
+            device,
+            &self.texture_bind_group_layout,
+            texture,
+            sampler,
+            Some(format!("egui_texid_{id:?}").as_str()),
+        );
+        *old_options = options;
+    }
+
+    /// Create the bind group for a mesh which overrides the options of its texture.
+    fn create_override_bind_group_if_needed(
+        &mut self,
+        device: &wgpu::Device,
+        id: epaint::TextureId,
+        options: epaint::textures::TextureOptions,
+    ) {
+        if self.override_bind_groups.contains_key(&(id, options)) {
+            return;
+        }
+        let Some(Texture {
+            texture: Some(texture),
+            options: Some(own_options),
+            ..
+        }) = self.textures.get(&id)
+        else {
+            return; // User textures come with their own sampler
+        };
+        if *own_options == options {
+            return;
+        }
+
+        let sampler = self
+            .samplers
+            .entry(options)
+            .or_insert_with(|| create_sampler(options, device));
+        let bind_group = create_texture_bind_group(
+            device,
+            &self.texture_bind_group_layout,
+            texture,
+            sampler,
+            Some(format!("egui_texid_{id:?}_{options:?}").as_str()),
+        );
+        self.override_bind_groups.insert((id, options), bind_group);
+    }
+
     pub fn free_texture(&mut self, id: &epaint::TextureId) {
+        self.override_bind_groups.retain(|(x, _), _| x != id);
+        self.texture_memory.on_free(*id);
         if let Some(texture) = self.textures.remove(id).and_then(|t| t.texture) {
             texture.destroy();
         }
@@ -695,6 +950,13 @@ impl Renderer {
         self.textures.get(id)
     }
 
+    /// How much GPU memory the textures use, and when each was last painted.
+    ///
+    /// Native textures (e.g. from [`Self::register_native_texture`]) are not included.
+    pub fn texture_memory(&self) -> &epaint::textures::TextureMemory {
+        &self.texture_memory
+    }
+
     /// Registers a [`wgpu::Texture`] with a [`epaint::TextureId`].
     ///
     /// This enables the application to reference the texture inside an image ui element.
@@ -837,7 +1099,8 @@ impl Renderer {
         *user_texture_binding = bind_group;
     }
 
-    /// Uploads the uniform, vertex and index data used by the renderer.
+    /// Uploads the uniform, vertex and index data used by the renderer,
+    /// and recreates the pipeline if the sample count was changed with [`Self::set_msaa_samples`].
     /// Should be called before [`Self::render`].
     ///
     /// Returns all user-defined command buffers gathered from [`CallbackTrait::prepare`] & [`CallbackTrait::finish_prepare`] callbacks.
@@ -851,12 +1114,24 @@ impl Renderer {
     ) -> Vec<wgpu::CommandBuffer> {
         crate::profile_function!();
 
+        self.update_pipeline(device);
+        self.texture_memory.begin_frame();
+        for clipped_primitive in paint_jobs {
+            if let Primitive::Mesh(mesh) = &clipped_primitive.primitive {
+                self.texture_memory.on_paint(mesh.texture_id);
+                self.create_pipeline_if_needed(device, mesh.blend_mode, mesh.distance_field);
+                if let Some(options) = mesh.texture_options {
+                    self.create_override_bind_group_if_needed(device, mesh.texture_id, options);
+                }
+            }
+        }
+
         let screen_size_in_points = screen_descriptor.screen_size_in_points();
 
         let uniform_buffer_content = UniformBuffer {
             screen_size_in_points,
             dithering: u32::from(self.dithering),
-            _padding: Default::default(),
+            color_space: color_space_uniform(self.output_color_space),
         };
         if uniform_buffer_content != self.previous_uniform_buffer_content {
             crate::profile_scope!("update uniforms");
@@ -996,6 +1271,110 @@ impl Renderer {
     }
 }
 
+/// Create the pipeline for painting meshes with this blend mode, and with or without [`epaint::Mesh::distance_field`].
+fn create_pipeline(
+    device: &wgpu::Device,
+    pipeline_layout: &wgpu::PipelineLayout,
+    module: &wgpu::ShaderModule,
+    output_color_format: wgpu::TextureFormat,
+    output_depth_format: Option<wgpu::TextureFormat>,
+    msaa_samples: u32,
+    (blend_mode, distance_field): (BlendMode, bool),
+) -> wgpu::RenderPipeline {
+    let depth_stencil = output_depth_format.map(|format| wgpu::DepthStencilState {
+        format,
+        depth_write_enabled: false,
+        depth_compare: wgpu::CompareFunction::Always,
+        stencil: wgpu::StencilState::default(),
+        bias: wgpu::DepthBiasState::default(),
+    });
+
+    {
+        crate::profile_scope!("create_render_pipeline");
+        device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("egui_pipeline"),
+            layout: Some(pipeline_layout),
+            vertex: wgpu::VertexState {
+                entry_point: Some("vs_main"),
+                module,
+                buffers: &[wgpu::VertexBufferLayout {
+                    array_stride: 5 * 4,
+                    step_mode: wgpu::VertexStepMode::Vertex,
+                    // 0: vec2 position
+                    // 1: vec2 texture coordinates
+                    // 2: uint color
+                    attributes: &wgpu::vertex_attr_array![0 => Float32x2, 1 => Float32x2, 2 => Uint32],
+                }],
+                compilation_options: wgpu::PipelineCompilationOptions::default()
+            },
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                unclipped_depth: false,
+                conservative: false,
+                cull_mode: None,
+                front_face: wgpu::FrontFace::default(),
+                polygon_mode: wgpu::PolygonMode::default(),
+                strip_index_format: None,
+            },
+            depth_stencil,
+            multisample: wgpu::MultisampleState {
+                alpha_to_coverage_enabled: false,
+                count: msaa_samples,
+                mask: !0,
+            },
+
+            fragment: Some(wgpu::FragmentState {
+                module,
+                entry_point: Some(match (output_color_format.is_srgb(), distance_field) {
+                    (true, distance_field) => {
+                        log::warn!("Detected a linear (sRGBA aware) framebuffer {:?}. egui prefers Rgba8Unorm or Bgra8Unorm", output_color_format);
+                        if distance_field {
+                            "fs_main_linear_framebuffer_distance_field"
+                        } else {
+                            "fs_main_linear_framebuffer"
+                        }
+                    }
+                    (false, false) => "fs_main_gamma_framebuffer", // this is what we prefer
+                    (false, true) => "fs_main_gamma_framebuffer_distance_field",
+                }),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: output_color_format,
+                    blend: Some(wgpu::BlendState {
+                        // egui outputs colors with premultiplied alpha:
+                        color: match blend_mode {
+                            BlendMode::Normal => wgpu::BlendComponent {
+                                src_factor: wgpu::BlendFactor::One,
+                                dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
+                                operation: wgpu::BlendOperation::Add,
+                            },
+                            BlendMode::Additive => wgpu::BlendComponent {
+                                src_factor: wgpu::BlendFactor::One,
+                                dst_factor: wgpu::BlendFactor::One,
+                                operation: wgpu::BlendOperation::Add,
+                            },
+                            BlendMode::Multiply => wgpu::BlendComponent {
+                                src_factor: wgpu::BlendFactor::Dst,
+                                dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
+                                operation: wgpu::BlendOperation::Add,
+                            },
+                        },
+                        alpha: wgpu::BlendComponent {
+                            src_factor: wgpu::BlendFactor::OneMinusDstAlpha,
+                            dst_factor: wgpu::BlendFactor::One,
+                            operation: wgpu::BlendOperation::Add,
+                        },
+                    }),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default()
+            }),
+            multiview: None,
+            cache: None,
+        }
+    )
+    }
+}
+
 fn create_sampler(
     options: epaint::textures::TextureOptions,
     device: &wgpu::Device,
@@ -1013,18 +1392,51 @@ fn create_sampler(
         epaint::textures::TextureWrapMode::Repeat => wgpu::AddressMode::Repeat,
         epaint::textures::TextureWrapMode::MirroredRepeat => wgpu::AddressMode::MirrorRepeat,
     };
+    // wgpu only allows anisotropic filtering when all filters are linear:
+    let (anisotropy_clamp, mipmap_filter) = if options.is_anisotropic() {
+        (options.anisotropy, wgpu::FilterMode::Linear)
+    } else {
+        (1, wgpu::FilterMode::Nearest)
+    };
     device.create_sampler(&wgpu::SamplerDescriptor {
         label: Some(&format!(
-            "egui sampler (mag: {mag_filter:?}, min {min_filter:?})"
+            "egui sampler (mag: {mag_filter:?}, min {min_filter:?}, anisotropy: {anisotropy_clamp})"
         )),
         mag_filter,
         min_filter,
+        mipmap_filter,
         address_mode_u: address_mode,
         address_mode_v: address_mode,
+        anisotropy_clamp,
         ..Default::default()
     })
 }
 
+fn create_texture_bind_group(
+    device: &wgpu::Device,
+    layout: &wgpu::BindGroupLayout,
+    texture: &wgpu::Texture,
+    sampler: &wgpu::Sampler,
+    label: Option<&str>,
+) -> wgpu::BindGroup {
+    device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label,
+        layout,
+        entries: &[
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::TextureView(
+                    &texture.create_view(&wgpu::TextureViewDescriptor::default()),
+                ),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: wgpu::BindingResource::Sampler(sampler),
+            },
+        ],
+    })
+}
+
 fn create_vertex_buffer(device: &wgpu::Device, size: u64) -> wgpu::Buffer {
     crate::profile_function!();
     device.create_buffer(&wgpu::BufferDescriptor {
@@ -5,7 +5,13 @@ use std::{num::NonZeroU32, sync::Arc};
 
 use egui::{ViewportId, ViewportIdMap, ViewportIdSet};
 
-use crate::{renderer, RenderState, SurfaceErrorAction, WgpuConfiguration};
+use crate::{renderer, RenderHookContext, RenderState, SurfaceErrorAction, WgpuConfiguration};
+
+#[cfg(not(target_arch = "wasm32"))]
+mod render_thread;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use render_thread::{ViewportFrame, ViewportRenderThread};
 
 struct SurfaceState {
     surface: wgpu::Surface<'static>,
@@ -150,6 +156,67 @@ impl Painter {
         self.render_state.clone()
     }
 
+    /// Move the surface of a viewport into a new painter of its own,
+    /// e.g. to paint it on another thread with a [`ViewportRenderThread`].
+    ///
+    /// Both painters share the same device and [`crate::Renderer`], and so all textures.
+    ///
+    /// Returns `None` if the viewport has no surface, see [`Self::set_window`].
+    pub fn split_off_viewport(&mut self, viewport_id: ViewportId) -> Option<Self> {
+        let render_state = self.render_state.clone()?;
+        let surface_state = self.surfaces.remove(&viewport_id)?;
+
+        let mut painter = Self {
+            configuration: self.configuration.clone(),
+            msaa_samples: self.msaa_samples,
+            support_transparent_backbuffer: self.support_transparent_backbuffer,
+            dithering: self.dithering,
+            depth_format: self.depth_format,
+            screen_capture_state: None,
+
+            instance: self.instance.clone(),
+            render_state: Some(render_state),
+
+            depth_texture_view: Default::default(),
+            surfaces: Default::default(),
+            msaa_texture_view: Default::default(),
+        };
+
+        painter.surfaces.insert(viewport_id, surface_state);
+        if let Some(view) = self.depth_texture_view.remove(&viewport_id) {
+            painter.depth_texture_view.insert(viewport_id, view);
+        }
+        if let Some(view) = self.msaa_texture_view.remove(&viewport_id) {
+            painter.msaa_texture_view.insert(viewport_id, view);
+        }
+
+        Some(painter)
+    }
+
+    /// Change how frames are presented, e.g. to switch between low latency and power saving at runtime.
+    ///
+    /// All surfaces are reconfigured.
+    /// See [`WgpuConfiguration::present_mode`] and [`WgpuConfiguration::desired_maximum_frame_latency`].
+    pub fn set_presentation(
+        &mut self,
+        present_mode: wgpu::PresentMode,
+        desired_maximum_frame_latency: Option<u32>,
+    ) {
+        crate::profile_function!();
+
+        self.configuration.present_mode = present_mode;
+        self.configuration.desired_maximum_frame_latency = desired_maximum_frame_latency;
+
+        if let Some(render_state) = &self.render_state {
+            for surface_state in self.surfaces.values() {
+                if surface_state.width == 0 || surface_state.height == 0 {
+                    continue; // Minimized. Configured when resized.
+                }
+                Self::configure_surface(surface_state, render_state, &self.configuration);
+            }
+        }
+    }
+
     fn configure_surface(
         surface_state: &SurfaceState,
         render_state: &RenderState,
@@ -316,6 +383,46 @@ impl Painter {
         Ok(())
     }
 
+    /// The number of MSAA samples per pixel.
+    #[inline]
+    pub fn msaa_samples(&self) -> u32 {
+        self.msaa_samples
+    }
+
+    /// Change the number of MSAA samples per pixel, e.g. when the user toggles anti-aliasing in the settings.
+    ///
+    /// This recreates the multisampled color and depth textures of each viewport,
+    /// and the render pipeline of the [`crate::Renderer`],
+    /// but keeps the device, the surfaces, and all egui and user textures.
+    pub fn set_msaa_samples(&mut self, msaa_samples: u32) {
+        if self.msaa_samples == msaa_samples {
+            return;
+        }
+        crate::profile_function!();
+
+        self.msaa_samples = msaa_samples;
+        let Some(render_state) = &self.render_state else {
+            return; // We will use the new sample count when the render state is created.
+        };
+        render_state.renderer.write().set_msaa_samples(msaa_samples);
+
+        self.msaa_texture_view.clear();
+        let sizes: Vec<_> = self
+            .surfaces
+            .iter()
+            .filter_map(|(viewport_id, surface_state)| {
+                Some((
+                    *viewport_id,
+                    NonZeroU32::new(surface_state.width)?,
+                    NonZeroU32::new(surface_state.height)?,
+                ))
+            })
+            .collect();
+        for (viewport_id, width, height) in sizes {
+            self.resize_and_generate_depth_texture_view_and_msaa_view(viewport_id, width, height);
+        }
+    }
+
     /// Returns the maximum texture dimension supported if known
     ///
     /// This API will only return a known dimension after `set_window()` has been called
@@ -516,6 +623,30 @@ impl Painter {
         })
     }
 
+    /// Upload new and changed textures, without painting anything.
+    ///
+    /// [`Self::paint_and_update_textures`] does this too, but this lets you make
+    /// the textures available to all painters sharing the [`crate::Renderer`] right away,
+    /// e.g. before handing the rest of a frame over to a [`ViewportRenderThread`].
+    ///
+    /// Textures in [`epaint::textures::TexturesDelta::free`] are not freed,
+    /// since they may still be in use by frames that are not yet submitted.
+    pub fn update_textures(&self, textures_delta: &epaint::textures::TexturesDelta) {
+        crate::profile_function!();
+
+        let Some(render_state) = &self.render_state else {
+            return;
+        };
+
+        let mut renderer = render_state.renderer.write();
+        for (id, image_delta) in &textures_delta.set {
+            renderer.update_texture(&render_state.device, &render_state.queue, *id, image_delta);
+        }
+        for &(id, options) in &textures_delta.options {
+            renderer.set_texture_options(&render_state.device, id, options);
+        }
+    }
+
     /// Returns two things:
     ///
     /// The approximate number of seconds spent on vsync-waiting (if any),
@@ -533,6 +664,8 @@ impl Painter {
 
         let mut vsync_sec = 0.0;
 
+        self.update_textures(textures_delta);
+
         let Some(render_state) = self.render_state.as_mut() else {
             return (vsync_sec, None);
         };
@@ -553,26 +686,6 @@ impl Painter {
             pixels_per_point,
         };
 
-        let user_cmd_bufs = {
-            let mut renderer = render_state.renderer.write();
-            for (id, image_delta) in &textures_delta.set {
-                renderer.update_texture(
-                    &render_state.device,
-                    &render_state.queue,
-                    *id,
-                    image_delta,
-                );
-            }
-
-            renderer.update_buffers(
-                &render_state.device,
-                &render_state.queue,
-                &mut encoder,
-                clipped_primitives,
-                &screen_descriptor,
-            )
-        };
-
         let capture = match (capture, surface_state.supports_screenshot) {
             (false, _) => false,
             (true, true) => true,
@@ -604,8 +717,21 @@ impl Painter {
             },
         };
 
-        {
-            let renderer = render_state.renderer.read();
+        // The vertex, index and uniform buffers of the renderer are shared by all painters,
+        // which may be painting on other threads.
+        // The writes to them only land when the queue is submitted,
+        // so we hold the lock until we've submitted our render pass:
+        let (mut renderer, user_cmd_bufs) = {
+            let mut renderer = render_state.renderer.write();
+
+            let user_cmd_bufs = renderer.update_buffers(
+                &render_state.device,
+                &render_state.queue,
+                &mut encoder,
+                clipped_primitives,
+                &screen_descriptor,
+            );
+
             let frame_view = if capture {
                 Self::update_capture_state(
                     &mut self.screen_capture_state,
@@ -632,18 +758,59 @@ impl Painter {
                     (texture_view, Some(&frame_view))
                 });
 
+            let clear_color = wgpu::Color {
+                r: clear_color[0] as f64,
+                g: clear_color[1] as f64,
+                b: clear_color[2] as f64,
+                a: clear_color[3] as f64,
+            };
+
+            let mut render_hooks = render_state.render_hooks.write();
+            let has_pre_ui_hooks = render_hooks.has_pre_ui();
+            if has_pre_ui_hooks {
+                // Clear before the hooks, so that egui can paint on top of what they render:
+                encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: Some("egui_clear"),
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view,
+                        resolve_target: None,
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Clear(clear_color),
+                            store: wgpu::StoreOp::Store,
+                        },
+                    })],
+                    depth_stencil_attachment: None,
+                    timestamp_writes: None,
+                    occlusion_query_set: None,
+                });
+
+                render_hooks.run_pre_ui(&mut RenderHookContext {
+                    viewport_id,
+                    device: &render_state.device,
+                    queue: &render_state.queue,
+                    encoder: &mut encoder,
+                    color_view: view,
+                    color_format: render_state.target_format,
+                    msaa_samples: if resolve_target.is_some() {
+                        self.msaa_samples
+                    } else {
+                        1
+                    },
+                    screen_descriptor: &screen_descriptor,
+                });
+            }
+
             let render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                 label: Some("egui_render"),
                 color_attachments: &[Some(wgpu::RenderPassColorAttachment {
                     view,
                     resolve_target,
                     ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(wgpu::Color {
-                            r: clear_color[0] as f64,
-                            g: clear_color[1] as f64,
-                            b: clear_color[2] as f64,
-                            a: clear_color[3] as f64,
-                        }),
+                        load: if has_pre_ui_hooks {
+                            wgpu::LoadOp::Load
+                        } else {
+                            wgpu::LoadOp::Clear(clear_color)
+                        },
                         store: wgpu::StoreOp::Store,
                     },
                 })],
@@ -671,7 +838,22 @@ impl Painter {
                 clipped_primitives,
                 &screen_descriptor,
             );
-        }
+
+            if render_hooks.has_post_ui() {
+                render_hooks.run_post_ui(&mut RenderHookContext {
+                    viewport_id,
+                    device: &render_state.device,
+                    queue: &render_state.queue,
+                    encoder: &mut encoder,
+                    color_view: &frame_view,
+                    color_format: render_state.target_format,
+                    msaa_samples: 1,
+                    screen_descriptor: &screen_descriptor,
+                });
+            }
+
+            (renderer, user_cmd_bufs)
+        };
 
         let encoded = {
             crate::profile_scope!("CommandEncoder::finish");
@@ -692,12 +874,10 @@ impl Painter {
         // Free textures marked for destruction **after** queue submit since they might still be used in the current frame.
         // Calling `wgpu::Texture::destroy` on a texture that is still in use would invalidate the command buffer(s) it is used in.
         // However, once we called `wgpu::Queue::submit`, it is up for wgpu to determine how long the underlying gpu resource has to live.
-        {
-            let mut renderer = render_state.renderer.write();
-            for id in &textures_delta.free {
-                renderer.free_texture(id);
-            }
+        for id in &textures_delta.free {
+            renderer.free_texture(id);
         }
+        drop(renderer);
 
         let screenshot = if capture {
             self.screen_capture_state
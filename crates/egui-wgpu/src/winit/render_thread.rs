@@ -0,0 +1,202 @@
+use std::{
+    num::NonZeroU32,
+    sync::{mpsc, Arc},
+    thread::JoinHandle,
+};
+
+use egui::ViewportId;
+use parking_lot::{Condvar, Mutex};
+
+use super::Painter;
+
+/// Everything needed to paint one frame of a viewport, taken from its [`egui::FullOutput`].
+pub struct ViewportFrame {
+    pub pixels_per_point: f32,
+    pub clear_color: [f32; 4],
+    pub clipped_primitives: Vec<epaint::ClippedPrimitive>,
+
+    /// Applied like in [`Painter::paint_and_update_textures`].
+    ///
+    /// To make new textures available to the other viewports right away, upload them with
+    /// [`Painter::update_textures`] and only pass on [`epaint::textures::TexturesDelta::free`].
+    pub textures_delta: epaint::textures::TexturesDelta,
+
+    /// Capture a screenshot of the frame, see [`ViewportRenderThread::take_screenshots`].
+    pub capture: bool,
+}
+
+/// What the thread should do next.
+#[derive(Default)]
+struct Mailbox {
+    frame: Option<ViewportFrame>,
+    size: Option<(NonZeroU32, NonZeroU32)>,
+    presentation: Option<(wgpu::PresentMode, Option<u32>)>,
+    shutdown: bool,
+}
+
+impl Mailbox {
+    fn is_empty(&self) -> bool {
+        self.frame.is_none() && self.size.is_none() && self.presentation.is_none()
+    }
+}
+
+/// Paints a viewport on a thread of its own, so that it can't hold back the painting of the other viewports.
+///
+/// The ui still runs wherever the [`egui::Context`] is run.
+/// The thread is only handed what comes out of it, as [`ViewportFrame`]s, and never touches the context.
+///
+/// If the thread is still busy when the next frame arrives, the frame waiting before it is skipped.
+///
+/// Dropping this waits for the frame being painted, if any, and then stops the thread.
+pub struct ViewportRenderThread {
+    mailbox: Arc<(Mutex<Mailbox>, Condvar)>,
+    screenshots: mpsc::Receiver<epaint::ColorImage>,
+    join_handle: Option<JoinHandle<()>>,
+}
+
+impl ViewportRenderThread {
+    /// Start painting the viewport with the given painter,
+    /// usually split off the main painter with [`Painter::split_off_viewport`].
+    ///
+    /// `on_screenshot` is called on the thread whenever a screenshot is ready,
+    /// e.g. to wake up the event loop so that it calls [`Self::take_screenshots`].
+    pub fn spawn(
+        viewport_id: ViewportId,
+        mut painter: Painter,
+        on_screenshot: impl Fn() + Send + 'static,
+    ) -> std::io::Result<Self> {
+        let mailbox = Arc::new((Mutex::new(Mailbox::default()), Condvar::new()));
+        let (screenshot_tx, screenshots) = mpsc::channel();
+
+        let join_handle = std::thread::Builder::new()
+            .name(format!("egui_wgpu {viewport_id:?}"))
+            .spawn({
+                let mailbox = mailbox.clone();
+                move || {
+                    loop {
+                        let Mailbox {
+                            frame,
+                            size,
+                            presentation,
+                            shutdown,
+                        } = {
+                            let (lock, condvar) = &*mailbox;
+                            let mut mailbox = lock.lock();
+                            while mailbox.is_empty() && !mailbox.shutdown {
+                                condvar.wait(&mut mailbox);
+                            }
+                            std::mem::take(&mut *mailbox)
+                        };
+
+                        if shutdown {
+                            break;
+                        }
+
+                        if let Some((present_mode, desired_maximum_frame_latency)) = presentation {
+                            painter.set_presentation(present_mode, desired_maximum_frame_latency);
+                        }
+
+                        if let Some((width, height)) = size {
+                            painter.on_window_resized(viewport_id, width, height);
+                        }
+
+                        if let Some(frame) = frame {
+                            let ViewportFrame {
+                                pixels_per_point,
+                                clear_color,
+                                clipped_primitives,
+                                textures_delta,
+                                capture,
+                            } = frame;
+
+                            let (_vsync_sec, screenshot) = painter.paint_and_update_textures(
+                                viewport_id,
+                                pixels_per_point,
+                                clear_color,
+                                &clipped_primitives,
+                                &textures_delta,
+                                capture,
+                            );
+
+                            if let Some(screenshot) = screenshot {
+                                if screenshot_tx.send(screenshot).is_ok() {
+                                    on_screenshot();
+                                }
+                            }
+                        }
+                    }
+
+                    painter.destroy();
+                }
+            })?;
+
+        Ok(Self {
+            mailbox,
+            screenshots,
+            join_handle: Some(join_handle),
+        })
+    }
+
+    fn post(&self, post: impl FnOnce(&mut Mailbox)) {
+        let (lock, condvar) = &*self.mailbox;
+        post(&mut lock.lock());
+        condvar.notify_one();
+    }
+
+    /// Paint a frame.
+    ///
+    /// If the previous frame has not been started yet, it is replaced by this one,
+    /// keeping its texture changes and screenshot request.
+    pub fn paint(&self, frame: ViewportFrame) {
+        self.post(|mailbox| {
+            mailbox.frame = Some(match mailbox.frame.take() {
+                Some(skipped) => {
+                    let mut textures_delta = skipped.textures_delta;
+                    textures_delta.append(frame.textures_delta);
+                    ViewportFrame {
+                        textures_delta,
+                        capture: skipped.capture || frame.capture,
+                        ..frame
+                    }
+                }
+                None => frame,
+            });
+        });
+    }
+
+    /// See [`Painter::on_window_resized`].
+    ///
+    /// Takes effect before the next frame is painted.
+    pub fn on_window_resized(&self, width_in_pixels: NonZeroU32, height_in_pixels: NonZeroU32) {
+        self.post(|mailbox| mailbox.size = Some((width_in_pixels, height_in_pixels)));
+    }
+
+    /// See [`Painter::set_presentation`].
+    ///
+    /// Takes effect before the next frame is painted.
+    pub fn set_presentation(
+        &self,
+        present_mode: wgpu::PresentMode,
+        desired_maximum_frame_latency: Option<u32>,
+    ) {
+        self.post(|mailbox| {
+            mailbox.presentation = Some((present_mode, desired_maximum_frame_latency));
+        });
+    }
+
+    /// The screenshots captured since the last call, see [`ViewportFrame::capture`].
+    pub fn take_screenshots(&self) -> Vec<epaint::ColorImage> {
+        self.screenshots.try_iter().collect()
+    }
+}
+
+impl Drop for ViewportRenderThread {
+    fn drop(&mut self) {
+        self.post(|mailbox| mailbox.shutdown = true);
+        if let Some(join_handle) = self.join_handle.take() {
+            if join_handle.join().is_err() {
+                log::error!("The render thread of a viewport panicked");
+            }
+        }
+    }
+}
@@ -22,6 +22,9 @@ pub struct Clipboard {
 
     /// Fallback manual clipboard.
     clipboard: String,
+
+    /// Fallback manual clipboard for images.
+    image: Option<std::sync::Arc<egui::ColorImage>>,
 }
 
 impl Clipboard {
@@ -44,6 +47,7 @@ impl Clipboard {
             smithay: init_smithay_clipboard(_raw_display_handle),
 
             clipboard: Default::default(),
+            image: None,
         }
     }
 
@@ -106,8 +110,65 @@ impl Clipboard {
             return;
         }
 
+        self.image = None;
         self.clipboard = text;
     }
+
+    /// Fetches an image from the clipboard, if it contains one.
+    pub fn get_image(&mut self) -> Option<egui::ColorImage> {
+        #[cfg(all(feature = "arboard", not(target_os = "android")))]
+        if let Some(clipboard) = &mut self.arboard {
+            return match clipboard.get_image() {
+                Ok(image) => Some(egui::ColorImage::from_rgba_unmultiplied(
+                    [image.width, image.height],
+                    &image.bytes,
+                )),
+                Err(arboard::Error::ContentNotAvailable) => None,
+                Err(err) => {
+                    log::error!("arboard image paste error: {err}");
+                    None
+                }
+            };
+        }
+
+        self.image.as_deref().cloned()
+    }
+
+    /// Places rich content on the clipboard.
+    ///
+    /// Content the OS clipboard can't hold is replaced with [`egui::ClipboardData::text_fallback`].
+    pub fn set_data(&mut self, data: egui::ClipboardData) {
+        #[cfg(all(feature = "arboard", not(target_os = "android")))]
+        if let Some(clipboard) = &mut self.arboard {
+            let result = match &data {
+                egui::ClipboardData::Image(image) => clipboard.set_image(arboard::ImageData {
+                    width: image.width(),
+                    height: image.height(),
+                    bytes: image
+                        .pixels
+                        .iter()
+                        .flat_map(|color| color.to_srgba_unmultiplied())
+                        .collect(),
+                }),
+                egui::ClipboardData::Html { html, alt_text } => {
+                    clipboard.set_html(html.as_str(), Some(alt_text.as_str()))
+                }
+                // arboard cannot place file lists on the clipboard, so we copy the paths as text.
+                egui::ClipboardData::Files(_) => clipboard.set_text(data.text_fallback()),
+            };
+            if let Err(err) = result {
+                log::error!("arboard copy error: {err}");
+            }
+            return;
+        }
+
+        if let egui::ClipboardData::Image(image) = data {
+            self.clipboard.clear();
+            self.image = Some(image);
+        } else {
+            self.set(data.text_fallback());
+        }
+    }
 }
 
 #[cfg(all(feature = "arboard", not(target_os = "android")))]
@@ -0,0 +1,154 @@
+//! Dragging [`egui::DragAndDrop`] payloads from one window to another.
+
+use egui::ViewportId;
+use winit::{
+    dpi::PhysicalPosition,
+    event::{ElementState, WindowEvent},
+    window::Window,
+};
+
+use crate::{EventResponse, State};
+
+/// Forwards the pointer to other windows while a [`egui::DragAndDrop`] payload
+/// is dragged out of the window the drag started in.
+///
+/// While a mouse button is held down, the OS keeps sending the pointer events
+/// to the window where the button was pressed, even after the pointer leaves it.
+/// This translates those events to the coordinates of the window under the pointer
+/// and gives them to that window instead, so the payload can be dropped there.
+///
+/// The [`egui::DragAndDrop`] payload itself is shared by all viewports of a [`egui::Context`].
+///
+/// This needs the windows' positions on the desktop, so it does nothing on e.g. Wayland.
+#[derive(Default)]
+pub struct DndForwarder {
+    /// The viewport we are currently forwarding the pointer to.
+    target: Option<ViewportId>,
+}
+
+impl DndForwarder {
+    /// Call this with each window event before giving it to the [`State`] of the window it is for.
+    ///
+    /// `viewports` should contain all viewports that have a window, including the `source` the event is for.
+    ///
+    /// If this returns `Some`, the event has been handled and should not be given to the `source` window.
+    /// Any other viewport that needs to repaint because of the event has been sent a repaint request.
+    pub fn on_window_event(
+        &mut self,
+        source: ViewportId,
+        viewports: &mut [(ViewportId, &Window, &mut State)],
+        event: &WindowEvent,
+    ) -> Option<EventResponse> {
+        let source_idx = viewports.iter().position(|(id, _, _)| *id == source)?;
+
+        match event {
+            WindowEvent::CursorMoved { position, .. } => {
+                let (_, source_window, source_state) = &viewports[source_idx];
+                if !egui::DragAndDrop::has_any_payload(source_state.egui_ctx())
+                    || window_contains(source_window, *position)
+                {
+                    self.stop(viewports);
+                    return None;
+                }
+
+                let inner_position = source_window.inner_position().ok()?;
+                let screen_pos = PhysicalPosition::new(
+                    position.x + f64::from(inner_position.x),
+                    position.y + f64::from(inner_position.y),
+                );
+                let Some((target_idx, pos_in_target)) =
+                    viewports
+                        .iter()
+                        .enumerate()
+                        .find_map(|(idx, (id, window, _))| {
+                            if *id == source
+                                || window.is_visible() == Some(false)
+                                || window.is_minimized() == Some(true)
+                            {
+                                return None;
+                            }
+                            let inner_position = window.inner_position().ok()?;
+                            let pos = PhysicalPosition::new(
+                                screen_pos.x - f64::from(inner_position.x),
+                                screen_pos.y - f64::from(inner_position.y),
+                            );
+                            window_contains(window, pos).then_some((idx, pos))
+                        })
+                else {
+                    // Not over any of our windows - let the source handle it as usual.
+                    self.stop(viewports);
+                    return None;
+                };
+
+                let target = viewports[target_idx].0;
+                if self.target != Some(target) {
+                    self.stop(viewports);
+                    log::trace!("Forwarding drag-and-drop from {source:?} to {target:?}");
+                    self.target = Some(target);
+
+                    // The source keeps its (stale) pointer position, so it can still be told about the release.
+                    let (_, _, source_state) = &mut viewports[source_idx];
+                    source_state
+                        .egui_input
+                        .events
+                        .push(egui::Event::PointerGone);
+                }
+
+                let (_, target_window, target_state) = &mut viewports[target_idx];
+                target_state.on_cursor_moved(target_window, pos_in_target);
+                target_state.egui_ctx().request_repaint_of(target);
+
+                Some(EventResponse {
+                    repaint: true,
+                    consumed: true,
+                })
+            }
+
+            WindowEvent::MouseInput { state, button, .. } => {
+                let target = self.target?;
+                let target_idx = viewports.iter().position(|(id, _, _)| *id == target)?;
+
+                let (_, _, target_state) = &mut viewports[target_idx];
+                target_state.on_mouse_button_input(*state, *button);
+                target_state.egui_ctx().request_repaint_of(target);
+
+                // The source needs to know the button was released too,
+                // but the pointer is not over it, so the payload is not dropped there.
+                let (_, _, source_state) = &mut viewports[source_idx];
+                source_state.on_mouse_button_input(*state, *button);
+                source_state
+                    .egui_input
+                    .events
+                    .push(egui::Event::PointerGone);
+
+                if *state == ElementState::Released {
+                    self.target = None;
+                }
+
+                Some(EventResponse {
+                    repaint: true,
+                    consumed: true,
+                })
+            }
+
+            _ => None,
+        }
+    }
+
+    /// Stop forwarding, telling the current target that the pointer left it.
+    fn stop(&mut self, viewports: &mut [(ViewportId, &Window, &mut State)]) {
+        let Some(target) = self.target.take() else {
+            return;
+        };
+        if let Some((_, _, state)) = viewports.iter_mut().find(|(id, _, _)| *id == target) {
+            state.pointer_pos_in_points = None;
+            state.egui_input.events.push(egui::Event::PointerGone);
+            state.egui_ctx().request_repaint_of(target);
+        }
+    }
+}
+
+fn window_contains(window: &Window, pos: PhysicalPosition<f64>) -> bool {
+    let size = window.inner_size();
+    0.0 <= pos.x && pos.x < f64::from(size.width) && 0.0 <= pos.y && pos.y < f64::from(size.height)
+}
@@ -14,13 +14,22 @@ pub use accesskit_winit;
 pub use egui;
 #[cfg(feature = "accesskit")]
 use egui::accesskit;
-use egui::{Pos2, Rect, Theme, Vec2, ViewportBuilder, ViewportCommand, ViewportId, ViewportInfo};
+use egui::{
+    Pos2, Rect, Theme, Vec2, ViewportBuilder, ViewportCommand, ViewportCommandOutcome,
+    ViewportCommandResult, ViewportId, ViewportInfo,
+};
 pub use winit;
 
 pub mod clipboard;
+mod dnd;
+mod monitors;
 mod window_settings;
 
-pub use window_settings::WindowSettings;
+pub use dnd::DndForwarder;
+pub use monitors::{
+    monitor_info, relocate_window, MonitorChanges, MonitorTracker, WindowRelocation,
+};
+pub use window_settings::{MonitorSettings, WindowSettings};
 
 use ahash::HashSet;
 use raw_window_handle::HasDisplayHandle;
@@ -47,10 +56,11 @@ pub fn screen_size_in_pixels(window: &Window) -> egui::Vec2 {
     egui::vec2(size.width as f32, size.height as f32)
 }
 
-/// Calculate the `pixels_per_point` for a given window, given the current egui zoom factor
-pub fn pixels_per_point(egui_ctx: &egui::Context, window: &Window) -> f32 {
+/// Calculate the `pixels_per_point` for the window of the given viewport,
+/// given the egui zoom factor of that viewport.
+pub fn pixels_per_point(egui_ctx: &egui::Context, viewport_id: ViewportId, window: &Window) -> f32 {
     let native_pixels_per_point = window.scale_factor() as f32;
-    let egui_zoom_factor = egui_ctx.zoom_factor();
+    let egui_zoom_factor = egui_ctx.zoom_factor_of(viewport_id);
     egui_zoom_factor * native_pixels_per_point
 }
 
@@ -101,6 +111,9 @@ pub struct State {
     /// Only one touch will be interpreted as pointer at any time.
     pointer_touch_id: Option<u64>,
 
+    /// The phase of the last mouse wheel event, used to tell momentum scrolling apart.
+    scroll_phase: egui::ScrollPhase,
+
     /// track ime state
     has_sent_ime_enabled: bool,
 
@@ -109,6 +122,17 @@ pub struct State {
 
     allow_ime: bool,
     ime_rect_px: Option<egui::Rect>,
+
+    monitor_relocation: WindowRelocation,
+
+    /// When the oldest input event that has not been taken with [`Self::take_egui_input`] arrived.
+    pending_input_time: Option<web_time::Instant>,
+
+    /// See [`Self::input_time`].
+    input_time: Option<web_time::Instant>,
+
+    /// See [`Self::set_os_drag_handler`].
+    os_drag_handler: Option<Box<dyn FnMut(&Window, egui::OsDragPayload)>>,
 }
 
 impl State {
@@ -144,6 +168,8 @@ impl State {
             simulate_touch_screen: false,
             pointer_touch_id: None,
 
+            scroll_phase: egui::ScrollPhase::Unknown,
+
             has_sent_ime_enabled: false,
 
             #[cfg(feature = "accesskit")]
@@ -151,6 +177,13 @@ impl State {
 
             allow_ime: false,
             ime_rect_px: None,
+
+            monitor_relocation: WindowRelocation::default(),
+
+            pending_input_time: None,
+            input_time: None,
+
+            os_drag_handler: None,
         };
 
         slf.egui_input
@@ -186,6 +219,18 @@ impl State {
         self.egui_input.max_texture_side = Some(max_texture_side);
     }
 
+    /// Start drag-and-drops of the operating system with this, for [`egui::PlatformOutput::os_drag`].
+    ///
+    /// `winit` can't start drags itself, so this is up to you,
+    /// e.g. using the [`drag`](https://docs.rs/drag) crate with the window handle.
+    /// Without a handler, dragging something out of the window just cancels the drag.
+    pub fn set_os_drag_handler(
+        &mut self,
+        os_drag_handler: impl FnMut(&Window, egui::OsDragPayload) + 'static,
+    ) {
+        self.os_drag_handler = Some(Box::new(os_drag_handler));
+    }
+
     /// Fetches text from the clipboard and returns it.
     pub fn clipboard_text(&mut self) -> Option<String> {
         self.clipboard.get()
@@ -196,6 +241,28 @@ impl State {
         self.clipboard.set(text);
     }
 
+    /// Fetches an image from the clipboard, if it contains one.
+    pub fn clipboard_image(&mut self) -> Option<egui::ColorImage> {
+        self.clipboard.get_image()
+    }
+
+    /// Reads the clipboard and queues an [`egui::Event::Paste`] with its text,
+    /// or an [`egui::Event::PasteImage`] if it holds an image instead.
+    pub fn push_paste_event(&mut self) {
+        let text = self
+            .clipboard
+            .get()
+            .map(|text| text.replace("\r\n", "\n"))
+            .unwrap_or_default();
+        if !text.is_empty() {
+            self.egui_input.events.push(egui::Event::Paste(text));
+        } else if let Some(image) = self.clipboard.get_image() {
+            self.egui_input
+                .events
+                .push(egui::Event::PasteImage(std::sync::Arc::new(image)));
+        }
+    }
+
     /// Returns [`false`] or the last value that [`Window::set_ime_allowed()`] was called with, used for debouncing.
     pub fn allow_ime(&self) -> bool {
         self.allow_ime
@@ -242,7 +309,7 @@ impl State {
         // This solves an issue where egui window positions would be changed when minimizing on Windows.
         let screen_size_in_pixels = screen_size_in_pixels(window);
         let screen_size_in_points =
-            screen_size_in_pixels / pixels_per_point(&self.egui_ctx, window);
+            screen_size_in_pixels / pixels_per_point(&self.egui_ctx, self.viewport_id, window);
 
         self.egui_input.screen_rect = (screen_size_in_points.x > 0.0
             && screen_size_in_points.y > 0.0)
@@ -257,9 +324,21 @@ impl State {
             .or_default()
             .native_pixels_per_point = Some(window.scale_factor() as f32);
 
+        self.input_time = self.pending_input_time.take();
+
         self.egui_input.take()
     }
 
+    /// When the oldest input event (keyboard, mouse, touch, …) in the [`egui::RawInput`]
+    /// last returned by [`Self::take_egui_input`] was received from winit.
+    ///
+    /// `None` if it contained no input events.
+    /// Integrations use this to measure the latency from input until the frame that
+    /// handles it is presented.
+    pub fn input_time(&self) -> Option<web_time::Instant> {
+        self.input_time
+    }
+
     /// Call this when there is a new event.
     ///
     /// The result can be found in [`Self::egui_input`] and be extracted with [`Self::take_egui_input`].
@@ -275,6 +354,11 @@ impl State {
             accesskit.process_event(window, event);
         }
 
+        if is_input_event(event) {
+            self.pending_input_time
+                .get_or_insert_with(web_time::Instant::now);
+        }
+
         use winit::event::WindowEvent;
         match event {
             WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
@@ -298,8 +382,8 @@ impl State {
                     consumed: self.egui_ctx.wants_pointer_input(),
                 }
             }
-            WindowEvent::MouseWheel { delta, .. } => {
-                self.on_mouse_wheel(window, *delta);
+            WindowEvent::MouseWheel { delta, phase, .. } => {
+                self.on_mouse_wheel(window, *delta, *phase);
                 EventResponse {
                     repaint: true,
                     consumed: self.egui_ctx.wants_pointer_input(),
@@ -338,6 +422,9 @@ impl State {
             WindowEvent::Ime(ime) => {
                 if cfg!(target_os = "linux") {
                     // We ignore IME events on linux, because of https://github.com/emilk/egui/issues/5008
+                } else if !self.allow_ime && !matches!(ime, winit::event::Ime::Disabled) {
+                    // egui turned the IME off (e.g. for a password field), but some platforms
+                    // still deliver composition events for a while, which we don't want to type.
                 } else {
                     // on Mac even Cmd-C is pressed during ime, a `c` is pushed to Preedit.
                     // So no need to check is_mac_cmd.
@@ -534,6 +621,32 @@ impl State {
         }));
     }
 
+    /// Call this when [`MonitorTracker::poll`] reports that the monitors changed.
+    ///
+    /// Sends [`egui::Event::MonitorConnected`] and [`egui::Event::MonitorDisconnected`] to egui,
+    /// and moves the window if needed, according to `policy`.
+    ///
+    /// Returns `true` if the window was moved.
+    pub fn on_monitors_changed(
+        &mut self,
+        window: &Window,
+        changes: &MonitorChanges,
+        policy: egui::MonitorRelocation,
+    ) -> bool {
+        for monitor in &changes.disconnected {
+            self.egui_input
+                .events
+                .push(egui::Event::MonitorDisconnected(monitor.clone()));
+        }
+        for monitor in &changes.connected {
+            self.egui_input
+                .events
+                .push(egui::Event::MonitorConnected(monitor.clone()));
+        }
+
+        relocate_window(window, policy, changes, &mut self.monitor_relocation)
+    }
+
     /// Call this when there is a new [`accesskit::ActionRequest`].
     ///
     /// The result can be found in [`Self::egui_input`] and be extracted with [`Self::take_egui_input`].
@@ -594,7 +707,7 @@ impl State {
         window: &Window,
         pos_in_pixels: winit::dpi::PhysicalPosition<f64>,
     ) {
-        let pixels_per_point = pixels_per_point(&self.egui_ctx, window);
+        let pixels_per_point = pixels_per_point(&self.egui_ctx, self.viewport_id, window);
 
         let pos_in_points = egui::pos2(
             pos_in_pixels.x as f32 / pixels_per_point,
@@ -624,7 +737,7 @@ impl State {
     }
 
     fn on_touch(&mut self, window: &Window, touch: &winit::event::Touch) {
-        let pixels_per_point = pixels_per_point(&self.egui_ctx, window);
+        let pixels_per_point = pixels_per_point(&self.egui_ctx, self.viewport_id, window);
 
         // Emit touch event
         self.egui_input.events.push(egui::Event::Touch {
@@ -688,8 +801,14 @@ impl State {
         }
     }
 
-    fn on_mouse_wheel(&mut self, window: &Window, delta: winit::event::MouseScrollDelta) {
-        let pixels_per_point = pixels_per_point(&self.egui_ctx, window);
+    fn on_mouse_wheel(
+        &mut self,
+        window: &Window,
+        delta: winit::event::MouseScrollDelta,
+        phase: winit::event::TouchPhase,
+    ) {
+        let pixels_per_point = pixels_per_point(&self.egui_ctx, self.viewport_id, window);
+        self.scroll_phase = next_scroll_phase(self.scroll_phase, phase);
 
         {
             let (unit, delta) = match delta {
@@ -709,6 +828,7 @@ impl State {
                 unit,
                 delta,
                 modifiers,
+                phase: self.scroll_phase,
             });
         }
     }
@@ -741,6 +861,8 @@ impl State {
 
         let pressed = *state == winit::event::ElementState::Pressed;
 
+        let scancode = scancode(*physical_key);
+
         let physical_key = if let winit::keyboard::PhysicalKey::Code(keycode) = *physical_key {
             key_from_key_code(keycode)
         } else {
@@ -771,12 +893,7 @@ impl State {
                     self.egui_input.events.push(egui::Event::Copy);
                     return;
                 } else if is_paste_command(self.egui_input.modifiers, active_key) {
-                    if let Some(contents) = self.clipboard.get() {
-                        let contents = contents.replace("\r\n", "\n");
-                        if !contents.is_empty() {
-                            self.egui_input.events.push(egui::Event::Paste(contents));
-                        }
-                    }
+                    self.push_paste_event();
                     return;
                 }
             }
@@ -784,6 +901,7 @@ impl State {
             self.egui_input.events.push(egui::Event::Key {
                 key: active_key,
                 physical_key,
+                scancode,
                 pressed,
                 repeat: false, // egui will fill this in for us!
                 modifiers: self.egui_input.modifiers,
@@ -814,7 +932,7 @@ impl State {
     ///
     /// This will, if needed:
     /// * update the cursor
-    /// * copy text to the clipboard
+    /// * copy text (or rich content) to the clipboard
     /// * open any clicked urls
     /// * update the IME
     /// *
@@ -829,6 +947,8 @@ impl State {
             cursor_icon,
             open_url,
             copied_text,
+            copied_data,
+            os_drag,
             events: _,                    // handled elsewhere
             mutable_text_under_cursor: _, // only used in eframe web
             ime,
@@ -844,10 +964,20 @@ impl State {
             open_url_in_browser(&open_url.url);
         }
 
-        if !copied_text.is_empty() {
+        if let Some(copied_data) = copied_data {
+            self.clipboard.set_data(copied_data);
+        } else if !copied_text.is_empty() {
             self.clipboard.set(copied_text);
         }
 
+        if let Some(os_drag) = os_drag {
+            if let Some(os_drag_handler) = &mut self.os_drag_handler {
+                os_drag_handler(window, os_drag);
+            } else {
+                log::debug!("Ignoring a drag out of the window, because no os_drag_handler is set");
+            }
+        }
+
         let allow_ime = ime.is_some();
         if self.allow_ime != allow_ime {
             self.allow_ime = allow_ime;
@@ -856,7 +986,7 @@ impl State {
         }
 
         if let Some(ime) = ime {
-            let pixels_per_point = pixels_per_point(&self.egui_ctx, window);
+            let pixels_per_point = pixels_per_point(&self.egui_ctx, self.viewport_id, window);
             let ime_rect_px = pixels_per_point * ime.rect;
             if self.ime_rect_px != Some(ime_rect_px)
                 || self.egui_ctx.input(|i| !i.events.is_empty())
@@ -950,12 +1080,13 @@ pub fn outer_rect_in_points(window: &Window, pixels_per_point: f32) -> Option<Re
 pub fn update_viewport_info(
     viewport_info: &mut ViewportInfo,
     egui_ctx: &egui::Context,
+    viewport_id: ViewportId,
     window: &Window,
     is_init: bool,
 ) {
     crate::profile_function!();
 
-    let pixels_per_point = pixels_per_point(egui_ctx, window);
+    let pixels_per_point = pixels_per_point(egui_ctx, viewport_id, window);
 
     let has_a_position = match window.is_minimized() {
         Some(true) => false,
@@ -984,10 +1115,27 @@ pub fn update_viewport_info(
         }
     };
 
+    let monitor_work_areas = {
+        crate::profile_scope!("monitor_work_areas");
+        // winit doesn't know about taskbars and docks, so use the whole monitors:
+        window
+            .available_monitors()
+            .map(|monitor| {
+                let pos = monitor.position();
+                let size = monitor.size();
+                egui::Rect::from_min_size(
+                    egui::pos2(pos.x as f32, pos.y as f32),
+                    egui::vec2(size.width as f32, size.height as f32),
+                ) / pixels_per_point
+            })
+            .collect()
+    };
+
     viewport_info.title = Some(window.title());
     viewport_info.native_pixels_per_point = Some(window.scale_factor() as f32);
 
     viewport_info.monitor_size = monitor_size;
+    viewport_info.monitor_work_areas = monitor_work_areas;
     viewport_info.inner_rect = inner_rect;
     viewport_info.outer_rect = outer_rect;
 
@@ -1045,6 +1193,34 @@ fn is_paste_command(modifiers: egui::Modifiers, keycode: egui::Key) -> bool {
         || (cfg!(target_os = "windows") && modifiers.shift && keycode == egui::Key::Insert)
 }
 
+/// Work out the [`egui::ScrollPhase`] of a mouse wheel event.
+///
+/// `winit` reports the start and end of the momentum scrolling on Mac
+/// as a second gesture right after the first one, so that is how we recognize it.
+/// Mouse wheels (and platforms without phases) only report [`winit::event::TouchPhase::Moved`].
+fn next_scroll_phase(
+    prev: egui::ScrollPhase,
+    phase: winit::event::TouchPhase,
+) -> egui::ScrollPhase {
+    use egui::ScrollPhase;
+    use winit::event::TouchPhase;
+
+    match (prev, phase) {
+        (ScrollPhase::Ended, TouchPhase::Started) => ScrollPhase::Momentum,
+        (_, TouchPhase::Started) => ScrollPhase::Started,
+        (ScrollPhase::Started | ScrollPhase::Moved, TouchPhase::Moved) => ScrollPhase::Moved,
+        (ScrollPhase::Momentum, TouchPhase::Moved) => ScrollPhase::Momentum,
+        (_, TouchPhase::Moved) => ScrollPhase::Unknown,
+        (ScrollPhase::Momentum, TouchPhase::Ended | TouchPhase::Cancelled) => {
+            ScrollPhase::MomentumEnded
+        }
+        (ScrollPhase::Started | ScrollPhase::Moved, TouchPhase::Ended | TouchPhase::Cancelled) => {
+            ScrollPhase::Ended
+        }
+        (_, TouchPhase::Ended | TouchPhase::Cancelled) => ScrollPhase::Unknown,
+    }
+}
+
 fn translate_mouse_button(button: winit::event::MouseButton) -> Option<egui::PointerButton> {
     match button {
         winit::event::MouseButton::Left => Some(egui::PointerButton::Primary),
@@ -1131,6 +1307,45 @@ fn key_from_named_key(named_key: winit::keyboard::NamedKey) -> Option<egui::Key>
     })
 }
 
+/// The platform-specific scancode of a physical key.
+#[cfg(any(
+    target_os = "windows",
+    target_os = "macos",
+    all(
+        any(feature = "x11", feature = "wayland"),
+        any(
+            target_os = "linux",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "netbsd",
+            target_os = "openbsd"
+        )
+    )
+))]
+fn scancode(physical_key: winit::keyboard::PhysicalKey) -> Option<u32> {
+    use winit::platform::scancode::PhysicalKeyExtScancode as _;
+    physical_key.to_scancode()
+}
+
+/// winit doesn't expose scancodes on this platform.
+#[cfg(not(any(
+    target_os = "windows",
+    target_os = "macos",
+    all(
+        any(feature = "x11", feature = "wayland"),
+        any(
+            target_os = "linux",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "netbsd",
+            target_os = "openbsd"
+        )
+    )
+)))]
+fn scancode(_physical_key: winit::keyboard::PhysicalKey) -> Option<u32> {
+    None
+}
+
 fn key_from_key_code(key: winit::keyboard::KeyCode) -> Option<egui::Key> {
     use egui::Key;
     use winit::keyboard::KeyCode;
@@ -1307,32 +1522,57 @@ pub enum ActionRequested {
     Paste,
 }
 
+/// Apply the commands to the window, in order.
+///
+/// What became of each command is pushed to [`ViewportInfo::command_results`],
+/// which should be handed to egui in the next pass.
 pub fn process_viewport_commands(
     egui_ctx: &egui::Context,
+    viewport_id: ViewportId,
     info: &mut ViewportInfo,
     commands: impl IntoIterator<Item = ViewportCommand>,
     window: &Window,
     actions_requested: &mut HashSet<ActionRequested>,
 ) {
     for command in commands {
-        process_viewport_command(egui_ctx, window, command, info, actions_requested);
+        let outcome = process_viewport_command(
+            egui_ctx,
+            viewport_id,
+            window,
+            command.clone(),
+            info,
+            actions_requested,
+        );
+        if let ViewportCommandOutcome::Failed(reason) = &outcome {
+            log::warn!("{command:?}: {reason}");
+        }
+        info.command_results
+            .push(ViewportCommandResult { command, outcome });
+    }
+}
+
+fn external_error_outcome(err: winit::error::ExternalError) -> ViewportCommandOutcome {
+    match err {
+        winit::error::ExternalError::NotSupported(_) => ViewportCommandOutcome::Unsupported,
+        err => ViewportCommandOutcome::Failed(err.to_string()),
     }
 }
 
 fn process_viewport_command(
     egui_ctx: &egui::Context,
+    viewport_id: ViewportId,
     window: &Window,
     command: ViewportCommand,
     info: &mut ViewportInfo,
     actions_requested: &mut HashSet<ActionRequested>,
-) {
+) -> ViewportCommandOutcome {
     crate::profile_function!();
 
     use winit::window::ResizeDirection;
 
     log::trace!("Processing ViewportCommand::{command:?}");
 
-    let pixels_per_point = pixels_per_point(egui_ctx, window);
+    let pixels_per_point = pixels_per_point(egui_ctx, viewport_id, window);
 
     match command {
         ViewportCommand::Close => {
@@ -1343,17 +1583,18 @@ fn process_viewport_command(
         }
         ViewportCommand::StartDrag => {
             // If `.has_focus()` is not checked on x11 the input will be permanently taken until the app is killed!
-            if window.has_focus() {
-                if let Err(err) = window.drag_window() {
-                    log::warn!("{command:?}: {err}");
-                }
+            if !window.has_focus() {
+                return ViewportCommandOutcome::Failed("the window does not have focus".to_owned());
+            }
+            if let Err(err) = window.drag_window() {
+                return external_error_outcome(err);
             }
         }
         ViewportCommand::InnerSize(size) => {
             let width_px = pixels_per_point * size.x.max(1.0);
             let height_px = pixels_per_point * size.y.max(1.0);
             let requested_size = PhysicalSize::new(width_px, height_px);
-            if let Some(_returned_inner_size) = window.request_inner_size(requested_size) {
+            if let Some(returned_inner_size) = window.request_inner_size(requested_size) {
                 // On platforms where the size is entirely controlled by the user the
                 // applied size will be returned immediately, resize event in such case
                 // may not be generated.
@@ -1363,12 +1604,21 @@ fn process_viewport_command(
                 // inner size is returned immediately, and the user one is ignored.
                 // e.g. Android, iOS, …
 
-                // However, comparing the results is prone to numerical errors
-                // because the linux backend converts physical to logical and back again.
-                // So let's just assume it worked:
-
                 info.inner_rect = inner_rect_in_points(window, pixels_per_point);
                 info.outer_rect = outer_rect_in_points(window, pixels_per_point);
+
+                // Comparing the results is prone to numerical errors
+                // because the linux backend converts physical to logical and back again,
+                // so only a clear difference counts as a failure:
+                let returned_inner_size = returned_inner_size.cast::<f32>();
+                if 1.5 < (returned_inner_size.width - width_px).abs()
+                    || 1.5 < (returned_inner_size.height - height_px).abs()
+                {
+                    return ViewportCommandOutcome::Failed(format!(
+                        "the window was given the inner size {}x{} px instead",
+                        returned_inner_size.width, returned_inner_size.height
+                    ));
+                }
             } else {
                 // e.g. macOS, Windows
                 // The request went to the display system,
@@ -1386,7 +1636,7 @@ fn process_viewport_command(
                 egui::viewport::ResizeDirection::NorthWest => ResizeDirection::NorthWest,
                 egui::viewport::ResizeDirection::SouthWest => ResizeDirection::SouthWest,
             }) {
-                log::warn!("{command:?}: {err}");
+                return external_error_outcome(err);
             }
         }
         ViewportCommand::Title(title) => {
@@ -1395,6 +1645,10 @@ fn process_viewport_command(
         ViewportCommand::Transparent(v) => window.set_transparent(v),
         ViewportCommand::Visible(v) => window.set_visible(v),
         ViewportCommand::OuterPosition(pos) => {
+            if window.outer_position().is_err() {
+                // e.g. Wayland, where windows can't be positioned by the application
+                return ViewportCommandOutcome::Unsupported;
+            }
             window.set_outer_position(PhysicalPosition::new(
                 pixels_per_point * pos.x,
                 pixels_per_point * pos.y,
@@ -1498,7 +1752,7 @@ fn process_viewport_command(
                 pixels_per_point * pos.x,
                 pixels_per_point * pos.y,
             )) {
-                log::warn!("{command:?}: {err}");
+                return external_error_outcome(err);
             }
         }
         ViewportCommand::CursorGrab(o) => {
@@ -1507,13 +1761,13 @@ fn process_viewport_command(
                 egui::viewport::CursorGrab::Confined => CursorGrabMode::Confined,
                 egui::viewport::CursorGrab::Locked => CursorGrabMode::Locked,
             }) {
-                log::warn!("{command:?}: {err}");
+                return external_error_outcome(err);
             }
         }
         ViewportCommand::CursorVisible(v) => window.set_cursor_visible(v),
         ViewportCommand::MousePassthrough(passthrough) => {
             if let Err(err) = window.set_cursor_hittest(!passthrough) {
-                log::warn!("{command:?}: {err}");
+                return external_error_outcome(err);
             }
         }
         ViewportCommand::Screenshot => {
@@ -1529,6 +1783,8 @@ fn process_viewport_command(
             actions_requested.insert(ActionRequested::Paste);
         }
     }
+
+    ViewportCommandOutcome::Applied
 }
 
 /// Build and intitlaize a window.
@@ -1539,20 +1795,22 @@ fn process_viewport_command(
 /// Possible causes of error include denied permission, incompatible system, and lack of memory.
 pub fn create_window(
     egui_ctx: &egui::Context,
+    viewport_id: ViewportId,
     event_loop: &ActiveEventLoop,
     viewport_builder: &ViewportBuilder,
 ) -> Result<Window, winit::error::OsError> {
     crate::profile_function!();
 
     let window_attributes =
-        create_winit_window_attributes(egui_ctx, event_loop, viewport_builder.clone());
+        create_winit_window_attributes(egui_ctx, viewport_id, event_loop, viewport_builder.clone());
     let window = event_loop.create_window(window_attributes)?;
-    apply_viewport_builder_to_window(egui_ctx, &window, viewport_builder);
+    apply_viewport_builder_to_window(egui_ctx, viewport_id, &window, viewport_builder);
     Ok(window)
 }
 
 pub fn create_winit_window_attributes(
     egui_ctx: &egui::Context,
+    viewport_id: ViewportId,
     event_loop: &ActiveEventLoop,
     viewport_builder: ViewportBuilder,
 ) -> winit::window::WindowAttributes {
@@ -1572,7 +1830,7 @@ pub fn create_winit_window_attributes(
             },
             |m| m.scale_factor() as f32,
         );
-    let zoom_factor = egui_ctx.zoom_factor();
+    let zoom_factor = egui_ctx.zoom_factor_of(viewport_id);
     let pixels_per_point = zoom_factor * native_pixels_per_point;
 
     let ViewportBuilder {
@@ -1612,6 +1870,10 @@ pub fn create_winit_window_attributes(
 
         mouse_passthrough: _, // handled in `apply_viewport_builder_to_window`
         clamp_size_to_monitor_size: _, // Handled in `viewport_builder` in `epi_integration.rs`
+        monitor_relocation: _, // Handled in `State::on_monitors_changed`
+        modal: _,             // Handled by the integration, which knows the parent window
+        modal_parent: _,      // Handled by the integration, which knows the parent window
+        surface: _,           // Handled by the integration, which creates the surface
     } = viewport_builder;
 
     let mut window_attributes = winit::window::WindowAttributes::default()
@@ -1766,6 +2028,7 @@ fn to_winit_icon(icon: &egui::IconData) -> Option<winit::window::Icon> {
 /// Applies what `create_winit_window_builder` couldn't
 pub fn apply_viewport_builder_to_window(
     egui_ctx: &egui::Context,
+    viewport_id: ViewportId,
     window: &Window,
     builder: &ViewportBuilder,
 ) {
@@ -1781,7 +2044,7 @@ pub fn apply_viewport_builder_to_window(
         // how to translate egui ui point to native physical pixels.
         // Now we do know:
 
-        let pixels_per_point = pixels_per_point(egui_ctx, window);
+        let pixels_per_point = pixels_per_point(egui_ctx, viewport_id, window);
 
         if let Some(size) = builder.inner_size {
             if window
@@ -1831,6 +2094,25 @@ pub fn short_device_event_description(event: &winit::event::DeviceEvent) -> &'st
     }
 }
 
+/// Is this event direct input from the user, e.g. from a keyboard, mouse or touch screen?
+fn is_input_event(event: &winit::event::WindowEvent) -> bool {
+    use winit::event::WindowEvent;
+
+    matches!(
+        event,
+        WindowEvent::KeyboardInput { .. }
+            | WindowEvent::Ime(_)
+            | WindowEvent::MouseInput { .. }
+            | WindowEvent::MouseWheel { .. }
+            | WindowEvent::CursorMoved { .. }
+            | WindowEvent::Touch(_)
+            | WindowEvent::PinchGesture { .. }
+            | WindowEvent::PanGesture { .. }
+            | WindowEvent::RotationGesture { .. }
+            | WindowEvent::DoubleTapGesture { .. }
+    )
+}
+
 /// Short and fast description of a window event.
 /// Useful for logging and profiling.
 pub fn short_window_event_description(event: &winit::event::WindowEvent) -> &'static str {
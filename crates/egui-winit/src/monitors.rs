@@ -0,0 +1,190 @@
+//! Detecting monitors being connected and disconnected,
+//! and moving windows that end up off-screen because of it.
+
+use egui::{MonitorInfo, MonitorRelocation, Pos2, Rect, Vec2};
+use winit::{
+    dpi::PhysicalPosition, event_loop::ActiveEventLoop, monitor::MonitorHandle, window::Window,
+};
+
+/// How often [`MonitorTracker::poll`] asks the OS for the list of monitors.
+const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// A window must show at least this many pixels (in both directions)
+/// on some monitor to not count as off-screen.
+const MIN_VISIBLE_PX: f32 = 32.0;
+
+pub fn monitor_info(monitor: &MonitorHandle) -> MonitorInfo {
+    let position = monitor.position();
+    let size = monitor.size();
+    MonitorInfo {
+        name: monitor.name(),
+        position_px: Pos2::new(position.x as f32, position.y as f32),
+        size_px: Vec2::new(size.width as f32, size.height as f32),
+        scale_factor: monitor.scale_factor() as f32,
+    }
+}
+
+/// Returned by [`MonitorTracker::poll`] when the set of monitors has changed.
+#[derive(Clone, Debug, Default)]
+pub struct MonitorChanges {
+    /// Monitors that are new since the last poll.
+    pub connected: Vec<MonitorInfo>,
+
+    /// Monitors that have gone away since the last poll.
+    pub disconnected: Vec<MonitorInfo>,
+
+    /// All the monitors that are connected now.
+    pub monitors: Vec<MonitorInfo>,
+}
+
+/// Keeps track of which monitors are connected.
+///
+/// winit has no event for monitors being connected or disconnected,
+/// so call [`Self::poll`] whenever the event loop wakes up.
+/// Connecting or disconnecting a monitor almost always causes some window event,
+/// so this is enough to notice.
+#[derive(Default)]
+pub struct MonitorTracker {
+    /// `None` before the first poll.
+    monitors: Option<Vec<MonitorInfo>>,
+    last_poll: Option<web_time::Instant>,
+}
+
+impl MonitorTracker {
+    /// Check if the monitors have changed since the last call.
+    ///
+    /// Only actually asks the OS once per second, since that can be slow.
+    pub fn poll(&mut self, event_loop: &ActiveEventLoop) -> Option<MonitorChanges> {
+        let now = web_time::Instant::now();
+        if self
+            .last_poll
+            .is_some_and(|last_poll| now - last_poll < POLL_INTERVAL)
+        {
+            return None;
+        }
+        self.last_poll = Some(now);
+
+        crate::profile_function!();
+
+        let monitors: Vec<MonitorInfo> = event_loop
+            .available_monitors()
+            .map(|monitor| monitor_info(&monitor))
+            .collect();
+        let previous = self.monitors.replace(monitors.clone())?;
+
+        if previous == monitors {
+            return None;
+        }
+
+        // Note: a monitor that changed resolution or was rearranged is neither connected nor disconnected,
+        // but we still report the change so windows can be moved back on-screen.
+        let connected = monitors
+            .iter()
+            .filter(|monitor| !previous.iter().any(|old| old.is_same_monitor(monitor)))
+            .cloned()
+            .collect();
+        let disconnected = previous
+            .iter()
+            .filter(|old| !monitors.iter().any(|monitor| monitor.is_same_monitor(old)))
+            .cloned()
+            .collect();
+
+        Some(MonitorChanges {
+            connected,
+            disconnected,
+            monitors,
+        })
+    }
+}
+
+/// Remembers where [`relocate_window`] moved a window from,
+/// so it can be moved back with [`MonitorRelocation::NearestAndRestore`].
+#[derive(Clone, Debug, Default)]
+pub struct WindowRelocation {
+    /// The disconnected monitor the window was on, and its outer position at the time.
+    displaced_from: Option<(MonitorInfo, PhysicalPosition<i32>)>,
+}
+
+/// Move a window after the monitors changed, according to the given policy.
+///
+/// Returns `true` if the window was moved.
+pub fn relocate_window(
+    window: &Window,
+    policy: MonitorRelocation,
+    changes: &MonitorChanges,
+    relocation: &mut WindowRelocation,
+) -> bool {
+    if policy == MonitorRelocation::Keep
+        || window.fullscreen().is_some()
+        || window.is_minimized() == Some(true)
+    {
+        return false;
+    }
+
+    if policy == MonitorRelocation::NearestAndRestore {
+        if let Some((monitor, position)) = &relocation.displaced_from {
+            if changes
+                .connected
+                .iter()
+                .any(|connected| connected.is_same_monitor(monitor))
+            {
+                log::debug!("Monitor {:?} is back - restoring window", monitor.name);
+                window.set_outer_position(*position);
+                relocation.displaced_from = None;
+                return true;
+            }
+        }
+    }
+
+    let Ok(position) = window.outer_position() else {
+        return false; // e.g. on Wayland
+    };
+    let size = window.outer_size();
+    let window_rect = Rect::from_min_size(
+        Pos2::new(position.x as f32, position.y as f32),
+        Vec2::new(size.width as f32, size.height as f32),
+    );
+
+    let Some(new_pos) = relocated_position(window_rect, &changes.monitors) else {
+        return false;
+    };
+
+    if policy == MonitorRelocation::NearestAndRestore && relocation.displaced_from.is_none() {
+        relocation.displaced_from = changes
+            .disconnected
+            .iter()
+            .find(|monitor| monitor.rect_px().intersects(window_rect))
+            .map(|monitor| (monitor.clone(), position));
+    }
+
+    log::debug!("Window is off-screen - moving it to {new_pos:?}");
+    window.set_outer_position(PhysicalPosition::new(
+        new_pos.x.round() as i32,
+        new_pos.y.round() as i32,
+    ));
+    true
+}
+
+/// Where to move a window so it is visible on one of the monitors,
+/// or `None` if it is visible already.
+fn relocated_position(window_rect: Rect, monitors: &[MonitorInfo]) -> Option<Pos2> {
+    let is_visible = monitors.iter().any(|monitor| {
+        let visible = monitor.rect_px().intersect(window_rect);
+        MIN_VISIBLE_PX <= visible.width() && MIN_VISIBLE_PX <= visible.height()
+    });
+    if is_visible {
+        return None;
+    }
+
+    let center = window_rect.center();
+    let nearest = monitors.iter().min_by(|a, b| {
+        let a = a.rect_px().distance_sq_to_pos(center);
+        let b = b.rect_px().distance_sq_to_pos(center);
+        a.total_cmp(&b)
+    })?;
+
+    // Fit as much of the window as we can, keeping the top-left corner (title bar) visible:
+    let monitor_rect = nearest.rect_px();
+    let max = (monitor_rect.max - window_rect.size()).max(monitor_rect.min);
+    Some(window_rect.min.clamp(monitor_rect.min, max))
+}
@@ -5,6 +5,11 @@ use egui::ViewportBuilder;
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[cfg_attr(feature = "serde", serde(default))]
 pub struct WindowSettings {
+    /// The version of the format the settings were saved with.
+    ///
+    /// `0` for settings saved before [`Self::CURRENT_VERSION`] was introduced.
+    version: u32,
+
     /// Position of window content in physical pixels.
     inner_position_pixels: Option<egui::Pos2>,
 
@@ -13,15 +18,64 @@ pub struct WindowSettings {
 
     fullscreen: bool,
 
-    /// Inner size of window in logical pixels
+    /// Inner size of window in egui points, i.e. depending on the egui zoom factor.
+    ///
+    /// Only used for reading settings of version `0`; see `inner_size_logical` for the real size.
     inner_size_points: Option<egui::Vec2>,
+
+    /// Inner size of window in logical pixels,
+    /// i.e. physical pixels divided by the scale factor of the monitor.
+    ///
+    /// Unlike egui points, this doesn't depend on the egui zoom factor.
+    inner_size_logical: Option<egui::Vec2>,
+
+    /// The monitor the window was on, if known.
+    monitor: Option<MonitorSettings>,
+}
+
+/// Identifies the monitor a window was on when its [`WindowSettings`] were saved.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct MonitorSettings {
+    /// Position of the monitor in the desktop, in physical pixels.
+    position_pixels: egui::Pos2,
+
+    /// Size of the monitor in physical pixels.
+    size_pixels: egui::Vec2,
+
+    /// The scale factor of the monitor, i.e. physical pixels per logical pixel.
+    scale_factor: f32,
+}
+
+impl MonitorSettings {
+    fn from_monitor(monitor: &winit::monitor::MonitorHandle) -> Self {
+        let position = monitor.position();
+        let size = monitor.size();
+        Self {
+            position_pixels: egui::pos2(position.x as f32, position.y as f32),
+            size_pixels: egui::vec2(size.width as f32, size.height as f32),
+            scale_factor: monitor.scale_factor() as f32,
+        }
+    }
+
+    /// Is this the same monitor, in the same place in the desktop?
+    ///
+    /// The scale factor of a monitor may change without it becoming a different monitor.
+    fn is_same_monitor(&self, other: &Self) -> bool {
+        self.position_pixels == other.position_pixels && self.size_pixels == other.size_pixels
+    }
 }
 
 impl WindowSettings {
+    /// The version of the format saved by [`Self::from_window`].
+    ///
+    /// * `0`: the size is in egui points.
+    /// * `1`: the size is in logical pixels, and the monitor is saved.
+    pub const CURRENT_VERSION: u32 = 1;
+
     pub fn from_window(egui_zoom_factor: f32, window: &winit::window::Window) -> Self {
-        let inner_size_points = window
-            .inner_size()
-            .to_logical::<f32>(egui_zoom_factor as f64 * window.scale_factor());
+        let inner_size_logical = window.inner_size().to_logical::<f32>(window.scale_factor());
+        let inner_size_logical = egui::vec2(inner_size_logical.width, inner_size_logical.height);
 
         let inner_position_pixels = window
             .inner_position()
@@ -34,18 +88,59 @@ impl WindowSettings {
             .map(|p| egui::pos2(p.x as f32, p.y as f32));
 
         Self {
+            version: Self::CURRENT_VERSION,
+
             inner_position_pixels,
             outer_position_pixels,
 
             fullscreen: window.fullscreen().is_some(),
 
-            inner_size_points: Some(egui::vec2(
-                inner_size_points.width,
-                inner_size_points.height,
-            )),
+            // Also saved for older versions of the app reading these settings:
+            inner_size_points: Some(inner_size_logical / egui_zoom_factor),
+            inner_size_logical: Some(inner_size_logical),
+
+            monitor: window
+                .current_monitor()
+                .map(|monitor| MonitorSettings::from_monitor(&monitor)),
+        }
+    }
+
+    /// Adapt settings that were saved with a different egui zoom factor,
+    /// by an older version, or on a different arrangement of monitors.
+    ///
+    /// Call this once after loading the settings, before any of the other methods.
+    pub fn normalize(
+        &mut self,
+        egui_zoom_factor: f32,
+        event_loop: &winit::event_loop::ActiveEventLoop,
+    ) {
+        crate::profile_function!();
+
+        if self.version == 0 {
+            // The best guess we have is that the zoom factor hasn't changed since then:
+            self.inner_size_logical = self.inner_size_points.map(|size| size * egui_zoom_factor);
+            self.version = Self::CURRENT_VERSION;
+        }
+
+        // The rest of the code works in the points of the current zoom factor:
+        self.inner_size_points = self.inner_size_logical.map(|size| size / egui_zoom_factor);
+
+        if let Some(monitor) = self.monitor {
+            let monitor_is_still_there = event_loop
+                .available_monitors()
+                .any(|m| MonitorSettings::from_monitor(&m).is_same_monitor(&monitor));
+            if !monitor_is_still_there {
+                // The position is on a monitor that is gone or has moved,
+                // so let the OS pick a new position:
+                log::debug!("The monitor of the saved window position is gone: {monitor:?}");
+                self.inner_position_pixels = None;
+                self.outer_position_pixels = None;
+                self.monitor = None;
+            }
         }
     }
 
+    /// The inner size of the window, in egui points of the zoom factor given to [`Self::normalize`].
     pub fn inner_size_points(&self) -> Option<egui::Vec2> {
         self.inner_size_points
     }
@@ -3,8 +3,9 @@
 //! It is the foundation for windows and popups.
 
 use crate::{
-    emath, pos2, Align2, Context, Id, InnerResponse, LayerId, NumExt, Order, Pos2, Rect, Response,
-    Sense, Ui, UiBuilder, UiKind, UiStackInfo, Vec2, WidgetRect, WidgetWithState,
+    emath, pos2, Align2, Context, EventFilter, Id, InnerResponse, Key, LayerId, Modifiers, NumExt,
+    Order, Pos2, Rect, Response, Sense, Ui, UiBuilder, UiKind, UiStackInfo, Vec2, WidgetRect,
+    WidgetWithState,
 };
 
 /// State of an [`Area`] that is persisted between frames.
@@ -84,6 +85,49 @@ impl AreaState {
     }
 }
 
+/// How far each press of an arrow key moves (or resizes) an [`Area`] in keyboard-move mode.
+///
+/// Unit: points.
+const KEYBOARD_MOVE_STEP: f32 = 10.0;
+
+/// An ongoing keyboard move/resize of an [`Area`], started with [`Area::start_keyboard_move`].
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct KeyboardMove {
+    pub area_id: Id,
+
+    /// Where the area was when we started, so that we can go back there on cancel.
+    start_pivot_pos: Option<Pos2>,
+
+    /// The size when we started, so that we can go back to it on cancel.
+    ///
+    /// Only resizable containers (i.e. [`crate::Window`]) set this.
+    pub start_size: Option<Vec2>,
+
+    /// How much the user wants to grow the area (using Shift + arrow keys), compared to [`Self::start_size`].
+    pub size_delta: Vec2,
+
+    /// The user pressed Enter or Escape (or clicked somewhere), so this is the last frame.
+    pub finished: bool,
+
+    /// The user pressed Escape: restore the original position and size.
+    pub cancelled: bool,
+}
+
+impl KeyboardMove {
+    fn load(ctx: &Context) -> Option<Self> {
+        ctx.data(|d| d.get_temp(Id::NULL))
+    }
+
+    /// Remember changes made by the container, unless we are done.
+    pub(crate) fn store(self, ctx: &Context) {
+        if self.finished {
+            ctx.data_mut(|d| d.remove::<Self>(Id::NULL));
+        } else {
+            ctx.data_mut(|d| d.insert_temp(Id::NULL, self));
+        }
+    }
+}
+
 /// An area on the screen that can be moved by dragging.
 ///
 /// This forms the base of the [`crate::Window`] container.
@@ -110,6 +154,7 @@ pub struct Area {
     enabled: bool,
     constrain: bool,
     constrain_rect: Option<Rect>,
+    snap: bool,
     order: Order,
     default_pos: Option<Pos2>,
     default_size: Vec2,
@@ -134,6 +179,7 @@ impl Area {
             interactable: true,
             constrain: true,
             constrain_rect: None,
+            snap: true,
             enabled: true,
             order: Order::Middle,
             default_pos: None,
@@ -283,6 +329,15 @@ impl Area {
         self
     }
 
+    /// Snap to edges and a grid while being dragged, as set up in [`crate::style::WindowSnap`]?
+    ///
+    /// Default: `true` (but the snapping in the style is off by default).
+    #[inline]
+    pub fn snap(mut self, snap: bool) -> Self {
+        self.snap = snap;
+        self
+    }
+
     /// Where the "root" of the area is.
     ///
     /// For instance, if you set this to [`Align2::RIGHT_TOP`]
@@ -346,6 +401,7 @@ pub(crate) struct Prepared {
     enabled: bool,
     constrain: bool,
     constrain_rect: Rect,
+    keyboard_move: Option<KeyboardMove>,
 
     /// We always make windows invisible the first frame to hide "first-frame-jitters".
     ///
@@ -370,6 +426,35 @@ impl Area {
         InnerResponse { inner, response }
     }
 
+    /// Start moving the movable [`Area`] (or [`crate::Window`]) with the given id using the keyboard.
+    ///
+    /// This is an accessibility feature for users who can't drag with a pointer.
+    /// While active, the arrow keys move the area and Shift + arrow keys resize it
+    /// (if it is a resizable [`crate::Window`]).
+    /// Enter confirms the new position and size, and Escape restores the old ones.
+    ///
+    /// [`crate::Window`]s start this from the context menu of the title bar,
+    /// or when pressing Alt+F7 while a widget in the window has keyboard focus.
+    pub fn start_keyboard_move(ctx: &Context, id: Id) {
+        KeyboardMove {
+            area_id: id,
+            start_pivot_pos: None,
+            start_size: None,
+            size_delta: Vec2::ZERO,
+            finished: false,
+            cancelled: false,
+        }
+        .store(ctx);
+        ctx.request_repaint();
+    }
+
+    /// Is the [`Area`] with the given id being moved with the keyboard?
+    ///
+    /// See [`Self::start_keyboard_move`].
+    pub fn is_keyboard_moving(ctx: &Context, id: Id) -> bool {
+        KeyboardMove::load(ctx).is_some_and(|keyboard_move| keyboard_move.area_id == id)
+    }
+
     pub(crate) fn begin(self, ctx: &Context) -> Prepared {
         let Self {
             id,
@@ -386,6 +471,7 @@ impl Area {
             anchor,
             constrain,
             constrain_rect,
+            snap,
             fade_in,
         } = self;
 
@@ -475,7 +561,9 @@ impl Area {
             );
 
             if movable && move_response.dragged() {
-                if let Some(pivot_pos) = &mut state.pivot_pos {
+                if snap && ctx.style().interaction.window_snap.is_enabled() {
+                    drag_with_snapping(ctx, layer_id, &move_response, &mut state, constrain_rect);
+                } else if let Some(pivot_pos) = &mut state.pivot_pos {
                     *pivot_pos += move_response.drag_delta();
                 }
             }
@@ -491,6 +579,12 @@ impl Area {
             move_response
         };
 
+        let keyboard_move = if movable && enabled {
+            keyboard_move_interaction(ctx, layer_id, move_response.id, &mut state)
+        } else {
+            None
+        };
+
         if constrain {
             state.set_left_top_pos(
                 ctx.constrain_window_rect_to_area(state.rect(), constrain_rect)
@@ -512,12 +606,105 @@ impl Area {
             enabled,
             constrain,
             constrain_rect,
+            keyboard_move,
             sizing_pass,
             fade_in,
         }
     }
 }
 
+/// Move the area with the arrow keys, if [`Area::start_keyboard_move`] was called for it.
+fn keyboard_move_interaction(
+    ctx: &Context,
+    layer_id: LayerId,
+    interact_id: Id,
+    state: &mut AreaState,
+) -> Option<KeyboardMove> {
+    let mut keyboard_move = KeyboardMove::load(ctx).filter(|m| m.area_id == layer_id.id)?;
+    if keyboard_move.start_pivot_pos.is_none() {
+        keyboard_move.start_pivot_pos = state.pivot_pos;
+        ctx.memory_mut(|mem| mem.areas_mut().move_to_top(layer_id));
+    }
+
+    // Keep the keyboard focus, and with it the arrow keys and Escape:
+    ctx.memory_mut(|mem| {
+        if !mem.has_focus(interact_id) {
+            mem.request_focus(interact_id);
+        }
+        mem.set_focus_lock_filter(
+            interact_id,
+            EventFilter {
+                tab: false,
+                horizontal_arrows: true,
+                vertical_arrows: true,
+                escape: true,
+            },
+        );
+    });
+
+    let mut move_delta = Vec2::ZERO;
+    let mut size_delta = Vec2::ZERO;
+    let (confirm, cancel) = ctx.input_mut(|i| {
+        for (key, direction) in [
+            (Key::ArrowLeft, -Vec2::X),
+            (Key::ArrowRight, Vec2::X),
+            (Key::ArrowUp, -Vec2::Y),
+            (Key::ArrowDown, Vec2::Y),
+        ] {
+            // Shift first, since `consume_key` ignores extra Shift modifiers:
+            size_delta += i.count_and_consume_key(Modifiers::SHIFT, key) as f32 * direction;
+            move_delta += i.count_and_consume_key(Modifiers::NONE, key) as f32 * direction;
+        }
+        let confirm = i.consume_key(Modifiers::NONE, Key::Enter) || i.pointer.any_pressed();
+        let cancel = i.consume_key(Modifiers::NONE, Key::Escape);
+        (confirm, cancel)
+    });
+
+    if let Some(pivot_pos) = &mut state.pivot_pos {
+        *pivot_pos += KEYBOARD_MOVE_STEP * move_delta;
+    }
+    keyboard_move.size_delta += KEYBOARD_MOVE_STEP * size_delta;
+
+    if cancel {
+        state.pivot_pos = keyboard_move.start_pivot_pos;
+        keyboard_move.size_delta = Vec2::ZERO;
+        keyboard_move.cancelled = true;
+    }
+    keyboard_move.finished = confirm || cancel;
+    if keyboard_move.finished {
+        ctx.memory_mut(|mem| mem.surrender_focus(interact_id));
+    }
+    keyboard_move.store(ctx);
+
+    if move_delta != Vec2::ZERO || size_delta != Vec2::ZERO || keyboard_move.finished {
+        ctx.request_repaint();
+    }
+
+    #[cfg(feature = "accesskit")]
+    ctx.accesskit_node_builder(interact_id, |builder| {
+        // Announce the instructions, and then every change of position:
+        builder.set_live(accesskit::Live::Polite);
+        if keyboard_move.finished {
+            builder.set_description(if cancel { "Move cancelled" } else { "Moved" });
+        } else {
+            builder.set_description(
+                "Moving: use the arrow keys to move, Shift + arrow keys to resize, \
+                 Enter to confirm and Escape to cancel",
+            );
+        }
+        let rect = state.rect();
+        builder.set_value(format!(
+            "Position {:.0}, {:.0}, size {:.0} × {:.0}",
+            rect.left(),
+            rect.top(),
+            rect.width(),
+            rect.height()
+        ));
+    });
+
+    Some(keyboard_move)
+}
+
 impl Prepared {
     pub(crate) fn state(&self) -> &AreaState {
         &self.state
@@ -535,6 +722,11 @@ impl Prepared {
         self.constrain_rect
     }
 
+    /// Set if the area is being moved with the keyboard, see [`Area::start_keyboard_move`].
+    pub(crate) fn keyboard_move(&self) -> Option<KeyboardMove> {
+        self.keyboard_move
+    }
+
     pub(crate) fn content_ui(&self, ctx: &Context) -> Ui {
         let max_rect = self.state.rect();
 
@@ -616,6 +808,125 @@ fn pointer_pressed_on_area(ctx: &Context, layer_id: LayerId) -> bool {
     }
 }
 
+/// Move the dragged area, snapping it into place, see [`crate::style::WindowSnap`].
+fn drag_with_snapping(
+    ctx: &Context,
+    layer_id: LayerId,
+    move_response: &Response,
+    state: &mut AreaState,
+    screen_rect: Rect,
+) {
+    let snap = ctx.style().interaction.window_snap;
+
+    // Keep track of where the area would be without snapping,
+    // so that it can be dragged away from an edge again:
+    let unsnapped_id = layer_id.id.with("unsnapped_pos");
+    let unsnapped_pos = if move_response.drag_started() {
+        None
+    } else {
+        ctx.data(|d| d.get_temp::<Pos2>(unsnapped_id))
+    };
+    let unsnapped_pos =
+        unsnapped_pos.unwrap_or_else(|| state.left_top_pos()) + move_response.drag_delta();
+    ctx.data_mut(|d| d.insert_temp(unsnapped_id, unsnapped_pos));
+
+    let rect = Rect::from_min_size(unsnapped_pos, state.size.unwrap_or_default());
+    let other_windows: Vec<Rect> = ctx.memory(|mem| {
+        mem.areas()
+            .visible_windows()
+            .filter(|(other, _)| *other != layer_id)
+            .map(|(_, other)| other.rect())
+            .collect()
+    });
+    let (offset, guides) = snap_offset(rect, screen_rect, &other_windows, &snap);
+    state.set_left_top_pos(rect.min + offset);
+
+    if snap.show_guides {
+        let painter = ctx.layer_painter(LayerId::new(Order::Foreground, Id::new("snap_guides")));
+        let stroke = ctx.style().visuals.selection.stroke;
+        for guide in guides {
+            painter.line_segment(guide, stroke);
+        }
+    }
+}
+
+/// How far to move `rect` so it snaps to the edges of `screen_rect`, to the edges of `other_windows`,
+/// or to the grid, together with the guide lines to show for the edges it snapped to.
+fn snap_offset(
+    rect: Rect,
+    screen_rect: Rect,
+    other_windows: &[Rect],
+    snap: &crate::style::WindowSnap,
+) -> (Vec2, Vec<[Pos2; 2]>) {
+    let mut offset = Vec2::ZERO;
+    let mut guides = vec![];
+
+    for axis in 0..2 {
+        let across = 1 - axis;
+
+        // A line along `axis = at`, from `from` to `to` along the other axis:
+        let line = |at: f32, from: f32, to: f32| {
+            let mut a = Pos2::ZERO;
+            let mut b = Pos2::ZERO;
+            a[axis] = at;
+            b[axis] = at;
+            a[across] = from;
+            b[across] = to;
+            [a, b]
+        };
+
+        // The smallest move, and the guide to show for it:
+        let mut best: Option<(f32, [Pos2; 2])> = None;
+        let mut consider = |target: f32, edge: f32, max_distance: f32, guide: [Pos2; 2]| {
+            let delta = target - edge;
+            if delta.abs() <= max_distance
+                && best.map_or(true, |(best, _)| delta.abs() < best.abs())
+            {
+                best = Some((delta, guide));
+            }
+        };
+
+        if 0.0 < snap.screen_edges {
+            let (from, to) = (screen_rect.min[across], screen_rect.max[across]);
+            for (target, edge) in [
+                (screen_rect.min[axis], rect.min[axis]),
+                (screen_rect.max[axis], rect.max[axis]),
+            ] {
+                consider(target, edge, snap.screen_edges, line(target, from, to));
+            }
+        }
+
+        if 0.0 < snap.window_edges {
+            for other in other_windows {
+                // Only snap to windows that are close along the other axis:
+                let close = other.min[across] - snap.window_edges <= rect.max[across]
+                    && rect.min[across] <= other.max[across] + snap.window_edges;
+                if !close {
+                    continue;
+                }
+                let from = other.min[across].min(rect.min[across]);
+                let to = other.max[across].max(rect.max[across]);
+                for target in [other.min[axis], other.max[axis]] {
+                    for edge in [rect.min[axis], rect.max[axis]] {
+                        consider(target, edge, snap.window_edges, line(target, from, to));
+                    }
+                }
+            }
+        }
+
+        if let Some((delta, guide)) = best {
+            offset[axis] = delta;
+            guides.push(guide);
+        } else if 0.0 < snap.grid {
+            let origin = screen_rect.min[axis];
+            let snapped = origin + ((rect.min[axis] - origin) / snap.grid).round() * snap.grid;
+            offset[axis] = snapped - rect.min[axis];
+        }
+    }
+
+    (offset, guides)
+}
+
 fn automatic_area_position(ctx: &Context, layer_id: LayerId) -> Pos2 {
     let mut existing: Vec<Rect> = ctx.memory(|mem| {
         mem.areas()
@@ -688,3 +999,33 @@ fn automatic_area_position(ctx: &Context, layer_id: LayerId) -> Pos2 {
     }
     best_pos
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{pos2, style::WindowSnap, vec2};
+
+    #[test]
+    fn snap_to_edges_and_grid() {
+        let screen = Rect::from_min_size(Pos2::ZERO, vec2(1000.0, 800.0));
+        let other = Rect::from_min_size(pos2(100.0, 100.0), vec2(200.0, 200.0));
+        let snap = WindowSnap {
+            screen_edges: 10.0,
+            window_edges: 10.0,
+            grid: 50.0,
+            show_guides: true,
+        };
+
+        // Close to the right side of the other window, and to the top of the screen:
+        let rect = Rect::from_min_size(pos2(306.0, 4.0), vec2(100.0, 100.0));
+        let (offset, guides) = snap_offset(rect, screen, &[other], &snap);
+        assert_eq!(offset, vec2(-6.0, -4.0));
+        assert_eq!(guides.len(), 2);
+
+        // Far from any edge, so it snaps to the grid:
+        let rect = Rect::from_min_size(pos2(520.0, 480.0), vec2(100.0, 100.0));
+        let (offset, guides) = snap_offset(rect, screen, &[other], &snap);
+        assert_eq!(offset, vec2(-20.0, 20.0));
+        assert!(guides.is_empty());
+    }
+}
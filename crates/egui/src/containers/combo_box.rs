@@ -2,8 +2,8 @@ use epaint::Shape;
 
 use crate::{
     epaint, style::WidgetVisuals, vec2, Align2, Context, Id, InnerResponse, NumExt, Painter,
-    PopupCloseBehavior, Rect, Response, ScrollArea, Sense, Stroke, TextStyle, TextWrapMode, Ui,
-    UiBuilder, Vec2, WidgetInfo, WidgetText, WidgetType,
+    PopupCloseBehavior, PopupSizing, Rect, Response, ScrollArea, Sense, Stroke, TextStyle,
+    TextWrapMode, Ui, UiBuilder, Vec2, WidgetInfo, WidgetText, WidgetType,
 };
 
 #[allow(unused_imports)] // Documentation
@@ -46,6 +46,7 @@ pub struct ComboBox {
     icon: Option<IconPainter>,
     wrap_mode: Option<TextWrapMode>,
     close_behavior: Option<PopupCloseBehavior>,
+    popup_sizing: PopupSizing,
 }
 
 impl ComboBox {
@@ -60,6 +61,7 @@ impl ComboBox {
             icon: None,
             wrap_mode: None,
             close_behavior: None,
+            popup_sizing: PopupSizing::default(),
         }
     }
 
@@ -75,6 +77,7 @@ impl ComboBox {
             icon: None,
             wrap_mode: None,
             close_behavior: None,
+            popup_sizing: PopupSizing::default(),
         }
     }
 
@@ -89,6 +92,7 @@ impl ComboBox {
             icon: None,
             wrap_mode: None,
             close_behavior: None,
+            popup_sizing: PopupSizing::default(),
         }
     }
 
@@ -196,6 +200,35 @@ impl ComboBox {
         self
     }
 
+    /// Let the menu grow as wide as its items need, up to this width (in points).
+    ///
+    /// Items that are still too wide are elided, showing their full text on hover,
+    /// unless a different [`Self::popup_wrap_mode`] is set.
+    ///
+    /// By default there is no limit, and the menu is as wide as its widest item.
+    #[inline]
+    pub fn popup_max_width(mut self, max_width: f32) -> Self {
+        self.popup_sizing.max_width = Some(max_width);
+        self
+    }
+
+    /// Controls the wrap mode used for the items in the menu.
+    ///
+    /// By default this is [`TextWrapMode::Extend`], or [`TextWrapMode::Truncate`]
+    /// if [`Self::popup_max_width`] is set.
+    #[inline]
+    pub fn popup_wrap_mode(mut self, wrap_mode: TextWrapMode) -> Self {
+        self.popup_sizing.wrap_mode = Some(wrap_mode);
+        self
+    }
+
+    /// Set both [`Self::popup_max_width`] and [`Self::popup_wrap_mode`].
+    #[inline]
+    pub fn popup_sizing(mut self, popup_sizing: PopupSizing) -> Self {
+        self.popup_sizing = popup_sizing;
+        self
+    }
+
     /// Show the combo box, with the given ui code for the menu contents.
     ///
     /// Returns `InnerResponse { inner: None }` if the combo box is closed.
@@ -221,6 +254,7 @@ impl ComboBox {
             icon,
             wrap_mode,
             close_behavior,
+            popup_sizing,
         } = self;
 
         let button_id = ui.make_persistent_id(id_salt);
@@ -234,6 +268,7 @@ impl ComboBox {
                 icon,
                 wrap_mode,
                 close_behavior,
+                popup_sizing,
                 (width, height),
             );
             if let Some(label) = label {
@@ -316,6 +351,7 @@ fn combo_box_dyn<'c, R>(
     icon: Option<IconPainter>,
     wrap_mode: Option<TextWrapMode>,
     close_behavior: Option<PopupCloseBehavior>,
+    popup_sizing: PopupSizing,
     (width, height): (Option<f32>, Option<f32>),
 ) -> InnerResponse<Option<R>> {
     let popup_id = ComboBox::widget_to_popup_id(button_id);
@@ -415,17 +451,21 @@ fn combo_box_dyn<'c, R>(
         above_or_below,
         close_behavior,
         |ui| {
+            // Often the button is very narrow, which means this popup
+            // is also very narrow. Having wrapping on would therefore
+            // result in labels that wrap very early.
+            // Instead, we turn it off by default so that the labels
+            // expand the width of the menu, up to its max width (if any).
+            ui.style_mut().wrap_mode = Some(if popup_sizing.max_width.is_some() {
+                TextWrapMode::Truncate
+            } else {
+                TextWrapMode::Extend
+            });
+            popup_sizing.apply(ui);
+
             ScrollArea::vertical()
                 .max_height(height)
-                .show(ui, |ui| {
-                    // Often the button is very narrow, which means this popup
-                    // is also very narrow. Having wrapping on would therefore
-                    // result in labels that wrap very early.
-                    // Instead, we turn it off by default so that the labels
-                    // expand the width of the menu.
-                    ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
-                    menu_contents(ui)
-                })
+                .show(ui, menu_contents)
                 .inner
         },
     );
@@ -6,11 +6,17 @@ pub(crate) mod area;
 pub mod collapsing_header;
 mod combo_box;
 pub mod frame;
+pub mod overflow;
 pub mod panel;
 pub mod popup;
+pub mod reorderable_list;
 pub(crate) mod resize;
+pub mod resize_border;
+pub mod scene;
 pub mod scroll_area;
 mod sides;
+pub mod status_bar;
+pub mod toolbar;
 pub(crate) mod window;
 
 pub use {
@@ -18,10 +24,16 @@ pub use {
     collapsing_header::{CollapsingHeader, CollapsingResponse},
     combo_box::*,
     frame::Frame,
+    overflow::{OverflowBehavior, OverflowStrip},
     panel::{CentralPanel, SidePanel, TopBottomPanel},
     popup::*,
+    reorderable_list::{ItemMove, ReorderableList, ReorderableListResponse},
     resize::Resize,
+    resize_border::{ResizeBorder, ResizeGrip},
+    scene::{Scene, SceneResponse},
     scroll_area::ScrollArea,
     sides::Sides,
+    status_bar::{StatusBar, StatusBarItems, StatusBarResponse, StatusItem},
+    toolbar::{Toolbar, ToolbarAction, ToolbarButtonStyle, ToolbarItem, ToolbarResponse},
     window::Window,
 };
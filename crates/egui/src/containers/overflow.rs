@@ -0,0 +1,362 @@
+//! Show a row (or column) of items that may not fit, e.g. a toolbar or a tab strip.
+
+use emath::Align;
+use epaint::{Color32, Mesh};
+
+use crate::{
+    scroll_area::ScrollBarVisibility, Id, Layout, Rect, ScrollArea, Ui, UiBuilder, Vec2, WidgetText,
+};
+
+/// What an [`OverflowStrip`] does with items that don't fit.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum OverflowBehavior {
+    /// Clip the items, and fade out the clipped edge so it is clear that there is more.
+    Fade,
+
+    /// Show the items that don't fit in a popup, opened with a chevron button at the end of the strip.
+    #[default]
+    Menu,
+
+    /// Make the strip scrollable (without visible scroll bars),
+    /// and fade out the edges that have more content beyond them.
+    Scroll,
+}
+
+/// What [`OverflowStrip`] remembers between frames.
+#[derive(Clone, Debug, Default)]
+struct State {
+    /// The size of each item along the strip, as last shown.
+    item_sizes: Vec<Option<f32>>,
+
+    /// The size of the overflow button along the strip, as last shown.
+    button_size: Option<f32>,
+}
+
+/// A horizontal or vertical strip of items that handles not having enough space,
+/// instead of silently clipping them.
+///
+/// This is useful for toolbars and tab strips.
+///
+/// Each item is added by the same closure, given the index of the item.
+/// It may be called for each item in the strip, or in the overflow popup (see [`OverflowBehavior::Menu`]),
+/// so it should not assume a particular layout.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// let tools = ["Open", "Save", "Cut", "Copy", "Paste", "Undo", "Redo"];
+/// egui::containers::OverflowStrip::horizontal("toolbar").show(ui, tools.len(), |ui, i| {
+///     if ui.button(tools[i]).clicked() {
+///         ui.close_menu();
+///         // …
+///     }
+/// });
+/// # });
+/// ```
+#[must_use = "You should call .show()"]
+#[derive(Clone)]
+pub struct OverflowStrip {
+    id_salt: Id,
+    vertical: bool,
+    behavior: OverflowBehavior,
+    overflow_icon: Option<WidgetText>,
+    fade_color: Option<Color32>,
+    fade_size: f32,
+}
+
+impl OverflowStrip {
+    /// A strip of items laid out left-to-right.
+    pub fn horizontal(id_salt: impl std::hash::Hash) -> Self {
+        Self::new(id_salt, false)
+    }
+
+    /// A strip of items laid out top-down.
+    pub fn vertical(id_salt: impl std::hash::Hash) -> Self {
+        Self::new(id_salt, true)
+    }
+
+    fn new(id_salt: impl std::hash::Hash, vertical: bool) -> Self {
+        Self {
+            id_salt: Id::new(id_salt),
+            vertical,
+            behavior: OverflowBehavior::default(),
+            overflow_icon: None,
+            fade_color: None,
+            fade_size: 16.0,
+        }
+    }
+
+    /// What to do with items that don't fit. Default: [`OverflowBehavior::Menu`].
+    #[inline]
+    pub fn behavior(mut self, behavior: OverflowBehavior) -> Self {
+        self.behavior = behavior;
+        self
+    }
+
+    /// The text of the button that opens the popup with the items that don't fit,
+    /// with [`OverflowBehavior::Menu`].
+    ///
+    /// Default: a chevron pointing along the strip.
+    #[inline]
+    pub fn overflow_icon(mut self, overflow_icon: impl Into<WidgetText>) -> Self {
+        self.overflow_icon = Some(overflow_icon.into());
+        self
+    }
+
+    /// The color faded to at clipped edges.
+    ///
+    /// Default: the fill of the closest surrounding [`crate::Frame`], or else the panel fill.
+    #[inline]
+    pub fn fade_color(mut self, fade_color: Color32) -> Self {
+        self.fade_color = Some(fade_color);
+        self
+    }
+
+    /// How far into the strip the fade at clipped edges reaches, in points. Default: 16.
+    #[inline]
+    pub fn fade_size(mut self, fade_size: f32) -> Self {
+        self.fade_size = fade_size;
+        self
+    }
+
+    /// Show `num_items` items, calling `add_item` with the index of each item.
+    pub fn show(self, ui: &mut Ui, num_items: usize, mut add_item: impl FnMut(&mut Ui, usize)) {
+        let Self {
+            id_salt,
+            vertical,
+            behavior,
+            overflow_icon,
+            fade_color,
+            fade_size,
+        } = self;
+
+        let id = ui.make_persistent_id(id_salt);
+        let layout = if vertical {
+            Layout::top_down(Align::Min)
+        } else {
+            Layout::left_to_right(Align::Center)
+        };
+        let fade_color = fade_color.unwrap_or_else(|| {
+            ui.stack()
+                .iter()
+                .map(|frame| frame.frame().fill)
+                .find(|fill| *fill != Color32::TRANSPARENT)
+                .unwrap_or(ui.visuals().panel_fill)
+        });
+        let max_rect = ui.available_rect_before_wrap();
+
+        let rect = match behavior {
+            OverflowBehavior::Fade => {
+                let mut strip_ui = ui.new_child(
+                    UiBuilder::new()
+                        .id_salt(id_salt)
+                        .max_rect(max_rect)
+                        .layout(layout),
+                );
+                strip_ui.set_clip_rect(max_rect.intersect(ui.clip_rect()));
+                for i in 0..num_items {
+                    add_item(&mut strip_ui, i);
+                }
+                let content_rect = strip_ui.min_rect();
+                let rect = clamp_along(content_rect, max_rect, vertical);
+                if along(content_rect.size(), vertical) > along(rect.size(), vertical) {
+                    paint_fade(ui, rect, vertical, false, fade_size, fade_color);
+                }
+                rect
+            }
+
+            OverflowBehavior::Scroll => {
+                let scroll_area = if vertical {
+                    ScrollArea::vertical()
+                } else {
+                    ScrollArea::horizontal()
+                };
+                let output = scroll_area
+                    .id_salt(id_salt)
+                    .scroll_bar_visibility(ScrollBarVisibility::AlwaysHidden)
+                    .show(ui, |ui| {
+                        ui.with_layout(layout, |ui| {
+                            for i in 0..num_items {
+                                add_item(ui, i);
+                            }
+                        });
+                    });
+                let rect = output.inner_rect;
+                let offset = along(output.state.offset, vertical);
+                let hidden_after =
+                    along(output.content_size, vertical) - along(rect.size(), vertical) - offset;
+                if 0.5 < offset {
+                    paint_fade(ui, rect, vertical, true, fade_size, fade_color);
+                }
+                if 0.5 < hidden_after {
+                    paint_fade(ui, rect, vertical, false, fade_size, fade_color);
+                }
+                rect
+            }
+
+            OverflowBehavior::Menu => {
+                let mut state = ui.data_mut(|d| d.get_temp::<State>(id)).unwrap_or_default();
+                state.item_sizes.resize(num_items, None);
+
+                let num_shown = num_items_that_fit(
+                    &state,
+                    along(max_rect.size(), vertical),
+                    along(ui.spacing().item_spacing, vertical),
+                );
+
+                let mut strip_ui = ui.new_child(
+                    UiBuilder::new()
+                        .id_salt(id_salt)
+                        .max_rect(max_rect)
+                        .layout(layout),
+                );
+                for (i, item_size) in state.item_sizes.iter_mut().enumerate().take(num_shown) {
+                    let item_rect = strip_ui.scope(|ui| add_item(ui, i)).response.rect;
+                    *item_size = Some(along(item_rect.size(), vertical));
+                }
+
+                if num_shown < num_items {
+                    let overflow_icon =
+                        overflow_icon.unwrap_or_else(|| if vertical { "⏷" } else { "⏵" }.into());
+                    let response = strip_ui
+                        .menu_button(overflow_icon, |ui| {
+                            for i in num_shown..num_items {
+                                add_item(ui, i);
+                            }
+                        })
+                        .response
+                        .on_hover_text(format!("{} more", num_items - num_shown));
+                    state.button_size = Some(along(response.rect.size(), vertical));
+                }
+
+                if state.item_sizes.iter().any(Option::is_none) && !ui.is_sizing_pass() {
+                    // Some items have not been measured yet, so we don't know what fits.
+                    ui.ctx().request_discard("OverflowStrip: new items");
+                }
+
+                ui.data_mut(|d| d.insert_temp(id, state));
+                clamp_along(strip_ui.min_rect(), max_rect, vertical)
+            }
+        };
+
+        ui.advance_cursor_after_rect(rect);
+    }
+}
+
+/// How many of the items fit, leaving room for the overflow button if not all of them do.
+///
+/// Items that have not been measured yet are assumed to fit, so that they get measured.
+fn num_items_that_fit(state: &State, available: f32, spacing: f32) -> usize {
+    let num_items = state.item_sizes.len();
+    if state.item_sizes.iter().any(Option::is_none) {
+        return num_items;
+    }
+
+    let total: f32 = state.item_sizes.iter().flatten().sum();
+    if total + spacing * num_items.saturating_sub(1) as f32 <= available {
+        return num_items;
+    }
+
+    let available = available - state.button_size.unwrap_or(0.0) - spacing;
+    let mut used = 0.0;
+    for (i, size) in state.item_sizes.iter().flatten().enumerate() {
+        used += size;
+        if available < used {
+            return i;
+        }
+        used += spacing;
+    }
+    num_items
+}
+
+/// The size along the strip.
+fn along(size: Vec2, vertical: bool) -> f32 {
+    if vertical {
+        size.y
+    } else {
+        size.x
+    }
+}
+
+/// Limit the extent of `rect` along the strip to that of `max_rect`.
+fn clamp_along(mut rect: Rect, max_rect: Rect, vertical: bool) -> Rect {
+    if vertical {
+        rect.max.y = rect.max.y.min(max_rect.max.y);
+    } else {
+        rect.max.x = rect.max.x.min(max_rect.max.x);
+    }
+    rect
+}
+
+/// Fade the start or end of `rect` (along the strip) to `color`.
+fn paint_fade(ui: &Ui, rect: Rect, vertical: bool, start: bool, size: f32, color: Color32) {
+    let size = size.min(along(rect.size(), vertical));
+    let (outer, inner) = match (vertical, start) {
+        (false, true) => (
+            rect.with_max_x(rect.min.x),
+            rect.with_max_x(rect.min.x + size),
+        ),
+        (false, false) => (
+            rect.with_min_x(rect.max.x),
+            rect.with_min_x(rect.max.x - size),
+        ),
+        (true, true) => (
+            rect.with_max_y(rect.min.y),
+            rect.with_max_y(rect.min.y + size),
+        ),
+        (true, false) => (
+            rect.with_min_y(rect.max.y),
+            rect.with_min_y(rect.max.y - size),
+        ),
+    };
+    let fade_rect = outer.union(inner);
+
+    // The two corners on the outer edge get `color`, the two on the inner edge are transparent:
+    let mut mesh = Mesh::default();
+    for corner in [
+        fade_rect.left_top(),
+        fade_rect.right_top(),
+        fade_rect.right_bottom(),
+        fade_rect.left_bottom(),
+    ] {
+        let is_outer = if vertical {
+            corner.y == outer.min.y
+        } else {
+            corner.x == outer.min.x
+        };
+        mesh.colored_vertex(
+            corner,
+            if is_outer {
+                color
+            } else {
+                Color32::TRANSPARENT
+            },
+        );
+    }
+    mesh.add_triangle(0, 1, 2);
+    mesh.add_triangle(0, 2, 3);
+    ui.painter().add(mesh);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn items_that_fit() {
+        let state = State {
+            item_sizes: vec![Some(10.0); 5],
+            button_size: Some(10.0),
+        };
+        assert_eq!(num_items_that_fit(&state, 100.0, 2.0), 5);
+        assert_eq!(num_items_that_fit(&state, 58.0, 2.0), 5);
+        assert_eq!(num_items_that_fit(&state, 57.0, 2.0), 3);
+        assert_eq!(num_items_that_fit(&state, 0.0, 2.0), 0);
+
+        let unmeasured = State {
+            item_sizes: vec![Some(10.0), None, None],
+            button_size: None,
+        };
+        assert_eq!(num_items_that_fit(&unmeasured, 10.0, 2.0), 3);
+    }
+}
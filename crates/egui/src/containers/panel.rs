@@ -17,7 +17,7 @@
 
 use crate::{
     lerp, vec2, Align, Context, CursorIcon, Frame, Id, InnerResponse, LayerId, Layout, NumExt,
-    Rangef, Rect, Sense, Stroke, Ui, UiBuilder, UiKind, UiStackInfo, Vec2,
+    Rangef, Rect, Sense, Stroke, Ui, UiBuilder, UiKind, UiStackInfo, Vec2, ViewportId,
 };
 
 fn animate_expansion(ctx: &Context, id: Id, is_expanded: bool) -> f32 {
@@ -32,8 +32,10 @@ pub struct PanelState {
 }
 
 impl PanelState {
+    /// The state of the panel in the current viewport.
     pub fn load(ctx: &Context, bar_id: Id) -> Option<Self> {
-        ctx.data_mut(|d| d.get_persisted(bar_id))
+        let id = Self::id(ctx, bar_id);
+        ctx.data_mut(|d| d.get_persisted(id))
     }
 
     /// The size of the panel (from previous frame).
@@ -42,7 +44,55 @@ impl PanelState {
     }
 
     fn store(self, ctx: &Context, bar_id: Id) {
-        ctx.data_mut(|d| d.insert_persisted(bar_id, self));
+        let id = Self::id(ctx, bar_id);
+        ctx.data_mut(|d| d.insert_persisted(id, self));
+    }
+
+    /// Go back to the default size.
+    fn forget(ctx: &Context, bar_id: Id) {
+        let id = Self::id(ctx, bar_id);
+        ctx.data_mut(|d| d.remove::<Self>(id));
+    }
+
+    /// A panel can have a different size in each viewport.
+    fn id(ctx: &Context, bar_id: Id) -> Id {
+        let viewport_id = ctx.viewport_id();
+        if viewport_id == ViewportId::ROOT {
+            bar_id
+        } else {
+            bar_id.with(viewport_id)
+        }
+    }
+}
+
+/// Panel sizes as fractions of the window size, see [`SidePanel::width_range_fraction`].
+#[derive(Clone, Copy, Debug, Default)]
+struct SizeFractions {
+    default: Option<f32>,
+    range: Option<Rangef>,
+}
+
+impl SizeFractions {
+    /// Combine with the absolute `default` and `range`, for a window of the given size.
+    fn resolve(
+        self,
+        default: Option<f32>,
+        range: Rangef,
+        window_size: f32,
+    ) -> (Option<f32>, Rangef) {
+        let range = if let Some(fractions) = self.range {
+            let max = range.max.at_most(fractions.max * window_size);
+            let min = range.min.at_least(fractions.min * window_size).at_most(max);
+            Rangef::new(min, max)
+        } else {
+            range
+        };
+        let default = self
+            .default
+            .map(|fraction| fraction * window_size)
+            .or(default)
+            .map(|default| clamp_to_range(default, range));
+        (default, range)
     }
 }
 
@@ -105,6 +155,7 @@ pub struct SidePanel {
     show_separator_line: bool,
     default_width: f32,
     width_range: Rangef,
+    width_fractions: SizeFractions,
 }
 
 impl SidePanel {
@@ -128,11 +179,14 @@ impl SidePanel {
             show_separator_line: true,
             default_width: 200.0,
             width_range: Rangef::new(96.0, f32::INFINITY),
+            width_fractions: SizeFractions::default(),
         }
     }
 
     /// Can panel be resized by dragging the edge of it?
     ///
+    /// Double-clicking the edge resets the panel to its default width.
+    ///
     /// Default is `true`.
     ///
     /// If you want your panel to be resizable you also need a widget in it that
@@ -196,6 +250,40 @@ impl SidePanel {
     pub fn exact_width(mut self, width: f32) -> Self {
         self.default_width = width;
         self.width_range = Rangef::point(width);
+        self.width_fractions = SizeFractions::default();
+        self
+    }
+
+    /// The initial width of the panel as a fraction of the window width, e.g. `0.25`.
+    ///
+    /// With [`Self::show_inside`], this is a fraction of the width of the parent [`Ui`].
+    /// This takes precedence over [`Self::default_width`].
+    #[inline]
+    pub fn default_width_fraction(mut self, fraction: f32) -> Self {
+        self.width_fractions.default = Some(fraction);
+        self
+    }
+
+    /// The allowable width range for the panel, as fractions of the window width, e.g. `0.2..=0.6`.
+    ///
+    /// With [`Self::show_inside`], these are fractions of the width of the parent [`Ui`].
+    ///
+    /// The panel stays within both this and the absolute [`Self::width_range`]
+    /// (which by default has a minimum of 96 points; use [`Self::min_width`] to change it).
+    ///
+    /// ```
+    /// # egui::__run_test_ctx(|ctx| {
+    /// egui::SidePanel::left("outline")
+    ///     .default_width_fraction(0.25)
+    ///     .width_range_fraction(0.2..=0.6)
+    ///     .show(ctx, |ui| {
+    ///         ui.label("Outline");
+    ///     });
+    /// # });
+    /// ```
+    #[inline]
+    pub fn width_range_fraction(mut self, range: impl Into<Rangef>) -> Self {
+        self.width_fractions.range = Some(range.into());
         self
     }
 
@@ -214,13 +302,17 @@ impl SidePanel {
         ui: &mut Ui,
         add_contents: impl FnOnce(&mut Ui) -> R,
     ) -> InnerResponse<R> {
-        self.show_inside_dyn(ui, Box::new(add_contents))
+        let window_size = ui.max_rect().size();
+        self.show_inside_dyn(ui, window_size, Box::new(add_contents))
     }
 
     /// Show the panel inside a [`Ui`].
+    ///
+    /// The size fractions are relative to `window_size`.
     fn show_inside_dyn<'c, R>(
         self,
         ui: &mut Ui,
+        window_size: Vec2,
         add_contents: Box<dyn FnOnce(&mut Ui) -> R + 'c>,
     ) -> InnerResponse<R> {
         let Self {
@@ -231,8 +323,13 @@ impl SidePanel {
             show_separator_line,
             default_width,
             width_range,
+            width_fractions,
         } = self;
 
+        let (default_width, width_range) =
+            width_fractions.resolve(Some(default_width), width_range, window_size.x);
+        let default_width = default_width.unwrap_or_default();
+
         let available_rect = ui.available_rect_before_wrap();
         let mut panel_rect = available_rect;
         let mut width = default_width;
@@ -248,6 +345,7 @@ impl SidePanel {
         let resize_id = id.with("__resize");
         let mut resize_hover = false;
         let mut is_resizing = false;
+        let mut reset_size = false;
         if resizable {
             // First we read the resize interaction results, to avoid frame latency in the resize:
             if let Some(resize_response) = ui.ctx().read_response(resize_id) {
@@ -307,9 +405,10 @@ impl SidePanel {
             let resize_x = side.opposite().side_x(panel_rect);
             let resize_rect = Rect::from_x_y_ranges(resize_x..=resize_x, panel_rect.y_range())
                 .expand2(vec2(ui.style().interaction.resize_grab_radius_side, 0.0));
-            let resize_response = ui.interact(resize_rect, resize_id, Sense::drag());
+            let resize_response = ui.interact(resize_rect, resize_id, Sense::click_and_drag());
             resize_hover = resize_response.hovered();
             is_resizing = resize_response.dragged();
+            reset_size = resize_response.double_clicked();
         }
 
         if resize_hover || is_resizing {
@@ -329,7 +428,12 @@ impl SidePanel {
             ui.ctx().set_cursor_icon(cursor_icon);
         }
 
-        PanelState { rect }.store(ui.ctx(), id);
+        if reset_size {
+            PanelState::forget(ui.ctx(), id);
+            ui.ctx().request_repaint();
+        } else {
+            PanelState { rect }.store(ui.ctx(), id);
+        }
 
         {
             let stroke = if is_resizing {
@@ -383,7 +487,8 @@ impl SidePanel {
         );
         panel_ui.set_clip_rect(ctx.screen_rect());
 
-        let inner_response = self.show_inside_dyn(&mut panel_ui, add_contents);
+        let inner_response =
+            self.show_inside_dyn(&mut panel_ui, ctx.screen_rect().size(), add_contents);
         let rect = inner_response.response.rect;
 
         match side {
@@ -587,6 +692,7 @@ pub struct TopBottomPanel {
     show_separator_line: bool,
     default_height: Option<f32>,
     height_range: Rangef,
+    height_fractions: SizeFractions,
 }
 
 impl TopBottomPanel {
@@ -610,11 +716,14 @@ impl TopBottomPanel {
             show_separator_line: true,
             default_height: None,
             height_range: Rangef::new(20.0, f32::INFINITY),
+            height_fractions: SizeFractions::default(),
         }
     }
 
     /// Can panel be resized by dragging the edge of it?
     ///
+    /// Double-clicking the edge resets the panel to its default height.
+    ///
     /// Default is `false`.
     ///
     /// If you want your panel to be resizable you also need a widget in it that
@@ -681,6 +790,28 @@ impl TopBottomPanel {
     pub fn exact_height(mut self, height: f32) -> Self {
         self.default_height = Some(height);
         self.height_range = Rangef::point(height);
+        self.height_fractions = SizeFractions::default();
+        self
+    }
+
+    /// The initial height of the panel as a fraction of the window height, e.g. `0.25`.
+    ///
+    /// With [`Self::show_inside`], this is a fraction of the height of the parent [`Ui`].
+    /// This takes precedence over [`Self::default_height`].
+    #[inline]
+    pub fn default_height_fraction(mut self, fraction: f32) -> Self {
+        self.height_fractions.default = Some(fraction);
+        self
+    }
+
+    /// The allowable height range for the panel, as fractions of the window height, e.g. `0.2..=0.6`.
+    ///
+    /// With [`Self::show_inside`], these are fractions of the height of the parent [`Ui`].
+    ///
+    /// The panel stays within both this and the absolute [`Self::height_range`].
+    #[inline]
+    pub fn height_range_fraction(mut self, range: impl Into<Rangef>) -> Self {
+        self.height_fractions.range = Some(range.into());
         self
     }
 
@@ -699,13 +830,17 @@ impl TopBottomPanel {
         ui: &mut Ui,
         add_contents: impl FnOnce(&mut Ui) -> R,
     ) -> InnerResponse<R> {
-        self.show_inside_dyn(ui, Box::new(add_contents))
+        let window_size = ui.max_rect().size();
+        self.show_inside_dyn(ui, window_size, Box::new(add_contents))
     }
 
     /// Show the panel inside a [`Ui`].
+    ///
+    /// The size fractions are relative to `window_size`.
     fn show_inside_dyn<'c, R>(
         self,
         ui: &mut Ui,
+        window_size: Vec2,
         add_contents: Box<dyn FnOnce(&mut Ui) -> R + 'c>,
     ) -> InnerResponse<R> {
         let Self {
@@ -716,8 +851,12 @@ impl TopBottomPanel {
             show_separator_line,
             default_height,
             height_range,
+            height_fractions,
         } = self;
 
+        let (default_height, height_range) =
+            height_fractions.resolve(default_height, height_range, window_size.y);
+
         let frame = frame.unwrap_or_else(|| Frame::side_top_panel(ui.style()));
 
         let available_rect = ui.available_rect_before_wrap();
@@ -739,6 +878,7 @@ impl TopBottomPanel {
         let resize_id = id.with("__resize");
         let mut resize_hover = false;
         let mut is_resizing = false;
+        let mut reset_size = false;
         if resizable {
             // First we read the resize interaction results, to avoid frame latency in the resize:
             if let Some(resize_response) = ui.ctx().read_response(resize_id) {
@@ -799,9 +939,10 @@ impl TopBottomPanel {
             let resize_y = side.opposite().side_y(panel_rect);
             let resize_rect = Rect::from_x_y_ranges(panel_rect.x_range(), resize_y..=resize_y)
                 .expand2(vec2(0.0, ui.style().interaction.resize_grab_radius_side));
-            let resize_response = ui.interact(resize_rect, resize_id, Sense::drag());
+            let resize_response = ui.interact(resize_rect, resize_id, Sense::click_and_drag());
             resize_hover = resize_response.hovered();
             is_resizing = resize_response.dragged();
+            reset_size = resize_response.double_clicked();
         }
 
         if resize_hover || is_resizing {
@@ -821,7 +962,12 @@ impl TopBottomPanel {
             ui.ctx().set_cursor_icon(cursor_icon);
         }
 
-        PanelState { rect }.store(ui.ctx(), id);
+        if reset_size {
+            PanelState::forget(ui.ctx(), id);
+            ui.ctx().request_repaint();
+        } else {
+            PanelState { rect }.store(ui.ctx(), id);
+        }
 
         {
             let stroke = if is_resizing {
@@ -876,7 +1022,8 @@ impl TopBottomPanel {
         );
         panel_ui.set_clip_rect(ctx.screen_rect());
 
-        let inner_response = self.show_inside_dyn(&mut panel_ui, add_contents);
+        let inner_response =
+            self.show_inside_dyn(&mut panel_ui, ctx.screen_rect().size(), add_contents);
         let rect = inner_response.response.rect;
 
         match side {
@@ -4,8 +4,8 @@ use pass_state::PerWidgetTooltipState;
 
 use crate::{
     pass_state, vec2, AboveOrBelow, Align, Align2, Area, AreaState, Context, Frame, Id,
-    InnerResponse, Key, LayerId, Layout, Order, Pos2, Rect, Response, Sense, Ui, UiKind, Vec2,
-    Widget, WidgetText,
+    InnerResponse, Key, LayerId, Layout, NumExt, Order, Pos2, Rect, Response, Sense, Shadow,
+    TextWrapMode, Ui, UiKind, Vec2, ViewportBuilder, ViewportId, Widget, WidgetText, WindowLevel,
 };
 
 // ----------------------------------------------------------------------------
@@ -184,20 +184,52 @@ fn show_tooltip_at_dyn<'c, R>(
         .and_then(|area| area.size)
         .unwrap_or(vec2(64.0, 32.0));
 
-    let screen_rect = ctx.screen_rect();
+    let work_area = monitor_work_area(ctx, state.bounding_rect);
+    let constrain_rect = popup_constrain_rect(ctx, state.bounding_rect);
 
-    let (pivot, anchor) = find_tooltip_position(
-        screen_rect,
+    let placement = find_tooltip_position(
+        constrain_rect,
         state.bounding_rect,
         allow_placing_below,
         expected_tooltip_size,
     );
 
+    if placement.is_none() && ctx.options(|o| o.tooltips_in_viewports) && !ctx.embed_viewports() {
+        // Try extending beyond the window, like native menus do:
+        if let Some(work_area) = work_area {
+            let viewport_size_id = tooltip_area_id.with("viewport_size");
+            let expected_tooltip_size = ctx
+                .data(|d| d.get_temp::<Vec2>(viewport_size_id))
+                .unwrap_or(expected_tooltip_size);
+            if let Some((pivot, anchor)) = find_tooltip_position(
+                work_area,
+                state.bounding_rect,
+                allow_placing_below,
+                expected_tooltip_size,
+            ) {
+                let tooltip_rect = pivot.anchor_size(anchor, expected_tooltip_size);
+                let (inner, size) =
+                    show_tooltip_viewport(ctx, tooltip_area_id, tooltip_rect, add_contents);
+                ctx.data_mut(|d| d.insert_temp(viewport_size_id, size));
+
+                state.tooltip_count += 1;
+                state.bounding_rect = state.bounding_rect.union(pivot.anchor_size(anchor, size));
+                ctx.pass_state_mut(|fs| fs.tooltips.widget_tooltips.insert(widget_id, state));
+                return inner;
+            }
+        }
+    }
+
+    // It doesn't fit anywhere :(
+    // Just show it anyway:
+    let (pivot, anchor) = placement.unwrap_or((Align2::LEFT_TOP, constrain_rect.left_top()));
+
     let InnerResponse { inner, response } = Area::new(tooltip_area_id)
         .kind(UiKind::Popup)
         .order(Order::Tooltip)
         .pivot(pivot)
         .fixed_pos(anchor)
+        .constrain_to(constrain_rect)
         .default_width(ctx.style().spacing.tooltip_width)
         .sense(Sense::hover()) // don't click to bring to front
         .show(ctx, |ui| {
@@ -218,6 +250,93 @@ fn show_tooltip_at_dyn<'c, R>(
     inner
 }
 
+/// Show a tooltip in a viewport of its own, at the given rectangle of the screen.
+///
+/// Returns what the contents returned, and the size they ended up taking.
+fn show_tooltip_viewport<'c, R>(
+    ctx: &Context,
+    tooltip_area_id: Id,
+    tooltip_rect: Rect,
+    add_contents: Box<dyn FnOnce(&mut Ui) -> R + 'c>,
+) -> (R, Vec2) {
+    let window_pos = ctx
+        .input(|i| i.viewport().inner_rect)
+        .map_or(Pos2::ZERO, |inner_rect| inner_rect.min);
+
+    let builder = ViewportBuilder::default()
+        .with_title("Tooltip")
+        .with_position(window_pos + tooltip_rect.min.to_vec2())
+        .with_inner_size(tooltip_rect.size())
+        .with_decorations(false)
+        .with_resizable(false)
+        .with_taskbar(false)
+        .with_active(false)
+        .with_window_level(WindowLevel::AlwaysOnTop)
+        .with_mouse_passthrough(true);
+
+    let mut add_contents = Some(add_contents);
+    ctx.show_viewport_immediate(
+        ViewportId::from_hash_of(tooltip_area_id),
+        builder,
+        |ctx, _class| {
+            let add_contents = add_contents
+                .take()
+                .expect("immediate viewports are only shown once per pass");
+
+            // The window is the frame, so no shadow or rounding outside of it:
+            let frame = Frame::popup(&ctx.style())
+                .shadow(Shadow::NONE)
+                .rounding(0.0);
+
+            let InnerResponse { inner, response } = Area::new(tooltip_area_id)
+                .kind(UiKind::Popup)
+                .order(Order::Tooltip)
+                .fixed_pos(Pos2::ZERO)
+                .constrain(false)
+                .default_width(ctx.style().spacing.tooltip_width)
+                .sense(Sense::hover())
+                .show(ctx, |ui| {
+                    ui.style_mut().interaction.selectable_labels = false;
+                    frame.show_dyn(ui, add_contents).inner
+                });
+
+            (inner, response.rect.size())
+        },
+    )
+}
+
+/// The work area of the monitor that `anchor_rect` is on, in the coordinates of the current viewport.
+///
+/// `None` if the integration doesn't report the monitors.
+fn monitor_work_area(ctx: &Context, anchor_rect: Rect) -> Option<Rect> {
+    ctx.input(|i| {
+        let viewport = i.viewport();
+        let window_offset = viewport.inner_rect?.min.to_vec2();
+        let anchor = anchor_rect.center() + window_offset;
+        viewport
+            .monitor_work_areas
+            .iter()
+            .min_by(|a, b| {
+                a.distance_sq_to_pos(anchor)
+                    .total_cmp(&b.distance_sq_to_pos(anchor))
+            })
+            .map(|work_area| work_area.translate(-window_offset))
+    })
+}
+
+/// The part of the screen that popups and tooltips next to `anchor_rect` are kept within.
+///
+/// This is [`Context::screen_rect`], limited to the work area of the monitor the anchor is on,
+/// so that a popup doesn't straddle two monitors when the window spans both,
+/// nor end up under a taskbar.
+pub fn popup_constrain_rect(ctx: &Context, anchor_rect: Rect) -> Rect {
+    let screen_rect = ctx.screen_rect();
+    match monitor_work_area(ctx, anchor_rect) {
+        Some(work_area) if work_area.intersects(screen_rect) => work_area.intersect(screen_rect),
+        _ => screen_rect,
+    }
+}
+
 /// What is the id of the next tooltip for this widget?
 pub fn next_tooltip_id(ctx: &Context, widget_id: Id) -> Id {
     let tooltip_count = ctx.pass_state(|fs| {
@@ -233,7 +352,8 @@ pub fn tooltip_id(widget_id: Id, tooltip_count: usize) -> Id {
     widget_id.with(tooltip_count)
 }
 
-/// Returns `(PIVOT, POS)` to mean: put the `PIVOT` corner of the tooltip at `POS`.
+/// Returns `(PIVOT, POS)` to mean: put the `PIVOT` corner of the tooltip at `POS`,
+/// or `None` if the tooltip doesn't fit on any side of the widget.
 ///
 /// Note: the position might need to be constrained to the screen,
 /// (e.g. moved sideways if shown under the widget)
@@ -243,47 +363,44 @@ fn find_tooltip_position(
     widget_rect: Rect,
     allow_placing_below: bool,
     tooltip_size: Vec2,
-) -> (Align2, Pos2) {
+) -> Option<(Align2, Pos2)> {
     let spacing = 4.0;
 
     // Does it fit below?
     if allow_placing_below
         && widget_rect.bottom() + spacing + tooltip_size.y <= screen_rect.bottom()
     {
-        return (
+        return Some((
             Align2::LEFT_TOP,
             widget_rect.left_bottom() + spacing * Vec2::DOWN,
-        );
+        ));
     }
 
     // Does it fit above?
     if screen_rect.top() + tooltip_size.y + spacing <= widget_rect.top() {
-        return (
+        return Some((
             Align2::LEFT_BOTTOM,
             widget_rect.left_top() + spacing * Vec2::UP,
-        );
+        ));
     }
 
     // Does it fit to the right?
     if widget_rect.right() + spacing + tooltip_size.x <= screen_rect.right() {
-        return (
+        return Some((
             Align2::LEFT_TOP,
             widget_rect.right_top() + spacing * Vec2::RIGHT,
-        );
+        ));
     }
 
     // Does it fit to the left?
     if screen_rect.left() + tooltip_size.x + spacing <= widget_rect.left() {
-        return (
+        return Some((
             Align2::RIGHT_TOP,
             widget_rect.left_top() + spacing * Vec2::LEFT,
-        );
+        ));
     }
 
-    // It doesn't fit anywhere :(
-
-    // Just show it anyway:
-    (Align2::LEFT_TOP, screen_rect.left_top())
+    None
 }
 
 /// Show some text at the current pointer position (if any).
@@ -338,6 +455,45 @@ pub enum PopupCloseBehavior {
     IgnoreClicks,
 }
 
+/// How wide the contents of a popup may get, and what happens to text that doesn't fit.
+///
+/// The popup starts out as wide as its contents need, up to [`Self::max_width`].
+/// Past that, item labels are wrapped or elided according to [`Self::wrap_mode`].
+/// Elided labels show their full text on hover.
+///
+/// Used by [`crate::ComboBox::popup_max_width`] and [`crate::menu::menu_button_with_sizing`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PopupSizing {
+    /// The maximum width of the contents of the popup, in points.
+    ///
+    /// The popup is never made narrower than its minimum width, e.g. that of the combo box button.
+    ///
+    /// `None` means no limit.
+    pub max_width: Option<f32>,
+
+    /// Overrides [`crate::Style::wrap_mode`] for the contents of the popup.
+    ///
+    /// Use [`TextWrapMode::Truncate`] to elide long labels, or [`TextWrapMode::Wrap`] to wrap them.
+    pub wrap_mode: Option<TextWrapMode>,
+}
+
+impl PopupSizing {
+    /// Apply to the [`Ui`] of the popup, before adding any contents.
+    pub(crate) fn apply(&self, ui: &mut Ui) {
+        if let Some(wrap_mode) = self.wrap_mode {
+            ui.style_mut().wrap_mode = Some(wrap_mode);
+        }
+        if let Some(max_width) = self.max_width {
+            if ui.is_sizing_pass() {
+                // Let the contents grow up to the max, so the popup ends up as wide as they need:
+                ui.set_max_width(max_width);
+            } else {
+                ui.set_max_width(ui.available_width().at_most(max_width));
+            }
+        }
+    }
+}
+
 /// Helper for [`popup_above_or_below_widget`].
 pub fn popup_below_widget<R>(
     ui: &Ui,
@@ -421,6 +577,10 @@ pub fn popup_above_or_below_widget<R>(
         .kind(UiKind::Popup)
         .order(Order::Foreground)
         .fixed_pos(pos)
+        .constrain_to(popup_constrain_rect(
+            parent_ui.ctx(),
+            Rect::from_min_size(pos, Vec2::ZERO),
+        ))
         .default_width(inner_width)
         .pivot(pivot)
         .show(parent_ui.ctx(), |ui| {
@@ -0,0 +1,412 @@
+//! A list of items that the user can reorder by dragging them.
+
+use epaint::{RectShape, Shape};
+
+use crate::{
+    pos2, style::ScrollAnimation, vec2, Align, CursorIcon, Id, InnerResponse, LayerId, Layout,
+    NumExt as _, Order, Rect, Response, Sense, Ui, UiBuilder,
+};
+
+/// An item was dragged to a new place in a [`ReorderableList`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ItemMove {
+    /// The index the item was at.
+    pub from: usize,
+
+    /// The index the item should be at after the move.
+    ///
+    /// That is: remove the item at [`Self::from`], then insert it at this index.
+    pub to: usize,
+}
+
+impl ItemMove {
+    /// Move the item in the given slice.
+    pub fn apply<T>(self, items: &mut [T]) {
+        let Self { from, to } = self;
+        if from < to {
+            items[from..=to].rotate_left(1);
+        } else {
+            items[to..=from].rotate_right(1);
+        }
+    }
+}
+
+/// What [`ReorderableList::show`] returns.
+pub struct ReorderableListResponse<R> {
+    /// What each item returned, in the order of the items.
+    pub inner: Vec<R>,
+
+    /// The response of the whole list.
+    pub response: Response,
+
+    /// The item the user dropped at a new place this frame, if any.
+    ///
+    /// Apply it with [`ItemMove::apply`], or the item will jump back to where it was.
+    pub moved: Option<ItemMove>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Drag {
+    /// The index of the dragged item.
+    index: usize,
+
+    /// How far below the top of the item the pointer grabbed it.
+    grab_offset: f32,
+
+    /// Where the item would be dropped.
+    target: usize,
+
+    /// Has the item been lifted out of the list yet?
+    lifted: bool,
+}
+
+/// What [`ReorderableList`] remembers between frames.
+#[derive(Clone, Debug, Default)]
+struct State {
+    /// The height of each item, as last shown.
+    item_heights: Vec<f32>,
+
+    drag: Option<Drag>,
+}
+
+/// A vertical list of items that the user can reorder by dragging them.
+///
+/// While an item is dragged it follows the pointer,
+/// and a gap opens where it would be dropped, with the other items sliding out of the way.
+/// When dragged near the top or bottom of an enclosing [`crate::ScrollArea`], it scrolls.
+/// Pressing escape cancels the drag.
+///
+/// The list doesn't own the items, it tells you how to move them:
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// # let mut items = vec!["Apple", "Banana", "Cherry"];
+/// let response = egui::containers::ReorderableList::new("fruits").show(ui, items.len(), |ui, i| {
+///     ui.label(items[i]);
+/// });
+/// if let Some(moved) = response.moved {
+///     moved.apply(&mut items);
+/// }
+/// # });
+/// ```
+#[must_use = "You should call .show()"]
+#[derive(Clone, Copy, Debug)]
+pub struct ReorderableList {
+    id_salt: Id,
+    drag_handle: bool,
+    auto_scroll_margin: f32,
+    auto_scroll_speed: f32,
+}
+
+impl ReorderableList {
+    /// The `id_salt` must be unique among the lists in the same [`Ui`].
+    pub fn new(id_salt: impl std::hash::Hash) -> Self {
+        Self {
+            id_salt: Id::new(id_salt),
+            drag_handle: true,
+            auto_scroll_margin: 32.0,
+            auto_scroll_speed: 800.0,
+        }
+    }
+
+    /// If `true` (default), each item gets a drag handle to the left of it, which is what the user drags.
+    ///
+    /// If `false`, the user can drag the items anywhere that isn't taken by another widget sensing drags.
+    #[inline]
+    pub fn drag_handle(mut self, drag_handle: bool) -> Self {
+        self.drag_handle = drag_handle;
+        self
+    }
+
+    /// How close to the edge of the visible area the pointer needs to be
+    /// when dragging an item for the enclosing [`crate::ScrollArea`] to scroll. Default: 32.
+    #[inline]
+    pub fn auto_scroll_margin(mut self, auto_scroll_margin: f32) -> Self {
+        self.auto_scroll_margin = auto_scroll_margin;
+        self
+    }
+
+    /// The fastest the enclosing [`crate::ScrollArea`] scrolls when dragging an item at its edge,
+    /// in points per second. Default: 800.
+    #[inline]
+    pub fn auto_scroll_speed(mut self, auto_scroll_speed: f32) -> Self {
+        self.auto_scroll_speed = auto_scroll_speed;
+        self
+    }
+
+    /// Show `num_items` items, each added by `add_item` given the index of the item.
+    pub fn show<R>(
+        self,
+        ui: &mut Ui,
+        num_items: usize,
+        mut add_item: impl FnMut(&mut Ui, usize) -> R,
+    ) -> ReorderableListResponse<R> {
+        let id = ui.make_persistent_id(self.id_salt);
+        let ctx = ui.ctx().clone();
+        let mut state = ctx
+            .data_mut(|d| d.get_temp::<State>(id))
+            .unwrap_or_default();
+        state.item_heights.resize(num_items, 0.0);
+
+        let item_id = |index: usize| id.with(index);
+        let spacing = ui.spacing().item_spacing.y;
+        let top_left = ui.cursor().min;
+        let pointer_pos = ctx.pointer_interact_pos();
+
+        // Is the drag still going on?
+        let mut moved = None;
+        let mut dragged = None;
+        if let Some(mut drag) = state.drag {
+            if num_items <= drag.index {
+                state.drag = None;
+            } else if ctx.is_being_dragged(item_id(drag.index)) {
+                if let Some(pointer_pos) = pointer_pos {
+                    drag.target = drop_target(
+                        &state.item_heights,
+                        drag.index,
+                        pointer_pos.y - drag.grab_offset - top_left.y,
+                        spacing,
+                    );
+                }
+                dragged = Some(drag);
+            } else {
+                state.drag = None;
+                // It could also have been cancelled, e.g. with escape:
+                let dropped = ctx.input(|i| i.pointer.any_released());
+                if dropped && drag.target != drag.index {
+                    moved = Some(ItemMove {
+                        from: drag.index,
+                        to: drag.target,
+                    });
+                }
+            }
+        }
+
+        if let (Some(_), Some(pointer_pos)) = (dragged, pointer_pos) {
+            ctx.set_cursor_icon(CursorIcon::Grabbing);
+            self.auto_scroll(ui, pointer_pos.y);
+        }
+
+        let mut inner = Vec::with_capacity(num_items);
+        let mut slot = 0;
+        for index in 0..num_items {
+            if let Some(drag) = dragged {
+                if index == drag.index {
+                    // Lifted out of the list, following the pointer:
+                    let pointer_y = pointer_pos.map_or(top_left.y, |pos| pos.y);
+                    let min = pos2(top_left.x, pointer_y - drag.grab_offset);
+                    let layer_id = LayerId::new(Order::Tooltip, id.with("dragged"));
+                    let mut item_ui = ui.new_child(
+                        UiBuilder::new()
+                            .layer_id(layer_id)
+                            .max_rect(Rect::from_min_size(min, ui.available_size_before_wrap())),
+                    );
+                    let background = item_ui.painter().add(Shape::Noop);
+                    let (item, response) =
+                        self.item_ui(&mut item_ui, item_id(index), true, |ui| add_item(ui, index));
+                    let visuals = ui.visuals();
+                    item_ui.painter().set(
+                        background,
+                        RectShape::new(
+                            response.rect.expand(2.0),
+                            visuals.widgets.active.rounding,
+                            visuals.window_fill,
+                            visuals.widgets.active.bg_stroke,
+                        ),
+                    );
+                    inner.push(item);
+                    continue;
+                }
+
+                gap(ui, id, slot, drag, spacing, &state.item_heights);
+                slot += 1;
+            }
+
+            let (item, response) =
+                self.item_ui(ui, item_id(index), false, |ui| add_item(ui, index));
+            state.item_heights[index] = response.rect.height();
+            if response.drag_started() {
+                if let Some(pointer_pos) = pointer_pos {
+                    state.drag = Some(Drag {
+                        index,
+                        grab_offset: pointer_pos.y - response.rect.top(),
+                        target: index,
+                        lifted: false,
+                    });
+                }
+            }
+            inner.push(item);
+        }
+        if let Some(drag) = dragged {
+            gap(ui, id, slot, drag, spacing, &state.item_heights);
+            state.drag = Some(Drag {
+                lifted: true,
+                ..drag
+            });
+        }
+
+        ctx.data_mut(|d| d.insert_temp(id, state));
+
+        let rect = Rect::from_min_max(top_left, ui.min_rect().max);
+        let response = ui.interact(rect, id, Sense::hover());
+
+        ReorderableListResponse {
+            inner,
+            response,
+            moved,
+        }
+    }
+
+    /// Show an item, with its drag handle if any.
+    ///
+    /// Returns what the item returned, and the response for dragging it.
+    fn item_ui<R>(
+        &self,
+        ui: &mut Ui,
+        item_id: Id,
+        is_dragged: bool,
+        add_item: impl FnOnce(&mut Ui) -> R,
+    ) -> (R, Response) {
+        let handle_width = if self.drag_handle {
+            ui.spacing().icon_width
+        } else {
+            0.0
+        };
+
+        let InnerResponse {
+            inner: (item, handle_x_range),
+            response,
+        } = ui.with_layout(Layout::left_to_right(Align::Min), |ui| {
+            let (handle_rect, _) = ui.allocate_exact_size(
+                vec2(handle_width, ui.spacing().interact_size.y),
+                Sense::hover(),
+            );
+            let item = ui.with_layout(Layout::top_down(Align::Min), add_item).inner;
+            (item, handle_rect.x_range())
+        });
+
+        let drag_rect = if self.drag_handle {
+            Rect::from_x_y_ranges(handle_x_range, response.rect.y_range())
+        } else {
+            response.rect
+        };
+        let drag_response = if is_dragged {
+            // The drag goes on without the item being interacted with.
+            response.clone()
+        } else {
+            ui.interact(drag_rect, item_id, Sense::drag())
+                .on_hover_cursor(CursorIcon::Grab)
+        };
+
+        if self.drag_handle && ui.is_rect_visible(drag_rect) {
+            paint_grip(ui, drag_rect, &drag_response, is_dragged);
+        }
+
+        (item, drag_response.with_new_rect(response.rect))
+    }
+
+    /// Scroll the enclosing [`crate::ScrollArea`] when the pointer is dragging near its edge.
+    fn auto_scroll(&self, ui: &Ui, pointer_y: f32) {
+        let clip_rect = ui.clip_rect();
+        let margin = self.auto_scroll_margin.at_most(clip_rect.height() / 2.0);
+        if margin <= 0.0 {
+            return;
+        }
+
+        let into_top = (clip_rect.top() + margin - pointer_y) / margin;
+        let into_bottom = (pointer_y - (clip_rect.bottom() - margin)) / margin;
+        let speed = if 0.0 < into_top {
+            into_top.at_most(1.0) * self.auto_scroll_speed
+        } else if 0.0 < into_bottom {
+            -into_bottom.at_most(1.0) * self.auto_scroll_speed
+        } else {
+            return;
+        };
+
+        let dt = ui.input(|i| i.stable_dt).at_most(0.1);
+        ui.scroll_with_delta_animation(vec2(0.0, speed * dt), ScrollAnimation::none());
+        ui.ctx().request_repaint();
+    }
+}
+
+/// Open (or close) the gap before the `slot`:th of the items that aren't dragged.
+fn gap(ui: &mut Ui, id: Id, slot: usize, drag: Drag, spacing: f32, heights: &[f32]) {
+    let target_gap = if slot == drag.target {
+        heights[drag.index] + spacing
+    } else {
+        0.0
+    };
+    // The gap starts out where the item was lifted from, so that nothing moves then:
+    let animation_time = if drag.lifted {
+        ui.style().animation_time
+    } else {
+        0.0
+    };
+    let gap = ui
+        .ctx()
+        .animate_value_with_time(id.with(("gap", slot)), target_gap, animation_time);
+    if 0.0 < gap {
+        ui.add_space(gap);
+    }
+}
+
+/// Where an item with its top at `item_top` (relative to the top of the list) would be dropped.
+fn drop_target(heights: &[f32], dragged: usize, item_top: f32, spacing: f32) -> usize {
+    let center = item_top + heights[dragged] / 2.0;
+    let mut y = 0.0;
+    let mut target = 0;
+    for (index, &height) in heights.iter().enumerate() {
+        if index == dragged {
+            continue;
+        }
+        if center < y + height / 2.0 {
+            break;
+        }
+        y += height + spacing;
+        target += 1;
+    }
+    target
+}
+
+/// Paint two columns of dots.
+fn paint_grip(ui: &Ui, rect: Rect, response: &Response, is_dragged: bool) {
+    let visuals = if is_dragged {
+        &ui.visuals().widgets.active
+    } else {
+        ui.style().interact(response)
+    };
+    let center = pos2(
+        rect.center().x,
+        rect.top() + ui.spacing().interact_size.y / 2.0,
+    );
+    let gap = 4.0;
+    for col in [-0.5, 0.5] {
+        for row in [-1.0, 0.0, 1.0] {
+            ui.painter().circle_filled(
+                center + vec2(col * gap, row * gap),
+                1.0,
+                visuals.fg_stroke.color,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn item_move() {
+        let mut items = ['a', 'b', 'c', 'd'];
+        ItemMove { from: 0, to: 2 }.apply(&mut items);
+        assert_eq!(items, ['b', 'c', 'a', 'd']);
+        ItemMove { from: 3, to: 0 }.apply(&mut items);
+        assert_eq!(items, ['d', 'b', 'c', 'a']);
+
+        let heights = [10.0; 4];
+        assert_eq!(drop_target(&heights, 1, 8.0, 0.0), 1);
+        assert_eq!(drop_target(&heights, 1, 14.0, 0.0), 2);
+        assert_eq!(drop_target(&heights, 1, -20.0, 0.0), 0);
+        assert_eq!(drop_target(&heights, 1, 100.0, 0.0), 3);
+    }
+}
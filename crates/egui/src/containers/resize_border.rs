@@ -0,0 +1,401 @@
+//! Resizing an undecorated viewport by its edges and corners, see [`ResizeBorder`].
+
+use crate::{
+    viewport::ResizeDirection, Align2, Context, Id, LayerId, Order, Painter, Pos2, Rect, Sense,
+    Vec2, ViewportCommand, ViewportCommandOutcome, ViewportInfo, WidgetRect,
+};
+
+use super::AreaState;
+
+/// A corner of the viewport, as given to [`ResizeBorder::grip_painter`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ResizeGrip {
+    /// One of the corner directions, e.g. [`ResizeDirection::SouthEast`].
+    pub direction: ResizeDirection,
+
+    /// The corner square, [`ResizeBorder::corner_size`] wide.
+    pub rect: Rect,
+
+    /// Is the pointer over this grip?
+    pub hovered: bool,
+
+    /// Is the user resizing the viewport with this grip?
+    pub dragged: bool,
+}
+
+type GripPainter = Box<dyn Fn(&Painter, &ResizeGrip)>;
+
+/// Lets the user resize an undecorated viewport (see [`crate::ViewportBuilder::with_decorations`])
+/// by dragging its edges and corners, like a native window.
+///
+/// Call this once per pass, before or after the rest of your ui.
+/// The border is on top of everything else, so make sure there is nothing important within
+/// [`Self::thickness`] of the edges of the viewport.
+///
+/// Dragging sends [`ViewportCommand::BeginResize`], which lets the windowing system do the resizing.
+/// If the integration reports that as unsupported, the viewport is instead resized
+/// with [`ViewportCommand::InnerSize`] and [`ViewportCommand::OuterPosition`] as the pointer moves.
+///
+/// Nothing is shown while the viewport is maximized or fullscreen.
+///
+/// ```
+/// # egui::__run_test_ctx(|ctx| {
+/// egui::containers::ResizeBorder::new()
+///     .thickness(6.0)
+///     .grip_painter(|painter, grip| {
+///         if grip.hovered || grip.dragged {
+///             painter.circle_filled(grip.rect.center(), 3.0, egui::Color32::GRAY);
+///         }
+///     })
+///     .show(ctx);
+/// # });
+/// ```
+#[must_use = "You should call .show()"]
+pub struct ResizeBorder {
+    thickness: f32,
+    corner_size: f32,
+    grip_painter: Option<GripPainter>,
+}
+
+impl Default for ResizeBorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ResizeBorder {
+    pub fn new() -> Self {
+        Self {
+            thickness: 4.0,
+            corner_size: 12.0,
+            grip_painter: None,
+        }
+    }
+
+    /// How far in from the edges of the viewport the user can grab it, in points.
+    ///
+    /// Default: `4.0`.
+    #[inline]
+    pub fn thickness(mut self, thickness: f32) -> Self {
+        self.thickness = thickness;
+        self
+    }
+
+    /// How far along the edges from each corner the user resizes in both directions, in points.
+    ///
+    /// Default: `12.0`.
+    #[inline]
+    pub fn corner_size(mut self, corner_size: f32) -> Self {
+        self.corner_size = corner_size;
+        self
+    }
+
+    /// Paint something at each of the four corners, e.g. a grip the user can see.
+    ///
+    /// This is called for each corner every pass, on a layer above everything else.
+    /// By default nothing is painted.
+    #[inline]
+    pub fn grip_painter(mut self, grip_painter: impl Fn(&Painter, &ResizeGrip) + 'static) -> Self {
+        self.grip_painter = Some(Box::new(grip_painter));
+        self
+    }
+
+    /// Returns the direction the viewport is being resized in, or would be if the user started dragging.
+    pub fn show(self, ctx: &Context) -> Option<ResizeDirection> {
+        let Self {
+            thickness,
+            corner_size,
+            grip_painter,
+        } = self;
+
+        let info = ctx.input(|i| i.viewport().clone());
+        if info.maximized == Some(true) || info.fullscreen == Some(true) {
+            return None;
+        }
+
+        let screen_rect = ctx.screen_rect();
+        let id = Id::new("egui_resize_border").with(ctx.viewport_id());
+        let layer_id = LayerId::new(Order::Foreground, id);
+
+        // Not interactable, so it doesn't count as the area under the pointer
+        // in the middle of the viewport. The grips are still hit-tested.
+        ctx.memory_mut(|mem| {
+            mem.areas_mut().set_state(
+                layer_id,
+                AreaState {
+                    pivot_pos: Some(screen_rect.left_top()),
+                    pivot: Align2::LEFT_TOP,
+                    size: Some(screen_rect.size()),
+                    interactable: false,
+                    last_became_visible_at: None,
+                },
+            );
+        });
+
+        let mut state: State = ctx.data_mut(|d| d.get_temp(id).unwrap_or_default());
+        if info.command_results.iter().any(|result| {
+            matches!(result.command, ViewportCommand::BeginResize(_))
+                && result.outcome == ViewportCommandOutcome::Unsupported
+        }) {
+            state.begin_resize_unsupported = true;
+        }
+
+        let mut active = None;
+        let mut corners = [None; 4];
+        for (i, (direction, rect)) in grip_rects(screen_rect, thickness, corner_size)
+            .into_iter()
+            .enumerate()
+        {
+            let response = ctx.create_widget(
+                WidgetRect {
+                    id: id.with(i),
+                    layer_id,
+                    rect,
+                    interact_rect: rect,
+                    sense: Sense::drag(),
+                    enabled: true,
+                },
+                true,
+            );
+
+            if response.drag_started() && !state.begin_resize_unsupported {
+                ctx.send_viewport_cmd(ViewportCommand::BeginResize(direction));
+            }
+
+            if response.dragged() && state.begin_resize_unsupported && state.manual.is_none() {
+                state.manual = ManualResize::start(ctx, &info, direction);
+            }
+
+            if response.hovered() || response.dragged() {
+                active = Some(direction);
+                ctx.set_cursor_icon(direction.cursor_icon());
+            }
+
+            if let Some(corner) = corner_index(direction) {
+                let grip = corners[corner].get_or_insert(ResizeGrip {
+                    direction,
+                    rect: Rect::from_min_size(
+                        corner_pos(screen_rect, direction, corner_size),
+                        Vec2::splat(corner_size),
+                    ),
+                    hovered: false,
+                    dragged: false,
+                });
+                grip.hovered |= response.hovered();
+                grip.dragged |= response.dragged();
+            }
+        }
+
+        if let Some(manual) = &state.manual {
+            if ctx.input(|i| i.pointer.primary_down()) {
+                manual.update(ctx, &info, 2.0 * corner_size);
+                active = Some(manual.direction);
+                ctx.set_cursor_icon(manual.direction.cursor_icon());
+            } else {
+                state.manual = None;
+            }
+        }
+
+        if let Some(grip_painter) = grip_painter {
+            let painter = ctx.layer_painter(layer_id);
+            for grip in corners.iter().flatten() {
+                grip_painter(&painter, grip);
+            }
+        }
+
+        ctx.data_mut(|d| d.insert_temp(id, state));
+
+        active
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct State {
+    /// The integration told us it can't do [`ViewportCommand::BeginResize`].
+    begin_resize_unsupported: bool,
+
+    /// We are resizing the viewport ourselves.
+    manual: Option<ManualResize>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct ManualResize {
+    direction: ResizeDirection,
+
+    /// Where the pointer started, in monitor space.
+    start_pointer: Pos2,
+
+    /// The inner rectangle of the viewport when we started, in monitor space.
+    start_rect: Rect,
+}
+
+impl ManualResize {
+    fn start(ctx: &Context, info: &ViewportInfo, direction: ResizeDirection) -> Option<Self> {
+        let start_rect = info.inner_rect?;
+        let start_pointer = start_rect.min + ctx.input(|i| i.pointer.interact_pos())?.to_vec2();
+        Some(Self {
+            direction,
+            start_pointer,
+            start_rect,
+        })
+    }
+
+    fn update(&self, ctx: &Context, info: &ViewportInfo, min_size: f32) {
+        let (Some(inner_rect), Some(pointer)) =
+            (info.inner_rect, ctx.input(|i| i.pointer.latest_pos()))
+        else {
+            return;
+        };
+
+        // The pointer is relative to the viewport, which moves when resizing from the top or left:
+        let delta = inner_rect.min + pointer.to_vec2() - self.start_pointer;
+        let rect = resized_rect(
+            self.start_rect,
+            self.direction,
+            delta,
+            Vec2::splat(min_size),
+        );
+
+        if rect.size() != inner_rect.size() {
+            ctx.send_viewport_cmd(ViewportCommand::InnerSize(rect.size()));
+        }
+        if rect.min != inner_rect.min {
+            let decoration_offset = info
+                .outer_rect
+                .map_or(Vec2::ZERO, |outer_rect| inner_rect.min - outer_rect.min);
+            ctx.send_viewport_cmd(ViewportCommand::OuterPosition(rect.min - decoration_offset));
+        }
+    }
+}
+
+/// The areas of the viewport that resize it, with each corner covering
+/// `corner_size` of both of its edges.
+fn grip_rects(screen_rect: Rect, thickness: f32, corner_size: f32) -> Vec<(ResizeDirection, Rect)> {
+    let Rect { min, max } = screen_rect;
+    let c = corner_size
+        .min(screen_rect.width() / 2.0)
+        .min(screen_rect.height() / 2.0);
+    let t = thickness.min(c);
+
+    let rect = |min_x: f32, max_x: f32, min_y: f32, max_y: f32| {
+        Rect::from_min_max(Pos2::new(min_x, min_y), Pos2::new(max_x, max_y))
+    };
+
+    use ResizeDirection::{East, North, NorthEast, NorthWest, South, SouthEast, SouthWest, West};
+    vec![
+        (North, rect(min.x + c, max.x - c, min.y, min.y + t)),
+        (South, rect(min.x + c, max.x - c, max.y - t, max.y)),
+        (West, rect(min.x, min.x + t, min.y + c, max.y - c)),
+        (East, rect(max.x - t, max.x, min.y + c, max.y - c)),
+        (NorthWest, rect(min.x, min.x + c, min.y, min.y + t)),
+        (NorthWest, rect(min.x, min.x + t, min.y + t, min.y + c)),
+        (NorthEast, rect(max.x - c, max.x, min.y, min.y + t)),
+        (NorthEast, rect(max.x - t, max.x, min.y + t, min.y + c)),
+        (SouthWest, rect(min.x, min.x + c, max.y - t, max.y)),
+        (SouthWest, rect(min.x, min.x + t, max.y - c, max.y - t)),
+        (SouthEast, rect(max.x - c, max.x, max.y - t, max.y)),
+        (SouthEast, rect(max.x - t, max.x, max.y - c, max.y - t)),
+    ]
+}
+
+fn corner_index(direction: ResizeDirection) -> Option<usize> {
+    match direction {
+        ResizeDirection::NorthWest => Some(0),
+        ResizeDirection::NorthEast => Some(1),
+        ResizeDirection::SouthWest => Some(2),
+        ResizeDirection::SouthEast => Some(3),
+        _ => None,
+    }
+}
+
+fn corner_pos(screen_rect: Rect, direction: ResizeDirection, corner_size: f32) -> Pos2 {
+    let (west, north) = moved_edges(direction);
+    Pos2::new(
+        if west {
+            screen_rect.min.x
+        } else {
+            screen_rect.max.x - corner_size
+        },
+        if north {
+            screen_rect.min.y
+        } else {
+            screen_rect.max.y - corner_size
+        },
+    )
+}
+
+/// Does resizing in this direction move the west and north edges?
+/// If not, it moves the east and south edges (or neither, for pure north/south/east/west).
+fn moved_edges(direction: ResizeDirection) -> (bool, bool) {
+    use ResizeDirection::{North, NorthEast, NorthWest, SouthWest, West};
+    (
+        matches!(direction, West | NorthWest | SouthWest),
+        matches!(direction, North | NorthWest | NorthEast),
+    )
+}
+
+/// `rect` with the edges given by `direction` moved by `delta`, keeping at least `min_size`.
+fn resized_rect(rect: Rect, direction: ResizeDirection, delta: Vec2, min_size: Vec2) -> Rect {
+    use ResizeDirection::{East, North, NorthEast, NorthWest, South, SouthEast, SouthWest, West};
+
+    let mut rect = rect;
+    match direction {
+        West | NorthWest | SouthWest => {
+            rect.min.x = (rect.min.x + delta.x).min(rect.max.x - min_size.x);
+        }
+        East | NorthEast | SouthEast => {
+            rect.max.x = (rect.max.x + delta.x).max(rect.min.x + min_size.x);
+        }
+        North | South => {}
+    }
+    match direction {
+        North | NorthWest | NorthEast => {
+            rect.min.y = (rect.min.y + delta.y).min(rect.max.y - min_size.y);
+        }
+        South | SouthWest | SouthEast => {
+            rect.max.y = (rect.max.y + delta.y).max(rect.min.y + min_size.y);
+        }
+        East | West => {}
+    }
+    rect
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resizing_moves_the_right_edges() {
+        let rect = Rect::from_min_max(Pos2::new(100.0, 100.0), Pos2::new(500.0, 400.0));
+        let min_size = Vec2::splat(50.0);
+
+        let resized = resized_rect(
+            rect,
+            ResizeDirection::NorthWest,
+            Vec2::new(-10.0, 20.0),
+            min_size,
+        );
+        assert_eq!(
+            resized,
+            Rect::from_min_max(Pos2::new(90.0, 120.0), Pos2::new(500.0, 400.0))
+        );
+
+        let resized = resized_rect(rect, ResizeDirection::East, Vec2::new(30.0, 30.0), min_size);
+        assert_eq!(
+            resized,
+            Rect::from_min_max(Pos2::new(100.0, 100.0), Pos2::new(530.0, 400.0))
+        );
+
+        // Shrinking stops at the minimum size, without moving the other edges:
+        let resized = resized_rect(
+            rect,
+            ResizeDirection::SouthWest,
+            Vec2::new(1000.0, -1000.0),
+            min_size,
+        );
+        assert_eq!(
+            resized,
+            Rect::from_min_max(Pos2::new(450.0, 100.0), Pos2::new(500.0, 150.0))
+        );
+    }
+}
@@ -0,0 +1,285 @@
+//! An infinite canvas that can be panned and zoomed, e.g. for node editors and diagrams.
+
+use emath::TSTransform;
+
+use crate::{
+    InnerResponse, LayerId, Pos2, Rangef, Rect, Response, Sense, Ui, UiBuilder, Vec2, ZoomHandler,
+};
+
+/// Creates a transformation that fits a given scene rectangle into the available screen size.
+///
+/// The resulting visual scene bounds can be larger, due to letterboxing.
+fn fit_to_rect_in_scene(
+    rect_in_global: Rect,
+    rect_in_scene: Rect,
+    zoom_range: Rangef,
+) -> TSTransform {
+    // Compute the scale factor to fit the bounding rectangle into the available screen size:
+    let scale = rect_in_global.size() / rect_in_scene.size();
+
+    // Use the smaller of the two scales to ensure the whole rectangle fits on the screen:
+    let scale = scale.min_elem();
+
+    // Clamp scale to what is allowed
+    let scale = zoom_range.clamp(scale);
+
+    // Compute the translation to center the bounding rect in the screen:
+    let center_in_global = rect_in_global.center().to_vec2();
+    let center_scene = rect_in_scene.center().to_vec2();
+
+    // Set the transformation to scale and then translate to center.
+    TSTransform::from_translation(center_in_global - scale * center_scene)
+        * TSTransform::from_scaling(scale)
+}
+
+/// What [`Scene::show`] returns.
+pub struct SceneResponse<R> {
+    /// What the contents returned.
+    pub inner: R,
+
+    /// The response of the background of the scene, which is what is dragged to pan it.
+    ///
+    /// It is marked as [`Response::changed`] when the scene was panned or zoomed.
+    pub response: Response,
+
+    /// The transform from scene coordinates to screen coordinates.
+    pub to_global: TSTransform,
+
+    /// The bounding rectangle of the contents, in scene coordinates.
+    ///
+    /// Set the `scene_rect` passed to [`Scene::show`] to this to fit the view to the contents.
+    pub content_rect: Rect,
+}
+
+impl<R> SceneResponse<R> {
+    /// Where a position in the scene is on the screen.
+    #[inline]
+    pub fn scene_to_screen(&self, pos: Pos2) -> Pos2 {
+        self.to_global * pos
+    }
+
+    /// Where a position on the screen (e.g. the pointer) is in the scene.
+    #[inline]
+    pub fn screen_to_scene(&self, pos: Pos2) -> Pos2 {
+        self.to_global.inverse() * pos
+    }
+
+    /// The zoom: how many points on the screen a point in the scene takes up.
+    #[inline]
+    pub fn zoom(&self) -> f32 {
+        self.to_global.scaling
+    }
+}
+
+/// A container that can be panned and zoomed, showing a part of an infinite canvas.
+///
+/// The contents are laid out and painted in "scene coordinates",
+/// which are transformed to the screen using a layer transform
+/// (see [`crate::Context::set_transform_layer`]),
+/// so widgets in the scene work as usual.
+///
+/// Interactions:
+/// * Drag the background to pan.
+/// * Scroll to pan.
+/// * Zoom about the pointer with ctrl+scroll (⌘+scroll on Mac) or a pinch gesture, see [`ZoomHandler`].
+///
+/// The part of the scene that is shown is stored by you, as a rectangle in scene coordinates:
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// // Keep this around between frames. An empty rectangle fits the view to the contents.
+/// let mut scene_rect = egui::Rect::ZERO;
+///
+/// let response = egui::containers::Scene::new().show(ui, &mut scene_rect, |ui| {
+///     ui.label("A node");
+/// });
+/// if response.response.double_clicked() {
+///     scene_rect = response.content_rect; // Fit to the contents
+/// }
+/// # });
+/// ```
+#[must_use = "You should call .show()"]
+#[derive(Clone, Copy, Debug)]
+pub struct Scene {
+    zoom_range: Rangef,
+    max_inner_size: Vec2,
+}
+
+impl Default for Scene {
+    fn default() -> Self {
+        Self {
+            zoom_range: Rangef::new(f32::EPSILON, 1.0),
+            max_inner_size: Vec2::splat(1000.0),
+        }
+    }
+}
+
+impl Scene {
+    #[inline]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// How far the user can zoom out and in, as the number of points on the screen
+    /// a point in the scene takes up.
+    ///
+    /// The default is `f32::EPSILON..=1.0`, i.e. no zooming in beyond the size the contents are laid out at,
+    /// which would make text blurry.
+    #[inline]
+    pub fn zoom_range(mut self, zoom_range: impl Into<Rangef>) -> Self {
+        self.zoom_range = zoom_range.into();
+        self
+    }
+
+    /// The maximum size of the [`Ui`] the contents are added to, in scene coordinates.
+    ///
+    /// This is where the contents are laid out, e.g. wrapped.
+    /// Contents placed explicitly (e.g. with [`crate::Ui::put`]) can be anywhere in the scene.
+    ///
+    /// Default: 1000 × 1000.
+    #[inline]
+    pub fn max_inner_size(mut self, max_inner_size: impl Into<Vec2>) -> Self {
+        self.max_inner_size = max_inner_size.into();
+        self
+    }
+
+    /// Show the scene, filling the available space.
+    ///
+    /// `scene_rect` is the part of the scene that is shown, in scene coordinates.
+    /// It is updated as the user pans and zooms.
+    /// If it is empty or otherwise invalid (e.g. [`Rect::ZERO`]),
+    /// it is set to fit the contents.
+    pub fn show<R>(
+        &self,
+        parent_ui: &mut Ui,
+        scene_rect: &mut Rect,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> SceneResponse<R> {
+        let (outer_rect, outer_response) =
+            parent_ui.allocate_exact_size(parent_ui.available_size_before_wrap(), Sense::hover());
+
+        let scene_rect_was_good = scene_rect.is_finite() && scene_rect.is_positive();
+        let mut to_global = fit_to_rect_in_scene(outer_rect, *scene_rect, self.zoom_range);
+
+        let mut content_rect = Rect::NOTHING;
+        let InnerResponse {
+            inner,
+            mut response,
+        } = self.show_global_transform(
+            parent_ui,
+            outer_response.id.with("scene"),
+            outer_rect,
+            &mut to_global,
+            |ui| {
+                let inner = add_contents(ui);
+                content_rect = ui.min_rect();
+                inner
+            },
+        );
+
+        if response.changed() {
+            *scene_rect = to_global.inverse() * outer_rect;
+        }
+
+        if !scene_rect_was_good && content_rect.is_positive() {
+            // Fit to the contents, e.g. the first time the scene is shown:
+            *scene_rect = content_rect;
+            response.mark_changed();
+        }
+
+        SceneResponse {
+            inner,
+            response,
+            to_global,
+            content_rect,
+        }
+    }
+
+    /// Show the contents with the given transform, letting the user change it.
+    fn show_global_transform<R>(
+        &self,
+        parent_ui: &mut Ui,
+        id: crate::Id,
+        outer_rect: Rect,
+        to_global: &mut TSTransform,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> InnerResponse<R> {
+        // A new layer for the contents, on top of the layer of the parent:
+        let scene_layer_id = LayerId::new(parent_ui.layer_id().order, id);
+        parent_ui
+            .ctx()
+            .set_sublayer(parent_ui.layer_id(), scene_layer_id);
+
+        let mut local_ui = parent_ui.new_child(
+            UiBuilder::new()
+                .layer_id(scene_layer_id)
+                .max_rect(Rect::from_min_size(Pos2::ZERO, self.max_inner_size))
+                .sense(Sense::click_and_drag()),
+        );
+
+        let mut pan_response = local_ui.response();
+        self.pan_and_zoom(&local_ui, &mut pan_response, to_global);
+
+        local_ui.set_clip_rect(to_global.inverse() * outer_rect);
+        local_ui
+            .ctx()
+            .set_transform_layer(scene_layer_id, *to_global);
+
+        let inner = add_contents(&mut local_ui);
+
+        // Catch drags anywhere on the background, not just where there are contents:
+        local_ui.force_set_min_rect(to_global.inverse() * outer_rect);
+
+        InnerResponse {
+            inner,
+            response: pan_response,
+        }
+    }
+
+    /// Update `to_global` from dragging, scrolling and zooming.
+    fn pan_and_zoom(&self, ui: &Ui, response: &mut Response, to_global: &mut TSTransform) {
+        if response.dragged() {
+            to_global.translation += to_global.scaling * response.drag_delta();
+            response.mark_changed();
+        }
+
+        if ZoomHandler::new()
+            .zoom_range(self.zoom_range)
+            .handle(ui, response, to_global)
+        {
+            response.mark_changed();
+        }
+
+        if !response.contains_pointer() {
+            return;
+        }
+
+        let pan_delta = ui.input(|i| i.smooth_scroll_delta);
+        if pan_delta != Vec2::ZERO {
+            to_global.translation += pan_delta;
+            response.mark_changed();
+
+            // Don't also scroll an enclosing `ScrollArea`:
+            ui.ctx().input_mut(|i| i.smooth_scroll_delta = Vec2::ZERO);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fit_to_rect() {
+        let global = Rect::from_min_size(Pos2::new(100.0, 100.0), Vec2::new(200.0, 100.0));
+        let scene = Rect::from_min_size(Pos2::ZERO, Vec2::new(100.0, 100.0));
+
+        let to_global = fit_to_rect_in_scene(global, scene, Rangef::new(0.1, 10.0));
+        assert_eq!(to_global.scaling, 1.0);
+        assert_eq!(to_global * scene.center(), global.center());
+
+        let to_global = fit_to_rect_in_scene(global, scene, Rangef::new(0.1, 0.5));
+        assert_eq!(to_global.scaling, 0.5);
+        assert_eq!(to_global * scene.center(), global.center());
+    }
+}
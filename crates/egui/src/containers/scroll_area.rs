@@ -1,8 +1,10 @@
 #![allow(clippy::needless_range_loop)]
 
 use crate::{
-    emath, epaint, lerp, pass_state, pos2, remap, remap_clamp, vec2, Context, Id, NumExt, Pos2,
-    Rangef, Rect, Sense, Ui, UiBuilder, UiKind, UiStackInfo, Vec2, Vec2b,
+    emath, epaint, lerp, pass_state, pos2, remap, remap_clamp,
+    style::{ScrollAnimation, ScrollEasing},
+    vec2, Context, Id, NumExt, Pos2, Rangef, Rect, ScrollPhase, Sense, Ui, UiBuilder, UiKind,
+    UiStackInfo, Vec2, Vec2b,
 };
 
 #[derive(Clone, Copy, Debug)]
@@ -10,6 +12,7 @@ use crate::{
 struct ScrollingToTarget {
     animation_time_span: (f64, f64),
     target_offset: f32,
+    easing: ScrollEasing,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -35,6 +38,12 @@ pub struct State {
     #[cfg_attr(feature = "serde", serde(skip))]
     vel: Vec2,
 
+    /// How far the content has been dragged past its ends, see [`ScrollArea::overscroll`].
+    ///
+    /// This is on top of [`Self::offset`], and springs back to zero when released.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    overscroll: Vec2,
+
     /// Mouse offset relative to the top of the handle when started moving the handle.
     scroll_start_offset_from_top_left: [Option<f32>; 2],
 
@@ -56,6 +65,7 @@ impl Default for State {
             content_is_too_large: Vec2b::FALSE,
             scroll_bar_interaction: Vec2b::FALSE,
             vel: Vec2::ZERO,
+            overscroll: Vec2::ZERO,
             scroll_start_offset_from_top_left: [None; 2],
             scroll_stuck_to_end: Vec2b::TRUE,
             interact_rect: None,
@@ -76,6 +86,68 @@ impl State {
     pub fn velocity(&self) -> Vec2 {
         self.vel
     }
+
+    /// How far the content is currently dragged past its ends, see [`ScrollArea::overscroll`].
+    pub fn overscroll(&self) -> Vec2 {
+        self.overscroll
+    }
+
+    /// Start scrolling smoothly to `target_offset` along axis `d`.
+    fn animate_offset(
+        &mut self,
+        d: usize,
+        target_offset: f32,
+        now: f64,
+        animation: &ScrollAnimation,
+    ) {
+        // The further we scroll, the more time we take.
+        let distance = (target_offset - self.offset[d]).abs();
+        let animation_duration = (distance / animation.points_per_second)
+            .clamp(animation.duration.min, animation.duration.max);
+        self.offset_target[d] = Some(ScrollingToTarget {
+            animation_time_span: (now, now + animation_duration as f64),
+            target_offset,
+            easing: animation.easing,
+        });
+    }
+
+    /// Drag the content by `delta` along axis `d`,
+    /// with increasing resistance the further it is dragged past the end.
+    ///
+    /// Dragging past the end in the first place is turned into overscroll in [`Prepared::end`].
+    fn drag_with_overscroll(&mut self, d: usize, delta: f32, visible_size: f32) {
+        let overscroll = self.overscroll[d];
+        if overscroll == 0.0 {
+            self.offset[d] += delta;
+        } else if (overscroll > 0.0) != (delta > 0.0) {
+            // Back towards the content, which scrolls once the overscroll is gone:
+            let new_overscroll = overscroll + delta;
+            if (new_overscroll > 0.0) == (overscroll > 0.0) {
+                self.overscroll[d] = new_overscroll;
+            } else {
+                self.overscroll[d] = 0.0;
+                self.offset[d] += new_overscroll;
+            }
+        } else {
+            // Can't be dragged further than half the visible size:
+            let resistance = (1.0 - 2.0 * overscroll.abs() / visible_size).at_least(0.0);
+            self.overscroll[d] += 0.5 * resistance * resistance * delta;
+        }
+    }
+
+    /// Let the overscroll along axis `d` spring back.
+    ///
+    /// Returns `true` if it hasn't settled yet.
+    fn spring_back(&mut self, d: usize, dt: f32) -> bool {
+        if self.overscroll[d] == 0.0 {
+            return false;
+        }
+        self.overscroll[d] *= (-15.0 * dt).exp();
+        if self.overscroll[d].abs() < 0.5 {
+            self.overscroll[d] = 0.0;
+        }
+        true
+    }
 }
 
 pub struct ScrollAreaOutput<R> {
@@ -94,6 +166,80 @@ pub struct ScrollAreaOutput<R> {
 
     /// Where on the screen the content is (excludes scroll bars).
     pub inner_rect: Rect,
+
+    /// The scroll wheel and trackpad scrolling the [`ScrollArea`] received this pass
+    /// but didn't apply, because of [`ScrollArea::intercept_scroll`].
+    ///
+    /// This has the same sign as [`crate::InputState::smooth_scroll_delta`],
+    /// i.e. positive when the user scrolls up or left.
+    pub intercepted_scroll_delta: Vec2,
+}
+
+impl<R> ScrollAreaOutput<R> {
+    /// Smoothly scroll to the given offset, starting next pass.
+    ///
+    /// The offset is clamped to the content, so you can use e.g. `f32::INFINITY` to scroll to the end.
+    /// Use the current [`State::offset`] for an axis you don't want to scroll.
+    ///
+    /// Any user scrolling interrupts the animation.
+    /// Use [`ScrollAnimation::none`] to jump there instead.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// use egui::style::ScrollAnimation;
+    ///
+    /// let output = egui::ScrollArea::vertical().show(ui, |ui| {
+    ///     // …
+    /// });
+    /// if ui.button("Back to top").clicked() {
+    ///     output.scroll_to_offset(
+    ///         ui.ctx(),
+    ///         egui::vec2(output.state.offset.x, 0.0),
+    ///         ScrollAnimation::duration(0.5),
+    ///     );
+    /// }
+    /// # });
+    /// ```
+    pub fn scroll_to_offset(&self, ctx: &Context, offset: Vec2, animation: ScrollAnimation) {
+        let Some(mut state) = State::load(ctx, self.id) else {
+            return;
+        };
+        let max_offset = (self.content_size - self.inner_rect.size()).at_least(Vec2::ZERO);
+        let now = ctx.input(|i| i.time);
+        for d in 0..2 {
+            let target_offset = offset[d].clamp(0.0, max_offset[d]);
+            if target_offset != state.offset[d] {
+                state.vel[d] = 0.0;
+                state.scroll_stuck_to_end[d] = false;
+                state.animate_offset(d, target_offset, now, &animation);
+            }
+        }
+        state.store(ctx, self.id);
+        ctx.request_repaint();
+    }
+
+    /// Smoothly scroll by the given amount, starting next pass.
+    ///
+    /// Positive values scroll down and right. See [`Self::scroll_to_offset`].
+    pub fn scroll_by(&self, ctx: &Context, delta: Vec2, animation: ScrollAnimation) {
+        self.scroll_to_offset(ctx, self.state.offset + delta, animation);
+    }
+}
+
+/// What to do with scrolling a nested [`ScrollArea`] can't use, see [`ScrollArea::scroll_chaining`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum ScrollChaining {
+    /// Once the scroll area is scrolled to its end, the rest of the scrolling goes to the parent.
+    #[default]
+    Chain,
+
+    /// The parent never scrolls while the pointer is over this scroll area,
+    /// as long as the content is too large to fit.
+    ///
+    /// Useful for e.g. lists in a scrollable page, where the page
+    /// shouldn't suddenly move when the list reaches its end.
+    Contain,
 }
 
 /// Indicate whether the horizontal and vertical scroll bars must be always visible, hidden or visible when needed.
@@ -179,6 +325,8 @@ pub struct ScrollArea {
     /// If false, we ignore scroll events.
     scrolling_enabled: bool,
     drag_to_scroll: bool,
+    kinetic_scrolling: bool,
+    overscroll: bool,
 
     /// If true for vertical or horizontal the scroll wheel will stick to the
     /// end position until user manually changes position. It will become true
@@ -187,6 +335,9 @@ pub struct ScrollArea {
 
     /// If false, `scroll_to_*` functions will not be animated
     animated: bool,
+
+    intercept_scroll: bool,
+    scroll_chaining: ScrollChaining,
 }
 
 impl ScrollArea {
@@ -230,8 +381,12 @@ impl ScrollArea {
             offset_y: None,
             scrolling_enabled: true,
             drag_to_scroll: true,
+            kinetic_scrolling: true,
+            overscroll: false,
             stick_to_end: Vec2b::FALSE,
             animated: true,
+            intercept_scroll: false,
+            scroll_chaining: ScrollChaining::Chain,
         }
     }
 
@@ -411,6 +566,32 @@ impl ScrollArea {
         self
     }
 
+    /// Should the content keep scrolling (and slowly come to a stop)
+    /// when the user lets go of it after a quick drag, i.e. a fling?
+    ///
+    /// Only has an effect if [`Self::drag_to_scroll`] is on.
+    ///
+    /// Default: `true`.
+    #[inline]
+    pub fn kinetic_scrolling(mut self, kinetic_scrolling: bool) -> Self {
+        self.kinetic_scrolling = kinetic_scrolling;
+        self
+    }
+
+    /// Can the content be dragged (or flung) past its ends,
+    /// with increasing resistance, and bounce back when let go?
+    ///
+    /// This is what scrolling feels like on most touch screens.
+    /// It also works when scrolling with a precision trackpad, if the backend
+    /// reports the [`crate::ScrollPhase`] (`egui-winit` does on Mac and Windows).
+    ///
+    /// Default: `false`.
+    #[inline]
+    pub fn overscroll(mut self, overscroll: bool) -> Self {
+        self.overscroll = overscroll;
+        self
+    }
+
     /// For each axis, should the containing area shrink if the content is small?
     ///
     /// * If `true`, egui will add blank space outside the scroll area.
@@ -425,6 +606,9 @@ impl ScrollArea {
 
     /// Should the scroll area animate `scroll_to_*` functions?
     ///
+    /// How the animation looks is controlled by the [`crate::style::ScrollAnimation`]
+    /// given to e.g. [`Ui::scroll_to_rect_animation`], or [`crate::Style::scroll_animation`].
+    ///
     /// Default: `true`.
     #[inline]
     pub fn animated(mut self, animated: bool) -> Self {
@@ -432,6 +616,54 @@ impl ScrollArea {
         self
     }
 
+    /// Don't scroll with the scroll wheel or trackpad, but report the scrolling
+    /// in [`ScrollAreaOutput::intercepted_scroll_delta`] instead.
+    ///
+    /// The scrolling is still consumed, so parent scroll areas don't scroll either.
+    /// This lets you take over the scrolling, e.g. to snap to whole pages
+    /// with [`ScrollAreaOutput::scroll_to_offset`].
+    /// Dragging the content and the scroll bars still works; see [`Self::drag_to_scroll`].
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// # let page_height = 400.0;
+    /// use egui::style::ScrollAnimation;
+    ///
+    /// let output = egui::ScrollArea::vertical()
+    ///     .intercept_scroll(true)
+    ///     .show(ui, |ui| {
+    ///         // Pages of `page_height` each…
+    ///     });
+    ///
+    /// let delta = output.intercepted_scroll_delta.y;
+    /// if delta != 0.0 {
+    ///     let page = (output.state.offset.y / page_height).round() - delta.signum();
+    ///     output.scroll_to_offset(
+    ///         ui.ctx(),
+    ///         egui::vec2(0.0, page * page_height),
+    ///         ScrollAnimation::duration(0.3),
+    ///     );
+    /// }
+    /// # });
+    /// ```
+    ///
+    /// Default: `false`.
+    #[inline]
+    pub fn intercept_scroll(mut self, intercept_scroll: bool) -> Self {
+        self.intercept_scroll = intercept_scroll;
+        self
+    }
+
+    /// What happens with scrolling this scroll area can't use,
+    /// e.g. because it is already scrolled to the end.
+    ///
+    /// Default: [`ScrollChaining::Chain`].
+    #[inline]
+    pub fn scroll_chaining(mut self, scroll_chaining: ScrollChaining) -> Self {
+        self.scroll_chaining = scroll_chaining;
+        self
+    }
+
     /// Is any scrolling enabled?
     pub(crate) fn is_any_scroll_enabled(&self) -> bool {
         self.scroll_enabled[0] || self.scroll_enabled[1]
@@ -500,6 +732,13 @@ struct Prepared {
     scrolling_enabled: bool,
     stick_to_end: Vec2b,
     animated: bool,
+    overscroll: bool,
+
+    /// Is the user dragging the contents to scroll?
+    content_dragged: bool,
+
+    intercept_scroll: bool,
+    scroll_chaining: ScrollChaining,
 }
 
 impl ScrollArea {
@@ -516,12 +755,17 @@ impl ScrollArea {
             offset_y,
             scrolling_enabled,
             drag_to_scroll,
+            kinetic_scrolling,
+            overscroll,
             stick_to_end,
             animated,
+            intercept_scroll,
+            scroll_chaining,
         } = self;
 
         let ctx = ui.ctx().clone();
-        let scrolling_enabled = scrolling_enabled && ui.is_enabled();
+        // You can still scroll around a read-only ui:
+        let scrolling_enabled = scrolling_enabled && (ui.is_enabled() || ui.is_read_only());
 
         let id_salt = id_salt.unwrap_or_else(|| Id::new("scroll_area"));
         let id = ui.make_persistent_id(id_salt);
@@ -583,7 +827,8 @@ impl ScrollArea {
             }
         }
 
-        let content_max_rect = Rect::from_min_size(inner_rect.min - state.offset, content_max_size);
+        let offset = state.offset + state.overscroll;
+        let content_max_rect = Rect::from_min_size(inner_rect.min - offset, content_max_size);
         let mut content_ui = ui.new_child(
             UiBuilder::new()
                 .ui_stack_info(UiStackInfo::new(UiKind::ScrollArea))
@@ -608,12 +853,16 @@ impl ScrollArea {
             content_ui.set_clip_rect(content_clip_rect);
         }
 
-        let viewport = Rect::from_min_size(Pos2::ZERO + state.offset, inner_size);
+        let viewport = Rect::from_min_size(Pos2::ZERO + offset, inner_size);
         let dt = ui.input(|i| i.stable_dt).at_most(0.1);
+        let mut content_dragged = false;
 
-        if (scrolling_enabled && drag_to_scroll)
-            && (state.content_is_too_large[0] || state.content_is_too_large[1])
-        {
+        // Don't spring back while the fingers are still on the trackpad:
+        let trackpad_touching = ui.input(|i| i.scroll_phase.is_touching());
+
+        let can_drag_to_scroll = (scrolling_enabled && drag_to_scroll)
+            && (state.content_is_too_large[0] || state.content_is_too_large[1]);
+        if can_drag_to_scroll {
             // Drag contents to scroll (for touch screens mostly).
             // We must do this BEFORE adding content to the `ScrollArea`,
             // or we will steal input from the widgets we contain.
@@ -625,20 +874,25 @@ impl ScrollArea {
                 .as_ref()
                 .is_some_and(|response| response.dragged())
             {
+                content_dragged = true;
                 for d in 0..2 {
                     if scroll_enabled[d] {
-                        ui.input(|input| {
-                            state.offset[d] -= input.pointer.delta()[d];
-                        });
+                        let delta = -ui.input(|input| input.pointer.delta()[d]);
+                        if overscroll {
+                            state.drag_with_overscroll(d, delta, inner_size[d]);
+                        } else {
+                            state.offset[d] += delta;
+                        }
                         state.scroll_stuck_to_end[d] = false;
                         state.offset_target[d] = None;
                     }
                 }
             } else {
                 // Apply the cursor velocity to the scroll area when the user releases the drag.
-                if content_response_option
-                    .as_ref()
-                    .is_some_and(|response| response.drag_stopped())
+                if kinetic_scrolling
+                    && content_response_option
+                        .as_ref()
+                        .is_some_and(|response| response.drag_stopped())
                 {
                     state.vel =
                         scroll_enabled.to_vec2() * ui.input(|input| input.pointer.velocity());
@@ -646,7 +900,11 @@ impl ScrollArea {
                 for d in 0..2 {
                     // Kinetic scrolling
                     let stop_speed = 20.0; // Pixels per second.
-                    let friction_coeff = 1000.0; // Pixels per second squared.
+                    let mut friction_coeff = 1000.0; // Pixels per second squared.
+                    if state.overscroll[d] != 0.0 {
+                        // Flung past the end: stop quickly, then bounce back.
+                        friction_coeff *= 10.0;
+                    }
 
                     let friction = friction_coeff * dt;
                     if friction > state.vel[d].abs() || state.vel[d].abs() < stop_speed {
@@ -655,13 +913,32 @@ impl ScrollArea {
                         state.vel[d] -= friction * state.vel[d].signum();
                         // Offset has an inverted coordinate system compared to
                         // the velocity, so we subtract it instead of adding it
-                        state.offset[d] -= state.vel[d] * dt;
+                        if state.overscroll[d] == 0.0 {
+                            state.offset[d] -= state.vel[d] * dt;
+                        } else {
+                            state.overscroll[d] -= state.vel[d] * dt;
+                        }
+                        ctx.request_repaint();
+                    }
+
+                    if state.vel[d] == 0.0 && !trackpad_touching && state.spring_back(d, dt) {
                         ctx.request_repaint();
                     }
                 }
             }
         }
 
+        if !overscroll {
+            state.overscroll = Vec2::ZERO;
+        } else if !can_drag_to_scroll && !trackpad_touching {
+            // Scrolled past the end with a trackpad (see `Prepared::end`):
+            for d in 0..2 {
+                if state.spring_back(d, dt) {
+                    ctx.request_repaint();
+                }
+            }
+        }
+
         // Scroll with an animation if we have a target offset (that hasn't been cleared by the code
         // above).
         for d in 0..2 {
@@ -678,7 +955,7 @@ impl ScrollArea {
                         scroll_target.animation_time_span,
                         ui.input(|i| i.time),
                         dt,
-                        emath::ease_in_ease_out,
+                        |t| scroll_target.easing.apply(t),
                     );
                     if t < 1.0 {
                         state.offset[d] =
@@ -708,6 +985,10 @@ impl ScrollArea {
             scrolling_enabled,
             stick_to_end,
             animated,
+            overscroll,
+            content_dragged,
+            intercept_scroll,
+            scroll_chaining,
         }
     }
 
@@ -792,20 +1073,21 @@ impl ScrollArea {
         let id = prepared.id;
         let inner_rect = prepared.inner_rect;
         let inner = add_contents(&mut prepared.content_ui, prepared.viewport);
-        let (content_size, state) = prepared.end(ui);
+        let (content_size, state, intercepted_scroll_delta) = prepared.end(ui);
         ScrollAreaOutput {
             inner,
             id,
             state,
             content_size,
             inner_rect,
+            intercepted_scroll_delta,
         }
     }
 }
 
 impl Prepared {
-    /// Returns content size and state
-    fn end(self, ui: &mut Ui) -> (Vec2, State) {
+    /// Returns content size, state and the intercepted scroll delta
+    fn end(self, ui: &mut Ui) -> (Vec2, State, Vec2) {
         let Self {
             id,
             mut state,
@@ -821,6 +1103,10 @@ impl Prepared {
             scrolling_enabled,
             stick_to_end,
             animated,
+            overscroll,
+            content_dragged,
+            intercept_scroll,
+            scroll_chaining,
         } = self;
 
         let content_size = content_ui.min_size();
@@ -888,14 +1174,8 @@ impl Prepared {
                         // so we don't want to reset the animation, but perhaps update the target:
                         animation.target_offset = target_offset;
                     } else {
-                        // The further we scroll, the more time we take.
                         let now = ui.input(|i| i.time);
-                        let animation_duration = (delta.abs() / animation.points_per_second)
-                            .clamp(animation.duration.min, animation.duration.max);
-                        state.offset_target[d] = Some(ScrollingToTarget {
-                            animation_time_span: (now, now + animation_duration as f64),
-                            target_offset,
-                        });
+                        state.animate_offset(d, target_offset, now, &animation);
                     }
                     ui.ctx().request_repaint();
                 }
@@ -926,10 +1206,25 @@ impl Prepared {
         );
 
         let max_offset = content_size - inner_rect.size();
+        let scroll_phase = ui.input(|i| i.scroll_phase);
         let is_hovering_outer_rect = ui.rect_contains_pointer(outer_rect);
+        let mut intercepted_scroll_delta = Vec2::ZERO;
         if scrolling_enabled && is_hovering_outer_rect {
             let always_scroll_enabled_direction = ui.style().always_scroll_the_only_direction
                 && scroll_enabled[0] != scroll_enabled[1];
+
+            // Clear scroll delta so no parent scroll will use it:
+            let consume_scroll_delta = |d: usize| {
+                ui.ctx().input_mut(|input| {
+                    if always_scroll_enabled_direction {
+                        input.smooth_scroll_delta[0] = 0.0;
+                        input.smooth_scroll_delta[1] = 0.0;
+                    } else {
+                        input.smooth_scroll_delta[d] = 0.0;
+                    }
+                });
+            };
+
             for d in 0..2 {
                 if scroll_enabled[d] {
                     let scroll_delta = ui.ctx().input_mut(|input| {
@@ -941,24 +1236,58 @@ impl Prepared {
                         }
                     });
 
+                    if intercept_scroll {
+                        intercepted_scroll_delta[d] += scroll_delta;
+                        consume_scroll_delta(d);
+                        continue;
+                    }
+
                     let scrolling_up = state.offset[d] > 0.0 && scroll_delta > 0.0;
                     let scrolling_down = state.offset[d] < max_offset[d] && scroll_delta < 0.0;
 
-                    if scrolling_up || scrolling_down {
+                    // Rubber-band when scrolling past the ends with a trackpad:
+                    let rubber_band = overscroll
+                        && content_is_too_large[d]
+                        && scroll_delta != 0.0
+                        && (scroll_phase.is_touching() || state.overscroll[d] != 0.0);
+
+                    if rubber_band
+                        && (state.overscroll[d] != 0.0 || !(scrolling_up || scrolling_down))
+                    {
+                        // Momentum only bounces off the end, it doesn't pull any further:
+                        let towards_content = (state.overscroll[d] > 0.0) == (scroll_delta > 0.0);
+                        if scroll_phase.is_touching() || towards_content {
+                            state.drag_with_overscroll(d, -scroll_delta, inner_rect.size()[d]);
+                        }
+                        consume_scroll_delta(d);
+                        state.scroll_stuck_to_end[d] = false;
+                        state.offset_target[d] = None;
+                        ui.ctx().request_repaint();
+                    } else if scrolling_up || scrolling_down {
                         state.offset[d] -= scroll_delta;
-
-                        // Clear scroll delta so no parent scroll will use it:
-                        ui.ctx().input_mut(|input| {
-                            if always_scroll_enabled_direction {
-                                input.smooth_scroll_delta[0] = 0.0;
-                                input.smooth_scroll_delta[1] = 0.0;
-                            } else {
-                                input.smooth_scroll_delta[d] = 0.0;
-                            }
-                        });
-
+                        consume_scroll_delta(d);
                         state.scroll_stuck_to_end[d] = false;
                         state.offset_target[d] = None;
+                    } else if scroll_chaining == ScrollChaining::Contain && content_is_too_large[d]
+                    {
+                        // Scrolled to the end, but the parent shouldn't take over:
+                        consume_scroll_delta(d);
+                    }
+                }
+            }
+        }
+
+        if overscroll {
+            for d in 0..2 {
+                let trackpad = scroll_phase.is_touching() || scroll_phase == ScrollPhase::Momentum;
+                if scroll_enabled[d] && (content_dragged || state.vel[d] != 0.0 || trackpad) {
+                    // Turn scrolling past the ends into overscroll, instead of just stopping:
+                    let clamped = state.offset[d].clamp(0.0, max_offset[d].at_least(0.0));
+                    let excess = state.offset[d] - clamped;
+                    if excess != 0.0 && state.overscroll[d] == 0.0 {
+                        state.offset[d] = clamped;
+                        state.overscroll[d] = 0.5 * excess;
+                        ui.ctx().request_repaint();
                     }
                 }
             }
@@ -1125,7 +1454,7 @@ impl Prepared {
             state.offset[d] = state.offset[d].max(0.0);
             state.offset[d] = state.offset[d].min(max_offset[d]);
 
-            if state.offset[d] != unbounded_offset {
+            if state.offset[d] != unbounded_offset && state.overscroll[d] == 0.0 {
                 state.vel[d] = 0.0;
             }
 
@@ -1260,6 +1589,51 @@ impl Prepared {
 
         state.store(ui.ctx(), id);
 
-        (content_size, state)
+        (content_size, state, intercepted_scroll_delta)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CentralPanel, RawInput};
+
+    #[test]
+    fn scroll_to_offset() {
+        let ctx = Context::default();
+        let run = |time: f64| {
+            let mut output = None;
+            let _ = ctx.run(
+                RawInput {
+                    time: Some(time),
+                    screen_rect: Some(Rect::from_min_size(Pos2::ZERO, vec2(100.0, 100.0))),
+                    ..Default::default()
+                },
+                |ctx| {
+                    CentralPanel::default().show(ctx, |ui| {
+                        output = Some(ScrollArea::vertical().show(ui, |ui| {
+                            ui.set_height(1000.0);
+                        }));
+                    });
+                },
+            );
+            output.unwrap()
+        };
+
+        let output = run(0.0);
+        assert_eq!(output.state.offset.y, 0.0);
+        output.scroll_to_offset(&ctx, vec2(0.0, 500.0), ScrollAnimation::duration(1.0));
+
+        let halfway = run(0.5).state.offset.y;
+        assert!(0.0 < halfway && halfway < 500.0, "{halfway}");
+        assert_eq!(run(1.0).state.offset.y, 500.0);
+
+        // Clamped to the content:
+        run(1.0).scroll_by(&ctx, vec2(0.0, 10_000.0), ScrollAnimation::none());
+        let output = run(1.1);
+        assert_eq!(
+            output.state.offset.y,
+            output.content_size.y - output.inner_rect.height()
+        );
     }
 }
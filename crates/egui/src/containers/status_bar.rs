@@ -0,0 +1,514 @@
+//! A bar showing the status of the application, usually at the bottom of the window.
+
+use emath::{pos2, Align};
+use epaint::Shape;
+
+use crate::{
+    popup_above_or_below_widget, AboveOrBelow, Id, Layout, PopupCloseBehavior, ProgressBar, Rect,
+    Response, Sense, Ui, UiBuilder, WidgetText,
+};
+
+/// What a [`StatusItem`] shows.
+enum StatusItemKind<'a> {
+    Label(WidgetText),
+    Progress {
+        progress: f32,
+        text: Option<WidgetText>,
+        width: f32,
+    },
+    Spinner(Option<WidgetText>),
+    Custom(Box<dyn FnOnce(&mut Ui) + 'a>),
+}
+
+impl<'a> StatusItemKind<'a> {
+    fn ui(self, ui: &mut Ui) {
+        match self {
+            Self::Label(text) => {
+                ui.label(text);
+            }
+            Self::Progress {
+                progress,
+                text,
+                width,
+            } => {
+                let mut progress_bar = ProgressBar::new(progress).desired_width(width);
+                if let Some(text) = text {
+                    progress_bar = progress_bar.text(text);
+                }
+                ui.add(progress_bar);
+            }
+            Self::Spinner(text) => {
+                ui.spinner();
+                if let Some(text) = text {
+                    ui.label(text);
+                }
+            }
+            Self::Custom(add_contents) => add_contents(ui),
+        }
+    }
+}
+
+/// Something shown in a [`StatusBar`].
+///
+/// When the bar is too narrow, the items with the lowest [`Self::priority`] are moved into a "…" menu.
+#[must_use = "You should add it to a StatusBar"]
+pub struct StatusItem<'a> {
+    kind: StatusItemKind<'a>,
+    priority: i32,
+    tooltip: Option<WidgetText>,
+    details: Option<Box<dyn FnOnce(&mut Ui) + 'a>>,
+}
+
+impl<'a> StatusItem<'a> {
+    fn new(kind: StatusItemKind<'a>) -> Self {
+        Self {
+            kind,
+            priority: 0,
+            tooltip: None,
+            details: None,
+        }
+    }
+
+    /// Some text, e.g. "Ready" or "Ln 12, Col 4".
+    pub fn label(text: impl Into<WidgetText>) -> Self {
+        Self::new(StatusItemKind::Label(text.into()))
+    }
+
+    /// A progress bar, with `progress` in the range `[0, 1]`.
+    pub fn progress(progress: f32) -> Self {
+        Self::new(StatusItemKind::Progress {
+            progress,
+            text: None,
+            width: 100.0,
+        })
+    }
+
+    /// A spinner, for work of unknown length.
+    pub fn spinner() -> Self {
+        Self::new(StatusItemKind::Spinner(None))
+    }
+
+    /// Anything you like. Keep it a single row.
+    pub fn ui(add_contents: impl FnOnce(&mut Ui) + 'a) -> Self {
+        Self::new(StatusItemKind::Custom(Box::new(add_contents)))
+    }
+
+    /// Text shown in the progress bar, or next to the spinner.
+    ///
+    /// For a label this replaces its text, and for a custom item it does nothing.
+    #[inline]
+    pub fn text(mut self, text: impl Into<WidgetText>) -> Self {
+        match &mut self.kind {
+            StatusItemKind::Label(label) => *label = text.into(),
+            StatusItemKind::Progress { text: t, .. } | StatusItemKind::Spinner(t) => {
+                *t = Some(text.into());
+            }
+            StatusItemKind::Custom(_) => {}
+        }
+        self
+    }
+
+    /// The width of a progress bar. Default: 100.
+    #[inline]
+    pub fn progress_width(mut self, progress_width: f32) -> Self {
+        if let StatusItemKind::Progress { width, .. } = &mut self.kind {
+            *width = progress_width;
+        }
+        self
+    }
+
+    /// Items with a higher priority are kept in the bar longer when it gets too narrow.
+    ///
+    /// Among items with the same priority, the ones added first are kept longer. Default: 0.
+    #[inline]
+    pub fn priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Shown when hovering the item.
+    #[inline]
+    pub fn tooltip(mut self, tooltip: impl Into<WidgetText>) -> Self {
+        self.tooltip = Some(tooltip.into());
+        self
+    }
+
+    /// Clicking the item opens a popup above it with these contents,
+    /// e.g. a list of background tasks, or the full text of the latest message.
+    #[inline]
+    pub fn details(mut self, add_contents: impl FnOnce(&mut Ui) + 'a) -> Self {
+        self.details = Some(Box::new(add_contents));
+        self
+    }
+
+    fn show(self, ui: &mut Ui, id: Id, max_rect: Rect) -> Response {
+        let Self {
+            kind,
+            priority: _,
+            tooltip,
+            details,
+        } = self;
+
+        let mut item_ui = ui.new_child(
+            UiBuilder::new()
+                .id_salt(id)
+                .max_rect(max_rect)
+                .layout(Layout::left_to_right(Align::Center)),
+        );
+        let background = item_ui.painter().add(Shape::Noop);
+        kind.ui(&mut item_ui);
+        let rect = item_ui.min_rect();
+
+        let mut response = ui.interact(rect, id, Sense::click());
+        if let Some(tooltip) = tooltip {
+            response = response.on_hover_text(tooltip);
+        }
+
+        if let Some(details) = details {
+            let popup_id = id.with("details");
+            if response.clicked() {
+                ui.memory_mut(|mem| mem.toggle_popup(popup_id));
+            }
+            let is_open = ui.memory(|mem| mem.is_popup_open(popup_id));
+            if response.hovered() || is_open {
+                let visuals = ui.visuals().widgets.hovered;
+                item_ui.painter().set(
+                    background,
+                    Shape::rect_filled(
+                        rect.expand(ui.spacing().button_padding.y),
+                        visuals.rounding,
+                        visuals.weak_bg_fill,
+                    ),
+                );
+            }
+            popup_above_or_below_widget(
+                ui,
+                popup_id,
+                &response,
+                AboveOrBelow::Above,
+                PopupCloseBehavior::CloseOnClickOutside,
+                details,
+            );
+        }
+
+        response
+    }
+
+    /// Show the item in the "…" menu of items that don't fit in the bar.
+    fn show_in_menu(self, ui: &mut Ui) {
+        let Self {
+            kind,
+            priority: _,
+            tooltip,
+            details,
+        } = self;
+
+        let response = ui.horizontal(|ui| kind.ui(ui)).response;
+        if let Some(details) = details {
+            ui.indent(response.id, details);
+        }
+        if let Some(tooltip) = tooltip {
+            response.on_hover_text(tooltip);
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Section {
+    Left,
+    Center,
+    Right,
+}
+
+/// The items of a [`StatusBar`], added in [`StatusBar::show`].
+#[derive(Default)]
+pub struct StatusBarItems<'a> {
+    items: Vec<(Section, StatusItem<'a>)>,
+}
+
+impl<'a> StatusBarItems<'a> {
+    /// Add an item to the left section. Items are shown left-to-right in the order they are added.
+    pub fn left(&mut self, item: StatusItem<'a>) {
+        self.items.push((Section::Left, item));
+    }
+
+    /// Add an item to the center section. Items are shown left-to-right in the order they are added.
+    pub fn center(&mut self, item: StatusItem<'a>) {
+        self.items.push((Section::Center, item));
+    }
+
+    /// Add an item to the right section. Items are shown left-to-right in the order they are added.
+    pub fn right(&mut self, item: StatusItem<'a>) {
+        self.items.push((Section::Right, item));
+    }
+}
+
+/// What happened in a [`StatusBar`].
+pub struct StatusBarResponse {
+    /// The response of the whole bar.
+    pub response: Response,
+
+    /// The response of each item, in the order they were added,
+    /// or `None` for items that didn't fit and are in the "…" menu.
+    pub items: Vec<Option<Response>>,
+}
+
+/// What [`StatusBar`] remembers between frames.
+#[derive(Clone, Debug, Default)]
+struct State {
+    /// The width of each item, as last shown.
+    item_widths: Vec<Option<f32>>,
+
+    /// The width of the "…" button, as last shown.
+    overflow_button_width: Option<f32>,
+}
+
+/// A bar with left, center and right sections of [`StatusItem`]s: text, progress bars, spinners or anything else.
+///
+/// When there isn't room for everything, the items with the lowest priority are moved into a "…" menu.
+/// Items can have details that are shown in a popup when they are clicked.
+///
+/// It is usually shown in a [`crate::TopBottomPanel::bottom`].
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// use egui::containers::{StatusBar, StatusItem};
+///
+/// StatusBar::new("status_bar").show(ui, |bar| {
+///     bar.left(StatusItem::label("Ready").priority(1));
+///     bar.left(StatusItem::spinner().text("Syncing…").details(|ui| {
+///         ui.label("Uploading 3 files");
+///     }));
+///     bar.right(StatusItem::progress(0.4).text("Indexing"));
+///     bar.right(StatusItem::label("Ln 12, Col 4").priority(2));
+/// });
+/// # });
+/// ```
+#[must_use = "You should call .show()"]
+#[derive(Clone, Copy, Debug)]
+pub struct StatusBar {
+    id_salt: Id,
+    separators: bool,
+}
+
+impl StatusBar {
+    /// The id salt is used to remember the size of each item.
+    pub fn new(id_salt: impl std::hash::Hash) -> Self {
+        Self {
+            id_salt: Id::new(id_salt),
+            separators: true,
+        }
+    }
+
+    /// Draw lines between the items of each section. Default: `true`.
+    #[inline]
+    pub fn separators(mut self, separators: bool) -> Self {
+        self.separators = separators;
+        self
+    }
+
+    /// Show the bar, with the items added by `add_items`.
+    pub fn show<'a>(
+        self,
+        ui: &mut Ui,
+        add_items: impl FnOnce(&mut StatusBarItems<'a>),
+    ) -> StatusBarResponse {
+        let Self {
+            id_salt,
+            separators,
+        } = self;
+
+        let mut items = StatusBarItems::default();
+        add_items(&mut items);
+        let items = items.items;
+
+        let id = ui.make_persistent_id(id_salt);
+        let gap = ui.spacing().item_spacing.x;
+        let mut bar_rect = ui.available_rect_before_wrap();
+        bar_rect.max.y = bar_rect.min.y + ui.spacing().interact_size.y;
+        let response = ui.interact(bar_rect, id, Sense::hover());
+
+        let mut state = ui.data_mut(|d| d.get_temp::<State>(id)).unwrap_or_default();
+        state.item_widths.resize(items.len(), None);
+
+        let priorities: Vec<i32> = items.iter().map(|(_, item)| item.priority).collect();
+        let visible = visible_items(
+            &state.item_widths,
+            &priorities,
+            bar_rect.width(),
+            gap,
+            state.overflow_button_width.unwrap_or(0.0),
+        );
+        let any_hidden = visible.contains(&false);
+
+        // Where each visible item starts:
+        let sections: Vec<Section> = items.iter().map(|(section, _)| *section).collect();
+        let width = |i: usize| state.item_widths[i].unwrap_or(0.0);
+        let section_width = |section: Section| {
+            let width: f32 = section_items(&sections, &visible, section)
+                .map(|i| width(i) + gap)
+                .sum();
+            (width - gap).max(0.0)
+        };
+        let mut item_x: Vec<Option<f32>> = vec![None; items.len()];
+        let mut place = |section: Section, mut x: f32| {
+            for i in section_items(&sections, &visible, section) {
+                item_x[i] = Some(x);
+                x += width(i) + gap;
+            }
+            x
+        };
+
+        let mut left_end = place(Section::Left, bar_rect.left());
+        let overflow_button_x = left_end;
+        if any_hidden {
+            left_end += state.overflow_button_width.unwrap_or(0.0) + gap;
+        }
+        let right_start = bar_rect.right() - section_width(Section::Right);
+        place(Section::Right, right_start);
+        let center_width = section_width(Section::Center);
+        let max_center_x = right_start - gap - center_width;
+        let center_x = if left_end <= max_center_x {
+            (bar_rect.center().x - 0.5 * center_width).clamp(left_end, max_center_x)
+        } else {
+            left_end // No room: the left section wins
+        };
+        place(Section::Center, center_x);
+
+        let mut item_responses = Vec::with_capacity(items.len());
+        let mut hidden_items = Vec::new();
+        let mut last_in_section: [Option<Rect>; 3] = [None; 3];
+        for (i, (section, item)) in items.into_iter().enumerate() {
+            let Some(x) = item_x[i] else {
+                hidden_items.push(item);
+                item_responses.push(None);
+                continue;
+            };
+
+            let max_rect = Rect::from_min_max(pos2(x, bar_rect.top()), bar_rect.right_bottom());
+            let item_response = item.show(ui, id.with(i), max_rect);
+            state.item_widths[i] = Some(item_response.rect.width());
+
+            let last = &mut last_in_section[section as usize];
+            if let (true, Some(last)) = (separators, *last) {
+                let x = 0.5 * (last.right() + item_response.rect.left());
+                ui.painter().vline(
+                    x,
+                    bar_rect.y_range().shrink(ui.spacing().button_padding.y),
+                    ui.visuals().widgets.noninteractive.bg_stroke,
+                );
+            }
+            *last = Some(item_response.rect);
+
+            item_responses.push(Some(item_response));
+        }
+
+        if !hidden_items.is_empty() {
+            let num_hidden = hidden_items.len();
+            let mut button_ui = ui.new_child(
+                UiBuilder::new()
+                    .id_salt(id.with("overflow"))
+                    .max_rect(bar_rect.with_min_x(overflow_button_x))
+                    .layout(Layout::left_to_right(Align::Center)),
+            );
+            let button_response = button_ui
+                .menu_button("…", |ui| {
+                    for (i, item) in hidden_items.into_iter().enumerate() {
+                        if 0 < i {
+                            ui.separator();
+                        }
+                        item.show_in_menu(ui);
+                    }
+                })
+                .response
+                .on_hover_text(format!("{num_hidden} more"));
+            state.overflow_button_width = Some(button_response.rect.width());
+        }
+
+        if state.item_widths.iter().any(Option::is_none) && !ui.is_sizing_pass() {
+            // Some items have not been measured yet, so we don't know what fits.
+            ui.ctx().request_discard("StatusBar: new items");
+        }
+
+        ui.data_mut(|d| d.insert_temp(id, state));
+        ui.advance_cursor_after_rect(bar_rect);
+
+        StatusBarResponse {
+            response,
+            items: item_responses,
+        }
+    }
+}
+
+/// The indices of the visible items in `section`, in the order they were added.
+fn section_items<'s>(
+    sections: &'s [Section],
+    visible: &'s [bool],
+    section: Section,
+) -> impl Iterator<Item = usize> + 's {
+    (0..sections.len()).filter(move |&i| visible[i] && sections[i] == section)
+}
+
+/// Which items to show, hiding the ones with the lowest priority until the rest fit,
+/// together with the "…" button for the hidden ones.
+///
+/// Items that have not been measured yet are all shown, so that they get measured.
+fn visible_items(
+    widths: &[Option<f32>],
+    priorities: &[i32],
+    available: f32,
+    gap: f32,
+    overflow_button_width: f32,
+) -> Vec<bool> {
+    let num_items = widths.len();
+    if widths.iter().any(Option::is_none) {
+        return vec![true; num_items];
+    }
+
+    let total: f32 = widths.iter().flatten().sum();
+    if total + gap * num_items.saturating_sub(1) as f32 <= available {
+        return vec![true; num_items];
+    }
+
+    // `sort_by_key` is stable, so items added first win ties:
+    let mut by_priority: Vec<usize> = (0..num_items).collect();
+    by_priority.sort_by_key(|&i| std::cmp::Reverse(priorities[i]));
+
+    let mut visible = vec![false; num_items];
+    let mut used = overflow_button_width;
+    for i in by_priority {
+        used += gap + widths[i].unwrap_or(0.0);
+        if available < used {
+            break;
+        }
+        visible[i] = true;
+    }
+    visible
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lowest_priority_is_hidden_first() {
+        let widths = [Some(10.0); 4];
+        let priorities = [0, 2, 1, 0];
+        assert_eq!(
+            visible_items(&widths, &priorities, 100.0, 2.0, 5.0),
+            [true; 4]
+        );
+        assert_eq!(
+            visible_items(&widths, &priorities, 41.0, 2.0, 5.0),
+            [true, true, true, false]
+        );
+        assert_eq!(
+            visible_items(&widths, &priorities, 30.0, 2.0, 5.0),
+            [false, true, true, false]
+        );
+        assert_eq!(
+            visible_items(&[Some(10.0), None], &[0, 0], 0.0, 2.0, 5.0),
+            [true, true]
+        );
+    }
+}
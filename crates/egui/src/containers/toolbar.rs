@@ -0,0 +1,570 @@
+//! A row of action buttons, which the user can customize.
+
+use crate::{
+    containers::OverflowStrip, Button, DragAndDrop, Id, Rect, Response, Sense, Stroke, Ui,
+    WidgetText,
+};
+
+/// An action that can be put in a [`Toolbar`].
+#[derive(Clone, Debug)]
+pub struct ToolbarAction {
+    id: String,
+    icon: String,
+    label: String,
+    tooltip: Option<String>,
+    enabled: bool,
+    selected: bool,
+}
+
+impl ToolbarAction {
+    /// `id` identifies the action in [`ToolbarResponse::clicked`] and in the persisted layout,
+    /// so it should stay the same between runs of your app.
+    ///
+    /// The `icon` is usually an emoji.
+    pub fn new(id: impl Into<String>, icon: impl Into<String>, label: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            icon: icon.into(),
+            label: label.into(),
+            tooltip: None,
+            enabled: true,
+            selected: false,
+        }
+    }
+
+    /// Shown when hovering the button. Default: the label.
+    #[inline]
+    pub fn tooltip(mut self, tooltip: impl Into<String>) -> Self {
+        self.tooltip = Some(tooltip.into());
+        self
+    }
+
+    /// If `false`, the button is grayed out and can't be clicked. Default: `true`.
+    #[inline]
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Show the button as toggled on, e.g. for a "bold" action when the selected text is bold.
+    #[inline]
+    pub fn selected(mut self, selected: bool) -> Self {
+        self.selected = selected;
+        self
+    }
+
+    /// The id given to [`Self::new`].
+    #[inline]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn text(&self, style: ToolbarButtonStyle) -> WidgetText {
+        match style {
+            ToolbarButtonStyle::IconOnly => self.icon.clone().into(),
+            ToolbarButtonStyle::LabelOnly => self.label.clone().into(),
+            ToolbarButtonStyle::IconAndLabel => format!("{} {}", self.icon, self.label).into(),
+        }
+    }
+}
+
+/// Something in a [`Toolbar`].
+#[derive(Clone, Debug)]
+pub enum ToolbarItem {
+    /// A button.
+    Action(ToolbarAction),
+
+    /// A line separating groups of actions.
+    Separator,
+}
+
+impl From<ToolbarAction> for ToolbarItem {
+    #[inline]
+    fn from(action: ToolbarAction) -> Self {
+        Self::Action(action)
+    }
+}
+
+/// How the buttons of a [`Toolbar`] look.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum ToolbarButtonStyle {
+    /// Only the icon, with the label in the tooltip.
+    IconOnly,
+
+    /// Only the label.
+    LabelOnly,
+
+    /// The icon followed by the label.
+    #[default]
+    IconAndLabel,
+}
+
+/// An entry in the layout of a [`Toolbar`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+enum ToolbarEntry {
+    /// The id of a [`ToolbarAction`].
+    Action(String),
+    Separator,
+}
+
+/// The layout of a [`Toolbar`] as customized by the user. Persisted.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+struct ToolbarLayout {
+    /// `None` until the user customizes the toolbar: then we use the items given by the app.
+    entries: Option<Vec<ToolbarEntry>>,
+
+    /// Actions the user has removed.
+    ///
+    /// Actions that are neither in `entries` nor here were added by the app after the user
+    /// customized the toolbar, and are shown at the end.
+    removed: Vec<String>,
+}
+
+impl ToolbarLayout {
+    fn load(ui: &Ui, id: Id) -> Self {
+        ui.data_mut(|d| d.get_persisted::<Self>(id))
+            .unwrap_or_default()
+    }
+
+    fn store(self, ui: &Ui, id: Id) {
+        ui.data_mut(|d| d.insert_persisted(id, self));
+    }
+
+    /// The entries to show, given the items available this pass.
+    fn entries(&self, items: &[ToolbarItem]) -> Vec<ToolbarEntry> {
+        let default_entries = items.iter().map(|item| match item {
+            ToolbarItem::Action(action) => ToolbarEntry::Action(action.id.clone()),
+            ToolbarItem::Separator => ToolbarEntry::Separator,
+        });
+
+        let Some(entries) = &self.entries else {
+            return default_entries.collect();
+        };
+
+        let mut result: Vec<ToolbarEntry> = entries
+            .iter()
+            .filter(|entry| match entry {
+                ToolbarEntry::Action(id) => find_action(items, id).is_some(),
+                ToolbarEntry::Separator => true,
+            })
+            .cloned()
+            .collect();
+        for entry in default_entries {
+            if let ToolbarEntry::Action(id) = &entry {
+                if !entries.contains(&entry) && !self.removed.contains(id) {
+                    result.push(entry);
+                }
+            }
+        }
+        result
+    }
+
+    /// Remember `entries` as customized by the user.
+    fn customize(&mut self, entries: Vec<ToolbarEntry>, items: &[ToolbarItem]) {
+        self.removed = items
+            .iter()
+            .filter_map(|item| match item {
+                ToolbarItem::Action(action)
+                    if !entries.contains(&ToolbarEntry::Action(action.id.clone())) =>
+                {
+                    Some(action.id.clone())
+                }
+                _ => None,
+            })
+            .collect();
+        self.entries = Some(entries);
+    }
+}
+
+fn find_action<'a>(items: &'a [ToolbarItem], id: &str) -> Option<&'a ToolbarAction> {
+    items.iter().find_map(|item| match item {
+        ToolbarItem::Action(action) if action.id == id => Some(action),
+        _ => None,
+    })
+}
+
+/// What is being dragged while customizing a [`Toolbar`].
+#[derive(Clone, Debug)]
+struct ToolbarDrag {
+    toolbar_id: Id,
+
+    /// Index into the entries, or `None` if it is dragged from the palette of unused entries.
+    from_index: Option<usize>,
+
+    entry: ToolbarEntry,
+}
+
+/// What happened in a [`Toolbar`].
+pub struct ToolbarResponse {
+    /// The id of the action that was clicked, if any.
+    pub clicked: Option<String>,
+
+    /// The response of the whole toolbar.
+    pub response: Response,
+}
+
+impl ToolbarResponse {
+    /// Was the action with this id clicked?
+    #[inline]
+    pub fn clicked(&self, id: &str) -> bool {
+        self.clicked.as_deref() == Some(id)
+    }
+}
+
+/// A row of action buttons, like in the top of many native apps.
+///
+/// The buttons that don't fit are put into a "»" menu at the end.
+///
+/// The user can customize the toolbar by right-clicking it and selecting "Customize toolbar…":
+/// actions and separators can then be added, removed and reordered by dragging them.
+/// The customized layout is persisted (with the `persistence` feature).
+/// Actions your app adds later are appended at the end, unless the user has removed them.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// use egui::containers::{Toolbar, ToolbarAction, ToolbarItem};
+///
+/// let response = Toolbar::new("main_toolbar").show(
+///     ui,
+///     &[
+///         ToolbarAction::new("open", "🗁", "Open").into(),
+///         ToolbarAction::new("save", "💾", "Save").into(),
+///         ToolbarItem::Separator,
+///         ToolbarAction::new("undo", "⮪", "Undo").enabled(false).into(),
+///     ],
+/// );
+/// if response.clicked("open") {
+///     // …
+/// }
+/// # });
+/// ```
+#[must_use = "You should call .show()"]
+#[derive(Clone, Copy, Debug)]
+pub struct Toolbar {
+    id_salt: Id,
+    button_style: ToolbarButtonStyle,
+    customizable: bool,
+}
+
+impl Toolbar {
+    /// The id salt is used to store the layout the user customized.
+    pub fn new(id_salt: impl std::hash::Hash) -> Self {
+        Self {
+            id_salt: Id::new(id_salt),
+            button_style: ToolbarButtonStyle::default(),
+            customizable: true,
+        }
+    }
+
+    /// How the buttons look. Default: [`ToolbarButtonStyle::IconAndLabel`].
+    #[inline]
+    pub fn button_style(mut self, button_style: ToolbarButtonStyle) -> Self {
+        self.button_style = button_style;
+        self
+    }
+
+    /// Can the user customize the toolbar? Default: `true`.
+    #[inline]
+    pub fn customizable(mut self, customizable: bool) -> Self {
+        self.customizable = customizable;
+        self
+    }
+
+    /// Is the toolbar with the given id salt (in the given [`Ui`]) being customized?
+    pub fn is_customizing(ui: &Ui, id_salt: impl std::hash::Hash) -> bool {
+        let id = ui.make_persistent_id(Id::new(id_salt));
+        ui.data(|d| d.get_temp::<bool>(customizing_id(id)))
+            .unwrap_or(false)
+    }
+
+    /// Start or stop customizing the toolbar with the given id salt (in the given [`Ui`]).
+    ///
+    /// The user can also do this from the context menu of the toolbar.
+    pub fn set_customizing(ui: &Ui, id_salt: impl std::hash::Hash, customizing: bool) {
+        let id = ui.make_persistent_id(Id::new(id_salt));
+        ui.data_mut(|d| d.insert_temp(customizing_id(id), customizing));
+    }
+
+    /// Show the toolbar with the given items.
+    ///
+    /// The items are the default layout, and also all the actions the user can choose from when customizing.
+    pub fn show(self, ui: &mut Ui, items: &[ToolbarItem]) -> ToolbarResponse {
+        let Self {
+            id_salt,
+            button_style,
+            customizable,
+        } = self;
+
+        let id = ui.make_persistent_id(id_salt);
+        let customizing = customizable
+            && ui
+                .data(|d| d.get_temp::<bool>(customizing_id(id)))
+                .unwrap_or(false);
+
+        let mut layout = ToolbarLayout::load(ui, id);
+        let entries = layout.entries(items);
+
+        // Register the background first, so the buttons are on top of it:
+        let background_rect = {
+            let mut rect = ui.available_rect_before_wrap();
+            rect.max.y = rect.min.y + ui.spacing().interact_size.y;
+            rect
+        };
+        let background = ui.interact(background_rect, id.with("background"), Sense::click());
+
+        let mut clicked = None;
+        let mut new_entries = None;
+
+        let inner_response = ui.scope(|ui| {
+            if customizing {
+                new_entries = customize_ui(ui, id, items, &entries, button_style);
+            } else {
+                OverflowStrip::horizontal(id_salt).overflow_icon("»").show(
+                    ui,
+                    entries.len(),
+                    |ui, i| match &entries[i] {
+                        ToolbarEntry::Action(action_id) => {
+                            let Some(action) = find_action(items, action_id) else {
+                                return;
+                            };
+                            let response = action_button(ui, action, button_style);
+                            if customizable {
+                                response.context_menu(|ui| context_menu(ui, id));
+                            }
+                            if response.clicked() {
+                                clicked = Some(action.id.clone());
+                                ui.close_menu();
+                            }
+                        }
+                        ToolbarEntry::Separator => {
+                            ui.separator();
+                        }
+                    },
+                );
+            }
+        });
+
+        if let Some(new_entries) = new_entries {
+            layout.customize(new_entries, items);
+            layout.store(ui, id);
+        }
+
+        let response = background | inner_response.response;
+        if customizable {
+            response.context_menu(|ui| context_menu(ui, id));
+        }
+
+        ToolbarResponse { clicked, response }
+    }
+}
+
+fn customizing_id(id: Id) -> Id {
+    id.with("customizing")
+}
+
+fn action_button(ui: &mut Ui, action: &ToolbarAction, style: ToolbarButtonStyle) -> Response {
+    let response = ui.add_enabled(
+        action.enabled,
+        Button::new(action.text(style)).selected(action.selected),
+    );
+    if let Some(tooltip) = &action.tooltip {
+        response.on_hover_text(tooltip)
+    } else if style == ToolbarButtonStyle::IconOnly {
+        response.on_hover_text(&action.label)
+    } else {
+        response
+    }
+}
+
+fn context_menu(ui: &mut Ui, id: Id) {
+    if ui.button("Customize toolbar…").clicked() {
+        ui.data_mut(|d| d.insert_temp(customizing_id(id), true));
+        ui.close_menu();
+    }
+    if ui.button("Reset toolbar").clicked() {
+        ToolbarLayout::default().store(ui, id);
+        ui.close_menu();
+    }
+}
+
+/// Show the entries of the toolbar as draggable, plus a palette of entries that can be added.
+///
+/// Returns the new entries if the user changed them.
+fn customize_ui(
+    ui: &mut Ui,
+    id: Id,
+    items: &[ToolbarItem],
+    entries: &[ToolbarEntry],
+    style: ToolbarButtonStyle,
+) -> Option<Vec<ToolbarEntry>> {
+    let drag = DragAndDrop::payload::<ToolbarDrag>(ui.ctx()).filter(|drag| drag.toolbar_id == id);
+    let pointer_pos = ui.ctx().pointer_interact_pos();
+    let released = ui.input(|i| i.pointer.any_released());
+
+    let show_entry = |ui: &mut Ui, entry: &ToolbarEntry| match entry {
+        ToolbarEntry::Action(action_id) => {
+            if let Some(action) = find_action(items, action_id) {
+                ui.add(Button::new(action.text(style)).sense(Sense::hover()));
+            }
+        }
+        ToolbarEntry::Separator => {
+            ui.add(Button::new("|").sense(Sense::hover()))
+                .on_hover_text("Separator");
+        }
+    };
+
+    // The toolbar itself:
+    let mut entry_rects: Vec<Rect> = Vec::with_capacity(entries.len());
+    let toolbar_rect = ui
+        .horizontal_wrapped(|ui| {
+            for (i, entry) in entries.iter().enumerate() {
+                let payload = ToolbarDrag {
+                    toolbar_id: id,
+                    from_index: Some(i),
+                    entry: entry.clone(),
+                };
+                let response = ui
+                    .dnd_drag_source(id.with(("entry", i)), payload, |ui| show_entry(ui, entry))
+                    .response;
+                entry_rects.push(response.rect);
+            }
+            if entries.is_empty() {
+                ui.weak("Drag actions here");
+            }
+        })
+        .response
+        .rect;
+
+    // Where a dragged entry would be inserted:
+    let insert_index = pointer_pos
+        .filter(|pos| drag.is_some() && toolbar_rect.expand(4.0).contains(*pos))
+        .map(|pos| {
+            entry_rects
+                .iter()
+                .position(|rect| rect.y_range().contains(pos.y) && pos.x < rect.center().x)
+                .unwrap_or_else(|| {
+                    // After the last entry on the row of the pointer, or at the very end:
+                    entry_rects
+                        .iter()
+                        .rposition(|rect| rect.y_range().contains(pos.y))
+                        .map_or(entries.len(), |i| i + 1)
+                })
+        });
+
+    if let Some(insert_index) = insert_index {
+        let x = entry_rects.get(insert_index).map_or_else(
+            || {
+                entry_rects
+                    .last()
+                    .map_or(toolbar_rect.left(), |rect| rect.right())
+            },
+            |rect| rect.left(),
+        ) - 0.5 * ui.spacing().item_spacing.x;
+        let y_range = entry_rects
+            .get(insert_index)
+            .or(entry_rects.last())
+            .map_or(toolbar_rect.y_range(), |rect| rect.y_range());
+        ui.painter().vline(
+            x,
+            y_range,
+            Stroke::new(2.0, ui.visuals().selection.stroke.color),
+        );
+    }
+
+    // The palette of entries that can be added, which is also where entries are dropped to remove them:
+    ui.separator();
+    let palette_rect = ui
+        .horizontal_wrapped(|ui| {
+            ui.label("Drag to add:");
+            let unused = items.iter().filter_map(|item| match item {
+                ToolbarItem::Action(action)
+                    if !entries.contains(&ToolbarEntry::Action(action.id.clone())) =>
+                {
+                    Some(ToolbarEntry::Action(action.id.clone()))
+                }
+                _ => None,
+            });
+            for (i, entry) in unused.chain([ToolbarEntry::Separator]).enumerate() {
+                let payload = ToolbarDrag {
+                    toolbar_id: id,
+                    from_index: None,
+                    entry: entry.clone(),
+                };
+                ui.dnd_drag_source(id.with(("palette", i)), payload, |ui| {
+                    show_entry(ui, &entry);
+                });
+            }
+        })
+        .response
+        .rect;
+
+    let mut new_entries = None;
+
+    ui.horizontal(|ui| {
+        if ui.button("Done").clicked() {
+            ui.data_mut(|d| d.insert_temp(customizing_id(id), false));
+        }
+        if ui.button("Reset").clicked() {
+            ToolbarLayout::default().store(ui, id);
+        }
+    });
+
+    if released {
+        if let (Some(drag), Some(pos)) = (drag, pointer_pos) {
+            let mut entries = entries.to_vec();
+            if let Some(insert_index) = insert_index {
+                let mut insert_index = insert_index;
+                if let Some(from_index) = drag.from_index {
+                    entries.remove(from_index);
+                    if from_index < insert_index {
+                        insert_index -= 1;
+                    }
+                }
+                entries.insert(insert_index, drag.entry.clone());
+                new_entries = Some(entries);
+            } else if let Some(from_index) = drag.from_index {
+                if palette_rect.contains(pos) {
+                    entries.remove(from_index);
+                    new_entries = Some(entries);
+                }
+            }
+            DragAndDrop::clear_payload(ui.ctx());
+        }
+    }
+
+    new_entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn customized_layout() {
+        let action = |id: &str| ToolbarItem::Action(ToolbarAction::new(id, "", id));
+        let entry = |id: &str| ToolbarEntry::Action(id.to_owned());
+
+        let items = [
+            action("a"),
+            action("b"),
+            ToolbarItem::Separator,
+            action("c"),
+        ];
+        let mut layout = ToolbarLayout::default();
+        assert_eq!(
+            layout.entries(&items),
+            vec![entry("a"), entry("b"), ToolbarEntry::Separator, entry("c")]
+        );
+
+        layout.customize(vec![entry("c"), entry("a")], &items);
+        assert_eq!(layout.removed, vec!["b".to_owned()]);
+
+        // "b" stays removed, "d" is new, and "c" is no longer available:
+        let items = [action("a"), action("b"), action("d")];
+        assert_eq!(layout.entries(&items), vec![entry("a"), entry("d")]);
+    }
+}
@@ -4,8 +4,8 @@ use std::sync::Arc;
 
 use crate::collapsing_header::CollapsingState;
 use crate::{
-    Align, Align2, Context, CursorIcon, Id, InnerResponse, LayerId, NumExt, Order, Response, Sense,
-    TextStyle, Ui, UiKind, Vec2b, WidgetInfo, WidgetRect, WidgetText, WidgetType,
+    Align, Align2, Context, CursorIcon, Id, InnerResponse, Key, LayerId, Modifiers, NumExt, Order,
+    Response, Sense, TextStyle, Ui, UiKind, Vec2b, WidgetInfo, WidgetRect, WidgetText, WidgetType,
 };
 use epaint::{emath, pos2, vec2, Galley, Pos2, Rect, RectShape, Rounding, Shape, Stroke, Vec2};
 
@@ -33,6 +33,10 @@ use super::{area, resize, Area, Frame, Resize, ScrollArea};
 ///
 /// Note that this is NOT a native OS window.
 /// To create a new native OS window, use [`crate::Context::show_viewport_deferred`].
+///
+/// A movable window can also be moved and resized with the keyboard,
+/// either from the context menu of the title bar or by pressing Alt+F7 while
+/// a widget in the window has keyboard focus. See [`Area::start_keyboard_move`].
 #[must_use = "You should call .show()"]
 pub struct Window<'open> {
     title: WidgetText,
@@ -114,6 +118,15 @@ impl<'open> Window<'open> {
         self
     }
 
+    /// Snap to edges and a grid while being dragged, as set up in [`crate::style::WindowSnap`]?
+    ///
+    /// Default: `true` (but the snapping in the style is off by default).
+    #[inline]
+    pub fn snap(mut self, snap: bool) -> Self {
+        self.area = self.area.snap(snap);
+        self
+    }
+
     /// `order(Order::Foreground)` for a Window that should always be on top
     #[inline]
     pub fn order(mut self, order: Order) -> Self {
@@ -475,6 +488,14 @@ impl<'open> Window<'open> {
         let resize = resize.resizable(false); // We resize it manually
         let mut resize = resize.id(resize_id);
 
+        let keyboard_movable = area.is_movable();
+        if keyboard_movable
+            && focus_is_in_layer(ctx, area_layer_id)
+            && ctx.input_mut(|i| i.consume_key(Modifiers::ALT, Key::F7))
+        {
+            Area::start_keyboard_move(ctx, area_id);
+        }
+
         let on_top = Some(area_layer_id) == ctx.top_layer_id();
         let mut area = area.begin(ctx);
 
@@ -520,6 +541,10 @@ impl<'open> Window<'open> {
             resize_id,
         );
 
+        if let Some(keyboard_move) = area.keyboard_move() {
+            keyboard_resize(ctx, keyboard_move, &possible, &resize, resize_id);
+        }
+
         let mut area_content_ui = area.content_ui(ctx);
         if is_open {
             // `Area` already takes care of fade-in animations,
@@ -623,6 +648,7 @@ impl<'open> Window<'open> {
                         open,
                         &mut collapsing,
                         collapsible,
+                        keyboard_movable.then_some((area_id, possible.resizable())),
                     );
                 }
 
@@ -810,6 +836,47 @@ fn resize_response(
     ctx.memory_mut(|mem| mem.areas_mut().move_to_top(area_layer_id));
 }
 
+/// Resize the window with Shift + arrow keys, see [`Area::start_keyboard_move`].
+fn keyboard_resize(
+    ctx: &Context,
+    mut keyboard_move: area::KeyboardMove,
+    possible: &PossibleInteractions,
+    resize: &Resize,
+    resize_id: Id,
+) {
+    let Some(mut state) = resize::State::load(ctx, resize_id) else {
+        return;
+    };
+    let start_size = *keyboard_move.start_size.get_or_insert(state.desired_size);
+
+    let mut size_delta = keyboard_move.size_delta;
+    if !(possible.resize_left || possible.resize_right) {
+        size_delta.x = 0.0;
+    }
+    if !(possible.resize_top || possible.resize_bottom) {
+        size_delta.y = 0.0;
+    }
+
+    let new_size = (start_size + size_delta)
+        .at_least(resize.min_size)
+        .at_most(resize.max_size);
+    // Don't let the user wind up the size beyond the limits:
+    keyboard_move.size_delta = new_size - start_size;
+
+    if size_delta != Vec2::ZERO || keyboard_move.cancelled {
+        state.requested_size = Some(new_size);
+        state.store(ctx, resize_id);
+    }
+    keyboard_move.store(ctx);
+}
+
+/// Does the widget with keyboard focus belong to this layer?
+fn focus_is_in_layer(ctx: &Context, layer_id: LayerId) -> bool {
+    ctx.memory(|mem| mem.focused())
+        .and_then(|id| ctx.read_response(id))
+        .is_some_and(|response| response.layer_id == layer_id)
+}
+
 fn move_and_resize_window(ctx: &Context, interaction: &ResizeInteraction) -> Option<Rect> {
     if !interaction.any_dragged() {
         return None;
@@ -1121,6 +1188,9 @@ impl TitleBar {
     ///   title if `collapsible` is `true`
     /// - `collapsible`: if `true`, double click on the title bar will be handled for a change
     ///   of `collapsing` state
+    /// - `keyboard_move`: if `Some((area_id, resizable))`, the context menu of the title bar
+    ///   can start moving (and resizing) the window with the keyboard
+    #[allow(clippy::too_many_arguments)]
     fn ui(
         mut self,
         ui: &mut Ui,
@@ -1129,6 +1199,7 @@ impl TitleBar {
         open: Option<&mut bool>,
         collapsing: &mut CollapsingState,
         collapsible: bool,
+        keyboard_move: Option<(Id, bool)>,
     ) {
         if let Some(content_response) = &content_response {
             // Now we know how large we got to be:
@@ -1167,13 +1238,24 @@ impl TitleBar {
         // Don't cover the close- and collapse buttons:
         let double_click_rect = self.rect.shrink2(vec2(32.0, 0.0));
 
-        if ui
-            .interact(double_click_rect, self.id, Sense::click())
-            .double_clicked()
-            && collapsible
-        {
+        let response = ui.interact(double_click_rect, self.id, Sense::click());
+        if response.double_clicked() && collapsible {
             collapsing.toggle(ui);
         }
+
+        if let Some((area_id, resizable)) = keyboard_move {
+            response.context_menu(|ui| {
+                let text = if resizable {
+                    "Move and resize with keyboard"
+                } else {
+                    "Move with keyboard"
+                };
+                if ui.button(text).clicked() {
+                    Area::start_keyboard_move(ui.ctx(), area_id);
+                    ui.close_menu();
+                }
+            });
+        }
     }
 
     /// Paints the "Close" button at the right side of the title bar
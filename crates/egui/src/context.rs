@@ -11,15 +11,16 @@ use epaint::{
     tessellator,
     text::{FontInsert, FontPriority, Fonts},
     util::OrderedFloat,
-    vec2, ClippedPrimitive, ClippedShape, Color32, ImageData, ImageDelta, Pos2, Rect,
+    vec2, ClippedPrimitive, ClippedShape, Color32, ColorImage, ImageData, ImageDelta, Pos2, Rect,
     TessellationOptions, TextureAtlas, TextureId, Vec2,
 };
 
 use crate::{
     animation_manager::AnimationManager,
     containers,
-    data::output::PlatformOutput,
+    data::output::{ClipboardData, OpenUrlPolicy, PlatformOutput},
     epaint, hit_test,
+    input_filter::{InputFilter, InputFilterId, InputFilters},
     input_state::{InputState, MultiTouchInfo, PointerEvent},
     interaction,
     layers::GraphicLayers,
@@ -33,11 +34,12 @@ use crate::{
     resize, scroll_area,
     util::IdTypeMap,
     viewport::ViewportClass,
-    Align2, CursorIcon, DeferredViewportUiCallback, FontDefinitions, Grid, Id, ImmediateViewport,
-    ImmediateViewportRendererCallback, Key, KeyboardShortcut, Label, LayerId, Memory,
-    ModifierNames, NumExt, Order, Painter, RawInput, Response, RichText, ScrollArea, Sense, Style,
-    TextStyle, TextureHandle, TextureOptions, Ui, ViewportBuilder, ViewportCommand, ViewportId,
-    ViewportIdMap, ViewportIdPair, ViewportIdSet, ViewportOutput, Widget, WidgetRect, WidgetText,
+    Align2, Area, CursorIcon, DeferredViewportUiCallback, FontDefinitions, Grid, Id,
+    ImmediateViewport, ImmediateViewportRendererCallback, Key, KeyboardShortcut, Label, LayerId,
+    Memory, ModifierNames, NumExt, Order, Painter, RawInput, Response, RichText, ScrollArea, Sense,
+    Style, TextStyle, TextureHandle, TextureOptions, Ui, ViewportBuilder, ViewportCommand,
+    ViewportId, ViewportIdMap, ViewportIdPair, ViewportIdSet, ViewportOutput, Widget, WidgetRect,
+    WidgetText,
 };
 
 #[cfg(feature = "accesskit")]
@@ -105,6 +107,8 @@ struct NamedContextCallback {
 struct Plugins {
     pub on_begin_pass: Vec<NamedContextCallback>,
     pub on_end_pass: Vec<NamedContextCallback>,
+    pub input_filters: InputFilters,
+    pub open_url_policy: Option<OpenUrlPolicy>,
 }
 
 impl Plugins {
@@ -393,6 +397,34 @@ impl ViewportRepaintInfo {
 
 // ----------------------------------------------------------------------------
 
+/// Fading from the old to the new [`crate::Visuals`], see [`Style::theme_transition_time`].
+struct ThemeTransition {
+    from: crate::Visuals,
+    to: crate::Visuals,
+    start_time: f64,
+    duration: f32,
+
+    /// The in-between style to use this pass, or `None` when the transition is done.
+    style: Option<Arc<Style>>,
+}
+
+impl ThemeTransition {
+    fn visuals_at(&self, time: f64) -> crate::Visuals {
+        let t = if self.duration > 0.0 {
+            ((time - self.start_time) as f32 / self.duration).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+        if t < 1.0 {
+            self.from.lerp(&self.to, emath::easing::cubic_in_out(t))
+        } else {
+            self.to.clone()
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 #[derive(Default)]
 struct ContextImpl {
     /// Since we could have multiple viewports across multiple monitors with
@@ -406,6 +438,9 @@ struct ContextImpl {
     memory: Memory,
     animation_manager: AnimationManager,
 
+    /// `None` before the first pass.
+    theme_transition: Option<ThemeTransition>,
+
     plugins: Plugins,
 
     /// All viewports share the same texture manager and texture namespace.
@@ -419,6 +454,12 @@ struct ContextImpl {
     /// Set during the pass, becomes active at the start of the next pass.
     new_zoom_factor: Option<f32>,
 
+    /// Set by [`Context::set_zoom_factor_of`], overriding [`Options::zoom_factor`] for those viewports.
+    zoom_factors: ViewportIdMap<f32>,
+
+    /// Set during a pass, becomes active at the start of the next pass of that viewport.
+    new_zoom_factors: ViewportIdMap<f32>,
+
     os: OperatingSystem,
 
     /// How deeply nested are we?
@@ -436,6 +477,12 @@ struct ContextImpl {
 
     embed_viewports: bool,
 
+    /// Set by [`Context::set_distance_field_text`].
+    distance_field_text: bool,
+
+    /// Set by [`Context::set_max_repaint_rate_of`].
+    max_repaint_rates: ViewportIdMap<f32>,
+
     #[cfg(feature = "accesskit")]
     is_accesskit_enabled: bool,
 
@@ -457,28 +504,36 @@ impl ContextImpl {
 
         self.begin_pass_repaint_logic(viewport_id);
 
-        let viewport = self.viewports.entry(viewport_id).or_default();
-
+        let old_zoom_factor = self.zoom_factor_of(viewport_id);
         if is_outermost_viewport {
             if let Some(new_zoom_factor) = self.new_zoom_factor.take() {
-                let ratio = self.memory.options.zoom_factor / new_zoom_factor;
                 self.memory.options.zoom_factor = new_zoom_factor;
-
-                let input = &viewport.input;
-                // This is a bit hacky, but is required to avoid jitter:
-                let mut rect = input.screen_rect;
-                rect.min = (ratio * rect.min.to_vec2()).to_pos2();
-                rect.max = (ratio * rect.max.to_vec2()).to_pos2();
-                new_raw_input.screen_rect = Some(rect);
-                // We should really scale everything else in the input too,
-                // but the `screen_rect` is the most important part.
             }
         }
+        if let Some(new_zoom_factor) = self.new_zoom_factors.remove(&viewport_id) {
+            self.zoom_factors.insert(viewport_id, new_zoom_factor);
+        }
+        let zoom_factor = self.zoom_factor_of(viewport_id);
+
+        let viewport = self.viewports.entry(viewport_id).or_default();
+
+        if zoom_factor != old_zoom_factor {
+            let ratio = old_zoom_factor / zoom_factor;
+
+            let input = &viewport.input;
+            // This is a bit hacky, but is required to avoid jitter:
+            let mut rect = input.screen_rect;
+            rect.min = (ratio * rect.min.to_vec2()).to_pos2();
+            rect.max = (ratio * rect.max.to_vec2()).to_pos2();
+            new_raw_input.screen_rect = Some(rect);
+            // We should really scale everything else in the input too,
+            // but the `screen_rect` is the most important part.
+        }
         let native_pixels_per_point = new_raw_input
             .viewport()
             .native_pixels_per_point
             .unwrap_or(1.0);
-        let pixels_per_point = self.memory.options.zoom_factor * native_pixels_per_point;
+        let pixels_per_point = zoom_factor * native_pixels_per_point;
 
         let all_viewport_ids: ViewportIdSet = self.all_viewport_ids();
 
@@ -564,6 +619,47 @@ impl ContextImpl {
         }
 
         self.update_fonts_mut();
+        self.update_theme_transition();
+    }
+
+    /// Start a new [`ThemeTransition`] if the visuals changed, and advance the current one.
+    fn update_theme_transition(&mut self) {
+        let time = self.viewport().input.time;
+        let style = self.memory.options.style().clone();
+
+        let transition = match self.theme_transition.take() {
+            Some(transition) if transition.to == style.visuals => transition,
+            Some(transition) => ThemeTransition {
+                // Start from what is shown right now, in case the previous transition is still going:
+                from: transition.visuals_at(time),
+                to: style.visuals.clone(),
+                start_time: time,
+                duration: style.theme_transition_time,
+                style: None,
+            },
+            None => ThemeTransition {
+                from: style.visuals.clone(),
+                to: style.visuals.clone(),
+                start_time: time,
+                duration: 0.0,
+                style: None,
+            },
+        };
+
+        let visuals = transition.visuals_at(time);
+        let in_between_style = (visuals != transition.to).then(|| {
+            let mut in_between_style = (*style).clone();
+            in_between_style.visuals = visuals;
+            Arc::new(in_between_style)
+        });
+        if in_between_style.is_some() {
+            self.request_repaint(self.viewport_id(), RepaintCause::new());
+        }
+
+        self.theme_transition = Some(ThemeTransition {
+            style: in_between_style,
+            ..transition
+        });
     }
 
     /// Load fonts unless already loaded.
@@ -606,6 +702,13 @@ impl ContextImpl {
 
         let mut is_new = false;
 
+        // The first fonts get all their glyphs at once, so the app starts out with all its text.
+        let max_new_glyphs_per_pass = if self.fonts.is_empty() {
+            None
+        } else {
+            self.memory.options.max_new_glyphs_per_pass
+        };
+
         let fonts = self
             .fonts
             .entry(pixels_per_point.into())
@@ -624,10 +727,13 @@ impl ContextImpl {
 
         {
             crate::profile_scope!("Fonts::begin_pass");
+            fonts.set_max_new_glyphs_per_pass(max_new_glyphs_per_pass);
+            fonts.set_distance_field_text(self.distance_field_text);
             fonts.begin_pass(pixels_per_point, max_texture_side);
         }
 
-        if is_new && self.memory.options.preload_font_glyphs {
+        // Preloading would use up the budget for new glyphs on glyphs that may not be needed:
+        if is_new && self.memory.options.preload_font_glyphs && max_new_glyphs_per_pass.is_none() {
             crate::profile_scope!("preload_font_glyphs");
             // Preload the most common characters for the most common fonts.
             // This is not very important to do, but may save a few GPU operations.
@@ -672,6 +778,13 @@ impl ContextImpl {
             .unwrap_or(&ViewportId::ROOT)
     }
 
+    fn zoom_factor_of(&self, viewport_id: ViewportId) -> f32 {
+        self.zoom_factors
+            .get(&viewport_id)
+            .copied()
+            .unwrap_or(self.memory.options.zoom_factor)
+    }
+
     fn all_viewport_ids(&self) -> ViewportIdSet {
         self.viewports
             .keys()
@@ -769,6 +882,8 @@ impl Default for Context {
         crate::debug_text::register(&ctx);
         crate::text_selection::LabelSelectionState::register(&ctx);
         crate::DragAndDrop::register(&ctx);
+        crate::ContextHelp::register(&ctx);
+        crate::Toasts::register(&ctx);
 
         ctx
     }
@@ -883,9 +998,14 @@ impl Context {
     /// let full_output = ctx.end_pass();
     /// // handle full_output
     /// ```
-    pub fn begin_pass(&self, new_input: RawInput) {
+    pub fn begin_pass(&self, mut new_input: RawInput) {
         crate::profile_function!();
 
+        let input_filters = self.read(|ctx| ctx.plugins.input_filters.clone());
+        if !input_filters.is_empty() {
+            input_filters.apply(self, &mut new_input);
+        }
+
         self.write(|ctx| ctx.begin_pass(new_input));
 
         // Plugins run just after the pass starts:
@@ -1341,6 +1461,16 @@ impl Context {
             if pointer_pressed_elsewhere && memory.has_focus(id) {
                 memory.surrender_focus(id);
             }
+
+            if crate::ContextHelp::suppresses_interaction(&mut memory.data) {
+                // In "What's this?" mode, clicks and drags are for picking a widget to get help on:
+                res.clicked = false;
+                res.fake_primary_click = false;
+                res.long_touched = false;
+                res.drag_started = false;
+                res.dragged = false;
+                res.drag_stopped = false;
+            }
         });
 
         res
@@ -1348,20 +1478,40 @@ impl Context {
 
     /// This is called by [`Response::widget_info`], but can also be called directly.
     ///
-    /// With some debug flags it will store the widget info in [`crate::WidgetRects`] for later display.
+    /// With some debug flags, or with [`crate::Options::record_ui_tree`],
+    /// it will store the widget info in [`crate::WidgetRects`] for later display.
     #[inline]
     pub fn register_widget_info(&self, id: Id, make_info: impl Fn() -> crate::WidgetInfo) {
-        #[cfg(debug_assertions)]
         self.write(|ctx| {
-            if ctx.memory.options.style().debug.show_interactive_widgets {
+            let options = &ctx.memory.options;
+            let show_interactive_widgets =
+                cfg!(debug_assertions) && options.style().debug.show_interactive_widgets;
+            if show_interactive_widgets || options.record_ui_tree {
                 ctx.viewport().this_pass.widgets.set_info(id, make_info());
             }
         });
+    }
 
-        #[cfg(not(debug_assertions))]
-        {
-            _ = (self, id, make_info);
-        }
+    /// Remember that the widget `id` is inside the [`Ui`] with id `parent`, if [`crate::Options::record_ui_tree`] is on.
+    ///
+    /// If `ui_stack` is `Some`, the widget is itself a [`Ui`] with that stack.
+    pub(crate) fn register_in_ui_tree(
+        &self,
+        id: Id,
+        parent: Option<Id>,
+        ui_stack: Option<&crate::UiStack>,
+    ) {
+        self.write(|ctx| {
+            if ctx.memory.options.record_ui_tree {
+                let widgets = &mut ctx.viewport().this_pass.widgets;
+                if let Some(parent) = parent {
+                    widgets.set_parent(id, parent);
+                }
+                if let Some(ui_stack) = ui_stack {
+                    widgets.set_ui_kind(id, ui_stack.kind());
+                }
+            }
+        });
     }
 
     /// Get a full-screen painter for a new or existing layer
@@ -1393,6 +1543,20 @@ impl Context {
         crate::debug_text::print(self, text);
     }
 
+    /// Show a notification that pops up in a corner of the screen, and goes away by itself.
+    ///
+    /// ```
+    /// # let ctx = egui::Context::default();
+    /// ctx.toast(egui::Toast::success("Saved"));
+    /// ctx.toast(egui::Toast::error("Failed to connect").duration(None));
+    /// ```
+    ///
+    /// Returns an id you can use to dismiss the toast early with [`crate::Toasts::dismiss`].
+    /// See [`crate::Toasts`] for more.
+    pub fn toast(&self, toast: crate::Toast) -> Id {
+        crate::Toasts::add(self, toast)
+    }
+
     /// What operating system are we running on?
     ///
     /// When compiling natively, this is
@@ -1425,6 +1589,8 @@ impl Context {
 
     /// Open an URL in a browser.
     ///
+    /// The request goes through the policy set with [`Self::set_open_url_policy`], if any.
+    ///
     /// Equivalent to:
     /// ```
     /// # let ctx = egui::Context::default();
@@ -1438,14 +1604,66 @@ impl Context {
     /// Copy the given text to the system clipboard.
     ///
     /// Empty strings are ignored.
+    /// This replaces anything copied with [`Self::copy_data`] earlier in the same pass.
     ///
-    /// Equivalent to:
+    /// Similar to:
     /// ```
     /// # let ctx = egui::Context::default();
     /// ctx.output_mut(|o| o.copied_text = "Copy this".to_owned());
     /// ```
     pub fn copy_text(&self, text: String) {
-        self.output_mut(|o| o.copied_text = text);
+        self.output_mut(|o| {
+            if !text.is_empty() {
+                o.copied_data = None;
+            }
+            o.copied_text = text;
+        });
+    }
+
+    /// Copy rich content (an image, HTML, or a list of files) to the system clipboard.
+    ///
+    /// This replaces anything copied with [`Self::copy_text`] earlier in the same pass.
+    pub fn copy_data(&self, data: ClipboardData) {
+        self.output_mut(|o| {
+            o.copied_text.clear();
+            o.copied_data = Some(data);
+        });
+    }
+
+    /// Copy the given image to the system clipboard.
+    ///
+    /// Shorthand for [`Self::copy_data`] with [`ClipboardData::Image`].
+    pub fn copy_image(&self, image: impl Into<Arc<ColorImage>>) {
+        self.copy_data(ClipboardData::Image(image.into()));
+    }
+
+    /// Did the user press the shortcut of this action in [`crate::Memory::shortcuts`]?
+    ///
+    /// The key press is consumed, so it won't also reach e.g. a focused [`crate::TextEdit`],
+    /// but asking again during the same pass gives the same answer.
+    ///
+    /// Actions with more specific shortcuts take precedence:
+    /// if `Cmd+Shift+S` is bound to "Save as…", pressing it won't trigger the `Cmd+S` of "Save".
+    ///
+    /// Returns `false` for actions that haven't been registered with [`crate::Shortcuts::register`].
+    pub fn action_triggered(&self, action: &str) -> bool {
+        let pass_nr = self.cumulative_pass_nr();
+        if let Some(triggered) = self.memory(|mem| mem.shortcuts.cached_trigger(action, pass_nr)) {
+            return triggered;
+        }
+
+        let modifiers = self.input(|i| i.modifiers);
+        let shortcut = self.memory(|mem| {
+            let shortcuts = &mem.shortcuts;
+            shortcuts
+                .shortcut(action)
+                .filter(|shortcut| !shortcuts.is_shadowed(shortcut, modifiers))
+        });
+        let triggered =
+            shortcut.is_some_and(|shortcut| self.input_mut(|i| i.consume_shortcut(&shortcut)));
+
+        self.memory_mut(|mem| mem.shortcuts.cache_trigger(action, pass_nr, triggered));
+        triggered
     }
 
     /// Format the given shortcut in a human-readable way (e.g. `Ctrl+Shift+X`).
@@ -1637,6 +1855,54 @@ impl Context {
         self.read(|ctx| ctx.has_requested_repaint(viewport_id))
     }
 
+    /// Limit how often the given viewport is repainted, in frames per second.
+    ///
+    /// This is useful for e.g. background tool windows that don't need to run at full frame rate.
+    /// Repaint requests that come in faster than this are merged into one.
+    ///
+    /// The viewport that has keyboard focus is never throttled.
+    ///
+    /// This is only a hint to the integration (`eframe` respects it on native).
+    /// Use [`f32::INFINITY`] to remove the limit, which is the default.
+    pub fn set_max_repaint_rate_of(&self, viewport_id: ViewportId, hz: f32) {
+        debug_assert!(0.0 < hz, "Expected a positive repaint rate, got {hz}");
+        self.write(|ctx| {
+            if hz.is_finite() {
+                ctx.max_repaint_rates.insert(viewport_id, hz);
+            } else {
+                ctx.max_repaint_rates.remove(&viewport_id);
+            }
+        });
+    }
+
+    /// The repaint rate limit set with [`Self::set_max_repaint_rate_of`].
+    ///
+    /// [`f32::INFINITY`] if there is no limit.
+    pub fn max_repaint_rate_of(&self, viewport_id: ViewportId) -> f32 {
+        self.read(|ctx| ctx.max_repaint_rates.get(&viewport_id).copied())
+            .unwrap_or(f32::INFINITY)
+    }
+
+    /// For integrations: the shortest time allowed between two repaints of the given viewport,
+    /// taking [`Self::set_max_repaint_rate_of`] and keyboard focus into account.
+    ///
+    /// `None` if the viewport may be repainted as often as it likes.
+    pub fn min_repaint_interval_of(&self, viewport_id: ViewportId) -> Option<Duration> {
+        let hz = self.max_repaint_rate_of(viewport_id);
+        if !hz.is_finite() || hz <= 0.0 {
+            return None;
+        }
+        let is_focused = self.read(|ctx| {
+            ctx.viewports
+                .get(&viewport_id)
+                .and_then(|viewport| viewport.input.viewport().focused)
+        });
+        if is_focused == Some(true) {
+            return None;
+        }
+        Some(Duration::from_secs_f32(1.0 / hz))
+    }
+
     /// Why are we repainting?
     ///
     /// This can be helpful in debugging why egui is constantly repainting.
@@ -1740,6 +2006,50 @@ impl Context {
         };
         self.write(|ctx| ctx.plugins.on_end_pass.push(named_cb));
     }
+
+    /// Look at, and maybe change, the input of each pass before any widget sees it.
+    ///
+    /// See [`InputFilter`] for more.
+    pub fn add_input_filter(&self, filter: InputFilter) -> InputFilterId {
+        self.write(|ctx| ctx.plugins.input_filters.add(filter))
+    }
+
+    /// Remove a filter added with [`Self::add_input_filter`].
+    ///
+    /// Returns `false` if there was no such filter.
+    pub fn remove_input_filter(&self, id: InputFilterId) -> bool {
+        self.write(|ctx| ctx.plugins.input_filters.remove(id))
+    }
+
+    /// Decide what happens to every request to open a URL,
+    /// e.g. from [`crate::Hyperlink`], a linkified [`Label`] or [`Self::open_url`].
+    ///
+    /// The policy is applied to [`PlatformOutput::open_url`] at the end of each pass,
+    /// so it can e.g. ask the user for confirmation, only allow some domains,
+    /// or open links itself and return `None`.
+    /// This is important when showing text you don't trust, like in a chat client.
+    ///
+    /// ```
+    /// # let ctx = egui::Context::default();
+    /// ctx.set_open_url_policy(Some(std::sync::Arc::new(|_ctx, open_url| {
+    ///     open_url.url.starts_with("https://").then_some(open_url)
+    /// })));
+    /// ```
+    ///
+    /// Use `None` to open all URLs (the default).
+    pub fn set_open_url_policy(&self, policy: Option<OpenUrlPolicy>) {
+        self.write(|ctx| ctx.plugins.open_url_policy = policy);
+    }
+
+    fn apply_open_url_policy(&self) {
+        let Some(policy) = self.read(|ctx| ctx.plugins.open_url_policy.clone()) else {
+            return;
+        };
+        if let Some(open_url) = self.output_mut(|o| o.open_url.take()) {
+            let open_url = policy(self, open_url);
+            self.output_mut(|o| o.open_url = open_url);
+        }
+    }
 }
 
 impl Context {
@@ -1829,8 +2139,17 @@ impl Context {
     }
 
     /// The currently active [`Style`] used by all subsequent windows, panels etc.
+    ///
+    /// During a theme transition (see [`Style::theme_transition_time`]) this has the in-between visuals.
+    /// Use [`Self::style_mut`] or [`Self::set_visuals`] to change the style,
+    /// rather than modifying and setting a copy of this.
     pub fn style(&self) -> Arc<Style> {
-        self.options(|opt| opt.style().clone())
+        self.read(|ctx| {
+            ctx.theme_transition
+                .as_ref()
+                .and_then(|transition| transition.style.clone())
+                .unwrap_or_else(|| ctx.memory.options.style().clone())
+        })
     }
 
     /// Mutate the currently active [`Style`] used by all subsequent windows, panels etc.
@@ -1949,10 +2268,13 @@ impl Context {
     /// Set the number of physical pixels for each logical point.
     /// Will become active at the start of the next pass.
     ///
-    /// This will actually translate to a call to [`Self::set_zoom_factor`].
+    /// This will actually translate to a call to [`Self::set_zoom_factor`],
+    /// or to [`Self::set_zoom_factor_of`] if the current viewport has its own zoom factor.
     pub fn set_pixels_per_point(&self, pixels_per_point: f32) {
         if pixels_per_point != self.pixels_per_point() {
-            self.set_zoom_factor(pixels_per_point / self.native_pixels_per_point().unwrap_or(1.0));
+            self.set_current_zoom_factor(
+                pixels_per_point / self.native_pixels_per_point().unwrap_or(1.0),
+            );
         }
     }
 
@@ -1965,16 +2287,26 @@ impl Context {
         self.input(|i| i.viewport().native_pixels_per_point)
     }
 
-    /// Global zoom factor of the UI.
+    /// Zoom factor of the UI in the current viewport.
     ///
     /// This is used to calculate the `pixels_per_point`
     /// for the UI as `pixels_per_point = zoom_factor * native_pixels_per_point`.
     ///
+    /// This is the global [`Options::zoom_factor`],
+    /// unless the viewport has its own zoom factor (see [`Self::set_zoom_factor_of`]).
+    ///
     /// The default is 1.0.
     /// Make larger to make everything larger.
     #[inline(always)]
     pub fn zoom_factor(&self) -> f32 {
-        self.options(|o| o.zoom_factor)
+        self.zoom_factor_of(self.viewport_id())
+    }
+
+    /// Zoom factor of the UI in the given viewport.
+    ///
+    /// See [`Self::zoom_factor`].
+    pub fn zoom_factor_of(&self, viewport_id: ViewportId) -> f32 {
+        self.read(|ctx| ctx.zoom_factor_of(viewport_id))
     }
 
     /// Sets zoom factor of the UI.
@@ -1988,6 +2320,9 @@ impl Context {
     /// The default is 1.0.
     /// Make larger to make everything larger.
     ///
+    /// This applies to all viewports,
+    /// except those with their own zoom factor (see [`Self::set_zoom_factor_of`]).
+    ///
     /// It is better to call this than modifying
     /// [`Options::zoom_factor`].
     #[inline(always)]
@@ -2003,6 +2338,32 @@ impl Context {
         });
     }
 
+    /// Give a viewport its own zoom factor, independent of the global one set with [`Self::set_zoom_factor`].
+    /// Will become active at the start of the next pass of that viewport.
+    ///
+    /// For instance, an attached tool palette can stay small while the main document is zoomed.
+    ///
+    /// The integration uses [`Self::zoom_factor_of`] to compute the `pixels_per_point` of each window.
+    pub fn set_zoom_factor_of(&self, viewport_id: ViewportId, zoom_factor: f32) {
+        let cause = RepaintCause::new();
+        self.write(|ctx| {
+            if ctx.zoom_factors.get(&viewport_id) != Some(&zoom_factor) {
+                ctx.new_zoom_factors.insert(viewport_id, zoom_factor);
+                ctx.request_repaint(viewport_id, cause);
+            }
+        });
+    }
+
+    /// Set the zoom factor of the current viewport: its own one if it has one, otherwise the global one.
+    pub(crate) fn set_current_zoom_factor(&self, zoom_factor: f32) {
+        let viewport_id = self.viewport_id();
+        if self.read(|ctx| ctx.zoom_factors.contains_key(&viewport_id)) {
+            self.set_zoom_factor_of(viewport_id, zoom_factor);
+        } else {
+            self.set_zoom_factor(zoom_factor);
+        }
+    }
+
     /// Useful for pixel-perfect rendering of lines that are one pixel wide (or any odd number of pixels).
     #[inline]
     pub(crate) fn round_to_pixel_center(&self, point: f32) -> f32 {
@@ -2149,9 +2510,14 @@ impl Context {
             crate::gui_zoom::zoom_with_keyboard(self);
         }
 
+        self.modal_backdrop();
+        self.long_press_peek();
+
         // Plugins run just before the pass ends.
         self.read(|ctx| ctx.plugins.clone()).on_end_pass(self);
 
+        self.apply_open_url_policy();
+
         #[cfg(debug_assertions)]
         self.debug_painting();
 
@@ -2165,6 +2531,82 @@ impl Context {
         self.end_pass()
     }
 
+    /// Dim the viewport and block its input if it has a modal child viewport that is still shown.
+    fn modal_backdrop(&self) {
+        let has_modal = self.read(|ctx| {
+            let this = ctx.viewport_id();
+            ctx.viewports.iter().any(|(id, viewport)| {
+                viewport.class == ViewportClass::Modal
+                    && viewport.used
+                    && ctx
+                        .viewport_parents
+                        .get(id)
+                        .is_some_and(|&parent| viewport.builder.modal_parent_or(parent) == this)
+            })
+        });
+        if !has_modal {
+            return;
+        }
+
+        let screen_rect = self.screen_rect();
+        let area = Area::new(Id::new("egui_modal_backdrop"))
+            .order(Order::Foreground)
+            .fixed_pos(screen_rect.min)
+            .show(self, |ui| {
+                ui.painter()
+                    .rect_filled(screen_rect, 0.0, ui.visuals().modal_backdrop);
+                ui.allocate_rect(screen_rect, Sense::click_and_drag());
+            });
+        self.move_to_top(area.response.layer_id);
+    }
+
+    /// Show a ring filling up around a finger held down on a widget,
+    /// so the user knows that a context menu is about to open.
+    ///
+    /// See [`crate::style::LongPress`].
+    fn long_press_peek(&self) {
+        if !self.style().interaction.long_press.show_peek {
+            return;
+        }
+        let Some((progress, center)) = self.input(|i| {
+            if i.any_touches() {
+                Some((i.pointer.long_press_progress()?, i.pointer.press_origin()?))
+            } else {
+                None
+            }
+        }) else {
+            return;
+        };
+        if progress < crate::input_state::LONG_PRESS_PEEK_START
+            || self.memory(|mem| mem.interaction().potential_click_id.is_none())
+        {
+            return;
+        }
+
+        let t = emath::remap_clamp(
+            progress,
+            crate::input_state::LONG_PRESS_PEEK_START..=1.0,
+            0.0..=1.0,
+        );
+        let visuals = self.style().visuals.clone();
+        let color = visuals.selection.bg_fill.gamma_multiply(t.sqrt());
+        let radius = 24.0 + 8.0 * (1.0 - t); // Shrink towards the finger as it fills up
+        let width = 4.0;
+
+        let painter = self.layer_painter(LayerId::new(Order::Tooltip, Id::new("long_press_peek")));
+        painter.circle_stroke(center, radius, (width, color.gamma_multiply(0.3)));
+
+        let num_points = 4 + (64.0 * t).round() as usize;
+        let points = (0..=num_points)
+            .map(|i| {
+                let angle = -std::f32::consts::FRAC_PI_2
+                    + std::f32::consts::TAU * t * i as f32 / num_points as f32;
+                center + radius * emath::Vec2::angled(angle)
+            })
+            .collect();
+        painter.add(epaint::PathShape::line(points, (width, color)));
+    }
+
     /// Called at the end of the pass.
     #[cfg(debug_assertions)]
     fn debug_painting(&self) {
@@ -2311,6 +2753,12 @@ impl ContextImpl {
 
         viewport.repaint.cumulative_pass_nr += 1;
 
+        // Some text is missing glyphs, which we'll rasterize next pass:
+        let has_deferred_glyphs = self
+            .fonts
+            .get(&pixels_per_point.into())
+            .is_some_and(|fonts| fonts.has_deferred_glyphs());
+
         self.memory.end_pass(&viewport.this_pass.used_ids);
 
         if let Some(fonts) = self.fonts.get(&pixels_per_point.into()) {
@@ -2475,6 +2923,10 @@ impl ContextImpl {
             self.memory.set_viewport_id(viewport_id);
         }
 
+        if has_deferred_glyphs {
+            self.request_repaint(ended_viewport_id, RepaintCause::new());
+        }
+
         let active_pixels_per_point: std::collections::BTreeSet<OrderedFloat<f32>> = self
             .viewports
             .values()
@@ -2523,7 +2975,13 @@ impl Context {
         // it takes to tessellate them, so it is not a worth optimization.
 
         self.write(|ctx| {
-            let tessellation_options = ctx.memory.options.tessellation_options;
+            let mut tessellation_options = ctx.memory.options.tessellation_options;
+            if ctx.memory.options.pixel_perfect {
+                tessellation_options.feathering = false;
+                tessellation_options.prerasterized_discs = false;
+                tessellation_options.round_text_to_pixels = true;
+                tessellation_options.round_vertices_to_pixels = true;
+            }
             let texture_atlas = if let Some(fonts) = ctx.fonts.get(&pixels_per_point.into()) {
                 fonts.texture_atlas()
             } else {
@@ -2794,6 +3252,67 @@ impl Context {
         true
     }
 
+    /// Capture the state of the UI the user can change by interacting with it:
+    /// scroll offsets, open collapsing headers, window positions, keyboard focus, etc.
+    ///
+    /// Restore it later with [`Self::restore_ui_state`].
+    /// See [`crate::UiStateSnapshot`] for details.
+    ///
+    /// ```
+    /// # let ctx = egui::Context::default();
+    /// let snapshot = ctx.snapshot_ui_state();
+    /// let ron = snapshot.to_ron().unwrap();
+    /// // … later, maybe after restarting the app:
+    /// ctx.restore_ui_state(&egui::UiStateSnapshot::from_ron(&ron).unwrap());
+    /// ```
+    #[cfg(feature = "persistence")]
+    pub fn snapshot_ui_state(&self) -> crate::UiStateSnapshot {
+        self.memory(crate::UiStateSnapshot::capture)
+    }
+
+    /// Restore a snapshot captured with [`Self::snapshot_ui_state`].
+    ///
+    /// This replaces all persisted widget state, so widgets not in the snapshot go back to their defaults.
+    /// Takes effect on the next pass.
+    #[cfg(feature = "persistence")]
+    pub fn restore_ui_state(&self, snapshot: &crate::UiStateSnapshot) {
+        self.memory_mut(|mem| snapshot.restore(mem));
+        self.request_repaint();
+    }
+
+    /// Describe all [`Ui`]s and widgets of the last pass as a tree,
+    /// with their rectangles, ids, texts and state.
+    ///
+    /// This needs [`crate::Options::record_ui_tree`] to be on during that pass.
+    /// Without it, all widgets are at the top level of their layer, and have no [`crate::WidgetInfo`].
+    ///
+    /// With the `serde` feature, the snapshot can be serialized, e.g. to JSON for external tools:
+    ///
+    /// ```
+    /// # let ctx = egui::Context::default();
+    /// ctx.options_mut(|o| o.record_ui_tree = true);
+    /// # let _ = ctx.run(Default::default(), |ctx| {
+    /// #     egui::CentralPanel::default().show(ctx, |ui| ui.button("Save"));
+    /// # });
+    /// // … after running a pass:
+    /// let snapshot = ctx.ui_tree_snapshot();
+    /// assert!(snapshot.iter().any(|node| node.text() == Some("Save")));
+    /// ```
+    pub fn ui_tree_snapshot(&self) -> crate::UiTreeSnapshot {
+        self.write(|ctx| {
+            let focused = ctx.memory.focused();
+            let layer_order = ctx.memory.areas().order().to_vec();
+            let viewport = ctx.viewport();
+            crate::UiTreeSnapshot::new(
+                viewport.repaint.cumulative_pass_nr.saturating_sub(1),
+                &viewport.prev_pass.widgets,
+                &layer_order,
+                &viewport.interact_widgets,
+                focused,
+            )
+        })
+    }
+
     // ---------------------------------------------------------------------
 
     /// Whether or not to debug widget layout on hover.
@@ -3257,6 +3776,8 @@ impl Context {
     /// The `Context` lock is held while the given closure is called!
     ///
     /// Returns `None` if acesskit is off.
+    ///
+    /// For custom widgets, [`Response::accesskit_node`] is usually more convenient.
     // TODO(emilk): consider making both read-only and read-write versions
     #[cfg(feature = "accesskit")]
     pub fn accesskit_node_builder<R>(
@@ -3522,6 +4043,22 @@ impl Context {
         self.read(|ctx| ctx.parent_viewport_id())
     }
 
+    /// Is the current viewport blocked by a modal child viewport?
+    ///
+    /// See [`ViewportBuilder::with_modal`] and [`ViewportBuilder::with_modal_parent`].
+    pub fn has_modal_viewport(&self) -> bool {
+        self.read(|ctx| {
+            let this = ctx.viewport_id();
+            ctx.viewports.iter().any(|(id, viewport)| {
+                viewport.class == ViewportClass::Modal
+                    && ctx
+                        .viewport_parents
+                        .get(id)
+                        .is_some_and(|&parent| viewport.builder.modal_parent_or(parent) == this)
+            })
+        })
+    }
+
     /// Read the state of the current viewport.
     pub fn viewport<R>(&self, reader: impl FnOnce(&ViewportState) -> R) -> R {
         self.write(|ctx| reader(ctx.viewport()))
@@ -3574,6 +4111,26 @@ impl Context {
         self.write(|ctx| ctx.embed_viewports = value);
     }
 
+    /// Can the renderer paint [`epaint::Mesh::distance_field`]?
+    ///
+    /// Only then are the font families with [`crate::text::GlyphRendering::DistanceField`] rendered that way,
+    /// otherwise they use the bitmap font atlas like the others.
+    ///
+    /// `eframe` sets this to `true`, but the default value is `false`.
+    pub fn distance_field_text(&self) -> bool {
+        self.read(|ctx| ctx.distance_field_text)
+    }
+
+    /// Tell egui whether the renderer can paint [`epaint::Mesh::distance_field`].
+    ///
+    /// Only then are the font families with [`crate::text::GlyphRendering::DistanceField`] rendered that way,
+    /// otherwise they use the bitmap font atlas like the others.
+    ///
+    /// `eframe` sets this to `true`, but the default value is `false`.
+    pub fn set_distance_field_text(&self, value: bool) {
+        self.write(|ctx| ctx.distance_field_text = value);
+    }
+
     /// Send a command to the current viewport.
     ///
     /// This lets you affect the current viewport, e.g. resizing the window.
@@ -3584,6 +4141,11 @@ impl Context {
     /// Send a command to a specific viewport.
     ///
     /// This lets you affect another viewport, e.g. resizing its window.
+    ///
+    /// The commands sent during a pass are applied by the integration after the pass,
+    /// in the order they were sent.
+    /// What became of them (e.g. if the compositor refused to move the window)
+    /// is reported in [`crate::ViewportInfo::command_results`] in the next pass.
     pub fn send_viewport_cmd_to(&self, id: ViewportId, command: ViewportCommand) {
         self.request_repaint_of(id);
 
@@ -3638,17 +4200,90 @@ impl Context {
                 ctx.viewport_parents
                     .insert(new_viewport_id, ctx.viewport_id());
 
+                let class = if viewport_builder.modal == Some(true) {
+                    ViewportClass::Modal
+                } else {
+                    ViewportClass::Deferred
+                };
+
                 let viewport = ctx.viewports.entry(new_viewport_id).or_default();
-                viewport.class = ViewportClass::Deferred;
+                viewport.class = class;
                 viewport.builder = viewport_builder;
                 viewport.used = true;
                 viewport.viewport_ui_cb = Some(Arc::new(move |ctx| {
-                    (viewport_ui_cb)(ctx, ViewportClass::Deferred);
+                    (viewport_ui_cb)(ctx, class);
                 }));
             });
         }
     }
 
+    /// Show a viewport that is rendered into a texture instead of into a native window.
+    ///
+    /// This is for putting egui onto a surface in a 3D scene.
+    /// Like [`Self::show_viewport_deferred`] this needs to be called each pass of the parent viewport
+    /// while the texture viewport should exist, and the callback is stored for later.
+    ///
+    /// Unlike other viewports, texture viewports are not run by the egui integration.
+    /// Instead you run them yourself with [`Self::run_texture_viewport`],
+    /// outside of any other pass, and then paint the output into your texture.
+    ///
+    /// Texture viewports work regardless of [`Self::embed_viewports`].
+    ///
+    /// See [`crate::TextureViewportInput`] for an example.
+    pub fn show_viewport_texture(
+        &self,
+        new_viewport_id: ViewportId,
+        viewport_ui_cb: impl Fn(&Self, ViewportClass) + Send + Sync + 'static,
+    ) {
+        crate::profile_function!();
+
+        self.write(|ctx| {
+            ctx.viewport_parents
+                .insert(new_viewport_id, ctx.viewport_id());
+
+            let viewport = ctx.viewports.entry(new_viewport_id).or_default();
+            viewport.class = ViewportClass::Texture;
+            viewport.used = true;
+            viewport.viewport_ui_cb = Some(Arc::new(move |ctx| {
+                (viewport_ui_cb)(ctx, ViewportClass::Texture);
+            }));
+        });
+    }
+
+    /// Run a pass of a viewport created with [`Self::show_viewport_texture`].
+    ///
+    /// Call this outside of any other pass, e.g. after the root viewport has finished its pass.
+    /// Tessellate the returned [`FullOutput::shapes`] with [`Self::tessellate`]
+    /// and paint them into your texture, e.g. with `egui_glow::Painter::paint_to_texture`
+    /// or `egui_wgpu::Renderer::render_to_texture`.
+    /// Pass them the [`FullOutput::textures_delta`] too, and use the same renderer as for the parent viewport.
+    ///
+    /// Returns `None` if there is no texture viewport with the id of the given input,
+    /// e.g. because the parent stopped calling [`Self::show_viewport_texture`].
+    pub fn run_texture_viewport(
+        &self,
+        input: &mut crate::TextureViewportInput,
+    ) -> Option<FullOutput> {
+        crate::profile_function!();
+
+        let viewport_id = input.viewport_id();
+        let (parent, viewport_ui_cb) = self.read(|ctx| {
+            let viewport = ctx.viewports.get(&viewport_id)?;
+            if viewport.class != ViewportClass::Texture {
+                return None;
+            }
+            let parent = ctx.viewport_parents.get(&viewport_id).copied();
+            Some((parent, viewport.viewport_ui_cb.clone()?))
+        })?;
+
+        let mut raw_input = input.take_raw_input(self.zoom_factor_of(viewport_id));
+        if let Some(info) = raw_input.viewports.get_mut(&viewport_id) {
+            info.parent = parent;
+        }
+
+        Some(self.run(raw_input, |ctx| viewport_ui_cb(ctx)))
+    }
+
     /// Show an immediate viewport, creating a new native window, if possible.
     ///
     /// This is the easier type of viewport to use, but it is less performant
@@ -3957,4 +4592,72 @@ mod test {
             );
         }
     }
+
+    #[test]
+    fn test_zoom_factor_of_viewport() {
+        use crate::{RawInput, ViewportId};
+
+        let ctx = Context::default();
+        let palette = ViewportId::from_hash_of("palette");
+        let run = |viewport_id: ViewportId| {
+            ctx.run(
+                RawInput {
+                    viewport_id,
+                    viewports: std::iter::once((viewport_id, Default::default())).collect(),
+                    ..Default::default()
+                },
+                |_| {},
+            )
+            .pixels_per_point
+        };
+
+        ctx.set_zoom_factor(2.0);
+        ctx.set_zoom_factor_of(palette, 1.0);
+        assert_eq!(run(ViewportId::ROOT), 2.0);
+        assert_eq!(run(palette), 1.0);
+
+        // The global zoom factor doesn't affect viewports with their own:
+        ctx.set_zoom_factor(3.0);
+        assert_eq!(run(ViewportId::ROOT), 3.0);
+        assert_eq!(run(palette), 1.0);
+        assert_eq!(ctx.zoom_factor_of(palette), 1.0);
+    }
+
+    #[test]
+    fn test_theme_transition() {
+        use crate::{RawInput, Theme, Visuals};
+
+        let ctx = Context::default();
+        ctx.all_styles_mut(|style| style.theme_transition_time = 1.0);
+        let run = |time: f64| {
+            let mut panel_fill = Default::default();
+            let _ = ctx.run(
+                RawInput {
+                    time: Some(time),
+                    ..Default::default()
+                },
+                |ctx| panel_fill = ctx.style().visuals.panel_fill,
+            );
+            panel_fill
+        };
+
+        let (dark, light) = (Visuals::dark().panel_fill, Visuals::light().panel_fill);
+        ctx.set_theme(Theme::Dark);
+        assert_eq!(run(0.0), dark);
+
+        ctx.set_theme(Theme::Light);
+        assert_eq!(run(1.0), dark, "The transition starts now");
+        let halfway = run(1.5);
+        assert!(halfway != dark && halfway != light);
+        assert!(ctx.has_requested_repaint());
+        assert_eq!(run(2.0), light);
+
+        // Changing back halfway starts from where we are:
+        ctx.set_theme(Theme::Dark);
+        run(3.0);
+        let midway = run(3.5);
+        ctx.set_theme(Theme::Light);
+        assert_eq!(run(3.5), midway);
+        assert_eq!(run(4.5), light);
+    }
 }
@@ -0,0 +1,362 @@
+//! Per-widget context help, a.k.a. "What's this?" mode.
+//!
+//! Attach help to a widget with [`crate::Response::context_help`].
+//! When the user enters help mode (e.g. via [`ContextHelp::toggle_button`]),
+//! the cursor changes to [`CursorIcon::Help`] and normal widget interaction is suspended.
+//! Clicking a widget with registered help then shows a help popover next to the pointer.
+//!
+//! All help entries that have been registered are kept in a registry,
+//! which can be read with [`ContextHelp::registry`], e.g. to generate documentation.
+
+use std::collections::BTreeMap;
+
+use crate::{
+    Align2, Area, Context, CursorIcon, Frame, Id, Key, LayerId, Order, Pos2, Response, Stroke, Ui,
+    UiKind,
+};
+
+/// Help for a single widget, identified by a stable, user-chosen help id.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// ui.button("Extrude")
+///     .context_help(
+///         egui::HelpEntry::new("tools.extrude", "Extrude")
+///             .text("Pulls the selected face along its normal.")
+///             .url("https://example.com/docs/tools/extrude"),
+///     );
+/// # });
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct HelpEntry {
+    /// Stable identifier of this help topic, e.g. `"tools.extrude"`.
+    ///
+    /// Several widgets may share the same help id.
+    pub help_id: String,
+
+    /// Short title, shown as the heading of the help popover.
+    pub title: String,
+
+    /// The body of the help text.
+    pub text: String,
+
+    /// Optional link to further documentation.
+    pub url: Option<String>,
+}
+
+impl HelpEntry {
+    pub fn new(help_id: impl Into<String>, title: impl Into<String>) -> Self {
+        Self {
+            help_id: help_id.into(),
+            title: title.into(),
+            text: String::new(),
+            url: None,
+        }
+    }
+
+    /// The body of the help text.
+    #[inline]
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.text = text.into();
+        self
+    }
+
+    /// Link to further documentation, shown at the bottom of the help popover.
+    #[inline]
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+}
+
+/// All help entries registered so far, keyed by help id.
+#[derive(Clone, Default)]
+struct HelpRegistry {
+    entries: BTreeMap<String, HelpEntry>,
+}
+
+#[derive(Clone, Default)]
+struct State {
+    /// Are we in "What's this?" mode?
+    active: bool,
+
+    /// The pass on which help mode was activated.
+    ///
+    /// The click that activated help mode should not also end it.
+    activated_pass: u64,
+
+    /// The help id of the widget hovered this pass (only tracked in help mode).
+    hovered_help_id: Option<String>,
+
+    /// The help popover that is currently open, if any.
+    open: Option<(String, Pos2)>,
+}
+
+/// Context help ("What's this?" mode).
+///
+/// This is a low-level API. Most users only need [`crate::Response::context_help`]
+/// and [`Self::toggle_button`].
+#[derive(Clone, Copy, Debug)]
+pub struct ContextHelp;
+
+impl ContextHelp {
+    pub(crate) fn register(ctx: &Context) {
+        ctx.on_begin_pass("ContextHelp", std::sync::Arc::new(Self::begin_pass));
+        ctx.on_end_pass("ContextHelp", std::sync::Arc::new(Self::end_pass));
+    }
+
+    fn begin_pass(ctx: &Context) {
+        ctx.data_mut(|data| {
+            data.get_temp_mut_or_default::<State>(Id::NULL)
+                .hovered_help_id = None;
+        });
+    }
+
+    fn end_pass(ctx: &Context) {
+        Self::show_popover(ctx);
+
+        let (escape, clicked, pointer_pos) = ctx.input(|i| {
+            (
+                i.key_pressed(Key::Escape),
+                i.pointer.primary_clicked(),
+                i.pointer.interact_pos(),
+            )
+        });
+        let pass_nr = ctx.cumulative_pass_nr();
+
+        let mut is_active = false;
+        ctx.data_mut(|data| {
+            let state = data.get_temp_mut_or_default::<State>(Id::NULL);
+            if !state.active {
+                return;
+            }
+
+            if escape {
+                state.active = false;
+            } else if clicked && state.activated_pass != pass_nr {
+                // Any click ends help mode, but only a click on a widget with help opens a popover:
+                state.active = false;
+                if let (Some(help_id), Some(pos)) = (state.hovered_help_id.take(), pointer_pos) {
+                    state.open = Some((help_id, pos));
+                }
+            }
+
+            is_active = state.active;
+        });
+
+        if is_active {
+            ctx.set_cursor_icon(CursorIcon::Help);
+        }
+    }
+
+    fn show_popover(ctx: &Context) {
+        let Some((help_id, pos)) = ctx.data(|data| data.get_temp::<State>(Id::NULL)?.open) else {
+            return;
+        };
+
+        let Some(entry) = Self::entry(ctx, &help_id) else {
+            Self::close_popover(ctx);
+            return;
+        };
+
+        let area_response = Area::new(Id::new("__context_help_popover"))
+            .kind(UiKind::Popup)
+            .order(Order::Foreground)
+            .fixed_pos(pos)
+            .pivot(Align2::LEFT_TOP)
+            .constrain(true)
+            .show(ctx, |ui| {
+                Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.set_max_width(ui.spacing().tooltip_width * 1.5);
+                    Self::entry_ui(ui, &entry);
+                });
+            })
+            .response;
+
+        if ctx.input(|i| i.key_pressed(Key::Escape)) || area_response.clicked_elsewhere() {
+            Self::close_popover(ctx);
+        }
+    }
+
+    /// Is "What's this?" mode currently active?
+    pub fn is_active(ctx: &Context) -> bool {
+        ctx.data(|data| data.get_temp::<State>(Id::NULL))
+            .map_or(false, |state| state.active)
+    }
+
+    /// Enter or leave "What's this?" mode.
+    ///
+    /// While active, widgets will not react to clicks or drags.
+    /// The next click leaves help mode again, showing the help
+    /// of the clicked widget (if any).
+    pub fn set_active(ctx: &Context, active: bool) {
+        let pass_nr = ctx.cumulative_pass_nr();
+        ctx.data_mut(|data| {
+            let state = data.get_temp_mut_or_default::<State>(Id::NULL);
+            if active && !state.active {
+                state.activated_pass = pass_nr;
+            }
+            state.active = active;
+            if active {
+                state.open = None;
+            }
+        });
+    }
+
+    /// A small `?` button that enters "What's this?" mode when clicked.
+    pub fn toggle_button(ui: &mut Ui) -> Response {
+        let is_active = Self::is_active(ui.ctx());
+        let response = ui.selectable_label(is_active, "?").on_hover_text(
+            "What's this?\n\nClick here, then click on any widget to get help for it.",
+        );
+        if response.clicked() {
+            Self::set_active(ui.ctx(), true);
+        }
+        response
+    }
+
+    /// Add an entry to the help registry, without attaching it to any widget.
+    ///
+    /// Registering the same help id again replaces the previous entry.
+    pub fn register_entry(ctx: &Context, entry: HelpEntry) {
+        ctx.data_mut(|data| {
+            let registry = data.get_temp_mut_or_default::<HelpRegistry>(Id::NULL);
+            if registry.entries.get(&entry.help_id) != Some(&entry) {
+                registry.entries.insert(entry.help_id.clone(), entry);
+            }
+        });
+    }
+
+    /// Look up a registered help entry.
+    pub fn entry(ctx: &Context, help_id: &str) -> Option<HelpEntry> {
+        ctx.data_mut(|data| {
+            data.get_temp_mut_or_default::<HelpRegistry>(Id::NULL)
+                .entries
+                .get(help_id)
+                .cloned()
+        })
+    }
+
+    /// All help entries registered so far, sorted by help id.
+    ///
+    /// Entries are registered the first time the widget they are attached to is shown,
+    /// or explicitly via [`Self::register_entry`].
+    /// This is useful for generating documentation of all help topics in an app.
+    pub fn registry(ctx: &Context) -> Vec<HelpEntry> {
+        ctx.data_mut(|data| {
+            data.get_temp_mut_or_default::<HelpRegistry>(Id::NULL)
+                .entries
+                .values()
+                .cloned()
+                .collect()
+        })
+    }
+
+    /// Show the help popover for the given help id at the given position.
+    pub fn open_popover(ctx: &Context, help_id: impl Into<String>, pos: Pos2) {
+        ctx.data_mut(|data| {
+            data.get_temp_mut_or_default::<State>(Id::NULL).open = Some((help_id.into(), pos));
+        });
+    }
+
+    /// Close the help popover, if open.
+    pub fn close_popover(ctx: &Context) {
+        ctx.data_mut(|data| {
+            data.get_temp_mut_or_default::<State>(Id::NULL).open = None;
+        });
+    }
+
+    /// Attach help to the widget of the given response.
+    ///
+    /// You usually want to use [`crate::Response::context_help`] instead.
+    pub fn attach(response: &Response, entry: HelpEntry) {
+        let ctx = &response.ctx;
+        let help_id = entry.help_id.clone();
+        Self::register_entry(ctx, entry);
+
+        if !Self::is_active(ctx) || !response.hovered {
+            return;
+        }
+
+        ctx.data_mut(|data| {
+            data.get_temp_mut_or_default::<State>(Id::NULL)
+                .hovered_help_id = Some(help_id);
+        });
+
+        let stroke = Stroke::new(2.0, ctx.style().visuals.selection.stroke.color);
+        ctx.layer_painter(LayerId::new(Order::Tooltip, Id::new("context_help")))
+            .rect_stroke(response.rect.expand(2.0), 2.0, stroke);
+    }
+
+    /// Show the contents of a help entry, as in the help popover.
+    pub fn entry_ui(ui: &mut Ui, entry: &HelpEntry) {
+        ui.strong(&entry.title);
+        if !entry.text.is_empty() {
+            ui.label(&entry.text);
+        }
+        if let Some(url) = &entry.url {
+            ui.separator();
+            ui.hyperlink_to("Open documentation", url);
+        }
+    }
+
+    /// Should widget interaction be suppressed because we are in help mode?
+    pub(crate) fn suppresses_interaction(data: &mut crate::util::IdTypeMap) -> bool {
+        data.get_temp_mut_or_default::<State>(Id::NULL).active
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{pos2, vec2, Event, PointerButton, RawInput, Rect};
+
+    #[test]
+    fn registry_is_sorted_and_deduplicated() {
+        let ctx = Context::default();
+        ContextHelp::register_entry(&ctx, HelpEntry::new("b", "B"));
+        ContextHelp::register_entry(&ctx, HelpEntry::new("a", "A"));
+        ContextHelp::register_entry(&ctx, HelpEntry::new("b", "B2").text("Updated"));
+
+        let registry = ContextHelp::registry(&ctx);
+        assert_eq!(registry.len(), 2);
+        assert_eq!(registry[0].help_id, "a");
+        assert_eq!(registry[1].title, "B2");
+        assert_eq!(registry[1].text, "Updated");
+    }
+
+    #[test]
+    fn activating_click_does_not_end_help_mode() {
+        let screen_rect = Some(Rect::from_min_size(pos2(0.0, 0.0), vec2(200.0, 200.0)));
+        let pointer = |pressed| Event::PointerButton {
+            pos: pos2(12.0, 12.0),
+            button: PointerButton::Primary,
+            pressed,
+            modifiers: Default::default(),
+        };
+
+        // Click the toggle button:
+        let ctx = Context::default();
+        let mut toggled = false;
+        for events in [
+            vec![Event::PointerMoved(pos2(12.0, 12.0))],
+            vec![pointer(true)],
+            vec![pointer(false)],
+        ] {
+            let input = RawInput {
+                screen_rect,
+                events,
+                ..Default::default()
+            };
+            let _ = ctx.run(input, |ctx| {
+                crate::CentralPanel::default().show(ctx, |ui| {
+                    toggled |= ContextHelp::toggle_button(ui).clicked();
+                });
+            });
+        }
+
+        assert!(toggled, "the toggle button was clicked");
+        assert!(ContextHelp::is_active(&ctx));
+    }
+}
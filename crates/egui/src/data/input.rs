@@ -81,6 +81,12 @@ pub struct RawInput {
     ///
     /// `None` means "don't know".
     pub system_theme: Option<Theme>,
+
+    /// The state of all connected gamepads (game controllers).
+    ///
+    /// Set by `eframe` when its `gamepad` feature is enabled.
+    /// See [`crate::InputState::gamepads`].
+    pub gamepads: Vec<GamepadState>,
 }
 
 impl Default for RawInput {
@@ -98,6 +104,7 @@ impl Default for RawInput {
             dropped_files: Default::default(),
             focused: true, // integrations opt into global focus tracking
             system_theme: None,
+            gamepads: Vec::new(),
         }
     }
 }
@@ -111,7 +118,7 @@ impl RawInput {
 
     /// Helper: move volatile (deltas and events), clone the rest.
     ///
-    /// * [`Self::hovered_files`] is cloned.
+    /// * [`Self::hovered_files`] and [`Self::gamepads`] are cloned.
     /// * [`Self::dropped_files`] is moved.
     pub fn take(&mut self) -> Self {
         Self {
@@ -131,6 +138,7 @@ impl RawInput {
             dropped_files: std::mem::take(&mut self.dropped_files),
             focused: self.focused,
             system_theme: self.system_theme,
+            gamepads: self.gamepads.clone(),
         }
     }
 
@@ -149,6 +157,7 @@ impl RawInput {
             mut dropped_files,
             focused,
             system_theme,
+            gamepads,
         } = newer;
 
         self.viewport_id = viewport_ids;
@@ -163,6 +172,7 @@ impl RawInput {
         self.dropped_files.append(&mut dropped_files);
         self.focused = focused;
         self.system_theme = system_theme;
+        self.gamepads = gamepads; // use latest
     }
 }
 
@@ -200,6 +210,12 @@ pub struct ViewportInfo {
 
     pub events: Vec<ViewportEvent>,
 
+    /// What became of the [`crate::ViewportCommand`]s sent to this viewport during the previous pass,
+    /// in the order they were sent.
+    ///
+    /// Not all integrations report this, in which case it stays empty.
+    pub command_results: Vec<crate::ViewportCommandResult>,
+
     /// The OS native pixels-per-point.
     ///
     /// This should always be set, if known.
@@ -211,6 +227,14 @@ pub struct ViewportInfo {
     /// Current monitor size in egui points.
     pub monitor_size: Option<Vec2>,
 
+    /// The work areas of all monitors, in monitor space and ui points scale.
+    ///
+    /// The work area is the part of a monitor not covered by taskbars, docks and the like.
+    /// Integrations that can't tell report the whole monitor.
+    ///
+    /// Empty if unknown.
+    pub monitor_work_areas: Vec<Rect>,
+
     /// The inner rectangle of the native window, in monitor space and ui points scale.
     ///
     /// This is the content rectangle of the viewport.
@@ -234,6 +258,12 @@ pub struct ViewportInfo {
     ///
     /// This should be the same as [`RawInput::focused`].
     pub focused: Option<bool>,
+
+    /// The surface the viewport is painted to, if the integration reports it.
+    ///
+    /// This can differ from what was requested with [`crate::ViewportBuilder::with_surface`],
+    /// if the driver does not support it.
+    pub surface: Option<crate::SurfaceConfig>,
 }
 
 impl ViewportInfo {
@@ -251,20 +281,30 @@ impl ViewportInfo {
             .any(|&event| event == ViewportEvent::Close)
     }
 
-    /// Helper: move [`Self::events`], clone the other fields.
+    /// The commands sent during the previous pass that were not carried out.
+    pub fn failed_commands(&self) -> impl Iterator<Item = &crate::ViewportCommandResult> {
+        self.command_results
+            .iter()
+            .filter(|result| !result.outcome.is_applied())
+    }
+
+    /// Helper: move [`Self::events`] and [`Self::command_results`], clone the other fields.
     pub fn take(&mut self) -> Self {
         Self {
             parent: self.parent,
             title: self.title.clone(),
             events: std::mem::take(&mut self.events),
+            command_results: std::mem::take(&mut self.command_results),
             native_pixels_per_point: self.native_pixels_per_point,
             monitor_size: self.monitor_size,
+            monitor_work_areas: self.monitor_work_areas.clone(),
             inner_rect: self.inner_rect,
             outer_rect: self.outer_rect,
             minimized: self.minimized,
             maximized: self.maximized,
             fullscreen: self.fullscreen,
             focused: self.focused,
+            surface: self.surface,
         }
     }
 
@@ -273,14 +313,17 @@ impl ViewportInfo {
             parent,
             title,
             events,
+            command_results,
             native_pixels_per_point,
             monitor_size,
+            monitor_work_areas,
             inner_rect,
             outer_rect,
             minimized,
             maximized,
             fullscreen,
             focused,
+            surface,
         } = self;
 
         crate::Grid::new("viewport_info").show(ui, |ui| {
@@ -296,6 +339,16 @@ impl ViewportInfo {
             ui.label(format!("{events:?}"));
             ui.end_row();
 
+            ui.label("Failed commands:");
+            ui.label(format!(
+                "{:?}",
+                command_results
+                    .iter()
+                    .filter(|result| !result.outcome.is_applied())
+                    .collect::<Vec<_>>()
+            ));
+            ui.end_row();
+
             ui.label("Native pixels-per-point:");
             ui.label(opt_as_str(native_pixels_per_point));
             ui.end_row();
@@ -304,6 +357,16 @@ impl ViewportInfo {
             ui.label(opt_as_str(monitor_size));
             ui.end_row();
 
+            ui.label("Monitor work areas:");
+            ui.label(
+                monitor_work_areas
+                    .iter()
+                    .map(|r| format!("Pos: {:?}, size: {:?}", r.min, r.size()))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            );
+            ui.end_row();
+
             ui.label("Inner rect:");
             ui.label(opt_rect_as_string(inner_rect));
             ui.end_row();
@@ -328,6 +391,10 @@ impl ViewportInfo {
             ui.label(opt_as_str(focused));
             ui.end_row();
 
+            ui.label("Surface:");
+            ui.label(opt_as_str(surface));
+            ui.end_row();
+
             fn opt_rect_as_string(v: &Option<Rect>) -> String {
                 v.as_ref().map_or(String::new(), |r| {
                     format!("Pos: {:?}, size: {:?}", r.min, r.size())
@@ -341,6 +408,44 @@ impl ViewportInfo {
     }
 }
 
+/// A monitor (display) connected to the system.
+///
+/// See [`Event::MonitorConnected`] and [`Event::MonitorDisconnected`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct MonitorInfo {
+    /// Human-readable name of the monitor, if known.
+    pub name: Option<String>,
+
+    /// Top-left corner of the monitor on the desktop, in physical pixels.
+    pub position_px: Pos2,
+
+    /// Size of the monitor, in physical pixels.
+    pub size_px: Vec2,
+
+    /// The native pixels-per-point of the monitor (the DPI scale factor).
+    pub scale_factor: f32,
+}
+
+impl MonitorInfo {
+    /// The area of the desktop covered by this monitor, in physical pixels.
+    #[inline]
+    pub fn rect_px(&self) -> Rect {
+        Rect::from_min_size(self.position_px, self.size_px)
+    }
+
+    /// Is this the same physical monitor?
+    ///
+    /// Compares names if both are known,
+    /// otherwise the position and size on the desktop.
+    pub fn is_same_monitor(&self, other: &Self) -> bool {
+        match (&self.name, &other.name) {
+            (Some(a), Some(b)) => a == b,
+            _ => self.rect_px() == other.rect_px(),
+        }
+    }
+}
+
 /// A file about to be dropped into egui.
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
@@ -372,6 +477,94 @@ pub struct DroppedFile {
     pub bytes: Option<std::sync::Arc<[u8]>>,
 }
 
+/// The state of a connected gamepad (game controller), see [`RawInput::gamepads`].
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct GamepadState {
+    /// Identifies the gamepad as long as it is connected.
+    pub id: usize,
+
+    /// The name of the gamepad, as reported by the OS.
+    pub name: String,
+
+    /// The buttons that are held down.
+    pub buttons_down: Vec<GamepadButton>,
+
+    /// The position of each axis that the gamepad has, in the range `-1.0..=1.0`.
+    ///
+    /// Up is positive for the sticks.
+    pub axes: Vec<(GamepadAxis, f32)>,
+}
+
+impl GamepadState {
+    /// Is the given button held down?
+    #[inline]
+    pub fn is_down(&self, button: GamepadButton) -> bool {
+        self.buttons_down.contains(&button)
+    }
+
+    /// The position of the given axis, or `0.0` if the gamepad doesn't have it.
+    pub fn axis(&self, axis: GamepadAxis) -> f32 {
+        self.axes
+            .iter()
+            .find(|(a, _)| *a == axis)
+            .map_or(0.0, |(_, value)| *value)
+    }
+
+    /// The position of the left stick.
+    pub fn left_stick(&self) -> Vec2 {
+        Vec2::new(
+            self.axis(GamepadAxis::LeftStickX),
+            self.axis(GamepadAxis::LeftStickY),
+        )
+    }
+
+    /// The position of the right stick.
+    pub fn right_stick(&self) -> Vec2 {
+        Vec2::new(
+            self.axis(GamepadAxis::RightStickX),
+            self.axis(GamepadAxis::RightStickY),
+        )
+    }
+}
+
+/// A button on a gamepad, named after its position on the gamepad.
+///
+/// On an Xbox controller, [`Self::South`] is A and [`Self::East`] is B.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum GamepadButton {
+    South,
+    East,
+    North,
+    West,
+    LeftTrigger,
+    LeftTrigger2,
+    RightTrigger,
+    RightTrigger2,
+    Select,
+    Start,
+    Mode,
+    LeftThumb,
+    RightThumb,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+}
+
+/// An axis of a gamepad, see [`GamepadState::axes`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum GamepadAxis {
+    LeftStickX,
+    LeftStickY,
+    RightStickX,
+    RightStickY,
+    LeftZ,
+    RightZ,
+}
+
 /// An input event generated by the integration.
 ///
 /// This only covers events that egui cares about.
@@ -387,6 +580,12 @@ pub enum Event {
     /// The integration detected a "paste" event (e.g. Cmd+V).
     Paste(String),
 
+    /// The integration detected a "paste" event (e.g. Cmd+V),
+    /// and the clipboard contained an image instead of text.
+    ///
+    /// Widgets that can accept images (e.g. a canvas) can look for this in [`crate::InputState::events`].
+    PasteImage(std::sync::Arc<ColorImage>),
+
     /// Text input, e.g. via keyboard.
     ///
     /// When the user presses enter/return, do not send a [`Text`](Event::Text) (just [`Key::Enter`]).
@@ -410,8 +609,20 @@ pub enum Event {
         /// even if the user is using Dvorak or AZERTY.
         ///
         /// `eframe` does not (yet) implement this on web.
+        ///
+        /// See also [`crate::InputState::key_down_physical`].
         physical_key: Option<Key>,
 
+        /// The platform-specific scancode of the physical key, if known.
+        ///
+        /// Like [`Self::Key::physical_key`] this ignores the keymap,
+        /// but it is also set for keys that egui has no [`Key`] for,
+        /// so it is useful for rebindable game controls.
+        /// Scancodes differ between platforms, so only compare them to other scancodes from the same machine.
+        ///
+        /// `egui-winit` sets this on Windows, Mac, and Linux.
+        scancode: Option<u32>,
+
         /// Was it pressed or released?
         pressed: bool,
 
@@ -517,6 +728,11 @@ pub enum Event {
 
         /// The state of the modifier keys at the time of the event.
         modifiers: Modifiers,
+
+        /// Where in a scroll gesture on a precision trackpad this event is.
+        ///
+        /// [`ScrollPhase::Unknown`] for mouse wheels, and for backends that don't know.
+        phase: ScrollPhase,
     },
 
     /// The native window gained or lost focused (e.g. the user clicked alt-tab).
@@ -531,6 +747,20 @@ pub enum Event {
         viewport_id: crate::ViewportId,
         image: std::sync::Arc<ColorImage>,
     },
+
+    /// A monitor was connected.
+    ///
+    /// Sent to all viewports.
+    /// Viewports that were moved off this monitor when it was disconnected may be moved back,
+    /// see [`crate::ViewportBuilder::with_monitor_relocation`].
+    MonitorConnected(MonitorInfo),
+
+    /// A monitor was disconnected.
+    ///
+    /// Sent to all viewports.
+    /// Viewports that were on this monitor may be moved to another one,
+    /// see [`crate::ViewportBuilder::with_monitor_relocation`].
+    MonitorDisconnected(MonitorInfo),
 }
 
 /// IME event.
@@ -1078,6 +1308,7 @@ impl RawInput {
             dropped_files,
             focused,
             system_theme,
+            gamepads,
         } = self;
 
         ui.label(format!("Active viwport: {viewport_id:?}"));
@@ -1103,6 +1334,9 @@ impl RawInput {
         ui.label(format!("dropped_files: {}", dropped_files.len()));
         ui.label(format!("focused: {focused}"));
         ui.label(format!("system_theme: {system_theme:?}"));
+        for gamepad in gamepads {
+            ui.label(format!("gamepad {}: {:?}", gamepad.id, gamepad.name));
+        }
         ui.scope(|ui| {
             ui.set_min_height(150.0);
             ui.label(format!("events: {events:#?}"))
@@ -1158,6 +1392,45 @@ pub enum MouseWheelUnit {
     Page,
 }
 
+/// Where in a scroll gesture on a precision trackpad an [`Event::MouseWheel`] is.
+///
+/// The current phase is also available as [`crate::InputState::scroll_phase`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum ScrollPhase {
+    /// Not part of a gesture, e.g. a notch of a mouse wheel,
+    /// or the backend doesn't know.
+    #[default]
+    Unknown,
+
+    /// The fingers were placed on the trackpad, and started scrolling.
+    Started,
+
+    /// The fingers are moving on the trackpad.
+    Moved,
+
+    /// The fingers were lifted from the trackpad.
+    ///
+    /// The platform may keep scrolling with [`Self::Momentum`] after this.
+    Ended,
+
+    /// The platform keeps scrolling after the fingers were lifted.
+    Momentum,
+
+    /// The momentum scrolling came to a stop.
+    MomentumEnded,
+}
+
+impl ScrollPhase {
+    /// Are the fingers of the user on the trackpad?
+    ///
+    /// When this is `true`, the scrolling is intentional, and the user may still change direction.
+    #[inline]
+    pub fn is_touching(self) -> bool {
+        matches!(self, Self::Started | Self::Moved)
+    }
+}
+
 impl From<u64> for TouchId {
     fn from(id: u64) -> Self {
         Self(id)
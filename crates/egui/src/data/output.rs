@@ -1,5 +1,9 @@
 //! All the data egui returns to the backend at the end of each frame.
 
+use std::{path::PathBuf, sync::Arc};
+
+use epaint::ColorImage;
+
 use crate::{RepaintCause, ViewportIdMap, ViewportOutput, WidgetType};
 
 /// What egui emits each frame from [`crate::Context::run`].
@@ -106,6 +110,22 @@ pub struct PlatformOutput {
     /// ```
     pub copied_text: String,
 
+    /// If set, put this rich content in the system clipboard instead of [`Self::copied_text`].
+    ///
+    /// Backends that cannot handle a given kind of content
+    /// should fall back to [`ClipboardData::text_fallback`].
+    ///
+    /// See also [`crate::Context::copy_image`] and [`crate::Context::copy_data`].
+    pub copied_data: Option<ClipboardData>,
+
+    /// If set, the pointer has left the window while dragging something
+    /// that can also be dropped into other applications (see [`crate::DragAndDrop::set_os_payload`]).
+    ///
+    /// The integration should start a drag-and-drop of the operating system with this,
+    /// so that the drop can complete in another application.
+    /// This is only set for a single frame.
+    pub os_drag: Option<OsDragPayload>,
+
     /// Events that may be useful to e.g. a screen reader.
     pub events: Vec<OutputEvent>,
 
@@ -113,9 +133,10 @@ pub struct PlatformOutput {
     /// Use by `eframe` web to show/hide mobile keyboard and IME agent.
     pub mutable_text_under_cursor: bool,
 
-    /// This is set if, and only if, the user is currently editing text.
+    /// This is set if, and only if, the user is currently editing text that accepts IME input.
     ///
-    /// Useful for IME.
+    /// The integration should allow the IME if this is `Some`, and disallow it otherwise.
+    /// It is `None` for e.g. password fields, see [`crate::TextEdit::ime`] and [`crate::Options::ime_allowed`].
     pub ime: Option<IMEOutput>,
 
     /// The difference in the widget tree since last frame.
@@ -166,6 +187,8 @@ impl PlatformOutput {
             cursor_icon,
             open_url,
             copied_text,
+            copied_data,
+            os_drag,
             mut events,
             mutable_text_under_cursor,
             ime,
@@ -181,6 +204,13 @@ impl PlatformOutput {
         }
         if !copied_text.is_empty() {
             self.copied_text = copied_text;
+            self.copied_data = None;
+        }
+        if copied_data.is_some() {
+            self.copied_data = copied_data;
+        }
+        if os_drag.is_some() {
+            self.os_drag = os_drag;
         }
         self.events.append(&mut events);
         self.mutable_text_under_cursor = mutable_text_under_cursor;
@@ -210,6 +240,57 @@ impl PlatformOutput {
     }
 }
 
+/// Rich content for the system clipboard.
+///
+/// Use with [`crate::Context::copy_data`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum ClipboardData {
+    /// An image, e.g. a screenshot or a rendered plot.
+    Image(Arc<ColorImage>),
+
+    /// HTML markup, e.g. a formatted table.
+    Html {
+        html: String,
+
+        /// Plain text for applications that can't accept HTML.
+        alt_text: String,
+    },
+
+    /// A list of files, e.g. to paste into a file manager.
+    Files(Vec<PathBuf>),
+}
+
+impl ClipboardData {
+    /// The plain text to use for clipboards that only support text.
+    ///
+    /// This is empty for images.
+    pub fn text_fallback(&self) -> String {
+        match self {
+            Self::Image(_) => String::new(),
+            Self::Html { alt_text, .. } => alt_text.clone(),
+            Self::Files(paths) => paths
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect::<Vec<_>>()
+                .join("\n"),
+        }
+    }
+}
+
+/// Something dragged within egui that can also be dropped into other applications.
+///
+/// See [`crate::DragAndDrop::set_os_payload`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum OsDragPayload {
+    /// Text, e.g. to drop into a text field.
+    Text(String),
+
+    /// A list of files, e.g. to drop into a file manager.
+    Files(Vec<PathBuf>),
+}
+
 /// What URL to open, and how.
 ///
 /// Use with [`crate::Context::open_url`].
@@ -242,6 +323,14 @@ impl OpenUrl {
     }
 }
 
+/// Decides what happens to a request to open a URL, before it reaches the backend.
+///
+/// Return the [`OpenUrl`] to open, maybe changed (e.g. to always open in a new tab),
+/// or `None` to not open anything.
+///
+/// See [`crate::Context::set_open_url_policy`].
+pub type OpenUrlPolicy = Arc<dyn Fn(&crate::Context, OpenUrl) -> Option<OpenUrl> + Send + Sync>;
+
 /// Types of attention to request from a user when a native window is not in focus.
 ///
 /// See [winit's documentation][user_attention_type] for platform-specific meaning of the attention types.
@@ -260,6 +349,39 @@ pub enum UserAttentionType {
     Reset,
 }
 
+/// How assistive technologies (e.g. screen readers) should announce changes to a widget.
+///
+/// A widget marked as a live region has its updates announced even when it doesn't have focus,
+/// which is useful for status messages and progress indicators.
+///
+/// See <https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/ARIA_Live_Regions>.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum LiveRegion {
+    /// Changes are not announced (the default).
+    #[default]
+    Off,
+
+    /// Changes are announced when the user is idle.
+    Polite,
+
+    /// Changes are announced immediately, interrupting the user.
+    ///
+    /// Use sparingly, e.g. for errors.
+    Assertive,
+}
+
+#[cfg(feature = "accesskit")]
+impl From<LiveRegion> for accesskit::Live {
+    fn from(live: LiveRegion) -> Self {
+        match live {
+            LiveRegion::Off => Self::Off,
+            LiveRegion::Polite => Self::Polite,
+            LiveRegion::Assertive => Self::Assertive,
+        }
+    }
+}
+
 /// A mouse cursor icon.
 ///
 /// egui emits a [`CursorIcon`] in [`PlatformOutput`] each frame as a request to the integration.
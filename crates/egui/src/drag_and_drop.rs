@@ -1,9 +1,15 @@
 use std::{any::Any, sync::Arc};
 
-use crate::{Context, CursorIcon, Id};
+use epaint::Shape;
+
+use crate::{Context, CursorIcon, Id, LayerId, Order, OsDragPayload, ViewportId};
 
 /// Tracking of drag-and-drop payload.
 ///
+/// The payload is shared by all viewports of a [`Context`],
+/// so things can be dragged from one viewport and dropped in another.
+/// (On native, `egui-winit` forwards the pointer between windows during the drag.)
+///
 /// This is a low-level API.
 ///
 /// For a higher-level API, see:
@@ -19,6 +25,27 @@ use crate::{Context, CursorIcon, Id};
 pub struct DragAndDrop {
     /// If set, something is currently being dragged
     payload: Option<Arc<dyn Any + Send + Sync>>,
+
+    /// The viewport the pointer was last seen in.
+    ///
+    /// The payload is shared by all viewports, so it can be dragged from one to another.
+    /// When the pointer is released, the payload is only dropped by the viewport the pointer is over.
+    pointer_viewport: Option<ViewportId>,
+
+    /// What the dragged item looks like, so we can show it when it is dragged into another viewport.
+    preview: Option<DragPreview>,
+
+    /// What to hand over to the operating system if the pointer leaves the app during the drag.
+    os_payload: Option<OsDragPayload>,
+}
+
+#[derive(Clone)]
+struct DragPreview {
+    /// The viewport the drag started in, which paints the dragged item itself.
+    viewport_id: ViewportId,
+
+    /// Relative to the pointer.
+    shapes: Arc<Vec<Shape>>,
 }
 
 impl DragAndDrop {
@@ -27,26 +54,94 @@ impl DragAndDrop {
     }
 
     fn end_pass(ctx: &Context) {
-        let abort_dnd =
-            ctx.input(|i| i.pointer.any_released() || i.key_pressed(crate::Key::Escape));
+        let viewport_id = ctx.viewport_id();
+        let (released, escape, pointer_pos, any_down, pointer_outside_app) = ctx.input(|i| {
+            (
+                i.pointer.any_released(),
+                i.key_pressed(crate::Key::Escape),
+                i.pointer.latest_pos(),
+                i.pointer.any_down(),
+                is_pointer_outside_app(i),
+            )
+        });
 
         let mut is_dragging = false;
+        let mut preview = None;
+        let mut os_drag = None;
 
         ctx.data_mut(|data| {
             let state = data.get_temp_mut_or_default::<Self>(Id::NULL);
 
-            if abort_dnd {
+            // If the pointer is gone, it could have just moved to another viewport.
+            // If none of them has claimed it since the last pass, it has left the app.
+            let left_app = pointer_outside_app.unwrap_or_else(|| state.pointer_viewport.is_none());
+
+            if pointer_pos.is_some() {
+                state.pointer_viewport = Some(viewport_id);
+            } else if state.pointer_viewport == Some(viewport_id) {
+                state.pointer_viewport = None;
+            }
+
+            // If the pointer was dragged into another viewport, that is where it is dropped:
+            let released_here =
+                released && state.pointer_viewport.map_or(true, |id| id == viewport_id);
+
+            if escape || released_here {
                 state.payload = None;
+                state.preview = None;
+                state.os_payload = None;
+            }
+
+            // Dragged out of the app? Then the operating system takes over:
+            if state.payload.is_some() && any_down && left_app {
+                os_drag = state.os_payload.take();
+                if os_drag.is_some() {
+                    state.payload = None;
+                    state.preview = None;
+                }
             }
 
             is_dragging = state.payload.is_some();
+
+            if let (Some(pointer_pos), Some(drag_preview)) = (pointer_pos, &state.preview) {
+                if drag_preview.viewport_id != viewport_id {
+                    preview = Some((pointer_pos, drag_preview.shapes.clone()));
+                }
+            }
         });
 
+        if os_drag.is_some() {
+            ctx.output_mut(|o| o.os_drag = os_drag);
+        }
+
         if is_dragging {
             ctx.set_cursor_icon(CursorIcon::Grabbing);
+
+            if let Some((pointer_pos, shapes)) = preview {
+                let layer_id = LayerId::new(Order::Tooltip, Id::new("dnd_preview"));
+                ctx.layer_painter(layer_id)
+                    .extend(shapes.iter().cloned().map(|mut shape| {
+                        shape.translate(pointer_pos.to_vec2());
+                        shape
+                    }));
+            }
         }
     }
 
+    /// Remember what the dragged item looks like, for showing it in other viewports.
+    ///
+    /// The shapes should be relative to the pointer.
+    pub(crate) fn set_preview(ctx: &Context, shapes: Vec<Shape>) {
+        let viewport_id = ctx.viewport_id();
+        ctx.data_mut(|data| {
+            let state = data.get_temp_mut_or_default::<Self>(Id::NULL);
+            state.preview = Some(DragPreview {
+                viewport_id,
+                shapes: Arc::new(shapes),
+            });
+        });
+    }
+
     /// Set a drag-and-drop payload.
     ///
     /// This can be read by [`Self::payload`] until the pointer is released.
@@ -60,11 +155,27 @@ impl DragAndDrop {
         });
     }
 
+    /// Let the current drag continue outside of the app, so it can be dropped into other applications,
+    /// e.g. text into a text field, or files into a file manager.
+    ///
+    /// Call this during the drag, together with [`Self::set_payload`].
+    /// If the pointer leaves the windows of the app before it is released,
+    /// the payload is cleared, and the integration is asked to start a drag-and-drop
+    /// of the operating system with `os_payload` (see [`crate::PlatformOutput::os_drag`]).
+    pub fn set_os_payload(ctx: &Context, os_payload: OsDragPayload) {
+        ctx.data_mut(|data| {
+            let state = data.get_temp_mut_or_default::<Self>(Id::NULL);
+            state.os_payload = Some(os_payload);
+        });
+    }
+
     /// Clears the payload, setting it to `None`.
     pub fn clear_payload(ctx: &Context) {
         ctx.data_mut(|data| {
             let state = data.get_temp_mut_or_default::<Self>(Id::NULL);
             state.payload = None;
+            state.preview = None;
+            state.os_payload = None;
         });
     }
 
@@ -124,3 +235,22 @@ impl DragAndDrop {
         })
     }
 }
+
+/// Is the pointer outside of all the viewports of the app?
+///
+/// `None` if the pointer position is unknown, e.g. because it left the window.
+fn is_pointer_outside_app(input: &crate::InputState) -> Option<bool> {
+    let pos = input.pointer.latest_pos()?;
+    if input.screen_rect().contains(pos) {
+        return Some(false);
+    }
+    let Some(inner_rect) = input.viewport().inner_rect else {
+        return Some(true);
+    };
+    let global_pos = inner_rect.min + pos.to_vec2();
+    Some(!input.raw.viewports.values().any(|viewport| {
+        viewport
+            .inner_rect
+            .is_some_and(|rect| rect.contains(global_pos))
+    }))
+}
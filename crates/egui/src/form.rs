@@ -0,0 +1,330 @@
+//! Forms: groups of input widgets that are validated together before they are submitted.
+//!
+//! Wrap the widgets in a [`Form`], and tell it about each field and whether its value is valid
+//! with [`FormFields::field`]. The form then:
+//! * outlines invalid fields in red and shows their error message below them,
+//!   once the user is done editing them (or tries to submit),
+//! * only reports [`FormResponse::submitted`] when every field is valid,
+//! * focuses (and scrolls to) the first invalid field when the user tries to submit anyway.
+//!
+//! ```
+//! # egui::__run_test_ui(|ui| {
+//! # let mut name = String::new();
+//! # let mut age = 0;
+//! use egui::form;
+//!
+//! let response = egui::Form::new("sign_up").show(ui, |ui, fields| {
+//!     ui.label("Name:");
+//!     let name_response = ui.text_edit_singleline(&mut name);
+//!     fields.field(ui, &name_response, form::required(&name));
+//!
+//!     ui.label("Age:");
+//!     let age_response = ui.add(egui::DragValue::new(&mut age));
+//!     fields.field(ui, &age_response, form::in_range(age, 18..=150));
+//!
+//!     fields.submit_button(ui, "Sign up");
+//! });
+//!
+//! if response.submitted {
+//!     // Everything is valid: save the new user.
+//! }
+//! # });
+//! ```
+//!
+//! A validation is just a `Result<(), String>`, where the error is the message shown to the user,
+//! so you can combine the helpers in this module with [`Result::and_then`], or write your own.
+
+use std::ops::RangeInclusive;
+
+use crate::{id::IdSet, Align, Button, Id, Rect, Response, RichText, Stroke, Ui, WidgetText};
+
+/// A group of input widgets that are validated together, see [the module docs](crate::form).
+#[derive(Clone, Debug)]
+pub struct Form {
+    id_salt: Id,
+    submit_on_enter: bool,
+}
+
+impl Form {
+    /// The id salt must be unique within the parent [`Ui`].
+    pub fn new(id_salt: impl std::hash::Hash) -> Self {
+        Self {
+            id_salt: Id::new(id_salt),
+            submit_on_enter: true,
+        }
+    }
+
+    /// Submit the form when the user presses Enter in one of its single-line text fields.
+    ///
+    /// Default: `true`.
+    #[inline]
+    pub fn submit_on_enter(mut self, submit_on_enter: bool) -> Self {
+        self.submit_on_enter = submit_on_enter;
+        self
+    }
+
+    /// Show the fields of the form.
+    ///
+    /// Register each field with [`FormFields::field`],
+    /// and add a submit button with [`FormFields::submit_button`].
+    pub fn show<R>(
+        self,
+        ui: &mut Ui,
+        add_contents: impl FnOnce(&mut Ui, &mut FormFields) -> R,
+    ) -> FormResponse<R> {
+        let Self {
+            id_salt,
+            submit_on_enter,
+        } = self;
+
+        let id = ui.make_persistent_id(id_salt);
+        let state = ui.data_mut(|data| data.get_temp::<FormState>(id).unwrap_or_default());
+
+        let mut fields = FormFields {
+            state,
+            submit_on_enter,
+            first_invalid: None,
+            num_invalid: 0,
+            submit_requested: false,
+        };
+        let crate::InnerResponse { inner, response } = ui.scope(|ui| add_contents(ui, &mut fields));
+
+        let FormFields {
+            mut state,
+            first_invalid,
+            num_invalid,
+            submit_requested,
+            ..
+        } = fields;
+        let is_valid = num_invalid == 0;
+
+        let submitted = submit_requested && is_valid;
+        if submitted {
+            // Start over, so the errors of an emptied form aren't shown right away:
+            state = FormState::default();
+        } else if submit_requested {
+            state.submit_attempted = true;
+            if let Some((field_id, rect)) = first_invalid {
+                ui.memory_mut(|mem| mem.request_focus(field_id));
+                ui.scroll_to_rect(rect, Some(Align::Center));
+            }
+            ui.ctx().request_discard("Form: show validation errors");
+        }
+
+        ui.data_mut(|data| data.insert_temp(id, state));
+
+        FormResponse {
+            inner,
+            response,
+            submitted,
+            is_valid,
+        }
+    }
+}
+
+/// The state of a [`Form`] that is remembered between passes.
+#[derive(Clone, Debug, Default)]
+struct FormState {
+    /// Fields the user is done editing at least once.
+    ///
+    /// We only show errors for these, so the user isn't told off for fields they haven't filled in yet.
+    touched: IdSet,
+
+    /// Did the user try to submit the form while it was invalid?
+    ///
+    /// Then we show the errors of all fields.
+    submit_attempted: bool,
+}
+
+/// Passed to the contents of a [`Form`], to register its fields.
+pub struct FormFields {
+    state: FormState,
+    submit_on_enter: bool,
+
+    /// The first invalid field, which gets focus if the user tries to submit.
+    first_invalid: Option<(Id, Rect)>,
+
+    num_invalid: usize,
+    submit_requested: bool,
+}
+
+impl FormFields {
+    /// Register the widget in `response` as a field of the form, and whether its value is valid.
+    ///
+    /// Call this right after adding the widget: if it is invalid, the error message
+    /// is shown as a label just after it.
+    ///
+    /// Returns `true` if the field is valid.
+    pub fn field(
+        &mut self,
+        ui: &mut Ui,
+        response: &Response,
+        validation: Result<(), String>,
+    ) -> bool {
+        let id = response.id;
+
+        if response.lost_focus() || (response.changed() && !response.has_focus()) {
+            self.state.touched.insert(id);
+        }
+
+        if self.submit_on_enter
+            && response.lost_focus()
+            && ui.input(|i| i.key_pressed(crate::Key::Enter))
+        {
+            self.submit_requested = true;
+        }
+
+        let Err(message) = validation else {
+            return true;
+        };
+
+        self.num_invalid += 1;
+        if self.first_invalid.is_none() {
+            self.first_invalid = Some((id, response.rect));
+        }
+
+        if self.state.submit_attempted || self.state.touched.contains(&id) {
+            let color = ui.visuals().error_fg_color;
+            ui.painter().rect_stroke(
+                response.rect.expand(1.0),
+                ui.visuals().widgets.inactive.rounding,
+                Stroke::new(1.0, color),
+            );
+            ui.label(RichText::new(message).small().color(color));
+        }
+
+        false
+    }
+
+    /// Ask to submit the form, e.g. from a keyboard shortcut.
+    ///
+    /// It is only submitted if all its fields are valid, see [`FormResponse::submitted`].
+    pub fn submit(&mut self) {
+        self.submit_requested = true;
+    }
+
+    /// Add a button that submits the form when clicked.
+    pub fn submit_button(&mut self, ui: &mut Ui, text: impl Into<WidgetText>) -> Response {
+        let response = ui.add(Button::new(text));
+        if response.clicked() {
+            self.submit();
+        }
+        response
+    }
+
+    /// Are all the fields registered so far valid?
+    pub fn is_valid(&self) -> bool {
+        self.num_invalid == 0
+    }
+}
+
+/// What [`Form::show`] returns.
+#[derive(Debug)]
+pub struct FormResponse<R> {
+    /// What the contents returned.
+    pub inner: R,
+
+    /// The response of the area the form covers.
+    pub response: Response,
+
+    /// The user submitted the form this pass, and all fields were valid.
+    pub submitted: bool,
+
+    /// Are all fields valid?
+    pub is_valid: bool,
+}
+
+// ----------------------------------------------------------------------------
+
+/// Valid if `condition` is true.
+///
+/// # Errors
+/// With `message` if `condition` is false.
+pub fn check(condition: bool, message: impl Into<String>) -> Result<(), String> {
+    if condition {
+        Ok(())
+    } else {
+        Err(message.into())
+    }
+}
+
+/// The text must not be empty (or only whitespace).
+///
+/// # Errors
+/// If the text is blank.
+pub fn required(text: &str) -> Result<(), String> {
+    check(!text.trim().is_empty(), "Required")
+}
+
+/// The text must be at least `min` characters long.
+///
+/// # Errors
+/// If the text is shorter.
+pub fn min_len(text: &str, min: usize) -> Result<(), String> {
+    if min <= text.chars().count() {
+        Ok(())
+    } else {
+        Err(format!("Must be at least {min} characters"))
+    }
+}
+
+/// The text must be at most `max` characters long.
+///
+/// # Errors
+/// If the text is longer.
+pub fn max_len(text: &str, max: usize) -> Result<(), String> {
+    if text.chars().count() <= max {
+        Ok(())
+    } else {
+        Err(format!("Must be at most {max} characters"))
+    }
+}
+
+/// The value must be within `range`.
+///
+/// # Errors
+/// If the value is outside the range.
+pub fn in_range<T: PartialOrd + Copy + std::fmt::Display>(
+    value: T,
+    range: RangeInclusive<T>,
+) -> Result<(), String> {
+    if range.contains(&value) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Must be between {} and {}",
+            range.start(),
+            range.end()
+        ))
+    }
+}
+
+/// The text must parse as a `T`, e.g. a number.
+///
+/// # Errors
+/// If the text doesn't parse.
+pub fn parses<T: std::str::FromStr>(text: &str) -> Result<(), String> {
+    check(text.trim().parse::<T>().is_ok(), "Invalid value")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validators() {
+        assert!(required("x").is_ok());
+        assert_eq!(required("  "), Err("Required".to_owned()));
+        assert!(min_len("åäö", 3).is_ok(), "Counts characters, not bytes");
+        assert!(max_len("åäö", 2).is_err());
+        assert!(in_range(5, 1..=10).is_ok());
+        assert_eq!(
+            in_range(0, 1..=10),
+            Err("Must be between 1 and 10".to_owned())
+        );
+        assert!(parses::<f64>(" 4.2 ").is_ok());
+        assert!(required("hello")
+            .and_then(|()| max_len("hello", 3))
+            .is_err());
+    }
+}
@@ -28,11 +28,13 @@ pub mod kb_shortcuts {
 /// Let the user scale the GUI (change [`Context::zoom_factor`]) by pressing
 /// Cmd+Plus, Cmd+Minus or Cmd+0, just like in a browser.
 ///
+/// A viewport with its own zoom factor (see [`Context::set_zoom_factor_of`]) is zoomed on its own.
+///
 /// By default, [`crate::Context`] calls this function at the end of each frame,
 /// controllable by [`crate::Options::zoom_with_keyboard`].
 pub(crate) fn zoom_with_keyboard(ctx: &Context) {
     if ctx.input_mut(|i| i.consume_shortcut(&kb_shortcuts::ZOOM_RESET)) {
-        ctx.set_zoom_factor(1.0);
+        ctx.set_current_zoom_factor(1.0);
     } else {
         if ctx.input_mut(|i| i.consume_shortcut(&kb_shortcuts::ZOOM_IN))
             || ctx.input_mut(|i| i.consume_shortcut(&kb_shortcuts::ZOOM_IN_SECONDARY))
@@ -54,7 +56,7 @@ pub fn zoom_in(ctx: &Context) {
     zoom_factor += 0.1;
     zoom_factor = zoom_factor.clamp(MIN_ZOOM_FACTOR, MAX_ZOOM_FACTOR);
     zoom_factor = (zoom_factor * 10.).round() / 10.;
-    ctx.set_zoom_factor(zoom_factor);
+    ctx.set_current_zoom_factor(zoom_factor);
 }
 
 /// Make everything smaller by decreasing [`Context::zoom_factor`].
@@ -63,7 +65,7 @@ pub fn zoom_out(ctx: &Context) {
     zoom_factor -= 0.1;
     zoom_factor = zoom_factor.clamp(MIN_ZOOM_FACTOR, MAX_ZOOM_FACTOR);
     zoom_factor = (zoom_factor * 10.).round() / 10.;
-    ctx.set_zoom_factor(zoom_factor);
+    ctx.set_current_zoom_factor(zoom_factor);
 }
 
 /// Show buttons for zooming the ui.
@@ -109,7 +111,7 @@ pub fn zoom_menu_buttons(ui: &mut Ui) {
         )
         .clicked()
     {
-        ui.ctx().set_zoom_factor(1.0);
+        ui.ctx().set_current_zoom_factor(1.0);
         ui.close_menu();
     }
 }
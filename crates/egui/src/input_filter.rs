@@ -0,0 +1,196 @@
+//! Input filters: see and change the input of each pass before any widget does.
+//!
+//! Register one with [`crate::Context::add_input_filter`]:
+//!
+//! ```
+//! # let ctx = egui::Context::default();
+//! // Kiosk mode: nobody gets to press Escape.
+//! ctx.add_input_filter(egui::InputFilter::new("kiosk", |_ctx, input| {
+//!     input.events.retain(|event| {
+//!         !matches!(event, egui::Event::Key { key: egui::Key::Escape, .. })
+//!     });
+//! }));
+//! ```
+
+use std::sync::Arc;
+
+use crate::{Context, RawInput, ViewportId};
+
+/// The callback of an [`InputFilter`].
+///
+/// It can look at, change, add or remove any of the [`RawInput::events`], or anything else in the [`RawInput`].
+/// Use [`RawInput::viewport_id`] to see which viewport the input is for.
+pub type InputFilterCallback = Arc<dyn Fn(&Context, &mut RawInput) + Send + Sync>;
+
+/// Identifies an [`InputFilter`], so it can be removed with [`crate::Context::remove_input_filter`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct InputFilterId(u64);
+
+/// Looks at, and maybe changes, the input of each pass before any widget sees it.
+///
+/// Useful for e.g. global leader keys, blocking keys in kiosk mode, or remapping input.
+///
+/// Filters run in the order of their [`Self::order`], before the [`RawInput`] is turned into
+/// the [`crate::InputState`], so changes to events are also reflected in e.g. [`crate::InputState::key_down`].
+///
+/// The filters are called while no lock is held on the [`Context`], so they may use it freely.
+/// Note however that [`Context::viewport_id`] is not yet set to the viewport of the new pass;
+/// use [`RawInput::viewport_id`] instead.
+#[derive(Clone)]
+pub struct InputFilter {
+    pub debug_name: &'static str,
+
+    /// Filters with a lower order run first, so they see the input before the others.
+    ///
+    /// Filters with the same order run in the order they were added.
+    pub order: i32,
+
+    /// Only filter the input of this viewport, or all of them if `None`.
+    pub viewport: Option<ViewportId>,
+
+    pub callback: InputFilterCallback,
+}
+
+impl std::fmt::Debug for InputFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InputFilter")
+            .field("debug_name", &self.debug_name)
+            .field("order", &self.order)
+            .field("viewport", &self.viewport)
+            .finish_non_exhaustive()
+    }
+}
+
+impl InputFilter {
+    /// A filter for the input of all viewports, with order `0`.
+    pub fn new(
+        debug_name: &'static str,
+        callback: impl Fn(&Context, &mut RawInput) + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            debug_name,
+            order: 0,
+            viewport: None,
+            callback: Arc::new(callback),
+        }
+    }
+
+    /// Filters with a lower order run first.
+    ///
+    /// Default: `0`.
+    #[inline]
+    pub fn order(mut self, order: i32) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Only filter the input of this viewport.
+    ///
+    /// Default: all viewports.
+    #[inline]
+    pub fn viewport(mut self, viewport: ViewportId) -> Self {
+        self.viewport = Some(viewport);
+        self
+    }
+}
+
+/// The registered filters, sorted by their order.
+#[derive(Clone, Default)]
+pub(crate) struct InputFilters {
+    filters: Vec<(InputFilterId, InputFilter)>,
+    next_id: u64,
+}
+
+impl InputFilters {
+    pub fn add(&mut self, filter: InputFilter) -> InputFilterId {
+        let id = InputFilterId(self.next_id);
+        self.next_id += 1;
+        // After all filters with the same order:
+        let index = self
+            .filters
+            .partition_point(|(_, other)| other.order <= filter.order);
+        self.filters.insert(index, (id, filter));
+        id
+    }
+
+    pub fn remove(&mut self, id: InputFilterId) -> bool {
+        let len = self.filters.len();
+        self.filters.retain(|(other, _)| *other != id);
+        self.filters.len() != len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.filters.is_empty()
+    }
+
+    pub fn apply(&self, ctx: &Context, input: &mut RawInput) {
+        crate::profile_function!();
+        for (_, filter) in &self.filters {
+            if filter.viewport.map_or(true, |id| id == input.viewport_id) {
+                crate::profile_scope!("input_filter", filter.debug_name);
+                (filter.callback)(ctx, input);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Event, Key, Modifiers};
+
+    #[test]
+    fn filters_run_in_order() {
+        let ctx = Context::default();
+        let press = |key| Event::Key {
+            key,
+            physical_key: None,
+            pressed: true,
+            repeat: false,
+            modifiers: Modifiers::NONE,
+            scancode: None,
+        };
+
+        // Remap A to B, then block B: A should be blocked too.
+        ctx.add_input_filter(InputFilter::new("block B", |_, input| {
+            input
+                .events
+                .retain(|e| !matches!(e, Event::Key { key: Key::B, .. }));
+        }));
+        let remap = ctx.add_input_filter(
+            InputFilter::new("remap A", |_, input| {
+                for event in &mut input.events {
+                    if let Event::Key { key, .. } = event {
+                        if *key == Key::A {
+                            *key = Key::B;
+                        }
+                    }
+                }
+            })
+            .order(-1),
+        );
+        ctx.add_input_filter(
+            InputFilter::new("other viewport", |_, input| input.events.clear())
+                .viewport(ViewportId::from_hash_of("other")),
+        );
+
+        let run = |events: Vec<Event>| {
+            let input = RawInput {
+                events,
+                ..Default::default()
+            };
+            let mut keys = vec![];
+            let _ = ctx.run(input, |ctx| {
+                keys = ctx.input(|i| i.keys_down.iter().copied().collect::<Vec<_>>());
+            });
+            keys
+        };
+
+        assert_eq!(run(vec![press(Key::A), press(Key::C)]), vec![Key::C]);
+
+        assert!(ctx.remove_input_filter(remap));
+        let mut keys = run(vec![press(Key::A)]);
+        keys.sort();
+        assert_eq!(keys, vec![Key::A, Key::C]);
+    }
+}
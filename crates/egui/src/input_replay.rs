@@ -0,0 +1,191 @@
+//! Record the [`RawInput`] of a session, and replay it later, e.g. as a regression test.
+//!
+//! A replay is deterministic: given the same recording, a fresh [`Context`]
+//! and the same ui code, it produces the same output each time.
+//! To get there, the time seen by egui is mocked: it starts at zero and advances as in the recording.
+//!
+//! With `eframe` you can record the input of your app in `App::raw_input_hook`,
+//! and replay it against the app with `eframe::run_replay`.
+//!
+//! ```
+//! use egui::input_replay::InputRecording;
+//!
+//! let mut recording = InputRecording::default();
+//! for time in [1.0, 1.5] {
+//!     let raw_input = egui::RawInput {
+//!         time: Some(time),
+//!         ..Default::default()
+//!     };
+//!     recording.record(&raw_input);
+//! }
+//! let text = recording.to_ron_string().unwrap();
+//!
+//! let recording = InputRecording::from_ron_str(&text).unwrap();
+//! let outputs = recording.replay(&egui::Context::default(), |ctx| {
+//!     egui::CentralPanel::default().show(ctx, |ui| {
+//!         ui.label(format!("Time: {}", ui.input(|i| i.time)));
+//!     });
+//! });
+//! assert_eq!(outputs.len(), 2);
+//! ```
+
+use std::path::Path;
+
+use crate::{Context, FullOutput, RawInput};
+
+/// An error from saving or loading an [`InputRecording`].
+#[derive(Debug)]
+pub enum RecordingError {
+    /// Failed to read or write the recording file.
+    Io(std::io::Error),
+
+    /// Failed to parse the recording as RON.
+    Ron(ron::error::SpannedError),
+
+    /// Failed to write the recording.
+    Serialize(String),
+}
+
+impl std::fmt::Display for RecordingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "Failed to read or write input recording: {err}"),
+            Self::Ron(err) => write!(f, "Failed to parse input recording: {err}"),
+            Self::Serialize(err) => write!(f, "Failed to write input recording: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for RecordingError {}
+
+/// The [`RawInput`] given to each pass of a [`Context`], in order.
+///
+/// See the [module-level docs](crate::input_replay).
+#[derive(Clone, Debug, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct InputRecording {
+    pub frames: Vec<RawInput>,
+}
+
+impl InputRecording {
+    /// Add the input of a frame, as it is given to [`Context::run`].
+    pub fn record(&mut self, raw_input: &RawInput) {
+        self.frames.push(raw_input.clone());
+    }
+
+    /// The recorded frames, with the time mocked so that it starts at zero.
+    ///
+    /// Frames recorded without a [`RawInput::time`] advance it by [`RawInput::predicted_dt`].
+    pub fn replay_frames(&self) -> impl Iterator<Item = RawInput> + '_ {
+        let mut start_time = None;
+        let mut time = 0.0;
+        self.frames.iter().enumerate().map(move |(i, raw_input)| {
+            let mut raw_input = raw_input.clone();
+            if let Some(recorded_time) = raw_input.time {
+                time = recorded_time - *start_time.get_or_insert(recorded_time);
+            } else if i > 0 {
+                time += raw_input.predicted_dt as f64;
+            }
+            raw_input.time = Some(time);
+            raw_input
+        })
+    }
+
+    /// Run `run_ui` once for each recorded frame, returning what egui output for each of them.
+    ///
+    /// Use a fresh [`Context`] for the replay to be deterministic,
+    /// set up (fonts, style, …) the same way as when it was recorded.
+    pub fn replay(&self, ctx: &Context, mut run_ui: impl FnMut(&Context)) -> Vec<FullOutput> {
+        self.replay_frames()
+            .map(|raw_input| ctx.run(raw_input, &mut run_ui))
+            .collect()
+    }
+
+    /// Parse a recording written with [`Self::to_ron_string`].
+    ///
+    /// # Errors
+    /// If the text is not a valid recording.
+    pub fn from_ron_str(text: &str) -> Result<Self, RecordingError> {
+        ron::from_str(text).map_err(RecordingError::Ron)
+    }
+
+    /// Write the recording as RON.
+    ///
+    /// # Errors
+    /// If the recording can't be represented as RON.
+    pub fn to_ron_string(&self) -> Result<String, RecordingError> {
+        ron::to_string(self).map_err(|err| RecordingError::Serialize(err.to_string()))
+    }
+
+    /// Load a recording file saved with [`Self::save`].
+    ///
+    /// # Errors
+    /// If the file can't be read, or is not a valid recording.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, RecordingError> {
+        let text = std::fs::read_to_string(path).map_err(RecordingError::Io)?;
+        Self::from_ron_str(&text)
+    }
+
+    /// Save the recording as a RON file.
+    ///
+    /// # Errors
+    /// If the file can't be written.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), RecordingError> {
+        let text = self.to_ron_string()?;
+        std::fs::write(path, text).map_err(RecordingError::Io)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{pos2, vec2, Event, PointerButton, Rect};
+
+    #[test]
+    fn replay_click() {
+        let screen_rect = Some(Rect::from_min_size(pos2(0.0, 0.0), vec2(200.0, 200.0)));
+        let pointer = |pressed| Event::PointerButton {
+            pos: pos2(12.0, 12.0),
+            button: PointerButton::Primary,
+            pressed,
+            modifiers: Default::default(),
+        };
+
+        let mut recording = InputRecording::default();
+        for (time, events) in [
+            (100.0, vec![Event::PointerMoved(pos2(12.0, 12.0))]),
+            (100.1, vec![pointer(true)]),
+            (100.2, vec![pointer(false)]),
+            (100.3, vec![]),
+        ] {
+            recording.record(&RawInput {
+                screen_rect,
+                time: Some(time),
+                events,
+                ..Default::default()
+            });
+        }
+        let recording = InputRecording::from_ron_str(&recording.to_ron_string().unwrap()).unwrap();
+
+        let replay = || {
+            let mut clicks = 0;
+            let mut times = vec![];
+            let _ = recording.replay(&Context::default(), |ctx| {
+                times.push(ctx.input(|i| i.time));
+                crate::CentralPanel::default().show(ctx, |ui| {
+                    if ui.button("Click me").clicked() {
+                        clicks += 1;
+                    }
+                });
+            });
+            (clicks, times)
+        };
+
+        let (clicks, times) = replay();
+        assert_eq!(clicks, 1);
+        assert!(
+            (times[3] - 0.3).abs() < 1e-6,
+            "time is mocked to start at zero"
+        );
+        assert_eq!(replay(), (clicks, times), "the replay is deterministic");
+    }
+}
@@ -2,10 +2,11 @@ mod touch_state;
 
 use crate::data::input::{
     Event, EventFilter, KeyboardShortcut, Modifiers, MouseWheelUnit, PointerButton, RawInput,
-    TouchDeviceId, ViewportInfo, NUM_POINTER_BUTTONS,
+    ScrollPhase, TouchDeviceId, ViewportInfo, NUM_POINTER_BUTTONS,
 };
 use crate::{
     emath::{vec2, NumExt, Pos2, Rect, Vec2},
+    style::LongPress,
     util::History,
 };
 use std::{
@@ -17,6 +18,11 @@ pub use crate::Key;
 pub use touch_state::MultiTouchInfo;
 use touch_state::TouchState;
 
+/// How far along a long press (in `0..=1`) before we show the ring filling up around the finger.
+///
+/// This way a quick tap doesn't flash the ring.
+pub(crate) const LONG_PRESS_PEEK_START: f32 = 0.2;
+
 /// Options for input state handling.
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
@@ -26,10 +32,8 @@ pub struct InputOptions {
 
     /// If the pointer is down for longer than this it will no longer register as a click.
     ///
-    /// If a touch is held for this many seconds while still, then it will register as a
-    /// "long-touch" which is equivalent to a secondary click.
-    ///
-    /// This is to support "press and hold for context menu" on touch screens.
+    /// Holding a touch still long enough opens the context menu instead,
+    /// see [`crate::style::LongPress`].
     pub max_click_duration: f64,
 
     /// The new pointer press must come within this many seconds from previous pointer release
@@ -149,6 +153,13 @@ pub struct InputState {
     /// at the end of the frame this will be zero if a scroll-area consumed the delta.
     pub smooth_scroll_delta: Vec2,
 
+    /// Where in a scroll gesture on a precision trackpad we are,
+    /// as of the last [`Event::MouseWheel`].
+    ///
+    /// Use this to e.g. tell scrolling with the fingers on the trackpad
+    /// from the momentum scrolling that follows when they are lifted.
+    pub scroll_phase: ScrollPhase,
+
     /// Zoom scale factor this frame (e.g. from ctrl-scroll or pinch gesture).
     ///
     /// * `zoom = 1`: no change.
@@ -221,13 +232,13 @@ pub struct InputState {
     // The keys that are currently being held down.
     pub keys_down: HashSet<Key>,
 
+    /// The physical keys that are currently being held down.
+    ///
+    /// See [`Event::Key::physical_key`].
+    pub physical_keys_down: HashSet<Key>,
+
     /// In-order events received this frame
     pub events: Vec<Event>,
-
-    /// Input state management configuration.
-    ///
-    /// This gets copied from `egui::Options` at the start of each frame for convenience.
-    input_options: InputOptions,
 }
 
 impl Default for InputState {
@@ -242,6 +253,7 @@ impl Default for InputState {
             unprocessed_scroll_delta_for_zoom: 0.0,
             raw_scroll_delta: Vec2::ZERO,
             smooth_scroll_delta: Vec2::ZERO,
+            scroll_phase: ScrollPhase::Unknown,
             zoom_factor_delta: 1.0,
 
             screen_rect: Rect::from_min_size(Default::default(), vec2(10_000.0, 10_000.0)),
@@ -254,8 +266,8 @@ impl Default for InputState {
             focused: false,
             modifiers: Default::default(),
             keys_down: Default::default(),
+            physical_keys_down: Default::default(),
             events: Default::default(),
-            input_options: Default::default(),
         }
     }
 }
@@ -290,6 +302,7 @@ impl InputState {
         let pointer = self.pointer.begin_pass(time, &new, options);
 
         let mut keys_down = self.keys_down;
+        let mut physical_keys_down = self.physical_keys_down;
         let mut zoom_factor_delta = 1.0; // TODO(emilk): smoothing for zoom factor
         let mut raw_scroll_delta = Vec2::ZERO;
 
@@ -297,11 +310,13 @@ impl InputState {
         let mut unprocessed_scroll_delta_for_zoom = self.unprocessed_scroll_delta_for_zoom;
         let mut smooth_scroll_delta = Vec2::ZERO;
         let mut smooth_scroll_delta_for_zoom = 0.0;
+        let mut scroll_phase = self.scroll_phase;
 
         for event in &mut new.events {
             match event {
                 Event::Key {
                     key,
+                    physical_key,
                     pressed,
                     repeat,
                     ..
@@ -312,12 +327,23 @@ impl InputState {
                     } else {
                         keys_down.remove(key);
                     }
+
+                    if let Some(physical_key) = physical_key {
+                        if *pressed {
+                            physical_keys_down.insert(*physical_key);
+                        } else {
+                            physical_keys_down.remove(physical_key);
+                        }
+                    }
                 }
                 Event::MouseWheel {
                     unit,
                     delta,
                     modifiers,
+                    phase,
                 } => {
+                    scroll_phase = *phase;
+
                     let mut delta = match unit {
                         MouseWheelUnit::Point => *delta,
                         MouseWheelUnit::Line => options.line_scroll_speed * *delta,
@@ -415,6 +441,7 @@ impl InputState {
             unprocessed_scroll_delta_for_zoom,
             raw_scroll_delta,
             smooth_scroll_delta,
+            scroll_phase,
             zoom_factor_delta,
 
             screen_rect,
@@ -427,9 +454,9 @@ impl InputState {
             focused: new.focused,
             modifiers: new.modifiers,
             keys_down,
+            physical_keys_down,
             events: new.events.clone(), // TODO(emilk): remove clone() and use raw.events
             raw: new,
-            input_options: options.input_options.clone(),
         }
     }
 
@@ -502,16 +529,17 @@ impl InputState {
             return Some(Duration::ZERO);
         }
 
-        if self.any_touches() && !self.pointer.is_decidedly_dragging() {
-            // We need to wake up and check for press-and-hold for the context menu.
-            if let Some(press_start_time) = self.pointer.press_start_time {
-                let press_duration = self.time - press_start_time;
-                if self.input_options.max_click_duration.is_finite()
-                    && press_duration < self.input_options.max_click_duration
-                {
-                    let secs_until_menu = self.input_options.max_click_duration - press_duration;
-                    return Some(Duration::from_secs_f64(secs_until_menu));
-                }
+        if self.any_touches() {
+            // We need to wake up and check for press-and-hold for the context menu,
+            // and animate the peek ring while waiting for it.
+            if let Some(progress) = self.pointer.long_press_progress() {
+                let long_press = self.pointer.long_press;
+                let secs_until_next = if long_press.show_peek {
+                    (LONG_PRESS_PEEK_START - progress).max(0.0) * long_press.duration
+                } else {
+                    (1.0 - progress) * long_press.duration
+                };
+                return Some(Duration::from_secs_f32(secs_until_next));
             }
         }
 
@@ -607,6 +635,28 @@ impl InputState {
         self.keys_down.contains(&desired_key)
     }
 
+    /// The state of all connected gamepads (game controllers).
+    ///
+    /// Empty unless the integration supports gamepads, like `eframe` with its `gamepad` feature.
+    /// That also lets the user move the keyboard focus with the D-pad,
+    /// activate widgets with [`crate::GamepadButton::South`] and go back with [`crate::GamepadButton::East`].
+    #[inline]
+    pub fn gamepads(&self) -> &[crate::GamepadState] {
+        &self.raw.gamepads
+    }
+
+    /// Is the key at the given physical location currently held down?
+    ///
+    /// This ignores the keyboard layout, so `key_down_physical(Key::W)` is true
+    /// when the user holds down the key where W is on a QWERTY keyboard,
+    /// which is Z on AZERTY and comma on Dvorak.
+    /// Use this for game controls like WASD, and [`Self::key_down`] for everything else.
+    ///
+    /// Requires the integration to set [`Event::Key::physical_key`], which `eframe` does not (yet) do on web.
+    pub fn key_down_physical(&self, physical_key: Key) -> bool {
+        self.physical_keys_down.contains(&physical_key)
+    }
+
     /// Was the given key released this frame?
     pub fn key_released(&self, desired_key: Key) -> bool {
         self.events.iter().any(|event| {
@@ -848,10 +898,16 @@ pub struct PointerState {
     pub(crate) has_moved_too_much_for_a_click: bool,
 
     /// Did [`Self::is_decidedly_dragging`] go from `false` to `true` this frame?
-    ///
-    /// This could also be the trigger point for a long-touch.
     pub(crate) started_decidedly_dragging: bool,
 
+    /// Set to `true` if the current press can no longer become a long press,
+    /// e.g. because the pointer moved or something was scrolled,
+    /// or because the long press already happened.
+    long_press_cancelled: bool,
+
+    /// Did the current press become a long press this frame?
+    started_long_press: bool,
+
     /// When did the pointer get click last?
     /// Used to check for double-clicks.
     last_click_time: f64,
@@ -871,6 +927,9 @@ pub struct PointerState {
     ///
     /// This gets copied from `egui::Options` at the start of each frame for convenience.
     input_options: InputOptions,
+
+    /// Copied from [`crate::style::Interaction::long_press`] at the start of each frame.
+    long_press: LongPress,
 }
 
 impl Default for PointerState {
@@ -889,11 +948,14 @@ impl Default for PointerState {
             press_start_time: None,
             has_moved_too_much_for_a_click: false,
             started_decidedly_dragging: false,
+            long_press_cancelled: false,
+            started_long_press: false,
             last_click_time: std::f64::NEG_INFINITY,
             last_last_click_time: std::f64::NEG_INFINITY,
             last_move_time: std::f64::NEG_INFINITY,
             pointer_events: vec![],
             input_options: Default::default(),
+            long_press: Default::default(),
         }
     }
 }
@@ -910,6 +972,7 @@ impl PointerState {
 
         self.time = time;
         self.input_options = options.input_options.clone();
+        self.long_press = options.style().interaction.long_press;
 
         self.pointer_events.clear();
 
@@ -930,6 +993,8 @@ impl PointerState {
                     if let Some(press_origin) = self.press_origin {
                         self.has_moved_too_much_for_a_click |=
                             press_origin.distance(pos) > self.input_options.max_click_dist;
+                        self.long_press_cancelled |=
+                            press_origin.distance(pos) > self.long_press.max_dist;
                     }
 
                     self.pointer_events.push(PointerEvent::Moved(pos));
@@ -958,6 +1023,7 @@ impl PointerState {
                         self.press_origin = Some(pos);
                         self.press_start_time = Some(time);
                         self.has_moved_too_much_for_a_click = false;
+                        self.long_press_cancelled = false;
                         self.pointer_events.push(PointerEvent::Pressed {
                             position: pos,
                             button,
@@ -1008,6 +1074,10 @@ impl PointerState {
                     self.pos_history.clear();
                 }
                 Event::MouseMoved(delta) => *self.motion.get_or_insert(Vec2::ZERO) += *delta,
+                Event::MouseWheel { .. } | Event::Zoom(_) => {
+                    // The user wants to scroll or zoom, not open a context menu.
+                    self.long_press_cancelled = true;
+                }
                 _ => {}
             }
         }
@@ -1041,6 +1111,11 @@ impl PointerState {
 
         self.started_decidedly_dragging = self.is_decidedly_dragging() && !was_decidedly_dragging;
 
+        self.started_long_press = self.long_press_progress() == Some(1.0);
+        if self.started_long_press {
+            self.long_press_cancelled = true; // Only fire once per press
+        }
+
         self
     }
 
@@ -1299,13 +1374,26 @@ impl PointerState {
     /// to trigger a secondary click (context menu).
     ///
     /// Returns `true` only on one frame.
+    /// See [`crate::style::LongPress`].
     pub(crate) fn is_long_press(&self) -> bool {
-        self.started_decidedly_dragging
-            && !self.has_moved_too_much_for_a_click
-            && self.button_down(PointerButton::Primary)
-            && self.press_start_time.map_or(false, |press_start_time| {
-                self.time - press_start_time > self.input_options.max_click_duration
-            })
+        self.started_long_press
+    }
+
+    /// How far along the current press is towards becoming a long press, in `0..=1`.
+    ///
+    /// `None` if the primary button isn't down, or if the press can no longer become a long press
+    /// (e.g. because the pointer moved, or the long press already happened).
+    pub fn long_press_progress(&self) -> Option<f32> {
+        if self.long_press_cancelled
+            || !self.long_press.duration.is_finite()
+            || self.long_press.duration <= 0.0
+            || !self.button_down(PointerButton::Primary)
+            || self.down.iter().filter(|down| **down).count() != 1
+        {
+            return None;
+        }
+        let press_duration = self.time - self.press_start_time?;
+        Some((press_duration as f32 / self.long_press.duration).clamp(0.0, 1.0))
     }
 
     /// Is the primary button currently down?
@@ -1339,6 +1427,7 @@ impl InputState {
             unprocessed_scroll_delta_for_zoom,
             raw_scroll_delta,
             smooth_scroll_delta,
+            scroll_phase,
 
             zoom_factor_delta,
             screen_rect,
@@ -1351,8 +1440,8 @@ impl InputState {
             focused,
             modifiers,
             keys_down,
+            physical_keys_down,
             events,
-            input_options: _,
         } = self;
 
         ui.style_mut()
@@ -1391,6 +1480,7 @@ impl InputState {
         ui.label(format!(
             "smooth_scroll_delta: {smooth_scroll_delta:?} points"
         ));
+        ui.label(format!("scroll_phase: {scroll_phase:?}"));
         ui.label(format!("zoom_factor_delta: {zoom_factor_delta:4.2}x"));
 
         ui.label(format!("screen_rect: {screen_rect:?} points"));
@@ -1410,6 +1500,7 @@ impl InputState {
         ui.label(format!("focused:   {focused}"));
         ui.label(format!("modifiers: {modifiers:#?}"));
         ui.label(format!("keys_down: {keys_down:?}"));
+        ui.label(format!("physical_keys_down: {physical_keys_down:?}"));
         ui.scope(|ui| {
             ui.set_min_height(150.0);
             ui.label(format!("events: {events:#?}"))
@@ -1434,11 +1525,14 @@ impl PointerState {
             press_start_time,
             has_moved_too_much_for_a_click,
             started_decidedly_dragging,
+            long_press_cancelled,
+            started_long_press,
             last_click_time,
             last_last_click_time,
             pointer_events,
             last_move_time,
             input_options: _,
+            long_press: _,
         } = self;
 
         ui.label(format!("latest_pos: {latest_pos:?}"));
@@ -1459,9 +1553,78 @@ impl PointerState {
         ui.label(format!(
             "started_decidedly_dragging: {started_decidedly_dragging}"
         ));
+        ui.label(format!("long_press_cancelled: {long_press_cancelled}"));
+        ui.label(format!("started_long_press: {started_long_press}"));
         ui.label(format!("last_click_time: {last_click_time:#?}"));
         ui.label(format!("last_last_click_time: {last_last_click_time:#?}"));
         ui.label(format!("last_move_time: {last_move_time:#?}"));
         ui.label(format!("pointer_events: {pointer_events:?}"));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pass(pointer: PointerState, time: f64, events: Vec<Event>) -> PointerState {
+        let new = RawInput {
+            events,
+            ..Default::default()
+        };
+        pointer.begin_pass(time, &new, &crate::Options::default())
+    }
+
+    fn press(pos: Pos2) -> Event {
+        Event::PointerButton {
+            pos,
+            button: PointerButton::Primary,
+            pressed: true,
+            modifiers: Modifiers::NONE,
+        }
+    }
+
+    #[test]
+    fn long_press() {
+        let duration = LongPress::default().duration as f64;
+        let origin = Pos2::new(10.0, 10.0);
+
+        let pointer = pass(PointerState::default(), 0.0, vec![press(origin)]);
+        assert_eq!(pointer.long_press_progress(), Some(0.0));
+
+        let pointer = pass(pointer, 0.5 * duration, vec![]);
+        assert_eq!(pointer.long_press_progress(), Some(0.5));
+        assert!(!pointer.is_long_press());
+
+        let pointer = pass(pointer, duration, vec![]);
+        assert!(pointer.is_long_press());
+
+        let pointer = pass(pointer, 2.0 * duration, vec![]);
+        assert!(!pointer.is_long_press(), "Only fires once");
+        assert_eq!(pointer.long_press_progress(), None);
+
+        // Moving too far cancels it:
+        let pointer = pass(PointerState::default(), 0.0, vec![press(origin)]);
+        let pointer = pass(
+            pointer,
+            0.5 * duration,
+            vec![Event::PointerMoved(origin + vec2(20.0, 0.0))],
+        );
+        let pointer = pass(pointer, duration, vec![]);
+        assert!(!pointer.is_long_press());
+
+        // So does scrolling:
+        let pointer = pass(PointerState::default(), 0.0, vec![press(origin)]);
+        let pointer = pass(
+            pointer,
+            0.5 * duration,
+            vec![Event::MouseWheel {
+                unit: MouseWheelUnit::Point,
+                delta: vec2(0.0, 5.0),
+                modifiers: Modifiers::NONE,
+                phase: ScrollPhase::Moved,
+            }],
+        );
+        let pointer = pass(pointer, duration, vec![]);
+        assert!(!pointer.is_long_press());
+    }
+}
@@ -144,6 +144,7 @@ impl Widget for &mut epaint::TessellationOptions {
                 coarse_tessellation_culling,
                 prerasterized_discs,
                 round_text_to_pixels,
+                round_vertices_to_pixels,
                 debug_paint_clip_rects,
                 debug_paint_text_rects,
                 debug_ignore_clip_rects,
@@ -164,6 +165,9 @@ impl Widget for &mut epaint::TessellationOptions {
 
             ui.checkbox(prerasterized_discs, "Speed up filled circles with pre-rasterization");
 
+            ui.checkbox(round_vertices_to_pixels, "Snap vertices to pixel grid")
+                .on_hover_text("For hard, pixel-perfect edges. Best used with feathering off.");
+
             ui.horizontal(|ui| {
                 ui.label("Spline tolerance");
                 let speed = 0.01 * *bezier_tolerance;
@@ -395,13 +395,18 @@
 mod animation_manager;
 pub mod containers;
 mod context;
+pub mod context_help;
 mod data;
 pub mod debug_text;
 mod drag_and_drop;
+pub mod form;
 pub(crate) mod grid;
 pub mod gui_zoom;
 mod hit_test;
 mod id;
+pub mod input_filter;
+#[cfg(feature = "input_replay")]
+pub mod input_replay;
 mod input_state;
 mod interaction;
 pub mod introspection;
@@ -414,18 +419,23 @@ pub mod os;
 mod painter;
 mod pass_state;
 pub(crate) mod placer;
+pub mod quantity;
 mod response;
 mod sense;
+mod shortcuts;
 pub mod style;
 pub mod text_selection;
+pub mod toasts;
 mod ui;
 mod ui_builder;
 mod ui_stack;
+mod ui_tree;
 pub mod util;
 pub mod viewport;
 mod widget_rect;
 pub mod widget_text;
 pub mod widgets;
+mod zoom_handler;
 
 #[cfg(feature = "callstack")]
 #[cfg(debug_assertions)]
@@ -450,7 +460,10 @@ pub use emath::{
 pub use epaint::{
     mutex,
     text::{FontData, FontDefinitions, FontFamily, FontId, FontTweak},
-    textures::{TextureFilter, TextureOptions, TextureWrapMode, TexturesDelta},
+    textures::{
+        TextureFilter, TextureMemory, TextureMemoryUse, TextureOptions, TextureWrapMode,
+        TexturesDelta,
+    },
     ClippedPrimitive, ColorImage, FontImage, ImageData, Margin, Mesh, PaintCallback,
     PaintCallbackInfo, Rounding, Shadow, Shape, Stroke, TextureHandle, TextureId,
 };
@@ -458,25 +471,30 @@ pub use epaint::{
 pub mod text {
     pub use crate::text_selection::{CCursorRange, CursorRange};
     pub use epaint::text::{
-        cursor::CCursor, FontData, FontDefinitions, FontFamily, Fonts, Galley, LayoutJob,
-        LayoutSection, TextFormat, TextWrapping, TAB_SIZE,
+        cursor::CCursor, FontData, FontDefinitions, FontFamily, FontHinting, FontRasterization,
+        Fonts, Galley, GlyphRendering, LayoutJob, LayoutSection, TextFormat, TextLocale,
+        TextWrapping, TAB_SIZE,
     };
 }
 
 pub use self::{
     containers::*,
     context::{Context, RepaintCause, RequestRepaintInfo},
+    context_help::{ContextHelp, HelpEntry},
     data::{
         input::*,
         output::{
-            self, CursorIcon, FullOutput, OpenUrl, PlatformOutput, UserAttentionType, WidgetInfo,
+            self, ClipboardData, CursorIcon, FullOutput, LiveRegion, OpenUrl, OpenUrlPolicy,
+            OsDragPayload, PlatformOutput, UserAttentionType, WidgetInfo,
         },
         Key,
     },
     drag_and_drop::DragAndDrop,
     epaint::text::TextWrapMode,
+    form::{Form, FormFields, FormResponse},
     grid::Grid,
     id::{Id, IdMap},
+    input_filter::{InputFilter, InputFilterId},
     input_state::{InputState, MultiTouchInfo, PointerState},
     layers::{LayerId, Order},
     layout::*,
@@ -485,17 +503,24 @@ pub use self::{
     painter::Painter,
     response::{InnerResponse, Response},
     sense::Sense,
+    shortcuts::{ShortcutAction, ShortcutConflict, Shortcuts},
     style::{FontSelection, Spacing, Style, TextStyle, Visuals},
     text::{Galley, TextFormat},
+    toasts::{Toast, ToastKind, Toasts},
     ui::Ui,
     ui_builder::UiBuilder,
     ui_stack::*,
+    ui_tree::{UiTreeLayer, UiTreeNode, UiTreeSnapshot},
     viewport::*,
     widget_rect::{WidgetRect, WidgetRects},
     widget_text::{RichText, WidgetText},
     widgets::*,
+    zoom_handler::ZoomHandler,
 };
 
+#[cfg(feature = "persistence")]
+pub use memory::UiStateSnapshot;
+
 // ----------------------------------------------------------------------------
 
 /// Helper function that adds a label when compiling with debug assertions enabled.
@@ -10,7 +10,11 @@ use crate::{
     ViewportId, ViewportIdMap, ViewportIdSet,
 };
 
+#[cfg(feature = "persistence")]
+mod snapshot;
 mod theme;
+#[cfg(feature = "persistence")]
+pub use snapshot::UiStateSnapshot;
 pub use theme::{Theme, ThemePreference};
 
 // ----------------------------------------------------------------------------
@@ -46,6 +50,11 @@ pub struct Memory {
     /// To store a state common for all your widgets (a singleton), use [`Id::NULL`] as the key.
     pub data: crate::util::IdTypeMap,
 
+    /// The keyboard shortcuts of the app, and the ones the user picked instead.
+    ///
+    /// See [`crate::Shortcuts`].
+    pub shortcuts: crate::Shortcuts,
+
     // ------------------------------------------
     /// Can be used to cache computations from one frame to another.
     ///
@@ -114,6 +123,7 @@ impl Default for Memory {
         let mut slf = Self {
             options: Default::default(),
             data: Default::default(),
+            shortcuts: Default::default(),
             caches: Default::default(),
             new_font_definitions: Default::default(),
             interactions: Default::default(),
@@ -227,6 +237,22 @@ pub struct Options {
     /// Controls the tessellator.
     pub tessellation_options: epaint::TessellationOptions,
 
+    /// Pixel-perfect rendering, for pixel-art UIs that should look exactly like their source images.
+    ///
+    /// When on, egui guarantees that:
+    /// * feathering (anti-aliasing) is off, so edges are never blended with the background,
+    /// * all painted vertices and clip rectangles are snapped to whole physical pixels,
+    /// * the rectangles allocated for widgets are rounded to whole physical pixels,
+    /// * [`crate::Image`]s use [`crate::TextureOptions::NEAREST`]
+    ///   unless they set [`crate::Image::texture_options`] themselves.
+    ///
+    /// The result is only truly 1:1 if the `pixels_per_point` (including the [`Self::zoom_factor`])
+    /// is a whole number, and images are shown at a whole multiple of their size.
+    /// Text is still rasterized by the font rasterizer, so it may contain gray pixels.
+    ///
+    /// Default: `false`.
+    pub pixel_perfect: bool,
+
     /// If any widget moves or changes id, repaint everything.
     ///
     /// It is recommended you keep this OFF, as it may
@@ -270,11 +296,53 @@ pub struct Options {
     /// if you are changing [`Style::text_styles`], or have a lot of text styles.
     pub preload_font_glyphs: bool,
 
+    /// Rasterize at most this many new glyphs into the font atlas per pass, or `None` for no limit.
+    ///
+    /// When e.g. the zoom changes, or a lot of CJK text is shown for the first time,
+    /// many glyphs need to be rasterized at once, causing a visible hitch.
+    /// With a limit, the work is spread over several frames instead,
+    /// and glyphs that didn't make it are invisible until then.
+    /// egui requests a repaint until all glyphs are in place.
+    ///
+    /// The first pass is never limited, so the app starts out with all its text.
+    /// When limited, [`Self::preload_font_glyphs`] only applies to the first pass.
+    ///
+    /// Default is `Some(256)`.
+    pub max_new_glyphs_per_pass: Option<usize>,
+
     /// Check reusing of [`Id`]s, and show a visual warning on screen when one is found.
     ///
     /// By default this is `true` in debug builds.
     pub warn_on_id_clash: bool,
 
+    /// If `true`, tooltips that don't fit in the window are shown in viewports of their own,
+    /// so they can extend beyond the window like native menus.
+    ///
+    /// This only has an effect if the integration supports multiple viewports
+    /// (see [`crate::Context::embed_viewports`]).
+    ///
+    /// Default is `false`.
+    pub tooltips_in_viewports: bool,
+
+    /// If `true`, egui records which [`crate::Ui`] each widget is in, and what each widget is
+    /// (its [`crate::WidgetInfo`]), so that [`crate::Context::ui_tree_snapshot`] can describe the whole UI.
+    ///
+    /// This costs a bit of performance, so only turn it on when needed, e.g. in tests or when inspecting the UI.
+    ///
+    /// Default is `false`.
+    pub record_ui_tree: bool,
+
+    /// If `false`, no [`crate::TextEdit`] uses the platform's input method editor (IME),
+    /// and the integration is told to turn it off in all viewports (see [`crate::PlatformOutput::ime`]).
+    ///
+    /// Set this to `false` temporarily while keys are used as shortcuts rather than for typing,
+    /// e.g. in a game, so that the IME doesn't swallow them.
+    /// To turn off the IME for a single [`crate::TextEdit`], use [`crate::TextEdit::ime`] instead.
+    ///
+    /// Default is `true`.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub ime_allowed: bool,
+
     // ------------------------------
     // Input:
     /// Multiplier for the scroll speed when reported in [`crate::MouseWheelUnit::Line`]s.
@@ -319,11 +387,16 @@ impl Default for Options {
             zoom_factor: 1.0,
             zoom_with_keyboard: true,
             tessellation_options: Default::default(),
+            pixel_perfect: false,
             repaint_on_widget_change: false,
             max_passes: NonZeroUsize::new(2).unwrap(),
             screen_reader: false,
             preload_font_glyphs: true,
+            max_new_glyphs_per_pass: Some(256),
             warn_on_id_clash: cfg!(debug_assertions),
+            tooltips_in_viewports: false,
+            record_ui_tree: false,
+            ime_allowed: true,
 
             // Input:
             line_scroll_speed,
@@ -377,11 +450,16 @@ impl Options {
             zoom_factor: _, // TODO(emilk)
             zoom_with_keyboard,
             tessellation_options,
+            pixel_perfect,
             repaint_on_widget_change,
             max_passes,
             screen_reader: _, // needs to come from the integration
             preload_font_glyphs: _,
+            max_new_glyphs_per_pass,
             warn_on_id_clash,
+            tooltips_in_viewports,
+            record_ui_tree: _,
+            ime_allowed,
 
             line_scroll_speed,
             scroll_zoom_speed,
@@ -412,6 +490,26 @@ impl Options {
 
                 ui.checkbox(warn_on_id_clash, "Warn if two widgets have the same Id");
 
+                ui.horizontal(|ui| {
+                    let mut limit = max_new_glyphs_per_pass.is_some();
+                    ui.checkbox(&mut limit, "Max new glyphs per pass:")
+                        .on_hover_text("Spread out rasterizing text over several frames, to avoid hitches when zooming");
+                    match (limit, max_new_glyphs_per_pass.as_mut()) {
+                        (true, Some(max)) => {
+                            ui.add(crate::DragValue::new(max).range(1..=10_000));
+                        }
+                        (true, None) => *max_new_glyphs_per_pass = Some(256),
+                        (false, _) => *max_new_glyphs_per_pass = None,
+                    }
+                });
+
+                ui.checkbox(
+                    tooltips_in_viewports,
+                    "Show tooltips that don't fit in the window in viewports of their own",
+                );
+
+                ui.checkbox(ime_allowed, "Allow IME (input method editor)");
+
                 ui.checkbox(reduce_texture_memory, "Reduce texture memory");
             });
 
@@ -430,6 +528,8 @@ impl Options {
         CollapsingHeader::new("✒ Painting")
             .default_open(false)
             .show(ui, |ui| {
+                ui.checkbox(pixel_perfect, "Pixel-perfect rendering")
+                    .on_hover_text("Snap everything to whole pixels, without anti-aliasing");
                 tessellation_options.ui(ui);
                 ui.vertical_centered(|ui| {
                     crate::reset_button(ui, tessellation_options, "Reset paint settings");
@@ -0,0 +1,74 @@
+use crate::{util::IdTypeMap, Id, ViewportIdMap};
+
+use super::{Areas, FocusWidget, Memory};
+
+/// The state of the UI that the user can change by interacting with it,
+/// captured with [`crate::Context::snapshot_ui_state`].
+///
+/// This includes:
+/// * How far each [`crate::ScrollArea`] is scrolled
+/// * Which [`crate::CollapsingHeader`]s are open
+/// * The position, size and stacking order of each [`crate::Window`] and [`crate::Area`]
+/// * Which widget has keyboard focus, and the cursor of each [`crate::TextEdit`]
+/// * Anything else widgets store with [`IdTypeMap::insert_persisted`]
+///
+/// It does not include your own application state, [`crate::Style`] or [`super::Options`].
+///
+/// Restore it with [`crate::Context::restore_ui_state`], e.g. to resume where the user left off,
+/// or to switch between several saved layouts.
+///
+/// It can be serialized with any `serde` format, or to compact RON with [`Self::to_ron`].
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct UiStateSnapshot {
+    data: IdTypeMap,
+    areas: ViewportIdMap<Areas>,
+    focus: ViewportIdMap<Id>,
+}
+
+impl UiStateSnapshot {
+    pub(crate) fn capture(memory: &Memory) -> Self {
+        Self {
+            data: memory.data.clone_persisted(),
+            areas: memory.areas.clone(),
+            focus: memory
+                .focus
+                .iter()
+                .filter_map(|(viewport_id, focus)| Some((*viewport_id, focus.focused()?)))
+                .collect(),
+        }
+    }
+
+    pub(crate) fn restore(&self, memory: &mut Memory) {
+        let Self { data, areas, focus } = self;
+
+        memory.data.replace_persisted(data);
+        memory.areas.clone_from(areas);
+
+        for (viewport_id, viewport_focus) in &mut memory.focus {
+            if !focus.contains_key(viewport_id) {
+                viewport_focus.focused_widget = None;
+            }
+        }
+        for (viewport_id, id) in focus {
+            memory.focus.entry(*viewport_id).or_default().focused_widget =
+                Some(FocusWidget::new(*id));
+        }
+    }
+
+    /// Serialize to a compact RON string.
+    ///
+    /// # Errors
+    /// If a stored value fails to serialize.
+    pub fn to_ron(&self) -> Result<String, ron::Error> {
+        ron::to_string(self)
+    }
+
+    /// Deserialize from a string created with [`Self::to_ron`].
+    ///
+    /// # Errors
+    /// If the string is not a valid snapshot.
+    pub fn from_ron(ron: &str) -> Result<Self, ron::error::SpannedError> {
+        ron::from_str(ron)
+    }
+}
@@ -22,8 +22,8 @@ use super::{
 use crate::{
     epaint, vec2,
     widgets::{Button, ImageButton},
-    Align2, Area, Color32, Frame, Key, LayerId, Layout, NumExt, Order, Stroke, Style, TextWrapMode,
-    UiKind, WidgetText,
+    Align2, Area, Color32, Frame, Key, LayerId, Layout, NumExt, Order, PopupSizing, Stroke, Style,
+    TextWrapMode, UiKind, WidgetText,
 };
 use epaint::mutex::RwLock;
 use std::sync::Arc;
@@ -50,8 +50,21 @@ impl BarState {
         &mut self,
         button: &Response,
         add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> Option<InnerResponse<R>> {
+        self.bar_menu_with_sizing(button, PopupSizing::default(), add_contents)
+    }
+
+    /// Like [`Self::bar_menu`], with the given sizing for the menu and its sub-menus.
+    pub fn bar_menu_with_sizing<R>(
+        &mut self,
+        button: &Response,
+        sizing: PopupSizing,
+        add_contents: impl FnOnce(&mut Ui) -> R,
     ) -> Option<InnerResponse<R>> {
         MenuRoot::stationary_click_interaction(button, &mut self.open_menu);
+        if let Some(root) = &self.open_menu.inner {
+            root.menu_state.write().sizing = sizing;
+        }
         self.open_menu.show(button, add_contents)
     }
 
@@ -107,7 +120,35 @@ pub fn menu_button<R>(
     title: impl Into<WidgetText>,
     add_contents: impl FnOnce(&mut Ui) -> R,
 ) -> InnerResponse<Option<R>> {
-    stationary_menu_impl(ui, title, Box::new(add_contents))
+    stationary_menu_impl(ui, title, PopupSizing::default(), Box::new(add_contents))
+}
+
+/// Like [`menu_button`], but controls how wide the menu may get and what happens to long items.
+///
+/// The menu grows as wide as its items need, up to [`PopupSizing::max_width`].
+/// Items that are wider than that are wrapped or elided, according to [`PopupSizing::wrap_mode`].
+/// Sub-menus use the same sizing.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// let sizing = egui::PopupSizing {
+///     max_width: Some(250.0),
+///     wrap_mode: Some(egui::TextWrapMode::Truncate),
+/// };
+/// egui::menu::menu_button_with_sizing(ui, "Recent", sizing, |ui| {
+///     if ui.button("/a/very/long/path/to/some/file/that/was/opened/recently.txt").clicked() {
+///         // …
+///     }
+/// });
+/// # });
+/// ```
+pub fn menu_button_with_sizing<R>(
+    ui: &mut Ui,
+    title: impl Into<WidgetText>,
+    sizing: PopupSizing,
+    add_contents: impl FnOnce(&mut Ui) -> R,
+) -> InnerResponse<Option<R>> {
+    stationary_menu_impl(ui, title, sizing, Box::new(add_contents))
 }
 
 /// Construct a top level menu with a custom button in a menu bar.
@@ -163,10 +204,10 @@ fn menu_popup<'c, R>(
     menu_id: Id,
     add_contents: impl FnOnce(&mut Ui) -> R + 'c,
 ) -> InnerResponse<R> {
-    let pos = {
+    let (pos, sizing) = {
         let mut menu_state = menu_state_arc.write();
         menu_state.entry_count = 0;
-        menu_state.rect.min
+        (menu_state.rect.min, menu_state.sizing)
     };
 
     let area_id = menu_id.with("__menu");
@@ -183,6 +224,10 @@ fn menu_popup<'c, R>(
         .kind(UiKind::Menu)
         .order(Order::Foreground)
         .fixed_pos(pos)
+        .constrain_to(crate::popup::popup_constrain_rect(
+            ctx,
+            Rect::from_min_size(pos, Vec2::ZERO),
+        ))
         .default_width(ctx.style().spacing.menu_width)
         .sense(Sense::hover());
 
@@ -196,8 +241,11 @@ fn menu_popup<'c, R>(
         Frame::menu(ui.style())
             .show(ui, |ui| {
                 ui.set_menu_state(Some(menu_state_arc.clone()));
-                ui.with_layout(Layout::top_down_justified(Align::LEFT), add_contents)
-                    .inner
+                ui.with_layout(Layout::top_down_justified(Align::LEFT), |ui| {
+                    sizing.apply(ui);
+                    add_contents(ui)
+                })
+                .inner
             })
             .inner
     });
@@ -224,6 +272,7 @@ fn menu_popup<'c, R>(
 fn stationary_menu_impl<'c, R>(
     ui: &mut Ui,
     title: impl Into<WidgetText>,
+    sizing: PopupSizing,
     add_contents: Box<dyn FnOnce(&mut Ui) -> R + 'c>,
 ) -> InnerResponse<Option<R>> {
     let title = title.into();
@@ -240,7 +289,7 @@ fn stationary_menu_impl<'c, R>(
     }
 
     let button_response = ui.add(button);
-    let inner = bar_state.bar_menu(&button_response, add_contents);
+    let inner = bar_state.bar_menu_with_sizing(&button_response, sizing, add_contents);
 
     bar_state.store(ui.ctx(), bar_id);
     InnerResponse::new(inner.map(|r| r.inner), button_response)
@@ -644,6 +693,9 @@ pub struct MenuState {
 
     /// Used to hash different [`Id`]s for sub-menus
     entry_count: usize,
+
+    /// How wide this menu may get, inherited by its sub-menus.
+    pub sizing: PopupSizing,
 }
 
 impl MenuState {
@@ -653,6 +705,7 @@ impl MenuState {
             sub_menu: None,
             response: MenuResponse::Stay,
             entry_count: 0,
+            sizing: PopupSizing::default(),
         }
     }
 
@@ -768,7 +821,11 @@ impl MenuState {
     /// Open submenu at position, if not already open.
     fn open_submenu(&mut self, id: Id, pos: Pos2) {
         if !self.is_open(id) {
-            self.sub_menu = Some((id, Arc::new(RwLock::new(Self::new(pos)))));
+            let sub_menu = Self {
+                sizing: self.sizing,
+                ..Self::new(pos)
+            };
+            self.sub_menu = Some((id, Arc::new(RwLock::new(sub_menu))));
         }
     }
 
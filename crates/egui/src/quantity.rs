@@ -0,0 +1,864 @@
+//! Formatting and parsing of numbers with units, see [`Quantity`].
+//!
+//! ```
+//! use egui::quantity::{NumberLocale, Quantity};
+//!
+//! let locale = NumberLocale::ENGLISH;
+//! assert_eq!(Quantity::si("Hz").format(1500.0, 0..=3, &locale), "1.5 kHz");
+//! assert_eq!(Quantity::bytes().format(2.5e9, 0..=1, &locale), "2.5 GB");
+//! assert_eq!(Quantity::Duration.format(3723.0, 0..=0, &locale), "1h 02m 03s");
+//! assert_eq!(Quantity::Percent.format(0.425, 0..=3, &locale), "42.5%");
+//! assert_eq!(Quantity::Number.format(1234567.0, 0..=0, &locale), "1,234,567");
+//!
+//! assert_eq!(Quantity::si("Hz").parse("1.5 kHz", &locale), Some(1500.0));
+//! ```
+//!
+//! What the user types into a [`crate::DragValue`] or [`crate::Slider`] may also be a bit of math,
+//! with [`Units`] after the numbers, like `2*pi` or `1 m + 20 cm`. See [`eval_expression`].
+
+use std::ops::RangeInclusive;
+
+use crate::style::NumberFormatter;
+
+/// How the digits of a number are written, e.g. `1,234.5` or `1.234,5`.
+///
+/// Used by [`Quantity`]. The one egui uses is [`crate::Style::number_locale`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct NumberLocale {
+    /// Separates the integer part from the decimals, e.g. `.` or `,`.
+    pub decimal_separator: char,
+
+    /// Put between each group of three digits in the integer part, e.g. `,` or `.`.
+    ///
+    /// `None` means no grouping: `1234567`.
+    pub thousands_separator: Option<char>,
+}
+
+impl Default for NumberLocale {
+    /// [`Self::PLAIN`]
+    fn default() -> Self {
+        Self::PLAIN
+    }
+}
+
+impl NumberLocale {
+    /// `1234567.5` (the way Rust formats numbers).
+    pub const PLAIN: Self = Self {
+        decimal_separator: '.',
+        thousands_separator: None,
+    };
+
+    /// `1,234,567.5`
+    pub const ENGLISH: Self = Self {
+        decimal_separator: '.',
+        thousands_separator: Some(','),
+    };
+
+    /// `1.234.567,5`
+    pub const GERMAN: Self = Self {
+        decimal_separator: ',',
+        thousands_separator: Some('.'),
+    };
+
+    /// `1 234 567,5`
+    pub const FRENCH: Self = Self {
+        decimal_separator: ',',
+        thousands_separator: Some(' '),
+    };
+
+    /// `1'234'567.5`
+    pub const SWISS: Self = Self {
+        decimal_separator: '.',
+        thousands_separator: Some('\''),
+    };
+
+    /// Format a number with the given range of decimals (see [`NumberFormatter::format`]) in this locale.
+    pub fn format(&self, value: f64, decimals: RangeInclusive<usize>) -> String {
+        self.localize(&emath::format_with_decimals_in_range(value, decimals))
+    }
+
+    /// Parse a number written in this locale.
+    ///
+    /// Whitespace is ignored, and so is the thousands separator.
+    pub fn parse(&self, text: &str) -> Option<f64> {
+        let text: String = text
+            .chars()
+            .filter(|&c| !c.is_whitespace() && Some(c) != self.thousands_separator)
+            .map(|c| match c {
+                '−' => '-',
+                c if c == self.decimal_separator => '.',
+                c => c,
+            })
+            .collect();
+        text.parse().ok()
+    }
+
+    /// Convert the output of Rust's number formatting (e.g. `-1234.5`) to this locale.
+    fn localize(&self, text: &str) -> String {
+        let (sign, unsigned) = match text.strip_prefix('-') {
+            Some(unsigned) => ("-", unsigned),
+            None => ("", text),
+        };
+        if !unsigned.starts_with(|c: char| c.is_ascii_digit()) {
+            return text.to_owned(); // inf, NaN
+        }
+        let (integer, fraction) = match unsigned.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (unsigned, None),
+        };
+
+        let mut out = String::with_capacity(text.len() + integer.len() / 3 + 1);
+        out.push_str(sign);
+        for (i, digit) in integer.chars().enumerate() {
+            if let Some(separator) = self.thousands_separator {
+                if i > 0 && (integer.len() - i) % 3 == 0 {
+                    out.push(separator);
+                }
+            }
+            out.push(digit);
+        }
+        if let Some(fraction) = fraction {
+            out.push(self.decimal_separator);
+            out.push_str(fraction);
+        }
+        out
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// What a number means, and so how to show it to the user.
+///
+/// Use it with [`crate::DragValue::quantity`] and [`crate::Slider::quantity`]
+/// instead of writing your own `custom_formatter` and `custom_parser`,
+/// or call [`Self::format`] directly, e.g. for axis labels.
+///
+/// The units are picked based on the size of the value, so `1500` Hz is shown as `1.5 kHz`.
+/// Parsing accepts any unit of the same quantity, so the user can type `2.5 MHz` or `2500 kHz`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Quantity {
+    /// A plain number, e.g. `1,234.5`.
+    #[default]
+    Number,
+
+    /// A number with an SI prefix and unit, e.g. `1.5 kHz` or `250 mV`.
+    ///
+    /// The unit may be empty, giving just the prefix: `1.5k`.
+    Si { unit: String },
+
+    /// A number of bytes, e.g. `512 B`, `1.5 MB` or `1.5 MiB`.
+    Bytes {
+        /// Use powers of 1024 (`KiB`, `MiB`, …) instead of powers of 1000 (`kB`, `MB`, …).
+        binary: bool,
+    },
+
+    /// A duration in seconds, e.g. `250 ms`, `12.5 s` or `1h 02m 03s`.
+    Duration,
+
+    /// A fraction shown as a percentage: `0.425` is shown as `42.5%`.
+    Percent,
+
+    /// An angle in radians, shown in degrees: [`std::f64::consts::PI`] is shown as `180°`.
+    Angle,
+}
+
+/// SI prefixes from 10⁻²⁴ to 10²⁴, in steps of 10³.
+const SI_PREFIXES: [&str; 17] = [
+    "y", "z", "a", "f", "p", "n", "µ", "m", "", "k", "M", "G", "T", "P", "E", "Z", "Y",
+];
+
+/// Index of the empty prefix in [`SI_PREFIXES`].
+const SI_UNIT_INDEX: i32 = 8;
+
+const DECIMAL_BYTE_UNITS: [&str; 7] = ["B", "kB", "MB", "GB", "TB", "PB", "EB"];
+const BINARY_BYTE_UNITS: [&str; 7] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+
+impl Quantity {
+    /// [`Self::Si`] with the given unit, e.g. `"Hz"`, `"m"` or `"V"`.
+    pub fn si(unit: impl Into<String>) -> Self {
+        Self::Si { unit: unit.into() }
+    }
+
+    /// [`Self::Bytes`] with decimal units (`kB`, `MB`, …).
+    pub fn bytes() -> Self {
+        Self::Bytes { binary: false }
+    }
+
+    /// [`Self::Bytes`] with binary units (`KiB`, `MiB`, …).
+    pub fn binary_bytes() -> Self {
+        Self::Bytes { binary: true }
+    }
+
+    /// Format the given value.
+    ///
+    /// `decimals` is the range of decimals the value itself needs (see [`NumberFormatter::format`]).
+    /// It is adjusted for the unit that is picked, so `1234` Hz with `0..=0` decimals is shown as `1.234 kHz`.
+    pub fn format(
+        &self,
+        value: f64,
+        decimals: RangeInclusive<usize>,
+        locale: &NumberLocale,
+    ) -> String {
+        match self {
+            Self::Number => locale.format(value, decimals),
+
+            Self::Si { unit } => {
+                let exponent = si_exponent(value, -SI_UNIT_INDEX..=SI_UNIT_INDEX);
+                format_si(value, exponent, unit, decimals, locale)
+            }
+
+            Self::Bytes { binary } => {
+                let (base, units) = if *binary {
+                    (1024.0_f64, BINARY_BYTE_UNITS)
+                } else {
+                    (1000.0, DECIMAL_BYTE_UNITS)
+                };
+                let exponent = if value == 0.0 || !value.is_finite() {
+                    0
+                } else {
+                    (value.abs().log(base).floor() as i32).clamp(0, units.len() as i32 - 1)
+                };
+                let number = locale.format(
+                    value / base.powi(exponent),
+                    shift_decimals(decimals, 3 * exponent),
+                );
+                format!("{number} {}", units[exponent as usize])
+            }
+
+            Self::Duration => format_duration(value, decimals, locale),
+
+            Self::Percent => {
+                let number = locale.format(100.0 * value, shift_decimals(decimals, -2));
+                format!("{number}%")
+            }
+
+            Self::Angle => {
+                let number = locale.format(value.to_degrees(), shift_decimals(decimals, -1));
+                format!("{number}°")
+            }
+        }
+    }
+
+    /// Parse text written by the user back into a value.
+    ///
+    /// Accepts what [`Self::format`] produces, but also any other unit of the same quantity.
+    /// The unit can be left out, in which case the base unit is assumed
+    /// (seconds for [`Self::Duration`], degrees for [`Self::Angle`]).
+    pub fn parse(&self, text: &str, locale: &NumberLocale) -> Option<f64> {
+        let text = text.trim();
+        match self {
+            Self::Number => locale.parse(text),
+
+            Self::Si { unit } => {
+                let (number, suffix) = split_number(text);
+                let number = locale.parse(number)?;
+                let prefix = suffix.strip_suffix(unit.as_str()).unwrap_or(suffix);
+                Some(number * si_prefix_factor(prefix)?)
+            }
+
+            Self::Bytes { binary } => {
+                let (number, suffix) = split_number(text);
+                let number = locale.parse(number)?;
+                let prefix = suffix.strip_suffix('B').unwrap_or(suffix);
+                let (prefix, base) = match prefix.strip_suffix('i') {
+                    Some(prefix) => (prefix, 1024.0_f64),
+                    None if *binary => (prefix, 1024.0),
+                    None => (prefix, 1000.0),
+                };
+                let exponent = match prefix.to_ascii_lowercase().as_str() {
+                    "" => 0,
+                    "k" => 1,
+                    "m" => 2,
+                    "g" => 3,
+                    "t" => 4,
+                    "p" => 5,
+                    "e" => 6,
+                    _ => return None,
+                };
+                Some(number * base.powi(exponent))
+            }
+
+            Self::Duration => parse_duration(text, locale),
+
+            Self::Percent => {
+                let number = text.strip_suffix('%').unwrap_or(text);
+                Some(locale.parse(number)? / 100.0)
+            }
+
+            Self::Angle => {
+                let (number, suffix) = split_number(text);
+                let number = locale.parse(number)?;
+                match suffix {
+                    "" | "°" | "deg" => Some(number.to_radians()),
+                    "rad" => Some(number),
+                    _ => None,
+                }
+            }
+        }
+    }
+
+    /// A [`NumberFormatter`] for this quantity,
+    /// e.g. for [`crate::Style::number_formatter`] or for formatting axis labels.
+    pub fn number_formatter(self, locale: NumberLocale) -> NumberFormatter {
+        NumberFormatter::new(move |value, decimals| self.format(value, decimals, &locale))
+    }
+}
+
+/// Adjust a range of decimals for a value that has been divided by `10^shift`.
+///
+/// The minimum is only ever lowered, so `1500` Hz is shown as `1.5 kHz` rather than `1.500 kHz`.
+fn shift_decimals(decimals: RangeInclusive<usize>, shift: i32) -> RangeInclusive<usize> {
+    let add = |d: usize, shift: i32| (d as i32 + shift).clamp(0, 16) as usize;
+    add(*decimals.start(), shift.min(0))..=add(*decimals.end(), shift)
+}
+
+/// The power of 1000 to divide `value` by to get it into the range `[1, 1000)`.
+fn si_exponent(value: f64, range: RangeInclusive<i32>) -> i32 {
+    if value == 0.0 || !value.is_finite() {
+        0
+    } else {
+        ((value.abs().log10() / 3.0).floor() as i32).clamp(*range.start(), *range.end())
+    }
+}
+
+fn format_si(
+    value: f64,
+    exponent: i32,
+    unit: &str,
+    decimals: RangeInclusive<usize>,
+    locale: &NumberLocale,
+) -> String {
+    let number = locale.format(
+        value / 1000_f64.powi(exponent),
+        shift_decimals(decimals, 3 * exponent),
+    );
+    let prefix = SI_PREFIXES[(SI_UNIT_INDEX + exponent) as usize];
+    if unit.is_empty() {
+        format!("{number}{prefix}")
+    } else {
+        format!("{number} {prefix}{unit}")
+    }
+}
+
+fn si_prefix_factor(prefix: &str) -> Option<f64> {
+    let prefix = match prefix {
+        "u" | "μ" => "µ", // ASCII and Greek mu
+        "K" => "k",
+        prefix => prefix,
+    };
+    let index = SI_PREFIXES.iter().position(|&p| p == prefix)?;
+    Some(1000_f64.powi(index as i32 - SI_UNIT_INDEX))
+}
+
+/// Split e.g. `"1.5 kHz"` into `("1.5", "kHz")`.
+fn split_number(text: &str) -> (&str, &str) {
+    let number_end = text
+        .rfind(|c: char| c.is_ascii_digit())
+        .map_or(0, |i| i + 1);
+    let (number, suffix) = text.split_at(number_end);
+    (number, suffix.trim())
+}
+
+const DURATION_UNITS: [(&str, f64); 4] = [("d", 86_400.0), ("h", 3_600.0), ("m", 60.0), ("s", 1.0)];
+
+fn format_duration(seconds: f64, decimals: RangeInclusive<usize>, locale: &NumberLocale) -> String {
+    if !seconds.is_finite() || seconds.abs() < 60.0 {
+        let exponent = si_exponent(seconds, -3..=0); // down to nanoseconds
+        return format_si(seconds, exponent, "s", decimals, locale);
+    }
+
+    let sign = if seconds < 0.0 { "-" } else { "" };
+    // Round first, so we never show e.g. `1m 60s`:
+    let mut remaining = emath::round_to_decimals(seconds.abs(), *decimals.end());
+
+    let mut parts = vec![];
+    for (name, length) in DURATION_UNITS {
+        if name == "s" {
+            let number = locale.format(remaining, decimals.clone());
+            let padding = if remaining < 10.0 { "0" } else { "" };
+            parts.push(format!("{padding}{number}{name}"));
+        } else {
+            let count = (remaining / length).floor();
+            remaining -= count * length;
+            if !parts.is_empty() {
+                parts.push(format!("{count:02}{name}"));
+            } else if count > 0.0 {
+                parts.push(format!("{count}{name}"));
+            }
+        }
+    }
+
+    format!("{sign}{}", parts.join(" "))
+}
+
+/// Parses e.g. `1h 2m 3.5s`, `250 ms`, or just `12.5` (seconds).
+fn parse_duration(text: &str, locale: &NumberLocale) -> Option<f64> {
+    let (sign, mut text) = match text.strip_prefix(['-', '−']) {
+        Some(rest) => (-1.0, rest.trim_start()),
+        None => (1.0, text),
+    };
+
+    let mut total = 0.0;
+    while !text.is_empty() {
+        let unit_start = text.find(|c: char| c.is_alphabetic()).unwrap_or(text.len());
+        let number = locale.parse(&text[..unit_start])?;
+        let rest = &text[unit_start..];
+        let unit_end = rest
+            .find(|c: char| !c.is_alphabetic())
+            .unwrap_or(rest.len());
+        let unit = &rest[..unit_end];
+        text = rest[unit_end..].trim_start();
+
+        let length = match unit {
+            "" | "s" | "sec" => 1.0,
+            "min" => 60.0,
+            "ms" | "us" | "µs" | "μs" | "ns" => si_prefix_factor(&unit[..unit.len() - 1])?,
+            unit => DURATION_UNITS.iter().find(|(name, _)| *name == unit)?.1,
+        };
+        total += number * length;
+    }
+    Some(sign * total)
+}
+
+// ----------------------------------------------------------------------------
+
+/// The units a user may type after a number, and what each one is worth in the unit of the value.
+///
+/// Used when evaluating what the user types into a [`crate::DragValue`] or [`crate::Slider`]
+/// (see [`eval_expression`]), so that e.g. `10 cm` becomes `0.1` for a value in meters.
+///
+/// If you don't give a widget any units, they come from its [`Quantity`] (see [`Self::for_quantity`]).
+///
+/// ```
+/// use egui::quantity::Units;
+///
+/// // A length in meters:
+/// let units = Units::si("m").with("in", 0.0254).with("ft", 0.3048);
+/// assert_eq!(units.factor("cm"), Some(0.01));
+/// assert_eq!(units.factor("ft"), Some(0.3048));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Units {
+    units: Vec<(String, f64)>,
+}
+
+impl Units {
+    /// No units.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a unit, or replace one with the same name.
+    ///
+    /// `factor` is how much one of it is in the unit of the value,
+    /// e.g. `0.01` for `cm` if the value is in meters.
+    #[inline]
+    pub fn with(mut self, name: impl Into<String>, factor: f64) -> Self {
+        let name = name.into();
+        self.units.retain(|(n, _)| *n != name);
+        self.units.push((name, factor));
+        self
+    }
+
+    /// The given unit with all SI prefixes, e.g. `km`, `m`, `cm` and `mm` for `"m"`.
+    ///
+    /// With an empty unit, you get just the prefixes: `k`, `M`, …
+    pub fn si(unit: &str) -> Self {
+        let mut units = Self::new();
+        if !unit.is_empty() {
+            units = units.with(unit, 1.0);
+        }
+        for (exponent, prefix) in (-SI_UNIT_INDEX..).zip(SI_PREFIXES) {
+            if !prefix.is_empty() {
+                units = units.with(format!("{prefix}{unit}"), 1000_f64.powi(exponent));
+            }
+        }
+        units
+            .with(format!("u{unit}"), 1e-6)
+            .with(format!("c{unit}"), 1e-2)
+            .with(format!("d{unit}"), 1e-1)
+    }
+
+    /// The units the user can type for a value of the given quantity.
+    pub fn for_quantity(quantity: &Quantity) -> Self {
+        match quantity {
+            Quantity::Number | Quantity::Percent => Self::new(),
+            Quantity::Si { unit } => Self::si(unit),
+            Quantity::Bytes { binary } => {
+                let mut units = Self::new();
+                let base = if *binary { 1024.0_f64 } else { 1000.0 };
+                for (exponent, (decimal, binary)) in
+                    DECIMAL_BYTE_UNITS.iter().zip(BINARY_BYTE_UNITS).enumerate()
+                {
+                    units = units
+                        .with(*decimal, base.powi(exponent as i32))
+                        .with(decimal.to_ascii_uppercase(), base.powi(exponent as i32))
+                        .with(binary, 1024_f64.powi(exponent as i32));
+                }
+                units
+            }
+            Quantity::Duration => Self::si("s")
+                .with("sec", 1.0)
+                .with("min", 60.0)
+                .with("m", 60.0) // As in `1m 30s`, not milli
+                .with("h", 3_600.0)
+                .with("d", 86_400.0),
+            Quantity::Angle => Self::new()
+                .with("°", 1_f64.to_radians())
+                .with("deg", 1_f64.to_radians())
+                .with("rad", 1.0),
+        }
+    }
+
+    /// How much one of the given unit is in the unit of the value, if it is known.
+    pub fn factor(&self, name: &str) -> Option<f64> {
+        self.units
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, factor)| *factor)
+    }
+}
+
+/// What [`eval_expression`] (or your own [`crate::style::ExpressionParser`]) needs to know
+/// about the widget the text was typed into.
+#[derive(Clone, Debug)]
+pub struct ExpressionContext<'a> {
+    /// The range of the widget. `15%` means 15% of the way through it.
+    pub range: RangeInclusive<f64>,
+
+    /// The units the user may type after a number.
+    pub units: &'a Units,
+
+    /// How numbers are written.
+    pub locale: &'a NumberLocale,
+}
+
+/// Evaluate a simple math expression typed by the user, e.g. into a [`crate::DragValue`].
+///
+/// Supports:
+/// * `+`, `-`, `*`, `/`, `^` and parentheses, e.g. `(1 + 2) * 3^2`
+/// * the constants `pi` (`π`), `tau` (`τ`) and `e`
+/// * the functions `sqrt`, `abs`, `sin`, `cos`, `tan`, `ln` and `exp`
+/// * units after a number, from [`ExpressionContext::units`], e.g. `10 cm` or `1h 30min`
+/// * `%` after a number, meaning that far through [`ExpressionContext::range`], e.g. `50%` for the middle
+/// * leaving out `*`, e.g. `2pi`
+///
+/// This is the default [`crate::Style::expression_parser`].
+///
+/// ```
+/// use egui::quantity::{eval_expression, ExpressionContext, NumberLocale, Units};
+///
+/// let units = Units::si("m");
+/// let ctx = ExpressionContext { range: 0.0..=10.0, units: &units, locale: &NumberLocale::PLAIN };
+/// assert_eq!(eval_expression("2 * (3 + 4)", &ctx), Some(14.0));
+/// assert_eq!(eval_expression("2pi", &ctx), Some(std::f64::consts::TAU));
+/// assert_eq!(eval_expression("1 m + 50 cm", &ctx), Some(1.5));
+/// assert_eq!(eval_expression("15%", &ctx), Some(1.5));
+/// ```
+pub fn eval_expression(text: &str, ctx: &ExpressionContext<'_>) -> Option<f64> {
+    let tokens = tokenize(text, ctx.locale)?;
+    let mut parser = Evaluator {
+        tokens: &tokens,
+        pos: 0,
+        ctx,
+        last_had_unit: false,
+    };
+    let value = parser.sum()?;
+    (parser.pos == tokens.len() && value.is_finite()).then_some(value)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Number(f64),
+    Ident(String),
+    Op(char),
+}
+
+fn tokenize(text: &str, locale: &NumberLocale) -> Option<Vec<Token>> {
+    let is_number_char = |c: char| {
+        c.is_ascii_digit() || c == '.' || c == locale.decimal_separator
+        // Thousands separators are ambiguous in an expression (`1,5` in English), so we don't allow them.
+    };
+    let is_ident_char = |c: char| c.is_alphabetic() || c == '°';
+
+    let mut tokens = vec![];
+    let mut chars = text.char_indices().peekable();
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if is_number_char(c) {
+            let mut end = start;
+            while let Some(&(i, c)) = chars.peek() {
+                let exponent = (c == 'e' || c == 'E')
+                    && text[i + 1..].starts_with(|c: char| c.is_ascii_digit() || c == '-');
+                if is_number_char(c) || exponent {
+                    chars.next();
+                    if exponent && text[i + 1..].starts_with('-') {
+                        chars.next();
+                    }
+                    end = chars.peek().map_or(text.len(), |&(i, _)| i);
+                } else {
+                    break;
+                }
+            }
+            let number = text[start..end].replace(locale.decimal_separator, ".");
+            tokens.push(Token::Number(number.parse().ok()?));
+        } else if is_ident_char(c) {
+            let mut end = start;
+            while let Some(&(i, c)) = chars.peek() {
+                if is_ident_char(c) {
+                    chars.next();
+                    end = i + c.len_utf8();
+                } else {
+                    break;
+                }
+            }
+            tokens.push(Token::Ident(text[start..end].to_owned()));
+        } else if "+-−*×/÷^%()".contains(c) {
+            chars.next();
+            tokens.push(Token::Op(match c {
+                '−' => '-',
+                '×' => '*',
+                '÷' => '/',
+                c => c,
+            }));
+        } else {
+            return None;
+        }
+    }
+    Some(tokens)
+}
+
+/// A recursive descent parser that evaluates as it goes.
+struct Evaluator<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+    ctx: &'a ExpressionContext<'a>,
+
+    /// Did the last number have a unit?
+    ///
+    /// Then another number with a unit right after it is added to it, as in `1h 30min`.
+    last_had_unit: bool,
+}
+
+impl Evaluator<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn eat_op(&mut self, op: char) -> bool {
+        let found = self.peek() == Some(&Token::Op(op));
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn next_is_number_after_unit(&self) -> bool {
+        self.last_had_unit && matches!(self.peek(), Some(Token::Number(_)))
+    }
+
+    /// `product (('+' | '-' | nothing) product)*`
+    fn sum(&mut self) -> Option<f64> {
+        let mut value = self.product()?;
+        loop {
+            if self.eat_op('+') || self.next_is_number_after_unit() {
+                value += self.product()?;
+            } else if self.eat_op('-') {
+                value -= self.product()?;
+            } else {
+                return Some(value);
+            }
+        }
+    }
+
+    /// `signed (('*' | '/' | nothing) signed)*`
+    fn product(&mut self) -> Option<f64> {
+        let mut value = self.signed()?;
+        loop {
+            if self.eat_op('*') {
+                value *= self.signed()?;
+            } else if self.eat_op('/') {
+                value /= self.signed()?;
+            } else if matches!(
+                self.peek(),
+                Some(Token::Number(_) | Token::Ident(_) | Token::Op('('))
+            ) && !self.next_is_number_after_unit()
+            {
+                // `2pi` or `2(1 + 2)`
+                value *= self.signed()?;
+            } else {
+                return Some(value);
+            }
+        }
+    }
+
+    /// `('-' | '+')* power`
+    fn signed(&mut self) -> Option<f64> {
+        if self.eat_op('-') {
+            Some(-self.signed()?)
+        } else if self.eat_op('+') {
+            self.signed()
+        } else {
+            self.power()
+        }
+    }
+
+    /// `with_unit ('^' signed)?`
+    fn power(&mut self) -> Option<f64> {
+        let base = self.with_unit()?;
+        if self.eat_op('^') {
+            Some(base.powf(self.signed()?))
+        } else {
+            Some(base)
+        }
+    }
+
+    /// `atom ('%' | unit)?`
+    fn with_unit(&mut self) -> Option<f64> {
+        let value = self.atom()?;
+        self.last_had_unit = false;
+        if self.eat_op('%') {
+            let range = &self.ctx.range;
+            return Some(lerp_range(range, value / 100.0));
+        }
+        if let Some(Token::Ident(name)) = self.peek() {
+            if let Some(factor) = self.ctx.units.factor(name) {
+                self.pos += 1;
+                self.last_had_unit = true;
+                return Some(value * factor);
+            }
+        }
+        Some(value)
+    }
+
+    /// A number, constant, function call, or an expression in parentheses.
+    fn atom(&mut self) -> Option<f64> {
+        let token = self.peek()?.clone();
+        self.pos += 1;
+        match token {
+            Token::Number(number) => Some(number),
+            Token::Op('(') => {
+                let value = self.sum()?;
+                self.eat_op(')').then_some(value)
+            }
+            Token::Ident(name) => {
+                let function: fn(f64) -> f64 = match name.as_str() {
+                    "pi" | "π" => return Some(std::f64::consts::PI),
+                    "tau" | "τ" => return Some(std::f64::consts::TAU),
+                    "e" => return Some(std::f64::consts::E),
+                    "sqrt" => f64::sqrt,
+                    "abs" => f64::abs,
+                    "sin" => f64::sin,
+                    "cos" => f64::cos,
+                    "tan" => f64::tan,
+                    "ln" => f64::ln,
+                    "exp" => f64::exp,
+                    _ => return None,
+                };
+                if !self.eat_op('(') {
+                    return None;
+                }
+                let argument = self.sum()?;
+                self.eat_op(')').then(|| function(argument))
+            }
+            Token::Op(_) => None,
+        }
+    }
+}
+
+fn lerp_range(range: &RangeInclusive<f64>, t: f64) -> f64 {
+    range.start() + t * (range.end() - range.start())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_and_parse() {
+        let en = NumberLocale::ENGLISH;
+        let de = NumberLocale::GERMAN;
+
+        let cases = [
+            (Quantity::Number, 1234.5, 1..=1, en, "1,234.5"),
+            (Quantity::Number, -1234567.0, 0..=0, de, "-1.234.567"),
+            (Quantity::si("Hz"), 1500.0, 0..=0, en, "1.5 kHz"),
+            (Quantity::si("Hz"), 1234.0, 0..=0, en, "1.234 kHz"),
+            (Quantity::si("V"), 0.25, 0..=2, en, "250 mV"),
+            (Quantity::si("Hz"), 2.5e6, 0..=0, de, "2,5 MHz"),
+            (Quantity::si(""), 1500.0, 0..=3, en, "1.5k"),
+            (Quantity::bytes(), 512.0, 0..=0, en, "512 B"),
+            (Quantity::bytes(), 1.5e6, 0..=0, en, "1.5 MB"),
+            (Quantity::binary_bytes(), 1536.0, 0..=2, en, "1.5 KiB"),
+            (Quantity::Duration, 0.25, 0..=3, en, "250 ms"),
+            (Quantity::Duration, 12.5, 0..=1, en, "12.5 s"),
+            (Quantity::Duration, 90.0, 0..=0, en, "1m 30s"),
+            (Quantity::Duration, 3723.0, 0..=0, en, "1h 02m 03s"),
+            (Quantity::Percent, 0.425, 0..=3, en, "42.5%"),
+            (Quantity::Angle, std::f64::consts::PI, 0..=2, en, "180°"),
+        ];
+
+        for (quantity, value, decimals, locale, expected) in cases {
+            let text = quantity.format(value, decimals, &locale);
+            assert_eq!(text, expected, "{quantity:?}");
+            let parsed = quantity.parse(&text, &locale).unwrap();
+            assert!(
+                emath::almost_equal(parsed as f32, value as f32, 1e-5),
+                "{quantity:?}: parsed {text:?} as {parsed}, expected {value}"
+            );
+        }
+    }
+
+    #[test]
+    fn parse_other_units() {
+        let en = NumberLocale::ENGLISH;
+        assert_eq!(Quantity::si("Hz").parse("2500 kHz", &en), Some(2.5e6));
+        assert_eq!(Quantity::si("Hz").parse("3", &en), Some(3.0));
+        assert_eq!(Quantity::si("m").parse("5 km", &en), Some(5000.0));
+        assert_eq!(Quantity::bytes().parse("2 KiB", &en), Some(2048.0));
+        assert_eq!(Quantity::binary_bytes().parse("2k", &en), Some(2048.0));
+        assert_eq!(Quantity::Duration.parse("1h 30min", &en), Some(5400.0));
+        assert_eq!(Quantity::Duration.parse("-1m30s", &en), Some(-90.0));
+        assert_eq!(Quantity::Duration.parse("1,234", &en), Some(1234.0));
+        assert_eq!(Quantity::Angle.parse("1 rad", &en), Some(1.0));
+        assert_eq!(Quantity::Percent.parse("50", &en), Some(0.5));
+        assert_eq!(Quantity::si("Hz").parse("1.5 kg", &en), None);
+    }
+
+    #[test]
+    fn expressions() {
+        let units = Units::for_quantity(&Quantity::Duration);
+        let ctx = ExpressionContext {
+            range: -1.0..=1.0,
+            units: &units,
+            locale: &NumberLocale::GERMAN,
+        };
+        let eval = |text| eval_expression(text, &ctx);
+
+        assert_eq!(eval("1 + 2 * 3"), Some(7.0));
+        assert_eq!(eval("(1 + 2) * 3"), Some(9.0));
+        assert_eq!(eval("-2^2"), Some(-4.0));
+        assert_eq!(eval("2^-1"), Some(0.5));
+        assert_eq!(
+            eval("1,5 * 2"),
+            Some(3.0),
+            "Decimal separator of the locale"
+        );
+        assert_eq!(eval("1e3 / 4"), Some(250.0));
+        assert_eq!(eval("sqrt(16) − 1"), Some(3.0));
+        assert_eq!(eval("250ms"), Some(0.25));
+        assert_eq!(eval("1h 30min"), Some(5400.0));
+        assert_eq!(eval("2 * 1m"), Some(120.0));
+        assert_eq!(eval("75%"), Some(0.5));
+        assert_eq!(eval("e"), Some(std::f64::consts::E));
+
+        assert_eq!(eval(""), None);
+        assert_eq!(eval("1 +"), None);
+        assert_eq!(eval("(1"), None);
+        assert_eq!(eval("1 parsec"), None);
+        assert_eq!(eval("1 / 0"), None);
+    }
+}
@@ -827,6 +827,17 @@ impl Response {
         self
     }
 
+    /// Attach context help to this widget.
+    ///
+    /// In "What's this?" mode, clicking the widget shows the help in a popover.
+    /// The entry is also added to the help registry.
+    ///
+    /// See [`crate::ContextHelp`] for more.
+    pub fn context_help(self, entry: crate::HelpEntry) -> Self {
+        crate::ContextHelp::attach(&self, entry);
+        self
+    }
+
     /// Show this text when hovering if the widget is disabled.
     pub fn on_disabled_hover_text(self, text: impl Into<WidgetText>) -> Self {
         self.on_disabled_hover_ui(|ui| {
@@ -987,6 +998,76 @@ impl Response {
         self.ctx.output_mut(|o| o.events.push(event));
     }
 
+    /// Describe this widget to screen readers and other assistive technology,
+    /// with full control over its [AccessKit](https://accesskit.dev) node.
+    ///
+    /// This is for custom widgets that don't fit a [`crate::WidgetType`] and [`Self::widget_info`],
+    /// e.g. a knob that should be read out as a slider with its current value.
+    ///
+    /// What you can rely on:
+    /// * `fill` is only called when AccessKit is enabled, so this costs nothing when it isn't.
+    /// * Before `fill` is called, the node is filled in like for any other widget:
+    ///   its bounds, whether it is disabled, the `Focus` action if the widget is focusable,
+    ///   and the `Click` default action verb if it senses clicks.
+    ///   `fill` can override all of these, and anything set by an earlier [`Self::widget_info`].
+    /// * The [`Context`] is not locked while `fill` runs, so you may use it.
+    /// * Actions you add (e.g. [`accesskit::Action::Increment`]) are sent back as requests,
+    ///   which you can read with [`Self::accesskit_action_requests`].
+    ///   `Focus` and `Default` (click) requests are already handled by egui.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// # let mut value = 0.5_f64;
+    /// let response = ui.allocate_response(egui::vec2(32.0, 32.0), egui::Sense::click_and_drag());
+    /// # #[cfg(feature = "accesskit")]
+    /// # {
+    /// use egui::accesskit::{Action, Role};
+    /// value += 0.1 * response.accesskit_action_requests(Action::Increment).len() as f64;
+    /// value -= 0.1 * response.accesskit_action_requests(Action::Decrement).len() as f64;
+    /// response.accesskit_node(|node| {
+    ///     node.set_role(Role::Slider);
+    ///     node.set_name("Volume");
+    ///     node.set_numeric_value(value);
+    ///     node.add_action(Action::Increment);
+    ///     node.add_action(Action::Decrement);
+    /// });
+    /// # }
+    /// # });
+    /// ```
+    #[cfg(feature = "accesskit")]
+    pub fn accesskit_node(&self, fill: impl FnOnce(&mut accesskit::NodeBuilder)) {
+        // Take the node out, so we don't hold the `Context` lock while calling `fill`:
+        let Some(mut builder) = self.ctx.accesskit_node_builder(self.id, std::mem::take) else {
+            return;
+        };
+        self.fill_accesskit_node_common(&mut builder);
+        fill(&mut builder);
+        self.ctx.accesskit_node_builder(self.id, |node| {
+            // Keep any children that were added while the node was taken out:
+            let new_children = node.children().to_vec();
+            *node = builder;
+            for child in new_children {
+                node.push_child(child);
+            }
+        });
+    }
+
+    /// All AccessKit action requests of the given kind for this widget this pass,
+    /// e.g. from a screen reader.
+    ///
+    /// Use this together with [`Self::accesskit_node`] to handle the actions your widget supports.
+    #[cfg(feature = "accesskit")]
+    pub fn accesskit_action_requests(
+        &self,
+        action: accesskit::Action,
+    ) -> Vec<accesskit::ActionRequest> {
+        self.ctx.input(|i| {
+            i.accesskit_action_requests(self.id, action)
+                .cloned()
+                .collect()
+        })
+    }
+
     #[cfg(feature = "accesskit")]
     pub(crate) fn fill_accesskit_node_common(&self, builder: &mut accesskit::NodeBuilder) {
         if !self.enabled {
@@ -1085,7 +1166,27 @@ impl Response {
         self
     }
 
-    /// Response to secondary clicks (right-clicks) by showing the given menu.
+    /// Mark this widget as a live region for accessibility.
+    ///
+    /// Screen readers will announce changes to the widget even when it doesn't have focus.
+    /// [`crate::Label`] and [`crate::ProgressBar`] call this for you
+    /// if their text was marked with [`crate::RichText::live_region`].
+    pub fn live_region(self, live_region: crate::LiveRegion) -> Self {
+        #[cfg(feature = "accesskit")]
+        if live_region != crate::LiveRegion::Off {
+            self.ctx.accesskit_node_builder(self.id, |builder| {
+                builder.set_live(live_region.into());
+            });
+        }
+        #[cfg(not(feature = "accesskit"))]
+        {
+            let _ = live_region;
+        }
+
+        self
+    }
+
+    /// Response to secondary clicks (right-clicks), or long presses on touch screens, by showing the given menu.
     ///
     /// Make sure the widget senses clicks (e.g. [`crate::Button`] does, [`crate::Label`] does not).
     ///
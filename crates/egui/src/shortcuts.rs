@@ -0,0 +1,397 @@
+use std::collections::BTreeMap;
+
+use ahash::HashMap;
+
+use crate::{Event, Id, Key, KeyboardShortcut, Modifiers, RichText, Ui};
+
+/// A named action the user can trigger with a keyboard shortcut, see [`Shortcuts`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShortcutAction {
+    /// Stable identifier of the action, e.g. `"file.save"`.
+    ///
+    /// This is what the user overrides are stored under, so don't change it between versions.
+    pub name: String,
+
+    /// Human-readable name, e.g. `"Save"`, shown in [`Shortcuts::settings_ui`].
+    pub label: String,
+
+    /// The shortcut unless the user picks another one.
+    pub default_shortcut: Option<KeyboardShortcut>,
+}
+
+/// Two or more actions bound to the same shortcut, see [`Shortcuts::conflicts`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShortcutConflict {
+    pub shortcut: KeyboardShortcut,
+
+    /// The names of the actions, in registration order.
+    pub actions: Vec<String>,
+}
+
+/// A registry of named actions and their keyboard shortcuts, in [`crate::Memory::shortcuts`].
+///
+/// Declare the actions of your app with their default shortcuts,
+/// then ask [`crate::Context::action_triggered`] if the user pressed them:
+///
+/// ```
+/// # egui::__run_test_ctx(|ctx| {
+/// use egui::{Key, KeyboardShortcut, Modifiers};
+///
+/// ctx.memory_mut(|mem| {
+///     let shortcuts = &mut mem.shortcuts;
+///     shortcuts.register("file.save", "Save", KeyboardShortcut::new(Modifiers::COMMAND, Key::S));
+///     shortcuts.register("file.export", "Export", None);
+/// });
+///
+/// if ctx.action_triggered("file.save") {
+///     // save…
+/// }
+/// # });
+/// ```
+///
+/// The user can bind other shortcuts with [`Self::settings_ui`] (or [`Self::set_shortcut`]).
+/// These overrides are stored with the rest of [`crate::Memory`] when using the `persistence` feature,
+/// while the registered actions are not: register them on every start.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "persistence", serde(default))]
+pub struct Shortcuts {
+    /// In registration order.
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    actions: Vec<ShortcutAction>,
+
+    /// The shortcuts the user picked, by action name.
+    /// `None` means the user removed the shortcut.
+    overrides: BTreeMap<String, Option<KeyboardShortcut>>,
+
+    /// Which actions were checked this pass (the pass number), and were they triggered?
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    triggered: (u64, HashMap<String, bool>),
+}
+
+impl Shortcuts {
+    /// Declare an action and its default shortcut (if any).
+    ///
+    /// Registering an action with the same name again updates it.
+    pub fn register(
+        &mut self,
+        name: impl Into<String>,
+        label: impl Into<String>,
+        default_shortcut: impl Into<Option<KeyboardShortcut>>,
+    ) {
+        let action = ShortcutAction {
+            name: name.into(),
+            label: label.into(),
+            default_shortcut: default_shortcut.into(),
+        };
+        if let Some(existing) = self.actions.iter_mut().find(|a| a.name == action.name) {
+            *existing = action;
+        } else {
+            self.actions.push(action);
+        }
+    }
+
+    /// All registered actions, in registration order.
+    pub fn actions(&self) -> &[ShortcutAction] {
+        &self.actions
+    }
+
+    /// The shortcut currently bound to the action, taking user overrides into account.
+    ///
+    /// `None` if the action has no shortcut, or isn't registered.
+    pub fn shortcut(&self, action: &str) -> Option<KeyboardShortcut> {
+        if let Some(shortcut) = self.overrides.get(action) {
+            return *shortcut;
+        }
+        self.actions
+            .iter()
+            .find(|a| a.name == action)
+            .and_then(|a| a.default_shortcut)
+    }
+
+    /// Bind another shortcut to the action, or `None` to remove its shortcut.
+    ///
+    /// Use [`Self::conflicts`] to check if the shortcut is already in use.
+    pub fn set_shortcut(&mut self, action: impl Into<String>, shortcut: Option<KeyboardShortcut>) {
+        self.overrides.insert(action.into(), shortcut);
+    }
+
+    /// Go back to the default shortcut of the action.
+    pub fn reset(&mut self, action: &str) {
+        self.overrides.remove(action);
+    }
+
+    /// Go back to the default shortcuts of all actions.
+    pub fn reset_all(&mut self) {
+        self.overrides.clear();
+    }
+
+    /// Has the user picked another shortcut for this action?
+    pub fn is_overridden(&self, action: &str) -> bool {
+        self.overrides.contains_key(action)
+    }
+
+    /// The registered actions bound to this shortcut, in registration order.
+    ///
+    /// `Ctrl` and `Cmd` are treated the same way as when matching key presses,
+    /// so [`crate::Modifiers::COMMAND`] conflicts with [`crate::Modifiers::CTRL`].
+    pub fn actions_for(&self, shortcut: &KeyboardShortcut) -> Vec<&str> {
+        self.actions
+            .iter()
+            .filter(|a| {
+                self.shortcut(&a.name)
+                    .is_some_and(|other| is_same_shortcut(shortcut, &other))
+            })
+            .map(|a| a.name.as_str())
+            .collect()
+    }
+
+    /// All shortcuts that are bound to more than one registered action.
+    pub fn conflicts(&self) -> Vec<ShortcutConflict> {
+        let mut conflicts: Vec<ShortcutConflict> = vec![];
+        for action in &self.actions {
+            let Some(shortcut) = self.shortcut(&action.name) else {
+                continue;
+            };
+            if conflicts
+                .iter()
+                .any(|c| is_same_shortcut(&c.shortcut, &shortcut))
+            {
+                continue;
+            }
+            let actions = self.actions_for(&shortcut);
+            if 1 < actions.len() {
+                conflicts.push(ShortcutConflict {
+                    shortcut,
+                    actions: actions.into_iter().map(ToOwned::to_owned).collect(),
+                });
+            }
+        }
+        conflicts
+    }
+
+    /// Was the action checked already during this pass, and was it triggered?
+    pub(crate) fn cached_trigger(&self, action: &str, pass_nr: u64) -> Option<bool> {
+        let (cached_pass_nr, triggered) = &self.triggered;
+        if *cached_pass_nr == pass_nr {
+            triggered.get(action).copied()
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn cache_trigger(&mut self, action: &str, pass_nr: u64, triggered: bool) {
+        if self.triggered.0 != pass_nr {
+            self.triggered = (pass_nr, HashMap::default());
+        }
+        self.triggered.1.insert(action.to_owned(), triggered);
+    }
+
+    /// Is there an action with a more specific shortcut than `shortcut` that matches
+    /// the modifiers that are down, e.g. `Cmd+Shift+S` when checking `Cmd+S`?
+    pub(crate) fn is_shadowed(&self, shortcut: &KeyboardShortcut, modifiers: Modifiers) -> bool {
+        self.actions.iter().any(|a| {
+            self.shortcut(&a.name).is_some_and(|other| {
+                other.logical_key == shortcut.logical_key
+                    && !is_same_shortcut(&other, shortcut)
+                    && other.modifiers.matches_logically(shortcut.modifiers)
+                    && modifiers.matches_logically(other.modifiers)
+            })
+        })
+    }
+
+    /// Show all actions with their shortcuts, and let the user bind other ones.
+    ///
+    /// Click a shortcut, then press the new key combination (or `Escape` to cancel).
+    pub fn settings_ui(ui: &mut Ui) {
+        let recording_id = Id::new("__shortcuts_recording");
+        let mut recording: Option<String> = ui.data(|d| d.get_temp(recording_id));
+        let mut shortcuts = ui.memory(|mem| mem.shortcuts.clone());
+        let mut changed = false;
+
+        if let Some(action) = &recording {
+            let pressed = ui.input(|i| {
+                i.events.iter().find_map(|event| match event {
+                    Event::Key {
+                        key,
+                        pressed: true,
+                        modifiers,
+                        ..
+                    } => Some(KeyboardShortcut::new(*modifiers, *key)),
+                    _ => None,
+                })
+            });
+            if let Some(pressed) = pressed {
+                if pressed.logical_key != Key::Escape {
+                    shortcuts.set_shortcut(action.clone(), Some(pressed));
+                    changed = true;
+                }
+                recording = None;
+            }
+        }
+
+        let conflicts = shortcuts.conflicts();
+        crate::Grid::new("__shortcuts_grid")
+            .num_columns(3)
+            .striped(true)
+            .show(ui, |ui| {
+                for action in shortcuts.actions.clone() {
+                    ui.label(&action.label);
+
+                    let shortcut = shortcuts.shortcut(&action.name);
+                    let is_recording = recording.as_deref() == Some(action.name.as_str());
+                    let text = if is_recording {
+                        RichText::new("Press keys…").italics()
+                    } else if let Some(shortcut) = &shortcut {
+                        let text = RichText::new(ui.ctx().format_shortcut(shortcut));
+                        if conflicts.iter().any(|c| c.actions.contains(&action.name)) {
+                            text.color(ui.visuals().error_fg_color)
+                        } else {
+                            text
+                        }
+                    } else {
+                        RichText::new("None").weak()
+                    };
+                    let response = ui.selectable_label(is_recording, text);
+                    if response.clicked() {
+                        recording = (!is_recording).then(|| action.name.clone());
+                    }
+
+                    ui.horizontal(|ui| {
+                        if ui
+                            .add_enabled(shortcut.is_some(), crate::Button::new("Clear"))
+                            .clicked()
+                        {
+                            shortcuts.set_shortcut(action.name.clone(), None);
+                            changed = true;
+                        }
+                        if ui
+                            .add_enabled(
+                                shortcuts.is_overridden(&action.name),
+                                crate::Button::new("Reset"),
+                            )
+                            .clicked()
+                        {
+                            shortcuts.reset(&action.name);
+                            changed = true;
+                        }
+                    });
+                    ui.end_row();
+                }
+            });
+
+        for conflict in &conflicts {
+            let labels: Vec<&str> = conflict
+                .actions
+                .iter()
+                .filter_map(|name| shortcuts.actions.iter().find(|a| &a.name == name))
+                .map(|a| a.label.as_str())
+                .collect();
+            ui.colored_label(
+                ui.visuals().error_fg_color,
+                format!(
+                    "{} is used by: {}",
+                    ui.ctx().format_shortcut(&conflict.shortcut),
+                    labels.join(", ")
+                ),
+            );
+        }
+
+        if changed {
+            ui.memory_mut(|mem| mem.shortcuts.overrides = shortcuts.overrides);
+        }
+        ui.data_mut(|d| match recording {
+            Some(action) => d.insert_temp(recording_id, action),
+            None => d.remove::<String>(recording_id),
+        });
+    }
+}
+
+/// Would pressing one of these also trigger the other?
+///
+/// Outside of Mac, pressing `Ctrl` sets both [`Modifiers::ctrl`] and [`Modifiers::command`].
+fn is_same_shortcut(a: &KeyboardShortcut, b: &KeyboardShortcut) -> bool {
+    let pressed = |modifiers: Modifiers| {
+        if !modifiers.mac_cmd && (modifiers.ctrl || modifiers.command) {
+            modifiers | Modifiers::CTRL | Modifiers::COMMAND
+        } else {
+            modifiers
+        }
+    };
+    a.logical_key == b.logical_key
+        && (pressed(a.modifiers).matches_exact(b.modifiers)
+            || pressed(b.modifiers).matches_exact(a.modifiers))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Context;
+
+    #[test]
+    fn overrides_and_conflicts() {
+        let save = KeyboardShortcut::new(Modifiers::COMMAND, Key::S);
+        let mut shortcuts = Shortcuts::default();
+        shortcuts.register("save", "Save", save);
+        shortcuts.register(
+            "search",
+            "Search",
+            KeyboardShortcut::new(Modifiers::CTRL, Key::F),
+        );
+        assert!(shortcuts.conflicts().is_empty());
+
+        let ctrl_s = KeyboardShortcut::new(Modifiers::CTRL, Key::S);
+        shortcuts.set_shortcut("search", Some(ctrl_s));
+        assert_eq!(shortcuts.shortcut("search"), Some(ctrl_s));
+        assert_eq!(
+            shortcuts.conflicts(),
+            vec![ShortcutConflict {
+                shortcut: save,
+                actions: vec!["save".to_owned(), "search".to_owned()],
+            }]
+        );
+
+        shortcuts.reset("search");
+        assert!(shortcuts.conflicts().is_empty());
+    }
+
+    #[test]
+    fn more_specific_shortcut_wins() {
+        let ctx = Context::default();
+        ctx.memory_mut(|mem| {
+            let shortcuts = &mut mem.shortcuts;
+            shortcuts.register(
+                "save",
+                "Save",
+                KeyboardShortcut::new(Modifiers::CTRL, Key::S),
+            );
+            shortcuts.register(
+                "save_as",
+                "Save as…",
+                KeyboardShortcut::new(Modifiers::CTRL | Modifiers::SHIFT, Key::S),
+            );
+        });
+
+        let modifiers = Modifiers::CTRL | Modifiers::SHIFT;
+        let input = crate::RawInput {
+            modifiers,
+            events: vec![Event::Key {
+                key: Key::S,
+                physical_key: None,
+                scancode: None,
+                pressed: true,
+                repeat: false,
+                modifiers,
+            }],
+            ..Default::default()
+        };
+        let _ = ctx.run(input, |ctx| {
+            assert!(!ctx.action_triggered("save"));
+            assert!(ctx.action_triggered("save_as"));
+            assert!(
+                ctx.action_triggered("save_as"),
+                "Same answer during the pass"
+            );
+        });
+    }
+}
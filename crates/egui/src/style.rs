@@ -10,10 +10,17 @@ use epaint::{text::FontTweak, Rounding, Shadow, Stroke};
 use crate::{
     ecolor::Color32,
     emath::{pos2, vec2, Rangef, Rect, Vec2},
+    quantity::{ExpressionContext, NumberLocale},
     ComboBox, CursorIcon, FontFamily, FontId, Grid, Margin, Response, RichText, TextWrapMode,
     WidgetText,
 };
 
+#[cfg(feature = "theme_loader")]
+mod theme_loader;
+
+#[cfg(feature = "theme_loader")]
+pub use theme_loader::{ThemeError, ThemeFormat, ThemeLoader};
+
 /// How to format numbers in e.g. a [`crate::DragValue`].
 #[derive(Clone)]
 pub struct NumberFormatter(
@@ -59,6 +66,55 @@ impl PartialEq for NumberFormatter {
     }
 }
 
+/// How to evaluate what the user types into e.g. a [`crate::DragValue`],
+/// when it isn't just a number.
+///
+/// The default is [`crate::quantity::eval_expression`].
+#[derive(Clone)]
+pub struct ExpressionParser(
+    Arc<dyn 'static + Sync + Send + Fn(&str, &ExpressionContext<'_>) -> Option<f64>>,
+);
+
+impl ExpressionParser {
+    /// The parser gets the text the user typed, and the range and units of the widget.
+    /// It returns `None` if the text isn't valid.
+    #[inline]
+    pub fn new(
+        parser: impl 'static + Sync + Send + Fn(&str, &ExpressionContext<'_>) -> Option<f64>,
+    ) -> Self {
+        Self(Arc::new(parser))
+    }
+
+    /// Evaluate the given text.
+    #[inline]
+    pub fn parse(&self, text: &str, ctx: &ExpressionContext<'_>) -> Option<f64> {
+        (self.0)(text, ctx)
+    }
+}
+
+impl Default for ExpressionParser {
+    fn default() -> Self {
+        // Shared, so that default styles compare equal:
+        static DEFAULT: std::sync::OnceLock<ExpressionParser> = std::sync::OnceLock::new();
+        DEFAULT
+            .get_or_init(|| Self::new(crate::quantity::eval_expression))
+            .clone()
+    }
+}
+
+impl std::fmt::Debug for ExpressionParser {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ExpressionParser")
+    }
+}
+
+impl PartialEq for ExpressionParser {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
 // ----------------------------------------------------------------------------
 
 /// Alias for a [`FontId`] (font of a certain size).
@@ -240,6 +296,17 @@ pub struct Style {
     #[cfg_attr(feature = "serde", serde(skip))]
     pub number_formatter: NumberFormatter,
 
+    /// Decimal and thousands separators used when formatting and parsing a [`crate::quantity::Quantity`],
+    /// e.g. in [`crate::DragValue::quantity`].
+    pub number_locale: NumberLocale,
+
+    /// Evaluates math and units typed into a [`crate::DragValue`] or [`crate::Slider`],
+    /// e.g. `2*pi` or `10 cm`.
+    ///
+    /// You can replace it to support your own syntax.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub expression_parser: ExpressionParser,
+
     /// If set, labels, buttons, etc. will use this to determine whether to wrap the text at the
     /// right edge of the [`Ui`] they are in. By default, this is `None`.
     ///
@@ -271,6 +338,16 @@ pub struct Style {
     /// How many seconds a typical animation should last.
     pub animation_time: f32,
 
+    /// How many seconds it takes to fade between the old and new [`Visuals`]
+    /// when they change, e.g. when switching between dark and light mode.
+    ///
+    /// The colors, strokes, roundings and shadows are interpolated;
+    /// everything else changes right away.
+    /// Use [`crate::Ui::disable_theme_transition`] to opt out for parts of the ui.
+    ///
+    /// The duration of the new style is used. Default: `0.0` (no transition).
+    pub theme_transition_time: f32,
+
     /// Options to help debug why egui behaves strangely.
     ///
     /// Only available in debug builds.
@@ -714,6 +791,9 @@ pub struct ScrollAnimation {
 
     /// The min / max scroll duration.
     pub duration: Rangef,
+
+    /// How the scrolling speeds up and slows down. (Default: [`ScrollEasing::EaseInOut`])
+    pub easing: ScrollEasing,
 }
 
 impl Default for ScrollAnimation {
@@ -721,6 +801,7 @@ impl Default for ScrollAnimation {
         Self {
             points_per_second: 1000.0,
             duration: Rangef::new(0.1, 0.3),
+            easing: ScrollEasing::default(),
         }
     }
 }
@@ -731,6 +812,7 @@ impl ScrollAnimation {
         Self {
             points_per_second,
             duration,
+            easing: ScrollEasing::default(),
         }
     }
 
@@ -739,6 +821,7 @@ impl ScrollAnimation {
         Self {
             points_per_second: f32::INFINITY,
             duration: Rangef::new(0.0, 0.0),
+            easing: ScrollEasing::default(),
         }
     }
 
@@ -747,9 +830,17 @@ impl ScrollAnimation {
         Self {
             points_per_second: f32::INFINITY,
             duration: Rangef::new(t, t),
+            easing: ScrollEasing::default(),
         }
     }
 
+    /// How the scrolling speeds up and slows down.
+    #[inline]
+    pub fn easing(mut self, easing: ScrollEasing) -> Self {
+        self.easing = easing;
+        self
+    }
+
     pub fn ui(&mut self, ui: &mut crate::Ui) {
         crate::Grid::new("scroll_animation").show(ui, |ui| {
             ui.label("Scroll animation:");
@@ -778,10 +869,63 @@ impl ScrollAnimation {
             );
             ui.label("seconds");
             ui.end_row();
+
+            ui.label("Easing:");
+            ComboBox::from_id_salt("scroll_easing")
+                .selected_text(format!("{:?}", self.easing))
+                .show_ui(ui, |ui| {
+                    for easing in ScrollEasing::ALL {
+                        ui.selectable_value(&mut self.easing, easing, format!("{easing:?}"));
+                    }
+                });
+            ui.end_row();
         });
     }
 }
 
+/// The easing curve of a [`ScrollAnimation`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum ScrollEasing {
+    /// Constant speed.
+    Linear,
+
+    /// Speed up, then slow down.
+    #[default]
+    EaseInOut,
+
+    /// Start fast, then slow down.
+    QuadraticOut,
+
+    /// Start fast, then slow down more gently than [`Self::QuadraticOut`].
+    CubicOut,
+
+    /// Start very fast, then slow down a lot.
+    ExponentialOut,
+}
+
+impl ScrollEasing {
+    pub const ALL: [Self; 5] = [
+        Self::Linear,
+        Self::EaseInOut,
+        Self::QuadraticOut,
+        Self::CubicOut,
+        Self::ExponentialOut,
+    ];
+
+    /// Map the time `t` (`0..=1`) to how far along the animation is (`0..=1`).
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Self::Linear => emath::easing::linear(t),
+            Self::EaseInOut => emath::ease_in_ease_out(t),
+            Self::QuadraticOut => emath::easing::quadratic_out(t),
+            Self::CubicOut => emath::easing::cubic_out(t),
+            Self::ExponentialOut => emath::easing::exponential_out(t),
+        }
+    }
+}
+
 // ----------------------------------------------------------------------------
 
 /// How and when interaction happens.
@@ -822,6 +966,149 @@ pub struct Interaction {
     /// The default is `true`, but text selection can be slightly glitchy,
     /// so you may want to disable it.
     pub multi_widget_text_select: bool,
+
+    /// Show `−` and `+` buttons next to each [`crate::Slider`],
+    /// for changing the value one step at a time without dragging.
+    ///
+    /// Can be overridden for a single slider with [`crate::Slider::steppers`].
+    pub slider_steppers: bool,
+
+    /// Let the user type in the value of a [`crate::Slider`] in a popover,
+    /// opened by pressing Enter while the slider has keyboard focus, or by double-clicking it.
+    ///
+    /// Can be overridden for a single slider with [`crate::Slider::value_popover`].
+    pub slider_value_popover: bool,
+
+    /// Snap [`crate::Window`]s to edges and a grid while dragging them.
+    pub window_snap: WindowSnap,
+
+    /// Open context menus by pressing and holding on touch screens.
+    pub long_press: LongPress,
+}
+
+/// How a long press (touch and hold) is detected on touch screens.
+///
+/// A long press opens the [`crate::Response::context_menu`], just like a secondary click.
+/// While the finger is held down, a ring around it fills up to show that a long press is coming.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct LongPress {
+    /// How long (in seconds) the finger must be held down.
+    ///
+    /// Set to [`f32::INFINITY`] to turn off long presses.
+    pub duration: f32,
+
+    /// How far (in points) the finger may move before the long press is cancelled.
+    ///
+    /// A long press is also cancelled if anything is scrolled or zoomed.
+    pub max_dist: f32,
+
+    /// Show a ring filling up around the finger before the long press fires.
+    pub show_peek: bool,
+}
+
+impl Default for LongPress {
+    fn default() -> Self {
+        Self {
+            duration: 0.5,
+            max_dist: 6.0,
+            show_peek: true,
+        }
+    }
+}
+
+impl LongPress {
+    pub fn ui(&mut self, ui: &mut crate::Ui) {
+        let Self {
+            duration,
+            max_dist,
+            show_peek,
+        } = self;
+
+        crate::Grid::new("long_press").show(ui, |ui| {
+            ui.label("Duration:");
+            ui.add(
+                DragValue::new(duration)
+                    .range(0.1..=f32::INFINITY)
+                    .speed(0.05)
+                    .suffix(" s"),
+            );
+            ui.end_row();
+
+            ui.label("Max movement:");
+            ui.add(DragValue::new(max_dist).range(0.0..=50.0).suffix(" pt"));
+            ui.end_row();
+        });
+        ui.checkbox(show_peek, "Show progress ring");
+    }
+}
+
+/// How [`crate::Window`]s (and other movable [`crate::Area`]s) snap into place while being dragged.
+///
+/// Each distance is how close (in points) an edge must be to snap. Zero turns that kind of snapping off,
+/// which is the default.
+///
+/// Turn snapping off for a single window with [`crate::Window::snap`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct WindowSnap {
+    /// Snap to the edges of the screen (or the rect the window is constrained to).
+    pub screen_edges: f32,
+
+    /// Snap to the edges of other windows, both side by side and aligned.
+    pub window_edges: f32,
+
+    /// Snap the top left corner to a grid with cells of this size.
+    ///
+    /// Snapping to edges takes precedence.
+    pub grid: f32,
+
+    /// Show lines where a window snapped to an edge.
+    pub show_guides: bool,
+}
+
+impl Default for WindowSnap {
+    fn default() -> Self {
+        Self {
+            screen_edges: 0.0,
+            window_edges: 0.0,
+            grid: 0.0,
+            show_guides: true,
+        }
+    }
+}
+
+impl WindowSnap {
+    /// Is any kind of snapping turned on?
+    pub fn is_enabled(&self) -> bool {
+        0.0 < self.screen_edges || 0.0 < self.window_edges || 0.0 < self.grid
+    }
+
+    pub fn ui(&mut self, ui: &mut crate::Ui) {
+        let Self {
+            screen_edges,
+            window_edges,
+            grid,
+            show_guides,
+        } = self;
+
+        crate::Grid::new("window_snap").show(ui, |ui| {
+            ui.label("Snap to screen edges:");
+            ui.add(DragValue::new(screen_edges).range(0.0..=50.0).suffix(" pt"));
+            ui.end_row();
+
+            ui.label("Snap to window edges:");
+            ui.add(DragValue::new(window_edges).range(0.0..=50.0).suffix(" pt"));
+            ui.end_row();
+
+            ui.label("Snap to grid:");
+            ui.add(DragValue::new(grid).range(0.0..=100.0).suffix(" pt"));
+            ui.end_row();
+        });
+        ui.checkbox(show_guides, "Show snap guides");
+    }
 }
 
 /// Look and feel of the text cursor.
@@ -923,6 +1210,11 @@ pub struct Visuals {
     /// Highlight the topmost window.
     pub window_highlight_topmost: bool,
 
+    /// Painted over the content of a viewport while a modal child viewport blocks its input.
+    ///
+    /// See [`crate::ViewportBuilder::with_modal`].
+    pub modal_backdrop: Color32,
+
     pub menu_rounding: Rounding,
 
     /// Panel background color
@@ -1019,6 +1311,106 @@ impl Visuals {
     pub fn gray_out(&self, color: Color32) -> Color32 {
         crate::ecolor::tint_color_towards(color, self.fade_out_to_color())
     }
+
+    /// Interpolate between `self` (at `t = 0`) and `other` (at `t = 1`).
+    ///
+    /// Colors, strokes, roundings, shadows and sizes are interpolated.
+    /// Everything else (like [`Self::dark_mode`]) is taken from `other`.
+    ///
+    /// This is used for [`Style::theme_transition_time`].
+    pub fn lerp(&self, other: &Self, t: f32) -> Self {
+        let Self {
+            dark_mode: _,
+            override_text_color,
+            widgets,
+            selection,
+            hyperlink_color,
+            faint_bg_color,
+            extreme_bg_color,
+            code_bg_color,
+            warn_fg_color,
+            error_fg_color,
+            window_rounding,
+            window_shadow,
+            window_fill,
+            window_stroke,
+            window_highlight_topmost: _,
+            modal_backdrop,
+            menu_rounding,
+            panel_fill,
+            popup_shadow,
+            resize_corner_size,
+            text_cursor,
+            clip_rect_margin,
+            button_frame: _,
+            collapsing_header_frame: _,
+            indent_has_left_vline: _,
+            striped: _,
+            slider_trailing_fill: _,
+            handle_shape: _,
+            interact_cursor: _,
+            image_loading_spinners: _,
+            numeric_color_space: _,
+        } = self;
+
+        Self {
+            override_text_color: match (override_text_color, other.override_text_color) {
+                (Some(from), Some(to)) => Some(from.lerp_to_gamma(to, t)),
+                (_, to) => to,
+            },
+            widgets: widgets.lerp(&other.widgets, t),
+            selection: Selection {
+                bg_fill: selection.bg_fill.lerp_to_gamma(other.selection.bg_fill, t),
+                stroke: lerp_stroke(selection.stroke, other.selection.stroke, t),
+            },
+            hyperlink_color: hyperlink_color.lerp_to_gamma(other.hyperlink_color, t),
+            faint_bg_color: faint_bg_color.lerp_to_gamma(other.faint_bg_color, t),
+            extreme_bg_color: extreme_bg_color.lerp_to_gamma(other.extreme_bg_color, t),
+            code_bg_color: code_bg_color.lerp_to_gamma(other.code_bg_color, t),
+            warn_fg_color: warn_fg_color.lerp_to_gamma(other.warn_fg_color, t),
+            error_fg_color: error_fg_color.lerp_to_gamma(other.error_fg_color, t),
+            window_rounding: lerp_rounding(*window_rounding, other.window_rounding, t),
+            window_shadow: lerp_shadow(*window_shadow, other.window_shadow, t),
+            window_fill: window_fill.lerp_to_gamma(other.window_fill, t),
+            window_stroke: lerp_stroke(*window_stroke, other.window_stroke, t),
+            modal_backdrop: modal_backdrop.lerp_to_gamma(other.modal_backdrop, t),
+            menu_rounding: lerp_rounding(*menu_rounding, other.menu_rounding, t),
+            panel_fill: panel_fill.lerp_to_gamma(other.panel_fill, t),
+            popup_shadow: lerp_shadow(*popup_shadow, other.popup_shadow, t),
+            resize_corner_size: emath::lerp(*resize_corner_size..=other.resize_corner_size, t),
+            text_cursor: TextCursorStyle {
+                stroke: lerp_stroke(text_cursor.stroke, other.text_cursor.stroke, t),
+                ..other.text_cursor.clone()
+            },
+            clip_rect_margin: emath::lerp(*clip_rect_margin..=other.clip_rect_margin, t),
+            ..other.clone()
+        }
+    }
+}
+
+fn lerp_stroke(from: Stroke, to: Stroke, t: f32) -> Stroke {
+    Stroke::new(
+        emath::lerp(from.width..=to.width, t),
+        from.color.lerp_to_gamma(to.color, t),
+    )
+}
+
+fn lerp_rounding(from: Rounding, to: Rounding, t: f32) -> Rounding {
+    Rounding {
+        nw: emath::lerp(from.nw..=to.nw, t),
+        ne: emath::lerp(from.ne..=to.ne, t),
+        sw: emath::lerp(from.sw..=to.sw, t),
+        se: emath::lerp(from.se..=to.se, t),
+    }
+}
+
+fn lerp_shadow(from: Shadow, to: Shadow, t: f32) -> Shadow {
+    Shadow {
+        offset: from.offset + t * (to.offset - from.offset),
+        blur: emath::lerp(from.blur..=to.blur, t),
+        spread: emath::lerp(from.spread..=to.spread, t),
+        color: from.color.lerp_to_gamma(to.color, t),
+    }
 }
 
 /// Selected text, selected elements etc
@@ -1071,6 +1463,16 @@ pub struct Widgets {
 }
 
 impl Widgets {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        Self {
+            noninteractive: self.noninteractive.lerp(&other.noninteractive, t),
+            inactive: self.inactive.lerp(&other.inactive, t),
+            hovered: self.hovered.lerp(&other.hovered, t),
+            active: self.active.lerp(&other.active, t),
+            open: self.open.lerp(&other.open, t),
+        }
+    }
+
     pub fn style(&self, response: &Response) -> &WidgetVisuals {
         if !response.sense.interactive() {
             &self.noninteractive
@@ -1120,6 +1522,17 @@ impl WidgetVisuals {
     pub fn text_color(&self) -> Color32 {
         self.fg_stroke.color
     }
+
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        Self {
+            bg_fill: self.bg_fill.lerp_to_gamma(other.bg_fill, t),
+            weak_bg_fill: self.weak_bg_fill.lerp_to_gamma(other.weak_bg_fill, t),
+            bg_stroke: lerp_stroke(self.bg_stroke, other.bg_stroke, t),
+            rounding: lerp_rounding(self.rounding, other.rounding, t),
+            fg_stroke: lerp_stroke(self.fg_stroke, other.fg_stroke, t),
+            expansion: emath::lerp(self.expansion..=other.expansion, t),
+        }
+    }
 }
 
 /// Options for help debug egui by adding extra visualization
@@ -1219,12 +1632,15 @@ impl Default for Style {
             text_styles: default_text_styles(),
             drag_value_text_style: TextStyle::Button,
             number_formatter: NumberFormatter(Arc::new(emath::format_with_decimals_in_range)),
+            number_locale: NumberLocale::default(),
+            expression_parser: Default::default(),
             wrap: None,
             wrap_mode: None,
             spacing: Spacing::default(),
             interaction: Interaction::default(),
             visuals: Visuals::default(),
             animation_time: 1.0 / 12.0,
+            theme_transition_time: 0.0,
             #[cfg(debug_assertions)]
             debug: Default::default(),
             explanation_tooltips: false,
@@ -1273,6 +1689,10 @@ impl Default for Interaction {
             tooltip_grace_time: 0.2,
             selectable_labels: true,
             multi_widget_text_select: true,
+            slider_steppers: false,
+            slider_value_popover: false,
+            window_snap: Default::default(),
+            long_press: Default::default(),
         }
     }
 }
@@ -1302,6 +1722,7 @@ impl Visuals {
             window_fill: Color32::from_gray(27),
             window_stroke: Stroke::new(1.0, Color32::from_gray(60)),
             window_highlight_topmost: true,
+            modal_backdrop: Color32::from_black_alpha(96),
 
             menu_rounding: Rounding::same(6.0),
 
@@ -1520,12 +1941,15 @@ impl Style {
             text_styles,
             drag_value_text_style,
             number_formatter: _, // can't change callbacks in the UI
+            number_locale,
+            expression_parser: _, // can't change callbacks in the UI
             wrap: _,
             wrap_mode,
             spacing,
             interaction,
             visuals,
             animation_time,
+            theme_transition_time,
             #[cfg(debug_assertions)]
             debug,
             explanation_tooltips,
@@ -1601,6 +2025,22 @@ impl Style {
                 });
             ui.end_row();
 
+            ui.label("Number locale");
+            crate::ComboBox::from_id_salt("number_locale")
+                .selected_text(number_locale.format(1234567.5, 1..=1))
+                .show_ui(ui, |ui| {
+                    for locale in [
+                        NumberLocale::PLAIN,
+                        NumberLocale::ENGLISH,
+                        NumberLocale::GERMAN,
+                        NumberLocale::FRENCH,
+                        NumberLocale::SWISS,
+                    ] {
+                        ui.selectable_value(number_locale, locale, locale.format(1234567.5, 1..=1));
+                    }
+                });
+            ui.end_row();
+
             ui.label("Text Wrap Mode");
             crate::ComboBox::from_id_salt("text_wrap_mode")
                 .selected_text(format!("{wrap_mode:?}"))
@@ -1626,6 +2066,15 @@ impl Style {
                     .suffix(" s"),
             );
             ui.end_row();
+
+            ui.label("Theme transition duration");
+            ui.add(
+                DragValue::new(theme_transition_time)
+                    .range(0.0..=2.0)
+                    .speed(0.02)
+                    .suffix(" s"),
+            );
+            ui.end_row();
         });
 
         ui.collapsing("🔠 Text Styles", |ui| text_styles_ui(ui, text_styles));
@@ -1805,6 +2254,10 @@ impl Interaction {
             tooltip_grace_time,
             selectable_labels,
             multi_widget_text_select,
+            slider_steppers,
+            slider_value_popover,
+            window_snap,
+            long_press,
         } = self;
 
         ui.spacing_mut().item_spacing = vec2(12.0, 8.0);
@@ -1861,6 +2314,15 @@ impl Interaction {
             }
         });
 
+        ui.checkbox(slider_steppers, "Show −/+ buttons on sliders");
+        ui.checkbox(
+            slider_value_popover,
+            "Type in slider values on Enter or double-click",
+        );
+
+        ui.collapsing("Window snapping", |ui| window_snap.ui(ui));
+        ui.collapsing("Long press", |ui| long_press.ui(ui));
+
         ui.vertical_centered(|ui| reset_button(ui, self, "Reset interaction settings"));
     }
 }
@@ -1984,6 +2446,7 @@ impl Visuals {
             window_fill,
             window_stroke,
             window_highlight_topmost,
+            modal_backdrop,
 
             menu_rounding,
 
@@ -2074,6 +2537,10 @@ impl Visuals {
                     ui.label("Shadow");
                     ui.add(window_shadow);
                     ui.end_row();
+
+                    ui.label("Modal backdrop");
+                    ui.color_edit_button_srgba(modal_backdrop);
+                    ui.end_row();
                 });
 
             ui.checkbox(window_highlight_topmost, "Highlight topmost Window");
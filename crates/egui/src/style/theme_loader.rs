@@ -0,0 +1,191 @@
+use std::path::Path;
+
+use super::Style;
+
+/// The file format of a theme, see [`ThemeLoader`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ThemeFormat {
+    /// [RON](https://github.com/ron-rs/ron), the same format egui uses for persistence.
+    Ron,
+
+    /// [TOML](https://toml.io).
+    Toml,
+}
+
+impl ThemeFormat {
+    /// Guess the format from the file extension (`.ron` or `.toml`).
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?;
+        if extension.eq_ignore_ascii_case("ron") {
+            Some(Self::Ron)
+        } else if extension.eq_ignore_ascii_case("toml") {
+            Some(Self::Toml)
+        } else {
+            None
+        }
+    }
+}
+
+/// An error from [`ThemeLoader`].
+#[derive(Debug)]
+pub enum ThemeError {
+    /// Failed to read the theme file.
+    Io(std::io::Error),
+
+    /// The file extension was neither `.ron` nor `.toml`.
+    UnknownFormat(std::path::PathBuf),
+
+    /// Failed to parse the theme as RON.
+    Ron(ron::error::SpannedError),
+
+    /// Failed to parse the theme as TOML.
+    Toml(toml::de::Error),
+
+    /// Failed to write the theme.
+    Serialize(String),
+}
+
+impl std::fmt::Display for ThemeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "Failed to read theme: {err}"),
+            Self::UnknownFormat(path) => write!(
+                f,
+                "Unknown theme format of {path:?}: expected a .ron or .toml file"
+            ),
+            Self::Ron(err) => write!(f, "Failed to parse RON theme: {err}"),
+            Self::Toml(err) => write!(f, "Failed to parse TOML theme: {err}"),
+            Self::Serialize(err) => write!(f, "Failed to write theme: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for ThemeError {}
+
+/// Reads and writes a full [`Style`] (including its [`crate::Visuals`]) as RON or TOML.
+///
+/// Any field missing from the file gets its default value,
+/// so a theme file only needs to contain what it changes.
+///
+/// This is useful for iterating on the look of your app without recompiling.
+/// `eframe` can also reload a theme file whenever it changes, see `eframe::WatchThemeFile`.
+///
+/// ```
+/// # use egui::style::ThemeLoader;
+/// let style = ThemeLoader::from_toml_str(
+///     r#"
+///     [spacing]
+///     item_spacing = { x = 12.0, y = 6.0 }
+///
+///     [visuals]
+///     dark_mode = false
+///     "#,
+/// )
+/// .unwrap();
+/// assert_eq!(style.spacing.item_spacing, egui::vec2(12.0, 6.0));
+/// assert!(!style.visuals.dark_mode);
+/// ```
+///
+/// The easiest way to get started is to write out the current style with [`Self::to_ron_string`] or [`Self::to_toml_string`],
+/// and then edit that.
+pub struct ThemeLoader;
+
+impl ThemeLoader {
+    /// Load a theme file, detecting the format from its extension (`.ron` or `.toml`).
+    ///
+    /// # Errors
+    /// If the file can't be read, has an unknown extension, or fails to parse.
+    pub fn load(path: impl AsRef<Path>) -> Result<Style, ThemeError> {
+        let path = path.as_ref();
+        let format = ThemeFormat::from_path(path)
+            .ok_or_else(|| ThemeError::UnknownFormat(path.to_owned()))?;
+        let text = std::fs::read_to_string(path).map_err(ThemeError::Io)?;
+        Self::from_str(&text, format)
+    }
+
+    /// Parse a theme in the given format.
+    ///
+    /// # Errors
+    /// If the text is not a valid theme.
+    pub fn from_str(text: &str, format: ThemeFormat) -> Result<Style, ThemeError> {
+        match format {
+            ThemeFormat::Ron => Self::from_ron_str(text),
+            ThemeFormat::Toml => Self::from_toml_str(text),
+        }
+    }
+
+    /// Parse a theme written in RON.
+    ///
+    /// # Errors
+    /// If the text is not a valid theme.
+    pub fn from_ron_str(text: &str) -> Result<Style, ThemeError> {
+        ron::from_str(text).map_err(ThemeError::Ron)
+    }
+
+    /// Parse a theme written in TOML.
+    ///
+    /// # Errors
+    /// If the text is not a valid theme.
+    pub fn from_toml_str(text: &str) -> Result<Style, ThemeError> {
+        toml::from_str(text).map_err(ThemeError::Toml)
+    }
+
+    /// Write a theme in the given format.
+    ///
+    /// # Errors
+    /// If the style can't be represented in the format.
+    pub fn to_string(style: &Style, format: ThemeFormat) -> Result<String, ThemeError> {
+        match format {
+            ThemeFormat::Ron => Self::to_ron_string(style),
+            ThemeFormat::Toml => Self::to_toml_string(style),
+        }
+    }
+
+    /// Write a theme as (pretty-printed) RON.
+    ///
+    /// # Errors
+    /// If the style can't be represented as RON.
+    pub fn to_ron_string(style: &Style) -> Result<String, ThemeError> {
+        ron::ser::to_string_pretty(style, Default::default())
+            .map_err(|err| ThemeError::Serialize(err.to_string()))
+    }
+
+    /// Write a theme as TOML.
+    ///
+    /// # Errors
+    /// If the style can't be represented as TOML.
+    pub fn to_toml_string(style: &Style) -> Result<String, ThemeError> {
+        toml::to_string_pretty(style).map_err(|err| ThemeError::Serialize(err.to_string()))
+    }
+
+    /// Write a theme file, picking the format from its extension (`.ron` or `.toml`).
+    ///
+    /// # Errors
+    /// If the extension is unknown, or the file can't be written.
+    pub fn save(path: impl AsRef<Path>, style: &Style) -> Result<(), ThemeError> {
+        let path = path.as_ref();
+        let format = ThemeFormat::from_path(path)
+            .ok_or_else(|| ThemeError::UnknownFormat(path.to_owned()))?;
+        let text = Self::to_string(style, format)?;
+        std::fs::write(path, text).map_err(ThemeError::Io)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let mut style = Style::default();
+        style.spacing.item_spacing = crate::vec2(3.0, 4.0);
+        style.visuals = crate::Visuals::light();
+
+        for format in [ThemeFormat::Ron, ThemeFormat::Toml] {
+            let text = ThemeLoader::to_string(&style, format).unwrap();
+            let mut loaded = ThemeLoader::from_str(&text, format).unwrap();
+            loaded.number_formatter = style.number_formatter.clone(); // not serialized
+            assert_eq!(loaded, style, "{format:?}");
+        }
+    }
+}
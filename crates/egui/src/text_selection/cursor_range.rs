@@ -329,7 +329,7 @@ fn move_single_cursor(
             } else if modifiers.mac_cmd {
                 *cursor = galley.cursor_begin_of_row(cursor);
             } else {
-                *cursor = galley.cursor_left_one_character(cursor);
+                *cursor = galley.cursor_left_visually(cursor);
             }
         }
         Key::ArrowRight => {
@@ -339,7 +339,7 @@ fn move_single_cursor(
             } else if modifiers.mac_cmd {
                 *cursor = galley.cursor_end_of_row(cursor);
             } else {
-                *cursor = galley.cursor_right_one_character(cursor);
+                *cursor = galley.cursor_right_visually(cursor);
             }
         }
         Key::ArrowUp => {
@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use crate::{pos2, vec2, Galley, Painter, Rect, Ui, Visuals};
+use crate::{epaint::text::Row, pos2, vec2, Color32, Galley, Painter, Rect, Ui, Visuals};
 
 use super::CursorRange;
 
@@ -32,6 +32,22 @@ pub fn paint_text_selection(
 
     for ri in min.row..=max.row {
         let row = &mut galley.rows[ri];
+
+        if row.has_rtl() {
+            // Mixed-direction text can need several rectangles to cover the selection:
+            let first = if ri == min.row { min.column } else { 0 };
+            let last = if ri == max.row {
+                max.column
+            } else {
+                row.char_count_excluding_newline()
+            };
+            for x_range in row.x_ranges(first..last) {
+                let rect = Rect::from_x_y_ranges(x_range, row.min_y()..=row.max_y());
+                add_selection_rect(row, ri, rect, color, &mut new_vertex_indices);
+            }
+            continue;
+        }
+
         let left = if ri == min.row {
             row.x_offset(min.column)
         } else {
@@ -49,44 +65,53 @@ pub fn paint_text_selection(
         };
 
         let rect = Rect::from_min_max(pos2(left, row.min_y()), pos2(right, row.max_y()));
-        let mesh = &mut row.visuals.mesh;
-
-        // Time to insert the selection rectangle into the row mesh.
-        // It should be on top (after) of any background in the galley,
-        // but behind (before) any glyphs. The row visuals has this information:
-        let glyph_index_start = row.visuals.glyph_index_start;
-
-        // Start by appending the selection rectangle to end of the mesh, as two triangles (= 6 indices):
-        let num_indices_before = mesh.indices.len();
-        mesh.add_colored_rect(rect, color);
-        assert_eq!(num_indices_before + 6, mesh.indices.len());
-
-        // Copy out the new triangles:
-        let selection_triangles = [
-            mesh.indices[num_indices_before],
-            mesh.indices[num_indices_before + 1],
-            mesh.indices[num_indices_before + 2],
-            mesh.indices[num_indices_before + 3],
-            mesh.indices[num_indices_before + 4],
-            mesh.indices[num_indices_before + 5],
-        ];
-
-        // Move every old triangle forwards by 6 indices to make room for the new triangle:
-        for i in (glyph_index_start..num_indices_before).rev() {
-            mesh.indices.swap(i, i + 6);
-        }
-        // Put the new triangle in place:
-        mesh.indices[glyph_index_start..glyph_index_start + 6]
-            .clone_from_slice(&selection_triangles);
+        add_selection_rect(row, ri, rect, color, &mut new_vertex_indices);
+    }
+}
+
+fn add_selection_rect(
+    row: &mut Row,
+    row_index: usize,
+    rect: Rect,
+    color: Color32,
+    new_vertex_indices: &mut Option<&mut Vec<RowVertexIndices>>,
+) {
+    let mesh = &mut row.visuals.mesh;
+
+    // Time to insert the selection rectangle into the row mesh.
+    // It should be on top (after) of any background in the galley,
+    // but behind (before) any glyphs. The row visuals has this information:
+    let glyph_index_start = row.visuals.glyph_index_start;
+
+    // Start by appending the selection rectangle to end of the mesh, as two triangles (= 6 indices):
+    let num_indices_before = mesh.indices.len();
+    mesh.add_colored_rect(rect, color);
+    assert_eq!(num_indices_before + 6, mesh.indices.len());
+
+    // Copy out the new triangles:
+    let selection_triangles = [
+        mesh.indices[num_indices_before],
+        mesh.indices[num_indices_before + 1],
+        mesh.indices[num_indices_before + 2],
+        mesh.indices[num_indices_before + 3],
+        mesh.indices[num_indices_before + 4],
+        mesh.indices[num_indices_before + 5],
+    ];
+
+    // Move every old triangle forwards by 6 indices to make room for the new triangle:
+    for i in (glyph_index_start..num_indices_before).rev() {
+        mesh.indices.swap(i, i + 6);
+    }
+    // Put the new triangle in place:
+    mesh.indices[glyph_index_start..glyph_index_start + 6].clone_from_slice(&selection_triangles);
 
-        row.visuals.mesh_bounds = mesh.calc_bounds();
+    row.visuals.mesh_bounds = mesh.calc_bounds();
 
-        if let Some(new_vertex_indices) = &mut new_vertex_indices {
-            new_vertex_indices.push(RowVertexIndices {
-                row: ri,
-                vertex_indices: selection_triangles,
-            });
-        }
+    if let Some(new_vertex_indices) = new_vertex_indices {
+        new_vertex_indices.push(RowVertexIndices {
+            row: row_index,
+            vertex_indices: selection_triangles,
+        });
     }
 }
 
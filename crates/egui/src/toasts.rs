@@ -0,0 +1,384 @@
+//! Toast notifications: short messages that pop up in a corner of the screen and go away by themselves.
+//!
+//! Show one with [`crate::Context::toast`]:
+//!
+//! ```
+//! # let ctx = egui::Context::default();
+//! ctx.toast(egui::Toast::success("Saved"));
+//! ```
+//!
+//! Toasts are shown stacked in the root viewport, on top of everything else.
+//! They fade out after their [`Toast::duration`], or when clicked.
+//! Hovering a toast pauses its countdown.
+
+use crate::{
+    emath::lerp, pos2, vec2, Align, Align2, Area, Color32, Context, Frame, Id, Order, Rect,
+    Response, RichText, Sense, Ui, ViewportId, WidgetText,
+};
+
+/// What a [`Toast`] is about, which decides its icon and color.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum ToastKind {
+    /// No icon.
+    Basic,
+
+    #[default]
+    Info,
+
+    Success,
+
+    Warning,
+
+    Error,
+}
+
+impl ToastKind {
+    /// The icon shown before the text, if any.
+    pub fn icon(self) -> Option<&'static str> {
+        match self {
+            Self::Basic => None,
+            Self::Info => Some("ℹ"),
+            Self::Success => Some("✔"),
+            Self::Warning => Some("⚠"),
+            Self::Error => Some("🗙"),
+        }
+    }
+
+    /// The color of the icon and the progress bar.
+    pub fn color(self, visuals: &crate::Visuals) -> Color32 {
+        match self {
+            Self::Basic => visuals.text_color(),
+            Self::Info => visuals.hyperlink_color,
+            Self::Success => Color32::from_rgb(0, 180, 80),
+            Self::Warning => visuals.warn_fg_color,
+            Self::Error => visuals.error_fg_color,
+        }
+    }
+}
+
+/// A notification to show with [`crate::Context::toast`].
+#[derive(Clone)]
+pub struct Toast {
+    pub kind: ToastKind,
+
+    pub text: WidgetText,
+
+    /// How many seconds to show the toast for.
+    ///
+    /// `None` means the toast stays until it is clicked, or dismissed with [`Toasts::dismiss`].
+    pub duration: Option<f32>,
+
+    /// Show how much of the [`Self::duration`] is left, as a thin bar at the bottom of the toast.
+    pub show_progress_bar: bool,
+}
+
+impl Toast {
+    pub fn new(kind: ToastKind, text: impl Into<WidgetText>) -> Self {
+        Self {
+            kind,
+            text: text.into(),
+            duration: Some(4.0),
+            show_progress_bar: true,
+        }
+    }
+
+    /// A toast without an icon.
+    pub fn basic(text: impl Into<WidgetText>) -> Self {
+        Self::new(ToastKind::Basic, text)
+    }
+
+    pub fn info(text: impl Into<WidgetText>) -> Self {
+        Self::new(ToastKind::Info, text)
+    }
+
+    pub fn success(text: impl Into<WidgetText>) -> Self {
+        Self::new(ToastKind::Success, text)
+    }
+
+    pub fn warning(text: impl Into<WidgetText>) -> Self {
+        Self::new(ToastKind::Warning, text)
+    }
+
+    pub fn error(text: impl Into<WidgetText>) -> Self {
+        Self::new(ToastKind::Error, text)
+    }
+
+    /// How many seconds to show the toast for, or `None` to show it until it is clicked.
+    ///
+    /// Default: 4 seconds.
+    #[inline]
+    pub fn duration(mut self, seconds: impl Into<Option<f32>>) -> Self {
+        self.duration = seconds.into();
+        self
+    }
+
+    /// Show how much time is left as a thin bar at the bottom of the toast.
+    ///
+    /// Default: `true`.
+    #[inline]
+    pub fn show_progress_bar(mut self, show_progress_bar: bool) -> Self {
+        self.show_progress_bar = show_progress_bar;
+        self
+    }
+}
+
+#[derive(Clone)]
+struct ShownToast {
+    id: Id,
+    toast: Toast,
+
+    /// Seconds left before the toast starts to fade out.
+    remaining: f32,
+
+    /// Has it been shown yet?
+    appeared: bool,
+
+    /// Fading out, and removed once invisible.
+    dismissed: bool,
+}
+
+#[derive(Clone)]
+struct State {
+    toasts: Vec<ShownToast>,
+    anchor: Align2,
+    next_id: u64,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            toasts: Vec::new(),
+            anchor: Align2::RIGHT_BOTTOM,
+            next_id: 0,
+        }
+    }
+}
+
+/// Manages the toasts shown with [`crate::Context::toast`].
+///
+/// The toasts are shown in the root viewport, so they don't move or disappear
+/// when other viewports open or close.
+#[derive(Clone, Copy, Debug)]
+pub struct Toasts;
+
+impl Toasts {
+    pub(crate) fn register(ctx: &Context) {
+        ctx.on_end_pass("Toasts", std::sync::Arc::new(Self::end_pass));
+    }
+
+    /// Show a toast.
+    ///
+    /// Returns an id you can use to [`Self::dismiss`] it.
+    /// You usually want to use [`crate::Context::toast`] instead.
+    pub fn add(ctx: &Context, toast: Toast) -> Id {
+        let id = ctx.data_mut(|data| {
+            let state = data.get_temp_mut_or_default::<State>(Id::NULL);
+            let id = Id::new("__toast").with(state.next_id);
+            state.next_id += 1;
+            state.toasts.push(ShownToast {
+                id,
+                remaining: toast.duration.unwrap_or(f32::INFINITY),
+                toast,
+                appeared: false,
+                dismissed: false,
+            });
+            id
+        });
+        ctx.request_repaint_of(ViewportId::ROOT);
+        id
+    }
+
+    /// Fade out the toast with the given id.
+    pub fn dismiss(ctx: &Context, id: Id) {
+        ctx.data_mut(|data| {
+            let state = data.get_temp_mut_or_default::<State>(Id::NULL);
+            for shown in &mut state.toasts {
+                if shown.id == id {
+                    shown.dismissed = true;
+                }
+            }
+        });
+        ctx.request_repaint_of(ViewportId::ROOT);
+    }
+
+    /// Fade out all toasts.
+    pub fn dismiss_all(ctx: &Context) {
+        ctx.data_mut(|data| {
+            let state = data.get_temp_mut_or_default::<State>(Id::NULL);
+            for shown in &mut state.toasts {
+                shown.dismissed = true;
+            }
+        });
+        ctx.request_repaint_of(ViewportId::ROOT);
+    }
+
+    /// The number of toasts currently shown, including those fading out.
+    pub fn len(ctx: &Context) -> usize {
+        ctx.data(|data| data.get_temp::<State>(Id::NULL))
+            .map_or(0, |state| state.toasts.len())
+    }
+
+    /// Are no toasts shown?
+    pub fn is_empty(ctx: &Context) -> bool {
+        Self::len(ctx) == 0
+    }
+
+    /// Which corner (or edge) of the screen the toasts are stacked in.
+    pub fn anchor(ctx: &Context) -> Align2 {
+        ctx.data(|data| data.get_temp::<State>(Id::NULL))
+            .map_or(Align2::RIGHT_BOTTOM, |state| state.anchor)
+    }
+
+    /// Where to stack the toasts.
+    ///
+    /// Default: [`Align2::RIGHT_BOTTOM`].
+    pub fn set_anchor(ctx: &Context, anchor: Align2) {
+        ctx.data_mut(|data| {
+            data.get_temp_mut_or_default::<State>(Id::NULL).anchor = anchor;
+        });
+    }
+
+    fn end_pass(ctx: &Context) {
+        if ctx.viewport_id() != ViewportId::ROOT {
+            return;
+        }
+        let Some(mut state) = ctx.data(|data| data.get_temp::<State>(Id::NULL)) else {
+            return;
+        };
+        if state.toasts.is_empty() {
+            return;
+        }
+
+        let num_toasts = state.toasts.len();
+        let style = ctx.style();
+        let dt = ctx.input(|i| i.stable_dt);
+        let spacing = style.spacing.item_spacing.y;
+        let screen_rect = ctx.screen_rect().shrink(style.spacing.window_margin.left);
+
+        // Stack away from the anchor, e.g. upwards from the bottom of the screen:
+        let anchor = state.anchor;
+        let direction = if anchor.y() == Align::Max { -1.0 } else { 1.0 };
+        let mut pos = anchor.pos_in_rect(&screen_rect);
+        let mut next_timeout = f32::INFINITY;
+
+        state.toasts.retain_mut(|shown| {
+            // Starts out invisible, so that it fades in:
+            let opacity = ctx.animate_bool_with_time(
+                shown.id,
+                shown.appeared && !shown.dismissed,
+                style.animation_time,
+            );
+            shown.appeared = true;
+            if shown.dismissed && opacity == 0.0 {
+                return false;
+            }
+
+            let area = Area::new(shown.id)
+                .order(Order::Foreground)
+                .fixed_pos(pos)
+                .pivot(anchor)
+                .show(ctx, |ui| {
+                    ui.multiply_opacity(opacity);
+                    Self::toast_ui(ui, shown)
+                });
+
+            if area.inner.clicked() {
+                shown.dismissed = true;
+            } else if !shown.dismissed && !area.inner.contains_pointer() {
+                shown.remaining -= dt;
+                if shown.remaining <= 0.0 {
+                    shown.dismissed = true;
+                } else if shown.toast.show_progress_bar {
+                    ctx.request_repaint(); // animate the progress bar
+                } else {
+                    next_timeout = next_timeout.min(shown.remaining);
+                }
+            }
+
+            // Collapse the gap as the toast fades out:
+            pos.y += direction * (area.response.rect.height() + spacing) * opacity;
+            true
+        });
+
+        if next_timeout.is_finite() {
+            ctx.request_repaint_after_secs(next_timeout);
+        }
+
+        ctx.data_mut(|data| {
+            let current = data.get_temp_mut_or_default::<State>(Id::NULL);
+            // Keep any toasts that were added (from another thread) in the meantime:
+            let added = current
+                .toasts
+                .split_off(num_toasts.min(current.toasts.len()));
+            current.toasts = state.toasts;
+            current.toasts.extend(added);
+        });
+    }
+
+    fn toast_ui(ui: &mut Ui, shown: &ShownToast) -> Response {
+        let Toast {
+            kind,
+            text,
+            duration,
+            show_progress_bar,
+        } = &shown.toast;
+        let color = kind.color(ui.visuals());
+
+        let frame = Frame::popup(ui.style()).show(ui, |ui| {
+            ui.set_max_width(ui.spacing().tooltip_width);
+            ui.horizontal(|ui| {
+                if let Some(icon) = kind.icon() {
+                    ui.label(RichText::new(icon).color(color));
+                }
+                ui.label(text.clone());
+            });
+        });
+        let rect = frame.response.rect;
+
+        if let (Some(duration), true) = (duration, show_progress_bar) {
+            let fraction = (shown.remaining / duration).clamp(0.0, 1.0);
+            let bar_rect =
+                Rect::from_min_max(pos2(rect.left(), rect.bottom() - 2.0), rect.right_bottom())
+                    .shrink2(vec2(ui.visuals().menu_rounding.sw, 0.0));
+            let bar_rect = bar_rect.with_max_x(lerp(bar_rect.x_range(), fraction));
+            ui.painter().rect_filled(bar_rect, 0.0, color);
+        }
+
+        ui.interact(rect, shown.id.with("click"), Sense::click())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toasts_expire() {
+        let ctx = Context::default();
+        let run = |time: f64| {
+            let input = crate::RawInput {
+                time: Some(time),
+                ..Default::default()
+            };
+            let _ = ctx.run(input, |_| {});
+        };
+
+        ctx.toast(Toast::info("Short").duration(1.0));
+        ctx.toast(Toast::info("Forever").duration(None));
+        run(0.0);
+        assert_eq!(Toasts::len(&ctx), 2);
+
+        // Counting down, fading out, and then gone:
+        for i in 1..200 {
+            run(f64::from(i) / 60.0);
+        }
+        assert_eq!(Toasts::len(&ctx), 1);
+
+        Toasts::dismiss_all(&ctx);
+        for i in 200..300 {
+            run(f64::from(i) / 60.0);
+        }
+        assert!(Toasts::is_empty(&ctx));
+    }
+}
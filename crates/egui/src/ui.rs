@@ -93,6 +93,15 @@ pub struct Ui {
     /// and all widgets will assume a gray style.
     enabled: bool,
 
+    /// Widgets show their values as usual, but can't be edited. See [`Self::read_only`].
+    read_only: bool,
+
+    /// The contents are waiting for something, see [`Self::busy`].
+    busy: bool,
+
+    /// Is this the outermost busy [`Ui`], which paints the busy indicator when it is done?
+    paint_busy_indicator: bool,
+
     /// Set to true in special cases where we do one frame
     /// where we size up the contents of the Ui, without actually showing it.
     sizing_pass: bool,
@@ -128,6 +137,8 @@ impl Ui {
             max_rect,
             layout,
             disabled,
+            read_only,
+            busy,
             invisible,
             sizing_pass,
             style,
@@ -165,6 +176,9 @@ impl Ui {
             style,
             placer,
             enabled: true,
+            read_only: false,
+            busy: false,
+            paint_busy_indicator: false,
             sizing_pass,
             menu_state: None,
             stack: Arc::new(ui_stack),
@@ -185,10 +199,14 @@ impl Ui {
             },
             true,
         );
+        ui.ctx()
+            .register_in_ui_tree(ui.unique_id, None, Some(&ui.stack));
 
         if disabled {
             ui.disable();
         }
+        ui.read_only(read_only);
+        ui.busy(busy);
         if invisible {
             ui.set_invisible();
         }
@@ -254,6 +272,8 @@ impl Ui {
             max_rect,
             layout,
             disabled,
+            read_only,
+            busy,
             invisible,
             sizing_pass,
             style,
@@ -301,7 +321,7 @@ impl Ui {
             min_rect: placer.min_rect(),
             max_rect: placer.max_rect(),
         };
-        let child_ui = Ui {
+        let mut child_ui = Ui {
             id: stable_id,
             unique_id,
             next_auto_id_salt,
@@ -309,6 +329,9 @@ impl Ui {
             style,
             placer,
             enabled,
+            read_only: self.read_only,
+            busy: self.busy,
+            paint_busy_indicator: false,
             sizing_pass,
             menu_state: self.menu_state.clone(),
             stack: Arc::new(ui_stack),
@@ -329,6 +352,14 @@ impl Ui {
             },
             true,
         );
+        child_ui.ctx().register_in_ui_tree(
+            child_ui.unique_id,
+            Some(self.unique_id),
+            Some(&child_ui.stack),
+        );
+
+        child_ui.read_only(read_only);
+        child_ui.busy(busy);
 
         child_ui
     }
@@ -418,6 +449,18 @@ impl Ui {
         self.style = self.ctx().style();
     }
 
+    /// Show this [`Ui`] and its children with the new [`crate::Visuals`] right away
+    /// during a theme transition, instead of fading to them.
+    ///
+    /// See [`crate::Style::theme_transition_time`].
+    /// Call this before changing the visuals of this [`Ui`], as it replaces them.
+    pub fn disable_theme_transition(&mut self) {
+        let visuals = self.ctx().options(|opt| opt.style().visuals.clone());
+        if self.visuals() != &visuals {
+            *self.visuals_mut() = visuals;
+        }
+    }
+
     /// The current spacing options for this [`Ui`].
     /// Short for `ui.style().spacing`.
     #[inline]
@@ -515,6 +558,71 @@ impl Ui {
         }
     }
 
+    /// Is this [`Ui`] read-only? See [`Self::read_only`].
+    #[inline]
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Calling `read_only(true)` makes the widgets in this [`Ui`] show their values as usual,
+    /// but refuse all edits.
+    ///
+    /// Unlike [`Self::disable`], nothing is grayed out, which suits e.g. a form
+    /// the user is allowed to look at, but not change.
+    /// All interaction is blocked, except for scrolling [`crate::ScrollArea`]s.
+    /// [`Self::is_enabled`] returns `false` in a read-only [`Ui`].
+    ///
+    /// Calling `read_only(false)` has no effect; like [`Self::disable`], it can't be undone.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// # let (mut name, can_edit) = (String::new(), false);
+    /// ui.scope(|ui| {
+    ///     ui.read_only(!can_edit);
+    ///     ui.text_edit_singleline(&mut name);
+    /// });
+    /// # });
+    /// ```
+    pub fn read_only(&mut self, read_only: bool) {
+        if read_only {
+            self.read_only = true;
+            self.enabled = false;
+        }
+    }
+
+    /// Is this [`Ui`] busy? See [`Self::busy`].
+    #[inline]
+    pub fn is_busy(&self) -> bool {
+        self.busy
+    }
+
+    /// Calling `busy(true)` shows that the contents of this [`Ui`] are waiting for something,
+    /// e.g. loading or saving.
+    ///
+    /// A small spinner is shown on top of the contents once they have been added,
+    /// and all interaction with them is blocked, but they aren't grayed out.
+    ///
+    /// Calling `busy(false)` has no effect; like [`Self::disable`], it can't be undone.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// # let mut query = String::new();
+    /// # let is_loading = true;
+    /// ui.group(|ui| {
+    ///     ui.busy(is_loading);
+    ///     ui.text_edit_singleline(&mut query);
+    ///     ui.label("Results…");
+    /// });
+    /// # });
+    /// ```
+    pub fn busy(&mut self, busy: bool) {
+        if busy && !self.busy {
+            self.busy = true;
+            self.enabled = false;
+            self.paint_busy_indicator = true;
+        }
+    }
+
     /// Calling `set_enabled(false)` will cause the [`Ui`] to deny all future interaction
     /// and all the widgets will draw with a gray look.
     ///
@@ -1045,7 +1153,7 @@ impl Ui {
 impl Ui {
     /// Check for clicks, drags and/or hover on a specific region of this [`Ui`].
     pub fn interact(&self, rect: Rect, id: Id, sense: Sense) -> Response {
-        self.ctx().create_widget(
+        let response = self.ctx().create_widget(
             WidgetRect {
                 id,
                 layer_id: self.layer_id(),
@@ -1055,7 +1163,12 @@ impl Ui {
                 enabled: self.enabled,
             },
             true,
-        )
+        );
+        if id != self.unique_id {
+            self.ctx()
+                .register_in_ui_tree(id, Some(self.unique_id), None);
+        }
+        response
     }
 
     /// Deprecated: use [`Self::interact`] instead.
@@ -1101,6 +1214,9 @@ impl Ui {
     /// [`Ui::min_rect`].
     fn remember_min_rect(&mut self) -> Response {
         self.min_rect_already_remembered = true;
+        if self.paint_busy_indicator {
+            self.paint_busy_indicator();
+        }
         // We remove the id from used_ids to prevent a duplicate id warning from showing
         // when the ui was created with `UiBuilder::sense`.
         // This is a bit hacky, is there a better way?
@@ -1121,6 +1237,27 @@ impl Ui {
         )
     }
 
+    /// A spinner on top of the contents of a busy [`Ui`], see [`Self::busy`].
+    fn paint_busy_indicator(&self) {
+        let rect = self.min_rect();
+        if !self.is_visible() || !self.is_rect_visible(rect) {
+            return;
+        }
+
+        let size = (0.5 * rect.size().min_elem()).min(self.spacing().interact_size.y);
+        let spinner_rect = Rect::from_center_size(rect.center(), Vec2::splat(size));
+        self.painter().circle_filled(
+            spinner_rect.center(),
+            0.75 * size,
+            self.visuals().window_fill,
+        );
+        Spinner::new().paint_at(self, spinner_rect);
+
+        if self.rect_contains_pointer(rect) {
+            self.ctx().set_cursor_icon(CursorIcon::Progress);
+        }
+    }
+
     /// Interact with the background of this [`Ui`],
     /// i.e. behind all the widgets.
     ///
@@ -1274,9 +1411,14 @@ impl Ui {
     /// Returns where to put the widget.
     fn allocate_space_impl(&mut self, desired_size: Vec2) -> Rect {
         let item_spacing = self.spacing().item_spacing;
-        let frame_rect = self.placer.next_space(desired_size, item_spacing);
+        let mut frame_rect = self.placer.next_space(desired_size, item_spacing);
         debug_assert!(!frame_rect.any_nan());
-        let widget_rect = self.placer.justify_and_align(frame_rect, desired_size);
+        let mut widget_rect = self.placer.justify_and_align(frame_rect, desired_size);
+
+        if self.ctx().options(|o| o.pixel_perfect) {
+            frame_rect = self.ctx().round_rect_to_pixels(frame_rect);
+            widget_rect = self.ctx().round_rect_to_pixels(widget_rect);
+        }
 
         self.placer
             .advance_after_rects(frame_rect, widget_rect, item_spacing);
@@ -2779,6 +2921,21 @@ impl Ui {
                 let delta = pointer_pos - response.rect.center();
                 self.ctx()
                     .transform_layer_shapes(layer_id, emath::TSTransform::from_translation(delta));
+
+                // Let other viewports show it, in case it is dragged into one of them:
+                let preview = self.ctx().graphics(|graphics| {
+                    graphics.get(layer_id).map_or_else(Vec::new, |paint_list| {
+                        paint_list
+                            .all_entries()
+                            .map(|clipped| {
+                                let mut shape = clipped.shape.clone();
+                                shape.translate(-pointer_pos.to_vec2());
+                                shape
+                            })
+                            .collect()
+                    })
+                });
+                crate::DragAndDrop::set_preview(self.ctx(), preview);
             }
 
             InnerResponse::new(inner, response)
@@ -19,6 +19,8 @@ pub struct UiBuilder {
     pub max_rect: Option<Rect>,
     pub layout: Option<Layout>,
     pub disabled: bool,
+    pub read_only: bool,
+    pub busy: bool,
     pub invisible: bool,
     pub sizing_pass: bool,
     pub style: Option<Arc<Style>>,
@@ -92,6 +94,24 @@ impl UiBuilder {
         self
     }
 
+    /// Make the new `Ui` read-only, see [`Ui::read_only`].
+    ///
+    /// Note that if the parent `Ui` is read-only, the child will always be read-only.
+    #[inline]
+    pub fn read_only(mut self) -> Self {
+        self.read_only = true;
+        self
+    }
+
+    /// Mark the new `Ui` as busy, see [`Ui::busy`].
+    ///
+    /// Note that if the parent `Ui` is busy, the child will always be busy.
+    #[inline]
+    pub fn busy(mut self) -> Self {
+        self.busy = true;
+        self
+    }
+
     /// Make the contents invisible.
     ///
     /// Will also disable the `Ui` (see [`Self::disabled`]).
@@ -5,6 +5,7 @@ use crate::{Direction, Frame, Id, Rect};
 
 /// What kind is this [`crate::Ui`]?
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum UiKind {
     /// A [`crate::Window`].
     Window,
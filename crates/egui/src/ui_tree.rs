@@ -0,0 +1,241 @@
+//! A description of the widgets of a pass as a tree, see [`crate::Context::ui_tree_snapshot`].
+
+use crate::{
+    interaction::InteractionSnapshot, Id, IdMap, LayerId, Rect, UiKind, WidgetInfo, WidgetRect,
+    WidgetRects,
+};
+
+/// All [`crate::Ui`]s and widgets of a pass, as a tree.
+///
+/// This is plain data, meant for external tools such as inspectors, documentation generators
+/// and tests that compare the UI between versions.
+/// With the `serde` feature it can be serialized, e.g. to JSON.
+///
+/// Capture it with [`crate::Context::ui_tree_snapshot`].
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct UiTreeSnapshot {
+    /// The pass this describes, see [`crate::Context::cumulative_pass_nr`].
+    pub pass_nr: u64,
+
+    /// All layers with widgets, from back to front.
+    pub layers: Vec<UiTreeLayer>,
+}
+
+/// The widgets in one layer of a [`UiTreeSnapshot`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct UiTreeLayer {
+    pub layer_id: LayerId,
+
+    /// The top-level [`crate::Ui`]s and widgets of this layer, in painting order.
+    pub nodes: Vec<UiTreeNode>,
+}
+
+/// A [`crate::Ui`] or a widget in a [`UiTreeSnapshot`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct UiTreeNode {
+    pub id: Id,
+
+    /// Is this a [`crate::Ui`] (as opposed to a widget)?
+    pub is_ui: bool,
+
+    /// What kind of [`crate::Ui`] this is, if it is one of the containers in [`UiKind`].
+    pub ui_kind: Option<UiKind>,
+
+    /// The full rectangle, see [`WidgetRect::rect`].
+    pub rect: Rect,
+
+    /// The clipped, interactive rectangle, see [`WidgetRect::interact_rect`].
+    pub interact_rect: Rect,
+
+    /// What the widget is, its text, value etc.
+    ///
+    /// `None` for widgets that don't describe themselves with [`crate::Response::widget_info`], and most [`crate::Ui`]s.
+    pub info: Option<WidgetInfo>,
+
+    pub enabled: bool,
+
+    /// Senses clicks.
+    pub clickable: bool,
+
+    /// Senses drags.
+    pub draggable: bool,
+
+    /// Can get keyboard focus.
+    pub focusable: bool,
+
+    /// The pointer is over the widget, and nothing is covering it.
+    pub hovered: bool,
+
+    /// The widget has keyboard focus.
+    pub focused: bool,
+
+    /// The widget is being dragged.
+    pub dragged: bool,
+
+    /// The [`crate::Ui`]s and widgets inside of this one, in painting order.
+    pub children: Vec<Self>,
+}
+
+impl UiTreeSnapshot {
+    /// All nodes in all layers, depth-first.
+    pub fn iter(&self) -> impl Iterator<Item = &UiTreeNode> + '_ {
+        self.layers
+            .iter()
+            .flat_map(|layer| layer.nodes.iter())
+            .flat_map(UiTreeNode::iter)
+    }
+
+    /// Find a node by its [`Id`].
+    pub fn get(&self, id: Id) -> Option<&UiTreeNode> {
+        self.iter().find(|node| node.id == id)
+    }
+
+    /// Build the tree from the widgets of a pass.
+    pub(crate) fn new(
+        pass_nr: u64,
+        widgets: &WidgetRects,
+        layer_order: &[LayerId],
+        interaction: &InteractionSnapshot,
+        focused: Option<Id>,
+    ) -> Self {
+        let mut layer_ids: Vec<LayerId> = layer_order
+            .iter()
+            .copied()
+            .filter(|layer_id| widgets.get_layer(*layer_id).next().is_some())
+            .collect();
+        let mut unordered: Vec<LayerId> = widgets
+            .layer_ids()
+            .filter(|layer_id| !layer_order.contains(layer_id))
+            .collect();
+        unordered.sort_by_key(|layer_id| (layer_id.order, layer_id.id.value()));
+        layer_ids.extend(unordered);
+
+        let layers = layer_ids
+            .into_iter()
+            .map(|layer_id| {
+                let rects: Vec<&WidgetRect> = widgets.get_layer(layer_id).collect();
+
+                // Parents are registered before their children, so anything else would be an id clash:
+                let index: IdMap<usize> =
+                    rects.iter().enumerate().map(|(i, w)| (w.id, i)).collect();
+                let mut roots = vec![];
+                let mut children: IdMap<Vec<usize>> = Default::default();
+                for (i, w) in rects.iter().enumerate() {
+                    match widgets.parent(w.id) {
+                        Some(parent) if index.get(&parent).is_some_and(|&p| p < i) => {
+                            children.entry(parent).or_default().push(i);
+                        }
+                        _ => roots.push(i),
+                    }
+                }
+
+                let node = |i: usize| UiTreeNode::new(rects[i], widgets, interaction, focused);
+                let nodes = roots
+                    .into_iter()
+                    .map(|i| node(i).with_children(&children, &node))
+                    .collect();
+                UiTreeLayer { layer_id, nodes }
+            })
+            .collect();
+
+        Self { pass_nr, layers }
+    }
+}
+
+impl UiTreeNode {
+    /// This node and all nodes inside of it, depth-first.
+    pub fn iter(&self) -> impl Iterator<Item = &Self> + '_ {
+        let mut stack = vec![self];
+        std::iter::from_fn(move || {
+            let node = stack.pop()?;
+            stack.extend(node.children.iter().rev());
+            Some(node)
+        })
+    }
+
+    /// The text of the widget, e.g. the label of a button or the contents of a text edit.
+    pub fn text(&self) -> Option<&str> {
+        let info = self.info.as_ref()?;
+        info.label.as_deref().or(info.current_text_value.as_deref())
+    }
+
+    fn new(
+        w: &WidgetRect,
+        widgets: &WidgetRects,
+        interaction: &InteractionSnapshot,
+        focused: Option<Id>,
+    ) -> Self {
+        Self {
+            id: w.id,
+            is_ui: widgets.is_ui(w.id),
+            ui_kind: widgets.ui_kind(w.id),
+            rect: w.rect,
+            interact_rect: w.interact_rect,
+            info: widgets.info(w.id).cloned(),
+            enabled: w.enabled,
+            clickable: w.sense.click,
+            draggable: w.sense.drag,
+            focusable: w.sense.focusable,
+            hovered: interaction.hovered.contains(&w.id),
+            focused: focused == Some(w.id),
+            dragged: interaction.dragged == Some(w.id),
+            children: vec![],
+        }
+    }
+
+    fn with_children(
+        mut self,
+        children: &IdMap<Vec<usize>>,
+        node: &impl Fn(usize) -> Self,
+    ) -> Self {
+        if let Some(indices) = children.get(&self.id) {
+            self.children = indices
+                .iter()
+                .map(|&i| node(i).with_children(children, node))
+                .collect();
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CentralPanel, Context, UiKind, WidgetType};
+
+    #[test]
+    fn widgets_are_in_their_uis() {
+        let ctx = Context::default();
+        ctx.options_mut(|o| o.record_ui_tree = true);
+        let _ = ctx.run(Default::default(), |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                ui.group(|ui| ui.checkbox(&mut true, "Check me"));
+            });
+        });
+
+        let snapshot = ctx.ui_tree_snapshot();
+        let panel = snapshot
+            .iter()
+            .find(|node| node.ui_kind == Some(UiKind::CentralPanel))
+            .unwrap();
+        let checkbox = panel
+            .iter()
+            .find(|node| node.text() == Some("Check me"))
+            .unwrap();
+        assert!(!checkbox.is_ui);
+        assert!(checkbox.clickable && checkbox.enabled);
+        let info = checkbox.info.as_ref().unwrap();
+        assert_eq!(info.typ, WidgetType::Checkbox);
+        assert_eq!(info.selected, Some(true));
+
+        assert!(
+            snapshot
+                .layers
+                .iter()
+                .all(|layer| layer.nodes.iter().all(|node| node.id != checkbox.id)),
+            "The checkbox should not be at the top level"
+        );
+    }
+}
@@ -167,6 +167,16 @@ impl Element {
         }
     }
 
+    /// Will this value be persisted?
+    #[cfg(feature = "persistence")]
+    #[inline]
+    fn is_persisted(&self) -> bool {
+        match self {
+            Self::Value { serialize_fn, .. } => serialize_fn.is_some(),
+            Self::Serialized(_) => true,
+        }
+    }
+
     /// The type of the stored value.
     #[inline]
     pub(crate) fn type_id(&self) -> TypeId {
@@ -524,6 +534,33 @@ impl IdTypeMap {
             .count()
     }
 
+    /// A copy of only the values that will be persisted.
+    #[cfg(feature = "persistence")]
+    pub(crate) fn clone_persisted(&self) -> Self {
+        Self {
+            map: self
+                .map
+                .iter()
+                .filter(|(_, element)| element.is_persisted())
+                .map(|(hash, element)| (*hash, element.clone()))
+                .collect(),
+            max_bytes_per_type: self.max_bytes_per_type,
+        }
+    }
+
+    /// Replace all persisted values with the ones in `persisted`, keeping the temporary values.
+    #[cfg(feature = "persistence")]
+    pub(crate) fn replace_persisted(&mut self, persisted: &Self) {
+        self.map.retain(|_, element| !element.is_persisted());
+        self.map.extend(
+            persisted
+                .map
+                .iter()
+                .filter(|(_, element)| element.is_persisted())
+                .map(|(hash, element)| (*hash, element.clone())),
+        );
+    }
+
     /// Count the number of values are stored with the given type.
     pub fn count<T: 'static>(&self) -> usize {
         let key = TypeId::of::<T>();
@@ -984,3 +1021,23 @@ fn test_serialize_gc() {
         Some(B(2_000_000))
     );
 }
+
+#[cfg(feature = "persistence")]
+#[test]
+fn test_replace_persisted() {
+    let a = Id::new("a");
+    let b = Id::new("b");
+
+    let mut map: IdTypeMap = Default::default();
+    map.insert_persisted(a, 1.0);
+    map.insert_temp(b, 2);
+    let snapshot = map.clone_persisted();
+    assert_eq!(snapshot.len(), 1);
+
+    map.insert_persisted(a, 3.0);
+    map.insert_persisted(b, 4.0);
+    map.replace_persisted(&snapshot);
+    assert_eq!(map.get_persisted::<f64>(a), Some(1.0));
+    assert_eq!(map.get_persisted::<f64>(b), None);
+    assert_eq!(map.get_temp::<i32>(b), Some(2));
+}
@@ -32,6 +32,14 @@
 //!
 //! In short: immediate viewports are simpler to use, but can waste a lot of CPU time.
 //!
+//! ### Texture viewports
+//! These are created with [`Context::show_viewport_texture`].
+//! Like deferred viewports they have a closure that can be called at a later time,
+//! but instead of getting an OS window, they are run by your own code with [`Context::run_texture_viewport`],
+//! and you paint the output into a texture of your choosing.
+//! This lets you put an egui UI onto a surface in a 3D scene, e.g. a computer screen in a game.
+//! Use [`TextureViewportInput`] to translate ray hits on that surface into egui input.
+//!
 //! ### Embedded viewports
 //! These are not real, independent viewports, but is a fallback mode for when the integration does not support real viewports. In your callback is called with [`ViewportClass::Embedded`] it means you need to create a [`crate::Window`] to wrap your ui in, which will then be embedded in the parent viewport, unable to escape it.
 //!
@@ -69,9 +77,9 @@
 
 use std::sync::Arc;
 
-use epaint::{Pos2, Vec2};
+use epaint::{Pos2, Rect, Vec2};
 
-use crate::{Context, Id};
+use crate::{Context, Event, Id, Modifiers, PointerButton, RawInput, ViewportInfo};
 
 // ----------------------------------------------------------------------------
 
@@ -102,6 +110,22 @@ pub enum ViewportClass {
     /// The fallback, when the egui integration doesn't support viewports,
     /// or [`crate::Context::embed_viewports`] is set to `true`.
     Embedded,
+
+    /// A viewport that is rendered into a texture by the user, instead of into an OS window.
+    ///
+    /// Integrations should not create windows for these.
+    ///
+    /// Create these with [`crate::Context::show_viewport_texture`],
+    /// and run them with [`crate::Context::run_texture_viewport`].
+    Texture,
+
+    /// A deferred viewport that blocks input to its parent viewport for as long as it is shown,
+    /// e.g. a dialog.
+    ///
+    /// egui dims the content of the parent while it is blocked.
+    ///
+    /// Create these with [`crate::Context::show_viewport_deferred`] and [`ViewportBuilder::with_modal`].
+    Modal,
 }
 
 // ----------------------------------------------------------------------------
@@ -307,6 +331,19 @@ pub struct ViewportBuilder {
 
     pub mouse_passthrough: Option<bool>,
 
+    /// See [`Self::with_modal`].
+    pub modal: Option<bool>,
+
+    /// See [`Self::with_modal_parent`].
+    pub modal_parent: Option<ViewportId>,
+
+    /// See [`Self::with_surface`].
+    pub surface: Option<SurfaceConfig>,
+
+    /// What to do if the viewport ends up off-screen because a monitor was disconnected.
+    /// See [`Self::with_monitor_relocation`].
+    pub monitor_relocation: Option<MonitorRelocation>,
+
     // X11
     pub window_type: Option<X11WindowType>,
 }
@@ -325,6 +362,9 @@ impl ViewportBuilder {
     ///
     /// The default is `true`.
     ///
+    /// Without decorations, most platforms don't let the user resize the window by its edges;
+    /// use [`crate::containers::ResizeBorder`] for that.
+    ///
     /// Look at winit for more details
     #[inline]
     pub fn with_decorations(mut self, decorations: bool) -> Self {
@@ -600,6 +640,67 @@ impl ViewportBuilder {
         self
     }
 
+    /// Block input to the parent viewport for as long as this viewport is shown, like a dialog.
+    ///
+    /// Only works for deferred viewports (see [`crate::Context::show_viewport_deferred`]),
+    /// which then get the class [`ViewportClass::Modal`].
+    /// The window is kept above its parent where the platform supports it (Windows and macOS),
+    /// and egui dims the content of the parent with [`crate::Visuals::modal_backdrop`].
+    #[inline]
+    pub fn with_modal(mut self, value: bool) -> Self {
+        self.modal = Some(value);
+        self
+    }
+
+    /// Make this a modal viewport of the given viewport, like a sheet on macOS.
+    ///
+    /// Like [`Self::with_modal`], but it is the given viewport whose input is blocked,
+    /// instead of the one showing this viewport.
+    /// Unless a position is set with [`Self::with_position`],
+    /// the window is centered over the window of its modal parent when it is opened.
+    ///
+    /// The modal parent must be shown for as long as this viewport is.
+    #[inline]
+    pub fn with_modal_parent(mut self, parent: ViewportId) -> Self {
+        self.modal = Some(true);
+        self.modal_parent = Some(parent);
+        self
+    }
+
+    /// The viewport whose input this viewport blocks if it is modal,
+    /// given the viewport that shows it.
+    ///
+    /// See [`Self::with_modal_parent`].
+    #[inline]
+    pub fn modal_parent_or(&self, parent: ViewportId) -> ViewportId {
+        self.modal_parent.unwrap_or(parent)
+    }
+
+    /// Request a surface (framebuffer) with multisampling, sRGB encoding or floating point color
+    /// that differs from the one of the root viewport.
+    ///
+    /// By default a viewport gets the same kind of surface as the root viewport.
+    /// What was actually obtained is reported in [`crate::ViewportInfo::surface`].
+    ///
+    /// Only supported by the `glow` backend of `eframe`, and only for viewports other than the root
+    /// (whose surface is configured with `eframe::NativeOptions`).
+    #[inline]
+    pub fn with_surface(mut self, surface: SurfaceConfig) -> Self {
+        self.surface = Some(surface);
+        self
+    }
+
+    /// What to do if the viewport ends up off-screen, e.g. because the monitor it was on was disconnected.
+    ///
+    /// The default is [`MonitorRelocation::NearestAndRestore`].
+    ///
+    /// Only implemented on native, and not on Wayland (where windows can't be positioned).
+    #[inline]
+    pub fn with_monitor_relocation(mut self, value: MonitorRelocation) -> Self {
+        self.monitor_relocation = Some(value);
+        self
+    }
+
     /// ### On X11
     /// This sets the window type.
     /// Maps directly to [`_NET_WM_WINDOW_TYPE`](https://specifications.freedesktop.org/wm-spec/wm-spec-1.5.html).
@@ -639,6 +740,10 @@ impl ViewportBuilder {
             maximize_button: new_maximize_button,
             window_level: new_window_level,
             mouse_passthrough: new_mouse_passthrough,
+            modal: new_modal,
+            modal_parent: new_modal_parent,
+            surface: new_surface,
+            monitor_relocation: new_monitor_relocation,
             taskbar: new_taskbar,
             window_type: new_window_type,
         } = new_vp_builder;
@@ -826,10 +931,69 @@ impl ViewportBuilder {
             recreate_window = true;
         }
 
+        if new_modal.is_some() && self.modal != new_modal {
+            // The parent of the window is set when it is created.
+            self.modal = new_modal;
+            recreate_window = true;
+        }
+
+        if new_modal_parent.is_some() && self.modal_parent != new_modal_parent {
+            self.modal_parent = new_modal_parent;
+            recreate_window = true;
+        }
+
+        if new_surface.is_some() && self.surface != new_surface {
+            // The surface is created together with the window.
+            self.surface = new_surface;
+            recreate_window = true;
+        }
+
+        if new_monitor_relocation.is_some() {
+            // Only read by the integration when monitors change, so no command needed.
+            self.monitor_relocation = new_monitor_relocation;
+        }
+
         (commands, recreate_window)
     }
 }
 
+/// The kind of surface (framebuffer) a viewport is painted to.
+///
+/// See [`ViewportBuilder::with_surface`] and [`crate::ViewportInfo::surface`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct SurfaceConfig {
+    /// The number of samples per pixel for multisample anti-aliasing (MSAA), or 0 for none.
+    pub multisampling: u16,
+
+    /// Can the surface convert linear colors to sRGB when written to?
+    pub srgb: bool,
+
+    /// Does the surface have floating point (16 bit) color channels?
+    ///
+    /// Useful for HDR content. Not supported by all drivers.
+    pub float: bool,
+}
+
+/// What to do with a viewport that ends up off-screen,
+/// e.g. because the monitor it was on was disconnected.
+///
+/// See [`ViewportBuilder::with_monitor_relocation`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum MonitorRelocation {
+    /// Leave the viewport where it is.
+    Keep,
+
+    /// Move the viewport onto the nearest remaining monitor.
+    Nearest,
+
+    /// Move the viewport onto the nearest remaining monitor,
+    /// and move it back to where it was if its monitor is reconnected.
+    #[default]
+    NearestAndRestore,
+}
+
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum WindowLevel {
@@ -934,6 +1098,23 @@ pub enum ResizeDirection {
     SouthWest,
 }
 
+impl ResizeDirection {
+    /// The cursor to show when hovering an edge or corner that resizes in this direction.
+    pub fn cursor_icon(self) -> crate::CursorIcon {
+        use crate::CursorIcon;
+        match self {
+            Self::North => CursorIcon::ResizeNorth,
+            Self::South => CursorIcon::ResizeSouth,
+            Self::East => CursorIcon::ResizeEast,
+            Self::West => CursorIcon::ResizeWest,
+            Self::NorthEast => CursorIcon::ResizeNorthEast,
+            Self::SouthEast => CursorIcon::ResizeSouthEast,
+            Self::NorthWest => CursorIcon::ResizeNorthWest,
+            Self::SouthWest => CursorIcon::ResizeSouthWest,
+        }
+    }
+}
+
 /// An output [viewport](crate::viewport)-command from egui to the backend, e.g. to change the window title or size.
 ///
 ///  You can send a [`ViewportCommand`] to the viewport with [`Context::send_viewport_cmd`].
@@ -989,6 +1170,11 @@ pub enum ViewportCommand {
     ///
     /// There's no guarantee that this will work unless the left mouse button was pressed
     /// immediately before this function is called.
+    ///
+    /// This hands the resize over to the windowing system (e.g. an `xdg_toplevel` resize on Wayland),
+    /// so it feels like resizing a decorated window.
+    /// Where that isn't supported the outcome is [`ViewportCommandOutcome::Unsupported`],
+    /// which [`crate::containers::ResizeBorder`] falls back from by resizing the window itself.
     BeginResize(ResizeDirection),
 
     /// Can the window be resized?
@@ -1100,6 +1286,45 @@ impl ViewportCommand {
     }
 }
 
+/// What became of a [`ViewportCommand`], as reported by the integration.
+///
+/// The commands sent during a pass are applied as one batch after the pass, in the order they were sent.
+/// The results are given in the same order in [`crate::ViewportInfo::command_results`] in the next pass.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ViewportCommandResult {
+    /// The command, as it was sent.
+    pub command: ViewportCommand,
+
+    pub outcome: ViewportCommandOutcome,
+}
+
+/// See [`ViewportCommandResult`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum ViewportCommandOutcome {
+    /// The command was carried out, or handed to the windowing system, which will carry it out.
+    ///
+    /// Some things (like the size of the window) only change once the windowing system gets to it,
+    /// so look at [`crate::ViewportInfo`] in later passes to see the actual result.
+    Applied,
+
+    /// The command is not supported by the integration or on this platform,
+    /// e.g. positioning windows on Wayland.
+    Unsupported,
+
+    /// The windowing system refused the command, or carried it out differently than requested,
+    /// e.g. the compositor kept the window at another size.
+    Failed(String),
+}
+
+impl ViewportCommandOutcome {
+    /// Was the command carried out?
+    pub fn is_applied(&self) -> bool {
+        matches!(self, Self::Applied)
+    }
+}
+
 /// Describes a viewport, i.e. a native window.
 ///
 /// This is returned by [`crate::Context::run`] on each frame, and should be applied
@@ -1113,6 +1338,9 @@ pub struct ViewportOutput {
     ///
     /// This will never be [`ViewportClass::Embedded`],
     /// since those don't result in real viewports.
+    ///
+    /// Viewports of class [`ViewportClass::Texture`] are run by the user,
+    /// and should not get a native window.
     pub class: ViewportClass,
 
     /// The window attrbiutes such as title, position, size, etc.
@@ -1122,7 +1350,7 @@ pub struct ViewportOutput {
     /// and apply them as needed.
     pub builder: ViewportBuilder,
 
-    /// The user-code that shows the GUI, used for deferred viewports.
+    /// The user-code that shows the GUI, used for deferred and texture viewports.
     ///
     /// `None` for immediate viewports and the ROOT viewport.
     pub viewport_ui_cb: Option<Arc<DeferredViewportUiCallback>>,
@@ -1170,3 +1398,218 @@ pub struct ImmediateViewport<'a> {
     /// The user-code that shows the GUI.
     pub viewport_ui_cb: Box<dyn FnMut(&Context) + 'a>,
 }
+
+// ----------------------------------------------------------------------------
+
+/// Collects input for a [texture viewport](Context::show_viewport_texture).
+///
+/// A texture viewport is painted onto some surface in a 3D scene,
+/// so the pointer position has to be re-projected from that surface.
+/// Cast a ray from the camera through the mouse cursor (or from a VR controller),
+/// and if it hits the surface, pass the UV coordinates of the hit to [`Self::pointer_hit`].
+///
+/// UV coordinates go from `(0, 0)` in the top left corner of the texture to `(1, 1)` in the bottom right.
+/// If your texture coordinates have `v` going upwards, flip it first.
+///
+/// ```
+/// # let ctx = egui::Context::default();
+/// let viewport_id = egui::ViewportId::from_hash_of("in_game_screen");
+/// let mut input = egui::TextureViewportInput::new(viewport_id, [512, 256]);
+///
+/// // In the parent viewport, each pass:
+/// # let _ = ctx.run(Default::default(), |ctx| {
+/// ctx.show_viewport_texture(viewport_id, |ctx, _class| {
+///     egui::CentralPanel::default().show(ctx, |ui| {
+///         ui.label("Hello from inside the game!");
+///     });
+/// });
+/// # });
+///
+/// // Whenever your ray hits the in-game screen:
+/// input.pointer_hit(Some(egui::vec2(0.25, 0.5)));
+/// input.pointer_button(egui::PointerButton::Primary, true);
+///
+/// // Outside of the parent viewport's pass, e.g. before rendering your scene:
+/// if let Some(output) = ctx.run_texture_viewport(&mut input) {
+/// # assert!(!output.shapes.is_empty());
+///     let clipped_primitives = ctx.tessellate(output.shapes, output.pixels_per_point);
+///     // Paint `clipped_primitives` into your texture,
+///     // e.g. with `egui_glow::Painter::paint_to_texture`.
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct TextureViewportInput {
+    viewport_id: ViewportId,
+    size_px: [u32; 2],
+    pixels_per_point: f32,
+    focused: bool,
+    modifiers: Modifiers,
+
+    /// Last known pointer position, in points.
+    pointer_pos: Option<Pos2>,
+
+    events: Vec<Event>,
+}
+
+impl TextureViewportInput {
+    /// The size of the target texture, in pixels.
+    pub fn new(viewport_id: ViewportId, size_px: [u32; 2]) -> Self {
+        Self {
+            viewport_id,
+            size_px,
+            pixels_per_point: 1.0,
+            focused: true,
+            modifiers: Modifiers::default(),
+            pointer_pos: None,
+            events: Vec::new(),
+        }
+    }
+
+    /// How many texture pixels per egui point. Default is `1.0`.
+    ///
+    /// This already includes the zoom factor of the [`Context`].
+    #[inline]
+    pub fn with_pixels_per_point(mut self, pixels_per_point: f32) -> Self {
+        self.pixels_per_point = pixels_per_point;
+        self
+    }
+
+    #[inline]
+    pub fn viewport_id(&self) -> ViewportId {
+        self.viewport_id
+    }
+
+    /// The size of the target texture, in pixels.
+    #[inline]
+    pub fn size_px(&self) -> [u32; 2] {
+        self.size_px
+    }
+
+    /// Change the size of the target texture, e.g. after reallocating it.
+    #[inline]
+    pub fn set_size_px(&mut self, size_px: [u32; 2]) {
+        self.size_px = size_px;
+    }
+
+    #[inline]
+    pub fn pixels_per_point(&self) -> f32 {
+        self.pixels_per_point
+    }
+
+    /// The area covered by the texture, in egui points.
+    pub fn screen_rect(&self) -> Rect {
+        let [w, h] = self.size_px;
+        Rect::from_min_size(
+            Pos2::ZERO,
+            Vec2::new(w as f32, h as f32) / self.pixels_per_point,
+        )
+    }
+
+    /// Convert texture coordinates to a position in egui points.
+    pub fn uv_to_pos(&self, uv: Vec2) -> Pos2 {
+        let rect = self.screen_rect();
+        rect.min + uv * rect.size()
+    }
+
+    /// Convert a position in egui points to texture coordinates.
+    pub fn pos_to_uv(&self, pos: Pos2) -> Vec2 {
+        let rect = self.screen_rect();
+        (pos - rect.min) / rect.size()
+    }
+
+    /// Does the texture viewport have keyboard focus?
+    ///
+    /// Default is `true`. Set this to `false` when the player is not interacting with the surface,
+    /// so that text fields don't show a blinking cursor.
+    #[inline]
+    pub fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    /// The currently held modifier keys.
+    #[inline]
+    pub fn set_modifiers(&mut self, modifiers: Modifiers) {
+        self.modifiers = modifiers;
+    }
+
+    /// Report where the pointer ray hit the surface, in texture coordinates.
+    ///
+    /// Pass `None` when the ray misses the surface.
+    /// Hits outside of the `[0, 1]` range are treated as misses.
+    pub fn pointer_hit(&mut self, uv: Option<Vec2>) {
+        let uv = uv.filter(|uv| (0.0..=1.0).contains(&uv.x) && (0.0..=1.0).contains(&uv.y));
+        match uv {
+            Some(uv) => {
+                let pos = self.uv_to_pos(uv);
+                if self.pointer_pos != Some(pos) {
+                    self.pointer_pos = Some(pos);
+                    self.events.push(Event::PointerMoved(pos));
+                }
+            }
+            None => {
+                if self.pointer_pos.take().is_some() {
+                    self.events.push(Event::PointerGone);
+                }
+            }
+        }
+    }
+
+    /// Press or release a pointer button at the last position given to [`Self::pointer_hit`].
+    ///
+    /// Ignored if the pointer is not over the surface.
+    pub fn pointer_button(&mut self, button: PointerButton, pressed: bool) {
+        if let Some(pos) = self.pointer_pos {
+            self.events.push(Event::PointerButton {
+                pos,
+                button,
+                pressed,
+                modifiers: self.modifiers,
+            });
+        }
+    }
+
+    /// Scroll by this many points, e.g. from a mouse wheel or thumbstick.
+    ///
+    /// Ignored if the pointer is not over the surface.
+    pub fn scroll(&mut self, delta: Vec2) {
+        if self.pointer_pos.is_some() {
+            self.events.push(Event::MouseWheel {
+                unit: crate::MouseWheelUnit::Point,
+                delta,
+                modifiers: self.modifiers,
+                phase: Default::default(),
+            });
+        }
+    }
+
+    /// Add any other event, e.g. [`Event::Text`] or [`Event::Key`].
+    ///
+    /// Positions in the event should be in egui points; see [`Self::uv_to_pos`].
+    pub fn push_event(&mut self, event: Event) {
+        self.events.push(event);
+    }
+
+    /// Produce the [`RawInput`] for the next pass, draining all queued events.
+    ///
+    /// This is called for you by [`Context::run_texture_viewport`].
+    pub fn take_raw_input(&mut self, zoom_factor: f32) -> RawInput {
+        let screen_rect = self.screen_rect();
+        let viewport = ViewportInfo {
+            native_pixels_per_point: Some(self.pixels_per_point / zoom_factor),
+            inner_rect: Some(screen_rect),
+            focused: Some(self.focused),
+            ..Default::default()
+        };
+
+        let mut raw_input = RawInput {
+            viewport_id: self.viewport_id,
+            screen_rect: Some(screen_rect),
+            modifiers: self.modifiers,
+            events: std::mem::take(&mut self.events),
+            focused: self.focused,
+            ..Default::default()
+        };
+        raw_input.viewports.insert(self.viewport_id, viewport);
+        raw_input
+    }
+}
@@ -1,6 +1,6 @@
 use ahash::HashMap;
 
-use crate::{Id, IdMap, LayerId, Rect, Sense, WidgetInfo};
+use crate::{Id, IdMap, LayerId, Rect, Sense, UiKind, WidgetInfo};
 
 /// Used to store each widget's [Id], [Rect] and [Sense] each frame.
 ///
@@ -57,8 +57,19 @@ pub struct WidgetRects {
     /// Info about some widgets.
     ///
     /// Only filled in if the widget is interacted with,
-    /// or if this is a debug build.
+    /// or if this is a debug build,
+    /// or with [`crate::Options::record_ui_tree`].
     infos: IdMap<WidgetInfo>,
+
+    /// The [`crate::Ui`] each widget is in.
+    ///
+    /// Only filled in with [`crate::Options::record_ui_tree`].
+    parents: IdMap<Id>,
+
+    /// The widgets that are [`crate::Ui`]s, with their kind.
+    ///
+    /// Only filled in with [`crate::Options::record_ui_tree`].
+    uis: IdMap<Option<UiKind>>,
 }
 
 impl PartialEq for WidgetRects {
@@ -106,6 +117,8 @@ impl WidgetRects {
             by_layer,
             by_id,
             infos,
+            parents,
+            uis,
         } = self;
 
         for rects in by_layer.values_mut() {
@@ -115,6 +128,8 @@ impl WidgetRects {
         by_id.clear();
 
         infos.clear();
+        parents.clear();
+        uis.clear();
     }
 
     /// Insert the given widget rect in the given layer.
@@ -123,6 +138,8 @@ impl WidgetRects {
             by_layer,
             by_id,
             infos: _,
+            parents: _,
+            uis: _,
         } = self;
 
         let layer_widgets = by_layer.entry(layer_id).or_default();
@@ -167,4 +184,33 @@ impl WidgetRects {
     pub fn info(&self, id: Id) -> Option<&WidgetInfo> {
         self.infos.get(&id)
     }
+
+    /// Remember that the widget is inside the [`crate::Ui`] with id `parent`.
+    ///
+    /// See [`crate::Options::record_ui_tree`].
+    pub fn set_parent(&mut self, id: Id, parent: Id) {
+        self.parents.insert(id, parent);
+    }
+
+    /// The [`crate::Ui`] the widget is in, if recorded with [`Self::set_parent`].
+    pub fn parent(&self, id: Id) -> Option<Id> {
+        self.parents.get(&id).copied()
+    }
+
+    /// Remember that the widget is a [`crate::Ui`], of the given kind.
+    ///
+    /// See [`crate::Options::record_ui_tree`].
+    pub fn set_ui_kind(&mut self, id: Id, kind: Option<UiKind>) {
+        self.uis.insert(id, kind);
+    }
+
+    /// Was the widget recorded as a [`crate::Ui`] with [`Self::set_ui_kind`]?
+    pub fn is_ui(&self, id: Id) -> bool {
+        self.uis.contains_key(&id)
+    }
+
+    /// The kind of [`crate::Ui`], if recorded with [`Self::set_ui_kind`].
+    pub fn ui_kind(&self, id: Id) -> Option<UiKind> {
+        self.uis.get(&id).copied().flatten()
+    }
 }
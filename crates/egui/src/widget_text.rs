@@ -2,7 +2,8 @@ use std::{borrow::Cow, sync::Arc};
 
 use crate::{
     text::{LayoutJob, TextWrapping},
-    Align, Color32, FontFamily, FontSelection, Galley, Style, TextStyle, TextWrapMode, Ui, Visuals,
+    Align, Color32, FontFamily, FontSelection, Galley, LiveRegion, Style, TextStyle, TextWrapMode,
+    Ui, Visuals,
 };
 
 /// Text and optional style choices for it.
@@ -37,6 +38,7 @@ pub struct RichText {
     underline: bool,
     italics: bool,
     raised: bool,
+    live_region: LiveRegion,
 }
 
 impl From<&str> for RichText {
@@ -277,6 +279,33 @@ impl RichText {
         self
     }
 
+    /// Mark this text as a polite live region.
+    ///
+    /// Screen readers will announce changes to the text when the user is idle,
+    /// even if the widget showing it doesn't have focus.
+    /// Useful for status messages.
+    ///
+    /// Supported by [`crate::Label`] and [`crate::ProgressBar`].
+    #[inline]
+    pub fn live_region(self) -> Self {
+        self.live(LiveRegion::Polite)
+    }
+
+    /// Set how screen readers should announce changes to this text.
+    ///
+    /// See also [`Self::live_region`].
+    #[inline]
+    pub fn live(mut self, live_region: LiveRegion) -> Self {
+        self.live_region = live_region;
+        self
+    }
+
+    /// How screen readers should announce changes to this text.
+    #[inline]
+    pub fn live_region_kind(&self) -> LiveRegion {
+        self.live_region
+    }
+
     /// Read the font height of the selected text style.
     pub fn font_height(&self, fonts: &epaint::Fonts, style: &Style) -> f32 {
         let mut font_id = self.text_style.as_ref().map_or_else(
@@ -368,6 +397,7 @@ impl RichText {
             underline,
             italics,
             raised,
+            live_region: _, // not part of the layout
         } = self;
 
         let line_color = text_color.unwrap_or_else(|| style.visuals.text_color());
@@ -635,6 +665,16 @@ impl WidgetText {
         }
     }
 
+    /// How screen readers should announce changes to this text.
+    ///
+    /// Only [`RichText`] can be marked as a live region, see [`RichText::live_region`].
+    pub fn live_region_kind(&self) -> LiveRegion {
+        match self {
+            Self::RichText(text) => text.live_region_kind(),
+            Self::LayoutJob(_) | Self::Galley(_) => LiveRegion::Off,
+        }
+    }
+
     pub(crate) fn font_height(&self, fonts: &epaint::Fonts, style: &Style) -> f32 {
         match self {
             Self::RichText(text) => text.font_height(fonts, style),
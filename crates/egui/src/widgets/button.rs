@@ -274,6 +274,11 @@ impl Widget for Button<'_> {
             }
         });
 
+        if let Some(galley) = galley.as_ref().filter(|galley| galley.elided) {
+            // Show the full (non-elided) text on hover:
+            response = response.on_hover_text(galley.text());
+        }
+
         if ui.is_rect_visible(rect) {
             let visuals = ui.style().interact(&response);
 
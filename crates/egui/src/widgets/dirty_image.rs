@@ -0,0 +1,309 @@
+use crate::{
+    load::SizedTexture, Color32, ColorImage, Context, Image, Response, TextureHandle,
+    TextureOptions, Ui, Widget,
+};
+
+/// Don't track more than this many separate dirty regions.
+///
+/// If there are more, they are merged into their bounding box.
+const MAX_DIRTY_REGIONS: usize = 8;
+
+/// A region of an image, in pixels. `max` is exclusive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct PixelRegion {
+    min: [usize; 2],
+    max: [usize; 2],
+}
+
+impl PixelRegion {
+    fn from_pos_size([x, y]: [usize; 2], [w, h]: [usize; 2]) -> Self {
+        Self {
+            min: [x, y],
+            max: [x + w, y + h],
+        }
+    }
+
+    fn size(&self) -> [usize; 2] {
+        [self.max[0] - self.min[0], self.max[1] - self.min[1]]
+    }
+
+    fn area(&self) -> usize {
+        let [w, h] = self.size();
+        w * h
+    }
+
+    fn is_empty(&self) -> bool {
+        self.area() == 0
+    }
+
+    fn union(&self, other: &Self) -> Self {
+        Self {
+            min: [self.min[0].min(other.min[0]), self.min[1].min(other.min[1])],
+            max: [self.max[0].max(other.max[0]), self.max[1].max(other.max[1])],
+        }
+    }
+
+    /// Do the regions overlap or touch?
+    fn touches(&self, other: &Self) -> bool {
+        self.min[0] <= other.max[0]
+            && other.min[0] <= self.max[0]
+            && self.min[1] <= other.max[1]
+            && other.min[1] <= self.max[1]
+    }
+}
+
+/// An image kept in CPU memory, where only the changed parts are re-uploaded to the GPU.
+///
+/// This is useful for oscilloscope- or heatmap-style widgets,
+/// where small parts of an image change every frame.
+/// Changing pixels marks their region as dirty,
+/// and the next time the image is shown only the dirty regions are uploaded
+/// using [`TextureHandle::set_partial`].
+///
+/// Store the [`DirtyImage`] in your app state.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// # let mut heatmap = egui::DirtyImage::new("heatmap", egui::ColorImage::new([64, 64], egui::Color32::BLACK));
+/// heatmap.set_pixel([3, 7], egui::Color32::RED);
+/// ui.add(&mut heatmap);
+/// # });
+/// ```
+pub struct DirtyImage {
+    name: String,
+    image: ColorImage,
+    options: TextureOptions,
+
+    /// Lazily allocated when we have an egui context.
+    texture: Option<TextureHandle>,
+
+    /// Regions changed since the last upload.
+    dirty: Vec<PixelRegion>,
+}
+
+impl DirtyImage {
+    /// The name is used for debugging.
+    pub fn new(name: impl Into<String>, image: ColorImage) -> Self {
+        Self {
+            name: name.into(),
+            image,
+            options: TextureOptions::default(),
+            texture: None,
+            dirty: Vec::new(),
+        }
+    }
+
+    /// How the texture is sampled when painted.
+    ///
+    /// For pixel-exact images you probably want [`TextureOptions::NEAREST`].
+    #[inline]
+    pub fn texture_options(mut self, options: TextureOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// width x height, in pixels.
+    #[inline]
+    pub fn size(&self) -> [usize; 2] {
+        self.image.size
+    }
+
+    /// The CPU-side image.
+    #[inline]
+    pub fn image(&self) -> &ColorImage {
+        &self.image
+    }
+
+    /// Read a single pixel.
+    #[inline]
+    pub fn pixel(&self, [x, y]: [usize; 2]) -> Color32 {
+        self.image.pixels[y * self.image.width() + x]
+    }
+
+    /// Change a single pixel.
+    pub fn set_pixel(&mut self, [x, y]: [usize; 2], color: Color32) {
+        let index = y * self.image.width() + x;
+        if self.image.pixels[index] != color {
+            self.image.pixels[index] = color;
+            self.mark_dirty([x, y], [1, 1]);
+        }
+    }
+
+    /// Copy `region` into the image, with its top left corner at `pos`.
+    pub fn write_region(&mut self, pos: [usize; 2], region: &ColorImage) {
+        let [x, y] = pos;
+        let [w, h] = region.size;
+        assert!(
+            x + w <= self.image.width() && y + h <= self.image.height(),
+            "Region is outside the bounds of the image"
+        );
+
+        let stride = self.image.width();
+        for row in 0..h {
+            let dst = (y + row) * stride + x;
+            self.image.pixels[dst..dst + w].copy_from_slice(&region.pixels[row * w..(row + 1) * w]);
+        }
+        self.mark_dirty(pos, region.size);
+    }
+
+    /// Fill a region with a single color.
+    pub fn fill_region(&mut self, pos: [usize; 2], size: [usize; 2], color: Color32) {
+        self.edit_region(pos, size, |_, pixel| *pixel = color);
+    }
+
+    /// Modify each pixel in a region, marking the region as dirty.
+    ///
+    /// The closure gets the position of each pixel, relative to the whole image.
+    pub fn edit_region(
+        &mut self,
+        pos: [usize; 2],
+        size: [usize; 2],
+        mut edit: impl FnMut([usize; 2], &mut Color32),
+    ) {
+        let [x, y] = pos;
+        let [w, h] = size;
+        assert!(
+            x + w <= self.image.width() && y + h <= self.image.height(),
+            "Region is outside the bounds of the image"
+        );
+
+        let stride = self.image.width();
+        for py in y..y + h {
+            for px in x..x + w {
+                edit([px, py], &mut self.image.pixels[py * stride + px]);
+            }
+        }
+        self.mark_dirty(pos, size);
+    }
+
+    /// Replace the whole image.
+    ///
+    /// If the size is unchanged, the existing texture is reused.
+    pub fn set_image(&mut self, image: ColorImage) {
+        if image.size != self.image.size {
+            // Force a reallocation on next upload:
+            self.texture = None;
+        }
+        self.image = image;
+        self.mark_dirty([0, 0], self.image.size);
+    }
+
+    /// Mark a region as changed, so that it gets uploaded the next time the image is shown.
+    ///
+    /// Only needed if you changed the pixels some other way than via the methods on [`DirtyImage`].
+    pub fn mark_dirty(&mut self, pos: [usize; 2], size: [usize; 2]) {
+        let mut region = PixelRegion::from_pos_size(pos, size);
+        if region.is_empty() {
+            return;
+        }
+
+        // Merge with any regions we touch, repeating as the region grows:
+        while let Some(index) = self.dirty.iter().position(|other| other.touches(&region)) {
+            region = region.union(&self.dirty.swap_remove(index));
+        }
+        self.dirty.push(region);
+
+        if MAX_DIRTY_REGIONS < self.dirty.len() {
+            let bounding = self.dirty.iter().fold(region, |a, b| a.union(b));
+            self.dirty.clear();
+            self.dirty.push(bounding);
+        }
+    }
+
+    /// Is there anything that has not yet been uploaded?
+    pub fn is_dirty(&self) -> bool {
+        !self.dirty.is_empty() || self.texture.is_none()
+    }
+
+    /// Upload any changes, and return the texture.
+    ///
+    /// This is called for you when you add the image with [`Ui::add`].
+    pub fn texture(&mut self, ctx: &Context) -> &TextureHandle {
+        let dirty = std::mem::take(&mut self.dirty);
+
+        if let Some(texture) = &mut self.texture {
+            let total_area: usize = dirty.iter().map(PixelRegion::area).sum();
+            if dirty.is_empty() {
+                // Nothing to upload.
+            } else if total_area * 2 >= self.image.pixels.len() {
+                // Cheaper to upload everything at once.
+                // We still use a partial update so that the texture doesn't need to be reallocated.
+                texture.set_partial([0, 0], self.image.clone(), self.options);
+            } else {
+                for region in dirty {
+                    let sub_image = self.image.sub_image(region.min, region.size());
+                    texture.set_partial(region.min, sub_image, self.options);
+                }
+            }
+        }
+
+        self.texture
+            .get_or_insert_with(|| ctx.load_texture(&self.name, self.image.clone(), self.options))
+    }
+
+    /// Upload any changes, and return an [`Image`] showing the texture.
+    ///
+    /// Use this if you want to customize how the image is shown, e.g. its size.
+    pub fn image_widget(&mut self, ctx: &Context) -> Image<'static> {
+        let texture = self.texture(ctx);
+        Image::from_texture(SizedTexture::from_handle(texture))
+    }
+}
+
+impl Widget for &mut DirtyImage {
+    fn ui(self, ui: &mut Ui) -> Response {
+        ui.add(self.image_widget(ui.ctx()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dirty_regions_are_merged() {
+        let mut image = DirtyImage::new("test", ColorImage::new([100, 100], Color32::BLACK));
+
+        image.set_pixel([10, 10], Color32::RED);
+        image.set_pixel([11, 10], Color32::RED);
+        assert_eq!(
+            image.dirty,
+            vec![PixelRegion {
+                min: [10, 10],
+                max: [12, 11]
+            }]
+        );
+
+        image.set_pixel([50, 50], Color32::RED);
+        assert_eq!(image.dirty.len(), 2);
+
+        // Setting a pixel to its current color doesn't dirty anything:
+        image.set_pixel([90, 90], Color32::BLACK);
+        assert_eq!(image.dirty.len(), 2);
+
+        // Bridging the two regions merges them:
+        image.fill_region([12, 10], [38, 40], Color32::GREEN);
+        assert_eq!(
+            image.dirty,
+            vec![PixelRegion {
+                min: [10, 10],
+                max: [51, 51]
+            }]
+        );
+    }
+
+    #[test]
+    fn too_many_regions_collapse_into_bounding_box() {
+        let mut image = DirtyImage::new("test", ColorImage::new([100, 100], Color32::BLACK));
+        for i in 0..=MAX_DIRTY_REGIONS {
+            image.set_pixel([10 * i, 5], Color32::RED);
+        }
+        assert_eq!(
+            image.dirty,
+            vec![PixelRegion {
+                min: [0, 5],
+                max: [10 * MAX_DIRTY_REGIONS + 1, 6]
+            }]
+        );
+    }
+}
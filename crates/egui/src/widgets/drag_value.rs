@@ -3,7 +3,9 @@
 use std::{cmp::Ordering, ops::RangeInclusive};
 
 use crate::{
-    emath, text, Button, CursorIcon, Key, Modifiers, NumExt, Response, RichText, Sense, TextEdit,
+    emath,
+    quantity::{ExpressionContext, Quantity, Units},
+    text, Button, CursorIcon, Key, Modifiers, NumExt, Response, RichText, Sense, TextEdit,
     TextWrapMode, Ui, Widget, WidgetInfo, MINUS_CHAR_STR,
 };
 
@@ -46,6 +48,8 @@ pub struct DragValue<'a> {
     max_decimals: Option<usize>,
     custom_formatter: Option<NumFormatter<'a>>,
     custom_parser: Option<NumParser<'a>>,
+    quantity: Option<Quantity>,
+    units: Option<Units>,
     update_while_editing: bool,
 }
 
@@ -77,6 +81,8 @@ impl<'a> DragValue<'a> {
             max_decimals: None,
             custom_formatter: None,
             custom_parser: None,
+            quantity: None,
+            units: None,
             update_while_editing: true,
         }
     }
@@ -415,6 +421,48 @@ impl<'a> DragValue<'a> {
         .custom_parser(|s| i64::from_str_radix(s, 16).map(|n| n as f64).ok())
     }
 
+    /// Show and parse the value as the given [`Quantity`], e.g. with units like `kHz` or `MiB`.
+    ///
+    /// Numbers are written using [`crate::Style::number_locale`].
+    ///
+    /// [`Self::custom_formatter`] and [`Self::custom_parser`] take precedence over this.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// use egui::quantity::Quantity;
+    /// # let mut frequency = 1000.0;
+    /// # let mut file_size: u64 = 0;
+    /// ui.add(egui::DragValue::new(&mut frequency).quantity(Quantity::si("Hz")));
+    /// ui.add(egui::DragValue::new(&mut file_size).quantity(Quantity::binary_bytes()));
+    /// # });
+    /// ```
+    #[inline]
+    pub fn quantity(mut self, quantity: Quantity) -> Self {
+        self.quantity = Some(quantity);
+        self
+    }
+
+    /// The units the user can type after a number, e.g. `10 cm` for a value in meters.
+    ///
+    /// The user can also type math, like `2*pi` or `50%` (of the range),
+    /// which is evaluated by [`crate::Style::expression_parser`].
+    ///
+    /// Default: the units of [`Self::quantity`], if any.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// use egui::quantity::Units;
+    /// # let mut length_in_meters = 1.0;
+    /// let units = Units::si("m").with("in", 0.0254);
+    /// ui.add(egui::DragValue::new(&mut length_in_meters).suffix(" m").units(units));
+    /// # });
+    /// ```
+    #[inline]
+    pub fn units(mut self, units: Units) -> Self {
+        self.units = Some(units);
+        self
+    }
+
     /// Update the value on each key press when text-editing the value.
     ///
     /// Default: `true`.
@@ -439,6 +487,8 @@ impl<'a> Widget for DragValue<'a> {
             max_decimals,
             custom_formatter,
             custom_parser,
+            quantity,
+            units,
             update_while_editing,
         } = self;
 
@@ -524,9 +574,13 @@ impl<'a> Widget for DragValue<'a> {
             ui.data_mut(|data| data.remove::<String>(id));
         }
 
-        let value_text = match custom_formatter {
-            Some(custom_formatter) => custom_formatter(value, auto_decimals..=max_decimals),
-            None => ui
+        let number_locale = ui.style().number_locale;
+        let value_text = match (custom_formatter, &quantity) {
+            (Some(custom_formatter), _) => custom_formatter(value, auto_decimals..=max_decimals),
+            (None, Some(quantity)) => {
+                quantity.format(value, auto_decimals..=max_decimals, &number_locale)
+            }
+            (None, None) => ui
                 .style()
                 .number_formatter
                 .format(value, auto_decimals..=max_decimals),
@@ -539,7 +593,14 @@ impl<'a> Widget for DragValue<'a> {
             if let Some(value_text) = value_text {
                 // We were editing the value as text last frame, but lost focus.
                 // Make sure we applied the last text value:
-                let parsed_value = parse(&custom_parser, &value_text);
+                let parsed_value = parse(
+                    ui.style(),
+                    &custom_parser,
+                    quantity.as_ref(),
+                    units.as_ref(),
+                    &range,
+                    &value_text,
+                );
                 if let Some(mut parsed_value) = parsed_value {
                     // User edits always clamps:
                     parsed_value = clamp_value_to_range(parsed_value, range.clone());
@@ -574,7 +635,14 @@ impl<'a> Widget for DragValue<'a> {
                 response.lost_focus() && !ui.input(|i| i.key_pressed(Key::Escape))
             };
             if update {
-                let parsed_value = parse(&custom_parser, &value_text);
+                let parsed_value = parse(
+                    ui.style(),
+                    &custom_parser,
+                    quantity.as_ref(),
+                    units.as_ref(),
+                    &range,
+                    &value_text,
+                );
                 if let Some(mut parsed_value) = parsed_value {
                     // User edits always clamps:
                     parsed_value = clamp_value_to_range(parsed_value, range.clone());
@@ -715,11 +783,35 @@ impl<'a> Widget for DragValue<'a> {
     }
 }
 
-fn parse(custom_parser: &Option<NumParser<'_>>, value_text: &str) -> Option<f64> {
-    match &custom_parser {
-        Some(parser) => parser(value_text),
-        None => default_parser(value_text),
-    }
+fn parse(
+    style: &crate::Style,
+    custom_parser: &Option<NumParser<'_>>,
+    quantity: Option<&Quantity>,
+    units: Option<&Units>,
+    range: &RangeInclusive<f64>,
+    value_text: &str,
+) -> Option<f64> {
+    let number_locale = &style.number_locale;
+    let value = match (custom_parser, quantity) {
+        (Some(parser), _) => return parser(value_text),
+        (None, Some(quantity)) => quantity.parse(value_text, number_locale),
+        (None, None) => default_parser(value_text),
+    };
+
+    value.or_else(|| {
+        // Not just a number: maybe math and units, like `2*pi` or `10 cm`.
+        let units = match (units, quantity) {
+            (Some(units), _) => units.clone(),
+            (None, Some(quantity)) => Units::for_quantity(quantity),
+            (None, None) => Units::new(),
+        };
+        let ctx = ExpressionContext {
+            range: range.clone(),
+            units: &units,
+            locale: number_locale,
+        };
+        style.expression_parser.parse(value_text, &ctx)
+    })
 }
 
 /// The default egui parser of numbers.
@@ -46,11 +46,14 @@ use crate::{
 #[derive(Debug, Clone)]
 pub struct Image<'a> {
     source: ImageSource<'a>,
-    texture_options: TextureOptions,
+
+    /// `None` means the default, which depends on [`crate::Options::pixel_perfect`].
+    texture_options: Option<TextureOptions>,
     image_options: ImageOptions,
     sense: Sense,
     size: ImageSize,
     pub(crate) show_loading_spinner: Option<bool>,
+    animate: bool,
 }
 
 impl<'a> Image<'a> {
@@ -71,11 +74,12 @@ impl<'a> Image<'a> {
 
             Image {
                 source,
-                texture_options: Default::default(),
+                texture_options: None,
                 image_options: Default::default(),
                 sense: Sense::hover(),
                 size,
                 show_loading_spinner: None,
+                animate: true,
             }
         }
 
@@ -109,9 +113,12 @@ impl<'a> Image<'a> {
     }
 
     /// Texture options used when creating the texture.
+    ///
+    /// Defaults to [`TextureOptions::LINEAR`],
+    /// or [`TextureOptions::NEAREST`] if [`crate::Options::pixel_perfect`] is on.
     #[inline]
     pub fn texture_options(mut self, texture_options: TextureOptions) -> Self {
-        self.texture_options = texture_options;
+        self.texture_options = Some(texture_options);
         self
     }
 
@@ -255,6 +262,18 @@ impl<'a> Image<'a> {
         self.show_loading_spinner = Some(show);
         self
     }
+
+    /// Play animated images, like gifs and animated webps.
+    ///
+    /// While playing, a repaint is requested for when the next frame is due.
+    /// When `false`, the first frame is shown.
+    ///
+    /// Default: `true`.
+    #[inline]
+    pub fn animate(mut self, animate: bool) -> Self {
+        self.animate = animate;
+        self
+    }
 }
 
 impl<'a, T: Into<ImageSource<'a>>> From<T> for Image<'a> {
@@ -286,13 +305,13 @@ impl<'a> Image<'a> {
 
     /// Returns the URI of the image.
     ///
-    /// For GIFs, returns the URI without the frame number.
+    /// For animated images, returns the URI without the frame number.
     #[inline]
     pub fn uri(&self) -> Option<&str> {
         let uri = self.source.uri()?;
 
-        if let Ok((gif_uri, _index)) = decode_gif_uri(uri) {
-            Some(gif_uri)
+        if let Ok((image_uri, _index)) = decode_animated_image_uri(uri) {
+            Some(image_uri)
         } else {
             Some(uri)
         }
@@ -306,20 +325,42 @@ impl<'a> Image<'a> {
     #[inline]
     pub fn source(&'a self, ctx: &Context) -> ImageSource<'a> {
         match &self.source {
-            ImageSource::Uri(uri) if is_gif_uri(uri) => {
-                let frame_uri = encode_gif_uri(uri, gif_frame_index(ctx, uri));
-                ImageSource::Uri(Cow::Owned(frame_uri))
+            ImageSource::Uri(uri) if is_animated_image_uri(uri) => {
+                let is_gif = is_gif_uri(uri);
+                match animated_image_frame_index(ctx, uri, is_gif, self.animate) {
+                    Some(index) => ImageSource::Uri(Cow::Owned(encode_frame_uri(uri, index))),
+                    None => self.source.clone(),
+                }
             }
 
-            ImageSource::Bytes { uri, bytes } if is_gif_uri(uri) || has_gif_magic_header(bytes) => {
-                let frame_uri = encode_gif_uri(uri, gif_frame_index(ctx, uri));
-                ctx.include_bytes(uri.clone(), bytes.clone());
-                ImageSource::Uri(Cow::Owned(frame_uri))
+            ImageSource::Bytes { uri, bytes }
+                if is_animated_image_uri(uri)
+                    || has_gif_magic_header(bytes)
+                    || has_webp_header(bytes) =>
+            {
+                let is_gif = is_gif_uri(uri) || has_gif_magic_header(bytes);
+                match animated_image_frame_index(ctx, uri, is_gif, self.animate) {
+                    Some(index) => {
+                        ctx.include_bytes(uri.clone(), bytes.clone());
+                        ImageSource::Uri(Cow::Owned(encode_frame_uri(uri, index)))
+                    }
+                    None => self.source.clone(),
+                }
             }
             _ => self.source.clone(),
         }
     }
 
+    fn texture_options_or_default(&self, ctx: &Context) -> TextureOptions {
+        self.texture_options.unwrap_or_else(|| {
+            if ctx.options(|o| o.pixel_perfect) {
+                TextureOptions::NEAREST
+            } else {
+                TextureOptions::default()
+            }
+        })
+    }
+
     /// Load the image from its [`Image::source`], returning the resulting [`SizedTexture`].
     ///
     /// The `available_size` is used as a hint when e.g. rendering an svg.
@@ -330,7 +371,7 @@ impl<'a> Image<'a> {
         let size_hint = self.size.hint(available_size, ctx.pixels_per_point());
         self.source(ctx)
             .clone()
-            .load(ctx, self.texture_options, size_hint)
+            .load(ctx, self.texture_options_or_default(ctx), size_hint)
     }
 
     /// Paint the image in the given rectangle.
@@ -796,57 +837,97 @@ pub fn paint_texture_at(
     }
 }
 
-/// gif uris contain the uri & the frame that will be displayed
-fn encode_gif_uri(uri: &str, frame_index: usize) -> String {
+/// Animated image uris contain the uri & the frame that will be displayed
+fn encode_frame_uri(uri: &str, frame_index: usize) -> String {
     format!("{uri}#{frame_index}")
 }
 
 /// extracts uri and frame index
 /// # Errors
-/// Will return `Err` if `uri` does not match pattern {uri}-{frame_index}
-pub fn decode_gif_uri(uri: &str) -> Result<(&str, usize), String> {
+/// Will return `Err` if `uri` does not match pattern `{uri}#{frame_index}`
+pub fn decode_animated_image_uri(uri: &str) -> Result<(&str, usize), String> {
     let (uri, index) = uri
         .rsplit_once('#')
         .ok_or("Failed to find index separator '#'")?;
     let index: usize = index
         .parse()
-        .map_err(|_err| format!("Failed to parse gif frame index: {index:?} is not an integer"))?;
+        .map_err(|_err| format!("Failed to parse frame index: {index:?} is not an integer"))?;
     Ok((uri, index))
 }
 
+/// extracts uri and frame index
+/// # Errors
+/// Will return `Err` if `uri` does not match pattern `{uri}#{frame_index}`
+#[deprecated = "Renamed to `decode_animated_image_uri`"]
+pub fn decode_gif_uri(uri: &str) -> Result<(&str, usize), String> {
+    decode_animated_image_uri(uri)
+}
+
 /// checks if uri is a gif file
 fn is_gif_uri(uri: &str) -> bool {
     uri.ends_with(".gif") || uri.contains(".gif#")
 }
 
+/// checks if uri is a webp file
+fn is_webp_uri(uri: &str) -> bool {
+    uri.ends_with(".webp") || uri.contains(".webp#")
+}
+
+/// checks if uri is of a format that may be animated
+fn is_animated_image_uri(uri: &str) -> bool {
+    is_gif_uri(uri) || is_webp_uri(uri)
+}
+
 /// checks if bytes are gifs
 pub fn has_gif_magic_header(bytes: &[u8]) -> bool {
     bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a")
 }
 
-/// calculates at which frame the gif is
-fn gif_frame_index(ctx: &Context, uri: &str) -> usize {
-    let now = ctx.input(|i| Duration::from_secs_f64(i.time));
+/// checks if bytes are webp
+pub fn has_webp_header(bytes: &[u8]) -> bool {
+    bytes.len() >= 12 && bytes.starts_with(b"RIFF") && &bytes[8..12] == b"WEBP"
+}
 
-    let durations: Option<GifFrameDurations> = ctx.data(|data| data.get_temp(Id::new(uri)));
-    if let Some(durations) = durations {
-        let frames: Duration = durations.0.iter().sum();
-        let pos_ms = now.as_millis() % frames.as_millis().max(1);
-        let mut cumulative_ms = 0;
-        for (i, duration) in durations.0.iter().enumerate() {
-            cumulative_ms += duration.as_millis();
-            if pos_ms < cumulative_ms {
-                let ms_until_next_frame = cumulative_ms - pos_ms;
-                ctx.request_repaint_after(Duration::from_millis(ms_until_next_frame as u64));
-                return i;
-            }
+/// calculates at which frame the animated image is,
+/// requesting a repaint for when the next frame is due.
+///
+/// Returns `None` if the image should be loaded without a frame index:
+/// gifs are always loaded frame by frame, but other formats only
+/// once their loader has found them to be animated.
+fn animated_image_frame_index(
+    ctx: &Context,
+    uri: &str,
+    is_gif: bool,
+    animate: bool,
+) -> Option<usize> {
+    let durations: Option<FrameDurations> = ctx.data(|data| data.get_temp(Id::new(uri)));
+    let Some(durations) = durations else {
+        return is_gif.then_some(0);
+    };
+    if !animate {
+        return Some(0);
+    }
+
+    let now = ctx.input(|i| Duration::from_secs_f64(i.time));
+    let frames: Duration = durations.0.iter().sum();
+    let pos_ms = now.as_millis() % frames.as_millis().max(1);
+    let mut cumulative_ms = 0;
+    for (i, duration) in durations.0.iter().enumerate() {
+        cumulative_ms += duration.as_millis();
+        if pos_ms < cumulative_ms {
+            let ms_until_next_frame = cumulative_ms - pos_ms;
+            ctx.request_repaint_after(Duration::from_millis(ms_until_next_frame as u64));
+            return Some(i);
         }
-        0
-    } else {
-        0
     }
+    Some(0)
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
-/// Stores the durations between each frame of a gif
-pub struct GifFrameDurations(pub Arc<Vec<Duration>>);
+/// Stores the durations between each frame of an animated image.
+///
+/// Loaders of animated images store this in [`crate::Memory::data`] at `Id::new(uri)`.
+pub struct FrameDurations(pub Arc<Vec<Duration>>);
+
+#[deprecated = "Renamed to `FrameDurations`"]
+pub type GifFrameDurations = FrameDurations;
@@ -1,8 +1,9 @@
-use std::sync::Arc;
+use std::{ops::Range, sync::Arc};
 
 use crate::{
-    epaint, pos2, text_selection, vec2, Align, Direction, FontSelection, Galley, Pos2, Response,
-    Sense, Stroke, TextWrapMode, Ui, Widget, WidgetInfo, WidgetText, WidgetType,
+    epaint, pos2, text::LayoutJob, text_selection, vec2, Align, Color32, CursorIcon, Direction,
+    FontSelection, Galley, Pos2, Rect, Response, Sense, Stroke, TextWrapMode, Ui, Widget,
+    WidgetInfo, WidgetText, WidgetType,
 };
 
 use self::text_selection::LabelSelectionState;
@@ -30,6 +31,7 @@ pub struct Label {
     sense: Option<Sense>,
     selectable: Option<bool>,
     halign: Option<Align>,
+    linkify: bool,
 }
 
 impl Label {
@@ -40,6 +42,7 @@ impl Label {
             sense: None,
             selectable: None,
             halign: None,
+            linkify: false,
         }
     }
 
@@ -97,6 +100,24 @@ impl Label {
         self
     }
 
+    /// Find URLs in the text (e.g. `https://github.com/emilk/egui`) and show them as clickable hyperlinks.
+    ///
+    /// Clicking a link opens it with [`crate::Context::open_url`],
+    /// so you can decide which links may be opened with [`crate::Context::set_open_url_policy`].
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// ui.add(egui::Label::new("Read more at https://www.egui.rs").linkify(true));
+    /// # });
+    /// ```
+    ///
+    /// Default: `false`.
+    #[inline]
+    pub fn linkify(mut self, linkify: bool) -> Self {
+        self.linkify = linkify;
+        self
+    }
+
     /// Make the label respond to clicks and/or drags.
     ///
     /// By default, a label is inert and does not respond to click or drags.
@@ -166,6 +187,9 @@ impl Label {
         let mut layout_job = self
             .text
             .into_layout_job(ui.style(), FontSelection::Default, valign);
+        if self.linkify {
+            color_links(&mut layout_job, ui.visuals().hyperlink_color);
+        }
 
         let available_width = ui.available_width();
 
@@ -247,10 +271,13 @@ impl Widget for Label {
         let interactive = self.sense.map_or(false, |sense| sense != Sense::hover());
 
         let selectable = self.selectable;
+        let linkify = self.linkify;
+        let live_region = self.text.live_region_kind();
 
         let (galley_pos, galley, mut response) = self.layout_in_ui(ui);
         response
             .widget_info(|| WidgetInfo::labeled(WidgetType::Label, ui.is_enabled(), galley.text()));
+        response = response.live_region(live_region);
 
         if ui.is_rect_visible(response.rect) {
             if galley.elided {
@@ -270,6 +297,10 @@ impl Widget for Label {
                 Stroke::NONE
             };
 
+            if linkify {
+                links_ui(ui, &response, galley_pos, &galley);
+            }
+
             let selectable = selectable.unwrap_or_else(|| ui.style().interaction.selectable_labels);
             if selectable {
                 LabelSelectionState::label_text_selection(
@@ -281,6 +312,19 @@ impl Widget for Label {
                     underline,
                 );
             } else {
+                // Let screen reader review cursors move through live status text by character and word:
+                #[cfg(feature = "accesskit")]
+                if live_region != crate::LiveRegion::Off {
+                    text_selection::accesskit_text::update_accesskit_for_text_widget(
+                        ui.ctx(),
+                        response.id,
+                        None,
+                        accesskit::Role::Label,
+                        galley_pos,
+                        &galley,
+                    );
+                }
+
                 ui.painter().add(
                     epaint::TextShape::new(galley_pos, galley, response_color)
                         .with_underline(underline),
@@ -291,3 +335,218 @@ impl Widget for Label {
         response
     }
 }
+
+/// Make the links in a linkified label clickable.
+fn links_ui(ui: &Ui, response: &Response, galley_pos: Pos2, galley: &Galley) {
+    let text = galley.text();
+    for (i, (byte_range, url)) in find_urls(text).into_iter().enumerate() {
+        let char_range =
+            text[..byte_range.start].chars().count()..text[..byte_range.end].chars().count();
+        let rects: Vec<Rect> = link_rects(galley, char_range)
+            .into_iter()
+            .map(|rect| rect.translate(galley_pos.to_vec2()))
+            .collect();
+
+        let mut link_response: Option<Response> = None;
+        for (j, rect) in rects.iter().enumerate() {
+            let row_response = ui.interact(*rect, response.id.with(("link", i, j)), Sense::click());
+            link_response = Some(match link_response {
+                Some(link_response) => link_response | row_response,
+                None => row_response,
+            });
+        }
+        let Some(link_response) = link_response else {
+            continue; // e.g. elided
+        };
+        link_response.widget_info(|| WidgetInfo::labeled(WidgetType::Link, ui.is_enabled(), &url));
+
+        if link_response.hovered() || link_response.has_focus() {
+            ui.ctx().set_cursor_icon(CursorIcon::PointingHand);
+            let stroke = Stroke::new(
+                ui.visuals().widgets.hovered.fg_stroke.width,
+                ui.visuals().hyperlink_color,
+            );
+            for rect in &rects {
+                ui.painter()
+                    .hline(rect.x_range(), rect.bottom() - 1.0, stroke);
+            }
+        }
+
+        if link_response.clicked() {
+            let modifiers = ui.ctx().input(|i| i.modifiers);
+            ui.ctx().open_url(crate::OpenUrl {
+                url: url.clone(),
+                new_tab: modifiers.any(),
+            });
+        }
+        if link_response.middle_clicked() {
+            ui.ctx().open_url(crate::OpenUrl {
+                url: url.clone(),
+                new_tab: true,
+            });
+        }
+
+        if ui.style().url_in_tooltip {
+            link_response.on_hover_text(url);
+        }
+    }
+}
+
+/// One rectangle per row that the characters in `char_range` are on, relative to the galley.
+fn link_rects(galley: &Galley, char_range: Range<usize>) -> Vec<Rect> {
+    let mut rects = vec![];
+    let mut row_start = 0;
+    for row in &galley.rows {
+        let rect = row
+            .glyphs
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| char_range.contains(&(row_start + i)))
+            .fold(Rect::NOTHING, |rect, (_, glyph)| {
+                rect.union(glyph.logical_rect())
+            });
+        if rect.is_positive() {
+            rects.push(rect);
+        }
+        row_start += row.char_count_including_newline();
+    }
+    rects
+}
+
+/// Show the URLs in the text with the given color.
+fn color_links(job: &mut LayoutJob, color: Color32) {
+    let urls = find_urls(&job.text);
+    if urls.is_empty() {
+        return;
+    }
+
+    let mut sections = Vec::with_capacity(job.sections.len() + 2 * urls.len());
+    for section in std::mem::take(&mut job.sections) {
+        let section_range = section.byte_range.clone();
+        let mut start = section_range.start;
+        for (url_range, _) in &urls {
+            let link = url_range.start.max(start)..url_range.end.min(section_range.end);
+            if link.is_empty() {
+                continue;
+            }
+            if start < link.start {
+                sections.push(epaint::text::LayoutSection {
+                    byte_range: start..link.start,
+                    leading_space: if start == section_range.start {
+                        section.leading_space
+                    } else {
+                        0.0
+                    },
+                    format: section.format.clone(),
+                });
+            }
+            let mut format = section.format.clone();
+            format.color = color;
+            sections.push(epaint::text::LayoutSection {
+                leading_space: if link.start == section_range.start {
+                    section.leading_space
+                } else {
+                    0.0
+                },
+                byte_range: link.clone(),
+                format,
+            });
+            start = link.end;
+        }
+        if start < section_range.end || start == section_range.start {
+            sections.push(epaint::text::LayoutSection {
+                leading_space: if start == section_range.start {
+                    section.leading_space
+                } else {
+                    0.0
+                },
+                byte_range: start..section_range.end,
+                format: section.format,
+            });
+        }
+    }
+    job.sections = sections;
+}
+
+/// Find the `http://` and `https://` URLs in the text, and those starting with `www.`.
+///
+/// Returns the byte range of each URL in the text, and the URL to open.
+fn find_urls(text: &str) -> Vec<(Range<usize>, String)> {
+    let mut urls = vec![];
+    let mut search_from = 0;
+    while let Some((start, prefix)) = ["https://", "http://", "www."]
+        .into_iter()
+        .filter_map(|prefix| Some((search_from + text[search_from..].find(prefix)?, prefix)))
+        .min_by_key(|(start, _)| *start)
+    {
+        let at_word_start = text[..start]
+            .chars()
+            .next_back()
+            .map_or(true, |c| !c.is_alphanumeric() && c != '/' && c != '.');
+        let len = text[start..]
+            .find(|c: char| c.is_whitespace() || "<>\"`".contains(c))
+            .unwrap_or(text.len() - start);
+        let mut end = start + len;
+
+        // Punctuation at the end is usually part of the sentence, not the URL:
+        while let Some(c) = text[..end].chars().next_back() {
+            let unbalanced = match c {
+                ')' => {
+                    text[start..end].matches('(').count() < text[start..end].matches(')').count()
+                }
+                ']' => {
+                    text[start..end].matches('[').count() < text[start..end].matches(']').count()
+                }
+                _ => ".,:;!?'*".contains(c),
+            };
+            if unbalanced {
+                end -= c.len_utf8();
+            } else {
+                break;
+            }
+        }
+
+        if at_word_start && start + prefix.len() < end {
+            let url = &text[start..end];
+            let url = if prefix == "www." {
+                format!("https://{url}")
+            } else {
+                url.to_owned()
+            };
+            urls.push((start..end, url));
+            search_from = end;
+        } else {
+            search_from = start + prefix.len();
+        }
+    }
+    urls
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_urls() {
+        let urls = |text: &str| -> Vec<String> {
+            find_urls(text)
+                .into_iter()
+                .map(|(range, url)| {
+                    assert!(url.ends_with(&text[range]));
+                    url
+                })
+                .collect()
+        };
+
+        assert_eq!(urls("no links here."), Vec::<String>::new());
+        assert_eq!(
+            urls("See https://github.com/emilk/egui, or (www.egui.rs)."),
+            vec!["https://github.com/emilk/egui", "https://www.egui.rs"]
+        );
+        assert_eq!(
+            urls("https://en.wikipedia.org/wiki/Rust_(programming_language)!"),
+            vec!["https://en.wikipedia.org/wiki/Rust_(programming_language)"]
+        );
+        assert_eq!(urls("nothttps://example.com http://"), Vec::<String>::new());
+    }
+}
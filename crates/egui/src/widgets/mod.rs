@@ -9,6 +9,7 @@ use crate::{epaint, Response, Ui};
 mod button;
 mod checkbox;
 pub mod color_picker;
+mod dirty_image;
 pub(crate) mod drag_value;
 mod hyperlink;
 mod image;
@@ -22,14 +23,18 @@ mod slider;
 mod spinner;
 pub mod text_edit;
 
+#[allow(deprecated)]
+pub use self::image::{decode_gif_uri, GifFrameDurations};
+
 pub use self::{
     button::Button,
     checkbox::Checkbox,
+    dirty_image::DirtyImage,
     drag_value::DragValue,
     hyperlink::{Hyperlink, Link},
     image::{
-        decode_gif_uri, has_gif_magic_header, paint_texture_at, GifFrameDurations, Image, ImageFit,
-        ImageOptions, ImageSize, ImageSource,
+        decode_animated_image_uri, has_gif_magic_header, has_webp_header, paint_texture_at,
+        FrameDurations, Image, ImageFit, ImageOptions, ImageSize, ImageSource,
     },
     image_button::ImageButton,
     label::Label,
@@ -58,6 +58,8 @@ impl ProgressBar {
     }
 
     /// A custom text to display on the progress bar.
+    ///
+    /// Use [`crate::RichText::live_region`] to have screen readers announce changes to it.
     #[inline]
     pub fn text(mut self, text: impl Into<WidgetText>) -> Self {
         self.text = Some(ProgressBarText::Custom(text.into()));
@@ -113,7 +115,7 @@ impl Widget for ProgressBar {
         let desired_width =
             desired_width.unwrap_or_else(|| ui.available_size_before_wrap().x.at_least(96.0));
         let height = desired_height.unwrap_or(ui.spacing().interact_size.y);
-        let (outer_rect, response) =
+        let (outer_rect, mut response) =
             ui.allocate_exact_size(vec2(desired_width, height), Sense::hover());
 
         response.widget_info(|| {
@@ -127,6 +129,10 @@ impl Widget for ProgressBar {
             info
         });
 
+        if let Some(ProgressBarText::Custom(text)) = &text {
+            response = response.live_region(text.live_region_kind());
+        }
+
         if ui.is_rect_visible(response.rect) {
             if animate {
                 ui.ctx().request_repaint();
@@ -48,7 +48,7 @@ impl Widget for SelectableLabel {
 
         let mut desired_size = total_extra + galley.size();
         desired_size.y = desired_size.y.at_least(ui.spacing().interact_size.y);
-        let (rect, response) = ui.allocate_at_least(desired_size, Sense::click());
+        let (rect, mut response) = ui.allocate_at_least(desired_size, Sense::click());
         response.widget_info(|| {
             WidgetInfo::selected(
                 WidgetType::SelectableLabel,
@@ -58,6 +58,11 @@ impl Widget for SelectableLabel {
             )
         });
 
+        if galley.elided {
+            // Show the full (non-elided) text on hover:
+            response = response.on_hover_text(galley.text());
+        }
+
         if ui.is_rect_visible(response.rect) {
             let text_pos = ui
                 .layout()
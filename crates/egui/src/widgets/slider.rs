@@ -3,9 +3,13 @@
 use std::ops::RangeInclusive;
 
 use crate::{
-    emath, epaint, lerp, pos2, remap, remap_clamp, style, style::HandleShape, vec2, Color32,
-    DragValue, EventFilter, Key, Label, NumExt, Pos2, Rangef, Rect, Response, Sense, TextStyle,
-    TextWrapMode, Ui, Vec2, Widget, WidgetInfo, WidgetText, MINUS_CHAR_STR,
+    emath, epaint, lerp, popup_below_widget, pos2,
+    quantity::{Quantity, Units},
+    remap, remap_clamp, style,
+    style::HandleShape,
+    vec2, Align2, Color32, DragValue, EventFilter, Key, Label, NumExt, PopupCloseBehavior, Pos2,
+    Rangef, Rect, Response, Sense, TextStyle, TextWrapMode, Ui, Vec2, Widget, WidgetInfo,
+    WidgetText, WidgetType, MINUS_CHAR_STR,
 };
 
 use super::drag_value::clamp_value_to_range;
@@ -115,8 +119,12 @@ pub struct Slider<'a> {
     max_decimals: Option<usize>,
     custom_formatter: Option<NumFormatter<'a>>,
     custom_parser: Option<NumParser<'a>>,
+    quantity: Option<Quantity>,
+    units: Option<Units>,
     trailing_fill: Option<bool>,
     handle_shape: Option<HandleShape>,
+    steppers: Option<bool>,
+    value_popover: Option<bool>,
 }
 
 impl<'a> Slider<'a> {
@@ -165,8 +173,12 @@ impl<'a> Slider<'a> {
             max_decimals: None,
             custom_formatter: None,
             custom_parser: None,
+            quantity: None,
+            units: None,
             trailing_fill: None,
             handle_shape: None,
+            steppers: None,
+            value_popover: None,
         }
     }
 
@@ -397,6 +409,32 @@ impl<'a> Slider<'a> {
         self
     }
 
+    /// Show `−` and `+` buttons on either side of the slider, which change the value one step at a time.
+    ///
+    /// Each click changes the value by [`Self::step_by`] if set, else by one point along the slider,
+    /// just like the arrow keys do.
+    ///
+    /// This setting can be enabled globally for all sliders with [`crate::style::Interaction::slider_steppers`].
+    /// Changing it here will override the above setting ONLY for this individual slider.
+    #[inline]
+    pub fn steppers(mut self, steppers: bool) -> Self {
+        self.steppers = Some(steppers);
+        self
+    }
+
+    /// Open a popover for typing in the value when the user presses Enter
+    /// while the slider has keyboard focus, or double-clicks it.
+    ///
+    /// Enter applies the typed value, Escape cancels, and either way the focus goes back to the slider.
+    ///
+    /// This setting can be enabled globally for all sliders with [`crate::style::Interaction::slider_value_popover`].
+    /// Changing it here will override the above setting ONLY for this individual slider.
+    #[inline]
+    pub fn value_popover(mut self, value_popover: bool) -> Self {
+        self.value_popover = Some(value_popover);
+        self
+    }
+
     /// Set custom formatter defining how numbers are converted into text.
     ///
     /// A custom formatter takes a `f64` for the numeric value and a `RangeInclusive<usize>` representing
@@ -483,6 +521,34 @@ impl<'a> Slider<'a> {
         self
     }
 
+    /// Show and parse the value as the given [`Quantity`], e.g. with units like `kHz` or `MiB`.
+    ///
+    /// Numbers are written using [`crate::Style::number_locale`].
+    ///
+    /// [`Self::custom_formatter`] and [`Self::custom_parser`] take precedence over this.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// # let mut opacity = 0.5;
+    /// ui.add(egui::Slider::new(&mut opacity, 0.0..=1.0).quantity(egui::quantity::Quantity::Percent));
+    /// # });
+    /// ```
+    #[inline]
+    pub fn quantity(mut self, quantity: Quantity) -> Self {
+        self.quantity = Some(quantity);
+        self
+    }
+
+    /// The units the user can type after a number when editing the value as text,
+    /// e.g. `10 cm` for a value in meters.
+    ///
+    /// See [`DragValue::units`].
+    #[inline]
+    pub fn units(mut self, units: Units) -> Self {
+        self.units = Some(units);
+        self
+    }
+
     /// Set `custom_formatter` and `custom_parser` to display and parse numbers as binary integers. Floating point
     /// numbers are *not* supported.
     ///
@@ -631,6 +697,15 @@ impl<'a> Slider<'a> {
         self.range.clone()
     }
 
+    /// The range that edits are clamped to.
+    fn clamp_range(&self) -> RangeInclusive<f64> {
+        if self.clamping == SliderClamping::Never {
+            f64::NEG_INFINITY..=f64::INFINITY
+        } else {
+            self.range()
+        }
+    }
+
     /// For instance, `position` is the mouse position and `position_range` is the physical location of the slider on the screen.
     fn value_from_position(&self, position: f32, position_range: Rangef) -> f64 {
         let normalized = remap_clamp(position, position_range, 0.0..=1.0) as f64;
@@ -889,38 +964,170 @@ impl<'a> Slider<'a> {
         };
 
         let mut value = self.get_value();
-        let response = ui.add({
-            let mut dv = DragValue::new(&mut value)
-                .speed(speed)
-                .min_decimals(self.min_decimals)
-                .max_decimals_opt(self.max_decimals)
-                .suffix(self.suffix.clone())
-                .prefix(self.prefix.clone());
-
-            match self.clamping {
-                SliderClamping::Never => {}
-                SliderClamping::Edits => {
-                    dv = dv.range(self.range.clone()).clamp_existing_to_range(false);
-                }
-                SliderClamping::Always => {
-                    dv = dv.range(self.range.clone()).clamp_existing_to_range(true);
-                }
-            }
-
-            if let Some(fmt) = &self.custom_formatter {
-                dv = dv.custom_formatter(fmt);
-            };
-            if let Some(parser) = &self.custom_parser {
-                dv = dv.custom_parser(parser);
-            }
-            dv
-        });
+        let response = ui.add(self.drag_value(&mut value, speed));
         if value != self.get_value() {
             self.set_value(value);
         }
         response
     }
 
+    /// The [`DragValue`] for showing and editing the value as a number.
+    fn drag_value<'v>(&'v self, value: &'v mut f64, speed: f64) -> DragValue<'v> {
+        let mut dv = DragValue::new(value)
+            .speed(speed)
+            .min_decimals(self.min_decimals)
+            .max_decimals_opt(self.max_decimals)
+            .suffix(self.suffix.clone())
+            .prefix(self.prefix.clone());
+
+        match self.clamping {
+            SliderClamping::Never => {}
+            SliderClamping::Edits => {
+                dv = dv.range(self.range.clone()).clamp_existing_to_range(false);
+            }
+            SliderClamping::Always => {
+                dv = dv.range(self.range.clone()).clamp_existing_to_range(true);
+            }
+        }
+
+        if let Some(fmt) = &self.custom_formatter {
+            dv = dv.custom_formatter(fmt);
+        };
+        if let Some(parser) = &self.custom_parser {
+            dv = dv.custom_parser(parser);
+        }
+        if let Some(quantity) = &self.quantity {
+            dv = dv.quantity(quantity.clone());
+        }
+        if let Some(units) = &self.units {
+            dv = dv.units(units.clone());
+        }
+        dv
+    }
+
+    /// Format a value for showing it outside of the [`DragValue`], e.g. the ends of the range.
+    fn format_value(&self, ui: &Ui, value: f64) -> String {
+        let max_decimals = self.max_decimals.unwrap_or(6).at_least(self.min_decimals);
+        let decimals = self.min_decimals..=max_decimals;
+        match (&self.custom_formatter, &self.quantity) {
+            (Some(custom_formatter), _) => custom_formatter(value, decimals),
+            (None, Some(quantity)) => quantity.format(value, decimals, &ui.style().number_locale),
+            (None, None) => ui.style().number_formatter.format(value, decimals),
+        }
+    }
+
+    /// The value one step up (`direction = 1.0`) or down (`direction = -1.0`) from the current one:
+    /// [`Self::step`] if set, else one point along the slider.
+    fn stepped_value(&mut self, direction: f64, position_range: Rangef) -> f64 {
+        let value = self.get_value();
+        if let Some(step) = self.step {
+            return value + direction * step;
+        }
+
+        // The position range is flipped for vertical sliders:
+        let towards_larger = (position_range.max - position_range.min).signum();
+        let position =
+            self.position_from_value(value, position_range) + direction as f32 * towards_larger;
+        if self.smart_aim {
+            let aim_radius = 0.49; // Chosen so we don't include `value` in the search.
+            emath::smart_aim::best_in_range_f64(
+                self.value_from_position(position - aim_radius, position_range),
+                self.value_from_position(position + aim_radius, position_range),
+            )
+        } else {
+            self.value_from_position(position, position_range)
+        }
+    }
+
+    /// A `−` or `+` button next to the slider, see [`Self::steppers`].
+    fn stepper_ui(&mut self, ui: &mut Ui, increment: bool, size: f32) -> Response {
+        let value = self.get_value();
+        let clamp_range = self.clamp_range();
+        let (symbol, action, enabled) = if increment {
+            ("+", "Increase", value < *clamp_range.end())
+        } else {
+            (MINUS_CHAR_STR, "Decrease", value > *clamp_range.start())
+        };
+        let label = if self.text.is_empty() {
+            action.to_owned()
+        } else {
+            format!("{action} {}", self.text.text())
+        };
+
+        ui.add_enabled_ui(enabled, |ui| {
+            let response = ui.allocate_response(Vec2::splat(size), Sense::click());
+            response
+                .widget_info(|| WidgetInfo::labeled(WidgetType::Button, ui.is_enabled(), &label));
+
+            if ui.is_rect_visible(response.rect) {
+                let visuals = ui.style().interact(&response);
+                ui.painter().rect(
+                    response.rect.expand(visuals.expansion),
+                    visuals.rounding,
+                    visuals.weak_bg_fill,
+                    visuals.bg_stroke,
+                );
+                ui.painter().text(
+                    response.rect.center(),
+                    Align2::CENTER_CENTER,
+                    symbol,
+                    TextStyle::Button.resolve(ui.style()),
+                    visuals.text_color(),
+                );
+            }
+            response
+        })
+        .inner
+    }
+
+    /// The popover for typing in the value, see [`Self::value_popover`].
+    fn value_popover_ui(&mut self, ui: &Ui, slider_response: &Response, position_range: Rangef) {
+        let popup_id = slider_response.id.with("value_popover");
+
+        let open = slider_response.double_clicked()
+            || (slider_response.has_focus() && ui.input(|i| i.key_pressed(Key::Enter)));
+        if open {
+            ui.memory_mut(|mem| mem.open_popup(popup_id));
+        }
+
+        let speed = self
+            .drag_value_speed
+            .unwrap_or_else(|| self.current_gradient(position_range));
+
+        let done = popup_below_widget(
+            ui,
+            popup_id,
+            slider_response,
+            PopupCloseBehavior::CloseOnClickOutside,
+            |ui| {
+                let range = self.range();
+                ui.label(format!(
+                    "{} – {}",
+                    self.format_value(ui, *range.start()),
+                    self.format_value(ui, *range.end())
+                ));
+
+                let mut value = self.get_value();
+                let response = ui.add(self.drag_value(&mut value, speed));
+                if open {
+                    // Start editing the value as text right away:
+                    response.request_focus();
+                }
+                if value != self.get_value() {
+                    self.set_value(value);
+                }
+
+                // Enter, Escape, or tabbing away:
+                response.lost_focus()
+            },
+        );
+
+        if done == Some(true) {
+            ui.memory_mut(|mem| mem.close_popup());
+            slider_response.request_focus();
+        }
+    }
+
     /// delta(value) / delta(points)
     fn current_gradient(&mut self, position_range: Rangef) -> f64 {
         // TODO(emilk): handle clamping
@@ -942,9 +1149,44 @@ impl<'a> Slider<'a> {
         let thickness = ui
             .text_style_height(&TextStyle::Body)
             .at_least(ui.spacing().interact_size.y);
+
+        // The steppers go on either side of the slider, with `−` at the low end:
+        let steppers = self
+            .steppers
+            .unwrap_or_else(|| ui.style().interaction.slider_steppers);
+        let first_stepper_increments = self.orientation == SliderOrientation::Vertical;
+        let first_stepper =
+            steppers.then(|| self.stepper_ui(ui, first_stepper_increments, thickness));
+
         let mut response = self.allocate_slider_space(ui, thickness);
         self.slider_ui(ui, &response);
 
+        let last_stepper =
+            steppers.then(|| self.stepper_ui(ui, !first_stepper_increments, thickness));
+
+        let handle_shape = self
+            .handle_shape
+            .unwrap_or_else(|| ui.style().visuals.handle_shape);
+        let position_range = self.position_range(&response.rect, &handle_shape);
+
+        for (stepper, increments) in [
+            (first_stepper, first_stepper_increments),
+            (last_stepper, !first_stepper_increments),
+        ] {
+            if stepper.is_some_and(|stepper| stepper.clicked()) {
+                let direction = if increments { 1.0 } else { -1.0 };
+                let new_value = self.stepped_value(direction, position_range);
+                self.set_value(new_value);
+            }
+        }
+
+        if self
+            .value_popover
+            .unwrap_or_else(|| ui.style().interaction.slider_value_popover)
+        {
+            self.value_popover_ui(ui, &response, position_range);
+        }
+
         let value = self.get_value();
         response.changed = value != old_value;
         response.widget_info(|| WidgetInfo::slider(ui.is_enabled(), value, self.text.text()));
@@ -959,11 +1201,7 @@ impl<'a> Slider<'a> {
             }
             builder.add_action(Action::SetValue);
 
-            let clamp_range = if self.clamping == SliderClamping::Never {
-                f64::NEG_INFINITY..=f64::INFINITY
-            } else {
-                self.range()
-            };
+            let clamp_range = self.clamp_range();
             if value < *clamp_range.end() {
                 builder.add_action(Action::Increment);
             }
@@ -975,10 +1213,6 @@ impl<'a> Slider<'a> {
         let slider_response = response.clone();
 
         let value_response = if self.show_value {
-            let handle_shape = self
-                .handle_shape
-                .unwrap_or_else(|| ui.style().visuals.handle_shape);
-            let position_range = self.position_range(&response.rect, &handle_shape);
             let value_response = self.value_ui(ui, position_range);
             if value_response.gained_focus()
                 || value_response.has_focus()
@@ -15,7 +15,12 @@ use crate::{
     TextStyle, TextWrapMode, Ui, Vec2, Widget, WidgetInfo, WidgetText, WidgetWithState,
 };
 
-use super::{TextEditOutput, TextEditState};
+use super::{
+    edit_op::RecordingTextBuffer,
+    multi_cursor,
+    text_drag::{self, TextDragPayload},
+    TextEditOp, TextEditOutput, TextEditSource, TextEditState,
+};
 
 /// A text region that the user can edit the contents of.
 ///
@@ -71,6 +76,7 @@ pub struct TextEdit<'t> {
     text_color: Option<Color32>,
     layouter: Option<&'t mut dyn FnMut(&Ui, &str, f32) -> Arc<Galley>>,
     password: bool,
+    ime: Option<bool>,
     frame: bool,
     margin: Margin,
     multiline: bool,
@@ -99,6 +105,47 @@ impl<'t> TextEdit<'t> {
     pub fn store_state(ctx: &Context, id: Id, state: TextEditState) {
         state.store(ctx, id);
     }
+
+    /// Apply changes made somewhere else (e.g. by a collaborator) to `text`,
+    /// and move the cursor of the [`TextEdit`] with the given id so it stays at the same place in the text.
+    ///
+    /// Call this before showing the [`TextEdit`].
+    /// The changes are not reported in [`TextEditOutput::edits`].
+    ///
+    /// The undo history of the [`TextEdit`] is cleared, since undoing would otherwise revert these changes too.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// use egui::text_edit::{TextEditOp, TextEditSource};
+    ///
+    /// # let mut text = String::from("world");
+    /// # let incoming_edits = vec![TextEditOp::insert(0, "Hello ", TextEditSource::Remote)];
+    /// # let mut send_to_collaborators = |_: Vec<TextEditOp>| {};
+    /// let id = ui.id().with("shared_text");
+    /// egui::TextEdit::apply_remote_edits(ui.ctx(), id, &mut text, &incoming_edits);
+    ///
+    /// let output = egui::TextEdit::multiline(&mut text).id(id).show(ui);
+    /// send_to_collaborators(output.edits);
+    /// # });
+    /// ```
+    pub fn apply_remote_edits(
+        ctx: &Context,
+        id: Id,
+        text: &mut dyn TextBuffer,
+        edits: &[TextEditOp],
+    ) {
+        if edits.is_empty() {
+            return;
+        }
+        for edit in edits {
+            edit.apply(text);
+        }
+        if let Some(mut state) = Self::load_state(ctx, id) {
+            state.transform_cursor(edits);
+            state.clear_undoer();
+            Self::store_state(ctx, id, state);
+        }
+    }
 }
 
 impl<'t> TextEdit<'t> {
@@ -124,6 +171,7 @@ impl<'t> TextEdit<'t> {
             text_color: None,
             layouter: None,
             password: false,
+            ime: None,
             frame: true,
             margin: Margin::symmetric(4.0, 2.0),
             multiline: true,
@@ -225,6 +273,18 @@ impl<'t> TextEdit<'t> {
         self
     }
 
+    /// Use the platform's input method editor (IME) for composing text, e.g. for typing Chinese or Japanese.
+    ///
+    /// Turn this off for fields where keys are bound to actions rather than text, e.g. a chat key-bind in a game.
+    /// See also [`crate::Options::ime_allowed`], to turn off the IME everywhere.
+    ///
+    /// Default: `true`, unless this is a [`Self::password`] field.
+    #[inline]
+    pub fn ime(mut self, ime: bool) -> Self {
+        self.ime = Some(ime);
+        self
+    }
+
     /// Pick a [`crate::FontId`] or [`TextStyle`].
     #[inline]
     pub fn font(mut self, font_selection: impl Into<FontSelection>) -> Self {
@@ -251,6 +311,8 @@ impl<'t> TextEdit<'t> {
     /// This function will be called at least once per frame,
     /// so it is strongly suggested that you cache the results of any syntax highlighter
     /// so as not to waste CPU highlighting the same string every frame.
+    /// If even that is too slow for large texts, `egui_extras::AsyncLayouter`
+    /// can run the highlighter on a background thread.
     ///
     /// The arguments is the enclosing [`Ui`] (so you can access e.g. [`Ui::fonts`]),
     /// the text and the wrap width.
@@ -476,6 +538,7 @@ impl<'t> TextEdit<'t> {
             text_color,
             layouter,
             password,
+            ime,
             frame: _,
             margin,
             multiline,
@@ -499,6 +562,8 @@ impl<'t> TextEdit<'t> {
 
         let prev_text = text.as_str().to_owned();
 
+        let ime_allowed = ime.unwrap_or(!password) && ui.ctx().options(|o| o.ime_allowed);
+
         let font_id = font_selection.resolve(ui.style());
         let row_height = ui.fonts(|f| f.row_height(&font_id));
         const MIN_WIDTH: f32 = 24.0; // Never make a [`TextEdit`] more narrow than this.
@@ -566,22 +631,46 @@ impl<'t> TextEdit<'t> {
 
         response.fake_primary_click = false; // Don't sent `OutputEvent::Clicked` when a user presses the space bar
 
+        let mut edits = vec![];
+        if let Some(char_index) = text_drag::remove_moved_text(ui.ctx(), id, text, &mut edits) {
+            // The user dragged our text to another `TextEdit`:
+            let ccursor_range = CCursorRange::one(CCursor::new(char_index));
+            state.cursor.set_char_range(Some(ccursor_range));
+            galley = layouter(ui, text.as_str(), wrap_width);
+            response.mark_changed();
+        }
+
         let text_clip_rect = rect;
         let painter = ui.painter_at(text_clip_rect.expand(1.0)); // expand to avoid clipping cursor
 
+        let mut is_over_selection = false;
         if interactive {
             if let Some(pointer_pos) = ui.ctx().pointer_interact_pos() {
                 if response.hovered() && text.is_mutable() {
                     ui.output_mut(|o| o.mutable_text_under_cursor = true);
                 }
 
-                // TODO(emilk): drag selected text to either move or clone (ctrl on windows, alt on mac)
-
                 let singleline_offset = vec2(state.singleline_offset, 0.0);
-                let cursor_at_pointer =
-                    galley.cursor_from_pos(pointer_pos - rect.min + singleline_offset);
-
-                if ui.visuals().text_cursor.preview
+                let pos_in_galley = pointer_pos - rect.min + singleline_offset;
+                let cursor_at_pointer = galley.cursor_from_pos(pos_in_galley);
+
+                let dragged_text = response.dnd_hover_payload::<TextDragPayload>();
+
+                if let Some(dragged_text) = &dragged_text {
+                    let copy = text_drag::is_copy(ui.ctx());
+                    if text.is_mutable()
+                        && dragged_text.can_drop_at(id, cursor_at_pointer.ccursor.index, copy)
+                    {
+                        // Where the dragged text would be dropped:
+                        let cursor_rect =
+                            cursor_rect(rect.min, &galley, &cursor_at_pointer, row_height);
+                        text_selection::visuals::paint_cursor_end(
+                            &painter,
+                            ui.visuals(),
+                            cursor_rect,
+                        );
+                    }
+                } else if ui.visuals().text_cursor.preview
                     && response.hovered()
                     && ui.input(|i| i.pointer.is_moving())
                 {
@@ -591,14 +680,86 @@ impl<'t> TextEdit<'t> {
                     text_selection::visuals::paint_cursor_end(&painter, ui.visuals(), cursor_rect);
                 }
 
+                // Selected text can be dragged somewhere else, unless it is a password:
+                is_over_selection = !password
+                    && response.hovered()
+                    && ui.memory(|mem| mem.has_focus(id))
+                    && state.cursor.range(&galley).is_some_and(|selection| {
+                        text_drag::is_over_selection(
+                            &galley,
+                            &selection,
+                            &cursor_at_pointer,
+                            pos_in_galley,
+                        )
+                    });
+                if ui.input(|i| i.pointer.any_pressed() && !i.modifiers.shift) && response.hovered()
+                {
+                    state.dragging_selection = is_over_selection;
+                }
+
+                let pressed = response.hovered() && ui.input(|i| i.pointer.any_pressed());
+                let add_cursor = pressed
+                    && !state.dragging_selection
+                    && ui.input(|i| i.modifiers.alt && !i.modifiers.shift)
+                    && ui.memory(|mem| mem.has_focus(id));
+
                 let is_being_dragged = ui.ctx().is_being_dragged(response.id);
-                let did_interact = state.cursor.pointer_interaction(
-                    ui,
-                    &response,
-                    cursor_at_pointer,
-                    &galley,
-                    is_being_dragged,
-                );
+                let did_interact = if add_cursor {
+                    // Alt+click adds another cursor (and Alt+drag another selection):
+                    state.add_char_range(CCursorRange::one(cursor_at_pointer.ccursor));
+                    true
+                } else if state.dragging_selection {
+                    if response.drag_started() {
+                        if let Some(selection) = state.cursor.range(&galley) {
+                            let range = selection.as_sorted_char_range();
+                            response.dnd_set_drag_payload(TextDragPayload {
+                                source: id,
+                                text: selection.slice_str(text.as_str()).to_owned(),
+                                range,
+                                movable: text.is_mutable(),
+                            });
+                        }
+                    } else if response.clicked() {
+                        // Clicking the selection without dragging it deselects it:
+                        state
+                            .cursor
+                            .set_range(Some(CursorRange::one(cursor_at_pointer)));
+                    }
+                    true
+                } else {
+                    state.cursor.pointer_interaction(
+                        ui,
+                        &response,
+                        cursor_at_pointer,
+                        &galley,
+                        is_being_dragged,
+                    )
+                };
+                if !ui.input(|i| i.pointer.any_down()) {
+                    state.dragging_selection = false;
+                }
+                if pressed && !add_cursor && !state.dragging_selection {
+                    state.clear_extra_char_ranges();
+                }
+
+                if text.is_mutable() {
+                    if let Some(dropped_text) = response.dnd_release_payload::<TextDragPayload>() {
+                        if let Some(dropped) = dropped_text.drop_into(
+                            ui.ctx(),
+                            id,
+                            text,
+                            cursor_at_pointer.ccursor.index,
+                            char_limit,
+                            &mut edits,
+                        ) {
+                            // Select the dropped text:
+                            state.cursor.set_char_range(Some(dropped));
+                            galley = layouter(ui, text.as_str(), wrap_width);
+                            response.mark_changed();
+                            ui.memory_mut(|mem| mem.request_focus(response.id));
+                        }
+                    }
+                }
 
                 if did_interact || response.clicked() {
                     ui.memory_mut(|mem| mem.request_focus(response.id));
@@ -607,7 +768,11 @@ impl<'t> TextEdit<'t> {
         }
 
         if interactive && response.hovered() {
-            ui.ctx().set_cursor_icon(CursorIcon::Text);
+            if is_over_selection || state.dragging_selection {
+                ui.ctx().set_cursor_icon(CursorIcon::Default);
+            } else {
+                ui.ctx().set_cursor_icon(CursorIcon::Text);
+            }
         }
 
         let mut cursor_range = None;
@@ -631,10 +796,12 @@ impl<'t> TextEdit<'t> {
                 wrap_width,
                 multiline,
                 password,
+                ime_allowed,
                 default_cursor_range,
                 char_limit,
                 event_filter,
                 return_key,
+                &mut edits,
             );
 
             if changed {
@@ -713,11 +880,28 @@ impl<'t> TextEdit<'t> {
 
             let has_focus = ui.memory(|mem| mem.has_focus(id));
 
+            // The other cursors, if there are several:
+            let extra_cursor_ranges: Vec<CursorRange> = if has_focus {
+                state
+                    .extra_char_ranges()
+                    .iter()
+                    .map(|range| CursorRange {
+                        primary: galley.from_ccursor(range.primary),
+                        secondary: galley.from_ccursor(range.secondary),
+                    })
+                    .collect()
+            } else {
+                vec![]
+            };
+
             if has_focus {
                 if let Some(cursor_range) = state.cursor.range(&galley) {
                     // Add text selection rectangles to the galley:
                     paint_text_selection(&mut galley, ui.visuals(), &cursor_range, None);
                 }
+                for cursor_range in &extra_cursor_ranges {
+                    paint_text_selection(&mut galley, ui.visuals(), cursor_range, None);
+                }
             }
 
             painter.galley(galley_pos, galley.clone(), text_color);
@@ -751,26 +935,39 @@ impl<'t> TextEdit<'t> {
                                 primary_cursor_rect,
                                 now - state.last_edit_time,
                             );
+                            for extra in &extra_cursor_ranges {
+                                let cursor_rect =
+                                    cursor_rect(galley_pos, &galley, &extra.primary, row_height);
+                                text_selection::visuals::paint_text_cursor(
+                                    ui,
+                                    &painter,
+                                    cursor_rect,
+                                    now - state.last_edit_time,
+                                );
+                            }
                         }
 
                         // Set IME output (in screen coords) when text is editable and visible
-                        let transform = ui
-                            .memory(|m| m.layer_transforms.get(&ui.layer_id()).copied())
-                            .unwrap_or_default();
-
-                        ui.ctx().output_mut(|o| {
-                            o.ime = Some(crate::output::IMEOutput {
-                                rect: transform * rect,
-                                cursor_rect: transform * primary_cursor_rect,
+                        if ime_allowed {
+                            let transform = ui
+                                .memory(|m| m.layer_transforms.get(&ui.layer_id()).copied())
+                                .unwrap_or_default();
+
+                            ui.ctx().output_mut(|o| {
+                                o.ime = Some(crate::output::IMEOutput {
+                                    rect: transform * rect,
+                                    cursor_rect: transform * primary_cursor_rect,
+                                });
                             });
-                        });
+                        }
                     }
                 }
             }
         }
 
-        // Ensures correct IME behavior when the text input area gains or loses focus.
-        if state.ime_enabled && (response.gained_focus() || response.lost_focus()) {
+        // Ensures correct IME behavior when the text input area gains or loses focus,
+        // or the IME is turned off in the middle of composing.
+        if state.ime_enabled && (response.gained_focus() || response.lost_focus() || !ime_allowed) {
             state.ime_enabled = false;
             if let Some(mut ccursor_range) = state.cursor.char_range() {
                 ccursor_range.secondary.index = ccursor_range.primary.index;
@@ -836,6 +1033,7 @@ impl<'t> TextEdit<'t> {
             text_clip_rect,
             state,
             cursor_range,
+            edits,
         }
     }
 }
@@ -857,7 +1055,7 @@ fn mask_if_password(is_password: bool, text: &str) -> String {
 // ----------------------------------------------------------------------------
 
 /// Check for (keyboard) events to edit the cursor and/or text.
-#[allow(clippy::too_many_arguments)]
+#[allow(clippy::too_many_arguments, clippy::fn_params_excessive_bools)]
 fn events(
     ui: &crate::Ui,
     state: &mut TextEditState,
@@ -868,10 +1066,12 @@ fn events(
     wrap_width: f32,
     multiline: bool,
     password: bool,
+    ime_allowed: bool,
     default_cursor_range: CursorRange,
     char_limit: usize,
     event_filter: EventFilter,
     return_key: Option<KeyboardShortcut>,
+    edits: &mut Vec<TextEditOp>,
 ) -> (bool, CursorRange) {
     let os = ui.ctx().os();
 
@@ -894,13 +1094,75 @@ fn events(
 
     let mut events = ui.input(|i| i.filtered_events(&event_filter));
 
+    if !ime_allowed {
+        events.retain(|e| !matches!(e, Event::Ime(_)));
+    }
+
     if state.ime_enabled {
         remove_ime_incompatible_events(&mut events);
         // Process IME events first:
         events.sort_by_key(|e| !matches!(e, Event::Ime(_)));
     }
 
+    let mut extra_ranges = std::mem::take(&mut state.extra_ccursor_ranges);
+
     for event in &events {
+        // Record the changes to the text, for `TextEditOutput::edits`:
+        let text = &mut RecordingTextBuffer {
+            inner: &mut *text,
+            source: match event {
+                Event::Cut => TextEditSource::Cut,
+                Event::Paste(_) => TextEditSource::Paste,
+                Event::Key { .. } => TextEditSource::Deletion,
+                _ => TextEditSource::Typing,
+            },
+            ops: &mut *edits,
+        };
+
+        let select_next_occurrence = matches!(
+            event,
+            Event::Key { key: Key::D, pressed: true, modifiers, .. }
+                if modifiers.matches_exact(Modifiers::COMMAND)
+        );
+
+        if select_next_occurrence || !extra_ranges.is_empty() {
+            let mut ranges = vec![cursor_range.as_ccursor_range()];
+            ranges.append(&mut extra_ranges);
+
+            let changed = if select_next_occurrence {
+                if !password {
+                    multi_cursor::select_next_occurrence(text.as_str(), &mut ranges);
+                }
+                Some(false)
+            } else {
+                multi_cursor::MultiCursorEdit {
+                    ui,
+                    os,
+                    id,
+                    galley,
+                    multiline,
+                    password,
+                    char_limit,
+                    return_key,
+                }
+                .on_event(event, text, &mut ranges)
+            };
+
+            if let Some(changed) = changed {
+                if changed {
+                    any_change = true;
+                    *galley = layouter(ui, text.as_str(), wrap_width);
+                }
+                cursor_range = CursorRange {
+                    primary: galley.from_ccursor(ranges[0].primary),
+                    secondary: galley.from_ccursor(ranges[0].secondary),
+                };
+                extra_ranges = ranges.split_off(1);
+                continue;
+            }
+            // E.g. undo: only with the primary cursor.
+        }
+
         let did_mutate_text = match event {
             // First handle events that only changes the selection cursor, not the text:
             event if cursor_range.on_event(os, event, galley, id) => None,
@@ -950,6 +1212,7 @@ fn events(
                 modifiers,
                 ..
             } if multiline => {
+                text.source = TextEditSource::Typing;
                 let mut ccursor = text.delete_selected(&cursor_range);
                 if modifiers.shift {
                     // TODO(emilk): support removing indentation over a selection?
@@ -969,6 +1232,7 @@ fn events(
             }) =>
             {
                 if multiline {
+                    text.source = TextEditSource::Typing;
                     let mut ccursor = text.delete_selected(&cursor_range);
                     text.insert_text_at(&mut ccursor, "\n", char_limit);
                     // TODO(emilk): if code editor, auto-indent by same leading tabs, + one if the lines end on an opening bracket
@@ -993,6 +1257,7 @@ fn events(
                     .lock()
                     .redo(&(cursor_range.as_ccursor_range(), text.as_str().to_owned()))
                 {
+                    text.source = TextEditSource::Redo;
                     text.replace_with(redo_txt);
                     Some(*redo_ccursor_range)
                 } else {
@@ -1011,6 +1276,7 @@ fn events(
                     .lock()
                     .undo(&(cursor_range.as_ccursor_range(), text.as_str().to_owned()))
                 {
+                    text.source = TextEditSource::Undo;
                     text.replace_with(undo_txt);
                     Some(*undo_ccursor_range)
                 } else {
@@ -1089,6 +1355,7 @@ fn events(
     }
 
     state.cursor.set_range(Some(cursor_range));
+    state.extra_ccursor_ranges = extra_ranges;
 
     state.undoer.lock().feed_state(
         ui.input(|i| i.time),
@@ -1122,7 +1389,7 @@ fn remove_ime_incompatible_events(events: &mut Vec<Event>) {
 // ----------------------------------------------------------------------------
 
 /// Returns `Some(new_cursor)` if we did mutate `text`.
-fn check_for_mutating_key_press(
+pub(super) fn check_for_mutating_key_press(
     os: OperatingSystem,
     cursor_range: &CursorRange,
     text: &mut dyn TextBuffer,
@@ -0,0 +1,178 @@
+use std::ops::Range;
+
+use super::TextBuffer;
+
+/// What caused a [`TextEditOp`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum TextEditSource {
+    /// Typed text, including IME input, newlines and indentation.
+    Typing,
+
+    /// Backspace, delete, and the other keyboard shortcuts that delete text.
+    Deletion,
+
+    /// Pasting from the clipboard.
+    Paste,
+
+    /// Cutting to the clipboard.
+    Cut,
+
+    /// Dragging selected text to another place, in the same or another [`crate::TextEdit`].
+    DragAndDrop,
+
+    /// Undoing a previous edit.
+    Undo,
+
+    /// Redoing an undone edit.
+    Redo,
+
+    /// Made somewhere else, e.g. by a collaborator.
+    Remote,
+}
+
+/// A single change to the text of a [`crate::TextEdit`]:
+/// the characters in [`Self::range`] were replaced with [`Self::text`].
+///
+/// A [`crate::TextEdit`] reports the changes the user makes in [`super::TextEditOutput::edits`],
+/// and you can apply changes made elsewhere with [`crate::TextEdit::apply_remote_edits`].
+/// Together this lets you build collaborative editors, e.g. based on CRDTs or operational transforms.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct TextEditOp {
+    /// The replaced characters, as character (not byte) indices into the text before this change.
+    ///
+    /// Empty for insertions.
+    pub range: Range<usize>,
+
+    /// The text that was inserted at `range.start`.
+    ///
+    /// Empty for deletions.
+    pub text: String,
+
+    /// What caused this change.
+    pub source: TextEditSource,
+}
+
+impl TextEditOp {
+    /// Insert `text` at the given character index.
+    pub fn insert(char_index: usize, text: impl Into<String>, source: TextEditSource) -> Self {
+        Self {
+            range: char_index..char_index,
+            text: text.into(),
+            source,
+        }
+    }
+
+    /// Delete the characters in the given range.
+    pub fn delete(char_range: Range<usize>, source: TextEditSource) -> Self {
+        Self {
+            range: char_range,
+            text: String::new(),
+            source,
+        }
+    }
+
+    /// Apply this change to the given text.
+    pub fn apply(&self, text: &mut dyn TextBuffer) {
+        if !self.range.is_empty() {
+            text.delete_char_range(self.range.clone());
+        }
+        if !self.text.is_empty() {
+            text.insert_text(&self.text, self.range.start);
+        }
+    }
+
+    /// Where the character at `char_index` ends up after this change.
+    ///
+    /// Indices inside the replaced range move to its start.
+    /// Text inserted exactly at `char_index` ends up after it.
+    pub fn transform_index(&self, char_index: usize) -> usize {
+        if char_index <= self.range.start {
+            char_index
+        } else if char_index < self.range.end {
+            self.range.start
+        } else {
+            char_index - self.range.len() + self.text.chars().count()
+        }
+    }
+}
+
+/// Wraps a [`TextBuffer`], recording every change made to it as a [`TextEditOp`].
+pub(crate) struct RecordingTextBuffer<'a> {
+    pub inner: &'a mut dyn TextBuffer,
+    pub source: TextEditSource,
+    pub ops: &'a mut Vec<TextEditOp>,
+}
+
+impl TextBuffer for RecordingTextBuffer<'_> {
+    fn is_mutable(&self) -> bool {
+        self.inner.is_mutable()
+    }
+
+    fn as_str(&self) -> &str {
+        self.inner.as_str()
+    }
+
+    fn insert_text(&mut self, text: &str, char_index: usize) -> usize {
+        let num_inserted = self.inner.insert_text(text, char_index);
+        if 0 < num_inserted {
+            let text = text.chars().take(num_inserted).collect::<String>();
+            self.ops
+                .push(TextEditOp::insert(char_index, text, self.source));
+        }
+        num_inserted
+    }
+
+    fn delete_char_range(&mut self, char_range: Range<usize>) {
+        let num_chars = self.inner.as_str().chars().count();
+        let clamped = char_range.start.min(num_chars)..char_range.end.min(num_chars);
+        self.inner.delete_char_range(char_range);
+        if !clamped.is_empty() && self.inner.is_mutable() {
+            self.ops.push(TextEditOp::delete(clamped, self.source));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recorded_ops_replay() {
+        let mut text = String::from("Hello wörld");
+        let mut ops = vec![];
+        {
+            let mut recorder = RecordingTextBuffer {
+                inner: &mut text,
+                source: TextEditSource::Typing,
+                ops: &mut ops,
+            };
+            recorder.insert_text("!", 11);
+            recorder.delete_char_range(6..11);
+            recorder.insert_text("egui", 6);
+            recorder.replace_with("Hallo");
+        }
+        assert_eq!(text, "Hallo");
+
+        let mut replayed = String::from("Hello wörld");
+        for op in &ops {
+            op.apply(&mut replayed);
+        }
+        assert_eq!(replayed, text);
+    }
+
+    #[test]
+    fn transform_index() {
+        let insert = TextEditOp::insert(2, "abc", TextEditSource::Remote);
+        assert_eq!(insert.transform_index(1), 1);
+        assert_eq!(insert.transform_index(2), 2);
+        assert_eq!(insert.transform_index(3), 6);
+
+        let delete = TextEditOp::delete(2..5, TextEditSource::Remote);
+        assert_eq!(delete.transform_index(2), 2);
+        assert_eq!(delete.transform_index(4), 2);
+        assert_eq!(delete.transform_index(5), 2);
+        assert_eq!(delete.transform_index(7), 4);
+    }
+}
@@ -1,9 +1,16 @@
 mod builder;
+mod edit_op;
+mod multi_cursor;
 mod output;
 mod state;
 mod text_buffer;
+mod text_drag;
 
 pub use {
-    crate::text_selection::TextCursorState, builder::TextEdit, output::TextEditOutput,
-    state::TextEditState, text_buffer::TextBuffer,
+    crate::text_selection::TextCursorState,
+    builder::TextEdit,
+    edit_op::{TextEditOp, TextEditSource},
+    output::TextEditOutput,
+    state::TextEditState,
+    text_buffer::TextBuffer,
 };
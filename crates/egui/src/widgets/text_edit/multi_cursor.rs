@@ -0,0 +1,358 @@
+//! Editing with several cursors at once, see [`super::TextEditState::add_char_range`].
+
+use epaint::text::{cursor::CCursor, Galley};
+
+use crate::{
+    os::OperatingSystem,
+    text_selection::{
+        text_cursor_state::{byte_index_from_char_index, select_word_at, slice_char_range},
+        CCursorRange, CursorRange,
+    },
+    Event, Id, ImeEvent, Key, KeyboardShortcut, Modifiers, TextBuffer, Ui,
+};
+
+use super::{builder::check_for_mutating_key_press, edit_op::RecordingTextBuffer, TextEditSource};
+
+/// What a [`crate::TextEdit`] needs to know to handle an event with several cursors.
+pub(super) struct MultiCursorEdit<'a> {
+    pub ui: &'a Ui,
+    pub os: OperatingSystem,
+    pub id: Id,
+    pub galley: &'a Galley,
+    pub multiline: bool,
+    pub password: bool,
+    pub char_limit: usize,
+    pub return_key: Option<KeyboardShortcut>,
+}
+
+impl MultiCursorEdit<'_> {
+    /// Handle the event at all cursors in `ranges`, the primary cursor first.
+    ///
+    /// Returns whether the text changed, or `None` for events that only make sense
+    /// with a single cursor (undo, redo and IME), which should be handled
+    /// with just the primary cursor.
+    pub fn on_event(
+        &self,
+        event: &Event,
+        text: &mut RecordingTextBuffer<'_>,
+        ranges: &mut Vec<CCursorRange>,
+    ) -> Option<bool> {
+        let changed = match event {
+            Event::Ime(ImeEvent::Enabled | ImeEvent::Preedit(_) | ImeEvent::Commit(_)) => {
+                return None
+            }
+            Event::Key {
+                key: Key::Y | Key::Z,
+                pressed: true,
+                modifiers,
+                ..
+            } if modifiers.matches_logically(Modifiers::COMMAND) => return None,
+
+            event if self.move_cursors(event, ranges) => false,
+
+            Event::Copy | Event::Cut => {
+                if !self.password {
+                    let selected = selected_texts(text.as_str(), ranges);
+                    if !selected.is_empty() {
+                        self.ui.ctx().copy_text(selected.join("\n"));
+                    }
+                }
+                matches!(event, Event::Cut)
+                    && edit_each(text, ranges, |text, _, range| {
+                        CCursorRange::one(delete_selected(text, range))
+                    })
+            }
+            Event::Paste(text_to_insert) if !text_to_insert.is_empty() => {
+                // One line per cursor, if that is what was copied:
+                let lines: Vec<&str> = text_to_insert.lines().collect();
+                let one_line_each = 1 < lines.len() && lines.len() == ranges.len();
+                edit_each(text, ranges, |text, i, range| {
+                    let text_to_insert = if one_line_each {
+                        lines[i]
+                    } else {
+                        text_to_insert
+                    };
+                    self.insert(text, range, text_to_insert)
+                })
+            }
+            Event::Text(text_to_insert)
+                if !text_to_insert.is_empty()
+                    && text_to_insert != "\n"
+                    && text_to_insert != "\r" =>
+            {
+                edit_each(text, ranges, |text, _, range| {
+                    self.insert(text, range, text_to_insert)
+                })
+            }
+            Event::Key {
+                key: Key::Tab,
+                pressed: true,
+                modifiers,
+                ..
+            } if self.multiline => {
+                text.source = TextEditSource::Typing;
+                edit_each(text, ranges, |text, _, range| {
+                    if modifiers.shift {
+                        let mut ccursor = delete_selected(text, range);
+                        text.decrease_indentation(&mut ccursor);
+                        CCursorRange::one(ccursor)
+                    } else {
+                        self.insert(text, range, "\t")
+                    }
+                })
+            }
+            Event::Key {
+                key,
+                pressed: true,
+                modifiers,
+                ..
+            } if self.return_key.is_some_and(|return_key| {
+                *key == return_key.logical_key && modifiers.matches_logically(return_key.modifiers)
+            }) =>
+            {
+                if self.multiline {
+                    text.source = TextEditSource::Typing;
+                    edit_each(text, ranges, |text, _, range| {
+                        self.insert(text, range, "\n")
+                    })
+                } else {
+                    self.ui.memory_mut(|mem| mem.surrender_focus(self.id)); // End input with enter
+                    false
+                }
+            }
+            Event::Key {
+                key,
+                pressed: true,
+                modifiers,
+                ..
+            } => edit_each(text, ranges, |text, _, range| {
+                let cursor_range = CursorRange {
+                    primary: self.galley.from_ccursor(range.primary),
+                    secondary: self.galley.from_ccursor(range.secondary),
+                };
+                check_for_mutating_key_press(
+                    self.os,
+                    &cursor_range,
+                    text,
+                    self.galley,
+                    modifiers,
+                    *key,
+                )
+                .unwrap_or(range)
+            }),
+
+            _ => false,
+        };
+
+        merge_overlapping(ranges);
+        Some(changed)
+    }
+
+    /// Move all cursors, e.g. with the arrow keys.
+    ///
+    /// Returns `false` if the event doesn't move cursors.
+    fn move_cursors(&self, event: &Event, ranges: &mut [CCursorRange]) -> bool {
+        let mut moved = false;
+        for range in ranges {
+            let mut cursor_range = CursorRange {
+                primary: self.galley.from_ccursor(range.primary),
+                secondary: self.galley.from_ccursor(range.secondary),
+            };
+            if cursor_range.on_event(self.os, event, self.galley, self.id) {
+                *range = cursor_range.as_ccursor_range();
+                moved = true;
+            }
+        }
+        moved
+    }
+
+    fn insert(
+        &self,
+        text: &mut dyn TextBuffer,
+        range: CCursorRange,
+        text_to_insert: &str,
+    ) -> CCursorRange {
+        let mut ccursor = delete_selected(text, range);
+        text.insert_text_at(&mut ccursor, text_to_insert, self.char_limit);
+        CCursorRange::one(ccursor)
+    }
+}
+
+/// The selected texts, in the order they appear in the text.
+fn selected_texts<'t>(text: &'t str, ranges: &[CCursorRange]) -> Vec<&'t str> {
+    let mut sorted: Vec<[CCursor; 2]> = ranges.iter().map(|r| r.sorted()).collect();
+    sorted.sort_by_key(|[min, _]| min.index);
+    sorted
+        .iter()
+        .filter(|[min, max]| min.index < max.index)
+        .map(|[min, max]| slice_char_range(text, min.index..max.index))
+        .collect()
+}
+
+fn delete_selected(text: &mut dyn TextBuffer, range: CCursorRange) -> CCursor {
+    text.delete_selected_ccursor_range(range.sorted())
+}
+
+/// Make the same kind of edit at every cursor, from the last one in the text to the first one,
+/// and move the cursors to where `edit` says.
+///
+/// `edit` gets the index of the cursor in text order.
+/// Returns `true` if the text changed.
+fn edit_each(
+    text: &mut RecordingTextBuffer<'_>,
+    ranges: &mut [CCursorRange],
+    mut edit: impl FnMut(&mut dyn TextBuffer, usize, CCursorRange) -> CCursorRange,
+) -> bool {
+    let mut order: Vec<usize> = (0..ranges.len()).collect();
+    order.sort_by_key(|&i| std::cmp::Reverse(ranges[i].sorted()[0].index));
+
+    let num_ops_before = text.ops.len();
+    for (n, &i) in order.iter().enumerate() {
+        let num_ops = text.ops.len();
+        let new_range = edit(&mut *text, ranges.len() - 1 - n, ranges[i]);
+
+        // The cursors after this one in the text have moved:
+        for op in &text.ops[num_ops..] {
+            for &j in &order[..n] {
+                ranges[j].primary.index = op.transform_index(ranges[j].primary.index);
+                ranges[j].secondary.index = op.transform_index(ranges[j].secondary.index);
+            }
+        }
+        ranges[i] = new_range;
+    }
+    num_ops_before < text.ops.len()
+}
+
+/// Merge cursors that ended up touching each other, keeping the primary one first.
+pub(super) fn merge_overlapping(ranges: &mut Vec<CCursorRange>) {
+    let mut i = 0;
+    while i < ranges.len() {
+        let [min_i, max_i] = ranges[i].sorted();
+        let overlapping = (i + 1..ranges.len()).find(|&j| {
+            let [min_j, max_j] = ranges[j].sorted();
+            let same_caret = min_i.index == min_j.index && max_i.index == max_j.index;
+            same_caret || (min_i.index < max_j.index && min_j.index < max_i.index)
+        });
+        if let Some(j) = overlapping {
+            let [min_j, max_j] = ranges[j].sorted();
+            let min = if min_j.index < min_i.index {
+                min_j
+            } else {
+                min_i
+            };
+            let max = if max_i.index < max_j.index {
+                max_j
+            } else {
+                max_i
+            };
+            ranges[i] = if ranges[i].is_sorted() {
+                CCursorRange {
+                    primary: min,
+                    secondary: max,
+                }
+            } else {
+                CCursorRange::two(min, max)
+            };
+            ranges.remove(j);
+        } else {
+            i += 1;
+        }
+    }
+}
+
+/// Select the next occurrence of the text selected by the primary cursor, as a new primary cursor.
+///
+/// If nothing is selected, the word at the primary cursor is selected instead.
+pub(super) fn select_next_occurrence(text: &str, ranges: &mut Vec<CCursorRange>) {
+    let Some(primary) = ranges.first().copied() else {
+        return;
+    };
+    let [min, max] = primary.sorted();
+    if min.index == max.index {
+        let word = select_word_at(text, min);
+        let [word_min, word_max] = word.sorted();
+        if word_min.index < word_max.index {
+            ranges[0] = word;
+        }
+        return;
+    }
+
+    let needle = slice_char_range(text, min.index..max.index);
+    let needle_len = max.index - min.index;
+    let search_from = byte_index_from_char_index(text, max.index);
+    let candidates = text[search_from..]
+        .match_indices(needle)
+        .map(|(byte, _)| search_from + byte)
+        .chain(
+            text[..search_from]
+                .match_indices(needle)
+                .map(|(byte, _)| byte),
+        );
+    for byte_index in candidates {
+        let start = text[..byte_index].chars().count();
+        let already_selected = ranges.iter().any(|range| {
+            let [min, max] = range.sorted();
+            min.index == start && max.index == start + needle_len
+        });
+        if !already_selected {
+            ranges.insert(
+                0,
+                CCursorRange::two(CCursor::new(start), CCursor::new(start + needle_len)),
+            );
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::TextEditOp;
+    use super::*;
+
+    #[test]
+    fn type_at_every_cursor() {
+        let mut text = String::from("one two one");
+        let mut ranges = vec![CCursorRange::one(CCursor::new(0))];
+        select_next_occurrence(&text, &mut ranges);
+        select_next_occurrence(&text, &mut ranges);
+        select_next_occurrence(&text, &mut ranges);
+        assert_eq!(ranges.len(), 2, "Both \"one\" are selected");
+
+        let mut ops: Vec<TextEditOp> = vec![];
+        let mut recording = RecordingTextBuffer {
+            inner: &mut text,
+            source: TextEditSource::Typing,
+            ops: &mut ops,
+        };
+        let changed = edit_each(&mut recording, &mut ranges, |text, _, range| {
+            let mut ccursor = delete_selected(text, range);
+            text.insert_text_at(&mut ccursor, "three", usize::MAX);
+            CCursorRange::one(ccursor)
+        });
+        assert!(changed);
+        assert_eq!(text, "three two three");
+        let mut carets: Vec<usize> = ranges.iter().map(|r| r.primary.index).collect();
+        carets.sort_unstable();
+        assert_eq!(carets, vec![5, 15]);
+
+        let mut replayed = String::from("one two one");
+        for op in &ops {
+            op.apply(&mut replayed);
+        }
+        assert_eq!(replayed, text);
+    }
+
+    #[test]
+    fn merge_touching_cursors() {
+        let mut ranges = vec![
+            CCursorRange::one(CCursor::new(3)),
+            CCursorRange::two(CCursor::new(1), CCursor::new(5)),
+            CCursorRange::one(CCursor::new(3)),
+            CCursorRange::one(CCursor::new(8)),
+        ];
+        merge_overlapping(&mut ranges);
+        assert_eq!(ranges.len(), 2);
+        assert_eq!(ranges[0].sorted().map(|c| c.index), [1, 5]);
+        assert_eq!(ranges[1].primary.index, 8);
+    }
+}
@@ -21,6 +21,11 @@ pub struct TextEditOutput {
 
     /// Where the text cursor is.
     pub cursor_range: Option<CursorRange>,
+
+    /// The changes the user made to the text this frame, in the order they were made.
+    ///
+    /// Each change is relative to the text after the previous one.
+    pub edits: Vec<super::TextEditOp>,
 }
 
 impl TextEditOutput {
@@ -7,6 +7,8 @@ use crate::{
     Context, Galley, Id,
 };
 
+use super::TextEditOp;
+
 pub type TextEditUndoer = crate::util::undoer::Undoer<(CCursorRange, String)>;
 
 /// The text edit state stored between frames.
@@ -37,6 +39,12 @@ pub struct TextEditState {
     /// Controls the text selection.
     pub cursor: TextCursorState,
 
+    /// More cursors, for editing in several places at once.
+    ///
+    /// See [`Self::add_char_range`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) extra_ccursor_ranges: Vec<CCursorRange>,
+
     /// Wrapped in Arc for cheaper clones.
     #[cfg_attr(feature = "serde", serde(skip))]
     pub(crate) undoer: Arc<Mutex<TextEditUndoer>>,
@@ -53,6 +61,10 @@ pub struct TextEditState {
     #[cfg_attr(feature = "serde", serde(skip))]
     pub(crate) singleline_offset: f32,
 
+    /// Did the user press the selected text, to drag it somewhere else?
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) dragging_selection: bool,
+
     /// When did the user last press a key?
     /// Used to pause the cursor animation when typing.
     #[cfg_attr(feature = "serde", serde(skip))]
@@ -97,6 +109,75 @@ impl TextEditState {
         self.set_undoer(TextEditUndoer::default());
     }
 
+    /// Move the cursor so it stays at the same place in the text after the given edits,
+    /// which were made by someone else.
+    ///
+    /// See also [`crate::TextEdit::apply_remote_edits`].
+    pub fn transform_cursor(&mut self, edits: &[TextEditOp]) {
+        let transform = |range: &mut CCursorRange| {
+            for edit in edits {
+                range.primary.index = edit.transform_index(range.primary.index);
+                range.secondary.index = edit.transform_index(range.secondary.index);
+            }
+        };
+        if let Some(mut range) = self.cursor.char_range() {
+            transform(&mut range);
+            self.cursor.set_char_range(Some(range));
+        }
+        for range in &mut self.extra_ccursor_ranges {
+            transform(range);
+        }
+    }
+
+    /// The cursors other than the primary one in [`Self::cursor`], if there are several.
+    pub fn extra_char_ranges(&self) -> &[CCursorRange] {
+        &self.extra_ccursor_ranges
+    }
+
+    /// Set the cursors other than the primary one in [`Self::cursor`].
+    pub fn set_extra_char_ranges(&mut self, ccursor_ranges: Vec<CCursorRange>) {
+        self.extra_ccursor_ranges = ccursor_ranges;
+    }
+
+    /// Add another cursor (or selection), which becomes the primary one.
+    ///
+    /// Typing, deleting, pasting etc. then happen at all cursors at the same time,
+    /// like when the user Alt-clicks, or presses Ctrl/Cmd+D to select the next occurrence.
+    /// Clicking without Alt goes back to a single cursor.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// # let mut text = String::from("let x = x + 1;");
+    /// use egui::text::{CCursor, CCursorRange};
+    ///
+    /// let mut output = egui::TextEdit::singleline(&mut text).show(ui);
+    /// output.state.cursor.set_char_range(Some(CCursorRange::one(CCursor::new(4))));
+    /// output.state.add_char_range(CCursorRange::one(CCursor::new(8)));
+    /// assert_eq!(output.state.char_ranges().len(), 2);
+    /// output.state.store(ui.ctx(), output.response.id);
+    /// # });
+    /// ```
+    pub fn add_char_range(&mut self, ccursor_range: CCursorRange) {
+        if let Some(primary) = self.cursor.char_range() {
+            self.extra_ccursor_ranges.insert(0, primary);
+        }
+        self.cursor.set_char_range(Some(ccursor_range));
+    }
+
+    /// All cursors, the primary one first.
+    pub fn char_ranges(&self) -> Vec<CCursorRange> {
+        self.cursor
+            .char_range()
+            .into_iter()
+            .chain(self.extra_ccursor_ranges.iter().copied())
+            .collect()
+    }
+
+    /// Go back to only the primary cursor.
+    pub fn clear_extra_char_ranges(&mut self) {
+        self.extra_ccursor_ranges.clear();
+    }
+
     #[deprecated = "Use `self.cursor.range` instead"]
     pub fn cursor_range(&self, galley: &Galley) -> Option<CursorRange> {
         self.cursor.range(galley)
@@ -0,0 +1,212 @@
+//! Dragging selected text within and between [`crate::TextEdit`]s.
+
+use std::ops::Range;
+
+use epaint::text::{
+    cursor::{CCursor, Cursor},
+    Galley,
+};
+
+use crate::{
+    os::OperatingSystem,
+    text_selection::{text_cursor_state::slice_char_range, CCursorRange, CursorRange},
+    Context, Id, TextBuffer, Vec2,
+};
+
+use super::{edit_op::RecordingTextBuffer, TextEditOp, TextEditSource};
+
+/// The selected text of a [`crate::TextEdit`], being dragged with [`crate::DragAndDrop`].
+pub(crate) struct TextDragPayload {
+    /// The [`crate::TextEdit`] the text is dragged from.
+    pub source: Id,
+
+    /// The dragged characters in the source.
+    pub range: Range<usize>,
+
+    pub text: String,
+
+    /// Can the text be removed from the source (i.e. is the source editable)?
+    pub movable: bool,
+}
+
+/// Stored in the temporary data of the source [`crate::TextEdit`]
+/// when its text was moved to another one, so that it can remove it.
+#[derive(Clone, Default)]
+struct TextMovedAway {
+    range: Range<usize>,
+    text: String,
+}
+
+/// Is the pointer over the selected text, so that pressing it starts dragging the text
+/// rather than a new selection?
+///
+/// `pos_in_galley` is the pointer position relative to the galley,
+/// and `cursor_at_pointer` the cursor closest to it.
+pub(crate) fn is_over_selection(
+    galley: &Galley,
+    selection: &CursorRange,
+    cursor_at_pointer: &Cursor,
+    pos_in_galley: Vec2,
+) -> bool {
+    let selected = selection.as_sorted_char_range();
+    if selected.is_empty() {
+        return false;
+    }
+    // The cursor is at the closest character boundary, to the left or right of the pointer:
+    let index = cursor_at_pointer.ccursor.index;
+    let cursor_rect = galley.pos_from_cursor(cursor_at_pointer);
+    let char_under_pointer = if cursor_rect.min.x <= pos_in_galley.x {
+        index
+    } else {
+        index.saturating_sub(1)
+    };
+    let on_row = cursor_rect.min.y <= pos_in_galley.y && pos_in_galley.y <= cursor_rect.max.y;
+    on_row && selected.contains(&char_under_pointer)
+}
+
+/// Should dropping the text copy it rather than move it?
+///
+/// The modifier is Alt on Mac, and Ctrl elsewhere.
+pub(crate) fn is_copy(ctx: &Context) -> bool {
+    let is_mac = matches!(ctx.os(), OperatingSystem::Mac | OperatingSystem::IOS);
+    ctx.input(|i| {
+        if is_mac {
+            i.modifiers.alt
+        } else {
+            i.modifiers.ctrl
+        }
+    })
+}
+
+impl TextDragPayload {
+    /// Would dropping the text at `char_index` of the [`crate::TextEdit`] `id` change anything?
+    pub fn can_drop_at(&self, id: Id, char_index: usize, copy: bool) -> bool {
+        copy || !self.movable || self.source != id || !self.is_in_range(char_index)
+    }
+
+    fn is_in_range(&self, char_index: usize) -> bool {
+        self.range.start <= char_index && char_index <= self.range.end
+    }
+
+    /// Drop the text at `char_index` of `text`, which belongs to the [`crate::TextEdit`] `id`.
+    ///
+    /// Returns the range of the dropped text, or `None` if nothing changed.
+    pub fn drop_into(
+        &self,
+        ctx: &Context,
+        id: Id,
+        text: &mut dyn TextBuffer,
+        char_index: usize,
+        char_limit: usize,
+        edits: &mut Vec<TextEditOp>,
+    ) -> Option<CCursorRange> {
+        let copy = is_copy(ctx) || !self.movable;
+        if !self.can_drop_at(id, char_index, copy) {
+            return None;
+        }
+
+        let mut text = RecordingTextBuffer {
+            inner: text,
+            source: TextEditSource::DragAndDrop,
+            ops: edits,
+        };
+
+        let moved_within = self.source == id && !copy;
+        let mut ccursor = CCursor::new(char_index);
+        if moved_within {
+            // Removing the text first keeps the char limit happy:
+            if self.range.end <= char_index {
+                ccursor.index -= self.range.len();
+            }
+            text.delete_char_range(self.range.clone());
+        }
+
+        let start = ccursor;
+        text.insert_text_at(&mut ccursor, &self.text, char_limit);
+        if ccursor == start && !moved_within {
+            return None; // at the char limit
+        }
+
+        if self.source != id && !copy {
+            ctx.data_mut(|d| {
+                d.insert_temp(
+                    self.source,
+                    TextMovedAway {
+                        range: self.range.clone(),
+                        text: self.text.clone(),
+                    },
+                );
+            });
+            ctx.request_repaint();
+        }
+
+        Some(CCursorRange::two(start, ccursor))
+    }
+}
+
+/// Remove text that was moved from the [`crate::TextEdit`] `id` to another one.
+///
+/// Returns where the text was, or `None` if nothing was moved
+/// (or the text changed in the meantime).
+pub(crate) fn remove_moved_text(
+    ctx: &Context,
+    id: Id,
+    text: &mut dyn TextBuffer,
+    edits: &mut Vec<TextEditOp>,
+) -> Option<usize> {
+    let moved = ctx.data_mut(|d| d.remove_temp::<TextMovedAway>(id))?;
+    if slice_char_range(text.as_str(), moved.range.clone()) != moved.text {
+        return None;
+    }
+
+    RecordingTextBuffer {
+        inner: text,
+        source: TextEditSource::DragAndDrop,
+        ops: edits,
+    }
+    .delete_char_range(moved.range.clone());
+    Some(moved.range.start)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn move_text() {
+        let ctx = Context::default();
+        let (a, b) = (Id::new("a"), Id::new("b"));
+        let payload = TextDragPayload {
+            source: a,
+            range: 6..11,
+            text: "world".to_owned(),
+            movable: true,
+        };
+
+        // Within the same `TextEdit`:
+        let mut text = String::from("hello world");
+        let mut edits = vec![];
+        assert_eq!(
+            payload.drop_into(&ctx, a, &mut text, 8, usize::MAX, &mut edits),
+            None,
+            "Dropping the text onto itself does nothing"
+        );
+        let dropped = payload.drop_into(&ctx, a, &mut text, 0, usize::MAX, &mut edits);
+        assert_eq!(text, "worldhello ");
+        assert_eq!(
+            dropped,
+            Some(CCursorRange::two(CCursor::new(0), CCursor::new(5)))
+        );
+
+        // To another one:
+        let mut source = String::from("hello world");
+        let mut target = String::from("big ");
+        payload.drop_into(&ctx, b, &mut target, 4, usize::MAX, &mut edits);
+        assert_eq!(target, "big world");
+        assert_eq!(remove_moved_text(&ctx, a, &mut source, &mut edits), Some(6));
+        assert_eq!(source, "hello ");
+        assert!(edits
+            .iter()
+            .all(|edit| edit.source == TextEditSource::DragAndDrop));
+    }
+}
@@ -0,0 +1,176 @@
+//! Zooming a view about the mouse cursor, see [`ZoomHandler`].
+
+use emath::TSTransform;
+
+use crate::{Context, Id, NumExt as _, Pos2, Rangef, Response, Ui};
+
+/// Zooms a view about the mouse cursor, in the same way everywhere.
+///
+/// Handles ctrl+scroll (⌘+scroll on Mac), pinch gestures and animated zooms requested with
+/// [`Self::request_zoom`]. It is used by [`crate::containers::Scene`], and you can use it
+/// for your own zoomable views, e.g. image viewers or plots, so they all feel the same.
+///
+/// The view is described by a [`TSTransform`] from the coordinates of the view (e.g. of an image)
+/// to the screen, which is zoomed so that the point under the cursor stays put.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// # let mut to_screen = egui::emath::TSTransform::IDENTITY;
+/// let response = ui.allocate_response(ui.available_size(), egui::Sense::drag());
+/// if egui::ZoomHandler::new()
+///     .zoom_range(0.1..=10.0)
+///     .handle(ui, &response, &mut to_screen)
+/// {
+///     // Repaint the view with the new `to_screen`.
+/// }
+/// # });
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct ZoomHandler {
+    zoom_range: Rangef,
+    animation_time: f32,
+}
+
+impl Default for ZoomHandler {
+    fn default() -> Self {
+        Self {
+            zoom_range: Rangef::new(f32::EPSILON, f32::INFINITY),
+            animation_time: 0.15,
+        }
+    }
+}
+
+/// An animated zoom that is in progress, see [`ZoomHandler::request_zoom`].
+#[derive(Clone, Copy, Debug)]
+struct PendingZoom {
+    /// How much more to zoom.
+    factor: f32,
+
+    /// Where to zoom about, in screen coordinates.
+    anchor: Pos2,
+}
+
+impl ZoomHandler {
+    #[inline]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// How far the user can zoom out and in, as the scaling of the transform.
+    ///
+    /// Default: unlimited.
+    #[inline]
+    pub fn zoom_range(mut self, zoom_range: impl Into<Rangef>) -> Self {
+        self.zoom_range = zoom_range.into();
+        self
+    }
+
+    /// How long a zoom requested with [`Self::request_zoom`] takes, in seconds.
+    ///
+    /// Zooming with the mouse wheel is already smoothed by [`crate::InputState::zoom_delta`],
+    /// and pinch gestures should follow the fingers, so they aren't animated.
+    ///
+    /// Default: `0.15`.
+    #[inline]
+    pub fn animation_time(mut self, animation_time: f32) -> Self {
+        self.animation_time = animation_time;
+        self
+    }
+
+    /// Zoom the view of `response` by `factor` about `anchor` (in screen coordinates),
+    /// animated over the next few frames.
+    ///
+    /// Use this for e.g. zoom buttons or keyboard shortcuts.
+    /// It is applied by [`Self::handle`] with the same [`Response::id`].
+    pub fn request_zoom(ctx: &Context, id: Id, factor: f32, anchor: Pos2) {
+        ctx.data_mut(|data| {
+            let pending = data.get_temp_mut_or(
+                id.with("zoom"),
+                PendingZoom {
+                    factor: 1.0,
+                    anchor,
+                },
+            );
+            pending.factor *= factor;
+            pending.anchor = anchor;
+        });
+        ctx.request_repaint();
+    }
+
+    /// Zoom `to_screen` about the cursor if the user zooms while hovering `response`,
+    /// and apply any zoom requested with [`Self::request_zoom`].
+    ///
+    /// Returns `true` if `to_screen` changed.
+    pub fn handle(&self, ui: &Ui, response: &Response, to_screen: &mut TSTransform) -> bool {
+        let before = *to_screen;
+
+        let zoom_delta = ui.input(|i| i.zoom_delta());
+        if zoom_delta != 1.0 && response.contains_pointer() {
+            if let Some(pointer) = ui.ctx().pointer_latest_pos() {
+                *to_screen = self.zoom_about(*to_screen, pointer, zoom_delta);
+            }
+        }
+
+        let pending_id = response.id.with("zoom");
+        if let Some(mut pending) = ui.data(|data| data.get_temp::<PendingZoom>(pending_id)) {
+            let dt = ui.input(|i| i.stable_dt).at_most(0.1);
+            let t = if self.animation_time <= 0.0 {
+                1.0
+            } else {
+                1.0 - (-dt / (0.3 * self.animation_time)).exp()
+            };
+            let mut step = pending.factor.powf(t);
+            if (pending.factor.ln() - step.ln()).abs() < 1e-3 {
+                step = pending.factor; // Close enough: finish the animation
+            }
+            *to_screen = self.zoom_about(*to_screen, pending.anchor, step);
+            pending.factor /= step;
+
+            ui.data_mut(|data| {
+                if pending.factor == 1.0 {
+                    data.remove::<PendingZoom>(pending_id);
+                } else {
+                    data.insert_temp(pending_id, pending);
+                }
+            });
+            ui.ctx().request_repaint();
+        }
+
+        *to_screen != before
+    }
+
+    /// Zoom `to_screen` by `factor` so that `anchor` (in screen coordinates) stays put,
+    /// without going outside of [`Self::zoom_range`].
+    pub fn zoom_about(&self, to_screen: TSTransform, anchor: Pos2, factor: f32) -> TSTransform {
+        if !factor.is_finite() || factor <= 0.0 {
+            return to_screen;
+        }
+        let new_scaling = self.zoom_range.clamp(to_screen.scaling * factor);
+        let factor = new_scaling / to_screen.scaling;
+        TSTransform::from_translation(anchor.to_vec2())
+            * TSTransform::from_scaling(factor)
+            * TSTransform::from_translation(-anchor.to_vec2())
+            * to_screen
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zoom_about_anchor() {
+        let zoom = ZoomHandler::new().zoom_range(0.5..=4.0);
+        let to_screen = TSTransform::new(emath::vec2(10.0, 20.0), 2.0);
+        let anchor = Pos2::new(50.0, 60.0);
+        let anchor_in_view = to_screen.inverse() * anchor;
+
+        let zoomed = zoom.zoom_about(to_screen, anchor, 1.5);
+        assert_eq!(zoomed.scaling, 3.0);
+        assert!((zoomed * anchor_in_view - anchor).length() < 1e-4);
+
+        let clamped = zoom.zoom_about(zoomed, anchor, 10.0);
+        assert_eq!(clamped.scaling, 4.0, "Clamped to the zoom range");
+        assert!((clamped * anchor_in_view - anchor).length() < 1e-4);
+    }
+}
@@ -150,6 +150,127 @@ fn window_children() {
     assert_button_exists(&output, "Hide", window_id);
 }
 
+#[test]
+fn live_region_label() {
+    let status = "Saving…";
+
+    let output = accesskit_output_single_egui_frame(|ctx| {
+        CentralPanel::default().show(ctx, |ui| {
+            ui.label(egui::RichText::new(status).live_region());
+        });
+    });
+
+    let (_, label) = output
+        .nodes
+        .iter()
+        .find(|(_, node)| node.role() == Role::Label && node.name() == Some(status))
+        .expect("Label should exist in the accesskit output");
+    assert_eq!(label.live(), Some(accesskit::Live::Polite));
+
+    // The status text is exposed as text runs, so review cursors can move by word:
+    let (_, text_run) = output
+        .nodes
+        .iter()
+        .find(|(_, node)| node.role() == Role::InlineTextBox)
+        .expect("Live label should expose its text runs");
+    assert_eq!(text_run.value(), Some(status));
+    assert!(!text_run.word_lengths().is_empty());
+}
+
+#[test]
+fn assertive_progress_bar() {
+    let output = accesskit_output_single_egui_frame(|ctx| {
+        CentralPanel::default().show(ctx, |ui| {
+            ui.add(
+                egui::ProgressBar::new(0.5)
+                    .text(egui::RichText::new("Upload failed").live(egui::LiveRegion::Assertive)),
+            );
+        });
+    });
+
+    let (_, progress_bar) = output
+        .nodes
+        .iter()
+        .find(|(_, node)| node.role() == Role::ProgressIndicator)
+        .expect("Progress bar should exist in the accesskit output");
+    assert_eq!(progress_bar.live(), Some(accesskit::Live::Assertive));
+}
+
+#[test]
+fn text_edit_word_granularity() {
+    let output = accesskit_output_single_egui_frame(|ctx| {
+        CentralPanel::default().show(ctx, |ui| {
+            let mut text = "hello brave world".to_owned();
+            ui.text_edit_singleline(&mut text);
+        });
+    });
+
+    let (_, text_run) = output
+        .nodes
+        .iter()
+        .find(|(_, node)| node.role() == Role::InlineTextBox)
+        .expect("TextEdit should expose its text runs");
+    assert_eq!(text_run.value(), Some("hello brave world"));
+    assert_eq!(text_run.character_lengths().len(), 17);
+    assert_eq!(text_run.word_lengths(), &[6, 6, 5]);
+}
+
+#[test]
+fn keyboard_move_window() {
+    let ctx = Context::default();
+    ctx.style_mut(|style| style.animation_time = 0.0);
+    ctx.enable_accesskit();
+
+    let window_id = egui::Id::new("window");
+    let key = |key, modifiers| egui::Event::Key {
+        key,
+        physical_key: None,
+        scancode: None,
+        pressed: true,
+        repeat: false,
+        modifiers,
+    };
+    let run = |events: Vec<egui::Event>| {
+        let input = RawInput {
+            events,
+            ..Default::default()
+        };
+        let output = ctx.run(input, |ctx| {
+            Window::new("Window")
+                .id(window_id)
+                .default_pos([100.0, 100.0])
+                .show(ctx, |ui| ui.label("Content"));
+        });
+        let rect = ctx.memory(|mem| mem.area_rect(window_id)).unwrap();
+        (rect, output.platform_output.accesskit_update.unwrap())
+    };
+
+    // Let the window find its size:
+    run(vec![]);
+    let (start_rect, _) = run(vec![]);
+
+    egui::Area::start_keyboard_move(&ctx, window_id);
+    assert!(egui::Area::is_keyboard_moving(&ctx, window_id));
+    let (rect, output) = run(vec![key(egui::Key::ArrowRight, egui::Modifiers::NONE)]);
+    assert_eq!(rect.min, start_rect.min + egui::vec2(10.0, 0.0));
+
+    let (_, window) = output
+        .nodes
+        .iter()
+        .find(|(_, node)| node.role() == Role::Window && node.name() == Some("Window"))
+        .expect("Window should exist in the accesskit output");
+    assert_eq!(window.live(), Some(accesskit::Live::Polite));
+    assert!(window
+        .value()
+        .is_some_and(|value| value.starts_with("Position")));
+
+    // Escape puts it back:
+    run(vec![key(egui::Key::Escape, egui::Modifiers::NONE)]);
+    let (rect, _) = run(vec![]);
+    assert_eq!(rect.min, start_rect.min);
+    assert!(!egui::Area::is_keyboard_moving(&ctx, window_id));
+}
+
 fn accesskit_output_single_egui_frame(run_ui: impl FnMut(&Context)) -> TreeUpdate {
     let ctx = Context::default();
     // Disable animations, so we do not need to wait for animations to end to see the result.
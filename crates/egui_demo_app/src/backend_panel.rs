@@ -88,6 +88,13 @@ impl BackendPanel {
 
         self.frame_history.ui(ui);
 
+        if let Some(input_latency) = frame.info().input_latency {
+            ui.label(format!("Input latency: {:.1} ms", 1e3 * input_latency))
+                .on_hover_text(
+                    "From receiving input until the frame that handled it was presented.",
+                );
+        }
+
         ui.separator();
 
         ui.label("egui windows:");
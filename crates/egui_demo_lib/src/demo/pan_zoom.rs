@@ -49,27 +49,22 @@ impl crate::View for PanZoom {
             self.transform = TSTransform::default();
         }
 
-        let transform =
-            TSTransform::from_translation(ui.min_rect().left_top().to_vec2()) * self.transform;
+        let to_ui = TSTransform::from_translation(ui.min_rect().left_top().to_vec2());
 
-        if let Some(pointer) = ui.ctx().input(|i| i.pointer.hover_pos()) {
-            // Note: doesn't catch zooming / panning if a button in this PanZoom container is hovered.
-            if response.hovered() {
-                let pointer_in_layer = transform.inverse() * pointer;
-                let zoom_delta = ui.ctx().input(|i| i.zoom_delta());
-                let pan_delta = ui.ctx().input(|i| i.smooth_scroll_delta);
+        // Note: doesn't catch zooming / panning if a button in this PanZoom container is hovered.
+        if response.hovered() {
+            // Zoom in on pointer:
+            let mut transform = to_ui * self.transform;
+            egui::ZoomHandler::new().handle(ui, &response, &mut transform);
+            self.transform = to_ui.inverse() * transform;
 
-                // Zoom in on pointer:
-                self.transform = self.transform
-                    * TSTransform::from_translation(pointer_in_layer.to_vec2())
-                    * TSTransform::from_scaling(zoom_delta)
-                    * TSTransform::from_translation(-pointer_in_layer.to_vec2());
-
-                // Pan:
-                self.transform = TSTransform::from_translation(pan_delta) * self.transform;
-            }
+            // Pan:
+            let pan_delta = ui.ctx().input(|i| i.smooth_scroll_delta);
+            self.transform = TSTransform::from_translation(pan_delta) * self.transform;
         }
 
+        let transform = to_ui * self.transform;
+
         for (i, (pos, callback)) in [
             (
                 egui::Pos2::new(0.0, 0.0),
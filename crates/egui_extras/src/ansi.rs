@@ -0,0 +1,468 @@
+use std::{collections::VecDeque, ops::Range};
+
+use egui::{
+    text::{LayoutJob, LayoutSection},
+    Color32, FontId, Stroke, Style, TextFormat,
+};
+
+/// Text with ANSI escape codes, e.g. the colored output of a command line tool.
+///
+/// The text is split into lines, with the colors and text styles set by the escape codes.
+/// Turn it into a [`LayoutJob`] to show it, e.g. in a label:
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// let text = egui_extras::AnsiText::parse("\x1b[1;32mok\x1b[0m 3 tests passed");
+/// let font_id = egui::TextStyle::Monospace.resolve(ui.style());
+/// ui.label(text.layout_job(ui.style(), &font_id));
+/// # });
+/// ```
+///
+/// For output that arrives bit by bit, keep the [`AnsiText`] around and [`Self::push_str`] to it.
+/// Escape codes and lines may be split across calls.
+/// Use [`Self::line_layout_job`] to only lay out the lines that are visible, e.g. in a log viewer.
+///
+/// Supported are colors (16, 256, and true color), bold, dim, italics, underline, strikethrough and inverse.
+/// A carriage return (`\r`) not followed by a newline overwrites the line, like progress bars expect.
+/// Other escape codes are ignored.
+#[derive(Default)]
+pub struct AnsiText {
+    lines: VecDeque<AnsiLine>,
+
+    /// Is the last line still being written, i.e. no newline yet?
+    last_line_open: bool,
+
+    parser: AnsiParser,
+}
+
+impl AnsiText {
+    /// No text.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse the given text.
+    pub fn parse(text: &str) -> Self {
+        let mut ansi_text = Self::default();
+        ansi_text.push_str(text);
+        ansi_text
+    }
+
+    /// Add more text, continuing with the style left by the text before it.
+    pub fn push_str(&mut self, text: &str) {
+        self.parser
+            .feed(text, &mut self.lines, &mut self.last_line_open);
+    }
+
+    /// Add text on a new line, without the style left by the text before it.
+    ///
+    /// The style of later calls to [`Self::push_str`] is not affected.
+    pub(crate) fn push_unstyled(&mut self, text: &str) {
+        self.last_line_open = false;
+        AnsiParser::default().feed(text, &mut self.lines, &mut self.last_line_open);
+    }
+
+    /// Remove all text, and reset the style.
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// The number of lines.
+    pub fn num_lines(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Drop the oldest lines, so that at most `max_lines` remain.
+    pub fn truncate_front(&mut self, max_lines: usize) {
+        let excess = self.lines.len().saturating_sub(max_lines);
+        self.lines.drain(..excess);
+        if self.lines.is_empty() {
+            self.last_line_open = false;
+        }
+    }
+
+    /// The text without the escape codes.
+    pub fn plain_text(&self) -> String {
+        let mut text = String::new();
+        for (i, line) in self.lines.iter().enumerate() {
+            if 0 < i {
+                text.push('\n');
+            }
+            text.push_str(&line.text);
+        }
+        text
+    }
+
+    /// Lay out all the text, with lines separated by newlines.
+    ///
+    /// Text without a color gets the text color of the given style.
+    pub fn layout_job(&self, style: &Style, font_id: &FontId) -> LayoutJob {
+        let mut job = LayoutJob::default();
+        for (i, line) in self.lines.iter().enumerate() {
+            if 0 < i {
+                job.append(
+                    "\n",
+                    0.0,
+                    TextFormat::simple(font_id.clone(), Color32::TRANSPARENT),
+                );
+            }
+            line.append_to(&mut job, style, font_id);
+        }
+        job
+    }
+
+    /// Lay out a single line, or `None` if there is no such line.
+    ///
+    /// The [`LayoutJob`] does not wrap.
+    pub fn line_layout_job(
+        &self,
+        index: usize,
+        style: &Style,
+        font_id: &FontId,
+    ) -> Option<LayoutJob> {
+        let line = self.lines.get(index)?;
+        let mut job = LayoutJob {
+            break_on_newline: false,
+            ..Default::default()
+        };
+        line.append_to(&mut job, style, font_id);
+        Some(job)
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// A line of text, with the style of each part of it.
+#[derive(Default)]
+struct AnsiLine {
+    text: String,
+    spans: Vec<(Range<usize>, AnsiStyle)>,
+}
+
+impl AnsiLine {
+    fn push(&mut self, chr: char, style: AnsiStyle) {
+        let start = self.text.len();
+        self.text.push(chr);
+        let end = self.text.len();
+        match self.spans.last_mut() {
+            Some((range, last_style)) if *last_style == style && range.end == start => {
+                range.end = end;
+            }
+            _ => self.spans.push((start..end, style)),
+        }
+    }
+
+    fn clear(&mut self) {
+        self.text.clear();
+        self.spans.clear();
+    }
+
+    fn append_to(&self, job: &mut LayoutJob, style: &Style, font_id: &FontId) {
+        let offset = job.text.len();
+        job.text += &self.text;
+        for (range, ansi_style) in &self.spans {
+            job.sections.push(LayoutSection {
+                leading_space: 0.0,
+                byte_range: offset + range.start..offset + range.end,
+                format: ansi_style.text_format(style, font_id),
+            });
+        }
+    }
+}
+
+/// The style set by ANSI escape codes.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) struct AnsiStyle {
+    /// `None` means the default text color.
+    pub fg: Option<Color32>,
+
+    /// `None` means no background.
+    pub bg: Option<Color32>,
+
+    pub bold: bool,
+    pub dim: bool,
+    pub italics: bool,
+    pub underline: bool,
+    pub strikethrough: bool,
+
+    /// Swap the text and background colors.
+    pub inverse: bool,
+}
+
+impl AnsiStyle {
+    pub(crate) fn text_format(&self, style: &Style, font_id: &FontId) -> TextFormat {
+        let color = match self.fg {
+            Some(color) => color,
+            None if self.bold => style.visuals.strong_text_color(),
+            None => style.visuals.text_color(),
+        };
+        let color = if self.dim {
+            color.gamma_multiply(0.6)
+        } else {
+            color
+        };
+        let (color, background) = if self.inverse {
+            (self.bg.unwrap_or(style.visuals.extreme_bg_color), color)
+        } else {
+            (color, self.bg.unwrap_or(Color32::TRANSPARENT))
+        };
+        let line = |on: bool| {
+            if on {
+                Stroke::new(1.0, color)
+            } else {
+                Stroke::NONE
+            }
+        };
+        TextFormat {
+            font_id: font_id.clone(),
+            color,
+            background,
+            italics: self.italics,
+            underline: line(self.underline),
+            strikethrough: line(self.strikethrough),
+            ..Default::default()
+        }
+    }
+
+    /// Apply the parameters of a Select Graphic Rendition escape code, i.e. `ESC [ … m`.
+    pub(crate) fn apply_sgr(&mut self, params: &str) {
+        let mut params = params
+            .split([';', ':'])
+            .map(|param| param.parse::<u8>().unwrap_or(0));
+        while let Some(param) = params.next() {
+            match param {
+                0 => *self = Self::default(),
+                1 => self.bold = true,
+                2 => self.dim = true,
+                3 => self.italics = true,
+                4 => self.underline = true,
+                7 => self.inverse = true,
+                9 => self.strikethrough = true,
+                22 => {
+                    self.bold = false;
+                    self.dim = false;
+                }
+                23 => self.italics = false,
+                24 => self.underline = false,
+                27 => self.inverse = false,
+                29 => self.strikethrough = false,
+                30..=37 => self.fg = Some(ansi_color(param - 30)),
+                38 => self.fg = extended_color(&mut params),
+                39 => self.fg = None,
+                40..=47 => self.bg = Some(ansi_color(param - 40)),
+                48 => self.bg = extended_color(&mut params),
+                49 => self.bg = None,
+                90..=97 => self.fg = Some(ansi_color(param - 90 + 8)),
+                100..=107 => self.bg = Some(ansi_color(param - 100 + 8)),
+                _ => {}
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+enum EscapeState {
+    #[default]
+    None,
+
+    /// After ESC.
+    Escape,
+
+    /// In a Control Sequence (ESC [), with the parameters so far.
+    Csi(String),
+
+    /// In an Operating System Command (ESC ]), which runs until BEL or ESC \.
+    Osc,
+}
+
+/// Splits text into lines, and interprets the ANSI escape codes in it.
+///
+/// Keeps its state between calls to [`Self::feed`], so escape codes may be split across calls.
+#[derive(Default)]
+struct AnsiParser {
+    style: AnsiStyle,
+    escape: EscapeState,
+
+    /// Got a `\r`, which clears the line unless followed by `\n`.
+    carriage_return: bool,
+}
+
+impl AnsiParser {
+    fn feed(&mut self, text: &str, lines: &mut VecDeque<AnsiLine>, last_line_open: &mut bool) {
+        for chr in text.chars() {
+            match &mut self.escape {
+                EscapeState::None => {}
+                EscapeState::Escape => {
+                    self.escape = match chr {
+                        '[' => EscapeState::Csi(String::new()),
+                        ']' => EscapeState::Osc,
+                        _ => EscapeState::None,
+                    };
+                    continue;
+                }
+                EscapeState::Csi(params) => {
+                    if ('\x40'..='\x7e').contains(&chr) {
+                        if chr == 'm' {
+                            self.style.apply_sgr(params);
+                        }
+                        self.escape = EscapeState::None;
+                    } else if params.len() < 64 {
+                        params.push(chr);
+                    }
+                    continue;
+                }
+                EscapeState::Osc => {
+                    match chr {
+                        '\x07' => self.escape = EscapeState::None,
+                        '\x1b' => self.escape = EscapeState::Escape, // ST is ESC \
+                        _ => {}
+                    }
+                    continue;
+                }
+            }
+
+            if std::mem::take(&mut self.carriage_return) && chr != '\n' {
+                // Overwrite the line, e.g. for progress bars:
+                if *last_line_open {
+                    if let Some(line) = lines.back_mut() {
+                        line.clear();
+                    }
+                }
+            }
+
+            match chr {
+                '\x1b' => self.escape = EscapeState::Escape,
+                '\r' => self.carriage_return = true,
+                '\n' => {
+                    if !*last_line_open {
+                        lines.push_back(AnsiLine::default());
+                    }
+                    *last_line_open = false;
+                }
+                '\t' => {
+                    for _ in 0..4 {
+                        open_line(lines, last_line_open).push(' ', self.style);
+                    }
+                }
+                chr if chr.is_control() => {}
+                chr => open_line(lines, last_line_open).push(chr, self.style),
+            }
+        }
+    }
+}
+
+/// The line to append to.
+fn open_line<'a>(lines: &'a mut VecDeque<AnsiLine>, last_line_open: &mut bool) -> &'a mut AnsiLine {
+    if !*last_line_open || lines.is_empty() {
+        lines.push_back(AnsiLine::default());
+        *last_line_open = true;
+    }
+    lines.back_mut().expect("just pushed")
+}
+
+/// The color of `38;5;n` (256 colors) or `38;2;r;g;b` (true color).
+fn extended_color(params: &mut impl Iterator<Item = u8>) -> Option<Color32> {
+    match params.next()? {
+        5 => Some(ansi_color(params.next()?)),
+        2 => Some(Color32::from_rgb(
+            params.next()?,
+            params.next()?,
+            params.next()?,
+        )),
+        _ => None,
+    }
+}
+
+/// One of the 256 colors of xterm.
+fn ansi_color(index: u8) -> Color32 {
+    const BASIC: [Color32; 16] = [
+        Color32::from_rgb(0, 0, 0),
+        Color32::from_rgb(205, 49, 49),
+        Color32::from_rgb(13, 188, 121),
+        Color32::from_rgb(229, 229, 16),
+        Color32::from_rgb(36, 114, 200),
+        Color32::from_rgb(188, 63, 188),
+        Color32::from_rgb(17, 168, 205),
+        Color32::from_rgb(229, 229, 229),
+        Color32::from_rgb(102, 102, 102),
+        Color32::from_rgb(241, 76, 76),
+        Color32::from_rgb(35, 209, 139),
+        Color32::from_rgb(245, 245, 67),
+        Color32::from_rgb(59, 142, 234),
+        Color32::from_rgb(214, 112, 214),
+        Color32::from_rgb(41, 184, 219),
+        Color32::from_rgb(255, 255, 255),
+    ];
+
+    match index {
+        0..=15 => BASIC[index as usize],
+        16..=231 => {
+            // 6x6x6 color cube:
+            let level = |i: u8| if i == 0 { 0 } else { 55 + 40 * i };
+            let i = index - 16;
+            Color32::from_rgb(level(i / 36), level(i / 6 % 6), level(i % 6))
+        }
+        232..=255 => {
+            let gray = 8 + 10 * (index - 232);
+            Color32::from_gray(gray)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spans(text: &AnsiText) -> Vec<Vec<(String, AnsiStyle)>> {
+        text.lines
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|(range, style)| (line.text[range.clone()].to_owned(), *style))
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn ansi_escape_codes() {
+        let red = AnsiStyle {
+            fg: Some(ansi_color(1)),
+            ..Default::default()
+        };
+        let bold_true_color = AnsiStyle {
+            fg: Some(Color32::from_rgb(1, 2, 3)),
+            bold: true,
+            ..Default::default()
+        };
+
+        // Escape codes and lines may be split across calls:
+        let mut text = AnsiText::new();
+        for chunk in [
+            "plain \x1b[3",
+            "1mred\x1b[0m\n\x1b[1;38;2;1;2;3mbold\x1b]0;title\x07",
+            "\x1b[m\n\ndownloading 10%\rdone",
+        ] {
+            text.push_str(chunk);
+        }
+        assert_eq!(
+            spans(&text),
+            vec![
+                vec![
+                    ("plain ".to_owned(), AnsiStyle::default()),
+                    ("red".to_owned(), red),
+                ],
+                vec![("bold".to_owned(), bold_true_color)],
+                vec![],
+                vec![("done".to_owned(), AnsiStyle::default())],
+            ]
+        );
+        assert_eq!(text.plain_text(), "plain red\nbold\n\ndone");
+
+        let job = text.layout_job(&Style::default(), &FontId::monospace(12.0));
+        assert_eq!(job.text, text.plain_text());
+        assert_eq!(job.sections[1].format.color, ansi_color(1));
+        assert_eq!(&job.text[job.sections[1].byte_range.clone()], "red");
+    }
+}
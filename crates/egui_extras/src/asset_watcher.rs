@@ -0,0 +1,189 @@
+//! Reloading images and fonts when their files change, see [`watch_assets`].
+
+use std::{
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, Instant, SystemTime},
+};
+
+use ahash::HashMap;
+use egui::{
+    epaint::text::{FontData, FontInsert, InsertFontFamily},
+    mutex::Mutex,
+    Context, Id,
+};
+
+/// How often we check the watched files for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Reload images and fonts when their files change on disk,
+/// so you can tweak icons and themes without restarting your app.
+///
+/// From now on, every image loaded from a `file://` URI (see [`crate::install_image_loaders`])
+/// is watched, as are fonts added with [`add_watched_font`].
+/// When a file changes, the image is loaded again (with [`egui::Context::forget_image`]),
+/// or the font is replaced, and egui repaints.
+///
+/// Call this before loading any images, since images that are already loaded aren't watched.
+///
+/// The files are checked twice a second, which keeps egui repainting at that rate
+/// while anything is watched, so this is meant for development:
+///
+/// ```
+/// # let ctx = egui::Context::default();
+/// if cfg!(debug_assertions) {
+///     egui_extras::watch_assets(&ctx);
+/// }
+/// ```
+pub fn watch_assets(ctx: &Context) {
+    AssetWatcher::get_or_install(ctx);
+}
+
+/// Add a font from a file, like [`egui::Context::add_font`],
+/// and replace it whenever the file changes.
+///
+/// This also turns on [`watch_assets`].
+///
+/// # Errors
+/// If the file can't be read.
+pub fn add_watched_font(
+    ctx: &Context,
+    name: impl Into<String>,
+    path: impl Into<PathBuf>,
+    families: Vec<InsertFontFamily>,
+) -> std::io::Result<()> {
+    let name = name.into();
+    let path = path.into();
+    let bytes = std::fs::read(&path)?;
+    ctx.add_font(FontInsert {
+        name: name.clone(),
+        data: FontData::from_owned(bytes),
+        families,
+    });
+    AssetWatcher::get_or_install(ctx).watch(Asset::Font { name }, path);
+    Ok(())
+}
+
+/// Something that is loaded from a file we watch.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum Asset {
+    /// An image loaded by [`crate::loaders`], by its `file://` URI.
+    #[cfg(feature = "file")]
+    Image { uri: String },
+
+    /// A font in [`egui::FontDefinitions::font_data`], by its name.
+    Font { name: String },
+}
+
+struct WatchedFile {
+    path: PathBuf,
+
+    /// When the file was last modified, as of when we last checked.
+    modified: Option<SystemTime>,
+}
+
+#[derive(Default)]
+struct State {
+    files: HashMap<Asset, WatchedFile>,
+    next_poll: Option<Instant>,
+}
+
+/// Keeps track of the files of the loaded assets, and checks them for changes at the end of each pass.
+#[derive(Default)]
+pub(crate) struct AssetWatcher {
+    state: Mutex<State>,
+}
+
+impl AssetWatcher {
+    fn id() -> Id {
+        Id::new("egui_extras::AssetWatcher")
+    }
+
+    /// The watcher of this context, if [`watch_assets`] has been called.
+    pub(crate) fn get(ctx: &Context) -> Option<Arc<Self>> {
+        ctx.data(|d| d.get_temp::<Arc<Self>>(Self::id()))
+    }
+
+    fn get_or_install(ctx: &Context) -> Arc<Self> {
+        if let Some(watcher) = Self::get(ctx) {
+            return watcher;
+        }
+
+        let watcher = Arc::new(Self::default());
+        ctx.data_mut(|d| d.insert_temp(Self::id(), watcher.clone()));
+        ctx.on_end_pass(
+            "egui_extras::AssetWatcher",
+            Arc::new({
+                let watcher = watcher.clone();
+                move |ctx| watcher.end_pass(ctx)
+            }),
+        );
+        watcher
+    }
+
+    /// Start watching the file of `asset`, unless we already do.
+    pub(crate) fn watch(&self, asset: Asset, path: PathBuf) {
+        self.state
+            .lock()
+            .files
+            .entry(asset)
+            .or_insert_with(|| WatchedFile {
+                modified: modified(&path),
+                path,
+            });
+    }
+
+    fn end_pass(&self, ctx: &Context) {
+        let now = Instant::now();
+        let changed: Vec<(Asset, PathBuf)> = {
+            let mut state = self.state.lock();
+            if state.files.is_empty() || state.next_poll.is_some_and(|next| now < next) {
+                return;
+            }
+            state.next_poll = Some(now + POLL_INTERVAL);
+            state
+                .files
+                .iter_mut()
+                .filter_map(|(asset, file)| {
+                    let modified = modified(&file.path);
+                    (modified != file.modified).then(|| {
+                        file.modified = modified;
+                        (asset.clone(), file.path.clone())
+                    })
+                })
+                .collect()
+        };
+
+        for (asset, path) in changed {
+            log::debug!("{path:?} changed; reloading {asset:?}");
+            match asset {
+                #[cfg(feature = "file")]
+                Asset::Image { uri } => ctx.forget_image(&uri),
+                Asset::Font { name } => reload_font(ctx, &name, &path),
+            }
+            ctx.request_repaint();
+        }
+
+        ctx.request_repaint_after(POLL_INTERVAL);
+    }
+}
+
+fn modified(path: &std::path::Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+fn reload_font(ctx: &Context, name: &str, path: &std::path::Path) {
+    let bytes = match std::fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(err) => {
+            log::warn!("Failed to reload font {name:?} from {path:?}: {err}");
+            return;
+        }
+    };
+
+    let mut definitions = ctx.fonts(|fonts| fonts.lock().fonts.definitions().clone());
+    if let Some(data) = definitions.font_data.get_mut(name) {
+        data.font = bytes.into();
+        ctx.set_fonts(definitions);
+    }
+}
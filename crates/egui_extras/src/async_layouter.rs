@@ -0,0 +1,455 @@
+use std::{
+    ops::Range,
+    sync::{
+        mpsc::{Receiver, Sender},
+        Arc,
+    },
+};
+
+use egui::{
+    text::{LayoutJob, LayoutSection},
+    Galley, TextFormat, TextStyle, Ui,
+};
+
+type HighlightFn = dyn Fn(&str) -> LayoutJob + Send + Sync;
+
+/// A [`egui::TextEdit::layouter`] that runs a slow highlighter on a background thread,
+/// so that editing large texts stays responsive.
+///
+/// Each time the text changes, the highlighter is asked to highlight the new text on the background thread.
+/// Until it is done, the last highlighted result is shown, patched up to fit the new text:
+/// paragraphs (lines) that did not change keep their highlighting,
+/// and the changed paragraphs get the highlighting of the paragraphs they replaced, as far as it fits.
+/// When the refined result is ready, a repaint is requested and it is shown instead.
+///
+/// If the text changes while the highlighter is busy, only the latest text is highlighted next.
+///
+/// On platforms without threads (i.e. the web) the highlighter is called on the ui thread instead.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// # let mut my_code = String::new();
+/// # fn my_slow_highlighter(s: &str) -> egui::text::LayoutJob { egui::text::LayoutJob::simple_singleline(s.to_owned(), Default::default(), egui::Color32::WHITE) }
+/// // Keep this around between frames, e.g. in your app state:
+/// let mut async_layouter = egui_extras::AsyncLayouter::new(my_slow_highlighter);
+///
+/// let mut layouter = |ui: &egui::Ui, text: &str, wrap_width: f32| {
+///     async_layouter.layout(ui, text, wrap_width)
+/// };
+/// ui.add(egui::TextEdit::multiline(&mut my_code).layouter(&mut layouter));
+/// # });
+/// ```
+pub struct AsyncLayouter {
+    highlight: Arc<HighlightFn>,
+    default_format: Option<TextFormat>,
+    worker: Worker,
+
+    /// The text that was last sent to the highlighter.
+    requested: Option<String>,
+
+    /// The latest result of the highlighter.
+    highlighted: Option<Highlighted>,
+
+    /// What we show for the last text passed to [`Self::layout`].
+    shown: Option<Shown>,
+}
+
+/// The result of the highlighter.
+struct Highlighted {
+    text: String,
+    job: LayoutJob,
+}
+
+struct Shown {
+    text: String,
+    job: LayoutJob,
+
+    /// Was the job guessed from an older highlighted text, see [`stale_job`]?
+    is_stale: bool,
+}
+
+enum Worker {
+    NotStarted,
+
+    Running {
+        requests: Sender<String>,
+        results: Receiver<Highlighted>,
+    },
+
+    /// Threads are not available, so we highlight on the ui thread.
+    Inline,
+}
+
+impl AsyncLayouter {
+    /// `highlight` turns the whole text into a [`LayoutJob`], and is called on a background thread.
+    ///
+    /// The [`LayoutJob::wrap`] settings of the result are ignored.
+    pub fn new(highlight: impl Fn(&str) -> LayoutJob + Send + Sync + 'static) -> Self {
+        Self {
+            highlight: Arc::new(highlight),
+            default_format: None,
+            worker: Worker::NotStarted,
+            requested: None,
+            highlighted: None,
+            shown: None,
+        }
+    }
+
+    /// The format of text that hasn't been highlighted yet.
+    ///
+    /// By default, the monospace font and the text color of [`egui::TextEdit`] is used.
+    #[inline]
+    pub fn default_format(mut self, default_format: TextFormat) -> Self {
+        self.default_format = Some(default_format);
+        self
+    }
+
+    /// Has the highlighter caught up with the last text passed to [`Self::layout`]?
+    pub fn is_up_to_date(&self) -> bool {
+        self.shown.as_ref().map_or(false, |shown| !shown.is_stale)
+    }
+
+    /// Lay out the text for an [`egui::TextEdit`], see [`egui::TextEdit::layouter`].
+    ///
+    /// Never waits for the highlighter.
+    pub fn layout(&mut self, ui: &Ui, text: &str, wrap_width: f32) -> Arc<Galley> {
+        crate::profile_function!();
+
+        self.receive_results();
+
+        let mut is_highlighted = self
+            .highlighted
+            .as_ref()
+            .map_or(false, |highlighted| highlighted.text == text);
+        if !is_highlighted && self.requested.as_deref() != Some(text) {
+            is_highlighted = self.request(ui, text);
+        }
+
+        let needs_update = self.shown.as_ref().map_or(true, |shown| {
+            shown.text != text || (shown.is_stale && is_highlighted)
+        });
+
+        if needs_update {
+            let job = match &self.highlighted {
+                Some(highlighted) if is_highlighted => highlighted.job.clone(),
+                highlighted => {
+                    let default_format = self
+                        .default_format
+                        .clone()
+                        .unwrap_or_else(|| default_format(ui));
+                    stale_job(highlighted.as_ref(), text, default_format)
+                }
+            };
+            self.shown = Some(Shown {
+                text: text.to_owned(),
+                job,
+                is_stale: !is_highlighted,
+            });
+        }
+
+        let mut job = self
+            .shown
+            .as_ref()
+            .map(|shown| shown.job.clone())
+            .unwrap_or_default();
+        job.wrap.max_width = wrap_width;
+        ui.fonts(|f| f.layout_job(job))
+    }
+
+    fn receive_results(&mut self) {
+        if let Worker::Running { results, .. } = &self.worker {
+            if let Some(highlighted) = results.try_iter().last() {
+                self.highlighted = Some(highlighted);
+            }
+        }
+    }
+
+    /// Ask for `text` to be highlighted.
+    ///
+    /// Returns `true` if it was highlighted right away, on the ui thread.
+    fn request(&mut self, ui: &Ui, text: &str) -> bool {
+        self.requested = Some(text.to_owned());
+
+        if matches!(self.worker, Worker::NotStarted) {
+            self.worker = spawn_worker(ui, self.highlight.clone());
+        }
+
+        if let Worker::Running { requests, .. } = &self.worker {
+            if requests.send(text.to_owned()).is_ok() {
+                return false;
+            }
+            log::warn!(
+                "The highlighter thread has stopped. Highlighting on the ui thread instead."
+            );
+            self.worker = Worker::Inline;
+        }
+
+        self.highlighted = Some(Highlighted {
+            text: text.to_owned(),
+            job: (self.highlight)(text),
+        });
+        true
+    }
+}
+
+fn spawn_worker(ui: &Ui, highlight: Arc<HighlightFn>) -> Worker {
+    let (request_sender, request_receiver) = std::sync::mpsc::channel::<String>();
+    let (result_sender, result_receiver) = std::sync::mpsc::channel();
+    let ctx = ui.ctx().clone();
+    let viewport_id = ui.ctx().viewport_id();
+
+    let result = std::thread::Builder::new()
+        .name("egui_extras::AsyncLayouter".to_owned())
+        .spawn(move || {
+            while let Ok(mut text) = request_receiver.recv() {
+                // Skip straight to the latest text:
+                if let Some(latest) = request_receiver.try_iter().last() {
+                    text = latest;
+                }
+                let job = highlight(&text);
+                if result_sender.send(Highlighted { text, job }).is_err() {
+                    break; // The layouter is gone
+                }
+                ctx.request_repaint_of(viewport_id);
+            }
+        });
+
+    match result {
+        Ok(_) => Worker::Running {
+            requests: request_sender,
+            results: result_receiver,
+        },
+        Err(err) => {
+            log::debug!(
+                "Failed to spawn a highlighter thread, highlighting on the ui thread: {err}"
+            );
+            Worker::Inline
+        }
+    }
+}
+
+/// Like the default layouter of [`egui::TextEdit`], but always monospace.
+fn default_format(ui: &Ui) -> TextFormat {
+    let style = ui.style();
+    let font_id = style
+        .override_font_id
+        .clone()
+        .unwrap_or_else(|| TextStyle::Monospace.resolve(style));
+    let color = style
+        .visuals
+        .override_text_color
+        .unwrap_or_else(|| style.visuals.widgets.inactive.text_color());
+    TextFormat::simple(font_id, color)
+}
+
+/// A quick guess at the highlighting of `text`, based on the highlighting of an older version of it.
+///
+/// The paragraphs at the start and end of `text` that are the same as in the old text keep their highlighting.
+/// The changed paragraphs in between get the highlighting of the old paragraphs they replaced,
+/// mapped byte for byte, and the rest of them gets `default_format`.
+fn stale_job(old: Option<&Highlighted>, text: &str, default_format: TextFormat) -> LayoutJob {
+    crate::profile_function!();
+
+    let mut job = LayoutJob {
+        text: text.to_owned(),
+        ..Default::default()
+    };
+
+    let Some(old) = old else {
+        job.sections.push(LayoutSection {
+            leading_space: 0.0,
+            byte_range: 0..text.len(),
+            format: default_format,
+        });
+        return job;
+    };
+
+    let old_paragraphs = paragraph_ranges(&old.text);
+    let new_paragraphs = paragraph_ranges(text);
+
+    let same = |old_index: usize, new_index: usize| {
+        old.text[old_paragraphs[old_index].clone()] == text[new_paragraphs[new_index].clone()]
+    };
+    let max_common = old_paragraphs.len().min(new_paragraphs.len());
+    let num_prefix = (0..max_common).take_while(|&i| same(i, i)).count();
+    let num_suffix = (0..max_common - num_prefix)
+        .take_while(|&i| same(old_paragraphs.len() - 1 - i, new_paragraphs.len() - 1 - i))
+        .count();
+
+    let old_damaged = damaged_range(&old_paragraphs, num_prefix, num_suffix, old.text.len());
+    let new_damaged = damaged_range(&new_paragraphs, num_prefix, num_suffix, text.len());
+
+    let mut sections = vec![];
+    for (old_range, new_range) in [
+        (0..old_damaged.start, 0..new_damaged.start),
+        (old_damaged.clone(), new_damaged.clone()),
+        (old_damaged.end..old.text.len(), new_damaged.end..text.len()),
+    ] {
+        map_sections(
+            &old.job.sections,
+            old_range,
+            text,
+            new_range,
+            &default_format,
+            &mut sections,
+        );
+    }
+    job.sections = sections;
+    job
+}
+
+/// The byte ranges of the paragraphs in `text`, each including its trailing newline.
+fn paragraph_ranges(text: &str) -> Vec<Range<usize>> {
+    let mut ranges = vec![];
+    let mut start = 0;
+    for (i, _) in text.match_indices('\n') {
+        ranges.push(start..i + 1);
+        start = i + 1;
+    }
+    ranges.push(start..text.len());
+    ranges
+}
+
+/// The byte range between the first `num_prefix` and the last `num_suffix` paragraphs.
+fn damaged_range(
+    paragraphs: &[Range<usize>],
+    num_prefix: usize,
+    num_suffix: usize,
+    text_len: usize,
+) -> Range<usize> {
+    let start = if num_prefix == 0 {
+        0
+    } else {
+        paragraphs[num_prefix - 1].end
+    };
+    let end = if num_suffix == 0 {
+        text_len
+    } else {
+        paragraphs[paragraphs.len() - num_suffix].start
+    };
+    start..end.max(start)
+}
+
+/// Copy the formats of the `old_sections` in `old_range` onto `new_range` of `new_text`,
+/// by their offset from the start of the range.
+///
+/// The whole of `new_range` is covered, using `default_format` where the old sections run out.
+fn map_sections(
+    old_sections: &[LayoutSection],
+    old_range: Range<usize>,
+    new_text: &str,
+    new_range: Range<usize>,
+    default_format: &TextFormat,
+    out: &mut Vec<LayoutSection>,
+) {
+    let mut cursor = new_range.start;
+    for section in old_sections {
+        if new_range.end <= cursor {
+            break;
+        }
+        let start = section.byte_range.start.max(old_range.start);
+        let end = section.byte_range.end.min(old_range.end);
+        if end <= start {
+            continue;
+        }
+        let end = floor_char_boundary(
+            new_text,
+            (new_range.start + end - old_range.start).min(new_range.end),
+        );
+        if cursor < end {
+            let unclipped = section.byte_range.start == start
+                && new_range.start + start - old_range.start == cursor;
+            out.push(LayoutSection {
+                leading_space: if unclipped {
+                    section.leading_space
+                } else {
+                    0.0
+                },
+                byte_range: cursor..end,
+                format: section.format.clone(),
+            });
+            cursor = end;
+        }
+    }
+    if cursor < new_range.end {
+        out.push(LayoutSection {
+            leading_space: 0.0,
+            byte_range: cursor..new_range.end,
+            format: default_format.clone(),
+        });
+    }
+}
+
+fn floor_char_boundary(text: &str, mut index: usize) -> usize {
+    while !text.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use egui::Color32;
+
+    fn highlight(text: &str) -> LayoutJob {
+        // Color each word by its length:
+        let mut job = LayoutJob::default();
+        let mut rest = text;
+        while !rest.is_empty() {
+            let len = rest
+                .find(char::is_whitespace)
+                .map_or(rest.len(), |i| i.max(1));
+            let color = Color32::from_gray(len as u8);
+            job.append(
+                &rest[..len],
+                0.0,
+                TextFormat::simple(Default::default(), color),
+            );
+            rest = &rest[len..];
+        }
+        job
+    }
+
+    fn colors(job: &LayoutJob) -> Vec<(&str, u8)> {
+        job.sections
+            .iter()
+            .map(|section| {
+                (
+                    &job.text[section.byte_range.clone()],
+                    section.format.color.r(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn stale_job_keeps_unchanged_paragraphs() {
+        let old_text = "ab cde\nf gh\nijkl\n";
+        let old = Highlighted {
+            text: old_text.to_owned(),
+            job: highlight(old_text),
+        };
+        let default_format = TextFormat::simple(Default::default(), Color32::from_gray(99));
+
+        let job = stale_job(Some(&old), "ab cde\nf ghxyz\nijkl\n", default_format);
+        assert_eq!(
+            colors(&job),
+            vec![
+                ("ab", 2),
+                (" ", 1),
+                ("cde", 3),
+                ("\n", 1),
+                // The changed paragraph keeps the old colors, as far as they go:
+                ("f", 1),
+                (" ", 1),
+                ("gh", 2),
+                ("x", 1),
+                ("yz\n", 99),
+                // The unchanged paragraph has been moved along:
+                ("ijkl", 4),
+                ("\n", 1),
+            ]
+        );
+    }
+}
@@ -0,0 +1,422 @@
+use std::sync::Arc;
+
+use egui::{
+    mutex::Mutex,
+    text::{CCursor, CCursorRange},
+    Context, Id, Key, Modifiers, NumExt as _, Response, ScrollArea, TextEdit, TextStyle, Ui,
+    ViewportId,
+};
+
+use crate::AnsiText;
+
+type Completer = Box<dyn FnMut(&str) -> Vec<String>>;
+
+/// An interactive console, e.g. for an embedded debug console in a game engine or a server.
+///
+/// It shows the output written to it, with a prompt below where the user types commands.
+/// The [`Console`] only collects the commands, it is up to you to run them:
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// // Keep this around between frames, e.g. in your app state:
+/// let mut console = egui_extras::Console::new("debug_console");
+///
+/// if let Some(command) = console.show(ui).command {
+///     // Commands can run in the background, streaming their output to the console:
+///     let mut writer = console.writer();
+///     std::thread::spawn(move || {
+///         use std::io::Write as _;
+///         writeln!(writer, "\x1b[32mRunning\x1b[0m {command}…").ok();
+///     });
+/// }
+/// # });
+/// ```
+///
+/// Features:
+/// * Use up/down to go through the history of commands.
+/// * Output written with [`ConsoleWriter`] can come from any thread, and is shown as it arrives.
+/// * ANSI escape codes for colors and text styles in the output are shown as such, see [`AnsiText`].
+/// * The output can be selected and copied.
+/// * Tab completion, see [`Self::completer`].
+///
+/// The output is shown without wrapping, one row per line.
+pub struct Console {
+    id_salt: Id,
+    prompt: String,
+    max_lines: usize,
+    completer: Option<Completer>,
+
+    input: String,
+
+    history: Vec<String>,
+
+    /// Where in the history we are, when going through it with up/down.
+    history_index: Option<usize>,
+
+    /// What was typed before going through the history.
+    draft: String,
+
+    output: AnsiText,
+
+    shared: Arc<Mutex<SharedOutput>>,
+}
+
+/// Written by the writers of a widget (e.g. [`ConsoleWriter`]), read by the widget.
+#[derive(Default)]
+pub(crate) struct SharedOutput {
+    /// Output that has not been shown yet.
+    pending: Vec<u8>,
+
+    /// What to repaint when output arrives, known once the widget has been shown.
+    repaint: Option<(Context, ViewportId)>,
+}
+
+impl SharedOutput {
+    pub(crate) fn write(&mut self, bytes: &[u8]) {
+        self.pending.extend_from_slice(bytes);
+        if let Some((ctx, viewport_id)) = &self.repaint {
+            ctx.request_repaint_of(*viewport_id);
+        }
+    }
+
+    /// Take the output written so far, and remember what to repaint when more arrives.
+    pub(crate) fn take(&mut self, ui: &Ui) -> String {
+        self.repaint
+            .get_or_insert_with(|| (ui.ctx().clone(), ui.ctx().viewport_id()));
+        take_complete_utf8(&mut self.pending)
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.pending.clear();
+    }
+}
+
+/// What [`Console::show`] returns.
+pub struct ConsoleResponse {
+    /// The response of the input field.
+    pub response: Response,
+
+    /// The command the user entered this frame, if any.
+    pub command: Option<String>,
+}
+
+impl Console {
+    /// The `id_salt` must be unique among the consoles shown in the same [`Ui`].
+    pub fn new(id_salt: impl std::hash::Hash) -> Self {
+        Self {
+            id_salt: Id::new(id_salt),
+            prompt: "> ".to_owned(),
+            max_lines: 10_000,
+            completer: None,
+            input: String::new(),
+            history: Vec::new(),
+            history_index: None,
+            draft: String::new(),
+            output: AnsiText::new(),
+            shared: Default::default(),
+        }
+    }
+
+    /// The prompt shown in front of the input, and in front of each command in the output.
+    ///
+    /// Default: `"> "`.
+    #[inline]
+    pub fn prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.prompt = prompt.into();
+        self
+    }
+
+    /// How many lines of output to keep. Older lines are dropped.
+    ///
+    /// Default: `10_000`.
+    #[inline]
+    pub fn max_lines(mut self, max_lines: usize) -> Self {
+        self.max_lines = max_lines.max(1);
+        self
+    }
+
+    /// Complete the input when the user presses tab.
+    ///
+    /// `completer` is given the current input, and returns the possible completions of it,
+    /// each a full replacement of the input.
+    /// A single completion replaces the input.
+    /// With several, the input is extended with their common prefix, and they are all listed in the output.
+    #[inline]
+    pub fn completer(mut self, completer: impl FnMut(&str) -> Vec<String> + 'static) -> Self {
+        self.completer = Some(Box::new(completer));
+        self
+    }
+
+    /// Something to write output to from anywhere, e.g. a background thread running a command.
+    pub fn writer(&self) -> ConsoleWriter {
+        ConsoleWriter {
+            shared: self.shared.clone(),
+        }
+    }
+
+    /// Write to the output. ANSI escape codes are supported.
+    ///
+    /// The same as writing to a [`Self::writer`].
+    pub fn write(&self, text: &str) {
+        self.writer().write_str(text);
+    }
+
+    /// Clear the output.
+    pub fn clear(&mut self) {
+        self.shared.lock().clear();
+        self.output.clear();
+    }
+
+    /// The commands entered so far, oldest first.
+    pub fn history(&self) -> &[String] {
+        &self.history
+    }
+
+    /// Replace the history, e.g. with one saved from an earlier session.
+    pub fn set_history(&mut self, history: Vec<String>) {
+        self.history = history;
+        self.history_index = None;
+    }
+
+    /// Show the output, and the prompt below it, using all available space.
+    pub fn show(&mut self, ui: &mut Ui) -> ConsoleResponse {
+        crate::profile_function!();
+
+        let id = ui.make_persistent_id(self.id_salt);
+        let input_id = id.with("input");
+
+        self.receive_output(ui);
+
+        let mut command = None;
+        if ui.memory(|mem| mem.has_focus(input_id)) {
+            command = self.handle_keys(ui, input_id);
+        }
+
+        let font_id = TextStyle::Monospace.resolve(ui.style());
+        let row_height = ui.fonts(|f| f.row_height(&font_id)).at_least(1.0); // Zero without fonts
+
+        let input_height = row_height + ui.spacing().item_spacing.y;
+        let available_height = ui.available_height();
+        let output_height = if available_height.is_finite() {
+            (available_height - input_height).at_least(row_height)
+        } else {
+            // E.g. in a vertical `ScrollArea`:
+            20.0 * row_height
+        };
+
+        ui.scope(|ui| {
+            ui.spacing_mut().item_spacing.y = 0.0;
+            ScrollArea::both()
+                .id_salt(id.with("output"))
+                .auto_shrink(false)
+                .stick_to_bottom(true)
+                .max_height(output_height)
+                .show_rows(ui, row_height, self.output.num_lines(), |ui, rows| {
+                    for row in rows {
+                        if let Some(job) = self.output.line_layout_job(row, ui.style(), &font_id) {
+                            ui.add(egui::Label::new(job).selectable(true).extend());
+                        }
+                    }
+                });
+        });
+
+        let response = ui
+            .horizontal(|ui| {
+                ui.label(egui::RichText::new(&self.prompt).font(font_id.clone()));
+                ui.add(
+                    TextEdit::singleline(&mut self.input)
+                        .id(input_id)
+                        .font(font_id)
+                        .frame(false)
+                        .lock_focus(true)
+                        .return_key(None)
+                        .desired_width(f32::INFINITY),
+                )
+            })
+            .inner;
+
+        if let Some(command) = &command {
+            // Echo the command, with the output it produces below it:
+            self.push_plain(&format!("{}{command}\n", self.prompt));
+            response.request_focus();
+        }
+
+        ConsoleResponse { response, command }
+    }
+
+    /// Show output written by the [`ConsoleWriter`]s.
+    fn receive_output(&mut self, ui: &Ui) {
+        let pending = self.shared.lock().take(ui);
+        if !pending.is_empty() {
+            self.output.push_str(&pending);
+            self.output.truncate_front(self.max_lines);
+        }
+    }
+
+    /// Add text to the output on a new line, without the style left over from the output before it.
+    fn push_plain(&mut self, text: &str) {
+        self.output.push_unstyled(text);
+        self.output.truncate_front(self.max_lines);
+    }
+
+    /// Handle the keys we use before the input field sees them.
+    ///
+    /// Returns the entered command, if any.
+    fn handle_keys(&mut self, ui: &Ui, input_id: Id) -> Option<String> {
+        let (enter, up, down, tab) = ui.input_mut(|i| {
+            (
+                i.consume_key(Modifiers::NONE, Key::Enter),
+                i.consume_key(Modifiers::NONE, Key::ArrowUp),
+                i.consume_key(Modifiers::NONE, Key::ArrowDown),
+                self.completer.is_some() && i.consume_key(Modifiers::NONE, Key::Tab),
+            )
+        });
+
+        if enter {
+            let command = std::mem::take(&mut self.input);
+            self.history_index = None;
+            self.draft.clear();
+            if !command.trim().is_empty() && self.history.last() != Some(&command) {
+                self.history.push(command.clone());
+            }
+            return Some(command);
+        }
+
+        let old_input = self.input.clone();
+        if up && !self.history.is_empty() {
+            let index = match self.history_index {
+                None => {
+                    self.draft = std::mem::take(&mut self.input);
+                    self.history.len() - 1
+                }
+                Some(index) => index.saturating_sub(1),
+            };
+            self.history_index = Some(index);
+            self.input.clone_from(&self.history[index]);
+        }
+        if down {
+            if let Some(index) = self.history_index {
+                if index + 1 < self.history.len() {
+                    self.history_index = Some(index + 1);
+                    self.input.clone_from(&self.history[index + 1]);
+                } else {
+                    self.history_index = None;
+                    self.input = std::mem::take(&mut self.draft);
+                }
+            }
+        }
+        if tab {
+            self.complete();
+        }
+
+        if self.input != old_input {
+            // Put the cursor at the end of the new input:
+            if let Some(mut state) = TextEdit::load_state(ui.ctx(), input_id) {
+                let ccursor = CCursor::new(self.input.chars().count());
+                state
+                    .cursor
+                    .set_char_range(Some(CCursorRange::one(ccursor)));
+                state.store(ui.ctx(), input_id);
+            }
+        }
+
+        None
+    }
+
+    fn complete(&mut self) {
+        let Some(completer) = &mut self.completer else {
+            return;
+        };
+        let completions = completer(&self.input);
+        match completions.as_slice() {
+            [] => {}
+            [completion] => {
+                self.input.clone_from(completion);
+            }
+            [first, rest @ ..] => {
+                let common_prefix = rest.iter().fold(first.as_str(), |prefix, completion| {
+                    let len = prefix
+                        .char_indices()
+                        .zip(completion.chars())
+                        .find(|((_, a), b)| a != b)
+                        .map_or(prefix.len().min(completion.len()), |((i, _), _)| i);
+                    &prefix[..len]
+                });
+                if self.input.len() < common_prefix.len() {
+                    self.input = common_prefix.to_owned();
+                }
+
+                self.push_plain(&format!("{}\n", completions.join("  ")));
+            }
+        }
+    }
+}
+
+/// Take the bytes that form complete UTF-8, leaving any incomplete character at the end for later.
+fn take_complete_utf8(bytes: &mut Vec<u8>) -> String {
+    let complete_len = match std::str::from_utf8(bytes) {
+        Err(err) if err.error_len().is_none() => err.valid_up_to(),
+        _ => bytes.len(), // All good, or invalid UTF-8 (not just cut off) that we show as it is.
+    };
+    let rest = bytes.split_off(complete_len);
+    let text = String::from_utf8_lossy(bytes).into_owned();
+    *bytes = rest;
+    text
+}
+
+// ----------------------------------------------------------------------------
+
+/// Writes to the output of a [`Console`], see [`Console::writer`].
+///
+/// Can be sent to other threads, and implements [`std::io::Write`] and [`std::fmt::Write`],
+/// so you can e.g. use `writeln!` with it, or copy the output of a child process to it.
+///
+/// Each write requests a repaint, so the output is shown as it arrives.
+#[derive(Clone)]
+pub struct ConsoleWriter {
+    shared: Arc<Mutex<SharedOutput>>,
+}
+
+impl ConsoleWriter {
+    /// Write to the output. ANSI escape codes are supported.
+    pub fn write_str(&self, text: &str) {
+        self.write_bytes(text.as_bytes());
+    }
+
+    fn write_bytes(&self, bytes: &[u8]) {
+        self.shared.lock().write(bytes);
+    }
+}
+
+impl std::io::Write for ConsoleWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.write_bytes(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl std::fmt::Write for ConsoleWriter {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        Self::write_str(self, s);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn incomplete_utf8() {
+        let mut bytes = "ok ✓".as_bytes().to_vec();
+        let last = bytes.pop().unwrap();
+        assert_eq!(take_complete_utf8(&mut bytes), "ok ");
+        bytes.push(last);
+        assert_eq!(take_complete_utf8(&mut bytes), "✓");
+        assert!(bytes.is_empty());
+    }
+}
@@ -0,0 +1,641 @@
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+
+use egui::{
+    Align2, CentralPanel, Context, Id, Ui, ViewportBuilder, ViewportClass, ViewportCommand,
+    ViewportId, Window,
+};
+
+/// A document of a multi-document app, managed by [`Documents`].
+pub trait Document: Send {
+    /// Shown in tabs, window titles and [`Documents::window_menu_ui`].
+    fn title(&self) -> String;
+
+    /// The file the document is saved to, if any.
+    ///
+    /// Opened and saved documents with a path are added to the [`RecentFiles`].
+    fn path(&self) -> Option<PathBuf> {
+        None
+    }
+
+    /// Are there changes that would be lost if the document was closed now?
+    fn is_dirty(&self) -> bool;
+
+    /// Save the document, e.g. to [`Self::path`], and clear the dirty flag.
+    ///
+    /// # Errors
+    /// The error message is shown to the user, and the document stays open.
+    fn save(&mut self) -> Result<(), String>;
+
+    /// Show the contents of the document.
+    fn ui(&mut self, ui: &mut Ui);
+}
+
+/// Identifies a document opened with [`Documents::open`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct DocumentId(u64);
+
+/// How [`Documents::show`] shows the open documents.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DocumentHosting {
+    /// As tabs in the [`CentralPanel`].
+    #[default]
+    Tabs,
+
+    /// Each in a viewport of its own, i.e. a native window if the integration supports it,
+    /// and otherwise an [`egui::Window`].
+    Viewports,
+}
+
+/// The files that were opened or saved most recently, most recent first.
+///
+/// Store it with your app state to remember it between runs, e.g. with `eframe`:
+///
+/// ```ignore
+/// fn save(&mut self, storage: &mut dyn eframe::Storage) {
+///     eframe::set_value(storage, "recent_files", self.documents.recent_files());
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct RecentFiles {
+    paths: VecDeque<PathBuf>,
+    max_len: usize,
+}
+
+impl Default for RecentFiles {
+    fn default() -> Self {
+        Self::new(10)
+    }
+}
+
+impl RecentFiles {
+    /// Remember at most `max_len` files.
+    pub fn new(max_len: usize) -> Self {
+        Self {
+            paths: VecDeque::new(),
+            max_len,
+        }
+    }
+
+    /// Move `path` to the front, forgetting the oldest file if there are too many.
+    pub fn add(&mut self, path: impl Into<PathBuf>) {
+        let path = path.into();
+        self.paths.retain(|p| *p != path);
+        self.paths.push_front(path);
+        self.paths.truncate(self.max_len);
+    }
+
+    /// Forget `path`, e.g. because it no longer exists.
+    pub fn remove(&mut self, path: &Path) {
+        self.paths.retain(|p| p != path);
+    }
+
+    pub fn clear(&mut self) {
+        self.paths.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.paths.len()
+    }
+
+    /// Most recent first.
+    pub fn iter(&self) -> impl Iterator<Item = &Path> + '_ {
+        self.paths.iter().map(PathBuf::as_path)
+    }
+
+    /// A button for each file and one to clear the list, e.g. for an "Open Recent" menu.
+    ///
+    /// Returns the file that was clicked, which you should open.
+    pub fn menu_ui(&mut self, ui: &mut Ui) -> Option<PathBuf> {
+        if self.paths.is_empty() {
+            ui.add_enabled(false, egui::Button::new("No recent files"));
+            return None;
+        }
+
+        let mut clicked = None;
+        for path in &self.paths {
+            let name = path.file_name().map_or_else(
+                || path.display().to_string(),
+                |name| name.to_string_lossy().into_owned(),
+            );
+            if ui
+                .button(name)
+                .on_hover_text(path.display().to_string())
+                .clicked()
+            {
+                clicked = Some(path.clone());
+                ui.close_menu();
+            }
+        }
+        ui.separator();
+        if ui.button("Clear Recent").clicked() {
+            self.clear();
+            ui.close_menu();
+        }
+        clicked
+    }
+}
+
+struct OpenDocument {
+    id: DocumentId,
+    document: Box<dyn Document>,
+}
+
+/// The open documents of a multi-document app.
+///
+/// Shows each document as a tab or in a viewport of its own (see [`DocumentHosting`]),
+/// asks the user to save unsaved changes before a document or the whole app is closed,
+/// keeps track of [`RecentFiles`], and provides a standard "Window" menu with [`Self::window_menu_ui`].
+///
+/// ```
+/// # egui::__run_test_ctx(|ctx| {
+/// use egui_extras::{Document, Documents};
+///
+/// struct Note {
+///     text: String,
+///     dirty: bool,
+/// }
+///
+/// impl Document for Note {
+///     fn title(&self) -> String {
+///         "Note".to_owned()
+///     }
+///
+///     fn is_dirty(&self) -> bool {
+///         self.dirty
+///     }
+///
+///     fn save(&mut self) -> Result<(), String> {
+///         self.dirty = false;
+///         Ok(())
+///     }
+///
+///     fn ui(&mut self, ui: &mut egui::Ui) {
+///         self.dirty |= ui.text_edit_multiline(&mut self.text).changed();
+///     }
+/// }
+///
+/// let mut documents = Documents::new("documents");
+/// documents.open(Note { text: String::new(), dirty: false });
+///
+/// // Each frame, in the root viewport:
+/// egui::TopBottomPanel::top("menu").show(ctx, |ui| {
+///     egui::menu::bar(ui, |ui| {
+///         ui.menu_button("Window", |ui| documents.window_menu_ui(ui));
+///     });
+/// });
+/// documents.show(ctx);
+/// # });
+/// ```
+pub struct Documents {
+    id: Id,
+    hosting: DocumentHosting,
+    documents: Vec<OpenDocument>,
+    active: Option<DocumentId>,
+    next_id: u64,
+    recent_files: RecentFiles,
+
+    /// Documents with unsaved changes that the user asked to close, waiting for confirmation.
+    pending_close: Vec<DocumentId>,
+
+    /// Close the app once [`Self::pending_close`] has been dealt with.
+    exit_when_closed: bool,
+
+    /// Why the last save in the confirmation dialog failed.
+    save_error: Option<String>,
+}
+
+impl Documents {
+    /// The `id_salt` is used for the ids of the tabs, viewports and dialogs, and must be unique.
+    pub fn new(id_salt: impl std::hash::Hash) -> Self {
+        Self {
+            id: Id::new(id_salt),
+            hosting: DocumentHosting::default(),
+            documents: Vec::new(),
+            active: None,
+            next_id: 0,
+            recent_files: RecentFiles::default(),
+            pending_close: Vec::new(),
+            exit_when_closed: false,
+            save_error: None,
+        }
+    }
+
+    /// How to show the documents. Default: [`DocumentHosting::Tabs`].
+    #[inline]
+    pub fn hosting(mut self, hosting: DocumentHosting) -> Self {
+        self.hosting = hosting;
+        self
+    }
+
+    /// Start with these recent files, e.g. loaded from storage.
+    #[inline]
+    pub fn with_recent_files(mut self, recent_files: RecentFiles) -> Self {
+        self.recent_files = recent_files;
+        self
+    }
+
+    pub fn recent_files(&self) -> &RecentFiles {
+        &self.recent_files
+    }
+
+    pub fn recent_files_mut(&mut self) -> &mut RecentFiles {
+        &mut self.recent_files
+    }
+
+    /// Add a document and make it the active one.
+    pub fn open(&mut self, document: impl Document + 'static) -> DocumentId {
+        let id = DocumentId(self.next_id);
+        self.next_id += 1;
+        if let Some(path) = document.path() {
+            self.recent_files.add(path);
+        }
+        self.documents.push(OpenDocument {
+            id,
+            document: Box::new(document),
+        });
+        self.active = Some(id);
+        id
+    }
+
+    /// The open document saved to `path`, if any, so you can activate it instead of opening it twice.
+    pub fn find_by_path(&self, path: &Path) -> Option<DocumentId> {
+        self.documents
+            .iter()
+            .find(|doc| doc.document.path().as_deref() == Some(path))
+            .map(|doc| doc.id)
+    }
+
+    pub fn get(&self, id: DocumentId) -> Option<&dyn Document> {
+        self.documents
+            .iter()
+            .find(|doc| doc.id == id)
+            .map(|doc| doc.document.as_ref())
+    }
+
+    pub fn get_mut(&mut self, id: DocumentId) -> Option<&mut (dyn Document + 'static)> {
+        self.documents
+            .iter_mut()
+            .find(|doc| doc.id == id)
+            .map(|doc| doc.document.as_mut())
+    }
+
+    /// The open documents, in the order they were opened.
+    pub fn ids(&self) -> impl Iterator<Item = DocumentId> + '_ {
+        self.documents.iter().map(|doc| doc.id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.documents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.documents.is_empty()
+    }
+
+    /// The selected tab, or the most recently focused viewport.
+    pub fn active(&self) -> Option<DocumentId> {
+        self.active
+    }
+
+    /// Select the tab of the document, or focus its viewport.
+    pub fn set_active(&mut self, ctx: &Context, id: DocumentId) {
+        if self.get(id).is_none() {
+            return;
+        }
+        self.active = Some(id);
+        if self.hosting == DocumentHosting::Viewports {
+            ctx.send_viewport_cmd_to(self.viewport_id(id), ViewportCommand::Focus);
+        }
+    }
+
+    /// Does any open document have unsaved changes?
+    pub fn has_unsaved_changes(&self) -> bool {
+        self.documents.iter().any(|doc| doc.document.is_dirty())
+    }
+
+    /// Save the document, adding it to the [`RecentFiles`].
+    ///
+    /// # Errors
+    /// If [`Document::save`] fails.
+    pub fn save(&mut self, id: DocumentId) -> Result<(), String> {
+        let Some(document) = self.get_mut(id) else {
+            return Ok(());
+        };
+        document.save()?;
+        if let Some(path) = document.path() {
+            self.recent_files.add(path);
+        }
+        Ok(())
+    }
+
+    /// Close the document, first asking the user to save it if it has unsaved changes.
+    pub fn request_close(&mut self, id: DocumentId) {
+        let Some(document) = self.get(id) else {
+            return;
+        };
+        if !document.is_dirty() {
+            self.close(id);
+        } else if !self.pending_close.contains(&id) {
+            self.pending_close.push(id);
+        }
+    }
+
+    /// Close all documents, first asking the user to save the ones with unsaved changes.
+    pub fn request_close_all(&mut self) {
+        for id in self.ids().collect::<Vec<_>>() {
+            self.request_close(id);
+        }
+    }
+
+    /// Close the document right away, throwing away any unsaved changes.
+    pub fn close(&mut self, id: DocumentId) {
+        let Some(index) = self.documents.iter().position(|doc| doc.id == id) else {
+            return;
+        };
+        self.documents.remove(index);
+        self.pending_close.retain(|&pending| pending != id);
+        if self.active == Some(id) {
+            self.active = self
+                .documents
+                .get(index.min(self.documents.len().saturating_sub(1)))
+                .map(|doc| doc.id);
+        }
+    }
+
+    /// Is the user being asked whether to save documents before they are closed?
+    pub fn is_confirming_close(&self) -> bool {
+        !self.pending_close.is_empty()
+    }
+
+    /// A selectable entry for each open document, and buttons to close documents,
+    /// e.g. for a "Window" menu.
+    ///
+    /// Documents with unsaved changes are marked with `*`.
+    pub fn window_menu_ui(&mut self, ui: &mut Ui) {
+        let mut activate = None;
+        for doc in &self.documents {
+            let text = title_text(doc.document.as_ref());
+            if ui
+                .selectable_label(self.active == Some(doc.id), text)
+                .clicked()
+            {
+                activate = Some(doc.id);
+                ui.close_menu();
+            }
+        }
+        if let Some(id) = activate {
+            self.set_active(ui.ctx(), id);
+        }
+
+        if !self.documents.is_empty() {
+            ui.separator();
+        }
+        if ui
+            .add_enabled(self.active.is_some(), egui::Button::new("Close"))
+            .clicked()
+        {
+            if let Some(id) = self.active {
+                self.request_close(id);
+            }
+            ui.close_menu();
+        }
+        if ui
+            .add_enabled(!self.documents.is_empty(), egui::Button::new("Close All"))
+            .clicked()
+        {
+            self.request_close_all();
+            ui.close_menu();
+        }
+    }
+
+    /// Show the documents, and the dialog asking to save unsaved changes.
+    ///
+    /// Call this in the root viewport each frame, after your other panels.
+    /// When the user tries to close the app while there are unsaved changes,
+    /// this cancels the close and asks them what to do first.
+    pub fn show(&mut self, ctx: &Context) {
+        if ctx.input(|i| i.viewport().close_requested()) && self.has_unsaved_changes() {
+            ctx.send_viewport_cmd(ViewportCommand::CancelClose);
+            self.exit_when_closed = true;
+            self.request_close_all();
+        }
+
+        match self.hosting {
+            DocumentHosting::Tabs => self.tabs_ui(ctx),
+            DocumentHosting::Viewports => self.show_viewports(ctx),
+        }
+
+        self.confirm_close_ui(ctx);
+
+        if self.exit_when_closed && self.pending_close.is_empty() {
+            self.exit_when_closed = false;
+            ctx.send_viewport_cmd_to(ViewportId::ROOT, ViewportCommand::Close);
+        }
+    }
+
+    fn viewport_id(&self, id: DocumentId) -> ViewportId {
+        ViewportId::from_hash_of(self.id.with(id))
+    }
+
+    fn tabs_ui(&mut self, ctx: &Context) {
+        CentralPanel::default().show(ctx, |ui| {
+            if self.documents.is_empty() {
+                return;
+            }
+
+            let mut close = None;
+            ui.horizontal_wrapped(|ui| {
+                for doc in &self.documents {
+                    let text = title_text(doc.document.as_ref());
+                    let response = ui.selectable_label(self.active == Some(doc.id), text);
+                    if response.clicked() {
+                        self.active = Some(doc.id);
+                    }
+                    if response.middle_clicked() || ui.small_button("🗙").clicked() {
+                        close = Some(doc.id);
+                    }
+                }
+            });
+            ui.separator();
+
+            if let Some(id) = close {
+                self.request_close(id);
+            }
+
+            let active = self.active;
+            if let Some(doc) = self.documents.iter_mut().find(|doc| Some(doc.id) == active) {
+                ui.push_id(self.id.with(doc.id), |ui| doc.document.ui(ui));
+            }
+        });
+    }
+
+    fn show_viewports(&mut self, ctx: &Context) {
+        let mut close = Vec::new();
+        for doc in &mut self.documents {
+            let title = title_text(doc.document.as_ref());
+            let viewport_id = ViewportId::from_hash_of(self.id.with(doc.id));
+            let builder = ViewportBuilder::default().with_title(&title);
+            ctx.show_viewport_immediate(viewport_id, builder, |ctx, class| {
+                if class == ViewportClass::Embedded {
+                    let mut open = true;
+                    Window::new(title.as_str())
+                        .id(self.id.with(doc.id))
+                        .open(&mut open)
+                        .show(ctx, |ui| doc.document.ui(ui));
+                    if !open {
+                        close.push(doc.id);
+                    }
+                } else {
+                    CentralPanel::default().show(ctx, |ui| doc.document.ui(ui));
+                    if ctx.input(|i| i.viewport().close_requested()) {
+                        // We close the viewport ourselves, once the user has decided what to do with the changes.
+                        ctx.send_viewport_cmd(ViewportCommand::CancelClose);
+                        close.push(doc.id);
+                    }
+                    if ctx.input(|i| i.viewport().focused == Some(true)) {
+                        self.active = Some(doc.id);
+                    }
+                }
+            });
+        }
+
+        for id in close {
+            self.request_close(id);
+        }
+    }
+
+    fn confirm_close_ui(&mut self, ctx: &Context) {
+        // Documents may have been saved or closed by other means:
+        let clean: Vec<DocumentId> = self
+            .pending_close
+            .iter()
+            .copied()
+            .filter(|&id| self.get(id).map_or(true, |doc| !doc.is_dirty()))
+            .collect();
+        for id in clean {
+            self.close(id);
+        }
+        if self.pending_close.is_empty() {
+            self.save_error = None;
+            return;
+        }
+
+        let mut save = false;
+        let mut discard = false;
+        let mut cancel = false;
+        Window::new("Unsaved changes")
+            .id(self.id.with("confirm_close"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label("Do you want to save the changes to these documents before closing them?");
+                for &id in &self.pending_close {
+                    if let Some(document) = self.get(id) {
+                        ui.label(format!("• {}", document.title()));
+                    }
+                }
+                if let Some(err) = &self.save_error {
+                    ui.colored_label(ui.visuals().error_fg_color, err);
+                }
+                ui.horizontal(|ui| {
+                    save = ui.button("Save").clicked();
+                    discard = ui.button("Don't Save").clicked();
+                    cancel = ui.button("Cancel").clicked();
+                });
+            });
+
+        if save {
+            self.save_error = None;
+            for id in self.pending_close.clone() {
+                match self.save(id) {
+                    Ok(()) => self.close(id),
+                    Err(err) => {
+                        self.save_error = Some(err);
+                        break;
+                    }
+                }
+            }
+        } else if discard {
+            for id in self.pending_close.clone() {
+                self.close(id);
+            }
+        } else if cancel {
+            self.pending_close.clear();
+            self.exit_when_closed = false;
+            self.save_error = None;
+        }
+    }
+}
+
+fn title_text(document: &dyn Document) -> String {
+    let title = document.title();
+    if document.is_dirty() {
+        format!("{title}*")
+    } else {
+        title
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TestDocument {
+        dirty: bool,
+    }
+
+    impl Document for TestDocument {
+        fn title(&self) -> String {
+            "Test".to_owned()
+        }
+
+        fn is_dirty(&self) -> bool {
+            self.dirty
+        }
+
+        fn save(&mut self) -> Result<(), String> {
+            self.dirty = false;
+            Ok(())
+        }
+
+        fn ui(&mut self, _ui: &mut Ui) {}
+    }
+
+    #[test]
+    fn recent_files() {
+        let mut recent = RecentFiles::new(2);
+        recent.add("a");
+        recent.add("b");
+        recent.add("a");
+        recent.add("c");
+        let paths: Vec<&Path> = recent.iter().collect();
+        assert_eq!(paths, [Path::new("c"), Path::new("a")]);
+    }
+
+    #[test]
+    fn only_dirty_documents_wait_for_confirmation() {
+        let mut documents = Documents::new("documents");
+        let clean = documents.open(TestDocument { dirty: false });
+        let dirty = documents.open(TestDocument { dirty: true });
+
+        documents.request_close_all();
+        assert!(documents.get(clean).is_none());
+        assert!(documents.get(dirty).is_some());
+        assert!(documents.is_confirming_close());
+        assert_eq!(documents.active(), Some(dirty));
+
+        documents.get_mut(dirty).unwrap().save().unwrap();
+        let ctx = Context::default();
+        let _ = ctx.run(Default::default(), |ctx| documents.show(ctx));
+        assert!(documents.is_empty());
+        assert!(!documents.is_confirming_close());
+    }
+}
@@ -9,8 +9,14 @@
 #![allow(clippy::float_cmp)]
 #![allow(clippy::manual_range_contains)]
 
+mod ansi;
+#[cfg(not(target_arch = "wasm32"))]
+mod asset_watcher;
+mod async_layouter;
+mod console;
 #[cfg(feature = "chrono")]
 mod datepicker;
+mod documents;
 
 pub mod syntax_highlighting;
 
@@ -18,22 +24,43 @@ pub mod syntax_highlighting;
 pub mod image;
 mod layout;
 mod loaders;
+mod panel_registry;
 mod sizing;
+mod snapshot;
 mod strip;
 mod table;
+mod table_edit;
+#[cfg(feature = "terminal")]
+mod terminal;
+mod tree_view;
 
+pub use crate::ansi::AnsiText;
+#[cfg(not(target_arch = "wasm32"))]
+pub use crate::asset_watcher::{add_watched_font, watch_assets};
+pub use crate::async_layouter::AsyncLayouter;
+pub use crate::console::{Console, ConsoleResponse, ConsoleWriter};
 #[cfg(feature = "chrono")]
 pub use crate::datepicker::DatePickerButton;
+pub use crate::documents::{Document, DocumentHosting, DocumentId, Documents, RecentFiles};
 
 #[doc(hidden)]
 #[allow(deprecated)]
 pub use crate::image::RetainedImage;
 pub(crate) use crate::layout::StripLayout;
+pub use crate::panel_registry::{PanelPlacement, PanelRegistry};
 pub use crate::sizing::Size;
+pub use crate::snapshot::{snapshot_channel, SnapshotReader, SnapshotWriter};
 pub use crate::strip::*;
 pub use crate::table::*;
+pub use crate::table_edit::CellEdit;
+#[cfg(feature = "terminal")]
+pub use crate::terminal::{Pty, Terminal, TerminalWriter};
+pub use crate::tree_view::{TreeMove, TreeSource, TreeView, TreeViewResponse, TreeViewState};
 
-pub use loaders::install_image_loaders;
+pub use loaders::{
+    install_image_loaders, on_image_loaded, set_image_load_options, set_image_load_priority,
+    ImageLoadOptions, LoadPriority,
+};
 
 // ---------------------------------------------------------------------------
 
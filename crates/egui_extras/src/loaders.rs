@@ -10,6 +10,8 @@
 /// - `http` feature: `http(s)://` loader
 /// - `image` feature: Loader of png, jpeg etc using the [`image`] crate
 /// - `svg` feature: `.svg` loader
+/// - `gif` feature: animated `.gif` loader
+/// - `webp` feature: still and animated `.webp` loader
 ///
 /// Calling this multiple times on the same [`egui::Context`] is safe.
 /// It will never install duplicate loaders.
@@ -54,19 +56,35 @@
 /// The content type specified by [`BytesPoll::Ready::mime`][`egui::load::BytesPoll::Ready::mime`] always takes precedence,
 /// and must include `svg` for it to be considered supported. For example, `image/svg+xml` would be loaded by the `svg` loader.
 ///
+/// The `gif` and `webp` loaders are [`ImageLoader`][`egui::load::ImageLoader`]s.
+/// They decode all frames of an animation, and [`egui::Image`] shows the frame that is due,
+/// requesting a repaint for the next one (see [`egui::Image::animate`]).
+/// The decoded frames are kept within [`ImageLoadOptions::max_animation_memory`].
+///
+/// ## Scheduling
+///
+/// The `file` and `http` loaders share a queue: images requested during a pass start loading at the end of it,
+/// at most [`ImageLoadOptions::max_concurrent_loads`] at a time, in the order given by [`set_image_load_priority`].
+/// Loads that haven't started yet are cancelled if their image is no longer shown, e.g. when scrolling fast.
+///
+/// A repaint is requested whenever a load finishes, and you can get a callback with [`on_image_loaded`],
+/// so you can turn off the loading spinner of [`egui::Image`] to avoid repainting while waiting.
+///
 /// See [`egui::load`] for more information about how loaders work.
 pub fn install_image_loaders(ctx: &egui::Context) {
     #[cfg(all(not(target_arch = "wasm32"), feature = "file"))]
     if !ctx.is_loader_installed(self::file_loader::FileLoader::ID) {
-        ctx.add_bytes_loader(std::sync::Arc::new(self::file_loader::FileLoader::default()));
+        ctx.add_bytes_loader(std::sync::Arc::new(self::file_loader::FileLoader::new(
+            self::scheduler::LoadScheduler::get_or_install(ctx),
+        )));
         log::trace!("installed FileLoader");
     }
 
     #[cfg(feature = "http")]
     if !ctx.is_loader_installed(self::ehttp_loader::EhttpLoader::ID) {
-        ctx.add_bytes_loader(std::sync::Arc::new(
-            self::ehttp_loader::EhttpLoader::default(),
-        ));
+        ctx.add_bytes_loader(std::sync::Arc::new(self::ehttp_loader::EhttpLoader::new(
+            self::scheduler::LoadScheduler::get_or_install(ctx),
+        )));
         log::trace!("installed EhttpLoader");
     }
 
@@ -84,6 +102,12 @@ pub fn install_image_loaders(ctx: &egui::Context) {
         log::trace!("installed GifLoader");
     }
 
+    #[cfg(feature = "webp")]
+    if !ctx.is_loader_installed(self::webp_loader::WebPLoader::ID) {
+        ctx.add_image_loader(std::sync::Arc::new(self::webp_loader::WebPLoader::default()));
+        log::trace!("installed WebPLoader");
+    }
+
     #[cfg(feature = "svg")]
     if !ctx.is_loader_installed(self::svg_loader::SvgLoader::ID) {
         ctx.add_image_loader(std::sync::Arc::new(self::svg_loader::SvgLoader::default()));
@@ -101,15 +125,28 @@ pub fn install_image_loaders(ctx: &egui::Context) {
     let _ = ctx;
 }
 
-#[cfg(not(target_arch = "wasm32"))]
+// Only the file and http loaders queue their loads.
+#[cfg_attr(not(any(feature = "file", feature = "http")), allow(dead_code))]
+mod scheduler;
+
+pub use scheduler::{
+    on_image_loaded, set_image_load_options, set_image_load_priority, ImageLoadOptions,
+    LoadPriority,
+};
+
+#[cfg(all(not(target_arch = "wasm32"), feature = "file"))]
 mod file_loader;
 
 #[cfg(feature = "http")]
 mod ehttp_loader;
 
+#[cfg(any(feature = "gif", feature = "webp"))]
+mod animated_image;
 #[cfg(feature = "gif")]
 mod gif_loader;
 #[cfg(feature = "image")]
 mod image_loader;
 #[cfg(feature = "svg")]
 mod svg_loader;
+#[cfg(feature = "webp")]
+mod webp_loader;
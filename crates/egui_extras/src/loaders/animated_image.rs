@@ -0,0 +1,211 @@
+//! Decoding and caching of animated images, shared by the gif and webp loaders.
+
+use ahash::HashMap;
+use egui::{
+    decode_animated_image_uri,
+    load::{BytesPoll, ImageLoadResult, ImagePoll, LoadError},
+    mutex::Mutex,
+    ColorImage, FrameDurations, Id, ViewportId,
+};
+use image::AnimationDecoder;
+use std::{mem::size_of, sync::Arc, time::Duration};
+
+use super::scheduler::LoadScheduler;
+
+/// Array of Frames and the duration for how long each frame should be shown
+#[derive(Debug, Clone)]
+pub struct AnimatedImage {
+    frames: Vec<Arc<ColorImage>>,
+    frame_durations: FrameDurations,
+}
+
+impl AnimatedImage {
+    /// Decode all frames, e.g. of an [`image::codecs::gif::GifDecoder`].
+    pub fn from_decoder<'a>(decoder: impl AnimationDecoder<'a>) -> Result<Self, String> {
+        let mut images = vec![];
+        let mut durations = vec![];
+        for frame in decoder.into_frames() {
+            let frame = frame.map_err(|err| err.to_string())?;
+            let img = frame.buffer();
+            let pixels = img.as_flat_samples();
+
+            let delay: Duration = frame.delay().into();
+            images.push(Arc::new(ColorImage::from_rgba_unmultiplied(
+                [img.width() as usize, img.height() as usize],
+                pixels.as_slice(),
+            )));
+            durations.push(delay);
+        }
+        if images.is_empty() {
+            return Err("No frames".to_owned());
+        }
+        Ok(Self {
+            frames: images,
+            frame_durations: FrameDurations(Arc::new(durations)),
+        })
+    }
+
+    /// A single frame.
+    #[cfg(feature = "webp")]
+    pub fn from_still(image: ColorImage) -> Self {
+        Self {
+            frames: vec![Arc::new(image)],
+            frame_durations: FrameDurations(Arc::new(vec![Duration::ZERO])),
+        }
+    }
+
+    pub fn byte_len(&self) -> usize {
+        size_of::<Self>()
+            + self
+                .frames
+                .iter()
+                .map(|image| {
+                    image.pixels.len() * size_of::<egui::Color32>() + size_of::<Duration>()
+                })
+                .sum::<usize>()
+    }
+
+    /// Gets image at index
+    pub fn get_image(&self, index: usize) -> Arc<ColorImage> {
+        self.frames[index % self.frames.len()].clone()
+    }
+}
+
+struct Entry {
+    result: Result<Arc<AnimatedImage>, String>,
+
+    /// The pass of the viewport that last showed this image.
+    last_used: (ViewportId, u64),
+
+    /// Lower means used longer ago.
+    order: u64,
+}
+
+impl Entry {
+    fn byte_len(&self) -> usize {
+        match &self.result {
+            Ok(image) => image.byte_len(),
+            Err(err) => err.len(),
+        }
+    }
+}
+
+/// The decoded animations of one loader,
+/// dropping the least recently used ones when over [`crate::ImageLoadOptions::max_animation_memory`].
+#[derive(Default)]
+pub struct AnimatedImageCache {
+    entries: Mutex<HashMap<String, Entry>>,
+    next_order: std::sync::atomic::AtomicU64,
+}
+
+impl AnimatedImageCache {
+    /// Load `uri`, which may have a frame index appended with [`egui::decode_animated_image_uri`].
+    ///
+    /// Without a frame index the first frame is returned,
+    /// and if the image turns out to be animated, its [`FrameDurations`] are stored
+    /// so that [`egui::Image`] asks for the other frames.
+    ///
+    /// Returns [`LoadError::NotSupported`] if `is_supported` returns `false` for the bytes.
+    pub fn load(
+        &self,
+        ctx: &egui::Context,
+        uri: &str,
+        is_supported: impl Fn(&[u8]) -> bool,
+        decode: impl Fn(&[u8]) -> Result<AnimatedImage, String>,
+    ) -> ImageLoadResult {
+        let (image_uri, frame_index) = decode_animated_image_uri(uri).unwrap_or((uri, 0));
+        let last_used = (ctx.viewport_id(), ctx.cumulative_pass_nr());
+        let order = self
+            .next_order
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+
+        let mut entries = self.entries.lock();
+        let result = if let Some(entry) = entries.get_mut(image_uri) {
+            entry.last_used = last_used;
+            entry.order = order;
+            entry.result.clone()
+        } else {
+            match ctx.try_load_bytes(image_uri) {
+                Ok(BytesPoll::Ready { bytes, .. }) => {
+                    if !is_supported(&bytes) {
+                        return Err(LoadError::NotSupported);
+                    }
+                    log::trace!("started loading {image_uri:?}");
+                    let result = decode(&bytes).map(Arc::new);
+                    log::trace!("finished loading {image_uri:?}");
+                    if let Ok(image) = &result {
+                        if image.frames.len() > 1 {
+                            ctx.data_mut(|data| {
+                                *data.get_temp_mut_or_default(Id::new(image_uri)) =
+                                    image.frame_durations.clone();
+                            });
+                        }
+                    }
+                    entries.insert(
+                        image_uri.to_owned(),
+                        Entry {
+                            result: result.clone(),
+                            last_used,
+                            order,
+                        },
+                    );
+                    let max_bytes = LoadScheduler::get_or_install(ctx)
+                        .options()
+                        .max_animation_memory;
+                    evict(ctx, &mut entries, max_bytes);
+                    result
+                }
+                Ok(BytesPoll::Pending { size }) => return Ok(ImagePoll::Pending { size }),
+                Err(err) => return Err(err),
+            }
+        };
+
+        match result {
+            Ok(image) => Ok(ImagePoll::Ready {
+                image: image.get_image(frame_index),
+            }),
+            Err(err) => Err(LoadError::Loading(err)),
+        }
+    }
+
+    pub fn forget(&self, uri: &str) {
+        let _ = self.entries.lock().remove(uri);
+    }
+
+    pub fn forget_all(&self) {
+        self.entries.lock().clear();
+    }
+
+    pub fn byte_size(&self) -> usize {
+        self.entries.lock().values().map(Entry::byte_len).sum()
+    }
+}
+
+/// Drop the least recently used entries that weren't shown in the current pass of their viewport,
+/// until the rest fits in `max_bytes`.
+fn evict(ctx: &egui::Context, entries: &mut HashMap<String, Entry>, max_bytes: usize) {
+    let mut total: usize = entries.values().map(Entry::byte_len).sum();
+    if total <= max_bytes {
+        return;
+    }
+
+    let mut unused: Vec<(u64, String)> = entries
+        .iter()
+        .filter(|(_, entry)| {
+            let (viewport_id, pass_nr) = entry.last_used;
+            pass_nr < ctx.cumulative_pass_nr_for(viewport_id)
+        })
+        .map(|(uri, entry)| (entry.order, uri.clone()))
+        .collect();
+    unused.sort_unstable();
+
+    for (_, uri) in unused {
+        if total <= max_bytes {
+            break;
+        }
+        if let Some(entry) = entries.remove(&uri) {
+            log::trace!("dropping the frames of {uri:?}");
+            total -= entry.byte_len();
+        }
+    }
+}
@@ -5,6 +5,8 @@ use egui::{
 };
 use std::{sync::Arc, task::Poll};
 
+use super::scheduler::{LoadFinished, LoadScheduler};
+
 #[derive(Clone)]
 struct File {
     bytes: Arc<[u8]>,
@@ -39,13 +41,22 @@ impl File {
 
 type Entry = Poll<Result<File, String>>;
 
-#[derive(Default)]
 pub struct EhttpLoader {
     cache: Arc<Mutex<HashMap<String, Entry>>>,
+
+    /// Decides when to start each request.
+    scheduler: Arc<LoadScheduler>,
 }
 
 impl EhttpLoader {
     pub const ID: &'static str = egui::generate_loader_id!(EhttpLoader);
+
+    pub(crate) fn new(scheduler: Arc<LoadScheduler>) -> Self {
+        Self {
+            cache: Default::default(),
+            scheduler,
+        }
+    }
 }
 
 const PROTOCOLS: &[&str] = &["http://", "https://"];
@@ -73,32 +84,46 @@ impl BytesLoader for EhttpLoader {
                     mime: file.mime,
                 }),
                 Poll::Ready(Err(err)) => Err(LoadError::Loading(err)),
-                Poll::Pending => Ok(BytesPoll::Pending { size: None }),
+                Poll::Pending => {
+                    drop(cache);
+                    self.scheduler.still_wanted(ctx, Self::ID, uri);
+                    Ok(BytesPoll::Pending { size: None })
+                }
             }
         } else {
-            log::trace!("started loading {uri:?}");
+            log::trace!("queued loading {uri:?}");
 
-            let uri = uri.to_owned();
-            cache.insert(uri.clone(), Poll::Pending);
+            cache.insert(uri.to_owned(), Poll::Pending);
             drop(cache);
 
-            ehttp::fetch(ehttp::Request::get(uri.clone()), {
-                let ctx = ctx.clone();
+            let start = {
+                let cache = self.cache.clone();
+                let uri = uri.to_owned();
+                move |finished: LoadFinished| {
+                    log::trace!("started loading {uri:?}");
+                    ehttp::fetch(ehttp::Request::get(uri.clone()), move |response| {
+                        let result = match response {
+                            Ok(response) => File::from_response(&uri, response),
+                            Err(err) => {
+                                // Log details; return summary
+                                log::error!("Failed to load {uri:?}: {err}");
+                                Err(format!("Failed to load {uri:?}"))
+                            }
+                        };
+                        log::trace!("finished loading {uri:?}");
+                        cache.lock().insert(uri, Poll::Ready(result));
+                        finished.finish();
+                    });
+                }
+            };
+            let cancel = {
                 let cache = self.cache.clone();
-                move |response| {
-                    let result = match response {
-                        Ok(response) => File::from_response(&uri, response),
-                        Err(err) => {
-                            // Log details; return summary
-                            log::error!("Failed to load {uri:?}: {err}");
-                            Err(format!("Failed to load {uri:?}"))
-                        }
-                    };
-                    log::trace!("finished loading {uri:?}");
-                    cache.lock().insert(uri, Poll::Ready(result));
-                    ctx.request_repaint();
+                let uri = uri.to_owned();
+                move || {
+                    cache.lock().remove(&uri);
                 }
-            });
+            };
+            self.scheduler.request(ctx, Self::ID, uri, start, cancel);
 
             Ok(BytesPoll::Pending { size: None })
         }
@@ -106,10 +131,12 @@ impl BytesLoader for EhttpLoader {
 
     fn forget(&self, uri: &str) {
         let _ = self.cache.lock().remove(uri);
+        self.scheduler.forget(Self::ID, uri);
     }
 
     fn forget_all(&self) {
         self.cache.lock().clear();
+        self.scheduler.forget_all(Self::ID);
     }
 
     fn byte_size(&self) -> usize {
@@ -5,6 +5,8 @@ use egui::{
 };
 use std::{sync::Arc, task::Poll, thread};
 
+use super::scheduler::{LoadFinished, LoadScheduler};
+
 #[derive(Clone)]
 struct File {
     bytes: Arc<[u8]>,
@@ -13,14 +15,23 @@ struct File {
 
 type Entry = Poll<Result<File, String>>;
 
-#[derive(Default)]
 pub struct FileLoader {
     /// Cache for loaded files
     cache: Arc<Mutex<HashMap<String, Entry>>>,
+
+    /// Decides when to start reading each file.
+    scheduler: Arc<LoadScheduler>,
 }
 
 impl FileLoader {
     pub const ID: &'static str = egui::generate_loader_id!(FileLoader);
+
+    pub(crate) fn new(scheduler: Arc<LoadScheduler>) -> Self {
+        Self {
+            cache: Default::default(),
+            scheduler,
+        }
+    }
 }
 
 const PROTOCOL: &str = "file://";
@@ -59,10 +70,14 @@ impl BytesLoader for FileLoader {
                     mime: file.mime,
                 }),
                 Poll::Ready(Err(err)) => Err(LoadError::Loading(err)),
-                Poll::Pending => Ok(BytesPoll::Pending { size: None }),
+                Poll::Pending => {
+                    drop(cache);
+                    self.scheduler.still_wanted(ctx, Self::ID, uri);
+                    Ok(BytesPoll::Pending { size: None })
+                }
             }
         } else {
-            log::trace!("started loading {uri:?}");
+            log::trace!("queued loading {uri:?}");
             // We need to load the file at `path`.
 
             // Set the file to `pending` until we finish loading it.
@@ -70,38 +85,57 @@ impl BytesLoader for FileLoader {
             cache.insert(uri.to_owned(), Poll::Pending);
             drop(cache);
 
-            // Spawn a thread to read the file, so that we don't block the render for too long.
-            thread::Builder::new()
-                .name(format!("egui_extras::FileLoader::load({uri:?})"))
-                .spawn({
-                    let ctx = ctx.clone();
-                    let cache = self.cache.clone();
-                    let uri = uri.to_owned();
-                    move || {
-                        let result = match std::fs::read(&path) {
-                            Ok(bytes) => {
-                                #[cfg(feature = "file")]
-                                let mime = mime_guess2::from_path(&path)
-                                    .first_raw()
-                                    .map(|v| v.to_owned());
-
-                                #[cfg(not(feature = "file"))]
-                                let mime = None;
-
-                                Ok(File {
-                                    bytes: bytes.into(),
-                                    mime,
-                                })
-                            }
-                            Err(err) => Err(err.to_string()),
-                        };
-                        let prev = cache.lock().insert(uri.clone(), Poll::Ready(result));
-                        assert!(matches!(prev, Some(Poll::Pending)));
-                        ctx.request_repaint();
-                        log::trace!("finished loading {uri:?}");
-                    }
-                })
-                .expect("failed to spawn thread");
+            if let Some(watcher) = crate::asset_watcher::AssetWatcher::get(ctx) {
+                let asset = crate::asset_watcher::Asset::Image {
+                    uri: uri.to_owned(),
+                };
+                watcher.watch(asset, path.clone().into());
+            }
+
+            // Read the file once the scheduler says there is room for it.
+            let start = {
+                let cache = self.cache.clone();
+                let uri = uri.to_owned();
+                move |finished: LoadFinished| {
+                    log::trace!("started loading {uri:?}");
+
+                    // Spawn a thread to read the file, so that we don't block the render for too long.
+                    thread::Builder::new()
+                        .name(format!("egui_extras::FileLoader::load({uri:?})"))
+                        .spawn(move || {
+                            let result = match std::fs::read(&path) {
+                                Ok(bytes) => {
+                                    #[cfg(feature = "file")]
+                                    let mime = mime_guess2::from_path(&path)
+                                        .first_raw()
+                                        .map(|v| v.to_owned());
+
+                                    #[cfg(not(feature = "file"))]
+                                    let mime = None;
+
+                                    Ok(File {
+                                        bytes: bytes.into(),
+                                        mime,
+                                    })
+                                }
+                                Err(err) => Err(err.to_string()),
+                            };
+                            let prev = cache.lock().insert(uri.clone(), Poll::Ready(result));
+                            assert!(matches!(prev, Some(Poll::Pending)));
+                            finished.finish();
+                            log::trace!("finished loading {uri:?}");
+                        })
+                        .expect("failed to spawn thread");
+                }
+            };
+            let cancel = {
+                let cache = self.cache.clone();
+                let uri = uri.to_owned();
+                move || {
+                    cache.lock().remove(&uri);
+                }
+            };
+            self.scheduler.request(ctx, Self::ID, uri, start, cancel);
 
             Ok(BytesPoll::Pending { size: None })
         }
@@ -109,10 +143,12 @@ impl BytesLoader for FileLoader {
 
     fn forget(&self, uri: &str) {
         let _ = self.cache.lock().remove(uri);
+        self.scheduler.forget(Self::ID, uri);
     }
 
     fn forget_all(&self) {
         self.cache.lock().clear();
+        self.scheduler.forget_all(Self::ID);
     }
 
     fn byte_size(&self) -> usize {
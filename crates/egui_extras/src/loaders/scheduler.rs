@@ -0,0 +1,395 @@
+//! Decides when the files and http requests of the image loaders are started.
+
+use std::sync::Arc;
+
+use ahash::HashMap;
+use egui::{mutex::Mutex, Context, Id, ViewportId};
+
+/// How urgently an image should be loaded, compared to other images waiting to be loaded.
+///
+/// See [`set_image_load_priority`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LoadPriority {
+    /// E.g. images that are prefetched, or are scrolled out of view.
+    Low,
+
+    /// The default.
+    #[default]
+    Normal,
+
+    /// E.g. images that are visible.
+    High,
+}
+
+/// Options for how the image loaders of [`crate::install_image_loaders`] schedule their work.
+///
+/// See [`set_image_load_options`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ImageLoadOptions {
+    /// How many files or http requests may be loading at the same time.
+    ///
+    /// Default: 8.
+    pub max_concurrent_loads: usize,
+
+    /// A load that hasn't started yet is cancelled if its image was not requested
+    /// during this many passes of the viewport that last requested it,
+    /// e.g. because it was scrolled out of view.
+    ///
+    /// Set to `u64::MAX` to never cancel loads. Default: 2.
+    pub cancel_after_passes: u64,
+
+    /// How many bytes the decoded frames of animated images, like gifs and webps, may use.
+    ///
+    /// When over budget, the frames of the animations that were least recently shown are dropped,
+    /// and decoded again if they are shown again.
+    /// Animations shown in the current pass are never dropped.
+    ///
+    /// Default: 256 MiB.
+    pub max_animation_memory: usize,
+}
+
+impl Default for ImageLoadOptions {
+    fn default() -> Self {
+        Self {
+            max_concurrent_loads: 8,
+            cancel_after_passes: 2,
+            max_animation_memory: 256 * 1024 * 1024,
+        }
+    }
+}
+
+/// Hint how urgently the image at `uri` should be loaded.
+///
+/// Images that are requested during a pass are queued, and at the end of the pass
+/// as many as [`ImageLoadOptions::max_concurrent_loads`] allows start loading,
+/// highest priority first and otherwise in the order they were first requested.
+///
+/// Call this before showing the image, each pass for as long as it is waiting to be loaded.
+/// For instance, when showing many images in a [`egui::ScrollArea`] you can prioritize the visible ones:
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// # let uri = "https://example.com/image.png";
+/// let rect = ui.available_rect_before_wrap();
+/// let priority = if ui.is_rect_visible(rect) {
+///     egui_extras::LoadPriority::High
+/// } else {
+///     egui_extras::LoadPriority::Low
+/// };
+/// egui_extras::set_image_load_priority(ui.ctx(), uri, priority);
+/// ui.add(egui::Image::new(uri).show_loading_spinner(false));
+/// # });
+/// ```
+pub fn set_image_load_priority(ctx: &Context, uri: &str, priority: LoadPriority) {
+    LoadScheduler::get_or_install(ctx).set_priority(uri, priority);
+}
+
+/// Change how the image loaders of [`crate::install_image_loaders`] schedule their work.
+pub fn set_image_load_options(ctx: &Context, options: ImageLoadOptions) {
+    let scheduler = LoadScheduler::get_or_install(ctx);
+    scheduler.state.lock().options = options;
+    scheduler.dispatch(ctx);
+}
+
+/// Call `callback` with the uri of each file or http request that finishes loading, successfully or not.
+///
+/// A repaint is requested at the same time,
+/// so you don't need to keep repainting (e.g. with a loading spinner) while waiting for images.
+///
+/// The callback is called from the thread that did the loading.
+pub fn on_image_loaded(ctx: &Context, callback: impl Fn(&Context, &str) + Send + Sync + 'static) {
+    LoadScheduler::get_or_install(ctx)
+        .state
+        .lock()
+        .on_loaded
+        .push(Arc::new(callback));
+}
+
+type StartFn = Box<dyn FnOnce(LoadFinished) + Send>;
+type CancelFn = Box<dyn FnOnce() + Send>;
+type LoadedCallback = Arc<dyn Fn(&Context, &str) + Send + Sync>;
+
+/// A load that has been requested, but not started.
+struct Queued {
+    loader: &'static str,
+    uri: String,
+    priority: LoadPriority,
+
+    /// Lower means requested earlier.
+    order: u64,
+
+    viewport_id: ViewportId,
+    last_requested_pass: u64,
+
+    start: StartFn,
+
+    /// Called instead of `start` if the load is cancelled.
+    cancel: CancelFn,
+}
+
+#[derive(Default)]
+struct State {
+    options: ImageLoadOptions,
+    queue: Vec<Queued>,
+    num_in_flight: usize,
+    next_order: u64,
+
+    /// Priorities set for uris that have not been requested yet. Cleared at the end of each pass.
+    priority_hints: HashMap<String, LoadPriority>,
+
+    on_loaded: Vec<LoadedCallback>,
+}
+
+/// Shared by the loaders that read files or do http requests,
+/// so that they have a common limit on concurrent loads.
+#[derive(Default)]
+pub(crate) struct LoadScheduler {
+    state: Mutex<State>,
+}
+
+impl LoadScheduler {
+    /// Get the scheduler of this context, creating it if needed.
+    pub(crate) fn get_or_install(ctx: &Context) -> Arc<Self> {
+        let id = Id::new("egui_extras::LoadScheduler");
+        if let Some(scheduler) = ctx.data(|d| d.get_temp::<Arc<Self>>(id)) {
+            return scheduler;
+        }
+
+        let scheduler = Arc::new(Self::default());
+        ctx.data_mut(|d| d.insert_temp(id, scheduler.clone()));
+        ctx.on_end_pass(
+            "egui_extras::LoadScheduler",
+            Arc::new({
+                let scheduler = scheduler.clone();
+                move |ctx| scheduler.end_pass(ctx)
+            }),
+        );
+        scheduler
+    }
+
+    #[cfg(any(feature = "gif", feature = "webp"))]
+    pub(crate) fn options(&self) -> ImageLoadOptions {
+        self.state.lock().options
+    }
+
+    /// Queue a load, which is started by calling `start` once there is room for it.
+    ///
+    /// `start` must call [`LoadFinished::finish`] once the load is done.
+    /// If the load is cancelled before it starts, `cancel` is called instead,
+    /// and should forget the uri so that it is requested again if it is needed after all.
+    pub(crate) fn request(
+        &self,
+        ctx: &Context,
+        loader: &'static str,
+        uri: &str,
+        start: impl FnOnce(LoadFinished) + Send + 'static,
+        cancel: impl FnOnce() + Send + 'static,
+    ) {
+        let mut state = self.state.lock();
+        let priority = state.priority_hints.remove(uri).unwrap_or_default();
+        let order = state.next_order;
+        state.next_order += 1;
+        state.queue.push(Queued {
+            loader,
+            uri: uri.to_owned(),
+            priority,
+            order,
+            viewport_id: ctx.viewport_id(),
+            last_requested_pass: ctx.cumulative_pass_nr(),
+            start: Box::new(start),
+            cancel: Box::new(cancel),
+        });
+    }
+
+    /// The loader was asked for `uri` again while it is still pending.
+    pub(crate) fn still_wanted(&self, ctx: &Context, loader: &'static str, uri: &str) {
+        let mut state = self.state.lock();
+        if let Some(queued) = state.find_mut(loader, uri) {
+            queued.viewport_id = ctx.viewport_id();
+            queued.last_requested_pass = ctx.cumulative_pass_nr();
+        }
+    }
+
+    /// Remove `uri` from the queue, without calling its `cancel`.
+    pub(crate) fn forget(&self, loader: &'static str, uri: &str) {
+        self.state
+            .lock()
+            .queue
+            .retain(|queued| queued.loader != loader || queued.uri != uri);
+    }
+
+    /// Remove everything requested by `loader` from the queue, without calling their `cancel`.
+    pub(crate) fn forget_all(&self, loader: &'static str) {
+        self.state
+            .lock()
+            .queue
+            .retain(|queued| queued.loader != loader);
+    }
+
+    fn set_priority(&self, uri: &str, priority: LoadPriority) {
+        let mut state = self.state.lock();
+        let mut found = false;
+        for queued in state.queue.iter_mut().filter(|queued| queued.uri == uri) {
+            queued.priority = priority;
+            found = true;
+        }
+        if !found {
+            state.priority_hints.insert(uri.to_owned(), priority);
+        }
+    }
+
+    fn end_pass(self: &Arc<Self>, ctx: &Context) {
+        let viewport_id = ctx.viewport_id();
+        let pass_nr = ctx.cumulative_pass_nr();
+
+        let cancelled: Vec<Queued> = {
+            let mut state = self.state.lock();
+            state.priority_hints.clear();
+            let cancel_after_passes = state.options.cancel_after_passes.max(1);
+            let (cancelled, kept) =
+                std::mem::take(&mut state.queue)
+                    .into_iter()
+                    .partition(|queued| {
+                        queued.viewport_id == viewport_id
+                            && cancel_after_passes
+                                <= pass_nr.saturating_sub(queued.last_requested_pass)
+                    });
+            state.queue = kept;
+            cancelled
+        };
+
+        for queued in cancelled {
+            log::trace!("cancelled loading {:?}", queued.uri);
+            (queued.cancel)();
+        }
+
+        self.dispatch(ctx);
+    }
+
+    /// Start as many of the queued loads as we have room for.
+    fn dispatch(self: &Arc<Self>, ctx: &Context) {
+        let started: Vec<Queued> = {
+            let mut state = self.state.lock();
+            let mut started = Vec::new();
+            while state.num_in_flight < state.options.max_concurrent_loads.max(1) {
+                let Some(index) = state.next_index() else {
+                    break;
+                };
+                started.push(state.queue.swap_remove(index));
+                state.num_in_flight += 1;
+            }
+            started
+        };
+
+        for queued in started {
+            (queued.start)(LoadFinished {
+                ctx: ctx.clone(),
+                scheduler: self.clone(),
+                uri: queued.uri,
+            });
+        }
+    }
+}
+
+impl State {
+    fn find_mut(&mut self, loader: &'static str, uri: &str) -> Option<&mut Queued> {
+        self.queue
+            .iter_mut()
+            .find(|queued| queued.loader == loader && queued.uri == uri)
+    }
+
+    /// The index of the queued load to start next.
+    fn next_index(&self) -> Option<usize> {
+        self.queue
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, queued)| (queued.priority, std::cmp::Reverse(queued.order)))
+            .map(|(index, _)| index)
+    }
+}
+
+/// Given to a started load, which must call [`Self::finish`] when done.
+///
+/// Dropping it also counts as finishing.
+pub(crate) struct LoadFinished {
+    ctx: Context,
+    scheduler: Arc<LoadScheduler>,
+    uri: String,
+}
+
+impl LoadFinished {
+    /// Call this after the result has been stored, so that it is there when the ui repaints.
+    pub(crate) fn finish(self) {
+        drop(self);
+    }
+}
+
+impl Drop for LoadFinished {
+    fn drop(&mut self) {
+        let on_loaded = {
+            let mut state = self.scheduler.state.lock();
+            state.num_in_flight = state.num_in_flight.saturating_sub(1);
+            state.on_loaded.clone()
+        };
+        for callback in &on_loaded {
+            callback(&self.ctx, &self.uri);
+        }
+        self.ctx.request_repaint();
+        self.scheduler.dispatch(&self.ctx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn priority_and_cancellation() {
+        let ctx = Context::default();
+        let scheduler = LoadScheduler::get_or_install(&ctx);
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let in_flight = Arc::new(Mutex::new(Vec::new()));
+        let request = |ctx: &Context, uri: &'static str| {
+            let (log, cancel_log, in_flight) = (log.clone(), log.clone(), in_flight.clone());
+            scheduler.request(
+                ctx,
+                "test",
+                uri,
+                move |finished| {
+                    log.lock().push(format!("start {uri}"));
+                    in_flight.lock().push(finished);
+                },
+                move || cancel_log.lock().push(format!("cancel {uri}")),
+            );
+        };
+        set_image_load_options(
+            &ctx,
+            ImageLoadOptions {
+                max_concurrent_loads: 1,
+                cancel_after_passes: 1,
+                ..Default::default()
+            },
+        );
+
+        // Nothing starts until the end of the pass, so that the priorities can be compared:
+        let _ = ctx.run(Default::default(), |ctx| {
+            request(ctx, "a");
+            set_image_load_priority(ctx, "b", LoadPriority::High);
+            request(ctx, "b");
+            request(ctx, "c");
+            assert!(log.lock().is_empty());
+        });
+        assert_eq!(*log.lock(), ["start b"]);
+
+        // "c" is no longer requested, so it is cancelled:
+        let _ = ctx.run(Default::default(), |ctx| {
+            scheduler.still_wanted(ctx, "test", "a");
+        });
+        assert_eq!(*log.lock(), ["start b", "cancel c"]);
+
+        // Finishing "b" makes room for "a":
+        let finished = in_flight.lock().pop().unwrap();
+        finished.finish();
+        assert_eq!(*log.lock(), ["start b", "cancel c", "start a"]);
+    }
+}
@@ -0,0 +1,52 @@
+use egui::{
+    has_webp_header,
+    load::{ImageLoadResult, ImageLoader, SizeHint},
+};
+use std::io::Cursor;
+
+use super::animated_image::{AnimatedImage, AnimatedImageCache};
+
+fn load_webp(data: &[u8]) -> Result<AnimatedImage, String> {
+    let decoder = image::codecs::webp::WebPDecoder::new(Cursor::new(data))
+        .map_err(|err| format!("Failed to decode webp: {err}"))?;
+    if decoder.has_animation() {
+        AnimatedImage::from_decoder(decoder).map_err(|err| format!("Failed to decode webp: {err}"))
+    } else {
+        crate::image::load_image_bytes(data).map(AnimatedImage::from_still)
+    }
+}
+
+/// Loads both still and animated WebPs.
+///
+/// A WebP is only known to be animated once it is decoded,
+/// so unlike with gifs, [`egui::Image`] first asks for the uri without a frame index.
+#[derive(Default)]
+pub struct WebPLoader {
+    cache: AnimatedImageCache,
+}
+
+impl WebPLoader {
+    pub const ID: &'static str = egui::generate_loader_id!(WebPLoader);
+}
+
+impl ImageLoader for WebPLoader {
+    fn id(&self) -> &str {
+        Self::ID
+    }
+
+    fn load(&self, ctx: &egui::Context, uri: &str, _: SizeHint) -> ImageLoadResult {
+        self.cache.load(ctx, uri, has_webp_header, load_webp)
+    }
+
+    fn forget(&self, uri: &str) {
+        self.cache.forget(uri);
+    }
+
+    fn forget_all(&self) {
+        self.cache.forget_all();
+    }
+
+    fn byte_size(&self) -> usize {
+        self.cache.byte_size()
+    }
+}
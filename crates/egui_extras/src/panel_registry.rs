@@ -0,0 +1,381 @@
+use std::collections::BTreeMap;
+
+use egui::{Context, Id, ScrollArea, SidePanel, TopBottomPanel, Ui, Window};
+
+/// Where a panel registered in a [`PanelRegistry`] is shown.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum PanelPlacement {
+    /// Docked in a side panel to the left.
+    Left,
+
+    /// Docked in a side panel to the right.
+    Right,
+
+    /// Docked in a panel at the bottom.
+    Bottom,
+
+    /// In a floating [`Window`].
+    Floating,
+}
+
+impl PanelPlacement {
+    pub const ALL: [Self; 4] = [Self::Left, Self::Right, Self::Bottom, Self::Floating];
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Left => "Dock left",
+            Self::Right => "Dock right",
+            Self::Bottom => "Dock bottom",
+            Self::Floating => "Floating",
+        }
+    }
+}
+
+type PanelUi = Box<dyn FnMut(&mut Ui) + Send>;
+type PanelFactory = Box<dyn Fn() -> PanelUi + Send + Sync>;
+
+struct RegisteredPanel {
+    id: String,
+    title: String,
+    default_placement: PanelPlacement,
+    default_enabled: bool,
+    factory: PanelFactory,
+
+    /// Created by the factory the first time the panel is shown.
+    instance: Option<PanelUi>,
+}
+
+/// The user-editable layout of the workspace.
+///
+/// Stored in [`egui::Memory`], so that it is persisted if the `serde` feature is on.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+struct WorkspaceState {
+    enabled: BTreeMap<String, bool>,
+    placement: BTreeMap<String, PanelPlacement>,
+
+    /// Which panel is the selected tab of each docking slot.
+    active_tab: BTreeMap<PanelPlacement, String>,
+}
+
+impl WorkspaceState {
+    fn load(ctx: &Context, id: Id) -> Self {
+        #[cfg(feature = "serde")]
+        let state = ctx.data_mut(|d| d.get_persisted::<Self>(id));
+        #[cfg(not(feature = "serde"))]
+        let state = ctx.data_mut(|d| d.get_temp::<Self>(id));
+        state.unwrap_or_default()
+    }
+
+    fn store(self, ctx: &Context, id: Id) {
+        #[cfg(feature = "serde")]
+        ctx.data_mut(|d| d.insert_persisted(id, self));
+        #[cfg(not(feature = "serde"))]
+        ctx.data_mut(|d| d.insert_temp(id, self));
+    }
+
+    fn is_enabled(&self, panel: &RegisteredPanel) -> bool {
+        self.enabled
+            .get(&panel.id)
+            .copied()
+            .unwrap_or(panel.default_enabled)
+    }
+
+    fn placement(&self, panel: &RegisteredPanel) -> PanelPlacement {
+        self.placement
+            .get(&panel.id)
+            .copied()
+            .unwrap_or(panel.default_placement)
+    }
+}
+
+/// A registry of named panels that can be added and removed at runtime.
+///
+/// This is the backbone of an extensible editor shell:
+/// the host application and its plugins register panels with a factory closure,
+/// and the host shows all enabled panels with [`Self::show`].
+///
+/// Panels docked to the same side are shown as tabs.
+/// The user can enable/disable panels (see [`Self::toggles_ui`])
+/// and move them between docking slots by right-clicking their tab.
+/// This workspace layout is remembered in [`egui::Memory`].
+///
+/// ```
+/// # egui::__run_test_ctx(|ctx| {
+/// use egui_extras::{PanelPlacement, PanelRegistry};
+///
+/// let mut registry = PanelRegistry::new("workspace");
+/// registry.register("outliner", "Outliner", PanelPlacement::Left, || {
+///     Box::new(|ui: &mut egui::Ui| {
+///         ui.label("Scene objects go here");
+///     })
+/// });
+///
+/// // Each frame, before the central panel:
+/// registry.show(ctx);
+/// egui::CentralPanel::default().show(ctx, |ui| {
+///     ui.label("Viewport");
+/// });
+/// # });
+/// ```
+pub struct PanelRegistry {
+    id: Id,
+    panels: Vec<RegisteredPanel>,
+}
+
+impl PanelRegistry {
+    /// The `id_salt` is used to store the workspace layout, and must be unique.
+    pub fn new(id_salt: impl std::hash::Hash) -> Self {
+        Self {
+            id: Id::new(id_salt),
+            panels: Vec::new(),
+        }
+    }
+
+    /// Register a panel.
+    ///
+    /// The `factory` is called to create the panel the first time it is shown.
+    /// The panel keeps its state while disabled, until it is [unregistered](Self::unregister).
+    ///
+    /// Panels are shown in registration order.
+    /// Registering a panel with the same `id` again replaces it.
+    pub fn register<F>(
+        &mut self,
+        id: impl Into<String>,
+        title: impl Into<String>,
+        placement: PanelPlacement,
+        factory: impl Fn() -> F + Send + Sync + 'static,
+    ) -> &mut Self
+    where
+        F: FnMut(&mut Ui) + Send + 'static,
+    {
+        let panel = RegisteredPanel {
+            id: id.into(),
+            title: title.into(),
+            default_placement: placement,
+            default_enabled: true,
+            factory: Box::new(move || Box::new(factory())),
+            instance: None,
+        };
+
+        if let Some(existing) = self.panels.iter_mut().find(|p| p.id == panel.id) {
+            *existing = panel;
+        } else {
+            self.panels.push(panel);
+        }
+        self
+    }
+
+    /// Remove a panel, dropping its state.
+    ///
+    /// Returns `false` if there was no panel with that id.
+    pub fn unregister(&mut self, id: &str) -> bool {
+        let len_before = self.panels.len();
+        self.panels.retain(|p| p.id != id);
+        self.panels.len() != len_before
+    }
+
+    /// Is there a panel with this id?
+    pub fn contains(&self, id: &str) -> bool {
+        self.panels.iter().any(|p| p.id == id)
+    }
+
+    /// The ids of all registered panels, in registration order.
+    pub fn ids(&self) -> impl Iterator<Item = &str> + '_ {
+        self.panels.iter().map(|p| p.id.as_str())
+    }
+
+    /// Is the panel enabled (shown)?
+    ///
+    /// Panels are enabled by default.
+    pub fn is_enabled(&self, ctx: &Context, id: &str) -> bool {
+        let state = WorkspaceState::load(ctx, self.id);
+        self.panels
+            .iter()
+            .find(|p| p.id == id)
+            .map_or(false, |panel| state.is_enabled(panel))
+    }
+
+    /// Show or hide a panel.
+    pub fn set_enabled(&self, ctx: &Context, id: &str, enabled: bool) {
+        let mut state = WorkspaceState::load(ctx, self.id);
+        state.enabled.insert(id.to_owned(), enabled);
+        state.store(ctx, self.id);
+    }
+
+    /// Where the panel is currently shown.
+    pub fn placement(&self, ctx: &Context, id: &str) -> Option<PanelPlacement> {
+        let state = WorkspaceState::load(ctx, self.id);
+        self.panels
+            .iter()
+            .find(|p| p.id == id)
+            .map(|panel| state.placement(panel))
+    }
+
+    /// Move a panel to another docking slot, or make it floating.
+    pub fn set_placement(&self, ctx: &Context, id: &str, placement: PanelPlacement) {
+        let mut state = WorkspaceState::load(ctx, self.id);
+        state.placement.insert(id.to_owned(), placement);
+        state.active_tab.insert(placement, id.to_owned());
+        state.store(ctx, self.id);
+    }
+
+    /// Forget all user changes to the workspace layout.
+    pub fn reset_layout(&self, ctx: &Context) {
+        ctx.data_mut(|d| d.remove::<WorkspaceState>(self.id));
+    }
+
+    /// A checkbox for each registered panel, e.g. for a "View" menu.
+    ///
+    /// Right-click a checkbox to change where the panel is docked.
+    pub fn toggles_ui(&self, ui: &mut Ui) {
+        let mut state = WorkspaceState::load(ui.ctx(), self.id);
+        let mut changed = false;
+
+        for panel in &self.panels {
+            let mut enabled = state.is_enabled(panel);
+            let response = ui.checkbox(&mut enabled, &panel.title);
+            if response.changed() {
+                state.enabled.insert(panel.id.clone(), enabled);
+                changed = true;
+            }
+            response.context_menu(|ui| {
+                changed |= placement_menu_ui(ui, &mut state, panel);
+            });
+        }
+
+        if changed {
+            state.store(ui.ctx(), self.id);
+        }
+    }
+
+    /// Show all enabled panels.
+    ///
+    /// Call this before showing your [`egui::CentralPanel`].
+    pub fn show(&mut self, ctx: &Context) {
+        let mut state = WorkspaceState::load(ctx, self.id);
+        let mut changed = false;
+
+        for placement in [
+            PanelPlacement::Left,
+            PanelPlacement::Right,
+            PanelPlacement::Bottom,
+        ] {
+            let docked: Vec<usize> = (0..self.panels.len())
+                .filter(|&i| {
+                    let panel = &self.panels[i];
+                    state.is_enabled(panel) && state.placement(panel) == placement
+                })
+                .collect();
+            if docked.is_empty() {
+                continue;
+            }
+
+            let slot_id = self.id.with(placement);
+            let add_contents = |ui: &mut Ui| {
+                changed |= self.docked_ui(ui, &mut state, placement, &docked);
+            };
+            match placement {
+                PanelPlacement::Left => {
+                    SidePanel::left(slot_id).show(ctx, add_contents);
+                }
+                PanelPlacement::Right => {
+                    SidePanel::right(slot_id).show(ctx, add_contents);
+                }
+                PanelPlacement::Bottom => {
+                    TopBottomPanel::bottom(slot_id)
+                        .resizable(true)
+                        .show(ctx, add_contents);
+                }
+                PanelPlacement::Floating => unreachable!(),
+            }
+        }
+
+        for panel in &mut self.panels {
+            if !state.is_enabled(panel) || state.placement(panel) != PanelPlacement::Floating {
+                continue;
+            }
+
+            let mut open = true;
+            Window::new(panel.title.as_str())
+                .id(self.id.with(&panel.id))
+                .open(&mut open)
+                .show(ctx, |ui| {
+                    let instance = panel.instance.get_or_insert_with(&panel.factory);
+                    instance(ui);
+                });
+            if !open {
+                state.enabled.insert(panel.id.clone(), false);
+                changed = true;
+            }
+        }
+
+        if changed {
+            state.store(ctx, self.id);
+        }
+    }
+
+    /// Show the panels docked in one slot as tabs. Returns `true` if the layout changed.
+    fn docked_ui(
+        &mut self,
+        ui: &mut Ui,
+        state: &mut WorkspaceState,
+        placement: PanelPlacement,
+        docked: &[usize],
+    ) -> bool {
+        let mut changed = false;
+
+        let active_index = docked
+            .iter()
+            .copied()
+            .find(|&i| state.active_tab.get(&placement) == Some(&self.panels[i].id))
+            .unwrap_or(docked[0]);
+
+        ui.horizontal_wrapped(|ui| {
+            for &i in docked {
+                let panel = &self.panels[i];
+                let response = ui.selectable_label(i == active_index, &panel.title);
+                if response.clicked() {
+                    state.active_tab.insert(placement, panel.id.clone());
+                    changed = true;
+                }
+                response.context_menu(|ui| {
+                    changed |= placement_menu_ui(ui, state, panel);
+                    if ui.button("Close").clicked() {
+                        state.enabled.insert(panel.id.clone(), false);
+                        changed = true;
+                        ui.close_menu();
+                    }
+                });
+            }
+        });
+        ui.separator();
+
+        let panel = &mut self.panels[active_index];
+        ScrollArea::both()
+            .id_salt(&panel.id)
+            .auto_shrink(false)
+            .show(ui, |ui| {
+                let instance = panel.instance.get_or_insert_with(&panel.factory);
+                instance(ui);
+            });
+
+        changed
+    }
+}
+
+/// Returns `true` if the placement was changed.
+fn placement_menu_ui(ui: &mut Ui, state: &mut WorkspaceState, panel: &RegisteredPanel) -> bool {
+    let current = state.placement(panel);
+    let mut changed = false;
+    for placement in PanelPlacement::ALL {
+        if ui.radio(current == placement, placement.label()).clicked() {
+            state.placement.insert(panel.id.clone(), placement);
+            state.active_tab.insert(placement, panel.id.clone());
+            changed = true;
+            ui.close_menu();
+        }
+    }
+    changed
+}
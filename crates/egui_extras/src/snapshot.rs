@@ -0,0 +1,215 @@
+use std::sync::Arc;
+
+use egui::mutex::Mutex;
+
+/// Create a triple-buffered channel for sending snapshots of some state
+/// from a worker thread to the UI thread.
+///
+/// This lets you run e.g. a heavy simulation on a background thread
+/// without ever blocking the UI (or the other way around):
+/// the worker owns one copy of the state that it mutates freely, and calls [`SnapshotWriter::publish`]
+/// when it has something worth showing.
+/// The UI thread always reads a complete, immutable snapshot with [`SnapshotReader::latest`].
+///
+/// Neither side ever waits for the other: a lock is only held long enough to swap two buffers.
+/// If the worker publishes faster than the UI reads, intermediate snapshots are skipped.
+///
+/// Publishing a changed state requests a repaint of the given [`egui::Context`].
+///
+/// ```
+/// # let ctx = egui::Context::default();
+/// let (mut writer, reader) = egui_extras::snapshot_channel(0_u64, ctx.clone());
+///
+/// std::thread::Builder::new()
+///     .name("simulation".to_owned())
+///     .spawn(move || {
+///         for _ in 0..1000 {
+///             *writer.state_mut() += 1; // expensive simulation step
+///             writer.publish();
+///         }
+///     })
+///     .unwrap();
+///
+/// // Each frame:
+/// fn ui(ui: &mut egui::Ui, reader: &mut egui_extras::SnapshotReader<u64>) {
+///     ui.label(format!("Steps: {}", reader.latest()));
+/// }
+/// ```
+pub fn snapshot_channel<T: Clone>(
+    initial: T,
+    ctx: egui::Context,
+) -> (SnapshotWriter<T>, SnapshotReader<T>) {
+    let shared = Arc::new(Mutex::new(Middle {
+        value: initial.clone(),
+        generation: 0,
+    }));
+    let writer = SnapshotWriter {
+        state: initial.clone(),
+        spare: initial.clone(),
+        changed: false,
+        generation: 0,
+        shared: shared.clone(),
+        ctx,
+    };
+    let reader = SnapshotReader {
+        front: initial,
+        generation: 0,
+        shared,
+    };
+    (writer, reader)
+}
+
+/// The buffer in the middle, swapped with by both sides.
+struct Middle<T> {
+    value: T,
+
+    /// Incremented on each publish.
+    generation: u64,
+}
+
+/// The worker side of a [`snapshot_channel`].
+pub struct SnapshotWriter<T> {
+    /// The state the worker is mutating.
+    state: T,
+
+    /// A copy of `state` that gets swapped into the middle on publish.
+    spare: T,
+
+    /// Has `state` been mutated since the last publish?
+    changed: bool,
+
+    generation: u64,
+    shared: Arc<Mutex<Middle<T>>>,
+    ctx: egui::Context,
+}
+
+impl<T: Clone> SnapshotWriter<T> {
+    /// The current (unpublished) state.
+    #[inline]
+    pub fn state(&self) -> &T {
+        &self.state
+    }
+
+    /// Mutate the current state.
+    ///
+    /// This marks the state as changed, so the next [`Self::publish`] will send it.
+    #[inline]
+    pub fn state_mut(&mut self) -> &mut T {
+        self.changed = true;
+        &mut self.state
+    }
+
+    /// Has the state been mutated since it was last published?
+    #[inline]
+    pub fn has_changes(&self) -> bool {
+        self.changed
+    }
+
+    /// Make a snapshot of the current state available to the [`SnapshotReader`],
+    /// and request a repaint.
+    ///
+    /// Does nothing if [`Self::state_mut`] hasn't been called since the last publish.
+    pub fn publish(&mut self) {
+        if self.changed {
+            self.force_publish();
+        }
+    }
+
+    /// Like [`Self::publish`], but publishes and repaints even if nothing has changed.
+    pub fn force_publish(&mut self) {
+        // Clone outside the lock, so we never block the reader for long:
+        self.spare.clone_from(&self.state);
+        self.generation += 1;
+
+        {
+            let mut middle = self.shared.lock();
+            std::mem::swap(&mut middle.value, &mut self.spare);
+            middle.generation = self.generation;
+        }
+
+        self.changed = false;
+        self.ctx.request_repaint();
+    }
+
+    /// How many snapshots have been published so far.
+    #[inline]
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+}
+
+/// The UI side of a [`snapshot_channel`].
+pub struct SnapshotReader<T> {
+    front: T,
+    generation: u64,
+    shared: Arc<Mutex<Middle<T>>>,
+}
+
+impl<T> SnapshotReader<T> {
+    /// Fetch the latest published snapshot, if there is a new one, and return it.
+    pub fn latest(&mut self) -> &T {
+        self.update();
+        &self.front
+    }
+
+    /// Fetch the latest published snapshot.
+    ///
+    /// Returns `true` if there was a new one.
+    pub fn update(&mut self) -> bool {
+        let mut middle = self.shared.lock();
+        if middle.generation == self.generation {
+            return false;
+        }
+        std::mem::swap(&mut middle.value, &mut self.front);
+        self.generation = middle.generation;
+        true
+    }
+
+    /// The snapshot returned by the last [`Self::latest`] or [`Self::update`], without checking for a newer one.
+    #[inline]
+    pub fn current(&self) -> &T {
+        &self.front
+    }
+
+    /// The generation of [`Self::current`], see [`SnapshotWriter::generation`].
+    ///
+    /// Useful for caching things derived from the snapshot.
+    #[inline]
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_sees_complete_snapshots() {
+        let (mut writer, mut reader) = snapshot_channel(vec![0; 8], egui::Context::default());
+
+        let worker = std::thread::Builder::new()
+            .spawn(move || {
+                for i in 1..=1000 {
+                    for x in writer.state_mut() {
+                        *x = i;
+                    }
+                    writer.publish();
+                }
+            })
+            .unwrap();
+
+        let mut last = 0;
+        while !worker.is_finished() {
+            let snapshot = reader.latest();
+            assert!(snapshot.iter().all(|&x| x == snapshot[0]), "Torn snapshot");
+            assert!(last <= snapshot[0], "Went back in time");
+            last = snapshot[0];
+        }
+        worker.join().unwrap();
+
+        assert_eq!(reader.latest(), &vec![1000; 8]);
+        assert_eq!(reader.generation(), 1000);
+        assert!(!reader.update());
+    }
+}
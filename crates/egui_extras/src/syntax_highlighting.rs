@@ -58,6 +58,44 @@ pub fn highlight(
     })
 }
 
+/// A layouter for an [`egui::TextEdit`] that highlights code on a background thread,
+/// so that editing large files stays responsive.
+///
+/// Keep the returned [`crate::AsyncLayouter`] around between frames,
+/// and create a new one if the theme or language changes.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// # let mut code = String::new();
+/// let theme = egui_extras::syntax_highlighting::CodeTheme::from_memory(ui.ctx(), ui.style());
+/// let mut async_layouter = egui_extras::syntax_highlighting::async_code_layouter(ui.style(), &theme, "rs");
+///
+/// let mut layouter = |ui: &egui::Ui, text: &str, wrap_width: f32| {
+///     async_layouter.layout(ui, text, wrap_width)
+/// };
+/// ui.add(egui::TextEdit::multiline(&mut code).code_editor().layouter(&mut layouter));
+/// # });
+/// ```
+pub fn async_code_layouter(
+    style: &egui::Style,
+    theme: &CodeTheme,
+    language: &str,
+) -> crate::AsyncLayouter {
+    let font_id = style
+        .override_font_id
+        .clone()
+        .unwrap_or_else(|| TextStyle::Monospace.resolve(style));
+    let default_format = egui::TextFormat::simple(font_id.clone(), theme.fallback_color());
+
+    let highlighter = Highlighter::default();
+    let theme = theme.clone();
+    let language = language.to_owned();
+    crate::AsyncLayouter::new(move |code| {
+        highlighter.highlight(font_id.clone(), &theme, code, &language)
+    })
+    .default_format(default_format)
+}
+
 fn monospace_font_size(style: &egui::Style) -> f32 {
     TextStyle::Monospace.resolve(style).size
 }
@@ -250,6 +288,15 @@ impl CodeTheme {
         #[cfg(not(feature = "serde"))]
         ctx.data_mut(|d| d.insert_temp(id, self));
     }
+
+    /// The color of code that is not highlighted.
+    fn fallback_color(&self) -> egui::Color32 {
+        if self.dark_mode {
+            egui::Color32::LIGHT_GRAY
+        } else {
+            egui::Color32::DARK_GRAY
+        }
+    }
 }
 
 #[cfg(feature = "syntect")]
@@ -424,16 +471,7 @@ impl Highlighter {
     ) -> LayoutJob {
         self.highlight_impl(theme, code, lang).unwrap_or_else(|| {
             // Fallback:
-            LayoutJob::simple(
-                code.into(),
-                font_id,
-                if theme.dark_mode {
-                    egui::Color32::LIGHT_GRAY
-                } else {
-                    egui::Color32::DARK_GRAY
-                },
-                f32::INFINITY,
-            )
+            LayoutJob::simple(code.into(), font_id, theme.fallback_color(), f32::INFINITY)
         })
     }
 
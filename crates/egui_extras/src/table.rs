@@ -10,7 +10,8 @@ use egui::{
 
 use crate::{
     layout::{CellDirection, CellSize, StripLayoutFlags},
-    StripLayout,
+    table_edit::CellEditState,
+    CellEdit, StripLayout,
 };
 
 // -----------------------------------------------------------------=----------
@@ -508,6 +509,7 @@ impl<'a> TableBuilder<'a> {
                 hovered: false,
                 selected: false,
                 response: &mut response,
+                edit_state: None,
             });
             layout.allocate_rect();
         });
@@ -742,6 +744,11 @@ impl<'a> Table<'a> {
 
         let cursor_position = ui.cursor().min;
 
+        let edit_state_id = state_id.with("__table_edit");
+        let mut edit_state = CellEditState::load(ui, edit_state_id);
+        let scroll_to_row =
+            scroll_to_row.or_else(|| edit_state.take_scroll_request().map(|row| (row, None)));
+
         let mut scroll_area = ScrollArea::new([false, vscroll])
             .id_salt(state_id.with("__scroll_area"))
             .drag_to_scroll(drag_to_scroll)
@@ -759,6 +766,7 @@ impl<'a> Table<'a> {
         let columns_ref = &columns;
         let widths_ref = &state.column_widths;
         let max_used_widths_ref = &mut max_used_widths;
+        let edit_state_ref = &mut edit_state;
 
         let scroll_area_out = scroll_area.show(ui, move |ui| {
             let mut scroll_to_y_range = None;
@@ -788,6 +796,7 @@ impl<'a> Table<'a> {
                     scroll_to_y_range: &mut scroll_to_y_range,
                     hovered_row_index,
                     hovered_row_index_id,
+                    edit_state: edit_state_ref,
                 });
 
                 if scroll_to_row.is_some() && scroll_to_y_range.is_none() {
@@ -909,6 +918,7 @@ impl<'a> Table<'a> {
         state.max_used_widths = max_used_widths;
 
         state.store(ui, state_id);
+        edit_state.store(ui, edit_state_id);
         scroll_area_out
     }
 }
@@ -942,6 +952,9 @@ pub struct TableBody<'a> {
 
     /// Used to store the hovered row index between frames.
     hovered_row_index_id: egui::Id,
+
+    /// Which cell is being edited, see [`TableRow::editable_col`].
+    edit_state: &'a mut CellEditState,
 }
 
 impl<'a> TableBody<'a> {
@@ -991,6 +1004,7 @@ impl<'a> TableBody<'a> {
             hovered: self.hovered_row_index == Some(self.row_index),
             selected: false,
             response: &mut response,
+            edit_state: Some(&mut *self.edit_state),
         });
         self.capture_hover_state(&response, self.row_index);
         let bottom_y = self.layout.cursor.y;
@@ -1035,6 +1049,8 @@ impl<'a> TableBody<'a> {
         let spacing = self.layout.ui.spacing().item_spacing;
         let row_height_with_spacing = row_height_sans_spacing + spacing.y;
 
+        self.edit_state.clamp_rows(total_rows);
+
         if let Some(scroll_to_row) = self.scroll_to_row {
             let scroll_to_row = scroll_to_row.at_most(total_rows.saturating_sub(1)) as f32;
             *self.scroll_to_y_range = Some(Rangef::new(
@@ -1072,6 +1088,7 @@ impl<'a> TableBody<'a> {
                 hovered: self.hovered_row_index == Some(row_index),
                 selected: false,
                 response: &mut response,
+                edit_state: Some(&mut *self.edit_state),
             });
             self.capture_hover_state(&response, row_index);
         }
@@ -1153,6 +1170,7 @@ impl<'a> TableBody<'a> {
                     hovered: self.hovered_row_index == Some(row_index),
                     selected: false,
                     response: &mut response,
+                    edit_state: Some(&mut *self.edit_state),
                 });
                 self.capture_hover_state(&response, row_index);
                 break;
@@ -1175,6 +1193,7 @@ impl<'a> TableBody<'a> {
                 hovered: self.hovered_row_index == Some(row_index),
                 selected: false,
                 response: &mut response,
+                edit_state: Some(&mut *self.edit_state),
             });
             self.capture_hover_state(&response, row_index);
             cursor_y += (row_height + spacing.y) as f64;
@@ -1262,6 +1281,9 @@ pub struct TableRow<'a, 'b> {
     selected: bool,
 
     response: &'b mut Option<Response>,
+
+    /// `None` for the header row, which can't be edited.
+    edit_state: Option<&'b mut CellEditState>,
 }
 
 impl<'a, 'b> TableRow<'a, 'b> {
@@ -1270,6 +1292,66 @@ impl<'a, 'b> TableRow<'a, 'b> {
     /// Returns the used space (`min_rect`) plus the [`Response`] of the whole cell.
     #[cfg_attr(debug_assertions, track_caller)]
     pub fn col(&mut self, add_cell_contents: impl FnOnce(&mut Ui)) -> (Rect, Response) {
+        if let Some(edit_state) = &mut self.edit_state {
+            edit_state.non_editable_cell((self.row_index, self.col_index), self.columns.len());
+        }
+        self.add_cell(add_cell_contents)
+    }
+
+    /// Add a cell showing `value`, which the user can edit.
+    ///
+    /// Editing starts when the cell is double-clicked, or when the user starts typing
+    /// after clicking it. Tab, Shift+Tab and Enter commit the edit and move on to edit
+    /// the next cell, the previous cell and the cell below, scrolling to it if needed.
+    /// Escape cancels the edit.
+    ///
+    /// Returns the [`Response`] of the whole cell, and the edit the user committed, if any.
+    /// The edit is only reported if the value changed. It is up to you to apply it.
+    /// Note that it may be for another cell, e.g. one that was being edited when this one was clicked.
+    ///
+    /// Cells of the header row can't be edited.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// use egui_extras::{TableBuilder, Column};
+    /// let mut names = vec![String::from("Alice"), String::from("Bob")];
+    /// TableBuilder::new(ui)
+    ///     .column(Column::remainder())
+    ///     .body(|body| {
+    ///         body.rows(18.0, names.len(), |mut row| {
+    ///             let (_, edit) = row.editable_col(&names[row.index()]);
+    ///             if let Some(edit) = edit {
+    ///                 names[edit.row] = edit.new_value;
+    ///             }
+    ///         });
+    ///     });
+    /// # });
+    /// ```
+    #[cfg_attr(debug_assertions, track_caller)]
+    pub fn editable_col(&mut self, value: &str) -> (Response, Option<CellEdit>) {
+        let cell = (self.row_index, self.col_index);
+        let num_columns = self.columns.len();
+
+        let Some(edit_state) = self.edit_state.take() else {
+            let (_, response) = self.add_cell(|ui| {
+                ui.add(egui::Label::new(value).selectable(false));
+            });
+            return (response, None);
+        };
+
+        let mut edit = None;
+        let (_, response) = self.add_cell(|ui| {
+            edit = edit_state.cell_ui(ui, cell, num_columns, value);
+        });
+        let response = response.interact(egui::Sense::click());
+        let edit = edit.or_else(|| edit_state.cell_response(&response, cell, value));
+
+        self.edit_state = Some(edit_state);
+        (response, edit)
+    }
+
+    #[cfg_attr(debug_assertions, track_caller)]
+    fn add_cell(&mut self, add_cell_contents: impl FnOnce(&mut Ui)) -> (Rect, Response) {
         let col_index = self.col_index;
 
         let clip = self.columns.get(col_index).map_or(false, |c| c.clip);
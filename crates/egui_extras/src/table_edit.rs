@@ -0,0 +1,288 @@
+//! Editing the cells of a [`crate::Table`], see [`crate::TableRow::editable_col`].
+
+use egui::{Event, Key, Label, Modifiers, Response, TextEdit, Ui};
+
+/// An edit of a table cell that the user committed,
+/// e.g. by pressing Enter or Tab, or by clicking elsewhere.
+///
+/// Returned by [`crate::TableRow::editable_col`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CellEdit {
+    pub row: usize,
+    pub col: usize,
+    pub new_value: String,
+}
+
+/// Where to move the edit focus after committing an edit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum CellMove {
+    /// Tab: the next cell on the row, or the first cell of the next row.
+    Next,
+
+    /// Shift+Tab: the previous cell on the row, or the last cell of the previous row.
+    Previous,
+
+    /// Enter: the same cell on the next row.
+    Down,
+}
+
+impl CellMove {
+    fn step(self, (row, col): (usize, usize), num_columns: usize) -> Option<(usize, usize)> {
+        match self {
+            Self::Next if col + 1 < num_columns => Some((row, col + 1)),
+            Self::Next => Some((row + 1, 0)),
+            Self::Previous if 0 < col => Some((row, col - 1)),
+            Self::Previous => Some((row.checked_sub(1)?, num_columns.checked_sub(1)?)),
+            Self::Down => Some((row + 1, col)),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct CellEditor {
+    original: String,
+    text: String,
+}
+
+/// Which cell of a table has the edit focus, and what is being typed into it.
+///
+/// Stored between frames in the [`egui::Memory`] of the table.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct CellEditState {
+    /// `(row, col)`
+    focused: Option<(usize, usize)>,
+    editor: Option<CellEditor>,
+
+    /// The editor should take the keyboard focus.
+    request_focus: bool,
+
+    /// Start editing the focused cell once it is shown.
+    /// If it can't be edited, keep moving in this direction.
+    moving: Option<CellMove>,
+
+    /// Scroll the focused row into view.
+    scroll_to_focused: bool,
+}
+
+impl CellEditState {
+    pub fn load(ui: &Ui, id: egui::Id) -> Self {
+        ui.data_mut(|d| d.get_temp::<Self>(id)).unwrap_or_default()
+    }
+
+    pub fn store(self, ui: &Ui, id: egui::Id) {
+        ui.data_mut(|d| d.insert_temp(id, self));
+    }
+
+    /// The row to scroll to this frame, if the edit focus moved last frame.
+    pub fn take_scroll_request(&mut self) -> Option<usize> {
+        if std::mem::take(&mut self.scroll_to_focused) {
+            self.focused.map(|(row, _)| row)
+        } else {
+            None
+        }
+    }
+
+    /// Forget about rows that no longer exist.
+    pub fn clamp_rows(&mut self, total_rows: usize) {
+        if self.focused.is_some_and(|(row, _)| total_rows <= row) {
+            *self = Self::default();
+        }
+    }
+
+    /// A cell that can't be edited is shown.
+    pub fn non_editable_cell(&mut self, cell: (usize, usize), num_columns: usize) {
+        if self.focused != Some(cell) {
+            return;
+        }
+        if let Some(moving) = self.moving {
+            self.move_focus(moving, num_columns);
+        } else {
+            self.editor = None;
+        }
+    }
+
+    /// Show an editable cell, returning an edit if one was committed.
+    pub fn cell_ui(
+        &mut self,
+        ui: &mut Ui,
+        cell: (usize, usize),
+        num_columns: usize,
+        value: &str,
+    ) -> Option<CellEdit> {
+        if self.focused != Some(cell) {
+            ui.add(Label::new(value).selectable(false));
+            return None;
+        }
+
+        if self.moving.take().is_some() {
+            self.start_editing(value.to_owned());
+        }
+
+        let Some(editor) = &mut self.editor else {
+            ui.add(Label::new(value).selectable(false));
+            ui.painter()
+                .rect_stroke(ui.max_rect(), 0.0, ui.visuals().widgets.active.fg_stroke);
+            self.start_editing_on_key_press(ui, value);
+            return None;
+        };
+
+        // Take the keys before the editor sees them:
+        let (moving, cancel) = ui.input_mut(|i| {
+            let moving = if i.consume_key(Modifiers::SHIFT, Key::Tab) {
+                Some(CellMove::Previous)
+            } else if i.consume_key(Modifiers::NONE, Key::Tab) {
+                Some(CellMove::Next)
+            } else if i.consume_key(Modifiers::NONE, Key::Enter) {
+                Some(CellMove::Down)
+            } else {
+                None
+            };
+            (moving, i.consume_key(Modifiers::NONE, Key::Escape))
+        });
+
+        let response = ui.add(
+            TextEdit::singleline(&mut editor.text)
+                .desired_width(f32::INFINITY)
+                .lock_focus(true),
+        );
+        if std::mem::take(&mut self.request_focus) {
+            response.request_focus();
+        }
+
+        if cancel {
+            self.editor = None;
+            response.surrender_focus();
+            None
+        } else if let Some(moving) = moving {
+            let edit = self.commit();
+            self.move_focus(moving, num_columns);
+            ui.ctx().request_repaint();
+            edit
+        } else if response.lost_focus() || response.clicked_elsewhere() {
+            self.commit()
+        } else {
+            None
+        }
+    }
+
+    /// Handle the response of the whole cell, e.g. double-clicks.
+    pub fn cell_response(
+        &mut self,
+        response: &Response,
+        cell: (usize, usize),
+        value: &str,
+    ) -> Option<CellEdit> {
+        if self.focused == Some(cell) {
+            if response.double_clicked() && self.editor.is_none() {
+                self.start_editing(value.to_owned());
+            } else if response.clicked_elsewhere() && self.editor.is_none() {
+                self.focused = None;
+            }
+            None
+        } else if response.clicked() || response.double_clicked() {
+            // Commit whatever was being edited in another cell:
+            let edit = self.commit();
+            *self = Self {
+                focused: Some(cell),
+                ..Default::default()
+            };
+            if response.double_clicked() {
+                self.start_editing(value.to_owned());
+            }
+            edit
+        } else {
+            None
+        }
+    }
+
+    fn start_editing_on_key_press(&mut self, ui: &Ui, value: &str) {
+        if ui.memory(|mem| mem.focused().is_some()) {
+            return; // The user is typing into something else
+        }
+
+        let mut typed = String::new();
+        let enter = ui.input_mut(|i| {
+            i.events.retain(|event| match event {
+                Event::Text(text) => {
+                    typed.push_str(text);
+                    false
+                }
+                _ => true,
+            });
+            i.consume_key(Modifiers::NONE, Key::Enter)
+        });
+
+        if !typed.is_empty() {
+            // Typing replaces the old value:
+            self.start_editing(value.to_owned());
+            if let Some(editor) = &mut self.editor {
+                editor.text = typed;
+            }
+        } else if enter {
+            self.start_editing(value.to_owned());
+        }
+    }
+
+    fn start_editing(&mut self, value: String) {
+        self.editor = Some(CellEditor {
+            original: value.clone(),
+            text: value,
+        });
+        self.request_focus = true;
+        self.moving = None;
+    }
+
+    /// Stop editing. Returns the edit, if the value changed.
+    fn commit(&mut self) -> Option<CellEdit> {
+        let editor = self.editor.take()?;
+        let (row, col) = self.focused?;
+        (editor.text != editor.original).then_some(CellEdit {
+            row,
+            col,
+            new_value: editor.text,
+        })
+    }
+
+    fn move_focus(&mut self, moving: CellMove, num_columns: usize) {
+        self.editor = None;
+        self.focused = self.focused.and_then(|cell| moving.step(cell, num_columns));
+        self.moving = self.focused.map(|_| moving);
+        self.scroll_to_focused = self.focused.is_some();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tab_navigation() {
+        let num_columns = 3;
+        assert_eq!(CellMove::Next.step((0, 1), num_columns), Some((0, 2)));
+        assert_eq!(CellMove::Next.step((0, 2), num_columns), Some((1, 0)));
+        assert_eq!(CellMove::Previous.step((1, 0), num_columns), Some((0, 2)));
+        assert_eq!(CellMove::Previous.step((0, 0), num_columns), None);
+        assert_eq!(CellMove::Down.step((4, 1), num_columns), Some((5, 1)));
+    }
+
+    #[test]
+    fn commit_only_changes() {
+        let mut state = CellEditState {
+            focused: Some((2, 1)),
+            ..Default::default()
+        };
+        state.start_editing("old".to_owned());
+        assert_eq!(state.commit(), None);
+
+        state.start_editing("old".to_owned());
+        state.editor.as_mut().unwrap().text = "new".to_owned();
+        assert_eq!(
+            state.commit(),
+            Some(CellEdit {
+                row: 2,
+                col: 1,
+                new_value: "new".to_owned()
+            })
+        );
+    }
+}
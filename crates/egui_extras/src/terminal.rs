@@ -0,0 +1,1215 @@
+use std::{collections::VecDeque, ops::Range, sync::Arc};
+
+use egui::{
+    mutex::Mutex,
+    text::{LayoutJob, LayoutSection},
+    vec2, CursorIcon, Event, EventFilter, FontId, Id, Key, Modifiers, OpenUrl, Pos2, Rect,
+    Response, ScrollArea, Sense, Stroke, Style, TextStyle, Ui, Vec2,
+};
+
+use crate::{ansi::AnsiStyle, console::SharedOutput};
+
+/// The process on the other end of a [`Terminal`], usually a shell running in a pseudo terminal (PTY).
+///
+/// egui doesn't spawn processes, so this is up to the app,
+/// e.g. using the [`portable-pty`](https://docs.rs/portable-pty) crate.
+/// The output of the process goes the other way, through a [`TerminalWriter`].
+pub trait Pty {
+    /// Send input from the user, e.g. key presses and pasted text, to the process.
+    fn write(&mut self, bytes: &[u8]);
+
+    /// The terminal was resized to the given number of columns and rows.
+    ///
+    /// Pass this on to the PTY, so that the process can adapt to it.
+    fn resize(&mut self, _cols: usize, _rows: usize) {}
+}
+
+/// Keys like tab and the arrows go to the terminal, instead of moving the focus.
+const FOCUS_LOCK_FILTER: EventFilter = EventFilter {
+    tab: true,
+    horizontal_arrows: true,
+    vertical_arrows: true,
+    escape: true,
+};
+
+/// A terminal emulator, for embedding a shell or other command line programs, e.g. in an IDE.
+///
+/// It shows what the process writes to it, and sends what the user types to the process.
+/// Talking to the process is up to you, see [`Pty`]:
+///
+/// ```
+/// struct Shell; // E.g. a PTY running a shell.
+///
+/// impl egui_extras::Pty for Shell {
+///     fn write(&mut self, bytes: &[u8]) {
+///         // Send the input to the process.
+///     }
+/// }
+///
+/// # egui::__run_test_ui(|ui| {
+/// // Keep this around between frames, e.g. in your app state:
+/// let mut terminal = egui_extras::Terminal::new("shell", Shell);
+///
+/// // Read the output of the process on a background thread, and write it to the terminal:
+/// let mut writer = terminal.writer();
+/// std::thread::spawn(move || {
+///     use std::io::Write as _;
+///     write!(writer, "\x1b[1;32m$\x1b[0m ").ok();
+/// });
+///
+/// terminal.show(ui);
+/// # });
+/// ```
+///
+/// The terminal fills the available space,
+/// and tells the [`Pty`] how many columns and rows that is.
+/// When it is resized, the lines are re-wrapped to the new width.
+///
+/// Features:
+/// * Most of what a VT100 (and `xterm`) does: cursor movement, erasing, colors and text styles,
+///   the alternate screen used by full-screen programs, bracketed paste, etc.
+/// * Scrollback, of which only the visible rows are laid out.
+/// * Select text by dragging, and copy it with the usual shortcut.
+///   Without a selection, that shortcut sends Ctrl+C to the process as usual.
+/// * Click URLs to open them.
+///
+/// Not supported are scroll regions, mouse reporting, and characters wider than one cell.
+///
+/// Like in any terminal, `\n` only moves down a row; processes running in a PTY output `\r\n`.
+pub struct Terminal {
+    id_salt: Id,
+    pty: Box<dyn Pty>,
+    grid: Grid,
+    shared: Arc<Mutex<SharedOutput>>,
+
+    /// Where the selection starts, and where it ends (or where it was dragged to).
+    selection: Option<(CellPos, CellPos)>,
+
+    /// Set when the user sends input, so that the cursor is scrolled into view.
+    scroll_to_bottom: bool,
+
+    /// The size last passed to [`Pty::resize`].
+    pty_size: Option<(usize, usize)>,
+}
+
+impl Terminal {
+    /// The `id_salt` must be unique among the terminals shown in the same [`Ui`].
+    pub fn new(id_salt: impl std::hash::Hash, pty: impl Pty + 'static) -> Self {
+        Self {
+            id_salt: Id::new(id_salt),
+            pty: Box::new(pty),
+            grid: Grid::new(80, 24, 10_000),
+            shared: Default::default(),
+            selection: None,
+            scroll_to_bottom: false,
+            pty_size: None,
+        }
+    }
+
+    /// How many rows that have scrolled off the top of the screen to keep.
+    ///
+    /// Default: `10_000`.
+    #[inline]
+    pub fn max_scrollback(mut self, max_scrollback: usize) -> Self {
+        self.grid.max_scrollback = max_scrollback;
+        self
+    }
+
+    /// Something to write the output of the process to, e.g. from a thread reading from the PTY.
+    pub fn writer(&self) -> TerminalWriter {
+        TerminalWriter {
+            shared: self.shared.clone(),
+        }
+    }
+
+    /// The title set by the process, or an empty string.
+    pub fn title(&self) -> &str {
+        &self.grid.title
+    }
+
+    /// The number of columns and rows.
+    pub fn size(&self) -> (usize, usize) {
+        (self.grid.cols, self.grid.rows)
+    }
+
+    /// Show the terminal, using all available space.
+    ///
+    /// The [`Response`] has focus when the terminal has keyboard focus.
+    pub fn show(&mut self, ui: &mut Ui) -> Response {
+        crate::profile_function!();
+
+        let id = ui.make_persistent_id(self.id_salt);
+        let grid_id = id.with("grid");
+
+        let output = self.shared.lock().take(ui);
+        if !output.is_empty() {
+            let generation = self.grid.generation;
+            self.grid.feed(&output);
+            if self.grid.generation != generation {
+                self.selection = None;
+            }
+            self.send_replies();
+        }
+
+        let font_id = TextStyle::Monospace.resolve(ui.style());
+        let cell_size = ui
+            .fonts(|f| vec2(f.glyph_width(&font_id, 'M'), f.row_height(&font_id)))
+            .max(Vec2::splat(1.0)); // Zero without fonts
+
+        let available = ui.available_size();
+        let cols = if available.x.is_finite() {
+            (available.x / cell_size.x) as usize
+        } else {
+            80
+        };
+        let rows = if available.y.is_finite() {
+            (available.y / cell_size.y) as usize
+        } else {
+            24
+        };
+        self.resize(cols.max(1), rows.max(1));
+
+        if ui.memory(|mem| mem.has_focus(grid_id)) {
+            ui.memory_mut(|mem| mem.set_focus_lock_filter(grid_id, FOCUS_LOCK_FILTER));
+            self.handle_input(ui);
+        }
+
+        let screen_size = vec2(
+            self.grid.cols as f32 * cell_size.x,
+            self.grid.rows as f32 * cell_size.y,
+        );
+        ui.painter().rect_filled(
+            Rect::from_min_size(ui.cursor().min, screen_size),
+            0.0,
+            ui.visuals().extreme_bg_color,
+        );
+
+        let num_lines = self.grid.lines.len();
+        let mut scroll_area = ScrollArea::vertical()
+            .id_salt(id.with("scroll"))
+            .auto_shrink(false)
+            .stick_to_bottom(true)
+            .max_height(screen_size.y);
+        if std::mem::take(&mut self.scroll_to_bottom) {
+            scroll_area =
+                scroll_area.vertical_scroll_offset(num_lines as f32 * cell_size.y - screen_size.y);
+        }
+
+        ui.scope(|ui| {
+            ui.spacing_mut().item_spacing.y = 0.0;
+            scroll_area
+                .show_rows(ui, cell_size.y, num_lines, |ui, lines| {
+                    self.show_lines(ui, grid_id, cell_size, &font_id, lines)
+                })
+                .inner
+        })
+        .inner
+    }
+
+    fn resize(&mut self, cols: usize, rows: usize) {
+        if (cols, rows) != (self.grid.cols, self.grid.rows) {
+            self.grid.resize(cols, rows);
+            self.selection = None;
+        }
+        if self.pty_size != Some((cols, rows)) {
+            self.pty_size = Some((cols, rows));
+            self.pty.resize(cols, rows);
+        }
+    }
+
+    fn send_replies(&mut self) {
+        let replies = std::mem::take(&mut self.grid.replies);
+        if !replies.is_empty() {
+            self.pty.write(&replies);
+        }
+    }
+
+    /// Send what the user typed to the process.
+    fn handle_input(&mut self, ui: &Ui) {
+        let events = ui.input(|i| i.filtered_events(&FOCUS_LOCK_FILTER));
+        let mut input = Vec::new();
+        for event in events {
+            match event {
+                Event::Copy => {
+                    if let Some(text) = self.selected_text() {
+                        ui.ctx().copy_text(text);
+                    } else {
+                        input.push(0x03); // Ctrl+C
+                    }
+                }
+                Event::Cut => input.push(0x18), // Ctrl+X
+                Event::Paste(text) => {
+                    let text = text.replace("\r\n", "\r").replace('\n', "\r");
+                    if self.grid.bracketed_paste {
+                        input.extend_from_slice(b"\x1b[200~");
+                        input.extend_from_slice(text.as_bytes());
+                        input.extend_from_slice(b"\x1b[201~");
+                    } else {
+                        input.extend_from_slice(text.as_bytes());
+                    }
+                }
+                Event::Text(text) => input.extend_from_slice(text.as_bytes()),
+                Event::Key {
+                    key,
+                    pressed: true,
+                    modifiers,
+                    ..
+                } => key_input(key, modifiers, self.grid.app_cursor_keys, &mut input),
+                _ => {}
+            }
+        }
+
+        if !input.is_empty() {
+            self.selection = None;
+            self.scroll_to_bottom = true;
+            self.pty.write(&input);
+        }
+    }
+
+    /// Show the given lines, and handle selection and clicks on them.
+    fn show_lines(
+        &mut self,
+        ui: &mut Ui,
+        grid_id: Id,
+        cell_size: Vec2,
+        font_id: &FontId,
+        lines: Range<usize>,
+    ) -> Response {
+        let top_left = ui.max_rect().min;
+        let rect = Rect::from_min_size(
+            top_left,
+            vec2(
+                self.grid.cols as f32 * cell_size.x,
+                lines.len() as f32 * cell_size.y,
+            ),
+        );
+        let response = ui.interact(rect, grid_id, Sense::click_and_drag());
+        ui.advance_cursor_after_rect(rect);
+
+        let first_line = self.grid.num_dropped_lines + lines.start;
+        let last_line = (first_line + lines.len()).saturating_sub(1);
+        let cell_at = |pos: Pos2, round: bool| {
+            let offset = (pos - top_left) / cell_size;
+            let line = first_line + offset.y.max(0.0) as usize;
+            let col = if round { offset.x.round() } else { offset.x };
+            CellPos {
+                line: line.min(last_line),
+                col: (col.max(0.0) as usize).min(self.grid.cols),
+            }
+        };
+
+        if response.drag_started() || response.clicked() {
+            response.request_focus();
+        }
+        if let Some(pos) = response.interact_pointer_pos() {
+            let cell = cell_at(pos, true);
+            if response.drag_started() {
+                self.selection = Some((cell, cell));
+            } else if response.dragged() {
+                if let Some((_, end)) = &mut self.selection {
+                    *end = cell;
+                }
+            }
+        }
+
+        let hovered_url = response.hover_pos().and_then(|pos| {
+            let cell = cell_at(pos, false);
+            let row = self.grid.line(cell.line)?;
+            let url = find_urls(&row.text(0..usize::MAX))
+                .into_iter()
+                .find(|url| url.contains(&cell.col))?;
+            Some((cell.line, url))
+        });
+        if let Some((line, url)) = &hovered_url {
+            ui.ctx().set_cursor_icon(CursorIcon::PointingHand);
+            if response.clicked() {
+                if let Some(row) = self.grid.line(*line) {
+                    ui.ctx().open_url(OpenUrl::new_tab(row.text(url.clone())));
+                }
+            }
+        }
+        if response.clicked() {
+            self.selection = None;
+        }
+
+        let visuals = ui.visuals();
+        let painter = ui.painter();
+        let has_focus = response.has_focus();
+        let selection = self.selection.map(|(a, b)| (a.min(b), a.max(b)));
+        let cursor_line =
+            self.grid.num_dropped_lines + self.grid.screen_top() + self.grid.cursor.row;
+        let cursor_col = self.grid.cursor.col.min(self.grid.cols - 1);
+        let cell_rect = |row_min: Pos2, cols: Range<usize>| {
+            Rect::from_min_size(
+                row_min + vec2(cols.start as f32 * cell_size.x, 0.0),
+                vec2(cols.len() as f32 * cell_size.x, cell_size.y),
+            )
+        };
+
+        for (i, line) in (first_line..).zip(lines) {
+            let row = &self.grid.lines[line];
+            let row_min = top_left + vec2(0.0, i as f32 * cell_size.y);
+
+            if let Some((start, end)) = selection {
+                if (start.line..=end.line).contains(&line) {
+                    let from = if line == start.line { start.col } else { 0 };
+                    let to = if line == end.line {
+                        end.col
+                    } else {
+                        self.grid.cols
+                    };
+                    painter.rect_filled(
+                        cell_rect(row_min, from..to),
+                        0.0,
+                        visuals.selection.bg_fill,
+                    );
+                }
+            }
+
+            let is_cursor_line = self.grid.cursor_visible && line == cursor_line;
+            let block_cursor = (is_cursor_line && has_focus).then_some(cursor_col);
+            let job = row.layout_job(ui.style(), font_id, block_cursor);
+            let galley = ui.fonts(|f| f.layout_job(job));
+            painter.galley(row_min, galley, visuals.text_color());
+
+            if is_cursor_line && !has_focus {
+                painter.rect_stroke(
+                    cell_rect(row_min, cursor_col..cursor_col + 1),
+                    0.0,
+                    Stroke::new(1.0, visuals.text_color()),
+                );
+            }
+
+            if let Some((url_line, url)) = &hovered_url {
+                if *url_line == line {
+                    let rect = cell_rect(row_min, url.clone());
+                    painter.line_segment(
+                        [rect.left_bottom(), rect.right_bottom()],
+                        Stroke::new(1.0, visuals.hyperlink_color),
+                    );
+                }
+            }
+        }
+
+        response
+    }
+
+    /// The selected text, if any.
+    fn selected_text(&self) -> Option<String> {
+        let (a, b) = self.selection?;
+        let (start, end) = (a.min(b), a.max(b));
+        if start == end {
+            return None;
+        }
+
+        let mut text = String::new();
+        for line in start.line..=end.line {
+            let Some(row) = self.grid.line(line) else {
+                continue;
+            };
+            let from = if line == start.line { start.col } else { 0 };
+            let to = if line == end.line {
+                end.col
+            } else {
+                usize::MAX
+            };
+            let part = row.text(from..to);
+            if line == end.line {
+                text += part.trim_end();
+            } else if row.wrapped {
+                text += &part;
+            } else {
+                text += part.trim_end();
+                text.push('\n');
+            }
+        }
+        Some(text)
+    }
+}
+
+/// What to send to the process for a key press that isn't text.
+fn key_input(key: Key, modifiers: Modifiers, app_cursor_keys: bool, input: &mut Vec<u8>) {
+    let cursor_key = |chr: u8| {
+        if app_cursor_keys {
+            [0x1b, b'O', chr]
+        } else {
+            [0x1b, b'[', chr]
+        }
+    };
+    match key {
+        Key::Enter => input.push(b'\r'),
+        Key::Backspace => input.push(0x7f),
+        Key::Tab if modifiers.shift => input.extend_from_slice(b"\x1b[Z"),
+        Key::Tab => input.push(b'\t'),
+        Key::Escape => input.push(0x1b),
+        Key::ArrowUp => input.extend_from_slice(&cursor_key(b'A')),
+        Key::ArrowDown => input.extend_from_slice(&cursor_key(b'B')),
+        Key::ArrowRight => input.extend_from_slice(&cursor_key(b'C')),
+        Key::ArrowLeft => input.extend_from_slice(&cursor_key(b'D')),
+        Key::Home => input.extend_from_slice(&cursor_key(b'H')),
+        Key::End => input.extend_from_slice(&cursor_key(b'F')),
+        Key::Insert => input.extend_from_slice(b"\x1b[2~"),
+        Key::Delete => input.extend_from_slice(b"\x1b[3~"),
+        Key::PageUp => input.extend_from_slice(b"\x1b[5~"),
+        Key::PageDown => input.extend_from_slice(b"\x1b[6~"),
+        Key::F1 => input.extend_from_slice(b"\x1bOP"),
+        Key::F2 => input.extend_from_slice(b"\x1bOQ"),
+        Key::F3 => input.extend_from_slice(b"\x1bOR"),
+        Key::F4 => input.extend_from_slice(b"\x1bOS"),
+        Key::F5 => input.extend_from_slice(b"\x1b[15~"),
+        Key::F6 => input.extend_from_slice(b"\x1b[17~"),
+        Key::F7 => input.extend_from_slice(b"\x1b[18~"),
+        Key::F8 => input.extend_from_slice(b"\x1b[19~"),
+        Key::F9 => input.extend_from_slice(b"\x1b[20~"),
+        Key::F10 => input.extend_from_slice(b"\x1b[21~"),
+        Key::F11 => input.extend_from_slice(b"\x1b[23~"),
+        Key::F12 => input.extend_from_slice(b"\x1b[24~"),
+        _ if modifiers.ctrl => {
+            // Ctrl+A is 0x01, etc:
+            if let &[letter] = key.name().as_bytes() {
+                if letter.is_ascii_alphabetic() {
+                    input.push(letter.to_ascii_uppercase() & 0x1f);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The columns of the URLs (e.g. `https://www.egui.rs`) in a row of text.
+fn find_urls(text: &str) -> Vec<Range<usize>> {
+    let mut urls = Vec::new();
+    let mut col = 0;
+    for word in text.split(' ') {
+        if let Some(start) = word.find("https://").or_else(|| word.find("http://")) {
+            let url = word[start..]
+                .trim_end_matches(['.', ',', ';', ':', '!', '?', ')', ']', '}', '\'', '"', '>']);
+            if !url.ends_with("//") {
+                let start_col = col + word[..start].chars().count();
+                urls.push(start_col..start_col + url.chars().count());
+            }
+        }
+        col += word.chars().count() + 1;
+    }
+    urls
+}
+
+// ----------------------------------------------------------------------------
+
+/// Writes the output of the process to a [`Terminal`], see [`Terminal::writer`].
+///
+/// Can be sent to other threads, and implements [`std::io::Write`] and [`std::fmt::Write`],
+/// so you can e.g. copy everything read from a PTY to it.
+///
+/// Each write requests a repaint, so the output is shown as it arrives.
+#[derive(Clone)]
+pub struct TerminalWriter {
+    shared: Arc<Mutex<SharedOutput>>,
+}
+
+impl TerminalWriter {
+    /// Write output, including escape codes.
+    pub fn write_str(&self, text: &str) {
+        self.shared.lock().write(text.as_bytes());
+    }
+}
+
+impl std::io::Write for TerminalWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.shared.lock().write(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl std::fmt::Write for TerminalWriter {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        Self::write_str(self, s);
+        Ok(())
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// A cell of the terminal, counting lines from the first one ever written,
+/// so that it stays the same when old lines are dropped from the scrollback.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct CellPos {
+    line: usize,
+    col: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Cell {
+    chr: char,
+    style: AnsiStyle,
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self {
+            chr: ' ',
+            style: AnsiStyle::default(),
+        }
+    }
+}
+
+/// A row of cells.
+#[derive(Clone, Debug, Default)]
+struct Row {
+    /// The cells after these are blank.
+    cells: Vec<Cell>,
+
+    /// Does the text continue on the next row, because it was too long for this one?
+    wrapped: bool,
+}
+
+impl Row {
+    fn set(&mut self, col: usize, cell: Cell) {
+        if self.cells.len() <= col {
+            self.cells.resize(col + 1, Cell::default());
+        }
+        self.cells[col] = cell;
+    }
+
+    fn erase(&mut self, cols: Range<usize>, blank: Cell) {
+        if blank == Cell::default() && self.cells.len() <= cols.end {
+            self.cells.truncate(cols.start);
+        } else {
+            for col in cols {
+                self.set(col, blank);
+            }
+        }
+    }
+
+    fn text(&self, cols: Range<usize>) -> String {
+        let end = cols.end.min(self.cells.len());
+        let start = cols.start.min(end);
+        self.cells[start..end].iter().map(|cell| cell.chr).collect()
+    }
+
+    /// With a block cursor in the given column, if any.
+    fn layout_job(&self, style: &Style, font_id: &FontId, cursor_col: Option<usize>) -> LayoutJob {
+        let mut job = LayoutJob {
+            break_on_newline: false,
+            ..Default::default()
+        };
+        let num_cells = self.cells.len().max(cursor_col.map_or(0, |col| col + 1));
+        let mut last_style = None;
+        for col in 0..num_cells {
+            let cell = self.cells.get(col).copied().unwrap_or_default();
+            let mut cell_style = cell.style;
+            if cursor_col == Some(col) {
+                cell_style.inverse = !cell_style.inverse;
+            }
+
+            let start = job.text.len();
+            job.text.push(cell.chr);
+            let end = job.text.len();
+            match job.sections.last_mut() {
+                Some(section) if last_style == Some(cell_style) => {
+                    section.byte_range.end = end;
+                }
+                _ => {
+                    job.sections.push(LayoutSection {
+                        leading_space: 0.0,
+                        byte_range: start..end,
+                        format: cell_style.text_format(style, font_id),
+                    });
+                    last_style = Some(cell_style);
+                }
+            }
+        }
+        job
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct Cursor {
+    /// From the top of the screen.
+    row: usize,
+
+    /// After writing to the last column, this is the number of columns, until the next character wraps.
+    col: usize,
+}
+
+#[derive(Debug, Default)]
+enum EscapeState {
+    #[default]
+    None,
+
+    /// After ESC.
+    Escape,
+
+    /// In a Control Sequence (ESC [), with the parameters so far.
+    Csi(String),
+
+    /// In an Operating System Command (ESC ]), which runs until BEL or ESC \.
+    Osc(String),
+
+    /// Selecting a character set (e.g. ESC ( B), which we ignore.
+    Charset,
+}
+
+/// The state of the terminal: the cells of the screen and the scrollback, the cursor, etc.
+struct Grid {
+    cols: usize,
+    rows: usize,
+    max_scrollback: usize,
+
+    /// The scrollback, followed by the screen, which is the last [`Self::rows`] of these.
+    lines: VecDeque<Row>,
+
+    /// How many lines have been dropped from the front of [`Self::lines`].
+    num_dropped_lines: usize,
+
+    /// The lines and cursor of the main screen, while the alternate screen is shown.
+    main_screen: Option<(VecDeque<Row>, Cursor)>,
+
+    cursor: Cursor,
+    saved_cursor: (Cursor, AnsiStyle),
+    style: AnsiStyle,
+
+    autowrap: bool,
+    cursor_visible: bool,
+    app_cursor_keys: bool,
+    bracketed_paste: bool,
+
+    title: String,
+    escape: EscapeState,
+
+    /// Answers to queries from the process (e.g. for the cursor position), to send back to it.
+    replies: Vec<u8>,
+
+    /// Changed when the lines are rearranged other than by scrolling.
+    generation: u64,
+}
+
+impl Grid {
+    fn new(cols: usize, rows: usize, max_scrollback: usize) -> Self {
+        Self {
+            cols,
+            rows,
+            max_scrollback,
+            lines: std::iter::repeat_with(Row::default).take(rows).collect(),
+            num_dropped_lines: 0,
+            main_screen: None,
+            cursor: Cursor::default(),
+            saved_cursor: Default::default(),
+            style: AnsiStyle::default(),
+            autowrap: true,
+            cursor_visible: true,
+            app_cursor_keys: false,
+            bracketed_paste: false,
+            title: String::new(),
+            escape: EscapeState::None,
+            replies: Vec::new(),
+            generation: 0,
+        }
+    }
+
+    fn screen_top(&self) -> usize {
+        self.lines.len() - self.rows
+    }
+
+    /// The line with the given [`CellPos::line`], if it is still around.
+    fn line(&self, line: usize) -> Option<&Row> {
+        self.lines.get(line.checked_sub(self.num_dropped_lines)?)
+    }
+
+    fn cursor_row(&mut self) -> &mut Row {
+        let index = self.screen_top() + self.cursor.row;
+        &mut self.lines[index]
+    }
+
+    /// An erased cell, which keeps the current background color.
+    fn blank(&self) -> Cell {
+        Cell {
+            chr: ' ',
+            style: AnsiStyle {
+                bg: self.style.bg,
+                ..Default::default()
+            },
+        }
+    }
+
+    fn resize(&mut self, cols: usize, rows: usize) {
+        let old_rows = self.rows;
+        if let Some((main_lines, main_cursor)) = &mut self.main_screen {
+            resize_without_reflow(&mut self.lines, &mut self.cursor, old_rows, cols, rows);
+            reflow(
+                main_lines,
+                main_cursor,
+                old_rows,
+                cols,
+                rows,
+                self.max_scrollback,
+            );
+        } else {
+            reflow(
+                &mut self.lines,
+                &mut self.cursor,
+                old_rows,
+                cols,
+                rows,
+                self.max_scrollback,
+            );
+        }
+        self.cols = cols;
+        self.rows = rows;
+        self.saved_cursor.0.row = self.saved_cursor.0.row.min(rows - 1);
+        self.saved_cursor.0.col = self.saved_cursor.0.col.min(cols - 1);
+        self.generation += 1;
+    }
+
+    fn feed(&mut self, text: &str) {
+        for chr in text.chars() {
+            match &mut self.escape {
+                EscapeState::None => {}
+                EscapeState::Escape => {
+                    self.escape = EscapeState::None;
+                    self.escape(chr);
+                    continue;
+                }
+                EscapeState::Csi(params) => {
+                    if ('\x40'..='\x7e').contains(&chr) {
+                        let params = std::mem::take(params);
+                        self.escape = EscapeState::None;
+                        self.csi(&params, chr);
+                    } else if params.len() < 64 {
+                        params.push(chr);
+                    }
+                    continue;
+                }
+                EscapeState::Osc(string) => {
+                    match chr {
+                        '\x07' | '\x1b' => {
+                            let string = std::mem::take(string);
+                            // ST is ESC \
+                            self.escape = if chr == '\x1b' {
+                                EscapeState::Escape
+                            } else {
+                                EscapeState::None
+                            };
+                            self.osc(&string);
+                        }
+                        _ if string.len() < 1024 => string.push(chr),
+                        _ => {}
+                    }
+                    continue;
+                }
+                EscapeState::Charset => {
+                    self.escape = EscapeState::None;
+                    continue;
+                }
+            }
+
+            match chr {
+                '\x1b' => self.escape = EscapeState::Escape,
+                '\r' => self.cursor.col = 0,
+                '\n' | '\x0b' | '\x0c' => self.linefeed(),
+                '\x08' => self.cursor.col = self.cursor.col.min(self.cols - 1).saturating_sub(1),
+                '\t' => self.cursor.col = ((self.cursor.col / 8 + 1) * 8).min(self.cols - 1),
+                chr if chr.is_control() => {}
+                chr => self.print(chr),
+            }
+        }
+    }
+
+    fn print(&mut self, chr: char) {
+        if self.cols <= self.cursor.col {
+            if self.autowrap {
+                let cols = self.cols;
+                let row = self.cursor_row();
+                row.wrapped = true;
+                row.cells.resize(cols, Cell::default());
+                self.cursor.col = 0;
+                self.linefeed();
+            } else {
+                self.cursor.col = self.cols - 1;
+            }
+        }
+        let cell = Cell {
+            chr,
+            style: self.style,
+        };
+        let col = self.cursor.col;
+        self.cursor_row().set(col, cell);
+        self.cursor.col += 1;
+    }
+
+    fn linefeed(&mut self) {
+        if self.cursor.row + 1 < self.rows {
+            self.cursor.row += 1;
+        } else {
+            self.lines.push_back(Row::default());
+            if self.main_screen.is_some() {
+                // No scrollback on the alternate screen:
+                self.lines.pop_front();
+            } else {
+                while self.rows + self.max_scrollback < self.lines.len() {
+                    self.lines.pop_front();
+                    self.num_dropped_lines += 1;
+                }
+            }
+        }
+    }
+
+    fn move_cursor_to(&mut self, row: usize, col: usize) {
+        self.cursor.row = row.min(self.rows - 1);
+        self.cursor.col = col.min(self.cols - 1);
+    }
+
+    /// The escape sequence `ESC chr`.
+    fn escape(&mut self, chr: char) {
+        match chr {
+            '[' => self.escape = EscapeState::Csi(String::new()),
+            ']' => self.escape = EscapeState::Osc(String::new()),
+            '(' | ')' | '*' | '+' => self.escape = EscapeState::Charset,
+            '7' => self.saved_cursor = (self.cursor, self.style),
+            '8' => (self.cursor, self.style) = self.saved_cursor,
+            'D' => self.linefeed(),
+            'E' => {
+                self.cursor.col = 0;
+                self.linefeed();
+            }
+            'M' => {
+                // Reverse index: move up, scrolling down at the top.
+                if self.cursor.row == 0 {
+                    let top = self.screen_top();
+                    self.lines.insert(top, Row::default());
+                    self.lines.remove(top + self.rows);
+                } else {
+                    self.cursor.row -= 1;
+                }
+            }
+            'c' => {
+                let generation = self.generation + 1;
+                *self = Self::new(self.cols, self.rows, self.max_scrollback);
+                self.generation = generation;
+            }
+            _ => {}
+        }
+    }
+
+    /// The control sequence `ESC [ params chr`.
+    fn csi(&mut self, params: &str, chr: char) {
+        let (prefix, params) = match params.strip_prefix(['?', '>', '=']) {
+            Some(rest) => (params.chars().next(), rest),
+            None => (None, params),
+        };
+        let nums: Vec<usize> = params
+            .split(';')
+            .map(|param| param.parse().unwrap_or(0))
+            .collect();
+        let num = |i: usize| nums.get(i).copied().unwrap_or(0);
+        let count = |i: usize| num(i).max(1);
+        let Cursor { row, col } = self.cursor;
+
+        match (prefix, chr) {
+            (None, 'A') => self.move_cursor_to(row.saturating_sub(count(0)), col),
+            (None, 'B' | 'e') => self.move_cursor_to(row + count(0), col),
+            (None, 'C' | 'a') => self.move_cursor_to(row, col + count(0)),
+            (None, 'D') => {
+                let col = col.min(self.cols - 1);
+                self.move_cursor_to(row, col.saturating_sub(count(0)));
+            }
+            (None, 'E') => self.move_cursor_to(row + count(0), 0),
+            (None, 'F') => self.move_cursor_to(row.saturating_sub(count(0)), 0),
+            (None, 'G' | '`') => self.move_cursor_to(row, count(0) - 1),
+            (None, 'd') => self.move_cursor_to(count(0) - 1, col),
+            (None, 'H' | 'f') => self.move_cursor_to(count(0) - 1, count(1) - 1),
+            (None, 'J') => self.erase_display(num(0)),
+            (None, 'K') => self.erase_line(num(0)),
+            (None, '@') => {
+                let (blank, cols) = (self.blank(), self.cols);
+                let row = self.cursor_row();
+                if col < row.cells.len() {
+                    row.cells
+                        .splice(col..col, std::iter::repeat(blank).take(count(0)));
+                    row.cells.truncate(cols);
+                }
+            }
+            (None, 'P') => {
+                let row = self.cursor_row();
+                if col < row.cells.len() {
+                    let end = (col + count(0)).min(row.cells.len());
+                    row.cells.drain(col..end);
+                }
+            }
+            (None, 'X') => {
+                let blank = self.blank();
+                self.cursor_row().erase(col..col + count(0), blank);
+            }
+            (None, 'L' | 'M') => {
+                let top = self.screen_top();
+                for _ in 0..count(0).min(self.rows - row) {
+                    if chr == 'L' {
+                        self.lines.remove(top + self.rows - 1);
+                        self.lines.insert(top + row, Row::default());
+                    } else {
+                        self.lines.remove(top + row);
+                        self.lines.insert(top + self.rows - 1, Row::default());
+                    }
+                }
+            }
+            (None, 'm') => self.style.apply_sgr(params),
+            (None, 'n') => match num(0) {
+                5 => self.replies.extend_from_slice(b"\x1b[0n"),
+                6 => {
+                    let reply = format!("\x1b[{};{}R", row + 1, col.min(self.cols - 1) + 1);
+                    self.replies.extend_from_slice(reply.as_bytes());
+                }
+                _ => {}
+            },
+            (None, 'c') => self.replies.extend_from_slice(b"\x1b[?1;2c"), // A VT100 with advanced video
+            (None, 's') => self.saved_cursor = (self.cursor, self.style),
+            (None, 'u') => (self.cursor, self.style) = self.saved_cursor,
+            (Some('?'), 'h' | 'l') => {
+                for &mode in &nums {
+                    self.set_mode(mode, chr == 'h');
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn set_mode(&mut self, mode: usize, on: bool) {
+        match mode {
+            1 => self.app_cursor_keys = on,
+            7 => self.autowrap = on,
+            25 => self.cursor_visible = on,
+            2004 => self.bracketed_paste = on,
+            47 | 1047 | 1049 => {
+                if on && self.main_screen.is_none() {
+                    let lines = std::iter::repeat_with(Row::default)
+                        .take(self.rows)
+                        .collect();
+                    let main_lines = std::mem::replace(&mut self.lines, lines);
+                    self.main_screen = Some((main_lines, self.cursor));
+                    self.generation += 1;
+                } else if !on {
+                    if let Some((lines, cursor)) = self.main_screen.take() {
+                        self.lines = lines;
+                        self.cursor = cursor;
+                        self.generation += 1;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// The operating system command `ESC ] string BEL`.
+    fn osc(&mut self, string: &str) {
+        if let Some(title) = string
+            .strip_prefix("0;")
+            .or_else(|| string.strip_prefix("2;"))
+        {
+            self.title = title.to_owned();
+        }
+    }
+
+    fn erase_line(&mut self, mode: usize) {
+        let (blank, cols, col) = (self.blank(), self.cols, self.cursor.col);
+        let row = self.cursor_row();
+        match mode {
+            0 => row.erase(col..cols, blank),
+            1 => row.erase(0..col + 1, blank),
+            2 => row.erase(0..cols, blank),
+            _ => {}
+        }
+    }
+
+    fn erase_display(&mut self, mode: usize) {
+        let (blank, cols, top) = (self.blank(), self.cols, self.screen_top());
+        let rows = match mode {
+            0 => {
+                self.erase_line(0);
+                top + self.cursor.row + 1..top + self.rows
+            }
+            1 => {
+                self.erase_line(1);
+                top..top + self.cursor.row
+            }
+            2 => top..top + self.rows,
+            3 => {
+                self.lines.drain(..top);
+                self.num_dropped_lines += top;
+                return;
+            }
+            _ => return,
+        };
+        for index in rows {
+            self.lines[index] = Row::default();
+            self.lines[index].erase(0..cols, blank);
+        }
+    }
+}
+
+/// Re-wrap the lines to the given number of columns, keeping the cursor in the same place in the text.
+fn reflow(
+    lines: &mut VecDeque<Row>,
+    cursor: &mut Cursor,
+    old_rows: usize,
+    cols: usize,
+    rows: usize,
+    max_scrollback: usize,
+) {
+    let cursor_line = lines.len() - old_rows + cursor.row;
+
+    // Join the rows that were wrapped, noting where the cursor is:
+    let mut logical_lines: Vec<(Vec<Cell>, Option<usize>)> = Vec::new();
+    let mut current = Vec::new();
+    let mut cursor_offset = None;
+    for (i, row) in std::mem::take(lines).into_iter().enumerate() {
+        if i == cursor_line {
+            cursor_offset = Some(current.len() + cursor.col);
+        }
+        current.extend(row.cells);
+        if !row.wrapped {
+            logical_lines.push((std::mem::take(&mut current), cursor_offset.take()));
+        }
+    }
+    if !current.is_empty() || cursor_offset.is_some() {
+        logical_lines.push((current, cursor_offset));
+    }
+
+    // Empty lines below the cursor are just the unused part of the screen:
+    while logical_lines
+        .last()
+        .is_some_and(|(cells, cursor_offset)| cells.is_empty() && cursor_offset.is_none())
+    {
+        logical_lines.pop();
+    }
+
+    let mut cursor_line = 0;
+    for (mut cells, cursor_offset) in logical_lines {
+        // Trailing blanks would only turn into extra rows:
+        while cursor_offset.unwrap_or(0) < cells.len() && cells.last() == Some(&Cell::default()) {
+            cells.pop();
+        }
+
+        let first = lines.len();
+        lines.extend(cells.chunks(cols).map(|chunk| Row {
+            cells: chunk.to_vec(),
+            wrapped: true,
+        }));
+        if first < lines.len() {
+            lines.back_mut().expect("just added").wrapped = false;
+        } else {
+            lines.push_back(Row::default()); // An empty line
+        }
+
+        if let Some(offset) = cursor_offset {
+            let (row, col) = if 0 < offset && offset == cells.len() && offset % cols == 0 {
+                (offset / cols - 1, cols) // Waiting to wrap
+            } else {
+                (offset / cols, offset % cols)
+            };
+            while lines.len() <= first + row {
+                // The cursor is past the end of the text:
+                let last = lines.back_mut().expect("at least one row");
+                last.cells.resize(cols, Cell::default());
+                last.wrapped = true;
+                lines.push_back(Row::default());
+            }
+            cursor_line = first + row;
+            cursor.col = col;
+        }
+    }
+
+    // Fill the screen, with the cursor on it:
+    while lines.len() < rows {
+        lines.push_back(Row::default());
+    }
+    while rows < lines.len() - cursor_line {
+        lines.pop_back();
+    }
+    cursor.row = cursor_line - (lines.len() - rows);
+
+    while rows + max_scrollback < lines.len() {
+        lines.pop_front();
+    }
+}
+
+/// Resize the alternate screen, which the program will redraw anyway.
+fn resize_without_reflow(
+    lines: &mut VecDeque<Row>,
+    cursor: &mut Cursor,
+    old_rows: usize,
+    cols: usize,
+    rows: usize,
+) {
+    for row in lines.iter_mut() {
+        row.cells.truncate(cols);
+        row.wrapped = false;
+    }
+    if rows < old_rows {
+        // Drop rows below the cursor first:
+        let below = (old_rows - rows).min(old_rows - 1 - cursor.row);
+        lines.truncate(old_rows - below);
+        let above = lines.len() - rows;
+        lines.drain(..above);
+        cursor.row -= above;
+    } else {
+        lines.resize_with(rows, Row::default);
+    }
+    cursor.col = cursor.col.min(cols - 1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn screen(grid: &Grid) -> Vec<String> {
+        grid.lines
+            .iter()
+            .map(|row| row.text(0..usize::MAX))
+            .collect()
+    }
+
+    #[test]
+    fn escape_codes_and_reflow() {
+        let mut grid = Grid::new(10, 3, 100);
+        grid.feed("hello \x1b[31mworld\x1b[0m!\r\n$ ");
+        assert_eq!(screen(&grid), ["hello worl", "d!", "$ "]);
+        assert_eq!(grid.cursor, Cursor { row: 2, col: 2 });
+        assert_eq!(
+            grid.lines[0].cells[6].style.fg,
+            Some(egui::Color32::from_rgb(205, 49, 49))
+        );
+
+        // Wider: the wrapped line is joined again.
+        grid.resize(20, 3);
+        assert_eq!(screen(&grid), ["hello world!", "$ ", ""]);
+        assert_eq!(grid.cursor, Cursor { row: 1, col: 2 });
+
+        // Narrower: the line is wrapped, and the top line goes into the scrollback.
+        grid.resize(5, 3);
+        assert_eq!(screen(&grid), ["hello", " worl", "d!", "$ "]);
+        assert_eq!(grid.cursor, Cursor { row: 2, col: 2 });
+
+        // Cursor movement, erasing, and the cursor position report:
+        grid.feed("\x1b[1;2H\x1b[K\x1b[6n");
+        assert_eq!(screen(&grid), ["hello", " ", "d!", "$ "]);
+        assert_eq!(grid.replies, b"\x1b[1;2R");
+
+        // The alternate screen doesn't touch the main screen:
+        grid.feed("\x1b[?1049hfull screen\x1b[?1049l");
+        assert_eq!(screen(&grid), ["hello", " ", "d!", "$ "]);
+    }
+
+    #[test]
+    fn urls() {
+        let text = "see (https://www.egui.rs/#demo). or http://a.b";
+        let urls: Vec<String> = find_urls(text)
+            .into_iter()
+            .map(|cols| text.chars().skip(cols.start).take(cols.len()).collect())
+            .collect();
+        assert_eq!(urls, ["https://www.egui.rs/#demo", "http://a.b"]);
+    }
+}
@@ -0,0 +1,921 @@
+//! A tree of nodes that can be expanded, selected, renamed and rearranged.
+//!
+//! Only the rows in view are laid out, so it stays fast with hundreds of thousands of nodes.
+
+use std::hash::Hash;
+
+use egui::{
+    ahash::HashSet,
+    collapsing_header::paint_default_icon,
+    pos2,
+    style::ScrollAnimation,
+    text::{CCursor, CCursorRange},
+    vec2, CursorIcon, EventFilter, Id, Key, LayerId, Order, Rect, Response, ScrollArea, Sense,
+    Spinner, Stroke, TextEdit, TextStyle, TextWrapMode, Ui, Vec2, WidgetText,
+};
+
+/// The nodes shown by a [`TreeView`].
+///
+/// The children of a node are only asked for while it is expanded,
+/// so they can be loaded lazily.
+pub trait TreeSource {
+    /// Identifies a node, e.g. an index or a path.
+    ///
+    /// Used to remember what is expanded and selected, so it should stay the same between frames.
+    type Node: Clone + Eq + Hash + Send + Sync + 'static;
+
+    /// The top-level nodes.
+    fn roots(&mut self) -> Vec<Self::Node>;
+
+    /// Can the node have children?
+    ///
+    /// Decides whether it gets an expand arrow, before its children are loaded.
+    fn has_children(&mut self, node: &Self::Node) -> bool;
+
+    /// The children of an expanded node, or `None` while they are still loading.
+    ///
+    /// A spinner is shown in their place until then.
+    /// Call [`egui::Context::request_repaint`] when they have loaded.
+    fn children(&mut self, node: &Self::Node) -> Option<Vec<Self::Node>>;
+
+    /// The name shown for the node, which is also what the user edits when renaming it.
+    fn name(&mut self, node: &Self::Node) -> String;
+}
+
+/// Moving some nodes of a [`TreeView`] by drag-and-drop.
+///
+/// The tree doesn't change the nodes, so it is up to you to apply this to your [`TreeSource`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TreeMove<N> {
+    /// The nodes that were dragged, in the order they were shown.
+    pub nodes: Vec<N>,
+
+    /// Their new parent, or `None` for the top level.
+    pub parent: Option<N>,
+
+    /// Where among the children of [`Self::parent`] to insert them,
+    /// counting the children as they were before the move.
+    ///
+    /// `None` means after the last child.
+    pub index: Option<usize>,
+}
+
+/// What [`TreeView::show`] returns.
+pub struct TreeViewResponse<N> {
+    /// The id of the [`TreeViewState`].
+    pub id: Id,
+
+    /// The response of the whole tree, which is what has keyboard focus.
+    pub response: Response,
+
+    /// Did the user change the selection?
+    pub selection_changed: bool,
+
+    /// The node the user double-clicked, or pressed enter on.
+    pub activated: Option<N>,
+
+    /// The node the user renamed, and its new name.
+    pub renamed: Option<(N, String)>,
+
+    /// The nodes the user moved by drag-and-drop.
+    pub moved: Option<TreeMove<N>>,
+}
+
+/// What of a [`TreeView`] is expanded and selected.
+///
+/// Stored in egui's memory, see [`Self::load`] and [`Self::store`].
+#[derive(Clone, Debug)]
+pub struct TreeViewState<N> {
+    expanded: HashSet<N>,
+    selected: HashSet<N>,
+
+    /// Where selecting a range with shift starts from.
+    anchor: Option<N>,
+
+    /// The node moved around with the arrow keys.
+    cursor: Option<N>,
+
+    /// The node being renamed, and what it is being renamed to.
+    ///
+    /// The name is `None` until it has been filled in from the [`TreeSource`].
+    renaming: Option<(N, Option<String>)>,
+
+    /// The nodes being dragged.
+    dragged: Vec<N>,
+
+    /// The cursor was moved with the keyboard, so scroll to it.
+    scroll_to_cursor: bool,
+
+    /// The scroll offset and height of the view last frame.
+    viewport: (f32, f32),
+}
+
+impl<N> Default for TreeViewState<N> {
+    fn default() -> Self {
+        Self {
+            expanded: Default::default(),
+            selected: Default::default(),
+            anchor: None,
+            cursor: None,
+            renaming: None,
+            dragged: Vec::new(),
+            scroll_to_cursor: false,
+            viewport: (0.0, 0.0),
+        }
+    }
+}
+
+impl<N: Clone + Eq + Hash + Send + Sync + 'static> TreeViewState<N> {
+    /// Load the state of the tree with the given id, see [`TreeViewResponse::id`].
+    pub fn load(ctx: &egui::Context, id: Id) -> Option<Self> {
+        ctx.data_mut(|d| d.get_temp(id))
+    }
+
+    /// Store the state of the tree, e.g. after changing the selection.
+    pub fn store(self, ctx: &egui::Context, id: Id) {
+        ctx.data_mut(|d| d.insert_temp(id, self));
+    }
+
+    pub fn is_expanded(&self, node: &N) -> bool {
+        self.expanded.contains(node)
+    }
+
+    pub fn set_expanded(&mut self, node: N, expanded: bool) {
+        if expanded {
+            self.expanded.insert(node);
+        } else {
+            self.expanded.remove(&node);
+        }
+    }
+
+    pub fn is_selected(&self, node: &N) -> bool {
+        self.selected.contains(node)
+    }
+
+    /// The selected nodes, in no particular order.
+    pub fn selected(&self) -> impl Iterator<Item = &N> {
+        self.selected.iter()
+    }
+
+    /// Select exactly these nodes.
+    pub fn set_selected(&mut self, nodes: impl IntoIterator<Item = N>) {
+        self.selected = nodes.into_iter().collect();
+    }
+
+    /// The node that is moved with the arrow keys.
+    pub fn cursor(&self) -> Option<&N> {
+        self.cursor.as_ref()
+    }
+
+    /// Let the user rename the node, as if they had pressed F2 on it.
+    pub fn start_rename(&mut self, node: N) {
+        self.renaming = Some((node, None));
+    }
+}
+
+/// A row of the flattened tree.
+struct Row<N> {
+    /// `None` for the placeholder of children that are still loading.
+    node: Option<N>,
+
+    depth: usize,
+
+    /// The row of the parent.
+    parent: Option<usize>,
+
+    /// Index among the children of the parent.
+    index: usize,
+
+    has_children: bool,
+}
+
+/// The rows of the tree, with the children of expanded nodes right after them.
+fn flatten<S: TreeSource>(source: &mut S, expanded: &HashSet<S::Node>) -> Vec<Row<S::Node>> {
+    crate::profile_function!();
+
+    let mut rows = Vec::new();
+    let mut stack: Vec<(S::Node, usize, Option<usize>, usize)> = source
+        .roots()
+        .into_iter()
+        .enumerate()
+        .rev()
+        .map(|(index, node)| (node, 0, None, index))
+        .collect();
+
+    while let Some((node, depth, parent, index)) = stack.pop() {
+        let has_children = source.has_children(&node);
+        let is_expanded = has_children && expanded.contains(&node);
+        let row = rows.len();
+        rows.push(Row {
+            node: Some(node.clone()),
+            depth,
+            parent,
+            index,
+            has_children,
+        });
+
+        if is_expanded {
+            if let Some(children) = source.children(&node) {
+                stack.extend(
+                    children
+                        .into_iter()
+                        .enumerate()
+                        .rev()
+                        .map(|(index, child)| (child, depth + 1, Some(row), index)),
+                );
+            } else {
+                rows.push(Row {
+                    node: None,
+                    depth: depth + 1,
+                    parent: Some(row),
+                    index: 0,
+                    has_children: false,
+                });
+            }
+        }
+    }
+
+    rows
+}
+
+/// The closest row with a node before or after `from`,
+/// or the first or last one if there is no `from`.
+fn next_node_row<N>(rows: &[Row<N>], from: Option<usize>, forward: bool) -> Option<usize> {
+    match (from, forward) {
+        (Some(from), true) => (from + 1..rows.len()).find(|&i| rows[i].node.is_some()),
+        (Some(from), false) => (0..from).rev().find(|&i| rows[i].node.is_some()),
+        (None, true) => (0..rows.len()).find(|&i| rows[i].node.is_some()),
+        (None, false) => (0..rows.len()).rev().find(|&i| rows[i].node.is_some()),
+    }
+}
+
+/// Where dragged nodes would be dropped, relative to a row.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DropPosition {
+    Before,
+    Onto,
+    After,
+}
+
+/// A tree of nodes that can be expanded and collapsed, like a file browser.
+///
+/// * Click to select a node, with ctrl/⌘ to toggle it, with shift to select a range.
+/// * Use the arrow keys to move around, left and right to collapse and expand.
+/// * Press F2 to rename the node under the keyboard cursor.
+/// * Drag selected nodes onto another node to move them into it, or between nodes to move them there.
+///
+/// Only the visible rows are shown, so all rows have the same height.
+/// What is expanded and selected is kept in a [`TreeViewState`].
+///
+/// ```
+/// struct Numbers;
+///
+/// impl egui_extras::TreeSource for Numbers {
+///     type Node = u64;
+///
+///     fn roots(&mut self) -> Vec<u64> {
+///         (1..=10).collect()
+///     }
+///
+///     fn has_children(&mut self, _node: &u64) -> bool {
+///         true
+///     }
+///
+///     fn children(&mut self, node: &u64) -> Option<Vec<u64>> {
+///         Some((1..=10).map(|i| node * 10 + i).collect())
+///     }
+///
+///     fn name(&mut self, node: &u64) -> String {
+///         node.to_string()
+///     }
+/// }
+///
+/// # egui::__run_test_ui(|ui| {
+/// let response = egui_extras::TreeView::new("numbers").show(ui, &mut Numbers);
+/// if let Some(node) = response.activated {
+///     println!("Opened {node}");
+/// }
+/// # });
+/// ```
+#[must_use = "You should call .show()"]
+pub struct TreeView {
+    id_salt: Id,
+    row_height: Option<f32>,
+    indent: Option<f32>,
+    multi_select: bool,
+    drag_and_drop: bool,
+    rename: bool,
+}
+
+impl TreeView {
+    pub fn new(id_salt: impl Hash) -> Self {
+        Self {
+            id_salt: Id::new(id_salt),
+            row_height: None,
+            indent: None,
+            multi_select: true,
+            drag_and_drop: true,
+            rename: true,
+        }
+    }
+
+    /// The height of each row.
+    ///
+    /// Default: [`egui::style::Spacing::interact_size`]`.y`.
+    #[inline]
+    pub fn row_height(mut self, row_height: f32) -> Self {
+        self.row_height = Some(row_height);
+        self
+    }
+
+    /// How much further in each level of the tree is.
+    ///
+    /// Default: [`egui::style::Spacing::indent`].
+    #[inline]
+    pub fn indent(mut self, indent: f32) -> Self {
+        self.indent = Some(indent);
+        self
+    }
+
+    /// Can the user select more than one node, with ctrl/⌘ and shift?
+    ///
+    /// Default: `true`.
+    #[inline]
+    pub fn multi_select(mut self, multi_select: bool) -> Self {
+        self.multi_select = multi_select;
+        self
+    }
+
+    /// Can the user move nodes by drag-and-drop? See [`TreeViewResponse::moved`].
+    ///
+    /// Default: `true`.
+    #[inline]
+    pub fn drag_and_drop(mut self, drag_and_drop: bool) -> Self {
+        self.drag_and_drop = drag_and_drop;
+        self
+    }
+
+    /// Can the user rename nodes with F2? See [`TreeViewResponse::renamed`].
+    ///
+    /// Default: `true`.
+    #[inline]
+    pub fn rename(mut self, rename: bool) -> Self {
+        self.rename = rename;
+        self
+    }
+
+    /// Show the tree, filling the available space and scrolling if it doesn't fit.
+    pub fn show<S: TreeSource>(self, ui: &mut Ui, source: &mut S) -> TreeViewResponse<S::Node> {
+        crate::profile_function!();
+
+        let id = ui.make_persistent_id(self.id_salt);
+        let mut state = TreeViewState::<S::Node>::load(ui.ctx(), id).unwrap_or_default();
+        let rows = flatten(source, &state.expanded);
+
+        let row_height = self
+            .row_height
+            .unwrap_or_else(|| ui.spacing().interact_size.y);
+
+        // The tree as a whole has the keyboard focus, not the rows:
+        let tree_response = ui.interact(
+            ui.available_rect_before_wrap(),
+            id,
+            Sense::focusable_noninteractive(),
+        );
+
+        let mut response = TreeViewResponse {
+            id,
+            response: tree_response,
+            selection_changed: false,
+            activated: None,
+            renamed: None,
+            moved: None,
+        };
+
+        if response.response.has_focus() && state.renaming.is_none() {
+            ui.memory_mut(|mem| {
+                mem.set_focus_lock_filter(
+                    id,
+                    EventFilter {
+                        horizontal_arrows: true,
+                        vertical_arrows: true,
+                        ..Default::default()
+                    },
+                );
+            });
+            self.keyboard_input(ui, &rows, &mut state, &mut response);
+        }
+
+        let mut scroll_area = ScrollArea::vertical()
+            .id_salt(id.with("scroll"))
+            .auto_shrink(false);
+        if std::mem::take(&mut state.scroll_to_cursor) {
+            let cursor_row = state.cursor.as_ref().and_then(|cursor| {
+                rows.iter()
+                    .position(|row| row.node.as_ref() == Some(cursor))
+            });
+            if let Some(cursor_row) = cursor_row {
+                let (offset, height) = state.viewport;
+                let top = cursor_row as f32 * row_height;
+                if top < offset {
+                    scroll_area = scroll_area.vertical_scroll_offset(top);
+                } else if offset + height < top + row_height {
+                    scroll_area = scroll_area.vertical_scroll_offset(top + row_height - height);
+                }
+            }
+        }
+
+        let mut drop = None;
+        let output = scroll_area.show_viewport(ui, |ui, viewport| {
+            ui.set_height(row_height * rows.len() as f32);
+
+            let first = (viewport.min.y / row_height).floor().max(0.0) as usize;
+            let last = ((viewport.max.y / row_height).ceil().max(0.0) as usize).min(rows.len());
+            let content_rect = ui.max_rect();
+            for i in first..last {
+                let rect = Rect::from_min_size(
+                    pos2(
+                        content_rect.left(),
+                        content_rect.top() + i as f32 * row_height,
+                    ),
+                    vec2(content_rect.width(), row_height),
+                );
+                if let Some(row_drop) =
+                    self.row_ui(ui, source, &rows, i, rect, &mut state, &mut response)
+                {
+                    drop = Some(row_drop);
+                }
+            }
+
+            if !state.dragged.is_empty() {
+                auto_scroll(ui, row_height);
+            }
+        });
+        state.viewport = (output.state.offset.y, output.inner_rect.height());
+
+        if !state.dragged.is_empty() {
+            paint_dragged(ui, source, id, &state.dragged);
+
+            let (released, cancelled) =
+                ui.input(|i| (i.pointer.any_released(), i.key_pressed(Key::Escape)));
+            if released || cancelled {
+                let dragged = std::mem::take(&mut state.dragged);
+                if released {
+                    response.moved = drop.map(|(parent, index)| TreeMove {
+                        nodes: dragged,
+                        parent,
+                        index,
+                    });
+                }
+            }
+        }
+
+        state.store(ui.ctx(), id);
+
+        response
+    }
+
+    fn keyboard_input<N: Clone + Eq + Hash>(
+        &self,
+        ui: &Ui,
+        rows: &[Row<N>],
+        state: &mut TreeViewState<N>,
+        response: &mut TreeViewResponse<N>,
+    ) {
+        let cursor_row = state.cursor.as_ref().and_then(|cursor| {
+            rows.iter()
+                .position(|row| row.node.as_ref() == Some(cursor))
+        });
+
+        let (modifiers, up, down, left, right, home, end, space, enter, f2) = ui.input(|i| {
+            (
+                i.modifiers,
+                i.key_pressed(Key::ArrowUp),
+                i.key_pressed(Key::ArrowDown),
+                i.key_pressed(Key::ArrowLeft),
+                i.key_pressed(Key::ArrowRight),
+                i.key_pressed(Key::Home),
+                i.key_pressed(Key::End),
+                i.key_pressed(Key::Space),
+                i.key_pressed(Key::Enter),
+                i.key_pressed(Key::F2),
+            )
+        });
+
+        let mut new_cursor_row = None;
+        if up {
+            new_cursor_row = next_node_row(rows, cursor_row, false);
+        } else if down {
+            new_cursor_row = next_node_row(rows, cursor_row, true);
+        } else if home {
+            new_cursor_row = next_node_row(rows, None, true);
+        } else if end {
+            new_cursor_row = next_node_row(rows, None, false);
+        } else if let Some(cursor_row) = cursor_row {
+            let row = &rows[cursor_row];
+            let node = row.node.clone().expect("the cursor is on a node");
+            let is_expanded = state.expanded.contains(&node);
+            if left {
+                if is_expanded {
+                    state.expanded.remove(&node);
+                } else {
+                    new_cursor_row = row.parent;
+                }
+            } else if right && row.has_children {
+                if is_expanded {
+                    new_cursor_row = next_node_row(rows, Some(cursor_row), true)
+                        .filter(|&child| rows[child].parent == Some(cursor_row));
+                } else {
+                    state.expanded.insert(node);
+                }
+            } else if space {
+                if !self.multi_select {
+                    state.selected = std::iter::once(node.clone()).collect();
+                } else if !state.selected.remove(&node) {
+                    state.selected.insert(node.clone());
+                }
+                state.anchor = Some(node);
+                response.selection_changed = true;
+            } else if enter {
+                response.activated = Some(node);
+            } else if f2 && self.rename {
+                state.renaming = Some((node, None));
+            }
+        }
+
+        if let Some(row) = new_cursor_row {
+            if modifiers.command && self.multi_select {
+                // Only move the cursor, so space can add to the selection.
+                state.cursor = rows[row].node.clone();
+            } else {
+                self.select_row(rows, row, modifiers.shift, state, response);
+            }
+            state.scroll_to_cursor = true;
+        }
+    }
+
+    /// Move the cursor to the row and select it, or the range from the anchor to it.
+    fn select_row<N: Clone + Eq + Hash>(
+        &self,
+        rows: &[Row<N>],
+        row: usize,
+        extend: bool,
+        state: &mut TreeViewState<N>,
+        response: &mut TreeViewResponse<N>,
+    ) {
+        let Some(node) = rows[row].node.clone() else {
+            return;
+        };
+
+        let anchor_row = state.anchor.as_ref().and_then(|anchor| {
+            rows.iter()
+                .position(|row| row.node.as_ref() == Some(anchor))
+        });
+
+        if let Some(anchor_row) = anchor_row.filter(|_| extend && self.multi_select) {
+            let range = anchor_row.min(row)..=anchor_row.max(row);
+            state.selected = rows[range]
+                .iter()
+                .filter_map(|row| row.node.clone())
+                .collect();
+        } else {
+            state.selected = std::iter::once(node.clone()).collect();
+            state.anchor = Some(node.clone());
+        }
+
+        state.cursor = Some(node);
+        response.selection_changed = true;
+    }
+
+    /// Show a row, returning where the dragged nodes would be dropped if the pointer is over it.
+    #[allow(clippy::too_many_arguments)]
+    fn row_ui<S: TreeSource>(
+        &self,
+        ui: &mut Ui,
+        source: &mut S,
+        rows: &[Row<S::Node>],
+        row_index: usize,
+        rect: Rect,
+        state: &mut TreeViewState<S::Node>,
+        response: &mut TreeViewResponse<S::Node>,
+    ) -> Option<(Option<S::Node>, Option<usize>)> {
+        let row = &rows[row_index];
+        let indent = self.indent.unwrap_or_else(|| ui.spacing().indent);
+        let arrow_rect = Rect::from_min_size(
+            pos2(rect.left() + row.depth as f32 * indent, rect.top()),
+            Vec2::splat(rect.height()),
+        );
+        let text_rect = Rect::from_min_max(
+            pos2(arrow_rect.right() + ui.spacing().icon_spacing, rect.top()),
+            rect.right_bottom(),
+        );
+
+        let Some(node) = &row.node else {
+            // The children are still loading:
+            ui.put(arrow_rect.shrink(2.0), Spinner::new());
+            ui.painter().text(
+                text_rect.left_center(),
+                egui::Align2::LEFT_CENTER,
+                "Loading…",
+                TextStyle::Body.resolve(ui.style()),
+                ui.visuals().weak_text_color(),
+            );
+            return None;
+        };
+
+        let row_id = response.id.with(node);
+        let sense = if self.drag_and_drop {
+            Sense::click_and_drag()
+        } else {
+            Sense::click()
+        };
+        let row_response = ui.interact(rect, row_id, sense);
+
+        let is_selected = state.selected.contains(node);
+        let is_expanded = state.expanded.contains(node);
+        let visuals = ui.visuals();
+        if is_selected {
+            ui.painter()
+                .rect_filled(rect, 0.0, visuals.selection.bg_fill);
+        } else if row_response.hovered() && state.dragged.is_empty() {
+            ui.painter()
+                .rect_filled(rect, 0.0, visuals.widgets.hovered.weak_bg_fill);
+        }
+        if state.cursor.as_ref() == Some(node) && response.response.has_focus() {
+            ui.painter()
+                .rect_stroke(rect.shrink(1.0), 0.0, visuals.selection.stroke);
+        }
+
+        if row.has_children {
+            let mut arrow_response = ui.interact(arrow_rect, row_id.with("arrow"), Sense::click());
+            if arrow_response.clicked() {
+                state.set_expanded(node.clone(), !is_expanded);
+            }
+            let openness = ui.ctx().animate_bool(row_id.with("arrow"), is_expanded);
+            arrow_response.rect = arrow_rect.shrink(arrow_rect.height() / 4.0);
+            paint_default_icon(ui, openness, &arrow_response);
+        }
+
+        let renaming = state
+            .renaming
+            .as_mut()
+            .filter(|(renamed, _)| renamed == node);
+        if let Some((_, name)) = renaming {
+            let edit_id = row_id.with("rename");
+            let name = name.get_or_insert_with(|| {
+                // Just started: select the whole name, so typing replaces it.
+                let name = source.name(node);
+                let mut edit_state = TextEdit::load_state(ui.ctx(), edit_id).unwrap_or_default();
+                edit_state.cursor.set_char_range(Some(CCursorRange::two(
+                    CCursor::new(0),
+                    CCursor::new(name.chars().count()),
+                )));
+                TextEdit::store_state(ui.ctx(), edit_id, edit_state);
+                ui.memory_mut(|mem| mem.request_focus(edit_id));
+                name
+            });
+            let edit_response = ui.put(
+                text_rect,
+                TextEdit::singleline(name)
+                    .id(edit_id)
+                    .margin(vec2(2.0, 0.0))
+                    .desired_width(text_rect.width()),
+            );
+            if edit_response.lost_focus() {
+                let cancelled = ui.input(|i| i.key_pressed(Key::Escape));
+                if let Some((node, Some(name))) = state.renaming.take() {
+                    if !cancelled {
+                        response.renamed = Some((node, name));
+                    }
+                }
+                ui.memory_mut(|mem| mem.request_focus(response.id));
+            }
+        } else {
+            let galley = WidgetText::from(source.name(node)).into_galley(
+                ui,
+                Some(TextWrapMode::Truncate),
+                text_rect.width(),
+                TextStyle::Button,
+            );
+            let text_color = if is_selected {
+                ui.visuals().selection.stroke.color
+            } else {
+                ui.visuals().text_color()
+            };
+            let text_pos = pos2(
+                text_rect.left(),
+                text_rect.center().y - galley.size().y / 2.0,
+            );
+            ui.painter().galley(text_pos, galley, text_color);
+        }
+
+        if row_response.clicked() {
+            let modifiers = ui.input(|i| i.modifiers);
+            if modifiers.command && self.multi_select {
+                if !state.selected.remove(node) {
+                    state.selected.insert(node.clone());
+                }
+                state.anchor = Some(node.clone());
+                state.cursor = Some(node.clone());
+                response.selection_changed = true;
+            } else {
+                self.select_row(rows, row_index, modifiers.shift, state, response);
+            }
+            ui.memory_mut(|mem| mem.request_focus(response.id));
+        }
+
+        if row_response.double_clicked() {
+            if row.has_children {
+                state.set_expanded(node.clone(), !is_expanded);
+            }
+            response.activated = Some(node.clone());
+        }
+
+        if row_response.drag_started() {
+            state.dragged = if is_selected {
+                rows.iter()
+                    .filter_map(|row| row.node.clone())
+                    .filter(|node| state.selected.contains(node))
+                    .collect()
+            } else {
+                vec![node.clone()]
+            };
+        }
+
+        if state.dragged.is_empty() {
+            return None;
+        }
+        let pointer = ui.ctx().pointer_interact_pos()?;
+        if !rect.contains(pointer) {
+            return None;
+        }
+
+        let t = (pointer.y - rect.top()) / rect.height();
+        let position = if row.has_children {
+            if t < 0.25 {
+                DropPosition::Before
+            } else if t < 0.75 || is_expanded {
+                DropPosition::Onto
+            } else {
+                DropPosition::After
+            }
+        } else if t < 0.5 {
+            DropPosition::Before
+        } else {
+            DropPosition::After
+        };
+
+        let parent_row = match position {
+            DropPosition::Onto => Some(row_index),
+            DropPosition::Before | DropPosition::After => row.parent,
+        };
+
+        // Nodes can't be moved into themselves:
+        let mut ancestor = parent_row;
+        while let Some(ancestor_row) = ancestor {
+            let ancestor_node = rows[ancestor_row].node.as_ref();
+            if ancestor_node.is_some_and(|node| state.dragged.contains(node)) {
+                return None;
+            }
+            ancestor = rows[ancestor_row].parent;
+        }
+
+        let stroke = Stroke::new(2.0, ui.visuals().selection.stroke.color);
+        let line_x = arrow_rect.left()..=rect.right();
+        let index = match position {
+            DropPosition::Before => {
+                ui.painter().hline(line_x, rect.top(), stroke);
+                Some(row.index)
+            }
+            DropPosition::After => {
+                ui.painter().hline(line_x, rect.bottom(), stroke);
+                Some(row.index + 1)
+            }
+            DropPosition::Onto => {
+                ui.painter().rect_stroke(rect.shrink(1.0), 2.0, stroke);
+                None
+            }
+        };
+        ui.ctx().set_cursor_icon(CursorIcon::Grabbing);
+
+        let parent = parent_row.and_then(|parent_row| rows[parent_row].node.clone());
+        Some((parent, index))
+    }
+}
+
+/// Paint what is being dragged next to the pointer.
+fn paint_dragged<S: TreeSource>(ui: &Ui, source: &mut S, id: Id, dragged: &[S::Node]) {
+    let Some(pointer) = ui.ctx().pointer_interact_pos() else {
+        return;
+    };
+    let text = match dragged {
+        [node] => source.name(node),
+        nodes => format!("{} items", nodes.len()),
+    };
+    let painter = ui
+        .ctx()
+        .layer_painter(LayerId::new(Order::Tooltip, id.with("dragged")));
+    let galley = painter.layout_no_wrap(
+        text,
+        TextStyle::Button.resolve(ui.style()),
+        ui.visuals().strong_text_color(),
+    );
+    let rect = Rect::from_min_size(pointer + vec2(16.0, 0.0), galley.size())
+        .expand2(ui.spacing().button_padding);
+    painter.rect(
+        rect,
+        ui.visuals().menu_rounding,
+        ui.visuals().window_fill,
+        ui.visuals().window_stroke,
+    );
+    painter.galley(
+        rect.min + ui.spacing().button_padding,
+        galley,
+        ui.visuals().strong_text_color(),
+    );
+}
+
+/// Scroll while dragging close to the top or bottom of the view.
+fn auto_scroll(ui: &Ui, margin: f32) {
+    let Some(pointer) = ui.ctx().pointer_interact_pos() else {
+        return;
+    };
+    let clip_rect = ui.clip_rect();
+    if !clip_rect.x_range().contains(pointer.x) {
+        return;
+    }
+
+    let speed = 8.0 * margin; // points per second
+    let dt = ui.input(|i| i.stable_dt).min(0.1);
+    let delta = if pointer.y < clip_rect.top() + margin {
+        speed * dt
+    } else if clip_rect.bottom() - margin < pointer.y {
+        -speed * dt
+    } else {
+        return;
+    };
+    ui.scroll_with_delta_animation(vec2(0.0, delta), ScrollAnimation::none());
+    ui.ctx().request_repaint();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A tree where node `n` has the children `10n + 1 ..= 10n + 3`, three levels deep.
+    struct Numbers;
+
+    impl TreeSource for Numbers {
+        type Node = u32;
+
+        fn roots(&mut self) -> Vec<u32> {
+            vec![1, 2, 3]
+        }
+
+        fn has_children(&mut self, node: &u32) -> bool {
+            *node < 100
+        }
+
+        fn children(&mut self, node: &u32) -> Option<Vec<u32>> {
+            if *node == 3 {
+                None // Still loading
+            } else {
+                Some((1..=3).map(|i| node * 10 + i).collect())
+            }
+        }
+
+        fn name(&mut self, node: &u32) -> String {
+            node.to_string()
+        }
+    }
+
+    #[test]
+    fn flatten_expanded() {
+        let expanded: HashSet<u32> = [1, 12, 3].into_iter().collect();
+        let rows = flatten(&mut Numbers, &expanded);
+        let nodes: Vec<Option<u32>> = rows.iter().map(|row| row.node).collect();
+        assert_eq!(
+            nodes,
+            [
+                Some(1),
+                Some(11),
+                Some(12),
+                Some(121),
+                Some(122),
+                Some(123),
+                Some(13),
+                Some(2),
+                Some(3),
+                None,
+            ]
+        );
+        assert_eq!(rows[3].depth, 2);
+        assert_eq!(rows[3].parent, Some(2));
+        assert_eq!(rows[6].index, 2);
+        assert_eq!(rows[9].parent, Some(8));
+
+        assert_eq!(next_node_row(&rows, Some(8), true), None);
+        assert_eq!(next_node_row(&rows, None, false), Some(8));
+        assert_eq!(next_node_row(&rows, Some(7), false), Some(6));
+    }
+}
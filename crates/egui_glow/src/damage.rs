@@ -0,0 +1,217 @@
+//! Only repaint the parts of the screen that changed, see [`DamageTracker`].
+
+use std::{
+    collections::{HashSet, VecDeque},
+    hash::{Hash as _, Hasher as _},
+};
+
+use egui::{epaint::Primitive, ClippedPrimitive, Rect, TextureId, TexturesDelta};
+
+/// How many frames back we remember, i.e. the largest buffer age we can repaint partially.
+const MAX_BUFFER_AGE: usize = 4;
+
+/// Finds out which parts of the screen changed since earlier frames,
+/// so that only those need to be repainted and presented.
+///
+/// The changes are found by comparing the [`ClippedPrimitive`]s of each frame to those of the frame before,
+/// so anything painted outside of egui (e.g. directly with OpenGL before egui paints) is not noticed.
+///
+/// Use the result with [`crate::Painter::set_damage`],
+/// and e.g. `eglSwapBuffersWithDamageKHR` when presenting the frame.
+#[derive(Default)]
+pub struct DamageTracker {
+    /// The hash and area of each primitive of the last frame.
+    last_primitives: Vec<(u64, Rect)>,
+
+    last_screen_size_px: [u32; 2],
+    last_pixels_per_point: f32,
+
+    /// What changed in each of the most recent frames, the latest last.
+    ///
+    /// `None` means everything.
+    history: VecDeque<Option<Rect>>,
+}
+
+impl DamageTracker {
+    /// Call once per frame, with what is about to be painted.
+    ///
+    /// `buffer_age` is how many frames ago the back buffer was presented,
+    /// e.g. from `eglQuerySurface(…, EGL_BUFFER_AGE_EXT, …)`, or `0` if unknown.
+    ///
+    /// Returns the area (in physical pixels, rounded outwards) that needs to be repainted,
+    /// or `None` if everything must be repainted.
+    pub fn update(
+        &mut self,
+        screen_size_px: [u32; 2],
+        pixels_per_point: f32,
+        clipped_primitives: &[ClippedPrimitive],
+        textures_delta: &TexturesDelta,
+        buffer_age: u32,
+    ) -> Option<Rect> {
+        crate::profile_function!();
+
+        let primitives: Vec<(u64, Rect)> = clipped_primitives.iter().map(hash_and_rect).collect();
+
+        let damage = if screen_size_px == self.last_screen_size_px
+            && pixels_per_point == self.last_pixels_per_point
+        {
+            let changed_textures: HashSet<TextureId> = textures_delta
+                .set
+                .iter()
+                .map(|(id, _)| *id)
+                .chain(textures_delta.options.iter().map(|(id, _)| *id))
+                .collect();
+            self.changes(clipped_primitives, &primitives, &changed_textures)
+        } else {
+            None
+        };
+
+        self.last_primitives = primitives;
+        self.last_screen_size_px = screen_size_px;
+        self.last_pixels_per_point = pixels_per_point;
+        self.history.push_back(damage);
+        while MAX_BUFFER_AGE < self.history.len() {
+            self.history.pop_front();
+        }
+
+        let buffer_age = buffer_age as usize;
+        if buffer_age == 0 || self.history.len() < buffer_age {
+            return None; // We don't know what is in the back buffer
+        }
+        let mut damage = Rect::NOTHING;
+        for frame_damage in self.history.iter().rev().take(buffer_age) {
+            damage = damage.union((*frame_damage)?);
+        }
+
+        // Make sure the anti-aliasing feathering and the rounding to pixels are inside:
+        let screen_rect = Rect::from_min_size(
+            egui::Pos2::ZERO,
+            egui::vec2(screen_size_px[0] as f32, screen_size_px[1] as f32),
+        );
+        let damage = (damage * pixels_per_point).expand(1.0);
+        Some(Rect::from_min_max(damage.min.floor(), damage.max.ceil()).intersect(screen_rect))
+    }
+
+    /// The area that changed since the last frame, or `None` if everything may have changed.
+    fn changes(
+        &self,
+        clipped_primitives: &[ClippedPrimitive],
+        primitives: &[(u64, Rect)],
+        changed_textures: &HashSet<TextureId>,
+    ) -> Option<Rect> {
+        let new: HashSet<u64> = primitives.iter().map(|(hash, _)| *hash).collect();
+        let old: HashSet<u64> = self.last_primitives.iter().map(|(hash, _)| *hash).collect();
+
+        // If things that didn't change were painted in a different order (e.g. a window was raised),
+        // we don't know what ends up on top where:
+        let kept_new = primitives.iter().filter(|(hash, _)| old.contains(hash));
+        let kept_old = self
+            .last_primitives
+            .iter()
+            .filter(|(hash, _)| new.contains(hash));
+        if !kept_new
+            .map(|(hash, _)| hash)
+            .eq(kept_old.map(|(hash, _)| hash))
+        {
+            return None;
+        }
+
+        let mut damage = Rect::NOTHING;
+        for ((hash, rect), clipped_primitive) in primitives.iter().zip(clipped_primitives) {
+            let changed = match &clipped_primitive.primitive {
+                Primitive::Mesh(mesh) => {
+                    !old.contains(hash) || changed_textures.contains(&mesh.texture_id)
+                }
+                Primitive::Callback(_) => true, // Could be painting anything
+            };
+            if changed {
+                damage = damage.union(*rect);
+            }
+        }
+        for (hash, rect) in &self.last_primitives {
+            if !new.contains(hash) {
+                damage = damage.union(*rect); // Gone, so what was below it shows
+            }
+        }
+        Some(damage)
+    }
+}
+
+/// The area a primitive may paint to, and a hash of what it paints.
+fn hash_and_rect(clipped_primitive: &ClippedPrimitive) -> (u64, Rect) {
+    let ClippedPrimitive {
+        clip_rect,
+        primitive,
+    } = clipped_primitive;
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    for value in [
+        clip_rect.min.x,
+        clip_rect.min.y,
+        clip_rect.max.x,
+        clip_rect.max.y,
+    ] {
+        value.to_bits().hash(&mut hasher);
+    }
+    let rect = match primitive {
+        Primitive::Mesh(mesh) => {
+            mesh.texture_id.hash(&mut hasher);
+            mesh.texture_options.hash(&mut hasher);
+            mesh.blend_mode.hash(&mut hasher);
+            hasher.write(bytemuck::cast_slice(&mesh.vertices));
+            hasher.write(bytemuck::cast_slice(&mesh.indices));
+            mesh.calc_bounds()
+        }
+        Primitive::Callback(callback) => callback.rect,
+    };
+    (hasher.finish(), rect.intersect(*clip_rect))
+}
+
+#[cfg(test)]
+mod tests {
+    use egui::{epaint::Mesh, pos2, Color32};
+
+    use super::*;
+
+    #[test]
+    fn only_changes_are_damaged() {
+        let square = |min: f32| {
+            let mut mesh = Mesh::default();
+            mesh.add_colored_rect(
+                Rect::from_min_size(pos2(min, min), egui::Vec2::splat(10.0)),
+                Color32::RED,
+            );
+            ClippedPrimitive {
+                clip_rect: Rect::EVERYTHING,
+                primitive: Primitive::Mesh(mesh),
+            }
+        };
+        let size = [200, 200];
+        let no_textures = TexturesDelta::default();
+
+        let mut tracker = DamageTracker::default();
+        let frame = [square(0.0), square(50.0)];
+        assert_eq!(tracker.update(size, 1.0, &frame, &no_textures, 1), None);
+
+        // Nothing changed:
+        let damage = tracker.update(size, 1.0, &frame, &no_textures, 1).unwrap();
+        assert!(!damage.is_positive());
+
+        // One square moved:
+        let frame = [square(0.0), square(60.0)];
+        let damage = tracker.update(size, 1.0, &frame, &no_textures, 1).unwrap();
+        assert!(damage.contains_rect(Rect::from_min_max(pos2(50.0, 50.0), pos2(70.0, 70.0))));
+        assert!(!damage.contains(pos2(5.0, 5.0)));
+
+        // With an older back buffer, the changes of the frame before are also repainted:
+        let damage = tracker.update(size, 1.0, &frame, &no_textures, 2).unwrap();
+        assert!(damage.contains(pos2(55.0, 55.0)));
+
+        // Unknown back buffer:
+        assert_eq!(tracker.update(size, 1.0, &frame, &no_textures, 0), None);
+
+        // Different order:
+        let frame = [square(60.0), square(0.0)];
+        assert_eq!(tracker.update(size, 1.0, &frame, &no_textures, 1), None);
+    }
+}
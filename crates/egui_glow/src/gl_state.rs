@@ -0,0 +1,210 @@
+//! Saving and restoring the OpenGL state around user paint callbacks.
+
+#![allow(unsafe_code)]
+
+use glow::HasContext as _;
+
+/// How many texture units we save the 2D texture binding of.
+const NUM_SAVED_TEXTURE_UNITS: u32 = 4;
+
+/// A snapshot of the OpenGL state that a [`crate::CallbackFn`] is likely to change.
+///
+/// This covers the state egui itself depends on, plus the most common state a 3D renderer touches:
+/// * The bound framebuffer, program, vertex array and buffers
+/// * The 2D textures bound to the first few texture units, and the active texture unit
+/// * Viewport and scissor box
+/// * Blend, scissor, depth, stencil, cull face and sRGB toggles
+/// * Blend equation and function
+/// * Color and depth write masks
+pub(crate) struct GlState {
+    framebuffer: Option<glow::Framebuffer>,
+    program: Option<glow::Program>,
+
+    supports_vao: bool,
+    vertex_array: Option<glow::VertexArray>,
+    array_buffer: Option<glow::Buffer>,
+    element_array_buffer: Option<glow::Buffer>,
+
+    active_texture: u32,
+    textures_2d: Vec<Option<glow::Texture>>,
+
+    viewport: [i32; 4],
+    scissor_box: [i32; 4],
+
+    blend: bool,
+    scissor_test: bool,
+    depth_test: bool,
+    stencil_test: bool,
+    cull_face: bool,
+
+    /// `None` if sRGB framebuffers are not supported.
+    framebuffer_srgb: Option<bool>,
+
+    blend_equation_rgb: u32,
+    blend_equation_alpha: u32,
+    blend_src_rgb: u32,
+    blend_dst_rgb: u32,
+    blend_src_alpha: u32,
+    blend_dst_alpha: u32,
+
+    color_mask: [bool; 4],
+    depth_mask: bool,
+}
+
+impl GlState {
+    pub(crate) unsafe fn capture(
+        gl: &glow::Context,
+        supports_vao: bool,
+        supports_srgb_framebuffer: bool,
+    ) -> Self {
+        crate::profile_function!();
+
+        unsafe {
+            let active_texture = gl.get_parameter_i32(glow::ACTIVE_TEXTURE) as u32;
+            let num_texture_units = NUM_SAVED_TEXTURE_UNITS
+                .min(gl.get_parameter_i32(glow::MAX_COMBINED_TEXTURE_IMAGE_UNITS) as u32);
+            let textures_2d = (0..num_texture_units)
+                .map(|unit| {
+                    gl.active_texture(glow::TEXTURE0 + unit);
+                    gl.get_parameter_texture(glow::TEXTURE_BINDING_2D)
+                })
+                .collect();
+            gl.active_texture(active_texture);
+
+            let mut viewport = [0; 4];
+            gl.get_parameter_i32_slice(glow::VIEWPORT, &mut viewport);
+            let mut scissor_box = [0; 4];
+            gl.get_parameter_i32_slice(glow::SCISSOR_BOX, &mut scissor_box);
+
+            let state = Self {
+                framebuffer: gl.get_parameter_framebuffer(glow::FRAMEBUFFER_BINDING),
+                program: gl.get_parameter_program(glow::CURRENT_PROGRAM),
+                supports_vao,
+                vertex_array: if supports_vao {
+                    gl.get_parameter_vertex_array(glow::VERTEX_ARRAY_BINDING)
+                } else {
+                    None
+                },
+                array_buffer: gl.get_parameter_buffer(glow::ARRAY_BUFFER_BINDING),
+                element_array_buffer: gl.get_parameter_buffer(glow::ELEMENT_ARRAY_BUFFER_BINDING),
+
+                active_texture,
+                textures_2d,
+
+                viewport,
+                scissor_box,
+
+                blend: gl.is_enabled(glow::BLEND),
+                scissor_test: gl.is_enabled(glow::SCISSOR_TEST),
+                depth_test: gl.is_enabled(glow::DEPTH_TEST),
+                stencil_test: gl.is_enabled(glow::STENCIL_TEST),
+                cull_face: gl.is_enabled(glow::CULL_FACE),
+                framebuffer_srgb: supports_srgb_framebuffer
+                    .then(|| gl.is_enabled(glow::FRAMEBUFFER_SRGB)),
+
+                blend_equation_rgb: gl.get_parameter_i32(glow::BLEND_EQUATION_RGB) as u32,
+                blend_equation_alpha: gl.get_parameter_i32(glow::BLEND_EQUATION_ALPHA) as u32,
+                blend_src_rgb: gl.get_parameter_i32(glow::BLEND_SRC_RGB) as u32,
+                blend_dst_rgb: gl.get_parameter_i32(glow::BLEND_DST_RGB) as u32,
+                blend_src_alpha: gl.get_parameter_i32(glow::BLEND_SRC_ALPHA) as u32,
+                blend_dst_alpha: gl.get_parameter_i32(glow::BLEND_DST_ALPHA) as u32,
+
+                color_mask: gl.get_parameter_bool_array::<4>(glow::COLOR_WRITEMASK),
+                depth_mask: gl.get_parameter_bool(glow::DEPTH_WRITEMASK),
+            };
+
+            crate::check_for_gl_error!(gl, "GlState::capture");
+
+            state
+        }
+    }
+
+    pub(crate) unsafe fn restore(&self, gl: &glow::Context) {
+        crate::profile_function!();
+
+        let Self {
+            framebuffer,
+            program,
+            supports_vao,
+            vertex_array,
+            array_buffer,
+            element_array_buffer,
+            active_texture,
+            textures_2d,
+            viewport,
+            scissor_box,
+            blend,
+            scissor_test,
+            depth_test,
+            stencil_test,
+            cull_face,
+            framebuffer_srgb,
+            blend_equation_rgb,
+            blend_equation_alpha,
+            blend_src_rgb,
+            blend_dst_rgb,
+            blend_src_alpha,
+            blend_dst_alpha,
+            color_mask,
+            depth_mask,
+        } = self;
+
+        unsafe {
+            gl.bind_framebuffer(glow::FRAMEBUFFER, *framebuffer);
+            gl.use_program(*program);
+
+            // The element array buffer binding is part of the VAO state, so restore the VAO first:
+            if *supports_vao {
+                gl.bind_vertex_array(*vertex_array);
+            }
+            gl.bind_buffer(glow::ARRAY_BUFFER, *array_buffer);
+            gl.bind_buffer(glow::ELEMENT_ARRAY_BUFFER, *element_array_buffer);
+
+            for (unit, texture) in (0..).zip(textures_2d) {
+                gl.active_texture(glow::TEXTURE0 + unit);
+                gl.bind_texture(glow::TEXTURE_2D, *texture);
+            }
+            gl.active_texture(*active_texture);
+
+            gl.viewport(viewport[0], viewport[1], viewport[2], viewport[3]);
+            gl.scissor(
+                scissor_box[0],
+                scissor_box[1],
+                scissor_box[2],
+                scissor_box[3],
+            );
+
+            set_enabled(gl, glow::BLEND, *blend);
+            set_enabled(gl, glow::SCISSOR_TEST, *scissor_test);
+            set_enabled(gl, glow::DEPTH_TEST, *depth_test);
+            set_enabled(gl, glow::STENCIL_TEST, *stencil_test);
+            set_enabled(gl, glow::CULL_FACE, *cull_face);
+            if let Some(framebuffer_srgb) = framebuffer_srgb {
+                set_enabled(gl, glow::FRAMEBUFFER_SRGB, *framebuffer_srgb);
+            }
+
+            gl.blend_equation_separate(*blend_equation_rgb, *blend_equation_alpha);
+            gl.blend_func_separate(
+                *blend_src_rgb,
+                *blend_dst_rgb,
+                *blend_src_alpha,
+                *blend_dst_alpha,
+            );
+
+            gl.color_mask(color_mask[0], color_mask[1], color_mask[2], color_mask[3]);
+            gl.depth_mask(*depth_mask);
+        }
+
+        crate::check_for_gl_error!(gl, "GlState::restore");
+    }
+}
+
+unsafe fn set_enabled(gl: &glow::Context, capability: u32, enabled: bool) {
+    unsafe {
+        if enabled {
+            gl.enable(capability);
+        } else {
+            gl.disable(capability);
+        }
+    }
+}
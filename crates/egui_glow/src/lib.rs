@@ -15,10 +15,13 @@
 pub mod painter;
 pub use glow;
 pub use painter::{CallbackFn, Painter, PainterError};
+mod damage;
+mod gl_state;
 mod misc_util;
 mod shader_version;
 mod vao;
 
+pub use damage::DamageTracker;
 pub use shader_version::ShaderVersion;
 
 #[cfg(feature = "winit")]
@@ -4,13 +4,15 @@
 use std::{collections::HashMap, sync::Arc};
 
 use egui::{
+    ecolor::ColorSpace,
     emath::Rect,
-    epaint::{Mesh, PaintCallbackInfo, Primitive, Vertex},
+    epaint::{BlendMode, Mesh, PaintCallbackInfo, Primitive, Vertex},
 };
 use glow::HasContext as _;
 use memoffset::offset_of;
 
 use crate::check_for_gl_error;
+use crate::gl_state::GlState;
 use crate::misc_util::{compile_shader, link_program};
 use crate::shader_version::ShaderVersion;
 use crate::vao;
@@ -87,6 +89,9 @@ pub struct Painter {
     program: glow::Program,
     u_screen_size: glow::UniformLocation,
     u_sampler: glow::UniformLocation,
+    u_color_space: glow::UniformLocation,
+    u_distance_field: glow::UniformLocation,
+    output_color_space: ColorSpace,
     is_webgl_1: bool,
     vao: crate::vao::VertexArrayObject,
     srgb_textures: bool,
@@ -94,13 +99,31 @@ pub struct Painter {
     vbo: glow::Buffer,
     element_array_buffer: glow::Buffer,
 
+    /// The blend mode currently set in OpenGL, see [`Mesh::blend_mode`].
+    blend_mode: BlendMode,
+
+    /// Is `u_distance_field` currently set? See [`Mesh::distance_field`].
+    distance_field: bool,
+
     textures: HashMap<egui::TextureId, glow::Texture>,
 
+    /// The options set on each texture we uploaded, see [`Mesh::texture_options`].
+    texture_options: HashMap<egui::TextureId, egui::TextureOptions>,
+
+    /// `None` if anisotropic filtering is not supported.
+    max_anisotropy: Option<f32>,
+
     next_native_tex_id: u64,
 
     /// Stores outdated OpenGL textures that are yet to be deleted
     textures_to_destroy: Vec<glow::Texture>,
 
+    /// How much GPU memory the textures use, see [`Self::texture_memory`].
+    texture_memory: egui::TextureMemory,
+
+    /// Only paint inside of this, see [`Self::set_damage`].
+    damage: Option<Rect>,
+
     /// Used to make sure we are destroyed correctly.
     destroyed: bool,
 }
@@ -111,17 +134,36 @@ pub struct Painter {
 /// The callback is passed, the [`egui::PaintCallbackInfo`] and the [`Painter`] which can be used to
 /// access the OpenGL context.
 ///
+/// By default the OpenGL state that callbacks commonly change (bound framebuffer, program, buffers and textures,
+/// viewport, scissor, blend, depth and stencil settings, write masks) is saved before the callback and restored after it,
+/// so the callback doesn't need to clean up after itself.
+/// Reading back the state can stall the GPU pipeline, so if your callback restores the state itself
+/// you can turn this off with [`Self::with_preserve_gl_state`].
+///
 /// # Example
 ///
 /// See the [`custom3d_glow`](https://github.com/emilk/egui/blob/master/crates/egui_demo_app/src/apps/custom3d_wgpu.rs) demo source for a detailed usage example.
 pub struct CallbackFn {
     f: Box<dyn Fn(PaintCallbackInfo, &Painter) + Sync + Send>,
+    preserve_gl_state: bool,
 }
 
 impl CallbackFn {
     pub fn new<F: Fn(PaintCallbackInfo, &Painter) + Sync + Send + 'static>(callback: F) -> Self {
         let f = Box::new(callback);
-        Self { f }
+        Self {
+            f,
+            preserve_gl_state: true,
+        }
+    }
+
+    /// Save the OpenGL state before calling the callback, and restore it afterwards.
+    ///
+    /// Default: `true`.
+    #[inline]
+    pub fn with_preserve_gl_state(mut self, preserve_gl_state: bool) -> Self {
+        self.preserve_gl_state = preserve_gl_state;
+        self
     }
 }
 
@@ -186,6 +228,15 @@ impl Painter {
             });
         log::debug!("SRGB framebuffer Support: {:?}", supports_srgb_framebuffer);
 
+        let max_anisotropy = supported_extensions
+            .iter()
+            .any(|extension| {
+                // GL_EXT_texture_filter_anisotropic, GL_ARB_texture_filter_anisotropic, EXT_texture_filter_anisotropic, …
+                extension.ends_with("texture_filter_anisotropic")
+            })
+            .then(|| unsafe { gl.get_parameter_f32(glow::MAX_TEXTURE_MAX_ANISOTROPY_EXT) });
+        log::debug!("Max anisotropy: {:?}", max_anisotropy);
+
         unsafe {
             let vert = compile_shader(
                 &gl,
@@ -202,11 +253,13 @@ impl Painter {
                 &gl,
                 glow::FRAGMENT_SHADER,
                 &format!(
-                    "{}\n#define NEW_SHADER_INTERFACE {}\n#define DITHERING {}\n#define SRGB_TEXTURES {}\n{}\n{}",
+                    "{}\n#define NEW_SHADER_INTERFACE {}\n#define DITHERING {}\n#define SRGB_TEXTURES {}\n#define DISTANCE_FIELD_DERIVATIVES {}\n{}\n{}",
                     shader_version_declaration,
                     shader_version.is_new_shader_interface() as i32,
                     dithering as i32,
                     srgb_textures as i32,
+                    // WebGL1 needs an extension for `dFdx` and `dFdy`:
+                    !is_webgl_1 as i32,
                     shader_prefix,
                     FRAG_SRC
                 ),
@@ -218,6 +271,10 @@ impl Painter {
             gl.delete_shader(frag);
             let u_screen_size = gl.get_uniform_location(program, "u_screen_size").unwrap();
             let u_sampler = gl.get_uniform_location(program, "u_sampler").unwrap();
+            let u_color_space = gl.get_uniform_location(program, "u_color_space").unwrap();
+            let u_distance_field = gl
+                .get_uniform_location(program, "u_distance_field")
+                .unwrap();
 
             let vbo = gl.create_buffer()?;
 
@@ -264,15 +321,24 @@ impl Painter {
                 program,
                 u_screen_size,
                 u_sampler,
+                u_color_space,
+                u_distance_field,
+                output_color_space: ColorSpace::default(),
                 is_webgl_1,
                 vao,
                 srgb_textures,
                 supports_srgb_framebuffer,
                 vbo,
                 element_array_buffer,
+                blend_mode: BlendMode::Normal,
+                distance_field: false,
                 textures: Default::default(),
+                texture_options: Default::default(),
+                max_anisotropy,
                 next_native_tex_id: 1 << 32,
                 textures_to_destroy: Vec::new(),
+                texture_memory: Default::default(),
+                damage: None,
                 destroyed: false,
             })
         }
@@ -283,10 +349,40 @@ impl Painter {
         &self.gl
     }
 
+    /// Only clear and paint inside of this area (in physical pixels) from now on, or everywhere if `None`.
+    ///
+    /// Use this to repaint only what changed, e.g. with [`crate::DamageTracker`].
+    /// The rest of the framebuffer must then still contain what was painted there before.
+    pub fn set_damage(&mut self, damage: Option<Rect>) {
+        self.damage = damage;
+    }
+
+    /// How much GPU memory the textures use, and when each was last painted.
+    ///
+    /// Textures registered with [`Self::register_native_texture`] are not included.
+    pub fn texture_memory(&self) -> &egui::TextureMemory {
+        &self.texture_memory
+    }
+
     pub fn max_texture_side(&self) -> usize {
         self.max_texture_side
     }
 
+    /// The color space the painter writes its pixels in.
+    pub fn output_color_space(&self) -> ColorSpace {
+        self.output_color_space
+    }
+
+    /// Set the color space of the framebuffer, e.g. [`ColorSpace::DisplayP3`] for a wide-gamut display.
+    ///
+    /// egui colors are in sRGB, and are converted to this color space when rasterized,
+    /// so that they look the same as on an sRGB display.
+    ///
+    /// Default: [`ColorSpace::Srgb`].
+    pub fn set_output_color_space(&mut self, color_space: ColorSpace) {
+        self.output_color_space = color_space;
+    }
+
     /// The framebuffer we use as an intermediate render target,
     /// or `None` if we are painting to the screen framebuffer directly.
     ///
@@ -319,15 +415,8 @@ impl Painter {
             self.gl.enable(glow::BLEND);
             self.gl
                 .blend_equation_separate(glow::FUNC_ADD, glow::FUNC_ADD);
-            self.gl.blend_func_separate(
-                // egui outputs colors with premultiplied alpha:
-                glow::ONE,
-                glow::ONE_MINUS_SRC_ALPHA,
-                // Less important, but this is technically the correct alpha blend function
-                // when you want to make use of the framebuffer alpha (for screenshots, compositing, etc).
-                glow::ONE_MINUS_DST_ALPHA,
-                glow::ONE,
-            );
+            set_blend_mode(&self.gl, BlendMode::Normal);
+            self.blend_mode = BlendMode::Normal;
 
             if self.supports_srgb_framebuffer {
                 self.gl.disable(glow::FRAMEBUFFER_SRGB);
@@ -344,6 +433,17 @@ impl Painter {
             self.gl
                 .uniform_2_f32(Some(&self.u_screen_size), width_in_points, height_in_points);
             self.gl.uniform_1_i32(Some(&self.u_sampler), 0);
+            self.gl.uniform_1_i32(Some(&self.u_distance_field), 0);
+            self.distance_field = false;
+            self.gl.uniform_1_i32(
+                Some(&self.u_color_space),
+                // Must match `fragment.glsl`:
+                match self.output_color_space {
+                    ColorSpace::Srgb => 0,
+                    ColorSpace::DisplayP3 => 1,
+                    ColorSpace::Linear => 2,
+                },
+            );
             self.gl.active_texture(glow::TEXTURE0);
 
             self.vao.bind(&self.gl);
@@ -354,8 +454,30 @@ impl Painter {
         check_for_gl_error!(&self.gl, "prepare_painting");
     }
 
+    /// Clears the area set with [`Self::set_damage`], or everything.
     pub fn clear(&self, screen_size_in_pixels: [u32; 2], clear_color: [f32; 4]) {
-        clear(&self.gl, screen_size_in_pixels, clear_color);
+        if let Some(damage) = self.damage {
+            crate::profile_function!();
+            unsafe {
+                self.gl.enable(glow::SCISSOR_TEST);
+                self.gl.viewport(
+                    0,
+                    0,
+                    screen_size_in_pixels[0] as i32,
+                    screen_size_in_pixels[1] as i32,
+                );
+                set_clip_rect(&self.gl, screen_size_in_pixels, 1.0, damage);
+                self.gl.clear_color(
+                    clear_color[0],
+                    clear_color[1],
+                    clear_color[2],
+                    clear_color[3],
+                );
+                self.gl.clear(glow::COLOR_BUFFER_BIT);
+            }
+        } else {
+            clear(&self.gl, screen_size_in_pixels, clear_color);
+        }
     }
 
     /// You are expected to have cleared the color buffer before calling this.
@@ -371,6 +493,9 @@ impl Painter {
         for (id, image_delta) in &textures_delta.set {
             self.set_texture(*id, image_delta);
         }
+        for &(id, options) in &textures_delta.options {
+            self.set_texture_options(id, options);
+        }
 
         self.paint_primitives(screen_size_px, pixels_per_point, clipped_primitives);
 
@@ -379,6 +504,62 @@ impl Painter {
         }
     }
 
+    /// Paint into a texture instead of into the current framebuffer.
+    ///
+    /// This is for [texture viewports](egui::Context::show_viewport_texture),
+    /// i.e. egui UIs shown on a surface in a 3D scene.
+    ///
+    /// `target` must be an RGBA texture of (at least) `screen_size_px`.
+    /// It is cleared to `clear_color` before painting.
+    /// To show the texture in egui too, use [`Self::register_native_texture`].
+    ///
+    /// As usual in OpenGL, the top of the UI ends up at texture coordinate `v = 1`.
+    ///
+    /// Afterwards [`Self::intermediate_fbo`] is bound again.
+    ///
+    /// # Errors
+    /// If the framebuffer for painting into `target` can't be created,
+    /// in which case nothing is painted and the `textures_delta` is not applied.
+    pub fn paint_to_texture(
+        &mut self,
+        target: glow::Texture,
+        screen_size_px: [u32; 2],
+        pixels_per_point: f32,
+        clear_color: [f32; 4],
+        clipped_primitives: &[egui::ClippedPrimitive],
+        textures_delta: &egui::TexturesDelta,
+    ) -> Result<(), PainterError> {
+        crate::profile_function!();
+        self.assert_not_destroyed();
+
+        unsafe {
+            let fbo = self.gl.create_framebuffer()?;
+            self.gl.bind_framebuffer(glow::FRAMEBUFFER, Some(fbo));
+            self.gl.framebuffer_texture_2d(
+                glow::FRAMEBUFFER,
+                glow::COLOR_ATTACHMENT0,
+                glow::TEXTURE_2D,
+                Some(target),
+                0,
+            );
+            check_for_gl_error!(&self.gl, "paint_to_texture: framebuffer_texture_2d");
+
+            self.clear(screen_size_px, clear_color);
+            self.paint_and_update_textures(
+                screen_size_px,
+                pixels_per_point,
+                clipped_primitives,
+                textures_delta,
+            );
+
+            self.gl
+                .bind_framebuffer(glow::FRAMEBUFFER, self.intermediate_fbo());
+            self.gl.delete_framebuffer(fbo);
+        }
+
+        Ok(())
+    }
+
     /// Main entry-point for painting a frame.
     ///
     /// You should call `target.clear_color(..)` before
@@ -409,12 +590,19 @@ impl Painter {
         self.assert_not_destroyed();
 
         unsafe { self.prepare_painting(screen_size_px, pixels_per_point) };
+        self.texture_memory.begin_frame();
 
         for egui::ClippedPrimitive {
             clip_rect,
             primitive,
         } in clipped_primitives
         {
+            let clip_rect = &self.damage.map_or(*clip_rect, |damage| {
+                clip_rect.intersect(damage / pixels_per_point)
+            });
+            if !clip_rect.is_positive() {
+                continue; // Nothing changed here
+            }
             set_clip_rect(&self.gl, screen_size_px, pixels_per_point, *clip_rect);
 
             match primitive {
@@ -443,7 +631,19 @@ impl Painter {
                         }
 
                         if let Some(callback) = callback.callback.downcast_ref::<CallbackFn>() {
+                            let saved_state = callback.preserve_gl_state.then(|| unsafe {
+                                GlState::capture(
+                                    &self.gl,
+                                    self.vao.is_native(),
+                                    self.supports_srgb_framebuffer,
+                                )
+                            });
+
                             (callback.f)(info, self);
+
+                            if let Some(saved_state) = saved_state {
+                                unsafe { saved_state.restore(&self.gl) };
+                            }
                         } else {
                             log::warn!("Warning: Unsupported render callback. Expected egui_glow::CallbackFn");
                         }
@@ -471,6 +671,19 @@ impl Painter {
     fn paint_mesh(&mut self, mesh: &Mesh) {
         debug_assert!(mesh.is_valid());
         if let Some(texture) = self.texture(mesh.texture_id) {
+            self.texture_memory.on_paint(mesh.texture_id);
+            if self.blend_mode != mesh.blend_mode {
+                unsafe { set_blend_mode(&self.gl, mesh.blend_mode) };
+                self.blend_mode = mesh.blend_mode;
+            }
+            if self.distance_field != mesh.distance_field {
+                unsafe {
+                    self.gl
+                        .uniform_1_i32(Some(&self.u_distance_field), mesh.distance_field as i32);
+                };
+                self.distance_field = mesh.distance_field;
+            }
+
             unsafe {
                 self.gl.bind_buffer(glow::ARRAY_BUFFER, Some(self.vbo));
                 self.gl.buffer_data_u8_slice(
@@ -490,6 +703,21 @@ impl Painter {
                 self.gl.bind_texture(glow::TEXTURE_2D, Some(texture));
             }
 
+            // Temporarily sample the texture differently:
+            let own_options = self.texture_options.get(&mesh.texture_id).copied();
+            let override_options =
+                mesh.texture_options
+                    .zip(own_options)
+                    .and_then(|(mut options, own_options)| {
+                        if own_options.mipmap_mode.is_none() {
+                            options.mipmap_mode = None; // There are no mipmaps to use
+                        }
+                        (options != own_options).then_some((options, own_options))
+                    });
+            if let Some((options, _)) = override_options {
+                unsafe { self.set_texture_parameters(options) };
+            }
+
             unsafe {
                 self.gl.draw_elements(
                     glow::TRIANGLES,
@@ -499,6 +727,10 @@ impl Painter {
                 );
             }
 
+            if let Some((_, own_options)) = override_options {
+                unsafe { self.set_texture_parameters(own_options) };
+            }
+
             check_for_gl_error!(&self.gl, "paint_mesh");
         } else {
             log::warn!("Failed to find texture {:?}", mesh.texture_id);
@@ -550,6 +782,16 @@ impl Painter {
                 self.upload_texture_srgb(delta.pos, image.size, delta.options, &data);
             }
         };
+        self.texture_options.insert(tex_id, delta.options);
+        if delta.pos.is_none() {
+            // The whole texture was allocated anew (always as RGBA):
+            self.texture_memory.on_alloc(
+                tex_id,
+                delta.image.size(),
+                4,
+                delta.options.mipmap_mode.is_some(),
+            );
+        }
     }
 
     fn upload_texture_srgb(
@@ -570,28 +812,7 @@ impl Painter {
         );
 
         unsafe {
-            self.gl.tex_parameter_i32(
-                glow::TEXTURE_2D,
-                glow::TEXTURE_MAG_FILTER,
-                options.magnification.glow_code(None) as i32,
-            );
-            self.gl.tex_parameter_i32(
-                glow::TEXTURE_2D,
-                glow::TEXTURE_MIN_FILTER,
-                options.minification.glow_code(options.mipmap_mode) as i32,
-            );
-
-            self.gl.tex_parameter_i32(
-                glow::TEXTURE_2D,
-                glow::TEXTURE_WRAP_S,
-                options.wrap_mode.glow_code() as i32,
-            );
-            self.gl.tex_parameter_i32(
-                glow::TEXTURE_2D,
-                glow::TEXTURE_WRAP_T,
-                options.wrap_mode.glow_code() as i32,
-            );
-            check_for_gl_error!(&self.gl, "tex_parameter");
+            self.set_texture_parameters(options);
 
             let (internal_format, src_format) = if self.is_webgl_1 {
                 let format = if self.srgb_textures {
@@ -647,7 +868,74 @@ impl Painter {
         }
     }
 
+    /// Change how a texture is sampled, without uploading it again.
+    pub fn set_texture_options(&mut self, tex_id: egui::TextureId, options: egui::TextureOptions) {
+        crate::profile_function!();
+
+        let (Some(&texture), Some(own_options)) = (
+            self.textures.get(&tex_id),
+            self.texture_options.get_mut(&tex_id),
+        ) else {
+            return;
+        };
+        let needs_mipmaps = options.mipmap_mode.is_some() && own_options.mipmap_mode.is_none();
+        *own_options = options;
+
+        unsafe {
+            self.gl.bind_texture(glow::TEXTURE_2D, Some(texture));
+            self.set_texture_parameters(options);
+            if needs_mipmaps {
+                self.gl.generate_mipmap(glow::TEXTURE_2D);
+                check_for_gl_error!(&self.gl, "generate_mipmap");
+                self.texture_memory.on_mipmaps(tex_id);
+            }
+        }
+    }
+
+    /// Set the sampling parameters of the bound texture.
+    unsafe fn set_texture_parameters(&self, options: egui::TextureOptions) {
+        unsafe {
+            self.gl.tex_parameter_i32(
+                glow::TEXTURE_2D,
+                glow::TEXTURE_MAG_FILTER,
+                options.magnification.glow_code(None) as i32,
+            );
+            self.gl.tex_parameter_i32(
+                glow::TEXTURE_2D,
+                glow::TEXTURE_MIN_FILTER,
+                options.minification.glow_code(options.mipmap_mode) as i32,
+            );
+
+            self.gl.tex_parameter_i32(
+                glow::TEXTURE_2D,
+                glow::TEXTURE_WRAP_S,
+                options.wrap_mode.glow_code() as i32,
+            );
+            self.gl.tex_parameter_i32(
+                glow::TEXTURE_2D,
+                glow::TEXTURE_WRAP_T,
+                options.wrap_mode.glow_code() as i32,
+            );
+
+            if let Some(max_anisotropy) = self.max_anisotropy {
+                let anisotropy = if options.is_anisotropic() {
+                    (options.anisotropy as f32).min(max_anisotropy)
+                } else {
+                    1.0
+                };
+                self.gl.tex_parameter_f32(
+                    glow::TEXTURE_2D,
+                    glow::TEXTURE_MAX_ANISOTROPY_EXT,
+                    anisotropy,
+                );
+            }
+            check_for_gl_error!(&self.gl, "tex_parameter");
+        }
+    }
+
     pub fn free_texture(&mut self, tex_id: egui::TextureId) {
+        self.texture_options.remove(&tex_id);
+        self.texture_memory.on_free(tex_id);
         if let Some(old_tex) = self.textures.remove(&tex_id) {
             unsafe { self.gl.delete_texture(old_tex) };
         }
@@ -669,6 +957,8 @@ impl Painter {
 
     #[allow(clippy::needless_pass_by_value)] // False positive
     pub fn replace_native_texture(&mut self, id: egui::TextureId, replacing: glow::Texture) {
+        self.texture_options.remove(&id);
+        self.texture_memory.on_free(id);
         if let Some(old_tex) = self.textures.insert(id, replacing) {
             self.textures_to_destroy.push(old_tex);
         }
@@ -778,6 +1068,27 @@ impl Drop for Painter {
     }
 }
 
+/// Set the OpenGL blend function for the premultiplied colors egui outputs.
+///
+/// The blend equation must be `FUNC_ADD`.
+unsafe fn set_blend_mode(gl: &glow::Context, blend_mode: BlendMode) {
+    let (src_color, dst_color) = match blend_mode {
+        BlendMode::Normal => (glow::ONE, glow::ONE_MINUS_SRC_ALPHA),
+        BlendMode::Additive => (glow::ONE, glow::ONE),
+        BlendMode::Multiply => (glow::DST_COLOR, glow::ONE_MINUS_SRC_ALPHA),
+    };
+    unsafe {
+        gl.blend_func_separate(
+            src_color,
+            dst_color,
+            // Less important, but this is technically the correct alpha blend function
+            // when you want to make use of the framebuffer alpha (for screenshots, compositing, etc).
+            glow::ONE_MINUS_DST_ALPHA,
+            glow::ONE,
+        );
+    }
+}
+
 fn set_clip_rect(
     gl: &glow::Context,
     [width_px, height_px]: [u32; 2],
@@ -4,6 +4,15 @@
 
 uniform sampler2D u_sampler;
 
+// The color space of the framebuffer: 0 = sRGB, 1 = Display P3, 2 = linear sRGB.
+uniform int u_color_space;
+
+// 1 if the texture is a signed distance field of glyphs (see `Mesh::distance_field`), else 0.
+uniform int u_distance_field;
+
+// Must match `epaint::text::DISTANCE_FIELD_SPREAD`:
+const float DISTANCE_FIELD_SPREAD = 4.0;
+
 #if NEW_SHADER_INTERFACE
     in vec4 v_rgba_in_gamma;
     in vec2 v_tc;
@@ -50,6 +59,46 @@ vec4 srgba_gamma_from_linear(vec4 rgba) {
     return vec4(srgb_gamma_from_linear(rgba.rgb), rgba.a);
 }
 
+// 0-1 linear  from  0-1 sRGB gamma
+vec3 linear_from_srgb_gamma(vec3 srgb) {
+    bvec3 cutoff = lessThan(srgb, vec3(0.04045));
+    vec3 lower = srgb / vec3(12.92);
+    vec3 higher = pow((srgb + vec3(0.055)) / vec3(1.055), vec3(2.4));
+    return mix(higher, lower, vec3(cutoff));
+}
+
+// The output color space (see `u_color_space`)  from  0-1 sRGB gamma
+vec3 output_from_srgb_gamma(vec3 srgb) {
+    if (u_color_space == 0) {
+        return srgb;
+    }
+    vec3 rgb_linear = linear_from_srgb_gamma(srgb);
+    if (u_color_space == 2) {
+        return rgb_linear;
+    }
+    // Linear Display P3 from linear sRGB (column major), see `ecolor::LINEAR_DISPLAY_P3_FROM_LINEAR_SRGB`:
+    mat3 p3_from_srgb = mat3(
+        0.8224621, 0.0331942, 0.0170827,
+        0.1775380, 0.9668058, 0.0723974,
+        0.0000000, 0.0000000, 0.9105199
+    );
+    // Display P3 uses the same transfer function as sRGB:
+    return srgb_gamma_from_linear(p3_from_srgb * rgb_linear);
+}
+
+// How much of the pixel is covered by the glyph, from a distance field where 0.5 is on the outline.
+float distance_field_coverage(float distance) {
+#if DISTANCE_FIELD_DERIVATIVES
+    // Smooth the edge over one pixel on screen, however much the glyph is scaled:
+    float half_width = 0.5 * length(vec2(dFdx(distance), dFdy(distance)));
+#else
+    // Assume the glyph is shown at the size it was rasterized at:
+    float half_width = 0.25 / DISTANCE_FIELD_SPREAD;
+#endif
+    half_width = max(half_width, 0.0001);
+    return smoothstep(0.5 - half_width, 0.5 + half_width, distance);
+}
+
 void main() {
 #if SRGB_TEXTURES
     vec4 texture_in_gamma = srgba_gamma_from_linear(texture2D(u_sampler, v_tc));
@@ -57,6 +106,10 @@ void main() {
     vec4 texture_in_gamma = texture2D(u_sampler, v_tc);
 #endif
 
+    if (u_distance_field != 0) {
+        texture_in_gamma = vec4(distance_field_coverage(texture_in_gamma.a));
+    }
+
     // We multiply the colors in gamma space, because that's the only way to get text to look right.
     vec4 frag_color_gamma = v_rgba_in_gamma * texture_in_gamma;
 
@@ -65,5 +118,5 @@ void main() {
 #if DITHERING
     frag_color_gamma.rgb = dither_interleaved(frag_color_gamma.rgb, 256.);
 #endif
-    gl_FragColor = frag_color_gamma;
+    gl_FragColor = vec4(output_from_srgb_gamma(frag_color_gamma.rgb), frag_color_gamma.a);
 }
@@ -72,6 +72,11 @@ impl VertexArrayObject {
         }
     }
 
+    /// Are we using a real VAO (as opposed to emulating one)?
+    pub(crate) fn is_native(&self) -> bool {
+        self.vao.is_some()
+    }
+
     pub(crate) unsafe fn bind(&self, gl: &glow::Context) {
         unsafe {
             if let Some(vao) = self.vao {
@@ -36,6 +36,7 @@ impl EguiGlow {
             .unwrap();
 
         let egui_ctx = egui::Context::default();
+        egui_ctx.set_distance_field_text(true);
 
         let egui_winit = egui_winit::State::new(
             egui_ctx.clone(),
@@ -67,7 +68,12 @@ impl EguiGlow {
 
     /// Call [`Self::paint`] later to paint.
     pub fn run(&mut self, window: &winit::window::Window, run_ui: impl FnMut(&egui::Context)) {
-        let raw_input = self.egui_winit.take_egui_input(window);
+        let mut raw_input = self.egui_winit.take_egui_input(window);
+        raw_input
+            .viewports
+            .entry(raw_input.viewport_id)
+            .or_default()
+            .command_results = std::mem::take(&mut self.viewport_info.command_results);
 
         let egui::FullOutput {
             platform_output,
@@ -84,6 +90,7 @@ impl EguiGlow {
             let mut actions_requested: HashSet<egui_winit::ActionRequested> = Default::default();
             egui_winit::process_viewport_commands(
                 &self.egui_ctx,
+                ViewportId::ROOT,
                 &mut self.viewport_info,
                 commands,
                 window,
@@ -110,6 +117,9 @@ impl EguiGlow {
         for (id, image_delta) in textures_delta.set {
             self.painter.set_texture(id, &image_delta);
         }
+        for (id, options) in textures_delta.options {
+            self.painter.set_texture_options(id, options);
+        }
 
         let pixels_per_point = self.pixels_per_point;
         let clipped_primitives = self.egui_ctx.tessellate(shapes, pixels_per_point);
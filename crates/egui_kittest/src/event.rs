@@ -51,6 +51,7 @@ impl EventState {
                         pressed: matches!(state, ElementState::Pressed),
                         repeat: false,
                         physical_key: None,
+                        scancode: None,
                     })
                 }
             },
@@ -76,6 +76,9 @@ impl TestRenderer {
             for (id, image_delta) in &delta.set {
                 renderer.update_texture(&self.device, &self.queue, *id, image_delta);
             }
+            for &(id, options) in &delta.options {
+                renderer.set_texture_options(&self.device, id, options);
+            }
         }
 
         let mut encoder = self
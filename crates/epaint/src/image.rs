@@ -171,6 +171,26 @@ impl ColorImage {
         }
     }
 
+    /// Copy out the pixels of a sub-region, given in pixel coordinates.
+    ///
+    /// This is useful together with [`crate::TextureHandle::set_partial`].
+    ///
+    /// Panics if the region is outside the image.
+    pub fn sub_image(&self, [x, y]: [usize; 2], [w, h]: [usize; 2]) -> Self {
+        assert!(x + w <= self.width());
+        assert!(y + h <= self.height());
+
+        let mut pixels = Vec::with_capacity(w * h);
+        for y in y..y + h {
+            let offset = y * self.width() + x;
+            pixels.extend_from_slice(&self.pixels[offset..(offset + w)]);
+        }
+        Self {
+            size: [w, h],
+            pixels,
+        }
+    }
+
     /// Create a [`ColorImage`] from flat RGB data.
     ///
     /// This is what you want to use after having loaded an image file (and if
@@ -264,6 +284,8 @@ impl std::fmt::Debug for ColorImage {
 /// Each value represents "coverage", i.e. how much a texel is covered by a character.
 ///
 /// This is roughly interpreted as the opacity of a white image.
+///
+/// Glyphs that come with their own colors (e.g. color emojis) are stored in [`Self::colors`].
 #[derive(Clone, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct FontImage {
@@ -274,13 +296,25 @@ pub struct FontImage {
     ///
     /// Often you want to use [`Self::srgba_pixels`] instead.
     pub pixels: Vec<f32>,
+
+    /// Premultiplied colors of the colored texels, in the same order as [`Self::pixels`].
+    ///
+    /// `None` until the first colored texel is set with [`Self::set_color`].
+    /// Texels that are [`Color32::TRANSPARENT`] here are white, with the opacity of [`Self::pixels`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub colors: Option<Vec<Color32>>,
 }
 
 impl FontImage {
+    /// The gamma that [`Self::srgba_pixels`] applies to the coverage by default.
+    // TODO(emilk): this default coverage gamma is a magic constant, chosen by eye. I don't even know why we need it.
+    pub const DEFAULT_GAMMA: f32 = 0.55;
+
     pub fn new(size: [usize; 2]) -> Self {
         Self {
             size,
             pixels: vec![0.0; size[0] * size[1]],
+            colors: None,
         }
     }
 
@@ -301,8 +335,14 @@ impl FontImage {
     /// If you are having problems with text looking skinny and pixelated, try using a low gamma, e.g. `0.4`.
     #[inline]
     pub fn srgba_pixels(&self, gamma: Option<f32>) -> impl ExactSizeIterator<Item = Color32> + '_ {
-        let gamma = gamma.unwrap_or(0.55); // TODO(emilk): this default coverage gamma is a magic constant, chosen by eye. I don't even know why we need it.
-        self.pixels.iter().map(move |coverage| {
+        let gamma = gamma.unwrap_or(Self::DEFAULT_GAMMA);
+        let colors = self.colors.as_deref();
+        self.pixels.iter().enumerate().map(move |(i, coverage)| {
+            if let Some(color) = colors.map(|colors| colors[i]) {
+                if color != Color32::TRANSPARENT {
+                    return color;
+                }
+            }
             let alpha = coverage.powf(gamma);
             // We want to multiply with `vec4(alpha)` in the fragment shader:
             let a = fast_round(alpha * 255.0);
@@ -321,9 +361,49 @@ impl FontImage {
             pixels.extend(&self.pixels[offset..(offset + w)]);
         }
         assert_eq!(pixels.len(), w * h);
+        let colors = self.colors.as_ref().map(|colors| {
+            let mut region = Vec::with_capacity(w * h);
+            for y in y..y + h {
+                let offset = y * self.width() + x;
+                region.extend(&colors[offset..(offset + w)]);
+            }
+            region
+        });
         Self {
             size: [w, h],
             pixels,
+            colors,
+        }
+    }
+
+    /// The premultiplied color of a colored texel, set with [`Self::set_color`].
+    #[inline]
+    pub fn color(&self, (x, y): (usize, usize)) -> Option<Color32> {
+        let [w, h] = self.size;
+        assert!(x < w && y < h);
+        let color = self.colors.as_ref()?[y * w + x];
+        (color != Color32::TRANSPARENT).then_some(color)
+    }
+
+    /// Give a texel its own color, e.g. for a color emoji.
+    ///
+    /// Text painted with it is not tinted by the text color.
+    /// The coverage in [`Self::pixels`] is set to the alpha of the color.
+    pub fn set_color(&mut self, (x, y): (usize, usize), color: Color32) {
+        let [w, h] = self.size;
+        assert!(x < w && y < h);
+        let num_texels = self.pixels.len();
+        self.colors
+            .get_or_insert_with(|| vec![Color32::TRANSPARENT; num_texels])[y * w + x] = color;
+        self.pixels[y * w + x] = color.a() as f32 / 255.0;
+    }
+
+    /// Resize to `size`, keeping the old texels in place (as long as the width stays the same).
+    pub(crate) fn resize(&mut self, size: [usize; 2]) {
+        self.size = size;
+        self.pixels.resize(size[0] * size[1], 0.0);
+        if let Some(colors) = &mut self.colors {
+            colors.resize(size[0] * size[1], Color32::TRANSPARENT);
         }
     }
 }
@@ -46,7 +46,7 @@ pub use self::{
     color::ColorMode,
     image::{ColorImage, FontImage, ImageData, ImageDelta},
     margin::Margin,
-    mesh::{Mesh, Mesh16, Vertex},
+    mesh::{BlendMode, Mesh, Mesh16, Vertex},
     shadow::Shadow,
     shape::{
         CircleShape, EllipseShape, PaintCallback, PaintCallbackInfo, PathShape, RectShape,
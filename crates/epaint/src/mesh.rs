@@ -1,4 +1,4 @@
-use crate::{emath, Color32, TextureId, WHITE_UV};
+use crate::{emath, textures::TextureOptions, Color32, TextureId, WHITE_UV};
 use emath::{Pos2, Rect, Rot2, TSTransform, Vec2};
 
 /// The 2D vertex type.
@@ -42,6 +42,35 @@ pub struct Vertex {
     pub uv: Pos2, // 64 bit
 }
 
+/// How the colors of a [`Mesh`] are combined with what has already been painted below it.
+///
+/// Each change of blend mode is a state change for the renderer,
+/// so keep shapes with the same blend mode together.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum BlendMode {
+    /// Cover what is below, according to the opacity (alpha).
+    ///
+    /// This is what egui uses for everything.
+    #[default]
+    Normal,
+
+    /// Add the colors to what is below, making it brighter. Alpha is ignored, except for premultiplying.
+    ///
+    /// Good for glows, highlights and light effects.
+    Additive,
+
+    /// Multiply what is below with the colors, making it darker.
+    ///
+    /// White (or transparent) leaves what is below as is, black makes it black.
+    /// Good for shadows and dimming overlays that should keep the colors of what they cover.
+    Multiply,
+}
+
+impl BlendMode {
+    pub const ALL: [Self; 3] = [Self::Normal, Self::Additive, Self::Multiply];
+}
+
 /// Textured triangles in two dimensions.
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
@@ -58,6 +87,21 @@ pub struct Mesh {
 
     /// The texture to use when drawing these triangles.
     pub texture_id: TextureId,
+
+    /// How to blend these triangles with what is below them.
+    pub blend_mode: BlendMode,
+
+    /// Sample the texture with these options instead of those it was created with,
+    /// e.g. to show the same texture both with [`TextureOptions::NEAREST`]
+    /// and [`TextureOptions::LINEAR`] filtering.
+    pub texture_options: Option<TextureOptions>,
+
+    /// The texture holds signed distance fields of glyphs (see [`crate::text::GlyphRendering::DistanceField`]),
+    /// which the fragment shader should turn into coverage instead of sampling it as colors.
+    ///
+    /// The distance is stored in the alpha of the texture, where `0.5` is the edge of the glyph,
+    /// and it changes by `0.5 / `[`crate::text::DISTANCE_FIELD_SPREAD`] per texel.
+    pub distance_field: bool,
     // TODO(emilk): bounding rectangle
 }
 
@@ -110,6 +154,7 @@ impl Mesh {
     /// Append all the indices and vertices of `other` to `self`.
     ///
     /// Panics when `other` mesh has a different texture.
+    /// The [`Self::blend_mode`], [`Self::texture_options`] and [`Self::distance_field`] of `self` are kept, unless `self` is empty.
     pub fn append(&mut self, other: Self) {
         crate::profile_function!();
         debug_assert!(other.is_valid());
@@ -125,11 +170,15 @@ impl Mesh {
     /// taking ownership.
     ///
     /// Panics when `other` mesh has a different texture.
+    /// The [`Self::blend_mode`], [`Self::texture_options`] and [`Self::distance_field`] of `self` are kept, unless `self` is empty.
     pub fn append_ref(&mut self, other: &Self) {
         debug_assert!(other.is_valid());
 
         if self.is_empty() {
             self.texture_id = other.texture_id;
+            self.blend_mode = other.blend_mode;
+            self.texture_options = other.texture_options;
+            self.distance_field = other.distance_field;
         } else {
             assert_eq!(
                 self.texture_id, other.texture_id,
@@ -230,6 +279,9 @@ impl Mesh {
                 indices: self.indices.iter().map(|&i| i as u16).collect(),
                 vertices: self.vertices,
                 texture_id: self.texture_id,
+                blend_mode: self.blend_mode,
+                texture_options: self.texture_options,
+                distance_field: self.distance_field,
             }];
         }
 
@@ -272,6 +324,9 @@ impl Mesh {
                     .collect(),
                 vertices: self.vertices[(min_vindex as usize)..=(max_vindex as usize)].to_vec(),
                 texture_id: self.texture_id,
+                blend_mode: self.blend_mode,
+                texture_options: self.texture_options,
+                distance_field: self.distance_field,
             };
             debug_assert!(mesh.is_valid());
             output.push(mesh);
@@ -319,6 +374,15 @@ pub struct Mesh16 {
 
     /// The texture to use when drawing these triangles.
     pub texture_id: TextureId,
+
+    /// How to blend these triangles with what is below them.
+    pub blend_mode: BlendMode,
+
+    /// See [`Mesh::texture_options`].
+    pub texture_options: Option<TextureOptions>,
+
+    /// See [`Mesh::distance_field`].
+    pub distance_field: bool,
 }
 
 impl Mesh16 {
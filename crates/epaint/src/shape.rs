@@ -5,7 +5,7 @@ use std::{any::Any, sync::Arc};
 use crate::{
     stroke::PathStroke,
     text::{FontId, Fonts, Galley},
-    Color32, Mesh, Stroke, TextureId,
+    BlendMode, Color32, Mesh, Stroke, TextureId,
 };
 use emath::{pos2, Align2, Pos2, Rangef, Rect, TSTransform, Vec2};
 
@@ -65,6 +65,12 @@ pub enum Shape {
 
     /// Backend-specific painting.
     Callback(PaintCallback),
+
+    /// Paint some shapes with another [`BlendMode`], e.g. additive for glows or multiply for dimming overlays.
+    ///
+    /// A [`Shape::Mesh`] inside keeps its own [`Mesh::blend_mode`] only if `mode` is [`BlendMode::Normal`].
+    /// See also [`Self::blend`].
+    Blend { mode: BlendMode, shapes: Vec<Shape> },
 }
 
 #[test]
@@ -329,11 +335,23 @@ impl Shape {
         Self::mesh(mesh)
     }
 
+    /// Paint the shapes with the given [`BlendMode`].
+    ///
+    /// ```
+    /// # use epaint::*;
+    /// let glow = Shape::circle_filled(pos2(50.0, 50.0), 20.0, Color32::from_rgb(80, 40, 0));
+    /// let shape = Shape::blend(BlendMode::Additive, vec![glow]);
+    /// ```
+    #[inline]
+    pub fn blend(mode: BlendMode, shapes: Vec<Self>) -> Self {
+        Self::Blend { mode, shapes }
+    }
+
     /// The visual bounding rectangle (includes stroke widths)
     pub fn visual_bounding_rect(&self) -> Rect {
         match self {
             Self::Noop => Rect::NOTHING,
-            Self::Vec(shapes) => {
+            Self::Vec(shapes) | Self::Blend { shapes, mode: _ } => {
                 let mut rect = Rect::NOTHING;
                 for shape in shapes {
                     rect = rect.union(shape.visual_bounding_rect());
@@ -396,7 +414,7 @@ impl Shape {
     pub fn transform(&mut self, transform: TSTransform) {
         match self {
             Self::Noop => {}
-            Self::Vec(shapes) => {
+            Self::Vec(shapes) | Self::Blend { shapes, mode: _ } => {
                 for shape in shapes {
                     shape.transform(transform);
                 }
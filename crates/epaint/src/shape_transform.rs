@@ -14,7 +14,7 @@ pub fn adjust_colors(
     match shape {
         Shape::Noop => {}
 
-        Shape::Vec(shapes) => {
+        Shape::Vec(shapes) | Shape::Blend { shapes, mode: _ } => {
             for shape in shapes {
                 adjust_colors(shape, adjust_color);
             }
@@ -100,6 +100,9 @@ pub fn adjust_colors(
             indices: _,
             vertices,
             texture_id: _,
+            blend_mode: _,
+            texture_options: _,
+            distance_field: _,
         }) => {
             for v in vertices {
                 adjust_color(&mut v.color);
@@ -191,7 +191,7 @@ impl PaintStats {
 
     fn add(&mut self, shape: &Shape) {
         match shape {
-            Shape::Vec(shapes) => {
+            Shape::Vec(shapes) | Shape::Blend { shapes, mode: _ } => {
                 // self += PaintStats::from_shapes(&shapes); // TODO(emilk)
                 self.shapes += AllocInfo::from_slice(shapes);
                 self.shape_vec += AllocInfo::from_slice(shapes);
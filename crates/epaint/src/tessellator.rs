@@ -7,9 +7,9 @@
 
 use crate::texture_atlas::PreparedDisc;
 use crate::{
-    color, emath, stroke, CircleShape, ClippedPrimitive, ClippedShape, Color32, CubicBezierShape,
-    EllipseShape, Mesh, PathShape, Primitive, QuadraticBezierShape, RectShape, Rounding, Shape,
-    Stroke, TextShape, TextureId, Vertex, WHITE_UV,
+    color, emath, stroke, BlendMode, CircleShape, ClippedPrimitive, ClippedShape, Color32,
+    CubicBezierShape, EllipseShape, Mesh, PathShape, Primitive, QuadraticBezierShape, RectShape,
+    Rounding, Shape, Stroke, TextShape, TextureId, Vertex, WHITE_UV,
 };
 use emath::{pos2, remap, vec2, NumExt, Pos2, Rect, Rot2, Vec2};
 
@@ -673,6 +673,14 @@ pub struct TessellationOptions {
     /// This makes the text sharper on most platforms.
     pub round_text_to_pixels: bool,
 
+    /// Snap every vertex (and clip rectangle) to the grid of physical pixels.
+    ///
+    /// Together with turning off [`Self::feathering`], this gives hard, pixel-perfect edges,
+    /// e.g. for pixel art.
+    ///
+    /// Default: `false`.
+    pub round_vertices_to_pixels: bool,
+
     /// Output the clip rectangles to be painted.
     pub debug_paint_clip_rects: bool,
 
@@ -706,6 +714,7 @@ impl Default for TessellationOptions {
             coarse_tessellation_culling: true,
             prerasterized_discs: true,
             round_text_to_pixels: true,
+            round_vertices_to_pixels: false,
             debug_paint_text_rects: false,
             debug_paint_clip_rects: false,
             debug_ignore_clip_rects: false,
@@ -1198,6 +1207,14 @@ fn stroke_path(
     }
 }
 
+/// Are some of the glyphs of the galley distance fields, see [`Mesh::distance_field`]?
+fn has_distance_field_glyphs(galley: &crate::Galley) -> bool {
+    galley
+        .rows
+        .iter()
+        .any(|row| !row.visuals.distance_field_vertex_range.is_empty())
+}
+
 fn mul_color(color: Color32, factor: f32) -> Color32 {
     // The fast gamma-space multiply also happens to be perceptually better.
     // Win-win!
@@ -1290,6 +1307,17 @@ impl Tessellator {
         &mut self,
         clipped_shape: ClippedShape,
         out_primitives: &mut Vec<ClippedPrimitive>,
+    ) {
+        self.tessellate_blended_shape(clipped_shape, BlendMode::Normal, out_primitives);
+    }
+
+    /// A new mesh is started whenever the [`BlendMode`] changes,
+    /// and the glyphs that are distance fields get meshes of their own (see [`Mesh::distance_field`]).
+    fn tessellate_blended_shape(
+        &mut self,
+        clipped_shape: ClippedShape,
+        blend_mode: BlendMode,
+        out_primitives: &mut Vec<ClippedPrimitive>,
     ) {
         let ClippedShape { clip_rect, shape } = clipped_shape;
 
@@ -1299,11 +1327,30 @@ impl Tessellator {
 
         if let Shape::Vec(shapes) = shape {
             for shape in shapes {
-                self.tessellate_clipped_shape(ClippedShape { clip_rect, shape }, out_primitives);
+                let clipped_shape = ClippedShape { clip_rect, shape };
+                self.tessellate_blended_shape(clipped_shape, blend_mode, out_primitives);
             }
             return;
         }
 
+        if let Shape::Blend { mode, shapes } = shape {
+            for shape in shapes {
+                let clipped_shape = ClippedShape { clip_rect, shape };
+                self.tessellate_blended_shape(clipped_shape, mode, out_primitives);
+            }
+            return;
+        }
+
+        let blend_mode = match &shape {
+            Shape::Mesh(mesh) if blend_mode == BlendMode::Normal => mesh.blend_mode,
+            _ => blend_mode,
+        };
+        let texture_options = match &shape {
+            Shape::Mesh(mesh) => mesh.texture_options,
+            _ => None,
+        };
+        let distance_field = matches!(&shape, Shape::Mesh(mesh) if mesh.distance_field);
+
         if let Shape::Callback(callback) = shape {
             out_primitives.push(ClippedPrimitive {
                 clip_rect,
@@ -1312,6 +1359,34 @@ impl Tessellator {
             return;
         }
 
+        if let Shape::Text(text_shape) = &shape {
+            if has_distance_field_glyphs(&text_shape.galley) {
+                // The glyphs that are distance fields go in a mesh of their own:
+                self.clip_rect = clip_rect;
+                let mut mesh = Mesh::default();
+                let mut distance_field_mesh = Mesh {
+                    distance_field: true,
+                    ..Default::default()
+                };
+                if self.options.debug_paint_text_rects {
+                    let rect = text_shape.galley.rect.translate(text_shape.pos.to_vec2());
+                    self.tessellate_rect(
+                        &RectShape::stroke(rect.expand(0.5), 2.0, (0.5, Color32::GREEN)),
+                        &mut mesh,
+                    );
+                }
+                self.tessellate_text_split(text_shape, &mut mesh, Some(&mut distance_field_mesh));
+                for mesh in [mesh, distance_field_mesh] {
+                    if !mesh.is_empty() {
+                        let shape = Shape::Mesh(mesh);
+                        let clipped_shape = ClippedShape { clip_rect, shape };
+                        self.tessellate_blended_shape(clipped_shape, blend_mode, out_primitives);
+                    }
+                }
+                return;
+            }
+        }
+
         let start_new_mesh = match out_primitives.last() {
             None => true,
             Some(output_clipped_primitive) => {
@@ -1319,6 +1394,9 @@ impl Tessellator {
                     || match &output_clipped_primitive.primitive {
                         Primitive::Mesh(output_mesh) => {
                             output_mesh.texture_id != shape.texture_id()
+                                || output_mesh.blend_mode != blend_mode
+                                || output_mesh.texture_options != texture_options
+                                || output_mesh.distance_field != distance_field
                         }
                         Primitive::Callback(_) => true,
                     }
@@ -1328,7 +1406,12 @@ impl Tessellator {
         if start_new_mesh {
             out_primitives.push(ClippedPrimitive {
                 clip_rect,
-                primitive: Primitive::Mesh(Mesh::default()),
+                primitive: Primitive::Mesh(Mesh {
+                    blend_mode,
+                    texture_options,
+                    distance_field,
+                    ..Default::default()
+                }),
             });
         }
 
@@ -1337,6 +1420,10 @@ impl Tessellator {
         if let Primitive::Mesh(out_mesh) = &mut out.primitive {
             self.clip_rect = clip_rect;
             self.tessellate_shape(shape, out_mesh);
+            // in case it was empty, and took those of an appended mesh:
+            out_mesh.blend_mode = blend_mode;
+            out_mesh.texture_options = texture_options;
+            out_mesh.distance_field = distance_field;
         } else {
             unreachable!();
         }
@@ -1346,12 +1433,17 @@ impl Tessellator {
     ///
     /// This call can panic the given shape is of [`Shape::Vec`] or [`Shape::Callback`].
     /// For that, use [`Self::tessellate_clipped_shape`] instead.
+    /// The [`BlendMode`] of a [`Shape::Blend`] is also only respected by [`Self::tessellate_clipped_shape`].
     /// * `shape`: the shape to tessellate.
     /// * `out`: triangles are appended to this.
     pub fn tessellate_shape(&mut self, shape: Shape, out: &mut Mesh) {
         match shape {
             Shape::Noop => {}
-            Shape::Vec(vec) => {
+            Shape::Vec(vec)
+            | Shape::Blend {
+                shapes: vec,
+                mode: _,
+            } => {
                 for shape in vec {
                     self.tessellate_shape(shape, out);
                 }
@@ -1729,7 +1821,20 @@ impl Tessellator {
     /// Tessellate a single [`TextShape`] into a [`Mesh`].
     /// * `text_shape`: the text to tessellate.
     /// * `out`: triangles are appended to this.
+    ///
+    /// Glyphs that are distance fields (see [`crate::text::GlyphRendering::DistanceField`]) end up in `out` too,
+    /// where they won't look right: only [`Self::tessellate_clipped_shape`] puts them in a [`Mesh`] of their own.
     pub fn tessellate_text(&mut self, text_shape: &TextShape, out: &mut Mesh) {
+        self.tessellate_text_split(text_shape, out, None);
+    }
+
+    /// Like [`Self::tessellate_text`], but puts the glyphs that are distance fields in `distance_field_out`.
+    fn tessellate_text_split(
+        &mut self,
+        text_shape: &TextShape,
+        out: &mut Mesh,
+        mut distance_field_out: Option<&mut Mesh>,
+    ) {
         let TextShape {
             pos: galley_pos,
             galley,
@@ -1795,53 +1900,93 @@ impl Tessellator {
                 continue;
             }
 
-            let index_offset = out.vertices.len() as u32;
+            let transform_vertex = |i: usize, vertex: &Vertex| {
+                let Vertex { pos, uv, mut color } = *vertex;
 
-            out.indices.extend(
-                row.visuals
-                    .mesh
-                    .indices
-                    .iter()
-                    .map(|index| index + index_offset),
-            );
+                if let Some(override_text_color) = override_text_color {
+                    // Only override the glyph color (not background color, strike-through color, etc)
+                    if row.visuals.glyph_vertex_range.contains(&i) {
+                        color = *override_text_color;
+                    }
+                } else if color == Color32::PLACEHOLDER {
+                    color = *fallback_color;
+                }
 
-            out.vertices.extend(
-                row.visuals
-                    .mesh
-                    .vertices
-                    .iter()
-                    .enumerate()
-                    .map(|(i, vertex)| {
-                        let Vertex { pos, uv, mut color } = *vertex;
-
-                        if let Some(override_text_color) = override_text_color {
-                            // Only override the glyph color (not background color, strike-through color, etc)
-                            if row.visuals.glyph_vertex_range.contains(&i) {
-                                color = *override_text_color;
-                            }
-                        } else if color == Color32::PLACEHOLDER {
-                            color = *fallback_color;
-                        }
+                if *opacity_factor < 1.0 {
+                    color = color.gamma_multiply(*opacity_factor);
+                }
 
-                        if *opacity_factor < 1.0 {
-                            color = color.gamma_multiply(*opacity_factor);
-                        }
+                debug_assert!(color != Color32::PLACEHOLDER, "A placeholder color made it to the tessellator. You forgot to set a fallback color.");
+
+                let offset = if *angle == 0.0 {
+                    pos.to_vec2()
+                } else {
+                    rotator * pos.to_vec2()
+                };
 
-                        debug_assert!(color != Color32::PLACEHOLDER, "A placeholder color made it to the tessellator. You forgot to set a fallback color.");
+                Vertex {
+                    pos: galley_pos + offset,
+                    uv: (uv.to_vec2() * uv_normalizer).to_pos2(),
+                    color,
+                }
+            };
 
-                        let offset = if *angle == 0.0 {
-                            pos.to_vec2()
+            let distance_field_range = &row.visuals.distance_field_vertex_range;
+            let index_offset = out.vertices.len() as u32;
+
+            match distance_field_out.as_deref_mut() {
+                Some(distance_field_out) if !distance_field_range.is_empty() => {
+                    // The triangles of a glyph are all in or all out of the range:
+                    let num_distance_field_vertices = distance_field_range.len() as u32;
+                    let distance_field_start = distance_field_range.start as u32;
+                    let distance_field_offset = distance_field_out.vertices.len() as u32;
+                    for triangle in row.visuals.mesh.indices.chunks_exact(3) {
+                        if distance_field_range.contains(&(triangle[0] as usize)) {
+                            distance_field_out.indices.extend(
+                                triangle.iter().map(|index| {
+                                    index - distance_field_start + distance_field_offset
+                                }),
+                            );
                         } else {
-                            rotator * pos.to_vec2()
-                        };
+                            out.indices.extend(triangle.iter().map(|&index| {
+                                if distance_field_range.end <= index as usize {
+                                    index - num_distance_field_vertices + index_offset
+                                } else {
+                                    index + index_offset
+                                }
+                            }));
+                        }
+                    }
 
-                        Vertex {
-                            pos: galley_pos + offset,
-                            uv: (uv.to_vec2() * uv_normalizer).to_pos2(),
-                            color,
+                    for (i, vertex) in row.visuals.mesh.vertices.iter().enumerate() {
+                        if distance_field_range.contains(&i) {
+                            distance_field_out
+                                .vertices
+                                .push(transform_vertex(i, vertex));
+                        } else {
+                            out.vertices.push(transform_vertex(i, vertex));
                         }
-                    }),
-            );
+                    }
+                }
+                _ => {
+                    out.indices.extend(
+                        row.visuals
+                            .mesh
+                            .indices
+                            .iter()
+                            .map(|index| index + index_offset),
+                    );
+
+                    out.vertices.extend(
+                        row.visuals
+                            .mesh
+                            .vertices
+                            .iter()
+                            .enumerate()
+                            .map(|(i, vertex)| transform_vertex(i, vertex)),
+                    );
+                }
+            }
 
             if *underline != Stroke::NONE {
                 self.scratchpad_path.clear();
@@ -2003,6 +2148,10 @@ impl Tessellator {
             }
         }
 
+        if self.options.round_vertices_to_pixels {
+            self.round_to_pixels(&mut clipped_primitives);
+        }
+
         clipped_primitives.retain(|p| {
             p.clip_rect.is_positive()
                 && match &p.primitive {
@@ -2020,6 +2169,31 @@ impl Tessellator {
         clipped_primitives
     }
 
+    /// See [`TessellationOptions::round_vertices_to_pixels`].
+    fn round_to_pixels(&self, clipped_primitives: &mut [ClippedPrimitive]) {
+        crate::profile_function!();
+
+        let pixels_per_point = self.pixels_per_point;
+        let round = |pos: Pos2| {
+            pos2(
+                (pos.x * pixels_per_point).round() / pixels_per_point,
+                (pos.y * pixels_per_point).round() / pixels_per_point,
+            )
+        };
+
+        for clipped_primitive in clipped_primitives {
+            let clip_rect = &mut clipped_primitive.clip_rect;
+            if clip_rect.is_finite() {
+                *clip_rect = Rect::from_min_max(round(clip_rect.min), round(clip_rect.max));
+            }
+            if let Primitive::Mesh(mesh) = &mut clipped_primitive.primitive {
+                for vertex in &mut mesh.vertices {
+                    vertex.pos = round(vertex.pos);
+                }
+            }
+        }
+    }
+
     /// Find large shapes and throw them on the rayon thread pool,
     /// then replace the original shape with their tessellated meshes.
     #[cfg(feature = "rayon")]
@@ -2033,7 +2207,10 @@ impl Tessellator {
         // and callocations that is only worth it for large shapes.
         fn should_parallelize(shape: &Shape) -> bool {
             match shape {
-                Shape::Vec(shapes) => 4 < shapes.len() || shapes.iter().any(should_parallelize),
+                Shape::Vec(shapes) => {
+                    !shapes.iter().any(has_blend_mode)
+                        && (4 < shapes.len() || shapes.iter().any(should_parallelize))
+                }
 
                 Shape::Path(path_shape) => 32 < path_shape.points.len(),
 
@@ -2045,7 +2222,18 @@ impl Tessellator {
                 | Shape::Mesh(_)
                 | Shape::LineSegment { .. }
                 | Shape::Rect(_)
-                | Shape::Callback(_) => false,
+                | Shape::Callback(_)
+                | Shape::Blend { .. } => false,
+            }
+        }
+
+        // These would lose their blend mode when tessellated into a single mesh:
+        fn has_blend_mode(shape: &Shape) -> bool {
+            match shape {
+                Shape::Vec(shapes) => shapes.iter().any(has_blend_mode),
+                Shape::Blend { .. } => true,
+                Shape::Mesh(mesh) => mesh.blend_mode != BlendMode::Normal,
+                _ => false,
             }
         }
 
@@ -2129,6 +2317,47 @@ fn test_tessellator() {
     assert_eq!(primitives.len(), 2);
 }
 
+#[test]
+fn blend_modes_are_batched() {
+    use crate::*;
+
+    let rect = Rect::from_min_max(pos2(0.0, 0.0), pos2(10.0, 10.0));
+    let square = || Shape::rect_filled(rect, 0.0, Color32::GRAY);
+    let shapes = vec![
+        square(),
+        Shape::blend(BlendMode::Additive, vec![square(), square()]),
+        Shape::blend(BlendMode::Multiply, vec![square()]),
+        square(),
+    ];
+    let clipped_shapes = shapes
+        .into_iter()
+        .map(|shape| ClippedShape {
+            clip_rect: rect,
+            shape,
+        })
+        .collect();
+
+    let primitives = Tessellator::new(1.0, Default::default(), [1024, 1024], vec![])
+        .tessellate_shapes(clipped_shapes);
+
+    let blend_modes: Vec<BlendMode> = primitives
+        .iter()
+        .map(|p| match &p.primitive {
+            Primitive::Mesh(mesh) => mesh.blend_mode,
+            Primitive::Callback(_) => panic!("Expected a mesh"),
+        })
+        .collect();
+    assert_eq!(
+        blend_modes,
+        [
+            BlendMode::Normal,
+            BlendMode::Additive,
+            BlendMode::Multiply,
+            BlendMode::Normal
+        ]
+    );
+}
+
 #[test]
 fn path_bounding_box() {
     use crate::*;
@@ -2173,3 +2402,38 @@ fn path_bounding_box() {
         );
     }
 }
+
+#[test]
+fn distance_field_glyphs_get_their_own_mesh() {
+    use crate::text::{FontDefinitions, FontFamily, Fonts, GlyphRendering};
+    use crate::*;
+
+    let mut definitions = FontDefinitions::default();
+    definitions
+        .glyph_rendering
+        .insert(FontFamily::Proportional, GlyphRendering::DistanceField);
+    let fonts = Fonts::new(1.0, 1024, definitions);
+
+    let text = |fonts: &Fonts| {
+        let galley = fonts.layout_no_wrap(
+            "Hello".to_owned(),
+            FontId::proportional(14.0),
+            Color32::WHITE,
+        );
+        Shape::galley(pos2(0.0, 0.0), galley, Color32::WHITE)
+    };
+    let distance_field_meshes = |shape: Shape| {
+        let clip_rect = Rect::from_min_size(pos2(0.0, 0.0), vec2(100.0, 100.0));
+        Tessellator::new(1.0, Default::default(), fonts.font_image_size(), vec![])
+            .tessellate_shapes(vec![ClippedShape { clip_rect, shape }])
+            .iter()
+            .filter(|p| matches!(&p.primitive, Primitive::Mesh(mesh) if mesh.distance_field))
+            .count()
+    };
+
+    // Falls back to the bitmap atlas unless the renderer supports distance fields:
+    assert_eq!(distance_field_meshes(text(&fonts)), 0);
+
+    fonts.set_distance_field_text(true);
+    assert_eq!(distance_field_meshes(text(&fonts)), 1);
+}
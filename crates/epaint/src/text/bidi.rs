@@ -0,0 +1,75 @@
+//! Bidirectional text: resolving the embedding level of each character,
+//! and reordering each row for display.
+//!
+//! Glyphs are always kept in logical order (one per `char`), so that cursors keep working.
+//! Only their x positions are changed to match the visual order.
+
+use unicode_bidi::{BidiInfo, Level};
+
+use super::{Glyph, Row};
+
+/// Resolve [`Glyph::bidi_level`] for all glyphs in a paragraph.
+///
+/// The base direction of the paragraph is decided by its first strong character.
+///
+/// Returns the paragraph embedding level (`0` for left-to-right, `1` for right-to-left).
+pub(crate) fn resolve_levels(glyphs: &mut [Glyph]) -> u8 {
+    // Early-out: nothing before the Hebrew block can be right-to-left.
+    if glyphs.iter().all(|glyph| (glyph.chr as u32) < 0x0590) {
+        return 0;
+    }
+
+    let text: String = glyphs.iter().map(|glyph| glyph.chr).collect();
+    let bidi_info = BidiInfo::new(&text, None);
+
+    for (glyph, (byte_index, _)) in glyphs.iter_mut().zip(text.char_indices()) {
+        glyph.bidi_level = bidi_info.levels[byte_index].number();
+    }
+
+    bidi_info
+        .paragraphs
+        .first()
+        .map_or(0, |paragraph| paragraph.level.number())
+}
+
+/// Whitespace at the end of a row gets the paragraph level
+/// ([rule L1](https://www.unicode.org/reports/tr9/#L1)),
+/// so that it ends up at the visual end of the row.
+pub(crate) fn reset_trailing_whitespace(row: &mut Row, paragraph_level: u8) {
+    for glyph in row.glyphs.iter_mut().rev() {
+        if !glyph.chr.is_whitespace() {
+            break;
+        }
+        glyph.bidi_level = paragraph_level;
+    }
+}
+
+/// Move the glyphs of a row so they are laid out in visual order.
+///
+/// Each glyph keeps the width of its slot (including kerning, letter spacing and justification),
+/// so the row keeps its extent.
+pub(crate) fn reorder_row(row: &mut Row) {
+    if !row.has_rtl() {
+        return;
+    }
+
+    let glyphs = &mut row.glyphs;
+    let slot_widths: Vec<f32> = (0..glyphs.len())
+        .map(|i| {
+            glyphs
+                .get(i + 1)
+                .map_or(glyphs[i].advance_width, |next| next.pos.x - glyphs[i].pos.x)
+        })
+        .collect();
+
+    let levels: Vec<Level> = glyphs
+        .iter()
+        .map(|glyph| Level::from(glyph.bidi_level))
+        .collect();
+
+    let mut x = glyphs[0].pos.x;
+    for i in BidiInfo::reorder_visual(&levels) {
+        glyphs[i].pos.x = x;
+        x += slot_widths[i];
+    }
+}
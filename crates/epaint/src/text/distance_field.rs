@@ -0,0 +1,202 @@
+//! Signed distance fields of glyphs, see [`super::GlyphRendering::DistanceField`].
+
+/// How far (in texels) the distance fields of glyphs reach outside and inside their outlines.
+///
+/// The distance stored in the font atlas goes from `0.0` (this far outside the outline)
+/// to `1.0` (this far inside), with `0.5` on the outline.
+/// Renderers need this to know how sharp to make the edges, see [`crate::Mesh::distance_field`].
+pub const DISTANCE_FIELD_SPREAD: f32 = 4.0;
+
+/// The glyphs are rasterized this many times larger before the distance field is calculated,
+/// so that the distances are accurate to a fraction of a texel.
+const SUPERSAMPLING: usize = 4;
+
+/// The signed distance field of a glyph.
+pub(crate) struct GlyphDistanceField {
+    /// The top left corner, in pixels relative to the glyph origin.
+    pub min: [i32; 2],
+
+    /// Width and height in texels.
+    pub size: [usize; 2],
+
+    /// Row-major, see [`DISTANCE_FIELD_SPREAD`].
+    pub distances: Vec<f32>,
+}
+
+/// Calculate the distance field of a glyph at the given scale.
+///
+/// Returns `None` for glyphs without an outline, e.g. a space.
+pub(crate) fn glyph_distance_field(
+    font: &ab_glyph::FontArc,
+    glyph_id: ab_glyph::GlyphId,
+    scale: ab_glyph::PxScale,
+) -> Option<GlyphDistanceField> {
+    use ab_glyph::Font as _;
+
+    let k = SUPERSAMPLING as i32;
+    let spread = DISTANCE_FIELD_SPREAD as i32;
+
+    let fine_scale = ab_glyph::PxScale {
+        x: scale.x * SUPERSAMPLING as f32,
+        y: scale.y * SUPERSAMPLING as f32,
+    };
+    let outline = font.outline_glyph(
+        glyph_id.with_scale_and_position(fine_scale, ab_glyph::Point { x: 0.0, y: 0.0 }),
+    )?;
+    let bb = outline.px_bounds();
+    if bb.width() == 0.0 || bb.height() == 0.0 {
+        return None;
+    }
+
+    let fine_min = [bb.min.x as i32, bb.min.y as i32];
+    let fine_max = [bb.max.x as i32, bb.max.y as i32];
+    let min = [
+        fine_min[0].div_euclid(k) - spread,
+        fine_min[1].div_euclid(k) - spread,
+    ];
+    let max = [
+        (fine_max[0] + k - 1).div_euclid(k) + spread,
+        (fine_max[1] + k - 1).div_euclid(k) + spread,
+    ];
+    let size = [(max[0] - min[0]) as usize, (max[1] - min[1]) as usize];
+
+    // Which of the supersampled pixels are inside the glyph:
+    let fine_size = [size[0] * SUPERSAMPLING, size[1] * SUPERSAMPLING];
+    let offset = [
+        (fine_min[0] - min[0] * k) as usize,
+        (fine_min[1] - min[1] * k) as usize,
+    ];
+    let mut inside = vec![false; fine_size[0] * fine_size[1]];
+    outline.draw(|x, y, coverage| {
+        if 0.5 <= coverage {
+            inside[(offset[1] + y as usize) * fine_size[0] + offset[0] + x as usize] = true;
+        }
+    });
+
+    let to_inside = squared_distances(&inside, fine_size, true);
+    let to_outside = squared_distances(&inside, fine_size, false);
+
+    // Average the signed distances of the supersampled pixels that make up each texel:
+    let mut distances = Vec::with_capacity(size[0] * size[1]);
+    for y in 0..size[1] {
+        for x in 0..size[0] {
+            let mut sum = 0.0;
+            for fine_y in y * SUPERSAMPLING..(y + 1) * SUPERSAMPLING {
+                for fine_x in x * SUPERSAMPLING..(x + 1) * SUPERSAMPLING {
+                    let i = fine_y * fine_size[0] + fine_x;
+                    // The outline goes between the pixel centers, half a pixel from each:
+                    sum += if inside[i] {
+                        0.5 - to_outside[i].sqrt()
+                    } else {
+                        to_inside[i].sqrt() - 0.5
+                    };
+                }
+            }
+            let outside_distance = sum / (SUPERSAMPLING * SUPERSAMPLING * SUPERSAMPLING) as f32;
+            distances.push((0.5 - 0.5 * outside_distance / DISTANCE_FIELD_SPREAD).clamp(0.0, 1.0));
+        }
+    }
+
+    Some(GlyphDistanceField {
+        min,
+        size,
+        distances,
+    })
+}
+
+/// The squared distance from each pixel to the closest pixel where `mask` is `target`.
+///
+/// This is the exact euclidean distance transform by Felzenszwalb and Huttenlocher,
+/// done for all the columns and then for all the rows.
+fn squared_distances(mask: &[bool], [width, height]: [usize; 2], target: bool) -> Vec<f32> {
+    const FAR: f32 = 1e20;
+
+    let mut grid: Vec<f32> = mask
+        .iter()
+        .map(|&m| if m == target { 0.0 } else { FAR })
+        .collect();
+
+    let n = width.max(height);
+    let mut line = vec![0.0; n];
+    let mut out = vec![0.0; n];
+    let mut parabolas = vec![0; n];
+    let mut boundaries = vec![0.0; n + 1];
+
+    for x in 0..width {
+        for y in 0..height {
+            line[y] = grid[y * width + x];
+        }
+        distance_transform_1d(
+            &line[..height],
+            &mut out[..height],
+            &mut parabolas,
+            &mut boundaries,
+        );
+        for y in 0..height {
+            grid[y * width + x] = out[y];
+        }
+    }
+
+    for y in 0..height {
+        let row = &mut grid[y * width..(y + 1) * width];
+        line[..width].copy_from_slice(row);
+        distance_transform_1d(&line[..width], row, &mut parabolas, &mut boundaries);
+    }
+
+    grid
+}
+
+/// The one-dimensional squared distance transform of `f` into `d`,
+/// as the lower envelope of the parabolas rooted at each sample.
+fn distance_transform_1d(
+    f: &[f32],
+    d: &mut [f32],
+    parabolas: &mut [usize],
+    boundaries: &mut [f32],
+) {
+    let intersection = |q: usize, p: usize| {
+        let (qf, pf) = (q as f32, p as f32);
+        ((f[q] + qf * qf) - (f[p] + pf * pf)) / (2.0 * (qf - pf))
+    };
+
+    let mut k = 0;
+    parabolas[0] = 0;
+    boundaries[0] = f32::NEG_INFINITY;
+    boundaries[1] = f32::INFINITY;
+    for q in 1..f.len() {
+        let mut s = intersection(q, parabolas[k]);
+        while s <= boundaries[k] {
+            k -= 1;
+            s = intersection(q, parabolas[k]);
+        }
+        k += 1;
+        parabolas[k] = q;
+        boundaries[k] = s;
+        boundaries[k + 1] = f32::INFINITY;
+    }
+
+    k = 0;
+    for (q, d) in d.iter_mut().enumerate() {
+        while boundaries[k + 1] < q as f32 {
+            k += 1;
+        }
+        let dq = q as f32 - parabolas[k] as f32;
+        *d = dq * dq + f[parabolas[k]];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn squared_distances_to_a_point() {
+        let mut mask = vec![false; 5 * 3];
+        mask[5 + 1] = true; // (1, 1)
+        let distances = squared_distances(&mask, [5, 3], true);
+        assert_eq!(distances[5 + 1], 0.0);
+        assert_eq!(distances[5 + 4], 9.0);
+        assert_eq!(distances[2 * 5 + 3], 5.0);
+        assert_eq!(distances[0], 2.0);
+    }
+}
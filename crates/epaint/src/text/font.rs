@@ -5,7 +5,7 @@ use emath::{vec2, Vec2};
 
 use crate::{
     mutex::{Mutex, RwLock},
-    text::FontTweak,
+    text::{FontHinting, FontRasterization, FontTweak},
     TextureAtlas,
 };
 
@@ -26,6 +26,14 @@ pub struct UvRect {
 
     /// Bottom right corner (exclusive).
     pub max: [u16; 2],
+
+    /// The texels have their own colors (e.g. a color emoji), so the glyph should not be tinted by the text color.
+    ///
+    /// See [`crate::FontImage::colors`].
+    pub colored: bool,
+
+    /// The texels are a signed distance field of the glyph, see [`super::GlyphRendering::DistanceField`].
+    pub distance_field: bool,
 }
 
 impl UvRect {
@@ -47,6 +55,11 @@ pub struct GlyphInfo {
 
     /// Texture coordinates.
     pub uv_rect: UvRect,
+
+    /// The glyph is not in the atlas yet, because of [`TextureAtlas::set_max_new_glyphs_per_pass`].
+    ///
+    /// It has the right [`Self::advance_width`], but is invisible. Such glyphs are not cached.
+    pub(crate) deferred: bool,
 }
 
 impl Default for GlyphInfo {
@@ -56,12 +69,45 @@ impl Default for GlyphInfo {
             id: ab_glyph::GlyphId(0),
             advance_width: 0.0,
             uv_rect: Default::default(),
+            deferred: false,
         }
     }
 }
 
 // ----------------------------------------------------------------------------
 
+#[cfg(feature = "shaping")]
+pub(crate) use font_file::FontFile;
+
+#[cfg(feature = "shaping")]
+#[allow(clippy::mem_forget)] // for the code generated by `self_cell!`
+mod font_file {
+    type Face<'a> = rustybuzz::Face<'a>;
+
+    self_cell::self_cell!(
+        /// The raw contents of a font file, parsed once for shaping with `rustybuzz`.
+        pub(crate) struct FontFile {
+            owner: std::borrow::Cow<'static, [u8]>,
+
+            #[not_covariant]
+            dependent: Face,
+        }
+    );
+}
+
+/// A glyph produced by [`FontImpl::shape`].
+#[cfg(feature = "shaping")]
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct ShapedGlyph {
+    /// Byte offset of the first `char` this glyph represents.
+    pub cluster: usize,
+
+    /// The advance width and offset include kerning and mark positioning.
+    pub glyph_info: GlyphInfo,
+}
+
+// ----------------------------------------------------------------------------
+
 /// A specific font with a size.
 /// The interface uses points as the unit for everything.
 pub struct FontImpl {
@@ -71,6 +117,18 @@ pub struct FontImpl {
     /// Maximum character height
     scale_in_pixels: u32,
 
+    /// The scale the glyphs are rasterized at, which differs from [`Self::scale_in_pixels`] with hinting.
+    raster_scale: ab_glyph::PxScale,
+
+    /// How much to thicken the strokes, see [`FontRasterization::stem_darkening`].
+    stem_darkening: f32,
+
+    /// See [`FontRasterization::pixel_snapping`].
+    pixel_snapping: bool,
+
+    /// Store distance fields of the glyphs instead of bitmaps, see [`super::GlyphRendering::DistanceField`].
+    distance_field: bool,
+
     height_in_points: f32,
 
     // move each character by this much (hack)
@@ -80,6 +138,13 @@ pub struct FontImpl {
     pixels_per_point: f32,
     glyph_info_cache: RwLock<ahash::HashMap<char, GlyphInfo>>, // TODO(emilk): standard Mutex
     atlas: Arc<Mutex<TextureAtlas>>,
+
+    #[cfg(feature = "shaping")]
+    font_file: Option<Arc<FontFile>>,
+
+    /// Glyphs produced by shaping, which need not map to a single `char`.
+    #[cfg(feature = "shaping")]
+    glyph_id_cache: RwLock<ahash::HashMap<ab_glyph::GlyphId, GlyphInfo>>,
 }
 
 impl FontImpl {
@@ -90,6 +155,7 @@ impl FontImpl {
         ab_glyph_font: ab_glyph::FontArc,
         scale_in_pixels: f32,
         tweak: FontTweak,
+        rasterization: &FontRasterization,
     ) -> Self {
         assert!(scale_in_pixels > 0.0);
         assert!(pixels_per_point > 0.0);
@@ -124,19 +190,47 @@ impl FontImpl {
         // Round to closest pixel:
         let y_offset_in_points = (y_offset_points * pixels_per_point).round() / pixels_per_point;
 
+        let raster_scale = match rasterization.hinting_at(pixels_per_point) {
+            FontHinting::Light => x_height_hinted_scale(&ab_glyph_font, scale_in_pixels as f32),
+            FontHinting::Auto | FontHinting::None => {
+                ab_glyph::PxScale::from(scale_in_pixels as f32)
+            }
+        };
+
         Self {
             name,
             ab_glyph_font,
             scale_in_pixels,
+            raster_scale,
+            stem_darkening: rasterization.stem_darkening_at(pixels_per_point),
+            pixel_snapping: rasterization.pixel_snapping_at(pixels_per_point),
+            distance_field: false,
             height_in_points: ascent - descent + line_gap,
             y_offset_in_points,
             ascent: ascent + baseline_offset,
             pixels_per_point,
             glyph_info_cache: Default::default(),
             atlas,
+            #[cfg(feature = "shaping")]
+            font_file: None,
+            #[cfg(feature = "shaping")]
+            glyph_id_cache: Default::default(),
         }
     }
 
+    /// Enable [`Self::shape`] for this font.
+    #[cfg(feature = "shaping")]
+    pub(crate) fn with_font_file(mut self, font_file: Arc<FontFile>) -> Self {
+        self.font_file = Some(font_file);
+        self
+    }
+
+    /// Store the glyphs of this font as distance fields, see [`super::GlyphRendering::DistanceField`].
+    pub(crate) fn with_distance_field(mut self) -> Self {
+        self.distance_field = true;
+        self
+    }
+
     /// Code points that will always be replaced by the replacement character.
     ///
     /// See also [`invisible_char`].
@@ -228,11 +322,72 @@ impl FontImpl {
             None // unsupported character
         } else {
             let glyph_info = self.allocate_glyph(glyph_id);
-            self.glyph_info_cache.write().insert(c, glyph_info);
+            if !glyph_info.deferred {
+                self.glyph_info_cache.write().insert(c, glyph_info);
+            }
             Some(glyph_info)
         }
     }
 
+    /// Shape a run of text using `rustybuzz`.
+    ///
+    /// The glyphs are returned in visual order, and may not map one-to-one to the `char`s
+    /// (e.g. ligatures, or characters with several glyphs).
+    ///
+    /// Returns `None` if this font cannot shape the text,
+    /// e.g. because it lacks some of the glyphs.
+    #[cfg(feature = "shaping")]
+    pub(crate) fn shape(&self, text: &str) -> Option<Vec<ShapedGlyph>> {
+        use ab_glyph::Font as _;
+
+        let font_file = self.font_file.as_ref()?;
+
+        let mut buffer = rustybuzz::UnicodeBuffer::new();
+        buffer.push_str(text);
+        buffer.set_cluster_level(rustybuzz::BufferClusterLevel::Characters);
+        buffer.guess_segment_properties();
+        let output = font_file.with_dependent(|_, face| rustybuzz::shape(face, &[], buffer));
+
+        // Same scale as `ab_glyph::Font::as_scaled` uses:
+        let points_per_unit = self.scale_in_pixels as f32
+            / self.ab_glyph_font.height_unscaled()
+            / self.pixels_per_point;
+
+        output
+            .glyph_infos()
+            .iter()
+            .zip(output.glyph_positions())
+            .map(|(info, position)| {
+                let glyph_id = ab_glyph::GlyphId(u16::try_from(info.glyph_id).ok()?);
+                if glyph_id.0 == 0 {
+                    return None; // missing glyph - leave it to the fallback fonts
+                }
+
+                let mut glyph_info = self.glyph_info_from_id(glyph_id);
+                glyph_info.advance_width = position.x_advance as f32 * points_per_unit;
+                glyph_info.uv_rect.offset +=
+                    points_per_unit * vec2(position.x_offset as f32, -position.y_offset as f32);
+
+                Some(ShapedGlyph {
+                    cluster: info.cluster as usize,
+                    glyph_info,
+                })
+            })
+            .collect()
+    }
+
+    #[cfg(feature = "shaping")]
+    fn glyph_info_from_id(&self, glyph_id: ab_glyph::GlyphId) -> GlyphInfo {
+        if let Some(glyph_info) = self.glyph_id_cache.read().get(&glyph_id) {
+            return *glyph_info;
+        }
+        let glyph_info = self.allocate_glyph(glyph_id);
+        if !glyph_info.deferred {
+            self.glyph_id_cache.write().insert(glyph_id, glyph_info);
+        }
+        glyph_info
+    }
+
     #[inline]
     pub fn pair_kerning(
         &self,
@@ -257,6 +412,12 @@ impl FontImpl {
         self.pixels_per_point
     }
 
+    /// Are glyphs snapped to whole pixels? See [`FontRasterization::pixel_snapping`].
+    #[inline]
+    pub fn pixel_snapping(&self) -> bool {
+        self.pixel_snapping
+    }
+
     /// This is the distance from the top to the baseline.
     ///
     /// Unit: points.
@@ -265,48 +426,91 @@ impl FontImpl {
         self.ascent
     }
 
+    /// Update the texture coordinates of the cached glyphs after [`TextureAtlas::compact`].
+    ///
+    /// `new_min` maps the old top left corner of each glyph in the atlas to the new one.
+    /// Glyphs that are not in the map are forgotten, and will be rasterized again when needed.
+    pub(crate) fn remap_glyphs(&self, new_min: &ahash::HashMap<[u16; 2], [u16; 2]>) {
+        let remap = |glyph_info: &mut GlyphInfo| {
+            let uv = &mut glyph_info.uv_rect;
+            if uv.is_nothing() {
+                return true; // Nothing in the atlas
+            }
+            let Some(&min) = new_min.get(&uv.min) else {
+                return false;
+            };
+            uv.max = [
+                min[0] + (uv.max[0] - uv.min[0]),
+                min[1] + (uv.max[1] - uv.min[1]),
+            ];
+            uv.min = min;
+            true
+        };
+
+        self.glyph_info_cache
+            .write()
+            .retain(|_, glyph_info| remap(glyph_info));
+
+        #[cfg(feature = "shaping")]
+        self.glyph_id_cache
+            .write()
+            .retain(|_, glyph_info| remap(glyph_info));
+    }
+
     fn allocate_glyph(&self, glyph_id: ab_glyph::GlyphId) -> GlyphInfo {
         assert!(glyph_id.0 != 0);
         use ab_glyph::{Font as _, ScaleFont};
 
-        let glyph = glyph_id.with_scale_and_position(
-            self.scale_in_pixels as f32,
-            ab_glyph::Point { x: 0.0, y: 0.0 },
-        );
+        let glyph =
+            glyph_id.with_scale_and_position(self.raster_scale, ab_glyph::Point { x: 0.0, y: 0.0 });
+        let coverage_factor = 1.0 + self.stem_darkening;
 
-        let uv_rect = self.ab_glyph_font.outline_glyph(glyph).map(|glyph| {
-            let bb = glyph.px_bounds();
-            let glyph_width = bb.width() as usize;
-            let glyph_height = bb.height() as usize;
-            if glyph_width == 0 || glyph_height == 0 {
-                UvRect::default()
-            } else {
-                let glyph_pos = {
-                    let atlas = &mut self.atlas.lock();
-                    let (glyph_pos, image) = atlas.allocate((glyph_width, glyph_height));
-                    glyph.draw(|x, y, v| {
-                        if 0.0 < v {
-                            let px = glyph_pos.0 + x as usize;
-                            let py = glyph_pos.1 + y as usize;
-                            image[(px, py)] = v;
+        let mut deferred = false;
+        let uv_rect = self.allocate_color_glyph(glyph_id, &mut deferred);
+        let uv_rect = uv_rect.or_else(|| {
+            if self.distance_field {
+                return self.allocate_distance_field_glyph(glyph_id, &mut deferred);
+            }
+            self.ab_glyph_font.outline_glyph(glyph).map(|glyph| {
+                let bb = glyph.px_bounds();
+                let glyph_width = bb.width() as usize;
+                let glyph_height = bb.height() as usize;
+                if glyph_width == 0 || glyph_height == 0 {
+                    UvRect::default()
+                } else {
+                    let glyph_pos = {
+                        let atlas = &mut self.atlas.lock();
+                        if !atlas.try_add_glyph() {
+                            deferred = true;
+                            return UvRect::default();
                         }
-                    });
-                    glyph_pos
-                };
-
-                let offset_in_pixels = vec2(bb.min.x, bb.min.y);
-                let offset =
-                    offset_in_pixels / self.pixels_per_point + self.y_offset_in_points * Vec2::Y;
-                UvRect {
-                    offset,
-                    size: vec2(glyph_width as f32, glyph_height as f32) / self.pixels_per_point,
-                    min: [glyph_pos.0 as u16, glyph_pos.1 as u16],
-                    max: [
-                        (glyph_pos.0 + glyph_width) as u16,
-                        (glyph_pos.1 + glyph_height) as u16,
-                    ],
+                        let (glyph_pos, image) = atlas.allocate((glyph_width, glyph_height));
+                        glyph.draw(|x, y, v| {
+                            if 0.0 < v {
+                                let px = glyph_pos.0 + x as usize;
+                                let py = glyph_pos.1 + y as usize;
+                                image[(px, py)] = (coverage_factor * v).min(1.0);
+                            }
+                        });
+                        glyph_pos
+                    };
+
+                    let offset_in_pixels = vec2(bb.min.x, bb.min.y);
+                    let offset = offset_in_pixels / self.pixels_per_point
+                        + self.y_offset_in_points * Vec2::Y;
+                    UvRect {
+                        offset,
+                        size: vec2(glyph_width as f32, glyph_height as f32) / self.pixels_per_point,
+                        min: [glyph_pos.0 as u16, glyph_pos.1 as u16],
+                        max: [
+                            (glyph_pos.0 + glyph_width) as u16,
+                            (glyph_pos.1 + glyph_height) as u16,
+                        ],
+                        colored: false,
+                        distance_field: false,
+                    }
                 }
-            }
+            })
         });
         let uv_rect = uv_rect.unwrap_or_default();
 
@@ -320,7 +524,199 @@ impl FontImpl {
             id: glyph_id,
             advance_width: advance_width_in_points,
             uv_rect,
+            deferred,
+        }
+    }
+}
+
+impl FontImpl {
+    /// Put the color bitmap of the glyph in the atlas, if the font has one (CBDT and sbix emoji fonts).
+    ///
+    /// Returns `None` for glyphs without a color bitmap, which are rasterized from their outline instead.
+    /// Colored outlines (COLR) are not supported, and are rasterized as monochrome outlines.
+    #[cfg(feature = "color_emoji")]
+    fn allocate_color_glyph(
+        &self,
+        glyph_id: ab_glyph::GlyphId,
+        deferred: &mut bool,
+    ) -> Option<UvRect> {
+        use ab_glyph::Font as _;
+
+        let raster_image = self
+            .ab_glyph_font
+            .glyph_raster_image(glyph_id, self.scale_in_pixels as u16)?;
+        if !matches!(raster_image.format, ab_glyph::GlyphImageFormat::Png) {
+            return None;
         }
+        let bitmap = decode_png(raster_image.data)?;
+
+        // The bitmaps come in a few fixed sizes (strikes), so we scale it to the font size:
+        let scale = self.scale_in_pixels as f32 / raster_image.scale;
+        let glyph_width = ((bitmap.width() as f32 * scale).round() as usize).max(1);
+        let glyph_height = ((bitmap.height() as f32 * scale).round() as usize).max(1);
+
+        let glyph_pos = {
+            let atlas = &mut self.atlas.lock();
+            if !atlas.try_add_glyph() {
+                *deferred = true;
+                return Some(UvRect::default());
+            }
+            let (glyph_pos, image) = atlas.allocate((glyph_width, glyph_height));
+            for y in 0..glyph_height {
+                for x in 0..glyph_width {
+                    let color = box_filter(&bitmap, [x, y], [glyph_width, glyph_height]);
+                    image.set_color((glyph_pos.0 + x, glyph_pos.1 + y), color);
+                }
+            }
+            glyph_pos
+        };
+
+        // The origin of the bitmap is its bottom left corner, relative to the baseline (with y up):
+        let offset_in_pixels = vec2(
+            raster_image.origin.x * scale,
+            -(raster_image.origin.y + bitmap.height() as f32) * scale,
+        );
+        let offset = offset_in_pixels / self.pixels_per_point + self.y_offset_in_points * Vec2::Y;
+        Some(UvRect {
+            offset,
+            size: vec2(glyph_width as f32, glyph_height as f32) / self.pixels_per_point,
+            min: [glyph_pos.0 as u16, glyph_pos.1 as u16],
+            max: [
+                (glyph_pos.0 + glyph_width) as u16,
+                (glyph_pos.1 + glyph_height) as u16,
+            ],
+            colored: true,
+            distance_field: false,
+        })
+    }
+
+    #[cfg(not(feature = "color_emoji"))]
+    #[allow(clippy::unused_self)]
+    fn allocate_color_glyph(&self, _: ab_glyph::GlyphId, _: &mut bool) -> Option<UvRect> {
+        None
+    }
+
+    /// Put the signed distance field of the glyph in the atlas.
+    fn allocate_distance_field_glyph(
+        &self,
+        glyph_id: ab_glyph::GlyphId,
+        deferred: &mut bool,
+    ) -> Option<UvRect> {
+        let field = super::distance_field::glyph_distance_field(
+            &self.ab_glyph_font,
+            glyph_id,
+            self.raster_scale,
+        )?;
+        let [width, height] = field.size;
+
+        let glyph_pos = {
+            let atlas = &mut self.atlas.lock();
+            if !atlas.try_add_glyph() {
+                *deferred = true;
+                return None;
+            }
+            let (glyph_pos, image) = atlas.allocate((width, height));
+            // Undo the gamma that is applied to the coverage when the atlas is uploaded,
+            // so the renderer gets the distances as they are:
+            let inverse_gamma = 1.0 / crate::FontImage::DEFAULT_GAMMA;
+            for y in 0..height {
+                for x in 0..width {
+                    let distance = field.distances[y * width + x];
+                    image[(glyph_pos.0 + x, glyph_pos.1 + y)] = distance.powf(inverse_gamma);
+                }
+            }
+            glyph_pos
+        };
+
+        let offset_in_pixels = vec2(field.min[0] as f32, field.min[1] as f32);
+        let offset = offset_in_pixels / self.pixels_per_point + self.y_offset_in_points * Vec2::Y;
+        Some(UvRect {
+            offset,
+            size: vec2(width as f32, height as f32) / self.pixels_per_point,
+            min: [glyph_pos.0 as u16, glyph_pos.1 as u16],
+            max: [(glyph_pos.0 + width) as u16, (glyph_pos.1 + height) as u16],
+            colored: false,
+            distance_field: true,
+        })
+    }
+}
+
+/// Decode the PNG of a color glyph.
+#[cfg(feature = "color_emoji")]
+fn decode_png(png_bytes: &[u8]) -> Option<crate::ColorImage> {
+    let mut decoder = png::Decoder::new(png_bytes);
+    decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+    let mut reader = decoder.read_info().ok()?;
+    let mut buf = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buf).ok()?;
+    let size = [info.width as usize, info.height as usize];
+    let bytes = &buf[..info.buffer_size()];
+    let pixels = match info.color_type {
+        png::ColorType::Rgba => bytes
+            .chunks_exact(4)
+            .map(|p| crate::Color32::from_rgba_unmultiplied(p[0], p[1], p[2], p[3]))
+            .collect(),
+        png::ColorType::Rgb => bytes
+            .chunks_exact(3)
+            .map(|p| crate::Color32::from_rgb(p[0], p[1], p[2]))
+            .collect(),
+        png::ColorType::GrayscaleAlpha => bytes
+            .chunks_exact(2)
+            .map(|p| crate::Color32::from_rgba_unmultiplied(p[0], p[0], p[0], p[1]))
+            .collect(),
+        png::ColorType::Grayscale => bytes
+            .iter()
+            .map(|&l| crate::Color32::from_gray(l))
+            .collect(),
+        png::ColorType::Indexed => return None, // `EXPAND` turns these into `Rgb` or `Rgba`
+    };
+    Some(crate::ColorImage { size, pixels })
+}
+
+/// The average premultiplied color of the texels of `bitmap` that end up in texel `pos` when resized to `size`.
+#[cfg(feature = "color_emoji")]
+fn box_filter(
+    bitmap: &crate::ColorImage,
+    [x, y]: [usize; 2],
+    [w, h]: [usize; 2],
+) -> crate::Color32 {
+    let range = |i: usize, n: usize, bitmap_n: usize| {
+        let start = (i * bitmap_n / n).min(bitmap_n - 1);
+        let end = ((i + 1) * bitmap_n / n).clamp(start + 1, bitmap_n);
+        start..end
+    };
+    let (xs, ys) = (range(x, w, bitmap.width()), range(y, h, bitmap.height()));
+
+    let mut sum = [0_u32; 4];
+    for y in ys.clone() {
+        for x in xs.clone() {
+            let color = bitmap[(x, y)];
+            for (sum, value) in sum.iter_mut().zip(color.to_array()) {
+                *sum += value as u32;
+            }
+        }
+    }
+    let count = (xs.len() * ys.len()) as u32;
+    let [r, g, b, a] = sum.map(|sum| ((sum + count / 2) / count) as u8);
+    crate::Color32::from_rgba_premultiplied(r, g, b, a)
+}
+
+/// Stretch the font vertically so that the x-height is a whole number of pixels.
+///
+/// Only the height changes, so the glyphs keep their advance widths.
+fn x_height_hinted_scale(font: &ab_glyph::FontArc, scale_in_pixels: f32) -> ab_glyph::PxScale {
+    use ab_glyph::{Font as _, ScaleFont as _};
+
+    let scale = ab_glyph::PxScale::from(scale_in_pixels);
+    let x_height = font
+        .outline(font.glyph_id('x'))
+        .map(|outline| outline.bounds.max.y * font.as_scaled(scale).v_scale_factor());
+    match x_height {
+        Some(x_height) if 0.5 <= x_height => ab_glyph::PxScale {
+            x: scale_in_pixels,
+            y: scale_in_pixels * x_height.round() / x_height,
+        },
+        _ => scale,
     }
 }
 
@@ -336,6 +732,7 @@ pub struct Font {
 
     replacement_glyph: (FontIndex, GlyphInfo),
     pixels_per_point: f32,
+    pixel_snapping: bool,
     row_height: f32,
     glyph_info_cache: ahash::HashMap<char, (FontIndex, GlyphInfo)>,
 }
@@ -348,12 +745,14 @@ impl Font {
                 characters: None,
                 replacement_glyph: Default::default(),
                 pixels_per_point: 1.0,
+                pixel_snapping: true,
                 row_height: 0.0,
                 glyph_info_cache: Default::default(),
             };
         }
 
         let pixels_per_point = fonts[0].pixels_per_point();
+        let pixel_snapping = fonts[0].pixel_snapping();
         let row_height = fonts[0].row_height();
 
         let mut slf = Self {
@@ -361,26 +760,29 @@ impl Font {
             characters: None,
             replacement_glyph: Default::default(),
             pixels_per_point,
+            pixel_snapping,
             row_height,
             glyph_info_cache: Default::default(),
         };
 
+        slf.replacement_glyph = slf.find_replacement_glyph();
+
+        slf
+    }
+
+    fn find_replacement_glyph(&mut self) -> (FontIndex, GlyphInfo) {
         const PRIMARY_REPLACEMENT_CHAR: char = '◻'; // white medium square
         const FALLBACK_REPLACEMENT_CHAR: char = '?'; // fallback for the fallback
 
-        let replacement_glyph = slf
-            .glyph_info_no_cache_or_fallback(PRIMARY_REPLACEMENT_CHAR)
-            .or_else(|| slf.glyph_info_no_cache_or_fallback(FALLBACK_REPLACEMENT_CHAR))
+        self.glyph_info_no_cache_or_fallback(PRIMARY_REPLACEMENT_CHAR)
+            .or_else(|| self.glyph_info_no_cache_or_fallback(FALLBACK_REPLACEMENT_CHAR))
             .unwrap_or_else(|| {
                 #[cfg(feature = "log")]
                 log::warn!(
                     "Failed to find replacement characters {PRIMARY_REPLACEMENT_CHAR:?} or {FALLBACK_REPLACEMENT_CHAR:?}. Will use empty glyph."
                 );
                 (0, GlyphInfo::default())
-            });
-        slf.replacement_glyph = replacement_glyph;
-
-        slf
+            })
     }
 
     pub fn preload_characters(&mut self, s: &str) {
@@ -413,9 +815,15 @@ impl Font {
         })
     }
 
+    /// Round a horizontal glyph position to whole pixels,
+    /// unless [`FontRasterization::pixel_snapping`] is turned off.
     #[inline(always)]
     pub fn round_to_pixel(&self, point: f32) -> f32 {
-        (point * self.pixels_per_point).round() / self.pixels_per_point
+        if self.pixel_snapping {
+            (point * self.pixels_per_point).round() / self.pixels_per_point
+        } else {
+            point
+        }
     }
 
     /// Height of one row of text. In points
@@ -436,6 +844,30 @@ impl Font {
         self.glyph_info(c).1.advance_width
     }
 
+    /// Width of the widest line of `text` in points, without wrapping and without shaping.
+    ///
+    /// Uses the same glyph advances, kerning and pixel rounding as the text layout,
+    /// but without building a [`crate::Galley`].
+    pub fn text_width(&mut self, text: &str) -> f32 {
+        let mut width: f32 = 0.0;
+        for line in text.split('\n') {
+            let mut cursor_x = 0.0;
+            let mut line_width = 0.0;
+            let mut last_glyph_id = None;
+            for chr in line.chars() {
+                let (font_impl, glyph_info) = self.font_impl_and_glyph_info(chr);
+                if let (Some(font_impl), Some(last_glyph_id)) = (font_impl, last_glyph_id) {
+                    cursor_x += font_impl.pair_kerning(last_glyph_id, glyph_info.id);
+                }
+                line_width = cursor_x + glyph_info.advance_width;
+                cursor_x = self.round_to_pixel(line_width);
+                last_glyph_id = Some(glyph_info.id);
+            }
+            width = width.max(line_width);
+        }
+        width
+    }
+
     /// Can we display this glyph?
     pub fn has_glyph(&mut self, c: char) -> bool {
         self.glyph_info(c) != self.replacement_glyph // TODO(emilk): this is a false negative if the user asks about the replacement character itself 🤦‍♂️
@@ -452,9 +884,15 @@ impl Font {
             return *font_index_glyph_info;
         }
 
+        if self.replacement_glyph.1.deferred {
+            self.replacement_glyph = self.find_replacement_glyph();
+        }
+
         let font_index_glyph_info = self.glyph_info_no_cache_or_fallback(c);
         let font_index_glyph_info = font_index_glyph_info.unwrap_or(self.replacement_glyph);
-        self.glyph_info_cache.insert(c, font_index_glyph_info);
+        if !font_index_glyph_info.1.deferred {
+            self.glyph_info_cache.insert(c, font_index_glyph_info);
+        }
         font_index_glyph_info
     }
 
@@ -468,6 +906,77 @@ impl Font {
         (Some(font_impl), glyph_info)
     }
 
+    /// Shape the runs of complex scripts (e.g. Arabic) in the given text.
+    ///
+    /// Returns one entry per `char` in the text.
+    /// Characters that do not need shaping are `None`, and should be laid out one by one.
+    /// Characters that were merged into the glyph of a previous character
+    /// (e.g. the second half of a ligature) get a zero-width glyph.
+    #[cfg(feature = "shaping")]
+    pub(crate) fn shape(&mut self, text: &str) -> Vec<Option<(Arc<FontImpl>, GlyphInfo)>> {
+        let chars: Vec<(usize, char)> = text.char_indices().collect();
+        let mut shaped: Vec<Option<(FontIndex, GlyphInfo)>> = vec![None; chars.len()];
+
+        let mut i = 0;
+        while i < chars.len() {
+            if !needs_shaping(chars[i].1) {
+                i += 1;
+                continue;
+            }
+
+            // Find a run of characters using the same font:
+            let (font_index, _) = self.glyph_info(chars[i].1);
+            let run_start = i;
+            while i < chars.len() {
+                let c = chars[i].1;
+                let is_joiner = matches!(c, '\u{200C}' | '\u{200D}'); // ZERO WIDTH (NON-)JOINER
+                let same_run =
+                    is_joiner || (needs_shaping(c) && self.glyph_info(c).0 == font_index);
+                if !same_run {
+                    break;
+                }
+                i += 1;
+            }
+
+            let run_byte_start = chars[run_start].0;
+            let run_byte_end = chars.get(i).map_or(text.len(), |(byte, _)| *byte);
+            let Some(font_impl) = self.fonts.get(font_index) else {
+                continue;
+            };
+            let Some(glyphs) = font_impl.shape(&text[run_byte_start..run_byte_end]) else {
+                continue;
+            };
+
+            let run = &chars[run_start..i];
+            for glyph in glyphs {
+                let byte = run_byte_start + glyph.cluster;
+                let char_index = run_start + run.partition_point(|(b, _)| *b < byte);
+                let Some(entry) = shaped.get_mut(char_index) else {
+                    continue;
+                };
+                if let Some((_, existing)) = entry {
+                    // Several glyphs for one char. We can only show one of them.
+                    existing.advance_width += glyph.glyph_info.advance_width;
+                    if existing.uv_rect.is_nothing() {
+                        existing.uv_rect = glyph.glyph_info.uv_rect;
+                    }
+                } else {
+                    *entry = Some((font_index, glyph.glyph_info));
+                }
+            }
+            for entry in &mut shaped[run_start..i] {
+                entry.get_or_insert((font_index, GlyphInfo::default()));
+            }
+        }
+
+        shaped
+            .into_iter()
+            .map(|entry| {
+                entry.map(|(font_index, glyph_info)| (self.fonts[font_index].clone(), glyph_info))
+            })
+            .collect()
+    }
+
     pub(crate) fn ascent(&self) -> f32 {
         if let Some(first) = self.fonts.first() {
             first.ascent()
@@ -479,7 +988,9 @@ impl Font {
     fn glyph_info_no_cache_or_fallback(&mut self, c: char) -> Option<(FontIndex, GlyphInfo)> {
         for (font_index, font_impl) in self.fonts.iter().enumerate() {
             if let Some(glyph_info) = font_impl.glyph_info(c) {
-                self.glyph_info_cache.insert(c, (font_index, glyph_info));
+                if !glyph_info.deferred {
+                    self.glyph_info_cache.insert(c, (font_index, glyph_info));
+                }
                 return Some((font_index, glyph_info));
             }
         }
@@ -487,6 +998,23 @@ impl Font {
     }
 }
 
+/// Scripts where the glyphs depend on the surrounding characters (e.g. Arabic joining),
+/// or where marks need careful positioning.
+#[cfg(feature = "shaping")]
+#[inline]
+pub(crate) fn needs_shaping(c: char) -> bool {
+    matches!(
+        c,
+        '\u{0590}'..='\u{08FF}' // Hebrew, Arabic, Syriac, Thaana, N'Ko, …
+            | '\u{0900}'..='\u{0DFF}' // Indic scripts
+            | '\u{0E00}'..='\u{0FFF}' // Thai, Lao, Tibetan
+            | '\u{1000}'..='\u{109F}' // Myanmar
+            | '\u{1780}'..='\u{17FF}' // Khmer
+            | '\u{FB1D}'..='\u{FDFF}' // Hebrew and Arabic presentation forms
+            | '\u{FE70}'..='\u{FEFF}' // Arabic presentation forms B
+    )
+}
+
 /// Code points that will always be invisible (zero width).
 ///
 /// See also [`FontImpl::ignore_character`].
@@ -196,6 +196,146 @@ impl Default for FontTweak {
 
 // ----------------------------------------------------------------------------
 
+/// Below this many pixels per point, the automatic [`FontRasterization`] settings
+/// sharpen small text.
+const LOW_DPI_PIXELS_PER_POINT: f32 = 2.0;
+
+/// How glyphs are rasterized and placed on the pixel grid, for all fonts.
+///
+/// The defaults adapt to the scale factor:
+/// on low-DPI screens (e.g. 100% scaling on Windows) small text is hinted, darkened and snapped to whole pixels
+/// so that it doesn't look washed out, while on high-DPI screens glyphs are rendered as designed.
+///
+/// See [`FontDefinitions::rasterization`].
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct FontRasterization {
+    /// Adjust glyph outlines to the pixel grid.
+    pub hinting: FontHinting,
+
+    /// Make the strokes of glyphs thicker, so that thin fonts stay legible at small sizes.
+    ///
+    /// `0.0` is off, `1.0` is strong.
+    ///
+    /// `None` (the default) picks `0.3` at 1 pixel per point, fading out towards 2 pixels per point.
+    pub stem_darkening: Option<f32>,
+
+    /// Round the horizontal positions of glyphs to whole pixels.
+    ///
+    /// This makes text crisper, but the spacing between letters less even.
+    /// The baseline is always on a whole pixel.
+    ///
+    /// `None` (the default) snaps when there are fewer than 2 pixels per point.
+    pub pixel_snapping: Option<bool>,
+}
+
+impl FontRasterization {
+    /// The hinting to use at this scale factor, never [`FontHinting::Auto`].
+    pub fn hinting_at(&self, pixels_per_point: f32) -> FontHinting {
+        match self.hinting {
+            FontHinting::Auto if pixels_per_point < LOW_DPI_PIXELS_PER_POINT => FontHinting::Light,
+            FontHinting::Auto => FontHinting::None,
+            hinting => hinting,
+        }
+    }
+
+    /// The stem darkening to use at this scale factor.
+    pub fn stem_darkening_at(&self, pixels_per_point: f32) -> f32 {
+        self.stem_darkening.unwrap_or_else(|| {
+            let low_dpi_factor =
+                (LOW_DPI_PIXELS_PER_POINT - pixels_per_point) / (LOW_DPI_PIXELS_PER_POINT - 1.0);
+            0.3 * low_dpi_factor.clamp(0.0, 1.0)
+        })
+    }
+
+    /// Whether or not to snap glyphs to whole pixels at this scale factor.
+    pub fn pixel_snapping_at(&self, pixels_per_point: f32) -> bool {
+        self.pixel_snapping
+            .unwrap_or(pixels_per_point < LOW_DPI_PIXELS_PER_POINT)
+    }
+}
+
+/// How glyph outlines are adjusted to the pixel grid, see [`FontRasterization::hinting`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum FontHinting {
+    /// [`Self::Light`] when there are fewer than 2 pixels per point, otherwise [`Self::None`].
+    #[default]
+    Auto,
+
+    /// Render the outlines exactly as designed.
+    None,
+
+    /// Stretch glyphs vertically by a fraction of a pixel,
+    /// so that the top of lowercase letters (the x-height) lands on a pixel boundary.
+    ///
+    /// This makes lowercase text sharper. It does not affect the text layout.
+    Light,
+}
+
+// ----------------------------------------------------------------------------
+
+/// How the glyphs of a [`FontFamily`] are stored in the font atlas and painted,
+/// see [`FontDefinitions::glyph_rendering`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum GlyphRendering {
+    /// Rasterize each glyph at the size it is shown at.
+    ///
+    /// This is the sharpest at that size, but the text gets blurry when it is scaled up,
+    /// e.g. with [`crate::Shape::transform`] or a zoomed layer.
+    #[default]
+    Bitmap,
+
+    /// Store a signed distance field of each glyph, which the renderer turns into sharp edges
+    /// at any scale (see [`crate::Mesh::distance_field`]).
+    ///
+    /// This needs support from the renderer (`egui_glow` and `egui-wgpu` have it).
+    /// If the renderer doesn't support it (see [`Fonts::set_distance_field_text`]), [`Self::Bitmap`] is used instead.
+    /// Color glyphs (emojis) are always bitmaps.
+    DistanceField,
+}
+
+// ----------------------------------------------------------------------------
+
+/// Where to look for glyphs that none of the fonts of a [`FontFamily`] have,
+/// see [`FontDefinitions::system_fonts`].
+///
+/// With the `system_fonts` feature, the fonts installed on the system are searched for such glyphs,
+/// and the first font that has one is added as the last fallback of every family.
+/// This lets you show e.g. CJK and emojis without bundling large fonts for them.
+/// Font files are only read once a glyph is needed from them.
+///
+/// Without the `system_fonts` feature, or on the web, this does nothing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct SystemFontFallback {
+    /// Search the system fonts for missing glyphs.
+    ///
+    /// Default: `true`.
+    pub enabled: bool,
+
+    /// Family names of system fonts to try first, in order, e.g. `"Noto Sans CJK JP"`
+    /// to prefer the Japanese forms of CJK ideographs.
+    ///
+    /// After these, some common fonts for the script of the glyph are tried,
+    /// and then all other system fonts.
+    pub preferred_families: Vec<String>,
+}
+
+impl Default for SystemFontFallback {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            preferred_families: Vec::new(),
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 fn ab_glyph_font_from_font_data(name: &str, data: &FontData) -> ab_glyph::FontArc {
     match &data.font {
         std::borrow::Cow::Borrowed(bytes) => {
@@ -252,6 +392,17 @@ pub struct FontDefinitions {
     /// the first font and then move to the second, and so on.
     /// So the first font is the primary, and then comes a list of fallbacks in order of priority.
     pub families: BTreeMap<FontFamily, Vec<String>>,
+
+    /// How glyphs are rasterized and placed on the pixel grid.
+    pub rasterization: FontRasterization,
+
+    /// Where to look for glyphs that none of the fonts of a family have.
+    pub system_fonts: SystemFontFallback,
+
+    /// How the glyphs of each [`FontFamily`] are rendered.
+    ///
+    /// Families that are not in here use [`GlyphRendering::Bitmap`].
+    pub glyph_rendering: BTreeMap<FontFamily, GlyphRendering>,
 }
 
 #[derive(Debug, Clone)]
@@ -360,6 +511,9 @@ impl Default for FontDefinitions {
         Self {
             font_data,
             families,
+            rasterization: Default::default(),
+            system_fonts: Default::default(),
+            glyph_rendering: Default::default(),
         }
     }
 }
@@ -374,6 +528,9 @@ impl FontDefinitions {
         Self {
             font_data: Default::default(),
             families,
+            rasterization: Default::default(),
+            system_fonts: Default::default(),
+            glyph_rendering: Default::default(),
         }
     }
 
@@ -423,6 +580,8 @@ impl Fonts {
         let fonts_and_cache = FontsAndCache {
             fonts: FontsImpl::new(pixels_per_point, max_texture_side, definitions),
             galley_cache: Default::default(),
+            text_width_cache: Default::default(),
+            atlas_texels_at_last_check: 0,
         };
         Self(Arc::new(Mutex::new(fonts_and_cache)))
     }
@@ -439,20 +598,53 @@ impl Fonts {
 
         let pixels_per_point_changed = fonts_and_cache.fonts.pixels_per_point != pixels_per_point;
         let max_texture_side_changed = fonts_and_cache.fonts.max_texture_side != max_texture_side;
-        let font_atlas_almost_full = fonts_and_cache.fonts.atlas.lock().fill_ratio() > 0.8;
-        let needs_recreate =
-            pixels_per_point_changed || max_texture_side_changed || font_atlas_almost_full;
+        let needs_recreate = pixels_per_point_changed || max_texture_side_changed;
 
         if needs_recreate {
-            let definitions = fonts_and_cache.fonts.definitions.clone();
-
-            *fonts_and_cache = FontsAndCache {
-                fonts: FontsImpl::new(pixels_per_point, max_texture_side, definitions),
-                galley_cache: Default::default(),
-            };
+            fonts_and_cache.recreate(pixels_per_point, max_texture_side);
         }
 
         fonts_and_cache.galley_cache.flush_cache();
+        fonts_and_cache.text_width_cache.flush_cache();
+
+        if !needs_recreate {
+            fonts_and_cache.compact_atlas_if_needed();
+        }
+
+        fonts_and_cache.fonts.atlas.lock().begin_pass();
+    }
+
+    /// Limit how many new glyphs are rasterized per pass, to spread out the work over several frames.
+    ///
+    /// See [`TextureAtlas::set_max_new_glyphs_per_pass`].
+    pub fn set_max_new_glyphs_per_pass(&self, max_new_glyphs_per_pass: Option<usize>) {
+        self.lock()
+            .fonts
+            .atlas
+            .lock()
+            .set_max_new_glyphs_per_pass(max_new_glyphs_per_pass);
+    }
+
+    /// Tell whether the renderer can paint [`crate::Mesh::distance_field`].
+    ///
+    /// Only then are the families with [`GlyphRendering::DistanceField`] rendered that way,
+    /// otherwise they fall back to [`GlyphRendering::Bitmap`].
+    ///
+    /// Default: `false`.
+    pub fn set_distance_field_text(&self, supported: bool) {
+        let mut fonts_and_cache = self.lock();
+        if fonts_and_cache.fonts.distance_field_text != supported {
+            fonts_and_cache.fonts.distance_field_text = supported;
+            fonts_and_cache.fonts.sized_family.clear();
+            fonts_and_cache.galley_cache = Default::default();
+        }
+    }
+
+    /// Are there glyphs that were left out this pass because of [`Self::set_max_new_glyphs_per_pass`]?
+    ///
+    /// If so, some text is incomplete, and another pass is needed to fill it in.
+    pub fn has_deferred_glyphs(&self) -> bool {
+        0 < self.lock().fonts.atlas.lock().num_deferred_glyphs()
     }
 
     /// Call at the end of each frame (before painting) to get the change to the font texture since last call.
@@ -518,6 +710,20 @@ impl Fonts {
         self.lock().fonts.row_height(font_id)
     }
 
+    /// Width of some text in points, laid out without wrapping.
+    ///
+    /// This is the same as `self.layout_no_wrap(text.to_owned(), font_id, color).size().x`,
+    /// but much cheaper, since no [`Galley`] is built.
+    /// Use it in hot loops where you only need to know how wide some text is,
+    /// e.g. when sizing table columns or checking if tick labels fit.
+    ///
+    /// If the text contains newlines, this is the width of the widest line.
+    ///
+    /// The implementation uses memoization so repeated calls are cheap.
+    pub fn text_width(&self, font_id: &FontId, text: &str) -> f32 {
+        self.lock().text_width(font_id, text)
+    }
+
     /// List of all known font families.
     pub fn families(&self) -> Vec<FontFamily> {
         self.lock()
@@ -598,12 +804,138 @@ impl Fonts {
 pub struct FontsAndCache {
     pub fonts: FontsImpl,
     galley_cache: GalleyCache,
+    text_width_cache: TextWidthCache,
+
+    /// [`TextureAtlas::used_texels`] when we last checked if the atlas needs compacting.
+    atlas_texels_at_last_check: usize,
 }
 
 impl FontsAndCache {
     fn layout_job(&mut self, job: LayoutJob) -> Arc<Galley> {
         self.galley_cache.layout(&mut self.fonts, job)
     }
+
+    fn text_width(&mut self, font_id: &FontId, text: &str) -> f32 {
+        #[cfg(feature = "shaping")]
+        if text.chars().any(super::font::needs_shaping) {
+            // The shaped glyphs have different widths, so we need the full layout:
+            let job = LayoutJob::simple(
+                text.to_owned(),
+                font_id.clone(),
+                crate::Color32::PLACEHOLDER,
+                f32::INFINITY,
+            );
+            return self.layout_job(job).size().x;
+        }
+
+        let fonts = &mut self.fonts;
+        self.text_width_cache.get_or_insert_with(font_id, text, || {
+            #[cfg(all(feature = "system_fonts", not(target_arch = "wasm32")))]
+            fonts.add_system_fallbacks(font_id, text);
+
+            // Round like `Galley::rect`, see `LayoutJob::round_output_size_to_nearest_ui_point`:
+            fonts.font(font_id).text_width(text).round()
+        })
+    }
+
+    /// Start over with empty caches and an empty font atlas.
+    fn recreate(&mut self, pixels_per_point: f32, max_texture_side: usize) {
+        let definitions = self.fonts.definitions.clone();
+        let max_new_glyphs_per_pass = self.fonts.atlas.lock().max_new_glyphs_per_pass();
+        let distance_field_text = self.fonts.distance_field_text;
+        #[cfg(all(feature = "system_fonts", not(target_arch = "wasm32")))]
+        let system_fonts = std::mem::take(&mut self.fonts.system_fonts);
+
+        *self = Self {
+            fonts: FontsImpl::new(pixels_per_point, max_texture_side, definitions),
+            galley_cache: Default::default(),
+            text_width_cache: Default::default(),
+            atlas_texels_at_last_check: 0,
+        };
+        self.fonts
+            .atlas
+            .lock()
+            .set_max_new_glyphs_per_pass(max_new_glyphs_per_pass);
+        self.fonts.distance_field_text = distance_field_text;
+        #[cfg(all(feature = "system_fonts", not(target_arch = "wasm32")))]
+        self.fonts.set_system_fonts(system_fonts);
+    }
+
+    /// Reclaim the space of glyphs that are no longer in use, when the font atlas needs it.
+    ///
+    /// Glyphs are never removed from the atlas one by one, so long-running apps that show
+    /// many different texts or font sizes slowly fill it up with glyphs that are not shown anymore.
+    /// When the atlas is almost full, or when most of the used space is taken up by such glyphs,
+    /// the glyphs used in the last pass are packed tightly together, and the rest are thrown away.
+    /// If that doesn't free up enough space, we start over with an empty atlas.
+    ///
+    /// Should be called after [`GalleyCache::flush_cache`], so the galley cache has the galleys of the last pass.
+    fn compact_atlas_if_needed(&mut self) {
+        /// Above this fill ratio we always compact (or recreate).
+        const ALMOST_FULL: f32 = 0.8;
+
+        /// Below this fill ratio the atlas is small enough that unused glyphs don't matter.
+        const WORTH_CHECKING: f32 = 0.5;
+
+        /// Compact when at least this fraction of the used space is not used by live glyphs.
+        const MAX_FRAGMENTATION: f32 = 0.5;
+
+        let (fill_ratio, used_texels) = {
+            let atlas = self.fonts.atlas.lock();
+            (atlas.fill_ratio(), atlas.used_texels())
+        };
+
+        if 1.0 <= fill_ratio {
+            // The atlas has overflowed, and glyphs may have been overwritten.
+            self.recreate(self.fonts.pixels_per_point, self.fonts.max_texture_side);
+            return;
+        }
+
+        let almost_full = ALMOST_FULL < fill_ratio;
+        let grown_since_last_check =
+            self.atlas_texels_at_last_check + used_texels / 10 < used_texels;
+        let worth_checking = almost_full || (WORTH_CHECKING < fill_ratio && grown_since_last_check);
+        if !worth_checking {
+            return;
+        }
+        self.atlas_texels_at_last_check = used_texels;
+
+        let live_glyphs = self.galley_cache.glyph_regions();
+        let live_texels: usize = live_glyphs
+            .iter()
+            .map(|(min, max)| {
+                // Include the padding between glyphs:
+                (usize::from(max[0] - min[0]) + 1) * (usize::from(max[1] - min[1]) + 1)
+            })
+            .sum();
+        let fragmentation = 1.0 - live_texels as f32 / used_texels.max(1) as f32;
+        if !almost_full && fragmentation < MAX_FRAGMENTATION {
+            return;
+        }
+
+        crate::profile_scope!("compact_font_atlas");
+        #[cfg(feature = "log")]
+        log::debug!(
+            "Compacting the font atlas: {:.0}% full, {:.0}% unused",
+            fill_ratio * 100.0,
+            fragmentation * 100.0
+        );
+
+        self.fonts.compact_atlas(&live_glyphs);
+
+        // The cached galleys have the old texture coordinates:
+        self.galley_cache = Default::default();
+
+        let atlas = self.fonts.atlas.lock();
+        let (fill_ratio, used_texels) = (atlas.fill_ratio(), atlas.used_texels());
+        drop(atlas);
+        self.atlas_texels_at_last_check = used_texels;
+
+        if ALMOST_FULL < fill_ratio {
+            // Too much text in use at once - start over, and hope we need less of it next pass.
+            self.recreate(self.fonts.pixels_per_point, self.fonts.max_texture_side);
+        }
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -618,6 +950,13 @@ pub struct FontsImpl {
     atlas: Arc<Mutex<TextureAtlas>>,
     font_impl_cache: FontImplCache,
     sized_family: ahash::HashMap<(OrderedFloat<f32>, FontFamily), Font>,
+
+    /// Can the renderer paint distance fields? See [`Fonts::set_distance_field_text`].
+    distance_field_text: bool,
+
+    /// Fallbacks for glyphs that none of the fonts in [`Self::definitions`] have.
+    #[cfg(all(feature = "system_fonts", not(target_arch = "wasm32")))]
+    system_fonts: super::system_fonts::SystemFonts,
 }
 
 impl FontsImpl {
@@ -639,8 +978,12 @@ impl FontsImpl {
 
         let atlas = Arc::new(Mutex::new(atlas));
 
-        let font_impl_cache =
-            FontImplCache::new(atlas.clone(), pixels_per_point, &definitions.font_data);
+        let font_impl_cache = FontImplCache::new(
+            atlas.clone(),
+            pixels_per_point,
+            &definitions.font_data,
+            definitions.rasterization,
+        );
 
         Self {
             pixels_per_point,
@@ -649,6 +992,9 @@ impl FontsImpl {
             atlas,
             font_impl_cache,
             sized_family: Default::default(),
+            distance_field_text: false,
+            #[cfg(all(feature = "system_fonts", not(target_arch = "wasm32")))]
+            system_fonts: Default::default(),
         }
     }
 
@@ -662,6 +1008,43 @@ impl FontsImpl {
         &self.definitions
     }
 
+    /// Are glyphs snapped to whole pixels? See [`FontRasterization::pixel_snapping`].
+    #[inline]
+    pub fn pixel_snapping(&self) -> bool {
+        self.definitions
+            .rasterization
+            .pixel_snapping_at(self.pixels_per_point)
+    }
+
+    /// Pack the given glyphs tightly together in the font atlas, and forget all other glyphs.
+    ///
+    /// The glyphs are given as `(min, max)` texel coordinates, as in [`super::UvRect`].
+    fn compact_atlas(&mut self, glyphs: &ahash::HashSet<([u16; 2], [u16; 2])>) {
+        let glyphs: Vec<_> = glyphs.iter().copied().collect();
+        let regions: Vec<_> = glyphs
+            .iter()
+            .map(|(min, max)| {
+                (
+                    [usize::from(min[0]), usize::from(min[1])],
+                    [usize::from(max[0] - min[0]), usize::from(max[1] - min[1])],
+                )
+            })
+            .collect();
+        let new_mins = self.atlas.lock().compact(&regions);
+
+        let new_min: ahash::HashMap<[u16; 2], [u16; 2]> = glyphs
+            .iter()
+            .zip(new_mins)
+            .map(|((min, _), new_min)| (*min, [new_min[0] as u16, new_min[1] as u16]))
+            .collect();
+        for font_impl in self.font_impl_cache.cache.values() {
+            font_impl.remap_glyphs(&new_min);
+        }
+
+        // Each `Font` has its own copies of the glyph infos:
+        self.sized_family.clear();
+    }
+
     /// Get the right font implementation from size and [`FontFamily`].
     pub fn font(&mut self, font_id: &FontId) -> &mut Font {
         let FontId { mut size, family } = font_id;
@@ -673,16 +1056,63 @@ impl FontsImpl {
                 let fonts = &self.definitions.families.get(family);
                 let fonts = fonts
                     .unwrap_or_else(|| panic!("FontFamily::{family:?} is not bound to any fonts"));
+                let distance_field = self.distance_field_text
+                    && self.definitions.glyph_rendering.get(family)
+                        == Some(&GlyphRendering::DistanceField);
+
+                #[cfg(all(feature = "system_fonts", not(target_arch = "wasm32")))]
+                let system_font_names = self.system_fonts.fallbacks.iter().map(|(name, _)| name);
+                #[cfg(not(all(feature = "system_fonts", not(target_arch = "wasm32"))))]
+                let system_font_names = std::iter::empty();
 
                 let fonts: Vec<Arc<FontImpl>> = fonts
                     .iter()
-                    .map(|font_name| self.font_impl_cache.font_impl(size, font_name))
+                    .chain(system_font_names)
+                    .map(|font_name| {
+                        self.font_impl_cache
+                            .font_impl(size, font_name, distance_field)
+                    })
                     .collect();
 
                 Font::new(fonts)
             })
     }
 
+    /// Look among the system fonts for the glyphs in `text` that the fonts of `family` don't have,
+    /// see [`SystemFontFallback`].
+    ///
+    /// Call this before laying out `text`, so that the new fallbacks are used.
+    #[cfg(all(feature = "system_fonts", not(target_arch = "wasm32")))]
+    fn add_system_fallbacks(&mut self, font_id: &FontId, text: &str) {
+        if !self.definitions.system_fonts.enabled {
+            return;
+        }
+
+        for chr in text.chars() {
+            if !self.system_fonts.should_search(chr) || self.font(font_id).has_glyph(chr) {
+                continue;
+            }
+            if let Some((name, font)) = self
+                .system_fonts
+                .search(&self.definitions.system_fonts, chr)
+                .cloned()
+            {
+                self.font_impl_cache.add_font(name, font);
+
+                // Each `Font` has its own list of fallbacks:
+                self.sized_family.clear();
+            }
+        }
+    }
+
+    #[cfg(all(feature = "system_fonts", not(target_arch = "wasm32")))]
+    fn set_system_fonts(&mut self, system_fonts: super::system_fonts::SystemFonts) {
+        for (name, font) in &system_fonts.fallbacks {
+            self.font_impl_cache.add_font(name.clone(), font.clone());
+        }
+        self.system_fonts = system_fonts;
+    }
+
     /// Width of this character in points.
     fn glyph_width(&mut self, font_id: &FontId, c: char) -> f32 {
         self.font(font_id).glyph_width(c)
@@ -710,6 +1140,9 @@ struct CachedGalley {
     /// When it was last used
     last_used: u32,
     galley: Arc<Galley>,
+
+    /// Some glyphs were deferred, so lay it out again next pass.
+    has_deferred_glyphs: bool,
 }
 
 #[derive(Default)]
@@ -755,11 +1188,23 @@ impl GalleyCache {
                 cached.galley.clone()
             }
             std::collections::hash_map::Entry::Vacant(entry) => {
+                #[cfg(all(feature = "system_fonts", not(target_arch = "wasm32")))]
+                for section in &job.sections {
+                    fonts.add_system_fallbacks(
+                        &section.format.font_id,
+                        &job.text[section.byte_range.clone()],
+                    );
+                }
+
+                let deferred_before = fonts.atlas.lock().num_deferred_glyphs();
                 let galley = super::layout(fonts, job.into());
                 let galley = Arc::new(galley);
+                let has_deferred_glyphs =
+                    deferred_before < fonts.atlas.lock().num_deferred_glyphs();
                 entry.insert(CachedGalley {
                     last_used: self.generation,
                     galley: galley.clone(),
+                    has_deferred_glyphs,
                 });
                 galley
             }
@@ -771,10 +1216,22 @@ impl GalleyCache {
     }
 
     /// Must be called once per frame to clear the [`Galley`] cache.
+    /// The `(min, max)` texel coordinates of all glyphs in the cached galleys.
+    fn glyph_regions(&self) -> ahash::HashSet<([u16; 2], [u16; 2])> {
+        self.cache
+            .values()
+            .flat_map(|cached| &cached.galley.rows)
+            .flat_map(|row| &row.glyphs)
+            .filter(|glyph| !glyph.uv_rect.is_nothing())
+            .map(|glyph| (glyph.uv_rect.min, glyph.uv_rect.max))
+            .collect()
+    }
+
     pub fn flush_cache(&mut self) {
         let current_generation = self.generation;
         self.cache.retain(|_key, cached| {
             cached.last_used == current_generation // only keep those that were used this frame
+                && !cached.has_deferred_glyphs // the deferred glyphs may be ready now
         });
         self.generation = self.generation.wrapping_add(1);
     }
@@ -782,13 +1239,54 @@ impl GalleyCache {
 
 // ----------------------------------------------------------------------------
 
+/// Memoizes [`Fonts::text_width`].
+#[derive(Default)]
+struct TextWidthCache {
+    /// Frame counter used to do garbage collection on the cache
+    generation: u32,
+
+    /// The width, and when it was last used.
+    cache: nohash_hasher::IntMap<u64, (u32, f32)>,
+}
+
+impl TextWidthCache {
+    fn get_or_insert_with(
+        &mut self,
+        font_id: &FontId,
+        text: &str,
+        text_width: impl FnOnce() -> f32,
+    ) -> f32 {
+        let hash = crate::util::hash((font_id, text));
+        let (last_used, width) = self
+            .cache
+            .entry(hash)
+            .or_insert_with(|| (self.generation, text_width()));
+        *last_used = self.generation;
+        *width
+    }
+
+    /// Must be called once per frame to clear out widths that were not used.
+    fn flush_cache(&mut self) {
+        let current_generation = self.generation;
+        self.cache
+            .retain(|_key, (last_used, _width)| *last_used == current_generation);
+        self.generation = self.generation.wrapping_add(1);
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 struct FontImplCache {
     atlas: Arc<Mutex<TextureAtlas>>,
     pixels_per_point: f32,
     ab_glyph_fonts: BTreeMap<String, (FontTweak, ab_glyph::FontArc)>,
+    rasterization: FontRasterization,
+
+    #[cfg(feature = "shaping")]
+    font_files: BTreeMap<String, Arc<super::font::FontFile>>,
 
-    /// Map font pixel sizes and names to the cached [`FontImpl`].
-    cache: ahash::HashMap<(u32, String), Arc<FontImpl>>,
+    /// Map font pixel sizes and names (and whether the glyphs are distance fields) to the cached [`FontImpl`].
+    cache: ahash::HashMap<(u32, String, bool), Arc<FontImpl>>,
 }
 
 impl FontImplCache {
@@ -796,6 +1294,7 @@ impl FontImplCache {
         atlas: Arc<Mutex<TextureAtlas>>,
         pixels_per_point: f32,
         font_data: &BTreeMap<String, FontData>,
+        rasterization: FontRasterization,
     ) -> Self {
         let ab_glyph_fonts = font_data
             .iter()
@@ -806,15 +1305,42 @@ impl FontImplCache {
             })
             .collect();
 
+        #[cfg(feature = "shaping")]
+        let font_files = font_data
+            .iter()
+            .filter_map(|(name, font_data)| {
+                let font_file = super::font::FontFile::try_new(font_data.font.clone(), |bytes| {
+                    rustybuzz::Face::from_slice(bytes, font_data.index).ok_or(())
+                })
+                .ok()?;
+                Some((name.clone(), Arc::new(font_file)))
+            })
+            .collect();
+
         Self {
             atlas,
             pixels_per_point,
             ab_glyph_fonts,
+            rasterization,
+            #[cfg(feature = "shaping")]
+            font_files,
             cache: Default::default(),
         }
     }
 
-    pub fn font_impl(&mut self, scale_in_points: f32, font_name: &str) -> Arc<FontImpl> {
+    /// Add a font that isn't part of the [`FontDefinitions`], e.g. a system font.
+    #[cfg(all(feature = "system_fonts", not(target_arch = "wasm32")))]
+    pub fn add_font(&mut self, name: String, font: ab_glyph::FontArc) {
+        self.ab_glyph_fonts
+            .insert(name, (FontTweak::default(), font));
+    }
+
+    pub fn font_impl(
+        &mut self,
+        scale_in_points: f32,
+        font_name: &str,
+        distance_field: bool,
+    ) -> Arc<FontImpl> {
         use ab_glyph::Font as _;
 
         let (tweak, ab_glyph_font) = self
@@ -836,16 +1362,33 @@ impl FontImplCache {
             .entry((
                 (scale_in_pixels * tweak.scale).round() as u32,
                 font_name.to_owned(),
+                distance_field,
             ))
             .or_insert_with(|| {
-                Arc::new(FontImpl::new(
+                let font_impl = FontImpl::new(
                     self.atlas.clone(),
                     self.pixels_per_point,
                     font_name.to_owned(),
                     ab_glyph_font,
                     scale_in_pixels,
                     tweak,
-                ))
+                    &self.rasterization,
+                );
+
+                #[cfg(feature = "shaping")]
+                let font_impl = if let Some(font_file) = self.font_files.get(font_name) {
+                    font_impl.with_font_file(font_file.clone())
+                } else {
+                    font_impl
+                };
+
+                let font_impl = if distance_field {
+                    font_impl.with_distance_field()
+                } else {
+                    font_impl
+                };
+
+                Arc::new(font_impl)
             })
             .clone()
     }
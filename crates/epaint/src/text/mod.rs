@@ -1,8 +1,13 @@
 //! Everything related to text, fonts, text layout, cursors etc.
 
+#[cfg(feature = "shaping")]
+mod bidi;
 pub mod cursor;
+mod distance_field;
 mod font;
 mod fonts;
+#[cfg(all(feature = "system_fonts", not(target_arch = "wasm32")))]
+mod system_fonts;
 mod text_layout;
 mod text_layout_types;
 
@@ -10,9 +15,11 @@ mod text_layout_types;
 pub const TAB_SIZE: usize = 4;
 
 pub use {
+    distance_field::DISTANCE_FIELD_SPREAD,
     fonts::{
-        FontData, FontDefinitions, FontFamily, FontId, FontInsert, FontPriority, FontTweak, Fonts,
-        FontsImpl, InsertFontFamily,
+        FontData, FontDefinitions, FontFamily, FontHinting, FontId, FontInsert, FontPriority,
+        FontRasterization, FontTweak, Fonts, FontsImpl, GlyphRendering, InsertFontFamily,
+        SystemFontFallback,
     },
     text_layout::layout,
     text_layout_types::*,
@@ -0,0 +1,150 @@
+//! Finding fonts installed on the system for glyphs that none of the fonts in [`super::FontDefinitions`] have.
+//!
+//! See [`super::SystemFontFallback`].
+
+use std::sync::OnceLock;
+
+use super::SystemFontFallback;
+
+/// The system fonts we have found fallbacks among, kept when the fonts are recreated.
+#[derive(Clone, Default)]
+pub(crate) struct SystemFonts {
+    /// The characters we have already looked for, found or not.
+    searched: ahash::HashSet<char>,
+
+    /// Name and font of each fallback, in the order they were found.
+    pub fallbacks: Vec<(String, ab_glyph::FontArc)>,
+}
+
+impl SystemFonts {
+    /// Should we look for `chr` among the system fonts?
+    pub fn should_search(&self, chr: char) -> bool {
+        !chr.is_whitespace() && !chr.is_control() && !self.searched.contains(&chr)
+    }
+
+    /// Look for a system font that has `chr`, adding it to [`Self::fallbacks`].
+    ///
+    /// Returns the new fallback, if any.
+    pub fn search(
+        &mut self,
+        settings: &SystemFontFallback,
+        chr: char,
+    ) -> Option<&(String, ab_glyph::FontArc)> {
+        crate::profile_function!();
+
+        if !self.searched.insert(chr) {
+            return None;
+        }
+
+        let db = database();
+        let Some(face) = find_face(db, settings, chr) else {
+            #[cfg(feature = "log")]
+            log::debug!("No system font has {chr:?}");
+            return None;
+        };
+
+        let name = format!("system:{}", face.post_script_name);
+        if self.fallbacks.iter().any(|(existing, _)| *existing == name) {
+            return None;
+        }
+
+        let font = db.with_face_data(face.id, |data, index| {
+            ab_glyph::FontVec::try_from_vec_and_index(data.to_vec(), index).ok()
+        })??;
+
+        #[cfg(feature = "log")]
+        log::debug!("Using the system font {name:?} for {chr:?}");
+        self.fallbacks.push((name, ab_glyph::FontArc::from(font)));
+        self.fallbacks.last()
+    }
+}
+
+/// All fonts installed on the system, loaded the first time it's needed.
+fn database() -> &'static fontdb::Database {
+    static DATABASE: OnceLock<fontdb::Database> = OnceLock::new();
+    DATABASE.get_or_init(|| {
+        crate::profile_scope!("load_system_fonts");
+        let mut db = fontdb::Database::new();
+        db.load_system_fonts();
+        db
+    })
+}
+
+fn find_face<'db>(
+    db: &'db fontdb::Database,
+    settings: &SystemFontFallback,
+    chr: char,
+) -> Option<&'db fontdb::FaceInfo> {
+    let has_glyph = |face: &fontdb::FaceInfo| {
+        db.with_face_data(face.id, |data, index| {
+            use ab_glyph::Font as _;
+            ab_glyph::FontRef::try_from_slice_and_index(data, index).is_ok_and(|font| {
+                // Fonts with only bitmaps or color layers for the glyph can't be rendered:
+                let glyph_id = font.glyph_id(chr);
+                glyph_id.0 != 0 && font.outline(glyph_id).is_some()
+            })
+        })
+        .unwrap_or(false)
+    };
+
+    let preferred_families = settings
+        .preferred_families
+        .iter()
+        .map(String::as_str)
+        .chain(common_families(chr).iter().copied());
+    for family in preferred_families {
+        let query = fontdb::Query {
+            families: &[fontdb::Family::Name(family)],
+            ..Default::default()
+        };
+        if let Some(face) = db.query(&query).and_then(|id| db.face(id)) {
+            if has_glyph(face) {
+                return Some(face);
+            }
+        }
+    }
+
+    db.faces()
+        .filter(|face| face.style == fontdb::Style::Normal && face.weight == fontdb::Weight::NORMAL)
+        .find(|face| has_glyph(face))
+}
+
+/// Fonts that cover the script of `chr` well, and that come with common operating systems.
+fn common_families(chr: char) -> &'static [&'static str] {
+    match chr {
+        // Hiragana and Katakana:
+        '\u{3040}'..='\u{30FF}' | '\u{31F0}'..='\u{31FF}' => &[
+            "Noto Sans CJK JP",
+            "Hiragino Sans",
+            "Yu Gothic UI",
+            "Meiryo",
+        ],
+
+        // Hangul:
+        '\u{1100}'..='\u{11FF}' | '\u{3130}'..='\u{318F}' | '\u{AC00}'..='\u{D7AF}' => {
+            &["Noto Sans CJK KR", "Apple SD Gothic Neo", "Malgun Gothic"]
+        }
+
+        // CJK ideographs, punctuation and fullwidth forms:
+        '\u{2E80}'..='\u{2FDF}'
+        | '\u{3000}'..='\u{303F}'
+        | '\u{3400}'..='\u{4DBF}'
+        | '\u{4E00}'..='\u{9FFF}'
+        | '\u{F900}'..='\u{FAFF}'
+        | '\u{FF00}'..='\u{FFEF}'
+        | '\u{20000}'..='\u{2FA1F}' => &[
+            "Noto Sans CJK SC",
+            "PingFang SC",
+            "Microsoft YaHei UI",
+            "Source Han Sans SC",
+            "WenQuanYi Micro Hei",
+        ],
+
+        // Emojis and symbols (color-only emoji fonts are skipped, as we can't render them):
+        '\u{2190}'..='\u{2BFF}' | '\u{1F000}'..='\u{1FAFF}' => {
+            &["Noto Emoji", "Segoe UI Emoji", "Segoe UI Symbol", "Symbola"]
+        }
+
+        _ => &[],
+    }
+}
@@ -5,7 +5,7 @@ use emath::{pos2, vec2, Align, NumExt, Pos2, Rect, Vec2};
 
 use crate::{stroke::PathStroke, text::font::Font, Color32, Mesh, Stroke, Vertex};
 
-use super::{FontsImpl, Galley, Glyph, LayoutJob, LayoutSection, Row, RowVisuals};
+use super::{FontsImpl, Galley, Glyph, LayoutJob, LayoutSection, Row, RowVisuals, TextLocale};
 
 // ----------------------------------------------------------------------------
 
@@ -13,12 +13,18 @@ use super::{FontsImpl, Galley, Glyph, LayoutJob, LayoutSection, Row, RowVisuals}
 #[derive(Clone, Copy)]
 struct PointScale {
     pub pixels_per_point: f32,
+
+    /// Round the x coordinates of glyphs to whole pixels?
+    pub pixel_snapping: bool,
 }
 
 impl PointScale {
     #[inline(always)]
-    pub fn new(pixels_per_point: f32) -> Self {
-        Self { pixels_per_point }
+    pub fn new(pixels_per_point: f32, pixel_snapping: bool) -> Self {
+        Self {
+            pixels_per_point,
+            pixel_snapping,
+        }
     }
 
     #[inline(always)]
@@ -35,6 +41,16 @@ impl PointScale {
     pub fn floor_to_pixel(&self, point: f32) -> f32 {
         (point * self.pixels_per_point).floor() / self.pixels_per_point
     }
+
+    /// Round the x coordinate of a glyph, if [`Self::pixel_snapping`] is on.
+    #[inline(always)]
+    pub fn round_glyph_x(&self, x: f32) -> f32 {
+        if self.pixel_snapping {
+            self.round_to_pixel(x)
+        } else {
+            x
+        }
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -52,6 +68,10 @@ struct Paragraph {
 
     /// In case of an empty paragraph ("\n"), use this as height.
     pub empty_paragraph_height: f32,
+
+    /// The bidi embedding level of the paragraph: `1` if it is right-to-left.
+    #[cfg(feature = "shaping")]
+    pub bidi_level: u8,
 }
 
 impl Paragraph {
@@ -61,6 +81,8 @@ impl Paragraph {
             section_index_at_start,
             glyphs: vec![],
             empty_paragraph_height: 0.0,
+            #[cfg(feature = "shaping")]
+            bidi_level: 0,
         }
     }
 }
@@ -91,7 +113,14 @@ pub fn layout(fonts: &mut FontsImpl, job: Arc<LayoutJob>) -> Galley {
         layout_section(fonts, &job, section_index as u32, section, &mut paragraphs);
     }
 
-    let point_scale = PointScale::new(fonts.pixels_per_point());
+    #[cfg(feature = "shaping")]
+    if job.shaping {
+        for paragraph in &mut paragraphs {
+            paragraph.bidi_level = super::bidi::resolve_levels(&mut paragraph.glyphs);
+        }
+    }
+
+    let point_scale = PointScale::new(fonts.pixels_per_point(), fonts.pixel_snapping());
 
     let mut elided = false;
     let mut rows = rows_from_paragraphs(paragraphs, &job, &mut elided);
@@ -121,6 +150,14 @@ pub fn layout(fonts: &mut FontsImpl, job: Arc<LayoutJob>) -> Galley {
         }
     }
 
+    #[cfg(feature = "shaping")]
+    if job.shaping {
+        // Done after alignment and justification, which need the glyphs in logical order:
+        for row in &mut rows {
+            super::bidi::reorder_row(row);
+        }
+    }
+
     // Calculate the Y positions and tessellate the text:
     galley_from_rows(point_scale, job, rows, elided)
 }
@@ -144,6 +181,11 @@ fn layout_section(
         .line_height
         .unwrap_or_else(|| font.row_height());
     let extra_letter_spacing = section.format.extra_letter_spacing;
+    let mixed_script_spacing = if job.locale.spaces_mixed_scripts() {
+        0.25 * format.font_id.size
+    } else {
+        0.0
+    };
 
     let mut paragraph = out_paragraphs.last_mut().unwrap();
     if paragraph.glyphs.is_empty() {
@@ -154,37 +196,83 @@ fn layout_section(
 
     let mut last_glyph_id = None;
 
+    #[cfg(feature = "shaping")]
+    let mut shaped = if job.shaping {
+        font.shape(&job.text[byte_range.clone()])
+    } else {
+        vec![]
+    }
+    .into_iter();
+
     for chr in job.text[byte_range.clone()].chars() {
+        #[cfg(feature = "shaping")]
+        let shaped_glyph = shaped.next().flatten();
+
         if job.break_on_newline && chr == '\n' {
             out_paragraphs.push(Paragraph::from_section_index(section_index));
             paragraph = out_paragraphs.last_mut().unwrap();
             paragraph.empty_paragraph_height = line_height; // TODO(emilk): replace this hack with actually including `\n` in the glyphs?
-        } else {
-            let (font_impl, glyph_info) = font.font_impl_and_glyph_info(chr);
-            if let Some(font_impl) = font_impl {
-                if let Some(last_glyph_id) = last_glyph_id {
-                    paragraph.cursor_x += font_impl.pair_kerning(last_glyph_id, glyph_info.id);
-                    paragraph.cursor_x += extra_letter_spacing;
-                }
-            }
+            continue;
+        }
 
+        if 0.0 < mixed_script_spacing
+            && paragraph
+                .glyphs
+                .last()
+                .is_some_and(|prev| is_mixed_script_boundary(prev.chr, chr))
+        {
+            paragraph.cursor_x += mixed_script_spacing;
+        }
+
+        #[cfg(feature = "shaping")]
+        if let Some((font_impl, glyph_info)) = shaped_glyph {
+            // Kerning is already part of the shaped advance widths,
+            // and extra letter spacing would break up joined scripts.
             paragraph.glyphs.push(Glyph {
                 chr,
                 pos: pos2(paragraph.cursor_x, f32::NAN),
                 advance_width: glyph_info.advance_width,
                 line_height,
-                font_impl_height: font_impl.map_or(0.0, |f| f.row_height()),
-                font_impl_ascent: font_impl.map_or(0.0, |f| f.ascent()),
+                font_impl_height: font_impl.row_height(),
+                font_impl_ascent: font_impl.ascent(),
                 font_height: font.row_height(),
                 font_ascent: font.ascent(),
                 uv_rect: glyph_info.uv_rect,
                 section_index,
+                bidi_level: 0,
             });
 
             paragraph.cursor_x += glyph_info.advance_width;
             paragraph.cursor_x = font.round_to_pixel(paragraph.cursor_x);
-            last_glyph_id = Some(glyph_info.id);
+            last_glyph_id = None; // Don't kern against a shaped glyph
+            continue;
         }
+
+        let (font_impl, glyph_info) = font.font_impl_and_glyph_info(chr);
+        if let Some(font_impl) = font_impl {
+            if let Some(last_glyph_id) = last_glyph_id {
+                paragraph.cursor_x += font_impl.pair_kerning(last_glyph_id, glyph_info.id);
+                paragraph.cursor_x += extra_letter_spacing;
+            }
+        }
+
+        paragraph.glyphs.push(Glyph {
+            chr,
+            pos: pos2(paragraph.cursor_x, f32::NAN),
+            advance_width: glyph_info.advance_width,
+            line_height,
+            font_impl_height: font_impl.map_or(0.0, |f| f.row_height()),
+            font_impl_ascent: font_impl.map_or(0.0, |f| f.ascent()),
+            font_height: font.row_height(),
+            font_ascent: font.ascent(),
+            uv_rect: glyph_info.uv_rect,
+            section_index,
+            bidi_level: 0,
+        });
+
+        paragraph.cursor_x += glyph_info.advance_width;
+        paragraph.cursor_x = font.round_to_pixel(paragraph.cursor_x);
+        last_glyph_id = Some(glyph_info.id);
     }
 }
 
@@ -211,6 +299,9 @@ fn rows_from_paragraphs(
 
         let is_last_paragraph = (i + 1) == num_paragraphs;
 
+        #[cfg(feature = "shaping")]
+        let (first_row_of_paragraph, paragraph_bidi_level) = (rows.len(), paragraph.bidi_level);
+
         if paragraph.glyphs.is_empty() {
             rows.push(Row {
                 section_index_at_start: paragraph.section_index_at_start,
@@ -239,6 +330,13 @@ fn rows_from_paragraphs(
                 rows.last_mut().unwrap().ends_with_newline = !is_last_paragraph;
             }
         }
+
+        #[cfg(feature = "shaping")]
+        if job.shaping {
+            for row in &mut rows[first_row_of_paragraph..] {
+                super::bidi::reset_trailing_whitespace(row, paragraph_bidi_level);
+            }
+        }
     }
 
     rows
@@ -311,7 +409,7 @@ fn line_break(paragraph: &Paragraph, job: &LayoutJob, out_rows: &mut Vec<Row>, e
             }
         }
 
-        row_break_candidates.add(i, &paragraph.glyphs[i..]);
+        row_break_candidates.add(i, &paragraph.glyphs[i..], job.locale);
     }
 
     if row_start_idx < paragraph.glyphs.len() {
@@ -403,6 +501,7 @@ fn replace_last_glyph_with_overflow_character(
             font_ascent: font.ascent(),
             uv_rect: replacement_glyph_info.uv_rect,
             section_index,
+            bidi_level: last_glyph.bidi_level,
         });
     } else {
         let section_index = row.section_index_at_start;
@@ -425,6 +524,7 @@ fn replace_last_glyph_with_overflow_character(
             font_ascent: font.ascent(),
             uv_rect: replacement_glyph_info.uv_rect,
             section_index,
+            bidi_level: 0,
         });
     }
 
@@ -576,7 +676,7 @@ fn halign_and_justify_row(
 
     for glyph in &mut row.glyphs {
         glyph.pos.x += translate_x;
-        glyph.pos.x = point_scale.round_to_pixel(glyph.pos.x);
+        glyph.pos.x = point_scale.round_glyph_x(glyph.pos.x);
         translate_x += extra_x_per_glyph;
         if glyph.chr.is_whitespace() {
             translate_x += extra_x_per_space;
@@ -715,9 +815,14 @@ fn tessellate_row(
 
     let glyph_index_start = mesh.indices.len();
     let glyph_vertex_start = mesh.vertices.len();
-    tessellate_glyphs(point_scale, job, row, &mut mesh);
+    tessellate_glyphs(point_scale, job, row, false, false, &mut mesh);
+    let distance_field_vertex_start = mesh.vertices.len();
+    tessellate_glyphs(point_scale, job, row, false, true, &mut mesh);
     let glyph_vertex_end = mesh.vertices.len();
 
+    // Color glyphs (emojis) go after `glyph_vertex_range`, so that they are not tinted by e.g. `TextShape::override_text_color`:
+    tessellate_glyphs(point_scale, job, row, true, false, &mut mesh);
+
     if format_summary.any_underline {
         add_row_hline(point_scale, row, &mut mesh, |glyph| {
             let format = &job.sections[glyph.section_index as usize].format;
@@ -743,6 +848,7 @@ fn tessellate_row(
         mesh_bounds,
         glyph_index_start,
         glyph_vertex_range: glyph_vertex_start..glyph_vertex_end,
+        distance_field_vertex_range: distance_field_vertex_start..glyph_vertex_end,
     }
 }
 
@@ -791,12 +897,23 @@ fn add_row_backgrounds(job: &LayoutJob, row: &Row, mesh: &mut Mesh) {
     end_run(run_start.take(), last_rect.right());
 }
 
-fn tessellate_glyphs(point_scale: PointScale, job: &LayoutJob, row: &Row, mesh: &mut Mesh) {
+/// Add either the normal glyphs, or the `UvRect::colored` ones (which are painted white, so that they keep their colors).
+fn tessellate_glyphs(
+    point_scale: PointScale,
+    job: &LayoutJob,
+    row: &Row,
+    colored: bool,
+    distance_field: bool,
+    mesh: &mut Mesh,
+) {
     for glyph in &row.glyphs {
         let uv_rect = glyph.uv_rect;
-        if !uv_rect.is_nothing() {
+        if !uv_rect.is_nothing()
+            && uv_rect.colored == colored
+            && uv_rect.distance_field == distance_field
+        {
             let mut left_top = glyph.pos + uv_rect.offset;
-            left_top.x = point_scale.round_to_pixel(left_top.x);
+            left_top.x = point_scale.round_glyph_x(left_top.x);
             left_top.y = point_scale.round_to_pixel(left_top.y);
 
             let rect = Rect::from_min_max(left_top, left_top + uv_rect.size);
@@ -807,7 +924,11 @@ fn tessellate_glyphs(point_scale: PointScale, job: &LayoutJob, row: &Row, mesh:
 
             let format = &job.sections[glyph.section_index as usize].format;
 
-            let color = format.color;
+            let color = if colored {
+                Color32::WHITE
+            } else {
+                format.color
+            };
 
             if format.italics {
                 let idx = mesh.vertices.len() as u32;
@@ -936,18 +1057,25 @@ struct RowBreakCandidates {
 }
 
 impl RowBreakCandidates {
-    fn add(&mut self, index: usize, glyphs: &[Glyph]) {
+    fn add(&mut self, index: usize, glyphs: &[Glyph], locale: TextLocale) {
         let chr = glyphs[0].chr;
+        let next = glyphs.get(1).map(|glyph| glyph.chr);
         const NON_BREAKING_SPACE: char = '\u{A0}';
         if chr.is_whitespace() && chr != NON_BREAKING_SPACE {
             self.space = Some(index);
-        } else if is_cjk(chr) && (glyphs.len() == 1 || is_cjk_break_allowed(glyphs[1].chr)) {
+        } else if locale == TextLocale::Korean {
+            // Keep words together
+        } else if locale != TextLocale::Generic {
+            if kinsoku_break_allowed(chr, next, locale) {
+                self.cjk = Some(index);
+            }
+        } else if is_cjk(chr) && next.map_or(true, is_cjk_break_allowed) {
             self.cjk = Some(index);
         } else if chr == '-' {
             self.dash = Some(index);
         } else if chr.is_ascii_punctuation() {
             self.punctuation = Some(index);
-        } else if glyphs.len() > 1 && is_cjk(glyphs[1].chr) {
+        } else if next.is_some_and(is_cjk) {
             self.pre_cjk = Some(index);
         }
         self.any = Some(index);
@@ -1034,6 +1162,58 @@ fn is_cjk_break_allowed(c: char) -> bool {
     !")]｝〕〉》」』】〙〗〟'\"｠»ヽヾーァィゥェォッャュョヮヵヶぁぃぅぇぉっゃゅょゎゕゖㇰㇱㇲㇳㇴㇵㇶㇷㇸㇹㇺㇻㇼㇽㇾㇿ々〻‐゠–〜?!‼⁇⁈⁉・、:;,。.".contains(c)
 }
 
+/// Is a row break allowed after `chr` (and before `next`) by the rules of `locale`?
+///
+/// See <https://www.w3.org/TR/jlreq/#line_breaking_rules> and <https://www.w3.org/TR/clreq/#prohibition_rules_for_line_start_end>.
+fn kinsoku_break_allowed(chr: char, next: Option<char>, locale: TextLocale) -> bool {
+    let Some(next) = next else {
+        return is_east_asian(chr) && !is_opening_punctuation(chr);
+    };
+    (is_east_asian(chr) || is_east_asian(next))
+        && !is_opening_punctuation(chr)
+        && !must_not_start_row(next, locale)
+        && !(chr == next && is_inseparable(chr))
+}
+
+/// CJK characters and punctuation.
+#[inline]
+fn is_east_asian(c: char) -> bool {
+    is_cjk(c)
+        || ('\u{3000}' <= c && c <= '\u{303F}') // CJK Symbols and Punctuation block
+        || ('\u{FF01}' <= c && c <= '\u{FF60}') // Fullwidth forms
+        || "…‥—―・".contains(c)
+}
+
+/// Opening brackets and quotes must not end a row.
+#[inline]
+fn is_opening_punctuation(c: char) -> bool {
+    "([{‘“«〈《「『【〔〖〘〚〝（［｛｟｢".contains(c)
+}
+
+/// Closing brackets, punctuation and the like must not start a row.
+#[inline]
+fn must_not_start_row(c: char, locale: TextLocale) -> bool {
+    let small_kana =
+        "ァィゥェォッャュョヮヵヶぁぃぅぇぉっゃゅょゎゕゖㇰㇱㇲㇳㇴㇵㇶㇷㇸㇹㇺㇻㇼㇽㇾㇿ";
+    ")]}’”»〉》」』】〕〗〙〛〞〟）］｝｠｣、。，．：；？！,.:;?!‼⁇⁈⁉・ー々〻ゝゞヽヾ゠〜～%％‰℃"
+        .contains(c)
+        || (locale == TextLocale::Japanese && small_kana.contains(c))
+}
+
+/// Two of these in a row must not be broken apart, e.g. `……`.
+#[inline]
+fn is_inseparable(c: char) -> bool {
+    "…‥—―".contains(c)
+}
+
+/// Between East Asian characters and Latin letters or digits,
+/// where [`TextLocale::spaces_mixed_scripts`] adds some space.
+#[inline]
+fn is_mixed_script_boundary(a: char, b: char) -> bool {
+    let is_latin = |c: char| c.is_ascii_alphanumeric();
+    (is_cjk(a) && is_latin(b)) || (is_latin(a) && is_cjk(b))
+}
+
 // ----------------------------------------------------------------------------
 
 #[cfg(test)]
@@ -1096,6 +1276,68 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_text_width_matches_layout() {
+        for pixels_per_point in [1.0, 1.5, 2.0] {
+            let fonts = Fonts::new(pixels_per_point, 1024, FontDefinitions::default());
+            for font_id in [FontId::proportional(13.0), FontId::monospace(11.5)] {
+                for text in [
+                    "",
+                    "Hello World!",
+                    "AVAWAY Tý",
+                    "short\na longer line\n",
+                    "1.25e-7",
+                ] {
+                    let galley =
+                        fonts.layout_no_wrap(text.to_owned(), font_id.clone(), Color32::WHITE);
+                    assert_eq!(
+                        fonts.text_width(&font_id, text),
+                        galley.size().x,
+                        "{text:?} in {font_id:?} at {pixels_per_point} pixels per point"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_deferred_glyphs() {
+        let text = "日本語とEnglishの混在した文章";
+        let font_id = FontId::proportional(14.0);
+        let complete = Fonts::new(1.0, 1024, FontDefinitions::default()).layout_no_wrap(
+            text.to_owned(),
+            font_id.clone(),
+            Color32::WHITE,
+        );
+
+        let fonts = Fonts::new(1.0, 1024, FontDefinitions::default());
+        fonts.set_max_new_glyphs_per_pass(Some(4));
+        let mut passes = 0;
+        loop {
+            fonts.begin_pass(1.0, 1024);
+            let galley = fonts.layout_no_wrap(text.to_owned(), font_id.clone(), Color32::WHITE);
+            assert_eq!(
+                galley.size(),
+                complete.size(),
+                "Deferred glyphs keep their size"
+            );
+            passes += 1;
+            if !fonts.has_deferred_glyphs() {
+                let visible = |galley: &Galley| {
+                    galley.rows[0]
+                        .glyphs
+                        .iter()
+                        .filter(|glyph| !glyph.uv_rect.is_nothing())
+                        .count()
+                };
+                assert_eq!(visible(&galley), visible(&complete));
+                break;
+            }
+            assert!(passes < 100, "Glyphs are never done");
+        }
+        assert!(1 < passes);
+    }
+
     #[test]
     fn test_cjk() {
         let mut fonts = FontsImpl::new(1.0, 1024, FontDefinitions::default());
@@ -1126,6 +1368,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_kinsoku() {
+        let mut fonts = FontsImpl::new(1.0, 1024, FontDefinitions::default());
+        for max_width in [60.0, 75.0, 90.0, 105.0] {
+            let mut layout_job = LayoutJob::single_section(
+                "これは「テスト」です。ちょっと、待って……ください。".into(),
+                TextFormat::default(),
+            );
+            layout_job.locale = TextLocale::Japanese;
+            layout_job.wrap.max_width = max_width;
+            let galley = layout(&mut fonts, layout_job.into());
+            assert!(1 < galley.rows.len());
+            for row in &galley.rows {
+                let text = row.text();
+                assert!(
+                    !text.starts_with(['。', '、', '」', 'ょ', 'っ']),
+                    "{text:?}"
+                );
+                assert!(!text.ends_with('「'), "{text:?}");
+            }
+        }
+    }
+
     #[test]
     fn test_truncate_width() {
         let mut fonts = FontsImpl::new(1.0, 1024, FontDefinitions::default());
@@ -1142,4 +1407,40 @@ mod tests {
         let row = &galley.rows[0];
         assert_eq!(row.rect.max.x, row.glyphs.last().unwrap().max_x());
     }
+
+    #[cfg(feature = "shaping")]
+    #[test]
+    fn test_bidi_reordering() {
+        let mut fonts = FontsImpl::new(1.0, 1024, FontDefinitions::default());
+        let layout_job =
+            LayoutJob::single_section("ab \u{5D0}\u{5D1}\u{5D2}".into(), TextFormat::default());
+        let galley = layout(&mut fonts, layout_job.into());
+        let row = &galley.rows[0];
+
+        // Logical order is kept:
+        assert_eq!(row.text(), "ab \u{5D0}\u{5D1}\u{5D2}");
+        assert!(row.has_rtl());
+
+        // …but the Hebrew is laid out right-to-left:
+        let x: Vec<f32> = row.glyphs.iter().map(|glyph| glyph.pos.x).collect();
+        assert!(x[0] < x[1] && x[1] < x[2]);
+        assert!(x[2] < x[5] && x[5] < x[4] && x[4] < x[3]);
+
+        // A cursor before the first Hebrew letter is at its right edge:
+        assert_eq!(row.x_offset(3), row.glyphs[3].max_x());
+        assert_eq!(row.x_offset(6), row.glyphs[5].pos.x);
+        assert_eq!(row.char_at(row.glyphs[3].max_x()), 3);
+
+        // Moving right on screen moves backwards in the Hebrew text:
+        let cursor = galley.from_ccursor(cursor::CCursor::new(5));
+        assert_eq!(galley.cursor_right_visually(&cursor).ccursor.index, 4);
+        assert_eq!(galley.cursor_left_visually(&cursor).ccursor.index, 6);
+
+        // Turning it off lays out everything left-to-right:
+        let mut layout_job =
+            LayoutJob::single_section("ab \u{5D0}\u{5D1}".into(), TextFormat::default());
+        layout_job.shaping = false;
+        let galley = layout(&mut fonts, layout_job.into());
+        assert!(!galley.rows[0].has_rtl());
+    }
 }
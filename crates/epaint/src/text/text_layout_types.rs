@@ -9,7 +9,7 @@ use super::{
     font::UvRect,
 };
 use crate::{Color32, FontId, Mesh, Stroke};
-use emath::{pos2, vec2, Align, NumExt, OrderedFloat, Pos2, Rect, Vec2};
+use emath::{pos2, vec2, Align, NumExt, OrderedFloat, Pos2, Rangef, Rect, Vec2};
 
 /// Describes the task of laying out text.
 ///
@@ -81,6 +81,20 @@ pub struct LayoutJob {
     /// Rounding to the closest ui point (not pixel!) allows the rest of the
     /// layout code to run on perfect integers, avoiding rounding errors.
     pub round_output_size_to_nearest_ui_point: bool,
+
+    /// Reorder bidirectional text (e.g. Hebrew or Arabic mixed with English)
+    /// and shape complex scripts (e.g. Arabic joining) before layout.
+    ///
+    /// Only has an effect if the `shaping` feature of `epaint` is enabled.
+    /// Turn it off to lay out each `char` on its own, left-to-right, which is faster.
+    ///
+    /// Default: `true`.
+    pub shaping: bool,
+
+    /// The language of the text, which decides how rows are broken, e.g. of Japanese text.
+    ///
+    /// Default: [`TextLocale::Generic`].
+    pub locale: TextLocale,
 }
 
 impl Default for LayoutJob {
@@ -95,6 +109,8 @@ impl Default for LayoutJob {
             halign: Align::LEFT,
             justify: false,
             round_output_size_to_nearest_ui_point: true,
+            shaping: true,
+            locale: TextLocale::Generic,
         }
     }
 }
@@ -201,6 +217,8 @@ impl std::hash::Hash for LayoutJob {
             halign,
             justify,
             round_output_size_to_nearest_ui_point,
+            shaping,
+            locale,
         } = self;
 
         text.hash(state);
@@ -211,6 +229,8 @@ impl std::hash::Hash for LayoutJob {
         halign.hash(state);
         justify.hash(state);
         round_output_size_to_nearest_ui_point.hash(state);
+        shaping.hash(state);
+        locale.hash(state);
     }
 }
 
@@ -347,6 +367,46 @@ impl TextFormat {
 
 // ----------------------------------------------------------------------------
 
+/// The language of a [`LayoutJob`], which decides how its text is broken into rows.
+///
+/// East Asian text has no spaces between words, so rows can break between most characters,
+/// but some characters must not start or end a row (_kinsoku shori_ in Japanese),
+/// e.g. a row must not start with `。` or `」`, nor end with `「`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum TextLocale {
+    /// Break at spaces, and between CJK characters unless the next one is closing punctuation.
+    ///
+    /// Good enough for most text, including a few CJK words.
+    #[default]
+    Generic,
+
+    /// Japanese: strict kinsoku shori, so that e.g. small kana (`ッ`, `ょ`) and `ー`
+    /// never start a row, opening brackets never end one, and `……` and `――` are kept together.
+    ///
+    /// A quarter of the font size is added between Japanese and Latin letters or digits.
+    Japanese,
+
+    /// Chinese: closing punctuation never starts a row, opening brackets never end one,
+    /// and `……` and `——` are kept together.
+    ///
+    /// A quarter of the font size is added between Chinese and Latin letters or digits.
+    Chinese,
+
+    /// Korean: words are kept together, so rows only break at spaces (like CSS `word-break: keep-all`).
+    Korean,
+}
+
+impl TextLocale {
+    /// Add some space between East Asian and Latin text?
+    #[inline]
+    pub fn spaces_mixed_scripts(self) -> bool {
+        matches!(self, Self::Japanese | Self::Chinese)
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 /// How to wrap and elide text.
 ///
 /// This enum is used in high-level APIs where providing a [`TextWrapping`] is too verbose.
@@ -584,7 +644,14 @@ pub struct RowVisuals {
     /// The range of vertices in the mesh that contain glyphs (as opposed to background, underlines, strikethorugh, etc).
     ///
     /// The glyph vertices comes after backgrounds (if any), but before any underlines and strikethrough.
+    /// Glyphs with their own colors (color emojis) are not included, and come right after this range.
     pub glyph_vertex_range: Range<usize>,
+
+    /// The range of vertices in the mesh for glyphs that are distance fields
+    /// (see [`super::GlyphRendering::DistanceField`]), at the end of [`Self::glyph_vertex_range`].
+    ///
+    /// The tessellator paints these triangles in a separate [`Mesh`] with [`Mesh::distance_field`] set.
+    pub distance_field_vertex_range: Range<usize>,
 }
 
 impl Default for RowVisuals {
@@ -594,6 +661,7 @@ impl Default for RowVisuals {
             mesh_bounds: Rect::NOTHING,
             glyph_index_start: 0,
             glyph_vertex_range: 0..0,
+            distance_field_vertex_range: 0..0,
         }
     }
 }
@@ -634,6 +702,13 @@ pub struct Glyph {
 
     /// Index into [`LayoutJob::sections`]. Decides color etc.
     pub section_index: u32,
+
+    /// The resolved bidirectional embedding level of this character.
+    ///
+    /// Even levels are left-to-right, odd levels are right-to-left.
+    /// This is always `0` unless [`LayoutJob::shaping`] is on
+    /// and the `shaping` feature is enabled.
+    pub bidi_level: u8,
 }
 
 impl Glyph {
@@ -647,6 +722,24 @@ impl Glyph {
         self.pos.x + self.advance_width
     }
 
+    /// Is this character part of a right-to-left run?
+    #[inline]
+    pub fn is_rtl(&self) -> bool {
+        self.bidi_level % 2 == 1
+    }
+
+    /// The x coordinate of a cursor placed just before this character (in logical order).
+    ///
+    /// That is the left edge for left-to-right text, and the right edge for right-to-left text.
+    #[inline]
+    pub fn leading_x(&self) -> f32 {
+        if self.is_rtl() {
+            self.max_x()
+        } else {
+            self.pos.x
+        }
+    }
+
     /// Same y range for all characters with the same [`TextFormat`].
     #[inline]
     pub fn logical_rect(&self) -> Rect {
@@ -689,9 +782,28 @@ impl Row {
         self.rect.height()
     }
 
+    /// Does this row contain any right-to-left text?
+    ///
+    /// If so, the glyphs are not laid out in increasing x order.
+    #[inline]
+    pub fn has_rtl(&self) -> bool {
+        self.glyphs.iter().any(|glyph| glyph.bidi_level > 0)
+    }
+
     /// Closest char at the desired x coordinate.
     /// Returns something in the range `[0, char_count_excluding_newline()]`.
     pub fn char_at(&self, desired_x: f32) -> usize {
+        if self.has_rtl() {
+            // Glyphs are not sorted by x, so pick the closest cursor position:
+            return (0..=self.char_count_excluding_newline())
+                .min_by(|&a, &b| {
+                    let a = (self.x_offset(a) - desired_x).abs();
+                    let b = (self.x_offset(b) - desired_x).abs();
+                    a.total_cmp(&b)
+                })
+                .unwrap_or(0);
+        }
+
         for (i, glyph) in self.glyphs.iter().enumerate() {
             if desired_x < glyph.logical_rect().center().x {
                 return i;
@@ -700,13 +812,47 @@ impl Row {
         self.char_count_excluding_newline()
     }
 
+    /// The x coordinate of a cursor placed before the given column.
     pub fn x_offset(&self, column: usize) -> f32 {
         if let Some(glyph) = self.glyphs.get(column) {
-            glyph.pos.x
+            glyph.leading_x()
+        } else if let Some(last) = self.glyphs.last().filter(|last| last.is_rtl()) {
+            // After the last character of a right-to-left run:
+            last.pos.x
         } else {
             self.rect.right()
         }
     }
+
+    /// The x ranges covered by the characters in the given (logical) column range.
+    ///
+    /// For left-to-right text this is a single range,
+    /// but mixed left-to-right and right-to-left text can result in several disjoint ranges.
+    pub fn x_ranges(&self, columns: Range<usize>) -> Vec<Rangef> {
+        if !self.has_rtl() {
+            return vec![Rangef::new(
+                self.x_offset(columns.start),
+                self.x_offset(columns.end),
+            )];
+        }
+
+        let end = columns.end.min(self.glyphs.len());
+        let mut ranges: Vec<Rangef> = self.glyphs[columns.start.min(end)..end]
+            .iter()
+            .map(|glyph| Rangef::new(glyph.pos.x, glyph.max_x()))
+            .collect();
+        ranges.sort_by(|a, b| a.min.total_cmp(&b.min));
+
+        // Merge touching ranges:
+        let mut merged: Vec<Rangef> = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last) if range.min <= last.max + 0.5 => last.max = last.max.max(range.max),
+                _ => merged.push(range),
+            }
+        }
+        merged
+    }
 }
 
 impl Galley {
@@ -1115,6 +1261,57 @@ impl Galley {
         self.from_ccursor(ccursor + 1)
     }
 
+    /// Move the cursor one character to the left on screen.
+    ///
+    /// For left-to-right text this is the same as [`Self::cursor_left_one_character`],
+    /// but in right-to-left text it moves the cursor forward in the text.
+    pub fn cursor_left_visually(&self, cursor: &Cursor) -> Cursor {
+        self.cursor_move_visually(cursor, -1.0)
+            .unwrap_or_else(|| self.cursor_left_one_character(cursor))
+    }
+
+    /// Move the cursor one character to the right on screen.
+    ///
+    /// For left-to-right text this is the same as [`Self::cursor_right_one_character`],
+    /// but in right-to-left text it moves the cursor backward in the text.
+    pub fn cursor_right_visually(&self, cursor: &Cursor) -> Cursor {
+        self.cursor_move_visually(cursor, 1.0)
+            .unwrap_or_else(|| self.cursor_right_one_character(cursor))
+    }
+
+    /// Find the closest cursor position in the given direction (`-1.0` or `1.0`) on the same row.
+    ///
+    /// Returns `None` for rows without right-to-left text, where logical movement is correct.
+    /// When there is nothing more in that direction, we leave the row in logical order:
+    /// forwards if the row ends on that side, otherwise backwards.
+    fn cursor_move_visually(&self, cursor: &Cursor, direction: f32) -> Option<Cursor> {
+        let row = self.rows.get(cursor.rcursor.row)?;
+        if !row.has_rtl() {
+            return None;
+        }
+
+        let x = row.x_offset(cursor.rcursor.column);
+        let closest = (0..=row.char_count_excluding_newline())
+            .map(|column| (column, (row.x_offset(column) - x) * direction))
+            .filter(|(_, distance)| 0.5 < *distance)
+            .min_by(|(_, a), (_, b)| a.total_cmp(b));
+
+        if let Some((column, _)) = closest {
+            Some(self.from_rcursor(RCursor {
+                row: cursor.rcursor.row,
+                column,
+            }))
+        } else {
+            let end_x = row.x_offset(row.char_count_excluding_newline());
+            let row_ends_this_way = (end_x - x) * direction >= 0.0;
+            Some(if row_ends_this_way {
+                self.cursor_right_one_character(cursor)
+            } else {
+                self.cursor_left_one_character(cursor)
+            })
+        }
+    }
+
     pub fn cursor_up_one_row(&self, cursor: &Cursor) -> Cursor {
         if cursor.rcursor.row == 0 {
             Cursor::default()
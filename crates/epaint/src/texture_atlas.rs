@@ -72,6 +72,15 @@ pub struct TextureAtlas {
 
     /// pre-rasterized discs of radii `2^i`, where `i` is the index.
     discs: Vec<PrerasterizedDisc>,
+
+    /// See [`Self::set_max_new_glyphs_per_pass`].
+    max_new_glyphs_per_pass: Option<usize>,
+
+    /// How many glyphs have been added since [`Self::begin_pass`].
+    new_glyphs_this_pass: usize,
+
+    /// How many glyphs have been deferred since [`Self::begin_pass`].
+    num_deferred_glyphs: usize,
 }
 
 impl TextureAtlas {
@@ -84,6 +93,9 @@ impl TextureAtlas {
             row_height: 0,
             overflowed: false,
             discs: vec![], // will be filled in below
+            max_new_glyphs_per_pass: None,
+            new_glyphs_this_pass: 0,
+            num_deferred_glyphs: 0,
         };
 
         // Make the top left pixel fully white for `WHITE_UV`, i.e. painting something with solid color:
@@ -184,6 +196,53 @@ impl TextureAtlas {
         &self.image
     }
 
+    /// Limit how many new glyphs are rasterized into the atlas per pass. `None` means no limit, which is the default.
+    ///
+    /// When more new glyphs are needed than this, the rest are deferred:
+    /// text is still laid out with the right size, but the deferred glyphs are invisible
+    /// until they are rasterized in one of the next passes.
+    /// This spreads out the work of e.g. a change of zoom, or the first screen full of CJK text,
+    /// over several frames, instead of causing a hitch.
+    ///
+    /// Check [`Self::num_deferred_glyphs`] to know if another pass is needed.
+    pub fn set_max_new_glyphs_per_pass(&mut self, max_new_glyphs_per_pass: Option<usize>) {
+        self.max_new_glyphs_per_pass = max_new_glyphs_per_pass;
+    }
+
+    /// See [`Self::set_max_new_glyphs_per_pass`].
+    pub fn max_new_glyphs_per_pass(&self) -> Option<usize> {
+        self.max_new_glyphs_per_pass
+    }
+
+    /// Call at the start of each pass, to start over with the budget of [`Self::set_max_new_glyphs_per_pass`].
+    pub fn begin_pass(&mut self) {
+        self.new_glyphs_this_pass = 0;
+        self.num_deferred_glyphs = 0;
+    }
+
+    /// Is there room for another glyph in the budget of this pass?
+    ///
+    /// If not, the glyph is counted as deferred.
+    pub(crate) fn try_add_glyph(&mut self) -> bool {
+        if self
+            .max_new_glyphs_per_pass
+            .is_some_and(|max| max <= self.new_glyphs_this_pass)
+        {
+            self.num_deferred_glyphs += 1;
+            false
+        } else {
+            self.new_glyphs_this_pass += 1;
+            true
+        }
+    }
+
+    /// How many times a glyph was deferred since [`Self::begin_pass`], because of [`Self::set_max_new_glyphs_per_pass`].
+    ///
+    /// If this is non-zero, some text is missing glyphs, and you should run another pass soon.
+    pub fn num_deferred_glyphs(&self) -> usize {
+        self.num_deferred_glyphs
+    }
+
     /// Call to get the change to the image since last call.
     pub fn take_delta(&mut self) -> Option<ImageDelta> {
         let texture_options = Self::texture_options();
@@ -248,17 +307,101 @@ impl TextureAtlas {
 
         (pos, &mut self.image)
     }
+
+    /// How many texels have been allocated so far, including padding and unused space at the end of rows.
+    pub fn used_texels(&self) -> usize {
+        self.image.width() * (self.cursor.1 + self.row_height).min(self.max_height())
+    }
+
+    /// Pack the given regions of the atlas tightly together, and throw away everything else.
+    ///
+    /// Use this to reclaim the space of glyphs that are no longer in use,
+    /// instead of clearing the whole atlas and rasterizing everything again.
+    ///
+    /// Each region is given as `(min, size)` in texels.
+    /// Returns the new `min` of each region, in the same order.
+    /// The pre-rasterized discs are recreated, and may also move (see [`Self::prepared_discs`]).
+    ///
+    /// The whole atlas is marked as dirty, so [`Self::take_delta`] will return the full new image.
+    pub fn compact(&mut self, regions: &[([usize; 2], [usize; 2])]) -> Vec<[usize; 2]> {
+        let initial_height = 32; // Start small, and grow as needed.
+        let old = std::mem::replace(self, Self::new([self.image.width(), initial_height]));
+        self.max_new_glyphs_per_pass = old.max_new_glyphs_per_pass;
+        self.new_glyphs_this_pass = old.new_glyphs_this_pass;
+        self.num_deferred_glyphs = old.num_deferred_glyphs;
+
+        // Allocating the tallest regions first gives us fewer, fuller rows:
+        let mut order: Vec<usize> = (0..regions.len()).collect();
+        order.sort_by_key(|&i| std::cmp::Reverse(regions[i].1[1]));
+
+        let mut new_mins = vec![[0, 0]; regions.len()];
+        for i in order {
+            let ([old_x, old_y], [w, h]) = regions[i];
+            let (new_min, image) = self.allocate((w, h));
+            let (new_x, new_y) = new_min;
+            for y in 0..h {
+                for x in 0..w {
+                    let (old_pos, new_pos) = ((old_x + x, old_y + y), (new_x + x, new_y + y));
+                    image[new_pos] = old.image[old_pos];
+                    if let Some(color) = old.image.color(old_pos) {
+                        image.set_color(new_pos, color);
+                    }
+                }
+            }
+            new_mins[i] = new_min.into();
+        }
+        new_mins
+    }
 }
 
 fn resize_to_min_height(image: &mut FontImage, required_height: usize) -> bool {
-    while required_height >= image.height() {
-        image.size[1] *= 2; // double the height
+    let mut height = image.height();
+    while required_height >= height {
+        height *= 2; // double the height
     }
 
-    if image.width() * image.height() > image.pixels.len() {
-        image.pixels.resize(image.width() * image.height(), 0.0);
+    if image.width() * height > image.pixels.len() {
+        image.resize([image.width(), height]);
         true
     } else {
+        image.size[1] = height;
         false
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Color32;
+
+    #[test]
+    fn compact_keeps_regions() {
+        let mut atlas = TextureAtlas::new([1024, 32]);
+
+        let mut regions = vec![];
+        for i in 0..100 {
+            let (w, h) = (20 + i % 7, 10 + i % 11);
+            let (min, image) = atlas.allocate((w, h));
+            let (x, y) = min;
+            image[(x, y)] = i as f32;
+            image.set_color((x + 1, y), Color32::from_gray(i as u8));
+            regions.push((min.into(), [w, h]));
+        }
+        let used_before = atlas.used_texels();
+
+        // Keep every third region:
+        let kept: Vec<_> = regions.iter().copied().step_by(3).collect();
+        let new_mins = atlas.compact(&kept);
+
+        assert!(atlas.used_texels() < used_before);
+        assert_eq!(atlas.image()[(0, 0)], 1.0, "white pixel");
+        for (i, new_min) in new_mins.into_iter().enumerate() {
+            assert_eq!(atlas.image()[(new_min[0], new_min[1])], (3 * i) as f32);
+            assert_eq!(
+                atlas.image().color((new_min[0] + 1, new_min[1])),
+                Some(Color32::from_gray((3 * i) as u8)),
+                "colors move along"
+            );
+        }
+    }
+}
@@ -84,6 +84,22 @@ impl TextureHandle {
             .set(self.id, ImageDelta::partial(pos, image.into(), options));
     }
 
+    /// Change how the texture is sampled, e.g. switch between [`TextureOptions::NEAREST`]
+    /// and [`TextureOptions::LINEAR`] filtering.
+    ///
+    /// This is cheap: the pixels are not uploaded again.
+    pub fn set_options(&mut self, options: TextureOptions) {
+        self.tex_mngr.write().set_options(self.id, options);
+    }
+
+    /// How the texture is sampled, see [`Self::set_options`].
+    pub fn options(&self) -> TextureOptions {
+        self.tex_mngr
+            .read()
+            .meta(self.id)
+            .map_or_else(TextureOptions::default, |tex| tex.options)
+    }
+
     /// width x height
     pub fn size(&self) -> [usize; 2] {
         self.tex_mngr
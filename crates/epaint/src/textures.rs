@@ -61,12 +61,37 @@ impl TextureManager {
                 // since we update the whole image, we can discard all old enqueued deltas
                 self.delta.set.retain(|(x, _)| x != &id);
             }
+            // The new delta comes with its own options:
+            meta.options = delta.options;
+            self.delta.options.retain(|(x, _)| x != &id);
             self.delta.set.push((id, delta));
         } else {
             debug_assert!(false, "Tried setting texture {id:?} which is not allocated");
         }
     }
 
+    /// Change how an existing texture is sampled, without uploading its pixels again.
+    pub fn set_options(&mut self, id: TextureId, options: TextureOptions) {
+        if let Some(meta) = self.metas.get_mut(&id) {
+            if meta.options == options {
+                return;
+            }
+            meta.options = options;
+
+            // Pending uploads should use the new options too:
+            for (_, delta) in self.delta.set.iter_mut().filter(|(x, _)| x == &id) {
+                delta.options = options;
+            }
+            self.delta.options.retain(|(x, _)| x != &id);
+            self.delta.options.push((id, options));
+        } else {
+            debug_assert!(
+                false,
+                "Tried setting options of texture {id:?} which is not allocated"
+            );
+        }
+    }
+
     /// Free an existing texture.
     pub fn free(&mut self, id: TextureId) {
         if let std::collections::hash_map::Entry::Occupied(mut entry) = self.metas.entry(id) {
@@ -169,6 +194,14 @@ pub struct TextureOptions {
     ///
     /// - This may not be available on all backends (currently only `egui_glow`).
     pub mipmap_mode: Option<TextureFilter>,
+
+    /// The maximum level of anisotropic filtering, which keeps textures sharp
+    /// when seen at a steep angle, e.g. in a 3D scene.
+    ///
+    /// `1` (the default) means no anisotropic filtering.
+    ///
+    /// Only used when all filters are [`TextureFilter::Linear`], and only if the backend supports it.
+    pub anisotropy: u16,
 }
 
 impl TextureOptions {
@@ -178,6 +211,7 @@ impl TextureOptions {
         minification: TextureFilter::Linear,
         wrap_mode: TextureWrapMode::ClampToEdge,
         mipmap_mode: None,
+        anisotropy: 1,
     };
 
     /// Nearest magnification and minification.
@@ -186,6 +220,7 @@ impl TextureOptions {
         minification: TextureFilter::Nearest,
         wrap_mode: TextureWrapMode::ClampToEdge,
         mipmap_mode: None,
+        anisotropy: 1,
     };
 
     /// Linear magnification and minification, but with the texture repeated.
@@ -194,6 +229,7 @@ impl TextureOptions {
         minification: TextureFilter::Linear,
         wrap_mode: TextureWrapMode::Repeat,
         mipmap_mode: None,
+        anisotropy: 1,
     };
 
     /// Linear magnification and minification, but with the texture mirrored and repeated.
@@ -202,6 +238,7 @@ impl TextureOptions {
         minification: TextureFilter::Linear,
         wrap_mode: TextureWrapMode::MirroredRepeat,
         mipmap_mode: None,
+        anisotropy: 1,
     };
 
     /// Nearest magnification and minification, but with the texture repeated.
@@ -210,6 +247,7 @@ impl TextureOptions {
         minification: TextureFilter::Nearest,
         wrap_mode: TextureWrapMode::Repeat,
         mipmap_mode: None,
+        anisotropy: 1,
     };
 
     /// Nearest magnification and minification, but with the texture mirrored and repeated.
@@ -218,6 +256,7 @@ impl TextureOptions {
         minification: TextureFilter::Nearest,
         wrap_mode: TextureWrapMode::MirroredRepeat,
         mipmap_mode: None,
+        anisotropy: 1,
     };
 
     pub const fn with_mipmap_mode(self, mipmap_mode: Option<TextureFilter>) -> Self {
@@ -226,6 +265,19 @@ impl TextureOptions {
             ..self
         }
     }
+
+    /// See [`Self::anisotropy`].
+    pub const fn with_anisotropy(self, anisotropy: u16) -> Self {
+        Self { anisotropy, ..self }
+    }
+
+    /// Should anisotropic filtering be used, see [`Self::anisotropy`]?
+    pub fn is_anisotropic(&self) -> bool {
+        1 < self.anisotropy
+            && self.magnification == TextureFilter::Linear
+            && self.minification == TextureFilter::Linear
+            && self.mipmap_mode != Some(TextureFilter::Nearest)
+    }
 }
 
 impl Default for TextureOptions {
@@ -278,22 +330,32 @@ pub struct TexturesDelta {
     /// New or changed textures. Apply before painting.
     pub set: Vec<(TextureId, ImageDelta)>,
 
+    /// Textures which should be sampled with new [`TextureOptions`],
+    /// but whose pixels are unchanged. Apply after [`Self::set`], before painting.
+    pub options: Vec<(TextureId, TextureOptions)>,
+
     /// Textures to free after painting.
     pub free: Vec<TextureId>,
 }
 
 impl TexturesDelta {
     pub fn is_empty(&self) -> bool {
-        self.set.is_empty() && self.free.is_empty()
+        self.set.is_empty() && self.options.is_empty() && self.free.is_empty()
     }
 
     pub fn append(&mut self, mut newer: Self) {
+        for (id, _) in &newer.set {
+            // The newer image comes with its own options:
+            self.options.retain(|(x, _)| x != id);
+        }
         self.set.extend(newer.set);
+        self.options.append(&mut newer.options);
         self.free.append(&mut newer.free);
     }
 
     pub fn clear(&mut self) {
         self.set.clear();
+        self.options.clear();
         self.free.clear();
     }
 }
@@ -324,9 +386,197 @@ impl std::fmt::Debug for TexturesDelta {
             }
             debug_struct.field("set", &string);
         }
+        if !self.options.is_empty() {
+            debug_struct.field("options", &self.options);
+        }
         if !self.free.is_empty() {
             debug_struct.field("free", &self.free);
         }
         debug_struct.finish()
     }
 }
+
+// ----------------------------------------------------------------------------
+
+/// How much GPU memory the textures of a painter use, and when each was last painted.
+///
+/// Kept up to date by the painters (e.g. `egui_glow::Painter::texture_memory`),
+/// so that apps can see how close they are to running out of video memory,
+/// and free the textures that haven't been shown for the longest time.
+///
+/// Textures registered directly by their native handle are only counted
+/// once the painter knows their size.
+#[derive(Clone, Debug, Default)]
+pub struct TextureMemory {
+    textures: ahash::HashMap<TextureId, TextureMemoryUse>,
+
+    /// Increased by one with each [`Self::begin_frame`].
+    frame_nr: u64,
+}
+
+/// How much GPU memory one texture uses, see [`TextureMemory`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TextureMemoryUse {
+    /// width x height
+    pub size: [usize; 2],
+
+    /// On the GPU, which may be more than in the [`ImageData`], e.g. for font textures.
+    pub bytes_per_pixel: usize,
+
+    /// Mipmaps use another third of the memory.
+    pub has_mipmaps: bool,
+
+    /// The [`TextureMemory::frame_nr`] when the texture was last painted.
+    pub last_painted: u64,
+}
+
+impl TextureMemoryUse {
+    /// Size in bytes, including the mipmaps.
+    pub fn bytes(&self) -> usize {
+        let [mut w, mut h] = self.size;
+        let mut bytes = w * h * self.bytes_per_pixel;
+        if self.has_mipmaps {
+            while 1 < w || 1 < h {
+                w = (w / 2).max(1);
+                h = (h / 2).max(1);
+                bytes += w * h * self.bytes_per_pixel;
+            }
+        }
+        bytes
+    }
+}
+
+impl TextureMemory {
+    /// Call before painting a new frame.
+    pub fn begin_frame(&mut self) {
+        self.frame_nr += 1;
+    }
+
+    /// The number of frames painted so far.
+    pub fn frame_nr(&self) -> u64 {
+        self.frame_nr
+    }
+
+    /// A texture was (re)allocated on the GPU.
+    pub fn on_alloc(
+        &mut self,
+        id: TextureId,
+        size: [usize; 2],
+        bytes_per_pixel: usize,
+        has_mipmaps: bool,
+    ) {
+        self.textures.insert(
+            id,
+            TextureMemoryUse {
+                size,
+                bytes_per_pixel,
+                has_mipmaps,
+                last_painted: self.frame_nr,
+            },
+        );
+    }
+
+    /// Mipmaps were generated for a texture that didn't have any.
+    pub fn on_mipmaps(&mut self, id: TextureId) {
+        if let Some(texture) = self.textures.get_mut(&id) {
+            texture.has_mipmaps = true;
+        }
+    }
+
+    /// A texture was painted this frame.
+    #[inline]
+    pub fn on_paint(&mut self, id: TextureId) {
+        if let Some(texture) = self.textures.get_mut(&id) {
+            texture.last_painted = self.frame_nr;
+        }
+    }
+
+    /// A texture was freed.
+    pub fn on_free(&mut self, id: TextureId) {
+        self.textures.remove(&id);
+    }
+
+    /// How much memory one texture uses.
+    pub fn get(&self, id: TextureId) -> Option<&TextureMemoryUse> {
+        self.textures.get(&id)
+    }
+
+    /// All textures, in some arbitrary order.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (&TextureId, &TextureMemoryUse)> {
+        self.textures.iter()
+    }
+
+    /// How many bytes all textures use together.
+    pub fn total_bytes(&self) -> usize {
+        self.textures.values().map(|texture| texture.bytes()).sum()
+    }
+
+    /// The textures to free to use at most `budget` bytes, the least recently painted first.
+    ///
+    /// Textures painted in the current frame, and the font texture ([`TextureId::default`]),
+    /// are never suggested, so the result may not get all the way down to the budget.
+    pub fn least_recently_painted_over_budget(&self, budget: usize) -> Vec<TextureId> {
+        let mut excess = self.total_bytes().saturating_sub(budget);
+        if excess == 0 {
+            return vec![];
+        }
+
+        let mut candidates: Vec<(&TextureId, &TextureMemoryUse)> = self
+            .textures
+            .iter()
+            .filter(|(id, texture)| {
+                **id != TextureId::default() && texture.last_painted < self.frame_nr
+            })
+            .collect();
+        candidates.sort_by_key(|(id, texture)| (texture.last_painted, **id));
+
+        let mut evict = vec![];
+        for (id, texture) in candidates {
+            if excess == 0 {
+                break;
+            }
+            evict.push(*id);
+            excess = excess.saturating_sub(texture.bytes());
+        }
+        evict
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evict_least_recently_painted() {
+        let mut memory = TextureMemory::default();
+        memory.begin_frame();
+        memory.on_alloc(TextureId::default(), [8, 8], 4, false);
+        memory.on_alloc(TextureId::Managed(1), [8, 8], 4, false);
+        memory.on_alloc(TextureId::Managed(2), [8, 8], 4, true);
+        memory.on_alloc(TextureId::User(0), [8, 8], 4, false);
+        assert_eq!(
+            memory.get(TextureId::Managed(2)).unwrap().bytes(),
+            4 * (64 + 16 + 4 + 1)
+        );
+
+        memory.begin_frame();
+        memory.on_paint(TextureId::Managed(1));
+        memory.begin_frame();
+        memory.on_paint(TextureId::User(0));
+
+        assert!(memory
+            .least_recently_painted_over_budget(usize::MAX)
+            .is_empty());
+        assert_eq!(
+            memory.least_recently_painted_over_budget(memory.total_bytes() - 1),
+            vec![TextureId::Managed(2)]
+        );
+        assert_eq!(
+            memory.least_recently_painted_over_budget(0),
+            vec![TextureId::Managed(2), TextureId::Managed(1)]
+        );
+
+        memory.on_free(TextureId::Managed(2));
+        assert_eq!(memory.total_bytes(), 3 * 8 * 8 * 4);
+    }
+}
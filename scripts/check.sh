@@ -65,6 +65,7 @@ cargo check --quiet -p egui --all-features
 cargo check --quiet -p egui_demo_app --all-features
 cargo check --quiet -p egui_extras --all-features
 cargo check --quiet -p egui_glow --all-features
+cargo check --quiet -p egui-sdl --all-features
 cargo check --quiet -p egui-winit --all-features
 cargo check --quiet -p emath --all-features
 cargo check --quiet -p epaint --all-features